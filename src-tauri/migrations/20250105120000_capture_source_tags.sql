@@ -0,0 +1,41 @@
+-- ==========================================
+-- 标签 (Tags)
+-- ==========================================
+CREATE TABLE tags (
+    tag_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+-- 节点 <-> 标签
+-- source: 'manual' 用户手动添加, 'rule' 来源规则自动添加
+CREATE TABLE node_tags (
+    node_id INTEGER NOT NULL,
+    tag_id INTEGER NOT NULL,
+    source TEXT NOT NULL DEFAULT 'manual' CHECK (source IN ('manual', 'rule')),
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY (node_id, tag_id),
+    FOREIGN KEY (node_id) REFERENCES nodes(node_id) ON DELETE CASCADE,
+    FOREIGN KEY (tag_id) REFERENCES tags(tag_id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_node_tags_tag ON node_tags(tag_id);
+
+-- ==========================================
+-- 来源自动打标签规则
+-- match_field:
+--   'domain': 按 source_meta.url 的域名匹配（含子域名），如 arxiv.org
+--   'app': 按 source_meta.process_name 匹配（忽略大小写）
+--   'window_title': 按 source_meta.window_title 包含匹配（忽略大小写）
+-- ==========================================
+CREATE TABLE source_tag_rules (
+    rule_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    match_field TEXT NOT NULL CHECK (match_field IN ('domain', 'app', 'window_title')),
+    pattern TEXT NOT NULL,
+    tag_name TEXT NOT NULL,
+    is_enabled BOOLEAN DEFAULT 1,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+
+    UNIQUE(match_field, pattern, tag_name)
+);
@@ -0,0 +1,24 @@
+-- ==========================================
+-- 应用内通知 (Notification Center)
+-- kind:
+--   'pipeline_error': AI 处理失败
+--   'review_needed': 分类结果需要用户确认
+--   'backup': 备份完成 / 失败
+--   'system': 其他系统消息
+-- action: 前端点击通知后的动作，如 'open_node' / 'open_inbox' / 'retry_resource'
+-- ==========================================
+CREATE TABLE notifications (
+    notification_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    kind TEXT NOT NULL CHECK (kind IN ('pipeline_error', 'review_needed', 'backup', 'system')),
+    title TEXT NOT NULL,
+    body TEXT,
+    node_id INTEGER,
+    action TEXT,
+    is_read BOOLEAN DEFAULT 0,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    read_at DATETIME,
+
+    FOREIGN KEY (node_id) REFERENCES nodes(node_id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_notifications_unread ON notifications(is_read, created_at);
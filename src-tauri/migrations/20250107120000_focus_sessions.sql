@@ -0,0 +1,22 @@
+-- ==========================================
+-- 专注模式会话 (Focus Sessions)
+-- status:
+--   'active': 进行中（异常退出后启动时会被标记为 interrupted）
+--   'completed': 倒计时正常结束
+--   'interrupted': 用户提前结束
+-- ==========================================
+CREATE TABLE focus_sessions (
+    session_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    task_id INTEGER,
+    planned_seconds INTEGER NOT NULL,
+    actual_seconds INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'active' CHECK (status IN ('active', 'completed', 'interrupted')),
+    do_not_disturb BOOLEAN DEFAULT 0,
+    started_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    ended_at DATETIME,
+
+    FOREIGN KEY (task_id) REFERENCES nodes(node_id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_focus_sessions_started_at ON focus_sessions(started_at);
+CREATE INDEX idx_focus_sessions_task ON focus_sessions(task_id) WHERE task_id IS NOT NULL;
@@ -0,0 +1,20 @@
+-- ==========================================
+-- 时间记录 (Time Tracking)
+-- source:
+--   'pomodoro': 完成一个番茄钟后自动记录
+--   'manual': 用户手动补录
+-- ==========================================
+CREATE TABLE time_entries (
+    entry_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    task_id INTEGER,
+    source TEXT NOT NULL DEFAULT 'manual' CHECK (source IN ('pomodoro', 'manual')),
+    started_at DATETIME NOT NULL,
+    ended_at DATETIME NOT NULL,
+    duration_seconds INTEGER NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+
+    FOREIGN KEY (task_id) REFERENCES nodes(node_id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_time_entries_task ON time_entries(task_id) WHERE task_id IS NOT NULL;
+CREATE INDEX idx_time_entries_started_at ON time_entries(started_at);
@@ -0,0 +1,11 @@
+-- ==========================================
+-- 月度目标回顾：每个周期 (YYYY-MM) 一条记录，指向生成的回顾资源
+-- ==========================================
+CREATE TABLE goal_reviews (
+    review_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    period TEXT NOT NULL UNIQUE,
+    node_id INTEGER,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+
+    FOREIGN KEY (node_id) REFERENCES nodes(node_id) ON DELETE SET NULL
+);
@@ -0,0 +1,19 @@
+-- ==========================================
+-- 文献元数据：学术 PDF 资源的引用信息（与资源节点一对一）
+-- ==========================================
+CREATE TABLE citations (
+    node_id INTEGER PRIMARY KEY,
+    title TEXT NOT NULL,
+    authors JSON NOT NULL DEFAULT '[]', -- ["Ashish Vaswani", "Noam Shazeer"]
+    year INTEGER,
+    venue TEXT,
+    doi TEXT,
+    -- 'llm': 处理流程自动抽取；'manual': 用户手动编辑（自动抽取不会覆盖）
+    source TEXT NOT NULL DEFAULT 'llm' CHECK (source IN ('llm', 'manual')),
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+
+    FOREIGN KEY (node_id) REFERENCES nodes(node_id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_citations_doi ON citations(doi) WHERE doi IS NOT NULL;
@@ -0,0 +1,46 @@
+-- SQLite 无法修改 CHECK 约束，重建 edges 表以加入 'derived_from' 关系。
+-- edges 没有被其他表外键引用，可以直接在默认事务中重建。
+
+CREATE TABLE edges_new (
+    edge_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    source_node_id INTEGER NOT NULL,
+    target_node_id INTEGER NOT NULL,
+
+    -- 关系类型
+    -- contains: Source (Parent) -> Target (Child)
+    -- related_to: Source <-> Target
+    -- derived_from: Source (派生产物) -> Target (来源节点)
+        -- 翻译、转写、抽取的表格、保存的对话回答、月度回顾等系统生成的产物
+    relation_type TEXT NOT NULL CHECK (relation_type IN ('contains', 'related_to', 'derived_from')),
+
+    -- AI 辅助元数据
+    confidence_score REAL DEFAULT 1.0, -- AI 自动关联的置信度
+    is_manual BOOLEAN DEFAULT 1,       -- 是否人工确认过
+
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    is_deleted BOOLEAN DEFAULT 0,
+    deleted_at DATETIME,
+
+    FOREIGN KEY (source_node_id) REFERENCES nodes(node_id) ON DELETE CASCADE,
+    FOREIGN KEY (target_node_id) REFERENCES nodes(node_id) ON DELETE CASCADE,
+    CHECK (relation_type != 'related_to' OR source_node_id < target_node_id),
+    CHECK (relation_type != 'derived_from' OR source_node_id != target_node_id),
+    UNIQUE(source_node_id, target_node_id, relation_type) -- 防止重复连线
+);
+
+INSERT INTO edges_new (
+    edge_id, source_node_id, target_node_id, relation_type, confidence_score, is_manual,
+    created_at, updated_at, is_deleted, deleted_at
+)
+SELECT
+    edge_id, source_node_id, target_node_id, relation_type, confidence_score, is_manual,
+    created_at, updated_at, is_deleted, deleted_at
+FROM edges;
+
+DROP TABLE edges;
+ALTER TABLE edges_new RENAME TO edges;
+
+CREATE INDEX idx_edges_source ON edges(source_node_id);
+CREATE INDEX idx_edges_target ON edges(target_node_id);
+CREATE INDEX idx_edges_relation_type ON edges(relation_type);
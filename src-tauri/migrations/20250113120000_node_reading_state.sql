@@ -0,0 +1,18 @@
+-- ==========================================
+-- 最近打开与阅读进度 (Recent Items / Resume Reading)
+-- 每个节点一行：最近打开时间、打开次数、上次阅读位置
+--   scroll_position: 滚动比例 0~1
+--   page: PDF 等分页资源的页码（从 1 开始）
+-- ==========================================
+CREATE TABLE node_reading_state (
+    node_id INTEGER PRIMARY KEY,
+    last_opened_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    open_count INTEGER NOT NULL DEFAULT 0,
+    scroll_position REAL CHECK (scroll_position IS NULL OR (scroll_position >= 0 AND scroll_position <= 1)),
+    page INTEGER CHECK (page IS NULL OR page >= 1),
+    position_updated_at DATETIME,
+
+    FOREIGN KEY (node_id) REFERENCES nodes(node_id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_node_reading_state_last_opened ON node_reading_state(last_opened_at);
@@ -0,0 +1,29 @@
+-- ==========================================
+-- 工作区快照 (Workspace Sessions)
+-- 保存一组打开的节点/聊天及前端布局，切换项目时整体恢复
+--   layout_json: 前端布局（分栏、当前标签等），后端不解析
+-- ==========================================
+CREATE TABLE workspaces (
+    workspace_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,
+    layout_json TEXT,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_restored_at DATETIME
+);
+
+-- 工作区中打开的条目：节点或聊天会话二选一，position 为打开顺序
+CREATE TABLE workspace_items (
+    item_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    workspace_id INTEGER NOT NULL,
+    node_id INTEGER,
+    session_id INTEGER,
+    position INTEGER NOT NULL,
+
+    CHECK ((node_id IS NULL) != (session_id IS NULL)),
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(workspace_id) ON DELETE CASCADE,
+    FOREIGN KEY (node_id) REFERENCES nodes(node_id) ON DELETE CASCADE,
+    FOREIGN KEY (session_id) REFERENCES chat_sessions(session_id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_workspace_items_workspace ON workspace_items(workspace_id, position);
@@ -0,0 +1,36 @@
+-- ==========================================
+-- 节点全文索引 (FTS5)
+-- 外部内容表，内容来自 nodes，通过触发器保持同步。
+-- 使用 trigram 分词器：支持中文等无空格语言的子串匹配，查询词需至少 3 个字符。
+-- 注意：以后若重建 nodes 表，需要重新创建下面的触发器。
+-- ==========================================
+CREATE VIRTUAL TABLE nodes_fts USING fts5(
+    title,
+    summary,
+    file_content,
+    user_note,
+    content = 'nodes',
+    content_rowid = 'node_id',
+    tokenize = 'trigram'
+);
+
+CREATE TRIGGER nodes_fts_after_insert AFTER INSERT ON nodes BEGIN
+    INSERT INTO nodes_fts (rowid, title, summary, file_content, user_note)
+    VALUES (new.node_id, new.title, new.summary, new.file_content, new.user_note);
+END;
+
+CREATE TRIGGER nodes_fts_after_delete AFTER DELETE ON nodes BEGIN
+    INSERT INTO nodes_fts (nodes_fts, rowid, title, summary, file_content, user_note)
+    VALUES ('delete', old.node_id, old.title, old.summary, old.file_content, old.user_note);
+END;
+
+CREATE TRIGGER nodes_fts_after_update
+AFTER UPDATE OF title, summary, file_content, user_note ON nodes BEGIN
+    INSERT INTO nodes_fts (nodes_fts, rowid, title, summary, file_content, user_note)
+    VALUES ('delete', old.node_id, old.title, old.summary, old.file_content, old.user_note);
+    INSERT INTO nodes_fts (rowid, title, summary, file_content, user_note)
+    VALUES (new.node_id, new.title, new.summary, new.file_content, new.user_note);
+END;
+
+-- 为已有节点建立索引
+INSERT INTO nodes_fts (nodes_fts) VALUES ('rebuild');
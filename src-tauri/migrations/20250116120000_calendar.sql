@@ -0,0 +1,39 @@
+-- ==========================================
+-- 日历导入 (iCalendar)
+-- .ics 文件或订阅 URL 中的事件同步为任务节点
+--   calendar_subscriptions: 定期轮询的订阅源
+--   calendar_events: 事件 UID 与任务节点的映射，重复同步时据此更新而非重复创建
+-- ==========================================
+CREATE TABLE calendar_subscriptions (
+    subscription_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    url TEXT NOT NULL UNIQUE,
+    poll_interval_minutes INTEGER NOT NULL DEFAULT 60 CHECK (poll_interval_minutes >= 5),
+    is_enabled BOOLEAN NOT NULL DEFAULT 1,
+    last_synced_at DATETIME,
+    last_error TEXT,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- 时间均为本地时间 `YYYY-MM-DD HH:MM:SS`（全天事件为 `YYYY-MM-DD`）
+--   starts_at: 当前（或下一次）发生时间，重复事件每次同步时前移
+--   reminder_at: 由 VALARM 计算出的提醒时间；reminder_sent_at 非空表示已提醒
+CREATE TABLE calendar_events (
+    event_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    subscription_id INTEGER,
+    uid TEXT NOT NULL UNIQUE,
+    node_id INTEGER NOT NULL,
+    starts_at TEXT NOT NULL,
+    ends_at TEXT,
+    location TEXT,
+    recurrence_rule TEXT,
+    reminder_at TEXT,
+    reminder_sent_at DATETIME,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    FOREIGN KEY (subscription_id) REFERENCES calendar_subscriptions(subscription_id) ON DELETE SET NULL,
+    FOREIGN KEY (node_id) REFERENCES nodes(node_id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_calendar_events_reminder ON calendar_events(reminder_at)
+    WHERE reminder_sent_at IS NULL;
@@ -0,0 +1,44 @@
+-- ==========================================
+-- GitHub issues / PR 同步
+-- 每个仓库绑定一个项目主题，issue 与 PR 同步为该主题下的任务，正文另存为文本资源以便检索
+--   token_encrypted: 访问令牌（AES-GCM 加密，公开仓库可为空）
+--   since_cursor: 已同步到的最大 updated_at，下次只拉取之后更新的条目
+-- ==========================================
+CREATE TABLE github_repos (
+    repo_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    owner TEXT NOT NULL,
+    name TEXT NOT NULL,
+    topic_id INTEGER NOT NULL,
+    token_encrypted BLOB,
+    poll_interval_minutes INTEGER NOT NULL DEFAULT 30 CHECK (poll_interval_minutes >= 5),
+    is_enabled BOOLEAN NOT NULL DEFAULT 1,
+    since_cursor TEXT,
+    last_synced_at DATETIME,
+    last_error TEXT,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    UNIQUE (owner, name),
+    FOREIGN KEY (topic_id) REFERENCES nodes(node_id) ON DELETE CASCADE
+);
+
+-- issue / PR 编号与节点的映射，(repo_id, number) 唯一
+--   synced_title / github_state / body_hash: 上次同步时 GitHub 侧的值，
+--     只有 GitHub 侧发生变化时才覆盖本地节点，保留用户在本地的修改
+--   github_state: 'open' / 'closed' / 'not_planned' / 'merged'
+CREATE TABLE github_items (
+    item_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    repo_id INTEGER NOT NULL,
+    number INTEGER NOT NULL,
+    is_pull_request BOOLEAN NOT NULL,
+    task_node_id INTEGER NOT NULL,
+    body_node_id INTEGER,
+    synced_title TEXT NOT NULL,
+    github_state TEXT NOT NULL,
+    body_hash TEXT,
+    github_updated_at TEXT NOT NULL,
+
+    UNIQUE (repo_id, number),
+    FOREIGN KEY (repo_id) REFERENCES github_repos(repo_id) ON DELETE CASCADE,
+    FOREIGN KEY (task_node_id) REFERENCES nodes(node_id) ON DELETE CASCADE,
+    FOREIGN KEY (body_node_id) REFERENCES nodes(node_id) ON DELETE SET NULL
+);
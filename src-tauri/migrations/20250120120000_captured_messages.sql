@@ -0,0 +1,16 @@
+-- ==========================================
+-- Slack / Discord 转发消息去重
+-- 每条消息捕获后记录到此表，重复转发时跳过
+--   message_key: 平台消息 ID；缺失时使用 permalink 或内容哈希
+--   node_id: 消息所在的资源（同一批次同一频道的消息合并为一个资源）
+-- ==========================================
+CREATE TABLE captured_messages (
+    captured_message_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    platform TEXT NOT NULL CHECK (platform IN ('slack', 'discord')),
+    message_key TEXT NOT NULL,
+    node_id INTEGER NOT NULL,
+    captured_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    UNIQUE (platform, message_key),
+    FOREIGN KEY (node_id) REFERENCES nodes(node_id) ON DELETE CASCADE
+);
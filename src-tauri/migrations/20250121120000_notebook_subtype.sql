@@ -0,0 +1,109 @@
+-- no-transaction
+-- 新增资源子类型 'notebook'（Jupyter .ipynb，内容为按单元格渲染的 Markdown / 代码 / 输出摘要）。
+-- SQLite 无法修改 CHECK 约束，同 20250119120000_audio_subtype 一样重建 nodes 表：
+-- 关闭外键避免级联删除，自行管理事务。
+-- DROP TABLE 会一并删除 nodes 上的触发器，重建后需恢复全文索引触发器（nodes_fts 内容不变）。
+PRAGMA foreign_keys = OFF;
+
+BEGIN;
+
+CREATE TABLE nodes_new (
+    node_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    uuid TEXT NOT NULL UNIQUE,
+    user_id INTEGER NOT NULL DEFAULT 1, -- 预留 user_id
+
+    -- 1. 基础属性 (所有节点都有)
+    title TEXT NOT NULL,         -- 标题 / 文件名 / 任务名
+    summary TEXT,                -- Topic/Task/Resource Summary
+    
+    -- 2. 类型标识 (用于 UI 渲染区分，但支持流转)
+    -- 'topic': 概念, 容器
+    -- 'task': 待办
+    -- 'resource': 文件, 链接
+    -- 'habit': 习惯打卡
+    node_type TEXT NOT NULL CHECK (node_type IN ('topic', 'task', 'resource', 'habit')),
+
+    -- 3. 任务组件 (Task Component) - 仅 node_type='task' 时有值，但允许赋予任何节点
+    task_status TEXT DEFAULT 'todo' CHECK (task_status IN ('todo', 'done', 'cancelled')),
+    priority TEXT DEFAULT 'medium' CHECK (priority IN ('high', 'medium', 'low')),
+    due_date DATETIME,
+    done_date DATETIME,
+
+    -- 4. 资源组件 (Resource Component) - 仅 node_type='resource' 时有值
+    file_hash TEXT,              -- SHA-256
+    file_path TEXT,              -- 本地存储路径
+    file_content TEXT,           -- 文件内容(文字/图片OCR/PDF解析)
+    user_note TEXT,              -- 用户备注(仅在上传非文本时保存)
+    resource_subtype TEXT CHECK (resource_subtype IN ('text', 'pdf', 'image', 'url', 'epub', 'office', 'audio', 'notebook', 'other')),
+    source_meta JSON,            -- { url, window_title, process_name, captured_at }
+
+    -- 向量化状态 (针对资源本身)
+    embedded_hash TEXT,
+    processing_hash TEXT,
+    embedding_status TEXT DEFAULT 'pending' CHECK (embedding_status IN ('pending', 'synced', 'dirty', 'error')),
+    last_embedding_at DATETIME,
+    last_embedding_error TEXT,
+    
+    -- 资源处理状态 (Rust 后台使用)
+    processing_stage TEXT DEFAULT 'todo' CHECK(processing_stage IN ('todo', 'embedding','done')),
+    -- 用户侧的状态 (Inbox 功能核心)
+    -- 'unreviewed': AI 处理完毕，在 Inbox 等待确认
+    -- 'reviewed': 用户已确认/已归档
+    -- 'rejected': 用户认为无效
+    review_status TEXT DEFAULT 'reviewed'
+        CHECK (
+            (node_type = 'resource' AND review_status IN ('unreviewed', 'reviewed', 'rejected'))
+            OR (node_type != 'resource' AND review_status = 'reviewed')
+        ),
+
+    -- 5. 系统/管理属性
+    is_pinned BOOLEAN DEFAULT 0, -- 是否出现在Sidebar的收藏
+    pinned_at DATETIME, -- 收藏时间,用来排序
+
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    is_deleted BOOLEAN DEFAULT 0,
+    deleted_at DATETIME,
+
+    FOREIGN KEY (user_id) REFERENCES users(user_id) ON DELETE CASCADE
+);
+
+INSERT INTO nodes_new SELECT * FROM nodes;
+
+DROP TABLE nodes;
+
+ALTER TABLE nodes_new RENAME TO nodes;
+
+CREATE INDEX idx_nodes_type ON nodes(node_type);
+CREATE INDEX idx_nodes_uuid ON nodes(uuid);
+CREATE INDEX idx_nodes_task_status ON nodes(task_status) WHERE task_status IS NOT NULL; -- 快速查任务
+CREATE INDEX idx_nodes_file_hash ON nodes(file_hash) WHERE file_hash IS NOT NULL; -- 资源去重
+CREATE INDEX idx_nodes_title ON nodes(title); -- 简单的标题搜索
+CREATE INDEX idx_nodes_user_note ON nodes(user_note);
+CREATE INDEX idx_nodes_due_date ON nodes(due_date) WHERE due_date IS NOT NULL;
+CREATE INDEX idx_nodes_review_status ON nodes(review_status);
+CREATE UNIQUE INDEX idx_nodes_topic_title_unique ON nodes(user_id, title)
+    WHERE node_type = 'topic' AND is_deleted = 0;
+
+-- 全文索引同步触发器（见 20250115120000_nodes_fts）
+CREATE TRIGGER nodes_fts_after_insert AFTER INSERT ON nodes BEGIN
+    INSERT INTO nodes_fts (rowid, title, summary, file_content, user_note)
+    VALUES (new.node_id, new.title, new.summary, new.file_content, new.user_note);
+END;
+
+CREATE TRIGGER nodes_fts_after_delete AFTER DELETE ON nodes BEGIN
+    INSERT INTO nodes_fts (nodes_fts, rowid, title, summary, file_content, user_note)
+    VALUES ('delete', old.node_id, old.title, old.summary, old.file_content, old.user_note);
+END;
+
+CREATE TRIGGER nodes_fts_after_update
+AFTER UPDATE OF title, summary, file_content, user_note ON nodes BEGIN
+    INSERT INTO nodes_fts (nodes_fts, rowid, title, summary, file_content, user_note)
+    VALUES ('delete', old.node_id, old.title, old.summary, old.file_content, old.user_note);
+    INSERT INTO nodes_fts (rowid, title, summary, file_content, user_note)
+    VALUES (new.node_id, new.title, new.summary, new.file_content, new.user_note);
+END;
+
+COMMIT;
+
+PRAGMA foreign_keys = ON;
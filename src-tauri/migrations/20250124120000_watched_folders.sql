@@ -0,0 +1,16 @@
+-- ==========================================
+-- 监听文件夹（自动导入）
+-- 文件夹中新出现的文件稳定后自动捕获为资源，内容哈希已存在的文件跳过
+--   move_to: 导入（或判定重复）后把文件移动到的目录；为空则保留原文件
+--   last_imported_at / last_error: 最近一次导入时间与错误
+-- ==========================================
+CREATE TABLE watched_folders (
+    folder_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    path TEXT NOT NULL UNIQUE,
+    move_to TEXT,
+    is_enabled BOOLEAN NOT NULL DEFAULT 1,
+    imported_count INTEGER NOT NULL DEFAULT 0,
+    last_imported_at DATETIME,
+    last_error TEXT,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
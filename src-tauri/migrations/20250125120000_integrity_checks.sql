@@ -0,0 +1,35 @@
+-- ==========================================
+-- 资源文件完整性校验
+-- 重新计算 assets 中文件的哈希并与节点 file_hash 比较，记录缺失与不一致的文件
+--   trigger: manual（手动）/ scheduled（定期）
+--   skipped_count: 内容在应用内编辑过（file_hash 已改为文本哈希）而无法比较的资源
+--   reparsed_count: 按新文件重新解析并入队的资源数
+-- ==========================================
+CREATE TABLE integrity_checks (
+    check_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    trigger TEXT NOT NULL CHECK (trigger IN ('manual', 'scheduled')),
+    started_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    finished_at DATETIME,
+    checked_count INTEGER NOT NULL DEFAULT 0,
+    missing_count INTEGER NOT NULL DEFAULT 0,
+    mismatched_count INTEGER NOT NULL DEFAULT 0,
+    skipped_count INTEGER NOT NULL DEFAULT 0,
+    reparsed_count INTEGER NOT NULL DEFAULT 0,
+    error TEXT
+);
+
+-- 单次校验发现的问题
+--   kind: missing（文件不存在）/ mismatch（哈希不一致）
+--   actual_hash: 当前文件哈希，missing 时为空
+CREATE TABLE integrity_issues (
+    issue_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    check_id INTEGER NOT NULL REFERENCES integrity_checks(check_id) ON DELETE CASCADE,
+    node_id INTEGER NOT NULL REFERENCES nodes(node_id) ON DELETE CASCADE,
+    kind TEXT NOT NULL CHECK (kind IN ('missing', 'mismatch')),
+    file_path TEXT NOT NULL,
+    expected_hash TEXT,
+    actual_hash TEXT,
+    reparsed BOOLEAN NOT NULL DEFAULT 0
+);
+
+CREATE INDEX idx_integrity_issues_check ON integrity_issues(check_id);
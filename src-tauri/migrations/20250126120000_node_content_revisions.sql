@@ -0,0 +1,16 @@
+-- ==========================================
+-- 资源正文版本
+-- 编辑或恢复正文前保存被替换的内容，可随时恢复（每个资源保留最近 50 个版本）
+--   content_hash: 当时的 file_hash，恢复时一并还原
+--   reason: edit（用户编辑）/ restore（恢复其他版本）
+-- ==========================================
+CREATE TABLE node_content_revisions (
+    revision_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    node_id INTEGER NOT NULL REFERENCES nodes(node_id) ON DELETE CASCADE,
+    content TEXT NOT NULL,
+    content_hash TEXT,
+    reason TEXT NOT NULL CHECK (reason IN ('edit', 'restore')),
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_node_content_revisions_node ON node_content_revisions(node_id, revision_id);
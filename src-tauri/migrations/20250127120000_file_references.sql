@@ -0,0 +1,18 @@
+-- ==========================================
+-- 外部文件引用（原地索引，不复制到 assets）
+-- 节点的 file_path 保存文件的绝对路径，本表记录引用状态
+--   status: online 文件可读 / offline 文件缺失（被删除、移走或所在硬盘未连接）
+--   file_size / modified_at: 上次确认时的大小与修改时间（Unix 秒），未变化时不重新计算哈希
+--   checked_at / last_error: 最近一次检查时间与错误
+-- ==========================================
+CREATE TABLE file_references (
+    node_id INTEGER PRIMARY KEY,
+    status TEXT NOT NULL DEFAULT 'online' CHECK (status IN ('online', 'offline')),
+    file_size INTEGER,
+    modified_at INTEGER,
+    checked_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_error TEXT,
+    FOREIGN KEY (node_id) REFERENCES nodes(node_id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_file_references_status ON file_references(status);
@@ -0,0 +1,17 @@
+-- ==========================================
+-- AI 建议标签：资源处理完成摘要后由模型提出 3–5 个标签，等待用户确认
+--   status: pending 待确认 / accepted 已采纳（已打到节点上）/ rejected 已拒绝
+--   同一节点同名建议只保留一条，被拒绝的名称不会再次建议
+-- ==========================================
+CREATE TABLE tag_suggestions (
+    suggestion_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    node_id INTEGER NOT NULL,
+    name TEXT NOT NULL COLLATE NOCASE,
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'accepted', 'rejected')),
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    decided_at DATETIME,
+    UNIQUE (node_id, name),
+    FOREIGN KEY (node_id) REFERENCES nodes(node_id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_tag_suggestions_node_status ON tag_suggestions(node_id, status);
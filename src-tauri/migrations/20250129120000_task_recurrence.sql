@@ -0,0 +1,9 @@
+-- ==========================================
+-- 重复任务
+-- recurrence_rule: 类 RRULE 规则，如 FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE;UNTIL=2025-12-31
+-- 以任务的 due_date 为起点。完成时生成下一次的任务并把规则移交给它，
+-- 因此同一系列中只有最新一次任务带规则
+-- ==========================================
+ALTER TABLE nodes ADD COLUMN recurrence_rule TEXT;
+
+CREATE INDEX idx_nodes_recurrence ON nodes(recurrence_rule) WHERE recurrence_rule IS NOT NULL;
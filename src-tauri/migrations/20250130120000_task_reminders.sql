@@ -0,0 +1,19 @@
+-- ==========================================
+-- 任务提醒
+-- 每个任务一行，删除即关闭提醒。时间均为本地时间 `YYYY-MM-DD HH:MM:SS`
+--   minutes_before: 在 due_date 前多少分钟提醒（全天任务的 due_date 按 00:00 计）
+--   snoozed_until: 稍后提醒的时间，非空时取代按 due_date 计算的提醒时间
+--   reminded_due_date: 已提醒过的 due_date；修改截止时间后重新提醒
+-- ==========================================
+CREATE TABLE task_reminders (
+    node_id INTEGER PRIMARY KEY,
+    minutes_before INTEGER NOT NULL DEFAULT 15 CHECK (minutes_before >= 0),
+    snoozed_until TEXT,
+    reminded_due_date TEXT,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    FOREIGN KEY (node_id) REFERENCES nodes(node_id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_reminders_snoozed ON task_reminders(snoozed_until)
+    WHERE snoozed_until IS NOT NULL;
@@ -0,0 +1,20 @@
+-- ==========================================
+-- 看板排序
+-- board_position: 任务在所属状态列中的位置，从 0 开始
+--   NULL 表示尚未在看板中排过序，排在该列末尾（按创建时间）
+-- ==========================================
+ALTER TABLE nodes ADD COLUMN board_position INTEGER;
+
+-- 现有任务按创建时间在各状态列内编号
+UPDATE nodes
+SET board_position = (
+    SELECT COUNT(*) FROM nodes AS earlier
+    WHERE earlier.node_type = 'task'
+      AND earlier.task_status = nodes.task_status
+      AND (earlier.created_at < nodes.created_at
+           OR (earlier.created_at = nodes.created_at AND earlier.node_id < nodes.node_id))
+)
+WHERE node_type = 'task';
+
+CREATE INDEX idx_nodes_task_board ON nodes(task_status, board_position)
+    WHERE node_type = 'task' AND is_deleted = 0;
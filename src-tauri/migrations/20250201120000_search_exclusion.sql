@@ -0,0 +1,8 @@
+-- ==========================================
+-- 检索排除
+-- exclude_from_search: 为 1 时节点不参与语义搜索与对话 RAG；
+--   通过 contains 边包含的节点（如主题下的资源）一并排除
+-- ==========================================
+ALTER TABLE nodes ADD COLUMN exclude_from_search BOOLEAN NOT NULL DEFAULT 0;
+
+CREATE INDEX idx_nodes_exclude_from_search ON nodes(node_id) WHERE exclude_from_search = 1;
@@ -0,0 +1,30 @@
+-- SQLite 无法修改 CHECK 约束，重建 time_entries 表以加入 'timer' 来源。
+-- time_entries 没有被其他表外键引用，可以直接在默认事务中重建。
+-- source:
+--   'pomodoro': 完成一个番茄钟后自动记录
+--   'manual': 用户手动补录
+--   'timer': 任务计时器停止时记录
+
+CREATE TABLE time_entries_new (
+    entry_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    task_id INTEGER,
+    source TEXT NOT NULL DEFAULT 'manual' CHECK (source IN ('pomodoro', 'manual', 'timer')),
+    started_at DATETIME NOT NULL,
+    ended_at DATETIME NOT NULL,
+    duration_seconds INTEGER NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+
+    FOREIGN KEY (task_id) REFERENCES nodes(node_id) ON DELETE SET NULL
+);
+
+INSERT INTO time_entries_new (
+    entry_id, task_id, source, started_at, ended_at, duration_seconds, created_at
+)
+SELECT entry_id, task_id, source, started_at, ended_at, duration_seconds, created_at
+FROM time_entries;
+
+DROP TABLE time_entries;
+ALTER TABLE time_entries_new RENAME TO time_entries;
+
+CREATE INDEX idx_time_entries_task ON time_entries(task_id) WHERE task_id IS NOT NULL;
+CREATE INDEX idx_time_entries_started_at ON time_entries(started_at);
@@ -0,0 +1,19 @@
+-- ==========================================
+-- 后台数据迁移 (Data Migrations)
+-- 启动后分批执行的数据回填，与 sqlx 的表结构迁移分开；每批提交后记录进度，
+-- 中断（退出、崩溃）后从 cursor 继续。
+--   name: 迁移标识（代码中的常量）
+--   cursor: 已处理到的位置（通常是 node_id），含义由各迁移自行决定
+--   status: pending / running / done / failed
+-- ==========================================
+CREATE TABLE data_migrations (
+    name TEXT PRIMARY KEY,
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'running', 'done', 'failed')),
+    cursor INTEGER NOT NULL DEFAULT 0,
+    processed INTEGER NOT NULL DEFAULT 0,
+    total INTEGER,
+    last_error TEXT,
+    started_at DATETIME,
+    finished_at DATETIME,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,14 @@
+-- ==========================================
+-- AI 队列任务 (Pipeline Jobs)
+-- 持久化排队与处理中的资源，应用被强制退出后启动时恢复队列。
+--   priority: bulk / normal / interactive
+--   status: queued / processing（启动时 processing 视为中断，重新排队）
+-- 任务完成（成功或失败）后删除。
+-- ==========================================
+CREATE TABLE pipeline_jobs (
+    node_id INTEGER PRIMARY KEY REFERENCES nodes(node_id) ON DELETE CASCADE,
+    priority TEXT NOT NULL DEFAULT 'normal' CHECK (priority IN ('bulk', 'normal', 'interactive')),
+    status TEXT NOT NULL DEFAULT 'queued' CHECK (status IN ('queued', 'processing')),
+    enqueued_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    started_at DATETIME
+);
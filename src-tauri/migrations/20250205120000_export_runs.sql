@@ -0,0 +1,22 @@
+-- ==========================================
+-- 导出记录 (Export Runs)
+-- 定期自动导出与手动触发的导出，用于判断下次导出时间与增量导出的起点。
+--   trigger: manual / scheduled
+--   format: archive（数据库快照 + 资源文件）/ markdown
+--   since: 增量导出的起点（上次成功导出的开始时间），全量导出为 NULL
+--   status: running / succeeded / failed
+-- ==========================================
+CREATE TABLE export_runs (
+    run_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    trigger TEXT NOT NULL CHECK (trigger IN ('manual', 'scheduled')),
+    format TEXT NOT NULL CHECK (format IN ('archive', 'markdown')),
+    since DATETIME,
+    status TEXT NOT NULL DEFAULT 'running' CHECK (status IN ('running', 'succeeded', 'failed')),
+    file_path TEXT,
+    node_count INTEGER NOT NULL DEFAULT 0,
+    file_count INTEGER NOT NULL DEFAULT 0,
+    size_bytes INTEGER NOT NULL DEFAULT 0,
+    error TEXT,
+    started_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    finished_at DATETIME
+);
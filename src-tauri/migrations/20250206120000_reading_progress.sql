@@ -0,0 +1,9 @@
+-- ==========================================
+-- 阅读时长与阅读进度 (Reading Progress)
+--   nodes.read_time_minutes: 按正文字数估算的阅读分钟数，解析或替换正文时写入
+--   node_reading_state.progress: 前端上报的阅读进度（0-1）
+-- ==========================================
+ALTER TABLE nodes ADD COLUMN read_time_minutes INTEGER;
+
+ALTER TABLE node_reading_state ADD COLUMN progress REAL CHECK (progress IS NULL OR (progress >= 0 AND progress <= 1));
+ALTER TABLE node_reading_state ADD COLUMN progress_updated_at DATETIME;
@@ -0,0 +1,14 @@
+-- ==========================================
+-- 正文统计 (Content Stats)
+-- 解析或替换正文时写入，用于按长度排序与 LLM 成本估算
+--   word_count: 词数（中日韩文字按字计）
+--   char_count: 字符数
+--   token_count: 估算的 token 数
+--   content_language: 检测到的语言（ISO 639-1），无法判断时为 NULL
+-- ==========================================
+ALTER TABLE nodes ADD COLUMN word_count INTEGER;
+ALTER TABLE nodes ADD COLUMN char_count INTEGER;
+ALTER TABLE nodes ADD COLUMN token_count INTEGER;
+ALTER TABLE nodes ADD COLUMN content_language TEXT;
+
+CREATE INDEX idx_nodes_word_count ON nodes(word_count);
@@ -0,0 +1,6 @@
+-- ==========================================
+-- AI 处理重试次数 (Processing Attempts)
+-- 限流、超时等临时错误按指数退避重试，超过次数后才标记为 error。
+--   processing_attempts: 连续失败次数，处理成功或手动重试时清零
+-- ==========================================
+ALTER TABLE nodes ADD COLUMN processing_attempts INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,23 @@
+-- ==========================================
+-- LLM 用量记录 (Usage Log)
+-- 每次对话或 AI 处理调用一行，用于按天 / 模型 / 用途统计 token 与估算费用。
+--   operation: chat / summary / chat_summary / classify / citation / tag_suggestion /
+--              transcribe / goal_review / restructure
+--   session_id: 对话用量关联的会话（删除会话后置空）
+-- ==========================================
+CREATE TABLE usage_log (
+    usage_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    provider TEXT NOT NULL,
+    model TEXT NOT NULL,
+    operation TEXT NOT NULL,
+    input_tokens INTEGER NOT NULL DEFAULT 0,
+    output_tokens INTEGER NOT NULL DEFAULT 0,
+    reasoning_tokens INTEGER NOT NULL DEFAULT 0,
+    total_tokens INTEGER NOT NULL DEFAULT 0,
+    session_id INTEGER,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    FOREIGN KEY (session_id) REFERENCES chat_sessions(session_id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_usage_log_created_at ON usage_log(created_at);
@@ -0,0 +1,17 @@
+-- ==========================================
+-- 节点引用索引 (Backlinks)
+-- 正文中的 uuid 或 [[标题]] 指向其他节点时记一行，正文变化时整体重建。
+--   link_kind: uuid / title（同一目标两种都有时记 uuid）
+-- ==========================================
+CREATE TABLE node_links (
+    source_node_id INTEGER NOT NULL,
+    target_node_id INTEGER NOT NULL,
+    link_kind TEXT NOT NULL CHECK (link_kind IN ('uuid', 'title')),
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY (source_node_id, target_node_id),
+    FOREIGN KEY (source_node_id) REFERENCES nodes(node_id) ON DELETE CASCADE,
+    FOREIGN KEY (target_node_id) REFERENCES nodes(node_id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_node_links_target ON node_links(target_node_id);
@@ -0,0 +1,26 @@
+-- ==========================================
+-- 待确认的 AI 修改 (Pending Changes)
+-- 对话中模型通过工具提出的修改先记在这里，用户批准后才执行。
+--   kind: rename_topic（new_value 为新标题）
+--         move_resource（new_value 为目标主题 id，old_value 为原主题 id，逗号分隔）
+--         set_due_date（new_value 为 YYYY-MM-DD，为空表示清除）
+--   old_value: 提出修改时的当前值，供审核对比
+--   reason: 模型给出的理由
+-- ==========================================
+CREATE TABLE pending_changes (
+    change_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    kind TEXT NOT NULL CHECK (kind IN ('rename_topic', 'move_resource', 'set_due_date')),
+    node_id INTEGER NOT NULL,
+    old_value TEXT,
+    new_value TEXT,
+    reason TEXT,
+    session_id INTEGER,
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'approved', 'rejected')),
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    decided_at DATETIME,
+
+    FOREIGN KEY (node_id) REFERENCES nodes(node_id) ON DELETE CASCADE,
+    FOREIGN KEY (session_id) REFERENCES chat_sessions(session_id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_pending_changes_status ON pending_changes(status, created_at);
@@ -0,0 +1,6 @@
+-- ==========================================
+-- 主题自动归类开关
+-- auto_classify_allowed: 为 0 时 AI 归类不会把资源放入该主题（人工整理的主题），
+--   也不会把它作为候选或父主题；手动操作不受影响
+-- ==========================================
+ALTER TABLE nodes ADD COLUMN auto_classify_allowed BOOLEAN NOT NULL DEFAULT 1;
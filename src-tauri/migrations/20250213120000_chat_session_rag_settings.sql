@@ -0,0 +1,6 @@
+-- ==========================================
+-- 会话级 RAG 检索设置
+-- rag_settings: JSON（top_k / score_threshold / embedding_type），
+--   为 NULL 或缺少的字段沿用全局检索配置
+-- ==========================================
+ALTER TABLE chat_sessions ADD COLUMN rag_settings TEXT;
@@ -0,0 +1,21 @@
+-- ==========================================
+-- 回答引用来源 (Message Sources)
+-- 发送消息时注入 prompt 的 RAG 片段，一行一个，供回答展示可点击的引用。
+--   rank: 片段在 prompt 中的编号（从 1 开始，对应回答里的 [n]）
+--   snippet: 片段文本，重新嵌入后分块可能变化，保留当时的内容
+-- ==========================================
+CREATE TABLE message_sources (
+    source_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    message_id INTEGER NOT NULL,
+    node_id INTEGER NOT NULL,
+    chunk_index INTEGER NOT NULL,
+    rank INTEGER NOT NULL,
+    score REAL NOT NULL,
+    snippet TEXT NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    FOREIGN KEY (message_id) REFERENCES chat_messages(message_id) ON DELETE CASCADE,
+    FOREIGN KEY (node_id) REFERENCES nodes(node_id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_message_sources_message ON message_sources(message_id);
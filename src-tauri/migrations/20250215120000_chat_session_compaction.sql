@@ -0,0 +1,6 @@
+-- ==========================================
+-- 对话历史压缩
+-- compacted_through_message_id: summary 已概括到的最后一条消息，
+--   为 NULL 时 summary 只是首轮对话生成的会话简介
+-- ==========================================
+ALTER TABLE chat_sessions ADD COLUMN compacted_through_message_id INTEGER;
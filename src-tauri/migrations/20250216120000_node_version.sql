@@ -0,0 +1,15 @@
+-- ==========================================
+-- 节点版本号（乐观并发控制）
+-- version: 标题、摘要、正文、备注每次修改加一。编辑命令带上读取时的版本号，
+--   不一致时拒绝写入；AI 流水线写回前同样按版本号检查，避免覆盖用户的修改。
+-- 显式设置 version 的写入不会再被触发器加一。
+-- 注意：以后若重建 nodes 表，需要重新创建下面的触发器。
+-- ==========================================
+ALTER TABLE nodes ADD COLUMN version INTEGER NOT NULL DEFAULT 0;
+
+CREATE TRIGGER nodes_version_after_update
+AFTER UPDATE OF title, summary, file_content, user_note ON nodes
+WHEN new.version = old.version
+BEGIN
+    UPDATE nodes SET version = old.version + 1 WHERE node_id = new.node_id;
+END;
@@ -0,0 +1,31 @@
+-- ==========================================
+-- 资源保留策略
+-- archived_at: 归档时间。归档的节点仍留在原主题下，但不参与语义搜索与对话 RAG
+-- retention_rules: 定期任务按规则归档或删除（移入回收站）创建时间较早的资源
+--   topic_id: 只处理该主题（含子主题）下的资源；为空时不限主题
+--   resource_subtype: 只处理该子类型的资源；为空时不限
+--   max_age_days: 创建超过该天数的资源才会被处理
+--   action: 'archive' 归档 / 'delete' 移入回收站
+--   skip_linked: 跳过有关联的资源（related_to / derived_from 边、正文引用、挂在任务下）
+--   收藏的资源总是跳过
+--   last_run_at / last_affected: 最近一次执行时间与处理的资源数
+-- ==========================================
+ALTER TABLE nodes ADD COLUMN archived_at DATETIME;
+
+CREATE INDEX idx_nodes_archived_at ON nodes(node_id) WHERE archived_at IS NOT NULL;
+
+CREATE TABLE retention_rules (
+    rule_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    topic_id INTEGER,
+    resource_subtype TEXT,
+    max_age_days INTEGER NOT NULL CHECK (max_age_days > 0),
+    action TEXT NOT NULL CHECK (action IN ('archive', 'delete')),
+    skip_linked BOOLEAN NOT NULL DEFAULT 1,
+    is_enabled BOOLEAN NOT NULL DEFAULT 1,
+    last_run_at DATETIME,
+    last_affected INTEGER NOT NULL DEFAULT 0,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    FOREIGN KEY (topic_id) REFERENCES nodes(node_id) ON DELETE CASCADE
+);
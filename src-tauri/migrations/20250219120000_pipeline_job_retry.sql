@@ -0,0 +1,6 @@
+-- ==========================================
+-- AI 队列任务的重试时间
+-- retry_after: 暂时性失败后等待重试的任务保留记录（status 为 queued），到该时间后重新入队；
+--   应用在等待期间重启时按剩余时间恢复。正常入队时为空
+-- ==========================================
+ALTER TABLE pipeline_jobs ADD COLUMN retry_after DATETIME;
@@ -0,0 +1,32 @@
+-- ==========================================
+-- 待确认的 AI 修改新增 create_task
+--   kind: create_task（new_value 为 JSON {title, due_date, note}）
+--   node_id: 提出时为空，批准后记为新建任务的 id
+-- SQLite 无法修改 CHECK / NOT NULL 约束，重建 pending_changes 表（没有其他表引用它）。
+-- ==========================================
+CREATE TABLE pending_changes_new (
+    change_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    kind TEXT NOT NULL CHECK (kind IN ('rename_topic', 'move_resource', 'set_due_date', 'create_task')),
+    node_id INTEGER CHECK (node_id IS NOT NULL OR kind = 'create_task'),
+    old_value TEXT,
+    new_value TEXT,
+    reason TEXT,
+    session_id INTEGER,
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'approved', 'rejected')),
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    decided_at DATETIME,
+
+    FOREIGN KEY (node_id) REFERENCES nodes(node_id) ON DELETE CASCADE,
+    FOREIGN KEY (session_id) REFERENCES chat_sessions(session_id) ON DELETE SET NULL
+);
+
+INSERT INTO pending_changes_new (
+    change_id, kind, node_id, old_value, new_value, reason, session_id, status, created_at, decided_at
+)
+SELECT change_id, kind, node_id, old_value, new_value, reason, session_id, status, created_at, decided_at
+FROM pending_changes;
+
+DROP TABLE pending_changes;
+ALTER TABLE pending_changes_new RENAME TO pending_changes;
+
+CREATE INDEX idx_pending_changes_status ON pending_changes(status, created_at);
@@ -0,0 +1,29 @@
+-- 任务计时器开始时即写入一条未结束的 time_entries（ended_at 为空），停止时补上结束时间与时长，
+-- 应用退出或崩溃时不丢失已计时长；启动时据此恢复计时器。
+-- SQLite 无法修改 NOT NULL 约束，重建 time_entries 表（没有其他表引用它）。
+-- 未结束的记录 duration_seconds 为 0，不计入统计。
+
+CREATE TABLE time_entries_new (
+    entry_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    task_id INTEGER,
+    source TEXT NOT NULL DEFAULT 'manual' CHECK (source IN ('pomodoro', 'manual', 'timer')),
+    started_at DATETIME NOT NULL,
+    ended_at DATETIME CHECK (ended_at IS NOT NULL OR source = 'timer'),
+    duration_seconds INTEGER NOT NULL DEFAULT 0,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+
+    FOREIGN KEY (task_id) REFERENCES nodes(node_id) ON DELETE SET NULL
+);
+
+INSERT INTO time_entries_new (
+    entry_id, task_id, source, started_at, ended_at, duration_seconds, created_at
+)
+SELECT entry_id, task_id, source, started_at, ended_at, duration_seconds, created_at
+FROM time_entries;
+
+DROP TABLE time_entries;
+ALTER TABLE time_entries_new RENAME TO time_entries;
+
+CREATE INDEX idx_time_entries_task ON time_entries(task_id) WHERE task_id IS NOT NULL;
+CREATE INDEX idx_time_entries_started_at ON time_entries(started_at);
+CREATE INDEX idx_time_entries_open ON time_entries(entry_id) WHERE ended_at IS NULL;
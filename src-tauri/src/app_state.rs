@@ -1,5 +1,9 @@
 use crate::db::DbPool;
-use crate::services::{AIConfigService, AiPipeline, AiServicesHandle};
+use crate::services::{
+    AIConfigService, AiPipeline, AiServicesHandle, FileReferenceService, FocusService,
+    FolderWatchService, JobRegistry, MessageCaptureService, MetricsEndpoint, PomodoroService,
+    SemanticSearchCache, SnapshotSessions, TaskTimerService,
+};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
@@ -9,4 +13,14 @@ pub struct AppState {
     pub ai: AiServicesHandle,
     pub ai_config: Arc<Mutex<AIConfigService>>,
     pub ai_pipeline: Arc<AiPipeline>,
+    pub focus: Arc<FocusService>,
+    pub pomodoro: Arc<PomodoroService>,
+    pub task_timer: Arc<TaskTimerService>,
+    pub search_cache: Arc<SemanticSearchCache>,
+    pub message_capture: Arc<MessageCaptureService>,
+    pub metrics_endpoint: Arc<MetricsEndpoint>,
+    pub folder_watch: Arc<FolderWatchService>,
+    pub file_references: Arc<FileReferenceService>,
+    pub jobs: Arc<JobRegistry>,
+    pub snapshots: Arc<SnapshotSessions>,
 }
@@ -0,0 +1,285 @@
+//! 命令行入口（供 macOS 快捷指令 / AppleScript / 分享菜单调用）
+//!
+//! ```text
+//! neuralvault add --text "内容" [--title 标题] [--source 来源]
+//! neuralvault add --text -            # 从 stdin 读取文本
+//! neuralvault add --url https://...   [--title 标题]
+//! neuralvault add --file /path/a.pdf  [--title 标题]
+//! ```
+//!
+//! 不启动窗口，直接写入与应用相同的数据目录，成功时向 stdout 输出
+//! `{"node_id": .., "node_uuid": ..}`。AI 处理在应用下次启动或执行"处理待定资源"时进行。
+
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use directories::BaseDirs;
+
+use crate::db::{self, NodeBuilder, ResourceSubtype, SourceMeta};
+use crate::services::{
+    apply_assets_config, apply_source_tag_rules,
+    parser::{build_text_title, parse_resource_content},
+    AIConfigService,
+};
+use crate::utils::{
+    compute_sha256, ensure_assets_dir, get_extension, parse_file_type_from_extension,
+    resolve_stored_path,
+};
+
+/// 与 tauri.conf.json 中的 identifier 一致，决定应用数据目录
+const APP_IDENTIFIER: &str = "com.hovsco.neuralvault";
+const DEFAULT_SOURCE: &str = "Shortcuts";
+
+const USAGE: &str = "用法:
+  neuralvault add --text <文本|-> [--title <标题>] [--source <来源>]
+  neuralvault add --url <URL> [--title <标题>] [--source <来源>]
+  neuralvault add --file <路径> [--title <标题>] [--source <来源>]";
+
+#[derive(Debug, PartialEq)]
+enum AddInput {
+    Text(String),
+    Url(String),
+    File(PathBuf),
+}
+
+#[derive(Debug, PartialEq)]
+struct AddRequest {
+    input: AddInput,
+    title: Option<String>,
+    source: String,
+}
+
+/// 若命令行参数是子命令则执行并返回退出码；否则返回 None，继续启动图形界面
+pub fn run_cli_if_requested() -> Option<i32> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("add") => Some(run_add(&args[1..])),
+        Some("--help" | "-h" | "help") => {
+            println!("{USAGE}");
+            Some(0)
+        }
+        _ => None,
+    }
+}
+
+fn run_add(args: &[String]) -> i32 {
+    let mut request = match parse_add_args(args) {
+        Ok(request) => request,
+        Err(err) => {
+            eprintln!("{err}\n{USAGE}");
+            return 2;
+        }
+    };
+    if request.input == AddInput::Text("-".to_string()) {
+        let mut text = String::new();
+        if let Err(err) = std::io::stdin().read_to_string(&mut text) {
+            eprintln!("读取 stdin 失败: {err}");
+            return 1;
+        }
+        request.input = AddInput::Text(text);
+    }
+
+    match tauri::async_runtime::block_on(add_resource(request)) {
+        Ok((node_id, node_uuid)) => {
+            println!(
+                "{}",
+                serde_json::json!({ "node_id": node_id, "node_uuid": node_uuid })
+            );
+            0
+        }
+        Err(err) => {
+            eprintln!("添加失败: {err}");
+            1
+        }
+    }
+}
+
+fn parse_add_args(args: &[String]) -> Result<AddRequest, String> {
+    let mut input = None;
+    let mut title = None;
+    let mut source = DEFAULT_SOURCE.to_string();
+
+    let mut iter = args.iter();
+    while let Some(flag) = iter.next() {
+        let value = iter
+            .next()
+            .ok_or_else(|| format!("{flag} 缺少参数值"))?
+            .to_string();
+        let parsed = match flag.as_str() {
+            "--text" => AddInput::Text(value),
+            "--url" => AddInput::Url(value),
+            "--file" => AddInput::File(PathBuf::from(value)),
+            "--title" => {
+                title = Some(value);
+                continue;
+            }
+            "--source" => {
+                source = value;
+                continue;
+            }
+            other => return Err(format!("未知参数: {other}")),
+        };
+        if input.replace(parsed).is_some() {
+            return Err("--text / --url / --file 只能提供一个".to_string());
+        }
+    }
+
+    let input = input.ok_or_else(|| "需要 --text、--url 或 --file".to_string())?;
+    Ok(AddRequest {
+        input,
+        title: title.filter(|title| !title.trim().is_empty()),
+        source,
+    })
+}
+
+fn app_data_dir() -> Result<PathBuf, String> {
+    let dirs = BaseDirs::new().ok_or("无法确定应用数据目录")?;
+    Ok(dirs.data_dir().join(APP_IDENTIFIER))
+}
+
+async fn add_resource(request: AddRequest) -> Result<(i64, String), String> {
+    let app_dir = app_data_dir()?;
+    fs::create_dir_all(&app_dir).map_err(|e| e.to_string())?;
+    // 与应用使用同一资源文件目录
+    if let Ok(config) = AIConfigService::new(&app_dir).and_then(|service| service.load()) {
+        apply_assets_config(&app_dir, config.assets_dir.as_deref());
+    }
+    let pool = db::init_pool(app_dir.join("neuralvault.sqlite3"))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let builder = NodeBuilder::resource();
+    let node_uuid = builder.get_uuid().to_string();
+
+    let (subtype, content, stored_path, file_hash, default_title) = match request.input {
+        AddInput::Text(text) => {
+            if text.trim().is_empty() {
+                return Err("文本为空".to_string());
+            }
+            let hash = compute_sha256(text.as_bytes());
+            let title = build_text_title(&text);
+            (ResourceSubtype::Text, Some(text), None, hash, title)
+        }
+        AddInput::Url(url) => {
+            let url = url.trim().to_string();
+            let hash = compute_sha256(url.as_bytes());
+            (ResourceSubtype::Url, Some(url.clone()), None, hash, url)
+        }
+        AddInput::File(path) => {
+            let (stored_path, hash) = copy_into_assets(&app_dir, &path, &node_uuid)?;
+            let subtype = parse_file_type_from_extension(get_extension(&stored_path).as_deref());
+            let title = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| "Untitled".to_string());
+            (subtype, None, Some(stored_path), hash, title)
+        }
+    };
+
+    let source_url = match (&subtype, &content) {
+        (ResourceSubtype::Url, Some(url)) => Some(url.clone()),
+        _ => None,
+    };
+    let meta = SourceMeta {
+        url: source_url,
+        window_title: None,
+        process_name: Some(request.source),
+        captured_at: Some(chrono::Utc::now().to_rfc3339()),
+        process_path: None,
+        sender: None,
+        sent_at: None,
+        auto_title: None,
+    };
+
+    let node_id = builder
+        .title(request.title.unwrap_or(default_title))
+        .file_hash(Some(&file_hash))
+        .file_path(stored_path.as_deref())
+        .resource_subtype(Some(subtype))
+        .source_meta(Some(meta.clone()))
+        .insert(&pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if let Err(err) = apply_source_tag_rules(&pool, node_id, &meta).await {
+        eprintln!("来源规则打标签失败: {err}");
+    }
+
+    let resolved_path = stored_path.map(|path| {
+        resolve_stored_path(&app_dir, &path)
+            .to_string_lossy()
+            .to_string()
+    });
+    match parse_resource_content(subtype, content.as_deref(), resolved_path.as_deref(), None) {
+        Ok(Some(text)) => {
+            db::update_node_content(&pool, node_id, Some(&text), Some(&file_hash))
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+        Ok(None) => {}
+        Err(err) => {
+            db::update_resource_sync_status(
+                &pool,
+                node_id,
+                db::ResourceEmbeddingStatus::Error,
+                None,
+                Some(&err),
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+        }
+    }
+
+    pool.close().await;
+    Ok((node_id, node_uuid))
+}
+
+/// 复制文件到资源文件目录，返回相对路径与内容哈希
+fn copy_into_assets(app_dir: &Path, source: &Path, uuid: &str) -> Result<(String, String), String> {
+    let bytes = fs::read(source).map_err(|e| format!("读取文件失败: {e}"))?;
+    let file_name = match get_extension(&source.to_string_lossy()) {
+        Some(ext) => format!("{uuid}.{ext}"),
+        None => uuid.to_string(),
+    };
+    let assets_dir = ensure_assets_dir(app_dir)?;
+    fs::write(assets_dir.join(&file_name), &bytes).map_err(|e| e.to_string())?;
+    Ok((format!("assets/{file_name}"), compute_sha256(&bytes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(raw: &[&str]) -> Vec<String> {
+        raw.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_add_args() {
+        let request = parse_add_args(&args(&[
+            "--url",
+            "https://a.com",
+            "--title",
+            "A",
+            "--source",
+            "Safari",
+        ]))
+        .unwrap();
+        assert_eq!(request.input, AddInput::Url("https://a.com".to_string()));
+        assert_eq!(request.title.as_deref(), Some("A"));
+        assert_eq!(request.source, "Safari");
+
+        let request = parse_add_args(&args(&["--text", "-"])).unwrap();
+        assert_eq!(request.input, AddInput::Text("-".to_string()));
+        assert_eq!(request.source, DEFAULT_SOURCE);
+    }
+
+    #[test]
+    fn test_parse_add_args_errors() {
+        assert!(parse_add_args(&args(&[])).is_err());
+        assert!(parse_add_args(&args(&["--text"])).is_err());
+        assert!(parse_add_args(&args(&["--text", "a", "--url", "b"])).is_err());
+        assert!(parse_add_args(&args(&["--bogus", "x"])).is_err());
+    }
+}
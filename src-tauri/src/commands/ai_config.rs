@@ -5,7 +5,14 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tauri::State;
 
-use crate::{app_state::AppState, services::ClassificationMode};
+use crate::{
+    app_state::AppState,
+    i18n::{set_current_language, Language},
+    services::{
+        provider_requires_api_key, AnnIndexConfig, ChatContextConfig, ClassificationMode,
+        LocalModel, RetrievalConfig, SummaryLanguageMode,
+    },
+};
 
 // ========== Request/Response Types ==========
 
@@ -27,11 +34,27 @@ pub struct SetClassificationModeRequest {
     pub mode: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct SetWarmupOnIdleRequest {
+    pub enabled: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetLanguageRequest {
+    pub language: Language,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetSummaryLanguageRequest {
+    pub mode: SummaryLanguageMode,
+}
+
 #[derive(Debug, Serialize)]
 pub struct AIProviderStatus {
     pub has_key: bool,
     pub enabled: bool,
     pub base_url: Option<String>,
+    pub requests_per_minute: Option<u32>,
 }
 
 #[derive(Debug, Serialize)]
@@ -40,6 +63,12 @@ pub struct AIConfigStatusResponse {
     pub processing_provider: Option<String>,
     pub processing_model: Option<String>,
     pub classification_mode: ClassificationMode,
+    pub warmup_on_idle: bool,
+    pub language: Language,
+    pub summary_language: SummaryLanguageMode,
+    pub retrieval: RetrievalConfig,
+    pub chat_context: ChatContextConfig,
+    pub ann_index: AnnIndexConfig,
 }
 
 // ========== Commands ==========
@@ -59,9 +88,10 @@ pub async fn get_ai_config_status(
             (
                 k.clone(),
                 AIProviderStatus {
-                    has_key: !v.api_key.is_empty(),
+                    has_key: !v.api_key.is_empty() || !provider_requires_api_key(k),
                     enabled: v.enabled,
                     base_url: v.base_url.clone(),
+                    requests_per_minute: v.requests_per_minute,
                 },
             )
         })
@@ -72,6 +102,12 @@ pub async fn get_ai_config_status(
         processing_provider: config.processing_provider,
         processing_model: config.processing_model,
         classification_mode: config.classification_mode,
+        warmup_on_idle: config.warmup_on_idle,
+        language: config.language,
+        summary_language: config.summary_language,
+        retrieval: config.retrieval,
+        chat_context: config.chat_context,
+        ann_index: config.vector_config.ann_index.clamped(),
     })
 }
 
@@ -92,6 +128,18 @@ pub async fn remove_api_key(state: State<'_, AppState>, provider: String) -> Res
     config_service.remove_provider(&provider)
 }
 
+/// Limit requests per minute to a provider, shared by chat and background processing
+/// (`None` or 0 removes the limit)
+#[tauri::command]
+pub async fn set_provider_rate_limit(
+    state: State<'_, AppState>,
+    provider: String,
+    requests_per_minute: Option<u32>,
+) -> Result<(), String> {
+    let config_service = state.ai_config.lock().await;
+    config_service.set_provider_rate_limit(&provider, requests_per_minute)
+}
+
 /// Set processing provider and model
 #[tauri::command]
 pub async fn set_processing_provider_model(
@@ -115,3 +163,90 @@ pub async fn set_classification_mode(
     let config_service = state.ai_config.lock().await;
     config_service.set_classification_mode(mode)
 }
+
+/// Enable or disable search model warm-up while the app is idle
+#[tauri::command]
+pub async fn set_warmup_on_idle(
+    state: State<'_, AppState>,
+    request: SetWarmupOnIdleRequest,
+) -> Result<(), String> {
+    let config_service = state.ai_config.lock().await;
+    config_service.set_warmup_on_idle(request.enabled)
+}
+
+/// Set the UI language used for backend messages (zh / en)
+#[tauri::command]
+pub async fn set_language(
+    state: State<'_, AppState>,
+    request: SetLanguageRequest,
+) -> Result<(), String> {
+    let config_service = state.ai_config.lock().await;
+    config_service.set_language(request.language)?;
+    set_current_language(request.language);
+    Ok(())
+}
+
+/// Set the summary language (match source / UI language / bilingual)
+#[tauri::command]
+pub async fn set_summary_language(
+    state: State<'_, AppState>,
+    request: SetSummaryLanguageRequest,
+) -> Result<(), String> {
+    let config_service = state.ai_config.lock().await;
+    config_service.set_summary_language(request.mode)
+}
+
+/// Set default RAG retrieval parameters (top_k is clamped to 1..=50, weight to 0..=1)
+#[tauri::command]
+pub async fn set_retrieval_config(
+    state: State<'_, AppState>,
+    retrieval: RetrievalConfig,
+) -> Result<RetrievalConfig, String> {
+    let config_service = state.ai_config.lock().await;
+    config_service.set_retrieval_config(retrieval)?;
+    Ok(config_service.load()?.retrieval)
+}
+
+/// Set chat history compaction limits (max_input_tokens 2000..=2000000, min_recent_messages 1..=50)
+#[tauri::command]
+pub async fn set_chat_context_config(
+    state: State<'_, AppState>,
+    chat_context: ChatContextConfig,
+) -> Result<ChatContextConfig, String> {
+    let config_service = state.ai_config.lock().await;
+    config_service.set_chat_context_config(chat_context)?;
+    Ok(config_service.load()?.chat_context)
+}
+
+/// Set ANN vector index parameters (min_rows >= 256, nprobes 1..=1024); applies immediately
+#[tauri::command]
+pub async fn set_ann_index_config(
+    state: State<'_, AppState>,
+    ann_index: AnnIndexConfig,
+) -> Result<AnnIndexConfig, String> {
+    let ann_index = ann_index.clamped();
+    state
+        .ai_config
+        .lock()
+        .await
+        .set_ann_index_config(ann_index)?;
+    if let Some(ai) = state.ai.try_ready() {
+        ai.embedding.set_ann_config(ann_index).await;
+    }
+    Ok(ann_index)
+}
+
+/// List models installed in the local Ollama daemon
+///
+/// Uses the configured `ollama` provider base URL, or localhost when not configured.
+#[tauri::command]
+pub async fn list_local_models(state: State<'_, AppState>) -> Result<Vec<LocalModel>, String> {
+    let base_url = {
+        let config_service = state.ai_config.lock().await;
+        config_service
+            .get_provider_config("ollama")?
+            .and_then(|config| config.base_url)
+    };
+    let ai = state.ai.wait_ready().await?;
+    ai.llm.list_local_models(base_url.as_deref()).await
+}
@@ -0,0 +1,130 @@
+//! 资源文件目录命令
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use tauri::{AppHandle, Manager, State};
+
+use crate::i18n::MessageCode;
+use crate::services::{
+    allow_assets_scope, assets_storage_status, copy_assets, emit_event, remove_old_assets,
+    validate_relocation_target, AssetsCopy, AssetsRelocateProgressPayload, AssetsStorageStatus,
+    RelocationGuard,
+};
+use crate::utils::{assets_root, set_custom_assets_root};
+use crate::{AppError, AppResult, AppState};
+
+use super::AssetsRelocateSummary;
+
+fn invalid_assets_dir(detail: impl ToString) -> AppError {
+    AppError::coded_with(MessageCode::InvalidAssetsDir, "detail", detail)
+}
+
+#[tauri::command]
+pub fn get_assets_storage(app: AppHandle) -> AppResult<AssetsStorageStatus> {
+    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    Ok(assets_storage_status(&app_data_dir))
+}
+
+/// 把资源文件迁移到 new_path 并保存为新的资源文件目录
+///
+/// 先复制再切换配置，切换后补复制迁移期间新写入的文件，最后删除旧文件；
+/// 复制失败时不改动配置。当前目录不可用（如外接硬盘丢失）时只切换目录，
+/// 用于指向已手动恢复的副本。new_path 为默认目录时恢复默认配置
+#[tauri::command]
+pub async fn relocate_assets(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    new_path: String,
+) -> AppResult<AssetsRelocateSummary> {
+    let _guard = RelocationGuard::acquire()
+        .ok_or_else(|| AppError::coded(MessageCode::AssetsRelocationRunning))?;
+
+    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let target = PathBuf::from(new_path.trim());
+    if !target.is_absolute() {
+        return Err(AppError::coded(MessageCode::AssetsDirNotAbsolute));
+    }
+    fs::create_dir_all(&target)?;
+    let target = target.canonicalize()?;
+
+    let current = assets_root(&app_data_dir);
+    let current_available = current.is_dir();
+    let current = current.canonicalize().unwrap_or(current);
+    validate_relocation_target(&current, &target)?;
+
+    let first_pass = if current_available {
+        copy_in_background(&app, &current, &target).await?
+    } else {
+        AssetsCopy::default()
+    };
+
+    let default_root = app_data_dir.join("assets");
+    let is_default = default_root.canonicalize().unwrap_or(default_root) == target;
+    let stored = (!is_default).then(|| target.to_string_lossy().to_string());
+    state
+        .ai_config
+        .lock()
+        .await
+        .set_assets_dir(stored.clone())?;
+    set_custom_assets_root(stored.map(PathBuf::from));
+    allow_assets_scope(&app, &target);
+    tracing::info!(from = %current.display(), to = %target.display(), "Assets directory relocated");
+
+    let mut summary = AssetsRelocateSummary {
+        status: assets_storage_status(&app_data_dir),
+        moved_files: first_pass.copied,
+        moved_bytes: first_pass.bytes,
+        left_behind: 0,
+    };
+    if current_available {
+        // 补复制切换前新写入的文件；失败时保留旧文件，不影响已完成的切换
+        let second_pass = match copy_in_background(&app, &current, &target).await {
+            Ok(copy) => copy,
+            Err(err) => {
+                tracing::warn!(error = %err, "Copy assets written during relocation failed");
+                AssetsCopy {
+                    files: first_pass.files,
+                    ..Default::default()
+                }
+            }
+        };
+        summary.moved_files += second_pass.copied;
+        summary.moved_bytes += second_pass.bytes;
+
+        let app_handle = app.clone();
+        summary.left_behind = tauri::async_runtime::spawn_blocking(move || {
+            emit_progress(&app_handle, "removing", 0, second_pass.files.len());
+            remove_old_assets(&current, &second_pass.files)
+        })
+        .await
+        .map_err(invalid_assets_dir)?;
+    }
+
+    emit_progress(&app, "done", summary.moved_files, summary.moved_files);
+    Ok(summary)
+}
+
+async fn copy_in_background(app: &AppHandle, from: &Path, to: &Path) -> AppResult<AssetsCopy> {
+    let app_handle = app.clone();
+    let from = from.to_path_buf();
+    let to = to.to_path_buf();
+    tauri::async_runtime::spawn_blocking(move || {
+        copy_assets(&from, &to, &mut |processed, total| {
+            emit_progress(&app_handle, "copying", processed, total);
+        })
+    })
+    .await
+    .map_err(invalid_assets_dir)?
+}
+
+fn emit_progress(app: &AppHandle, status: &'static str, processed: usize, total: usize) {
+    emit_event(
+        app,
+        &AssetsRelocateProgressPayload {
+            status,
+            processed,
+            total,
+        },
+    );
+}
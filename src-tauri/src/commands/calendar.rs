@@ -0,0 +1,118 @@
+//! 日历导入与订阅命令
+
+use std::fs;
+
+use tauri::State;
+
+use crate::db::{self, CalendarSubscriptionRecord};
+use crate::i18n::MessageCode;
+use crate::services::{
+    self, build_tasks_ics, parse_ics, sync_calendar_events, CalendarSyncSummary,
+};
+use crate::utils::validate_not_empty;
+use crate::{AppError, AppResult, AppState};
+
+const DEFAULT_POLL_INTERVAL_MINUTES: i64 = 60;
+const MIN_POLL_INTERVAL_MINUTES: i64 = 5;
+
+/// 导入 .ics 文件：事件按 UID 创建或更新为任务，重复导入不会产生重复任务
+#[tauri::command]
+pub async fn import_ics(
+    state: State<'_, AppState>,
+    path: String,
+) -> AppResult<CalendarSyncSummary> {
+    let text = fs::read_to_string(&path)?;
+    let events =
+        parse_ics(&text).map_err(|e| AppError::coded_with(MessageCode::InvalidIcs, "detail", e))?;
+    let summary = sync_calendar_events(&state.db, &events, None).await?;
+    tracing::info!(
+        path = %path,
+        created = summary.created,
+        updated = summary.updated,
+        "ICS imported"
+    );
+    Ok(summary)
+}
+
+/// 导出有截止日期的任务为 .ics 文件，返回导出的事件数
+///
+/// 重复任务带 RRULE，待办任务的提醒写为 VALARM；重新导出覆盖原文件，日历应用按 UID 更新事件
+#[tauri::command]
+pub async fn export_tasks_ics(state: State<'_, AppState>, path: String) -> AppResult<usize> {
+    let (text, count) = build_tasks_ics(&state.db).await?;
+    fs::write(&path, text)?;
+    tracing::info!(path = %path, count, "Tasks exported to ICS");
+    Ok(count)
+}
+
+/// 添加日历订阅并立即同步一次；同步失败记录在订阅的 last_error 中，不影响添加
+///
+/// 轮询间隔默认 60 分钟，最短 5 分钟
+#[tauri::command]
+pub async fn add_calendar_subscription(
+    state: State<'_, AppState>,
+    name: String,
+    url: String,
+    poll_interval_minutes: Option<i64>,
+) -> AppResult<CalendarSubscriptionRecord> {
+    let name = validate_not_empty(&name, "name")?;
+    let url = url.trim();
+    let lower = url.to_ascii_lowercase();
+    if !["http://", "https://", "webcal://"]
+        .iter()
+        .any(|scheme| lower.starts_with(scheme))
+    {
+        return Err(AppError::coded(MessageCode::InvalidCalendarUrl));
+    }
+    let interval = poll_interval_minutes
+        .unwrap_or(DEFAULT_POLL_INTERVAL_MINUTES)
+        .max(MIN_POLL_INTERVAL_MINUTES);
+
+    let subscription_id = db::insert_calendar_subscription(&state.db, name, url, interval).await?;
+    let subscription = fetch_subscription(&state, subscription_id).await?;
+    if let Err(err) = services::sync_calendar_subscription(&state.db, &subscription).await {
+        tracing::warn!(subscription_id, error = %err, "Initial calendar sync failed");
+    }
+    fetch_subscription(&state, subscription_id).await
+}
+
+#[tauri::command]
+pub async fn list_calendar_subscriptions(
+    state: State<'_, AppState>,
+) -> AppResult<Vec<CalendarSubscriptionRecord>> {
+    Ok(db::list_calendar_subscriptions(&state.db).await?)
+}
+
+/// 删除订阅；已导入的任务保留
+#[tauri::command]
+pub async fn remove_calendar_subscription(state: State<'_, AppState>, id: i64) -> AppResult<()> {
+    if !db::delete_calendar_subscription(&state.db, id).await? {
+        return Err(AppError::NotFound {
+            entity: "calendar_subscription",
+            id,
+        });
+    }
+    Ok(())
+}
+
+/// 立即同步一个订阅
+#[tauri::command]
+pub async fn sync_calendar_subscription(
+    state: State<'_, AppState>,
+    id: i64,
+) -> AppResult<CalendarSyncSummary> {
+    let subscription = fetch_subscription(&state, id).await?;
+    Ok(services::sync_calendar_subscription(&state.db, &subscription).await?)
+}
+
+async fn fetch_subscription(
+    state: &AppState,
+    subscription_id: i64,
+) -> AppResult<CalendarSubscriptionRecord> {
+    db::get_calendar_subscription(&state.db, subscription_id)
+        .await?
+        .ok_or(AppError::NotFound {
+            entity: "calendar_subscription",
+            id: subscription_id,
+        })
+}
@@ -0,0 +1,140 @@
+//! 捕获模板命令
+
+use tauri::{AppHandle, State};
+
+use crate::db::{self, NodeType};
+use crate::i18n::MessageCode;
+use crate::services::{normalize_profile_tags, CaptureProfile};
+use crate::utils::validate_not_empty;
+use crate::window::{default_hud_shortcut, parse_shortcut, register_profile_shortcuts};
+use crate::{AppError, AppResult, AppState};
+
+use super::SaveCaptureProfileRequest;
+
+#[tauri::command]
+pub async fn list_capture_profiles(state: State<'_, AppState>) -> AppResult<Vec<CaptureProfile>> {
+    Ok(state.ai_config.lock().await.load()?.capture_profiles)
+}
+
+/// 新建或更新捕获模板，保存后重新注册全部模板快捷键
+///
+/// 快捷键不能与 HUD 默认快捷键或其他模板重复
+#[tauri::command]
+pub async fn save_capture_profile(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    payload: SaveCaptureProfileRequest,
+) -> AppResult<CaptureProfile> {
+    let SaveCaptureProfileRequest {
+        profile_id,
+        name,
+        shortcut,
+        topic_id,
+        tags,
+        defer_processing,
+    } = payload;
+    let name = validate_not_empty(&name, "name")?.to_string();
+    if let Some(topic_id) = topic_id {
+        let is_topic = db::get_node_by_id(&state.db, topic_id)
+            .await
+            .is_ok_and(|node| node.node_type == NodeType::Topic && !node.is_deleted);
+        if !is_topic {
+            return Err(AppError::NotFound {
+                entity: "topic",
+                id: topic_id,
+            });
+        }
+    }
+
+    let config_service = state.ai_config.lock().await;
+    let mut profiles = config_service.load()?.capture_profiles;
+
+    let shortcut = shortcut
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    if let Some(value) = shortcut.as_deref() {
+        let parsed = parse_shortcut(value).ok_or_else(|| {
+            AppError::coded_with(MessageCode::InvalidShortcut, "value", value.to_string())
+        })?;
+        let taken = parsed == default_hud_shortcut()
+            || profiles.iter().any(|profile| {
+                Some(profile.profile_id) != profile_id
+                    && profile.shortcut.as_deref().and_then(parse_shortcut) == Some(parsed)
+            });
+        if taken {
+            return Err(AppError::coded_with(
+                MessageCode::ShortcutTaken,
+                "value",
+                value.to_string(),
+            ));
+        }
+    }
+
+    let profile_id = match profile_id {
+        Some(id) if profiles.iter().any(|profile| profile.profile_id == id) => id,
+        Some(id) => {
+            return Err(AppError::NotFound {
+                entity: "capture_profile",
+                id,
+            })
+        }
+        None => {
+            profiles
+                .iter()
+                .map(|profile| profile.profile_id)
+                .max()
+                .unwrap_or(0)
+                + 1
+        }
+    };
+    let profile = CaptureProfile {
+        profile_id,
+        name,
+        shortcut,
+        topic_id,
+        tags: normalize_profile_tags(&tags),
+        defer_processing,
+    };
+    match profiles
+        .iter_mut()
+        .find(|existing| existing.profile_id == profile_id)
+    {
+        Some(existing) => *existing = profile.clone(),
+        None => profiles.push(profile.clone()),
+    }
+
+    config_service.set_capture_profiles(profiles.clone())?;
+    if let Err(failed) = register_profile_shortcuts(&app, &profiles) {
+        return Err(AppError::coded_with(
+            MessageCode::ShortcutTaken,
+            "value",
+            failed,
+        ));
+    }
+    Ok(profile)
+}
+
+/// 删除捕获模板并注销其快捷键；已捕获的资源不受影响
+#[tauri::command]
+pub async fn delete_capture_profile(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    profile_id: i64,
+) -> AppResult<()> {
+    let config_service = state.ai_config.lock().await;
+    let mut profiles = config_service.load()?.capture_profiles;
+    let before = profiles.len();
+    profiles.retain(|profile| profile.profile_id != profile_id);
+    if profiles.len() == before {
+        return Err(AppError::NotFound {
+            entity: "capture_profile",
+            id: profile_id,
+        });
+    }
+
+    config_service.set_capture_profiles(profiles.clone())?;
+    if let Err(failed) = register_profile_shortcuts(&app, &profiles) {
+        tracing::warn!(shortcuts = %failed, "Capture profile shortcuts not registered");
+    }
+    Ok(())
+}
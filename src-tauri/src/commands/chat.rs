@@ -7,18 +7,23 @@ use crate::{
         get_chat_session_by_id, list_chat_sessions_by_node, list_message_attachments_with_node,
         list_session_bound_resources, set_session_bindings, update_chat_message_contents,
         update_chat_session, delete_chat_message as delete_chat_message_record,
-        delete_message_attachment, soft_delete_chat_session,
-        BindingType, NewChatMessage, NewChatSession, NewMessageAttachment, SessionType,
+        delete_message_attachment, fork_chat_session as fork_chat_session_record,
+        resolve_node_ref, resolve_node_refs, soft_delete_chat_session,
+        update_chat_session_rag_settings, list_session_message_sources, BindingType,
+        MessageSourceRecord, NewChatMessage, NewChatSession, NewMessageAttachment,
+        SessionRagSettings, SessionType,
     },
-    AppResult,
+    i18n::MessageCode,
+    services::MAX_RETRIEVAL_TOP_K,
+    AppError, AppResult,
 };
 
 use super::{
     AddMessageAttachmentsRequest, ChatMessageAttachmentPayload, CreateChatMessageRequest,
     CreateChatMessageResponse, CreateChatSessionRequest, CreateChatSessionResponse,
-    DeleteChatMessageRequest, DeleteChatSessionRequest, ListChatSessionsRequest,
-    RemoveMessageAttachmentRequest, SetSessionBindingsRequest, UpdateChatMessageRequest,
-    UpdateChatSessionRequest,
+    DeleteChatMessageRequest, DeleteChatSessionRequest, ForkChatSessionRequest,
+    ListChatSessionsRequest, RemoveMessageAttachmentRequest, SetSessionBindingsRequest,
+    UpdateChatMessageRequest, UpdateChatSessionRequest,
 };
 
 #[tauri::command]
@@ -39,7 +44,8 @@ pub async fn create_chat_session(
     )
     .await?;
 
-    if let Some(node_ids) = payload.context_node_ids {
+    if let Some(nodes) = payload.context_node_ids {
+        let node_ids = resolve_node_refs(&state.db, &nodes).await?;
         let binding_type = payload.binding_type.unwrap_or(BindingType::Primary);
         set_session_bindings(&state.db, session_id, &node_ids, binding_type).await?;
     }
@@ -53,7 +59,8 @@ pub async fn list_chat_sessions(
     payload: ListChatSessionsRequest,
 ) -> AppResult<Vec<crate::db::ChatSessionRecord>> {
     let include_deleted = payload.include_deleted.unwrap_or(false);
-    if let Some(node_id) = payload.node_id {
+    if let Some(node) = payload.node_id {
+        let node_id = resolve_node_ref(&state.db, &node).await?;
         return Ok(list_chat_sessions_by_node(&state.db, node_id, include_deleted).await?);
     }
     Err("node_id is required".into())
@@ -90,6 +97,27 @@ pub async fn delete_chat_session(
     Ok(soft_delete_chat_session(&state.db, payload.session_id).await?)
 }
 
+/// 从指定消息处分叉出新会话，原会话不变
+#[tauri::command]
+pub async fn fork_chat_session(
+    state: State<'_, AppState>,
+    payload: ForkChatSessionRequest,
+) -> AppResult<CreateChatSessionResponse> {
+    let title = payload
+        .title
+        .as_deref()
+        .map(str::trim)
+        .filter(|title| !title.is_empty());
+    let session_id =
+        fork_chat_session_record(&state.db, payload.session_id, payload.message_id, title)
+            .await?
+            .ok_or(AppError::NotFound {
+                entity: "chat_message",
+                id: payload.message_id,
+            })?;
+    Ok(CreateChatSessionResponse { session_id })
+}
+
 #[tauri::command]
 pub async fn create_chat_message(
     state: State<'_, AppState>,
@@ -111,8 +139,9 @@ pub async fn create_chat_message(
     )
     .await?;
 
-    if let Some(node_ids) = payload.attachment_node_ids {
-        let attachments: Vec<NewMessageAttachment> = node_ids
+    if let Some(nodes) = payload.attachment_node_ids {
+        let attachments: Vec<NewMessageAttachment> = resolve_node_refs(&state.db, &nodes)
+            .await?
             .into_iter()
             .map(|node_id| NewMessageAttachment {
                 message_id,
@@ -185,8 +214,8 @@ pub async fn add_message_attachments(
     state: State<'_, AppState>,
     payload: AddMessageAttachmentsRequest,
 ) -> AppResult<()> {
-    let attachments: Vec<NewMessageAttachment> = payload
-        .node_ids
+    let attachments: Vec<NewMessageAttachment> = resolve_node_refs(&state.db, &payload.node_ids)
+        .await?
         .into_iter()
         .map(|node_id| NewMessageAttachment {
             message_id: payload.message_id,
@@ -201,7 +230,8 @@ pub async fn remove_message_attachment(
     state: State<'_, AppState>,
     payload: RemoveMessageAttachmentRequest,
 ) -> AppResult<()> {
-    Ok(delete_message_attachment(&state.db, payload.message_id, payload.node_id).await?)
+    let node_id = resolve_node_ref(&state.db, &payload.node_id).await?;
+    Ok(delete_message_attachment(&state.db, payload.message_id, node_id).await?)
 }
 
 #[tauri::command]
@@ -209,11 +239,44 @@ pub async fn set_session_bindings_command(
     state: State<'_, AppState>,
     payload: SetSessionBindingsRequest,
 ) -> AppResult<()> {
+    let node_ids = resolve_node_refs(&state.db, &payload.node_ids).await?;
     Ok(set_session_bindings(
         &state.db,
         payload.session_id,
-        &payload.node_ids,
+        &node_ids,
         payload.binding_type,
     )
     .await?)
 }
+
+/// 会话内各条回答引用的 RAG 来源（按消息、编号排序）
+#[tauri::command]
+pub async fn list_message_sources(
+    state: State<'_, AppState>,
+    session_id: i64,
+) -> AppResult<Vec<MessageSourceRecord>> {
+    Ok(list_session_message_sources(&state.db, session_id).await?)
+}
+
+/// 设置会话的 RAG 检索参数（top_k 限制在 1..=50），发送消息时优先于全局配置；全部为空时恢复全局配置
+#[tauri::command]
+pub async fn set_session_rag_settings(
+    state: State<'_, AppState>,
+    session_id: i64,
+    settings: SessionRagSettings,
+) -> AppResult<SessionRagSettings> {
+    if settings
+        .score_threshold
+        .is_some_and(|threshold| !threshold.is_finite())
+    {
+        return Err(AppError::coded(MessageCode::ScoreThresholdInvalid));
+    }
+    let settings = SessionRagSettings {
+        top_k: settings.top_k.map(|top_k| top_k.clamp(1, MAX_RETRIEVAL_TOP_K)),
+        ..settings
+    };
+    // 确认会话存在
+    get_chat_session_by_id(&state.db, session_id).await?;
+    update_chat_session_rag_settings(&state.db, session_id, &settings).await?;
+    Ok(settings)
+}
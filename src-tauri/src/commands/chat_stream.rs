@@ -4,7 +4,7 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
-use tauri::{AppHandle, Emitter, State};
+use tauri::{AppHandle, State};
 use tokio::sync::Mutex;
 use tracing::{debug, warn};
 
@@ -12,11 +12,16 @@ use crate::{
     app_state::AppState,
     db::{
         get_chat_session_by_id, get_node_by_id, insert_chat_message, insert_message_attachments,
-        list_chat_messages, list_message_attachments_with_node, list_session_bound_resources,
-        update_chat_message_contents, update_chat_session, NewChatMessage, NewMessageAttachment,
-        ResourceSubtype,
+        insert_message_sources, list_chat_messages, list_message_attachments_with_node,
+        list_search_excluded_node_ids, list_session_bound_resources, update_chat_message_contents,
+        update_chat_session, EmbeddingType, NewChatMessage, NewMessageAttachment, NewMessageSource,
+        ResourceSubtype, UsageOperation,
+    },
+    services::{
+        compact_chat_history, emit_event, get_processing_config, provider_requires_api_key,
+        record_usage, ChatMessage, ChatRole, ChatStreamEvent, ChatStreamEventPayload,
+        ChatStreamPayload, ChatUsage, RetrievalOverrides, VaultTools,
     },
-    services::{get_processing_config, ChatMessage, ChatRole, ChatStreamEvent},
     utils::resolve_file_path,
 };
 
@@ -30,6 +35,14 @@ pub struct SendChatRequest {
     pub files: Option<Vec<i64>>,
     pub thinking_effort: Option<String>,
     pub rag_scope: Option<String>,
+    /// Let the model call vault tools (search_vault / fetch_url / propose_* changes)
+    pub use_tools: Option<bool>,
+    /// Per-message overrides of the retrieval parameters (over the session's RAG settings)
+    pub retrieval: Option<RetrievalOverrides>,
+    /// Nodes kept out of RAG for this message
+    pub exclude_node_ids: Option<Vec<i64>>,
+    /// Topics (and everything they contain) kept out of RAG for this message
+    pub exclude_topic_ids: Option<Vec<i64>>,
 }
 
 #[derive(Debug, Serialize)]
@@ -37,8 +50,6 @@ pub struct ChatStreamAck {
     pub ok: bool,
 }
 
-const RAG_TOP_K: u64 = 5;
-
 #[derive(Clone, Copy)]
 enum RagScope {
     Local,
@@ -60,19 +71,40 @@ pub async fn send_chat_message(
     state: State<'_, AppState>,
     request: SendChatRequest,
 ) -> Result<ChatStreamAck, String> {
+    let session = get_chat_session_by_id(&state.db, request.session_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    // Saved RAG settings of the session sit between the global config and `request.retrieval`
+    let session_rag = session
+        .rag_settings
+        .as_ref()
+        .map(|settings| settings.0)
+        .unwrap_or_default();
+    let request_overrides = request.retrieval.unwrap_or_default();
+    let retrieval_overrides = RetrievalOverrides {
+        top_k: request_overrides.top_k.or(session_rag.top_k),
+        ..request_overrides
+    };
+
     // 1. Get API key from encrypted config
     let config_service = state.ai_config.lock().await;
     let provider_config = config_service
         .get_provider_config(&request.provider)?
         .ok_or_else(|| format!("Provider {} not configured", request.provider))?;
 
-    if provider_config.api_key.is_empty() {
+    if provider_config.api_key.is_empty() && provider_requires_api_key(&request.provider) {
         return Err(format!("API key not set for {}", request.provider));
     }
     if !provider_config.enabled {
         return Err(format!("Provider {} is disabled", request.provider));
     }
 
+    let ai_config = config_service.load()?;
+    let retrieval = ai_config
+        .retrieval
+        .with_overrides(Some(&retrieval_overrides));
+    let chat_context = ai_config.chat_context;
+
     // Release lock to avoid holding it during HTTP requests
     drop(config_service);
 
@@ -131,6 +163,16 @@ pub async fn send_chat_message(
         .await
         .map_err(|e| e.to_string())?;
     let is_first_message = messages.len() == 1;
+    // Older turns beyond the token budget are replaced by the session's history summary
+    let history = compact_chat_history(
+        &state.db,
+        &ai,
+        &state.ai_config,
+        &session,
+        &messages,
+        chat_context,
+    )
+    .await;
     let attachments = list_message_attachments_with_node(&state.db, request.session_id)
         .await
         .map_err(|e| e.to_string())?;
@@ -177,9 +219,20 @@ pub async fn send_chat_message(
     }
 
     let rag_scope = parse_rag_scope(request.rag_scope.as_deref()).map_err(|e| e.to_string())?;
+    let excluded_node_ids = list_search_excluded_node_ids(
+        &state.db,
+        request.exclude_node_ids.as_deref().unwrap_or_default(),
+        request.exclude_topic_ids.as_deref().unwrap_or_default(),
+    )
+    .await
+    .map_err(|e| e.to_string())?;
     let scope_node_ids = match rag_scope {
         RagScope::Local => {
-            let ids: Vec<i64> = context_resources.iter().map(|r| r.node_id).collect();
+            let ids: Vec<i64> = context_resources
+                .iter()
+                .map(|r| r.node_id)
+                .filter(|id| excluded_node_ids.binary_search(id).is_err())
+                .collect();
             if ids.is_empty() {
                 None
             } else {
@@ -188,11 +241,28 @@ pub async fn send_chat_message(
         }
         RagScope::Global => None,
     };
+    let rag_embedding_type = match session_rag.embedding_type {
+        Some(EmbeddingType::Summary) => "summary",
+        Some(EmbeddingType::Content) | None => "content",
+    };
     let rag_results = if matches!(rag_scope, RagScope::Global) || scope_node_ids.is_some() {
         ai.search
-            .search_hybrid(&request.content, "content", scope_node_ids.as_deref(), RAG_TOP_K)
+            .search_hybrid_with(
+                &request.content,
+                rag_embedding_type,
+                scope_node_ids.as_deref(),
+                &excluded_node_ids,
+                &retrieval,
+            )
             .await
             .map_err(|e| e.to_string())?
+            .into_iter()
+            .filter(|result| {
+                session_rag
+                    .score_threshold
+                    .is_none_or(|threshold| result.score >= threshold)
+            })
+            .collect()
     } else {
         Vec::new()
     };
@@ -201,19 +271,59 @@ pub async fn send_chat_message(
         None
     } else {
         let mut lines = Vec::new();
-        for result in rag_results {
+        let mut sources = Vec::with_capacity(rag_results.len());
+        for (index, result) in rag_results.iter().enumerate() {
             let node = get_node_by_id(&state.db, result.node_id)
                 .await
                 .map_err(|e| e.to_string())?;
-            lines.push(format!("- [{}] {}", node.title, result.chunk_text));
+            let rank = index as i64 + 1;
+            lines.push(format!("[{}] {}: {}", rank, node.title, result.chunk_text));
+            sources.push(NewMessageSource {
+                node_id: result.node_id,
+                chunk_index: result.chunk_index,
+                rank,
+                score: result.score,
+                snippet: &result.chunk_text,
+            });
         }
+
+        // Sources are stored before the answer so the UI can show them while it streams
+        let sources = insert_message_sources(&state.db, user_message_id, &sources)
+            .await
+            .map_err(|e| e.to_string())?;
+        emit_event(
+            &app,
+            &ChatStreamPayload {
+                session_id: request.session_id,
+                event: ChatStreamEventPayload::Sources {
+                    message_id: user_message_id,
+                    sources,
+                },
+            },
+        );
+
         Some(ChatMessage::new(
             ChatRole::User,
-            format!("Retrieved context:\n{}", lines.join("\n")),
+            format!(
+                "Retrieved context (cite a source as [n] when the answer uses it):\n{}",
+                lines.join("\n")
+            ),
         ))
     };
 
-    let mut chat_messages: Vec<ChatMessage> = Vec::with_capacity(messages.len() * 2 + 2);
+    // fetch_url may only fetch links the user typed in this session
+    let user_messages: Vec<String> = messages
+        .iter()
+        .map(|message| message.user_content.clone())
+        .collect();
+
+    let mut chat_messages: Vec<ChatMessage> = Vec::with_capacity(messages.len() * 2 + 3);
+    if let Some(summary) = history.summary.as_deref() {
+        chat_messages.push(ChatMessage::new(
+            ChatRole::User,
+            format!("Summary of the earlier conversation:\n{summary}"),
+        ));
+    }
     if !context_images.is_empty() || !context_files.is_empty() || !context_lines.is_empty() {
         let content = if context_lines.is_empty() {
             "Context files attached.".to_string()
@@ -225,7 +335,7 @@ pub async fn send_chat_message(
         message.files = context_files;
         chat_messages.push(message);
     }
-    for message in messages {
+    for message in messages.into_iter().skip(history.start) {
         let (images, files) = attachment_map.remove(&message.message_id).unwrap_or_default();
         if !message.user_content.is_empty() {
             let mut chat_message = ChatMessage::new(ChatRole::User, message.user_content.clone());
@@ -274,98 +384,131 @@ pub async fn send_chat_message(
     let usage_tokens: Arc<Mutex<Option<(i64, i64, i64, i64)>>> = Arc::new(Mutex::new(None));
     let stream_app = app.clone();
 
-    let stream_result = ai
-        .llm
-        .stream_chat(
-            &provider,
-            &model,
-            &provider_config,
-            &chat_messages,
-            thinking_effort.as_deref(),
-            {
-                let assistant_accum = assistant_accum.clone();
-                let thinking_accum = thinking_accum.clone();
-                let usage_tokens = usage_tokens.clone();
-                let stream_app = stream_app.clone();
-                move |event| {
-                    let assistant_accum = assistant_accum.clone();
-                    let thinking_accum = thinking_accum.clone();
-                    let usage_tokens = usage_tokens.clone();
-                    let stream_app = stream_app.clone();
-                    async move {
-                        match event {
-                            ChatStreamEvent::AnswerDelta(delta) => {
-                                let mut guard = assistant_accum.lock().await;
-                                guard.push_str(&delta);
-                                let payload = serde_json::json!({
-                                    "session_id": session_id,
-                                    "type": "answer_delta",
-                                    "delta": delta,
-                                });
-                                let _ = stream_app.emit("chat-stream", payload);
-                            }
-                            ChatStreamEvent::ThinkingDelta(delta) => {
-                                let mut guard = thinking_accum.lock().await;
-                                guard.push_str(&delta);
-                                let payload = serde_json::json!({
-                                    "session_id": session_id,
-                                    "type": "thinking_delta",
-                                    "delta": delta,
-                                });
-                                let _ = stream_app.emit("chat-stream", payload);
-                            }
-                            ChatStreamEvent::AnswerFullText(full_text) => {
-                                let mut guard = assistant_accum.lock().await;
-                                *guard = full_text;
-                            }
-                            ChatStreamEvent::ThinkingFullText(full_text) => {
-                                let mut guard = thinking_accum.lock().await;
-                                *guard = full_text;
-                            }
-                            ChatStreamEvent::Usage(usage) => {
-                                let mut guard = usage_tokens.lock().await;
-                                *guard = Some((
-                                    usage.input_tokens,
-                                    usage.output_tokens,
-                                    usage.reasoning_tokens,
-                                    usage.total_tokens,
-                                ));
-                                let payload = serde_json::json!({
-                                    "session_id": session_id,
-                                    "type": "usage",
-                                    "usage": {
-                                        "input_tokens": usage.input_tokens,
-                                        "output_tokens": usage.output_tokens,
-                                        "reasoning_tokens": usage.reasoning_tokens,
-                                        "total_tokens": usage.total_tokens,
-                                    }
-                                });
-                                let _ = stream_app.emit("chat-stream", payload);
-                            }
-                            ChatStreamEvent::Error(message) => {
-                                let payload = serde_json::json!({
-                                    "session_id": session_id,
-                                    "type": "error",
-                                    "message": message,
-                                });
-                                let _ = stream_app.emit("chat-stream", payload);
-                                return Err("LLM stream error".to_string());
-                            }
-                        }
-                        Ok(())
+    let on_event = {
+        let assistant_accum = assistant_accum.clone();
+        let thinking_accum = thinking_accum.clone();
+        let usage_tokens = usage_tokens.clone();
+        let stream_app = stream_app.clone();
+        move |event| {
+            let assistant_accum = assistant_accum.clone();
+            let thinking_accum = thinking_accum.clone();
+            let usage_tokens = usage_tokens.clone();
+            let stream_app = stream_app.clone();
+            async move {
+                match event {
+                    ChatStreamEvent::AnswerDelta(delta) => {
+                        let mut guard = assistant_accum.lock().await;
+                        guard.push_str(&delta);
+                        emit_event(
+                            &stream_app,
+                            &ChatStreamPayload {
+                                session_id,
+                                event: ChatStreamEventPayload::AnswerDelta { delta },
+                            },
+                        );
+                    }
+                    ChatStreamEvent::ThinkingDelta(delta) => {
+                        let mut guard = thinking_accum.lock().await;
+                        guard.push_str(&delta);
+                        emit_event(
+                            &stream_app,
+                            &ChatStreamPayload {
+                                session_id,
+                                event: ChatStreamEventPayload::ThinkingDelta { delta },
+                            },
+                        );
+                    }
+                    ChatStreamEvent::AnswerFullText(full_text) => {
+                        let mut guard = assistant_accum.lock().await;
+                        *guard = full_text;
+                    }
+                    ChatStreamEvent::ThinkingFullText(full_text) => {
+                        let mut guard = thinking_accum.lock().await;
+                        *guard = full_text;
+                    }
+                    ChatStreamEvent::Usage(usage) => {
+                        let mut guard = usage_tokens.lock().await;
+                        *guard = Some((
+                            usage.input_tokens,
+                            usage.output_tokens,
+                            usage.reasoning_tokens,
+                            usage.total_tokens,
+                        ));
+                        emit_event(
+                            &stream_app,
+                            &ChatStreamPayload {
+                                session_id,
+                                event: ChatStreamEventPayload::Usage { usage },
+                            },
+                        );
+                    }
+                    ChatStreamEvent::ToolCall(call) => {
+                        emit_event(
+                            &stream_app,
+                            &ChatStreamPayload {
+                                session_id,
+                                event: ChatStreamEventPayload::ToolCall {
+                                    name: call.name,
+                                    args: call.args,
+                                },
+                            },
+                        );
+                    }
+                    ChatStreamEvent::Error(message) => {
+                        emit_event(
+                            &stream_app,
+                            &ChatStreamPayload {
+                                session_id,
+                                event: ChatStreamEventPayload::Error { message },
+                            },
+                        );
+                        return Err("LLM stream error".to_string());
                     }
                 }
-            },
-        )
-        .await;
+                Ok(())
+            }
+        }
+    };
+
+    let stream_result = if request.use_tools.unwrap_or(false) {
+        let tools = VaultTools::new(
+            &state.db,
+            &ai,
+            session_id,
+            user_messages.iter().map(String::as_str),
+        );
+        ai.agent
+            .chat_with_tools(
+                &provider,
+                &model,
+                &provider_config,
+                &chat_messages,
+                &tools,
+                thinking_effort.as_deref(),
+                on_event,
+            )
+            .await
+    } else {
+        ai.llm
+            .stream_chat(
+                &provider,
+                &model,
+                &provider_config,
+                &chat_messages,
+                thinking_effort.as_deref(),
+                on_event,
+            )
+            .await
+    };
 
     if let Err(err) = stream_result {
-        let payload = serde_json::json!({
-            "session_id": session_id,
-            "type": "error",
-            "message": err,
-        });
-        let _ = app.emit("chat-stream", payload);
+        emit_event(
+            &app,
+            &ChatStreamPayload {
+                session_id,
+                event: ChatStreamEventPayload::Error { message: err },
+            },
+        );
         return Err("LLM stream failed".to_string());
     }
 
@@ -405,6 +548,24 @@ pub async fn send_chat_message(
     .await
     .map_err(|e| e.to_string())?;
 
+    if let Some((input_tokens, output_tokens, reasoning_tokens, total_tokens)) = usage_tokens {
+        let usage = ChatUsage {
+            input_tokens,
+            output_tokens,
+            reasoning_tokens,
+            total_tokens,
+        };
+        record_usage(
+            &state.db,
+            &provider,
+            &model,
+            UsageOperation::Chat,
+            &usage,
+            Some(session_id),
+        )
+        .await;
+    }
+
     if is_first_message {
         let assistant_text = final_assistant.as_deref().unwrap_or("").trim();
         let user_text = request.content.trim();
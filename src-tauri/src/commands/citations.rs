@@ -0,0 +1,151 @@
+//! 文献元数据相关命令
+
+use std::fs;
+
+use tauri::{AppHandle, State};
+
+use crate::{
+    app_state::AppState,
+    db::{
+        delete_citation, get_citation_by_node, get_node_by_id, list_citations, upsert_citation,
+        CitationRecord, CitationSource, NewCitation, ResourceSubtype,
+    },
+    error::AppError,
+    i18n::MessageCode,
+    services::{
+        build_bibtex_export, extract_and_store_citation, get_processing_config, BibtexExport,
+        BibtexScope,
+    },
+    simple_void_command,
+    utils::{resolve_file_path, validate_title},
+    AppResult,
+};
+
+use super::UpdateCitationRequest;
+
+simple_void_command!(remove_citation_command, delete_citation, node_id: i64);
+
+#[tauri::command]
+pub async fn get_citation(
+    state: State<'_, AppState>,
+    node_id: i64,
+) -> AppResult<Option<CitationRecord>> {
+    Ok(get_citation_by_node(&state.db, node_id).await?)
+}
+
+/// 文献库列表，topic_id 为空时返回全部
+#[tauri::command]
+pub async fn list_papers(
+    state: State<'_, AppState>,
+    topic_id: Option<i64>,
+) -> AppResult<Vec<CitationRecord>> {
+    Ok(list_citations(&state.db, topic_id).await?)
+}
+
+/// 手动编辑后标记为 manual，之后的自动抽取不再覆盖
+#[tauri::command]
+pub async fn update_citation(
+    state: State<'_, AppState>,
+    node_id: i64,
+    payload: UpdateCitationRequest,
+) -> AppResult<CitationRecord> {
+    match get_node_by_id(&state.db, node_id).await {
+        Ok(node) if !node.is_deleted => {}
+        Ok(_) | Err(sqlx::Error::RowNotFound) => {
+            return Err(AppError::NotFound {
+                entity: "node",
+                id: node_id,
+            })
+        }
+        Err(err) => return Err(err.into()),
+    }
+    let title = validate_title(&payload.title)?;
+    let authors: Vec<String> = payload
+        .authors
+        .iter()
+        .map(|author| author.trim().to_string())
+        .filter(|author| !author.is_empty())
+        .collect();
+    let trimmed = |value: &Option<String>| {
+        value
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+    };
+    let venue = trimmed(&payload.venue);
+    let doi = trimmed(&payload.doi);
+
+    upsert_citation(
+        &state.db,
+        NewCitation {
+            node_id,
+            title,
+            authors: &authors,
+            year: payload.year,
+            venue: venue.as_deref(),
+            doi: doi.as_deref(),
+            source: CitationSource::Manual,
+        },
+    )
+    .await?;
+
+    get_citation_by_node(&state.db, node_id)
+        .await?
+        .ok_or(AppError::NotFound {
+            entity: "citation",
+            id: node_id,
+        })
+}
+
+/// 立即对 PDF 资源重新抽取引用信息
+#[tauri::command]
+pub async fn extract_citation_command(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    node_id: i64,
+) -> AppResult<Option<CitationRecord>> {
+    let node = get_node_by_id(&state.db, node_id).await?;
+    if node.resource_subtype != Some(ResourceSubtype::Pdf) {
+        return Err(AppError::coded(MessageCode::PdfOnly));
+    }
+    let pdf_path = match node.file_path.as_deref() {
+        Some(path) => Some(resolve_file_path(&app, path)?),
+        None => None,
+    };
+
+    let ai = state.ai.wait_ready().await?;
+    let (provider, model, _, provider_config) = get_processing_config(&state.ai_config).await?;
+    Ok(extract_and_store_citation(
+        &state.db,
+        &ai,
+        &provider,
+        &model,
+        &provider_config,
+        node_id,
+        node.file_content.as_deref().unwrap_or(""),
+        pdf_path.as_deref(),
+    )
+    .await?)
+}
+
+/// 导出 BibTeX（topic_id 与 node_ids 二选一）；传入 output_path 时同时写入 .bib 文件
+#[tauri::command]
+pub async fn export_bibtex(
+    state: State<'_, AppState>,
+    topic_id: Option<i64>,
+    node_ids: Option<Vec<i64>>,
+    output_path: Option<String>,
+) -> AppResult<BibtexExport> {
+    let scope = match (topic_id, node_ids) {
+        (Some(topic_id), None) => BibtexScope::Topic(topic_id),
+        (None, Some(node_ids)) if !node_ids.is_empty() => BibtexScope::Nodes(node_ids),
+        _ => return Err(AppError::coded(MessageCode::BibtexScopeInvalid)),
+    };
+
+    let export = build_bibtex_export(&state.db, scope).await?;
+    if let Some(path) = output_path.as_deref() {
+        fs::write(path, &export.content)?;
+    }
+    Ok(export)
+}
@@ -1,14 +1,21 @@
-use clipboard_rs::{common::RustImage, Clipboard, ClipboardContext, ContentFormat};
+use clipboard_rs::{
+    common::{RustImage, RustImageData},
+    Clipboard, ClipboardContext, ContentFormat,
+};
 use tauri::AppHandle;
 use uuid::Uuid;
 
-use crate::utils::get_assets_dir;
+use crate::services::parser::preview_image_text;
+use crate::utils::{
+    extract_html_table, get_assets_dir, guess_code_language, looks_like_code, parse_tab_separated,
+};
 
-use super::{ClipboardContent, ReadClipboardResponse};
+use super::{ClipboardContent, ClipboardOcrPreview, ReadClipboardResponse};
 
 /// 读取系统剪贴板内容
 /// 
 /// 优先级：文件 > 图片 > HTML > 文本
+/// HTML 中含表格时返回 Table；纯文本为制表符分隔的表格或像代码时返回 Table / Code
 /// 注意：文件优先于图片，因为复制文件时 macOS 会同时放置预览图片
 #[tauri::command]
 pub fn read_clipboard(app: AppHandle) -> Result<ReadClipboardResponse, String> {
@@ -28,21 +35,7 @@ pub fn read_clipboard(app: AppHandle) -> Result<ReadClipboardResponse, String> {
     // 2. 检查图片（截图或复制的图片）
     if ctx.has(ContentFormat::Image) {
         if let Ok(img) = ctx.get_image() {
-            // 生成唯一文件名
-            let uuid = Uuid::new_v4().to_string();
-            let file_name = format!("{}.png", uuid);
-            
-            // 获取 assets 目录
-            let assets_dir = get_assets_dir(&app)?;
-            let target_path = assets_dir.join(&file_name);
-            
-            // 保存图片
-            img.save_to_path(target_path.to_str().unwrap_or_default())
-                .map_err(|e| format!("保存图片失败: {}", e))?;
-            
-            // 返回相对路径
-            let relative_path = format!("assets/{}", file_name);
-            
+            let (relative_path, file_name) = save_clipboard_image(&app, &img)?;
             return Ok(ReadClipboardResponse {
                 content: ClipboardContent::Image {
                     file_path: relative_path,
@@ -58,7 +51,13 @@ pub fn read_clipboard(app: AppHandle) -> Result<ReadClipboardResponse, String> {
             if !html.trim().is_empty() {
                 // 同时尝试获取纯文本版本
                 let plain_text = ctx.get_text().ok().filter(|t| !t.trim().is_empty());
-                
+
+                if let Some(rows) = extract_html_table(&html) {
+                    return Ok(ReadClipboardResponse {
+                        content: ClipboardContent::Table { rows, plain_text },
+                    });
+                }
+
                 return Ok(ReadClipboardResponse {
                     content: ClipboardContent::Html {
                         content: html,
@@ -74,7 +73,7 @@ pub fn read_clipboard(app: AppHandle) -> Result<ReadClipboardResponse, String> {
         if let Ok(text) = ctx.get_text() {
             if !text.trim().is_empty() {
                 return Ok(ReadClipboardResponse {
-                    content: ClipboardContent::Text { content: text },
+                    content: classify_plain_text(text),
                 });
             }
         }
@@ -85,3 +84,70 @@ pub fn read_clipboard(app: AppHandle) -> Result<ReadClipboardResponse, String> {
         content: ClipboardContent::Empty,
     })
 }
+
+/// 纯文本：制表符分隔的表格 > 代码 > 普通文本
+fn classify_plain_text(text: String) -> ClipboardContent {
+    if let Some(rows) = parse_tab_separated(&text) {
+        return ClipboardContent::Table {
+            rows,
+            plain_text: Some(text),
+        };
+    }
+    if looks_like_code(&text) {
+        let language = guess_code_language(&text).map(str::to_string);
+        return ClipboardContent::Code {
+            content: text,
+            language,
+        };
+    }
+    ClipboardContent::Text { content: text }
+}
+
+/// 剪贴板中的图片先保存到 assets 并快速 OCR，供 HUD 在捕获前预览和修改识别文字
+///
+/// 剪贴板中没有图片（或复制的是文件）时返回 None，前端改用 `read_clipboard`
+#[tauri::command]
+pub async fn preview_clipboard_ocr(app: AppHandle) -> Result<Option<ClipboardOcrPreview>, String> {
+    let saved = {
+        let ctx = ClipboardContext::new().map_err(|e| format!("无法访问剪贴板: {}", e))?;
+        if ctx.has(ContentFormat::Files) || !ctx.has(ContentFormat::Image) {
+            return Ok(None);
+        }
+        match ctx.get_image() {
+            Ok(img) => save_clipboard_image(&app, &img)?,
+            Err(_) => return Ok(None),
+        }
+    };
+    let (file_path, file_name) = saved;
+
+    let image_path = get_assets_dir(&app)?
+        .join(&file_name)
+        .to_string_lossy()
+        .to_string();
+    let ocr = tokio::task::spawn_blocking(move || preview_image_text(&image_path))
+        .await
+        .map_err(|e| e.to_string())?;
+    let (text, error) = match ocr {
+        Ok(text) => (text, None),
+        Err(err) => {
+            tracing::warn!(error = %err, "Clipboard OCR preview failed");
+            (String::new(), Some(err))
+        }
+    };
+
+    Ok(Some(ClipboardOcrPreview {
+        file_path,
+        file_name,
+        text,
+        error,
+    }))
+}
+
+/// 保存剪贴板图片到 assets，返回（相对路径, 文件名）
+fn save_clipboard_image(app: &AppHandle, img: &RustImageData) -> Result<(String, String), String> {
+    let file_name = format!("{}.png", Uuid::new_v4());
+    let target_path = get_assets_dir(app)?.join(&file_name);
+    img.save_to_path(target_path.to_str().unwrap_or_default())
+        .map_err(|e| format!("保存图片失败: {}", e))?;
+    Ok((format!("assets/{}", file_name), file_name))
+}
@@ -1,18 +1,53 @@
+use std::collections::HashSet;
+
 use tauri::State;
 
 use crate::{
     app_state::AppState,
-    db::{list_active_tasks, list_all_resources},
+    db::{
+        list_active_tasks, list_all_resources, list_node_ids_with_all_tags,
+        list_recently_opened_nodes, sum_focus_seconds_today,
+    },
+    services::list_habit_overviews,
 };
 
 use super::{DashboardData};
 
+/// 首页展示的最近打开节点数
+const DASHBOARD_RECENT_LIMIT: i32 = 8;
+
+/// 传入 tag_ids 时，任务与资源只保留同时带有这些标签的节点
 #[tauri::command]
-pub async fn get_dashboard(state: State<'_, AppState>) -> Result<DashboardData, String> {
+pub async fn get_dashboard(
+    state: State<'_, AppState>,
+    tag_ids: Option<Vec<i64>>,
+) -> Result<DashboardData, String> {
     let pool = &state.db;
-    let tasks = list_active_tasks(pool).await.map_err(|e| e.to_string())?;
-    let resources = list_all_resources(pool)
+    let mut tasks = list_active_tasks(pool).await.map_err(|e| e.to_string())?;
+    let mut resources = list_all_resources(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    if let Some(tag_ids) = tag_ids.filter(|ids| !ids.is_empty()) {
+        let tagged: HashSet<i64> = list_node_ids_with_all_tags(pool, &tag_ids)
+            .await
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .collect();
+        tasks.retain(|task| tagged.contains(&task.node_id));
+        resources.retain(|resource| tagged.contains(&resource.node_id));
+    }
+    let focus_seconds_today = sum_focus_seconds_today(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    let habits = list_habit_overviews(pool).await?;
+    let recent = list_recently_opened_nodes(pool, DASHBOARD_RECENT_LIMIT)
         .await
         .map_err(|e| e.to_string())?;
-    Ok(DashboardData { tasks, resources })
+    Ok(DashboardData {
+        tasks,
+        resources,
+        focus_seconds_today,
+        habits,
+        recent,
+    })
 }
\ No newline at end of file
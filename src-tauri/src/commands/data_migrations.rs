@@ -0,0 +1,14 @@
+//! 后台数据迁移命令
+
+use tauri::State;
+
+use crate::db::{list_data_migrations, DataMigrationRecord};
+use crate::{AppResult, AppState};
+
+/// 后台数据迁移的进度（尚未开始执行的迁移不在列表中）
+#[tauri::command]
+pub async fn list_data_migrations_command(
+    state: State<'_, AppState>,
+) -> AppResult<Vec<DataMigrationRecord>> {
+    Ok(list_data_migrations(&state.db).await?)
+}
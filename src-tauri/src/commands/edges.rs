@@ -1,17 +1,22 @@
+use std::fs;
+
 use serde::Serialize;
 use tauri::State;
 
 use crate::{
     app_state::AppState,
     db::{
-        confirm_edge, contains_creates_cycle, delete_edge, get_node_by_id, insert_edge,
-        list_all_edges, list_edges_to, list_source_nodes, list_target_nodes, EdgeRecord,
-        EdgeRelationType, NewEdge, NodeRecord,
+        confirm_edge, contains_creates_cycle, count_graph_nodes, delete_edge, get_node_by_id,
+        insert_edge, list_all_edges, list_backlinks, list_edges_to, list_graph_edges, list_graph_nodes,
+        list_source_nodes, list_target_nodes, resolve_node_ref, BacklinkRecord, EdgeRecord,
+        EdgeRelationType, GraphRelationType, GraphScope, NewEdge, NodeRecord, NodeType,
     },
+    services::{build_edge_export, import_edges_jsonl, EdgeImportReport},
+    utils::validate_limit,
     AppResult,
 };
 
-use super::{LinkNodesRequest, LinkNodesResponse};
+use super::{EdgeExportFilter, GraphRequest, GraphResponse, LinkNodesRequest, LinkNodesResponse};
 use super::types::NodeListResponse;
 
 #[derive(Debug, Serialize)]
@@ -24,6 +29,7 @@ fn parse_relation_type(raw: &str) -> Result<EdgeRelationType, String> {
     match raw {
         "contains" => Ok(EdgeRelationType::Contains),
         "related_to" => Ok(EdgeRelationType::RelatedTo),
+        "derived_from" => Ok(EdgeRelationType::DerivedFrom),
         _ => Err(format!("Unknown relation_type: {raw}")),
     }
 }
@@ -146,3 +152,123 @@ pub async fn list_all_edges_command(
     let edges = list_all_edges(&state.db).await?;
     Ok(edges)
 }
+
+/// 由该节点派生出的产物（翻译、转写、保存的回答等）
+#[tauri::command]
+pub async fn get_derivatives(
+    state: State<'_, AppState>,
+    node_id: i64,
+) -> AppResult<NodeListResponse> {
+    let nodes = list_source_nodes(&state.db, node_id, EdgeRelationType::DerivedFrom).await?;
+    Ok(NodeListResponse { nodes })
+}
+
+/// 派生产物的来源节点
+#[tauri::command]
+pub async fn get_derivation_sources(
+    state: State<'_, AppState>,
+    node_id: i64,
+) -> AppResult<NodeListResponse> {
+    let nodes = list_target_nodes(&state.db, node_id, EdgeRelationType::DerivedFrom).await?;
+    Ok(NodeListResponse { nodes })
+}
+
+/// 正文中引用了该节点的节点（uuid 或 [[标题]]）
+#[tauri::command]
+pub async fn get_backlinks(
+    state: State<'_, AppState>,
+    node_id: i64,
+) -> AppResult<Vec<BacklinkRecord>> {
+    Ok(list_backlinks(&state.db, node_id).await?)
+}
+
+/// 图谱视图：一次返回一页节点（含度数）及相关的边
+///
+/// 给出 root_node_id 时只返回其 depth 步内的邻域（沿边与正文引用双向走，只经过所选类型的节点）。
+/// 逐页累加 nodes / edges 即得到完整子图。
+#[tauri::command]
+pub async fn get_graph(
+    state: State<'_, AppState>,
+    request: Option<GraphRequest>,
+) -> AppResult<GraphResponse> {
+    let request = request.unwrap_or_default();
+    let node_types = request
+        .node_types
+        .filter(|types| !types.is_empty())
+        .unwrap_or_else(|| vec![NodeType::Topic, NodeType::Resource, NodeType::Task]);
+    let relation_types = request
+        .relation_types
+        .filter(|types| !types.is_empty())
+        .unwrap_or_else(|| {
+            vec![
+                GraphRelationType::Contains,
+                GraphRelationType::RelatedTo,
+                GraphRelationType::DerivedFrom,
+                GraphRelationType::References,
+            ]
+        });
+    let depth = request.depth.unwrap_or(2).clamp(1, 3);
+    let limit = validate_limit(request.limit, 200, 1000) as i64;
+    let offset = request.offset.unwrap_or(0).max(0);
+
+    let scope = GraphScope {
+        root: request.root_node_id.map(|root| (root, depth)),
+        node_types: &node_types,
+        relation_types: &relation_types,
+    };
+    let total = count_graph_nodes(&state.db, &scope).await?;
+    let nodes = list_graph_nodes(&state.db, &scope, limit, offset).await?;
+    let edges = list_graph_edges(&state.db, &scope, limit, offset).await?;
+
+    Ok(GraphResponse {
+        nodes,
+        edges,
+        total,
+        has_more: offset + limit < total,
+    })
+}
+
+/// 导出边为 JSONL 文件（节点以 uuid 引用），返回导出的边数
+#[tauri::command]
+pub async fn export_edges(
+    state: State<'_, AppState>,
+    path: String,
+    filter: Option<EdgeExportFilter>,
+) -> AppResult<usize> {
+    let filter = filter.unwrap_or_default();
+    let relation_types = filter
+        .relation_types
+        .filter(|types| !types.is_empty())
+        .unwrap_or_else(|| {
+            vec![
+                EdgeRelationType::Contains,
+                EdgeRelationType::RelatedTo,
+                EdgeRelationType::DerivedFrom,
+            ]
+        });
+    let mut node_ids = Vec::new();
+    for node in filter.node_ids.unwrap_or_default() {
+        node_ids.push(resolve_node_ref(&state.db, &node).await?);
+    }
+
+    let (text, count) =
+        build_edge_export(&state.db, &relation_types, filter.manual_only, &node_ids).await?;
+    fs::write(&path, text)?;
+    tracing::info!(path = %path, count, "Edges exported");
+    Ok(count)
+}
+
+/// 从 JSONL 文件导入边，返回逐行的创建 / 跳过报告
+///
+/// 校验关系类型、两端节点是否存在、自环与 contains 环；已有的边跳过。
+/// dry_run 时只报告将会创建 / 跳过的边，不写入数据库
+#[tauri::command]
+pub async fn import_edges(
+    state: State<'_, AppState>,
+    path: String,
+    dry_run: Option<bool>,
+) -> AppResult<EdgeImportReport> {
+    let text = fs::read_to_string(&path)?;
+    let report = import_edges_jsonl(&state.db, &text, dry_run.unwrap_or(false)).await?;
+    Ok(report)
+}
@@ -0,0 +1,7 @@
+use crate::services::{event_schema, EventSchema};
+
+/// 获取后端事件的名称与版本，供前端做兼容判断
+#[tauri::command]
+pub fn get_event_schema() -> EventSchema {
+    event_schema()
+}
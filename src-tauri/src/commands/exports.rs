@@ -0,0 +1,123 @@
+//! 导出与定期自动导出命令
+
+use std::path::{Path, PathBuf};
+
+use tauri::{AppHandle, State};
+
+use crate::db::{self, ExportFormat, ExportRunRecord, ExportTrigger};
+use crate::i18n::MessageCode;
+use crate::services::{export_vault_folder, run_export, ExportScheduleConfig, JobKind};
+use crate::utils::validate_limit;
+use crate::{AppError, AppResult, AppState};
+
+/// 自动导出间隔上限（30 天）
+const MAX_INTERVAL_HOURS: u32 = 30 * 24;
+const MAX_KEEP: u32 = 100;
+
+#[tauri::command]
+pub async fn get_export_schedule(state: State<'_, AppState>) -> AppResult<ExportScheduleConfig> {
+    Ok(state.ai_config.lock().await.load()?.export_schedule)
+}
+
+/// 保存自动导出设置；启用时导出文件夹必须是已存在的绝对路径
+///
+/// 间隔限制在 1 小时到 30 天，保留数限制在 1–100
+#[tauri::command]
+pub async fn set_export_schedule(
+    state: State<'_, AppState>,
+    mut schedule: ExportScheduleConfig,
+) -> AppResult<ExportScheduleConfig> {
+    schedule.folder = schedule
+        .folder
+        .as_deref()
+        .map(str::trim)
+        .filter(|folder| !folder.is_empty())
+        .map(str::to_string);
+    if schedule.enabled {
+        let folder = schedule.folder.as_deref().ok_or_else(|| {
+            AppError::coded_with(
+                MessageCode::InvalidExportFolder,
+                "detail",
+                "未设置导出文件夹",
+            )
+        })?;
+        let folder = Path::new(folder);
+        if !folder.is_absolute() || !folder.is_dir() {
+            return Err(AppError::coded_with(
+                MessageCode::InvalidExportFolder,
+                "detail",
+                format!("{} 不是文件夹", folder.display()),
+            ));
+        }
+    }
+    schedule.interval_hours = schedule.interval_hours.clamp(1, MAX_INTERVAL_HOURS);
+    schedule.keep = schedule.keep.clamp(1, MAX_KEEP);
+
+    state
+        .ai_config
+        .lock()
+        .await
+        .set_export_schedule(schedule.clone())?;
+    Ok(schedule)
+}
+
+/// 立即按设置导出一次，返回任务 ID，导出记录见任务结果；format / incremental 给出时覆盖设置中的值
+#[tauri::command]
+pub async fn run_export_now(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    format: Option<ExportFormat>,
+    incremental: Option<bool>,
+) -> AppResult<i64> {
+    let mut config = state.ai_config.lock().await.load()?.export_schedule;
+    if let Some(format) = format {
+        config.format = format;
+    }
+    if let Some(incremental) = incremental {
+        config.incremental = incremental;
+    }
+
+    let state = state.inner().clone();
+    let jobs = state.jobs.clone();
+    let job_app = app.clone();
+    let job_id = jobs.spawn(&app, JobKind::Export, move |job| async move {
+        run_export(&job_app, &state, &config, ExportTrigger::Manual, Some(&job)).await
+    });
+    Ok(job_id)
+}
+
+/// 把整个库导出为 Markdown 文件夹（front matter 带 id、标签与链接，附资源文件与 edges.jsonl），
+/// 写入 folder 下新建的子文件夹；返回任务 ID，导出结果见任务结果
+#[tauri::command]
+pub async fn export_vault(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    folder: String,
+) -> AppResult<i64> {
+    let folder = PathBuf::from(folder.trim());
+    if !folder.is_absolute() || !folder.is_dir() {
+        return Err(AppError::coded_with(
+            MessageCode::InvalidExportFolder,
+            "detail",
+            format!("{} 不是文件夹", folder.display()),
+        ));
+    }
+
+    let state = state.inner().clone();
+    let jobs = state.jobs.clone();
+    let job_app = app.clone();
+    let job_id = jobs.spawn(&app, JobKind::VaultExport, move |job| async move {
+        export_vault_folder(&job_app, &state, &folder, Some(&job)).await
+    });
+    Ok(job_id)
+}
+
+/// 最近的导出记录（手动与定期），新的在前
+#[tauri::command]
+pub async fn list_export_runs(
+    state: State<'_, AppState>,
+    limit: Option<i32>,
+) -> AppResult<Vec<ExportRunRecord>> {
+    let limit = validate_limit(limit, 20, 50);
+    Ok(db::list_recent_export_runs(&state.db, i64::from(limit)).await?)
+}
@@ -0,0 +1,35 @@
+//! 外部文件引用命令
+
+use tauri::{AppHandle, State};
+
+use crate::db::{get_file_reference, list_file_references, FileReferenceRecord};
+use crate::services::relink_file_reference;
+use crate::{AppResult, AppState};
+
+/// 资源的引用状态；以复制方式捕获的资源返回 None
+#[tauri::command]
+pub async fn get_file_reference_status(
+    state: State<'_, AppState>,
+    node_id: i64,
+) -> AppResult<Option<FileReferenceRecord>> {
+    Ok(get_file_reference(&state.db, node_id).await?)
+}
+
+/// 所有未删除资源的引用状态
+#[tauri::command]
+pub async fn list_file_reference_statuses(
+    state: State<'_, AppState>,
+) -> AppResult<Vec<FileReferenceRecord>> {
+    Ok(list_file_references(&state.db).await?)
+}
+
+/// 为离线或已移动的引用重新指定文件；内容变化时按新内容重新解析入队
+#[tauri::command]
+pub async fn relink_referenced_file(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    node_id: i64,
+    new_path: String,
+) -> AppResult<FileReferenceRecord> {
+    relink_file_reference(&app, &state, node_id, &new_path).await
+}
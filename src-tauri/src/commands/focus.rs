@@ -0,0 +1,74 @@
+//! 专注模式命令
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::db::{
+    self, list_focus_daily_stats, list_focus_task_stats, FocusDailyStat, FocusSessionRecord,
+    FocusTaskStat,
+};
+use crate::services::FocusState;
+use crate::utils::validate_limit;
+use crate::{AppResult, AppState};
+
+/// 专注时长统计
+#[derive(Debug, Serialize)]
+pub struct FocusStatsResponse {
+    pub total_seconds: i64,
+    pub daily: Vec<FocusDailyStat>,
+    pub tasks: Vec<FocusTaskStat>,
+}
+
+/// 开始专注（duration 单位：分钟）
+#[tauri::command]
+pub async fn start_focus_session(
+    state: State<'_, AppState>,
+    task_id: Option<i64>,
+    duration: i64,
+    do_not_disturb: Option<bool>,
+) -> AppResult<FocusState> {
+    Ok(state
+        .focus
+        .start(task_id, duration, do_not_disturb.unwrap_or(false))
+        .await?)
+}
+
+/// 结束当前专注；没有进行中的会话时返回 None
+#[tauri::command]
+pub async fn end_focus_session(
+    state: State<'_, AppState>,
+) -> AppResult<Option<FocusSessionRecord>> {
+    Ok(state.focus.end().await?)
+}
+
+#[tauri::command]
+pub fn get_current_focus_session(state: State<'_, AppState>) -> Option<FocusState> {
+    state.focus.current()
+}
+
+#[tauri::command]
+pub async fn list_focus_sessions(
+    state: State<'_, AppState>,
+    task_id: Option<i64>,
+    limit: Option<i32>,
+) -> AppResult<Vec<FocusSessionRecord>> {
+    let limit = validate_limit(limit, 50, 500);
+    Ok(db::list_focus_sessions(&state.db, task_id, limit as i64).await?)
+}
+
+/// 按日 / 按任务统计专注时长（日期格式 YYYY-MM-DD，闭区间）
+#[tauri::command]
+pub async fn get_focus_stats(
+    state: State<'_, AppState>,
+    start_date: String,
+    end_date: String,
+) -> AppResult<FocusStatsResponse> {
+    let daily = list_focus_daily_stats(&state.db, &start_date, &end_date).await?;
+    let tasks = list_focus_task_stats(&state.db, &start_date, &end_date).await?;
+    let total_seconds = daily.iter().map(|d| d.focus_seconds).sum();
+    Ok(FocusStatsResponse {
+        total_seconds,
+        daily,
+        tasks,
+    })
+}
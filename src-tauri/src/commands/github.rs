@@ -0,0 +1,92 @@
+//! GitHub issues / PR 同步命令
+
+use tauri::State;
+
+use crate::db::{self, GithubRepoRecord, NodeType};
+use crate::i18n::MessageCode;
+use crate::services::{self, parse_repo_slug, GithubSyncSummary};
+use crate::utils::crypto::CryptoService;
+use crate::{AppError, AppResult, AppState};
+
+const DEFAULT_POLL_INTERVAL_MINUTES: i64 = 30;
+const MIN_POLL_INTERVAL_MINUTES: i64 = 5;
+
+/// 添加同步仓库并立即同步一次；同步失败记录在仓库的 last_error 中，不影响添加
+///
+/// repo 可以是 `owner/name` 或仓库 URL；token 可选（公开仓库可不填），加密后保存。
+/// 轮询间隔默认 30 分钟，最短 5 分钟
+#[tauri::command]
+pub async fn add_github_repo(
+    state: State<'_, AppState>,
+    repo: String,
+    topic_id: i64,
+    token: Option<String>,
+    poll_interval_minutes: Option<i64>,
+) -> AppResult<GithubRepoRecord> {
+    let (owner, name) =
+        parse_repo_slug(&repo).ok_or_else(|| AppError::coded(MessageCode::InvalidGithubRepo))?;
+    let is_topic = db::get_node_by_id(&state.db, topic_id)
+        .await
+        .is_ok_and(|node| node.node_type == NodeType::Topic && !node.is_deleted);
+    if !is_topic {
+        return Err(AppError::NotFound {
+            entity: "topic",
+            id: topic_id,
+        });
+    }
+    let token_encrypted = match token.as_deref().map(str::trim) {
+        Some(token) if !token.is_empty() => Some(CryptoService::new()?.encrypt(token.as_bytes())?),
+        _ => None,
+    };
+    let interval = poll_interval_minutes
+        .unwrap_or(DEFAULT_POLL_INTERVAL_MINUTES)
+        .max(MIN_POLL_INTERVAL_MINUTES);
+
+    let repo_id = db::insert_github_repo(
+        &state.db,
+        &owner,
+        &name,
+        topic_id,
+        token_encrypted.as_deref(),
+        interval,
+    )
+    .await?;
+    let record = fetch_repo(&state, repo_id).await?;
+    if let Err(err) = services::sync_github_repo(&state.db, &state.ai_pipeline, &record).await {
+        tracing::warn!(repo_id, error = %err, "Initial GitHub sync failed");
+    }
+    fetch_repo(&state, repo_id).await
+}
+
+#[tauri::command]
+pub async fn list_github_repos(state: State<'_, AppState>) -> AppResult<Vec<GithubRepoRecord>> {
+    Ok(db::list_github_repos(&state.db).await?)
+}
+
+/// 删除同步仓库；已同步的任务与正文保留
+#[tauri::command]
+pub async fn remove_github_repo(state: State<'_, AppState>, id: i64) -> AppResult<()> {
+    if !db::delete_github_repo(&state.db, id).await? {
+        return Err(AppError::NotFound {
+            entity: "github_repo",
+            id,
+        });
+    }
+    Ok(())
+}
+
+/// 立即同步一个仓库
+#[tauri::command]
+pub async fn sync_github_repo(state: State<'_, AppState>, id: i64) -> AppResult<GithubSyncSummary> {
+    let record = fetch_repo(&state, id).await?;
+    Ok(services::sync_github_repo(&state.db, &state.ai_pipeline, &record).await?)
+}
+
+async fn fetch_repo(state: &AppState, repo_id: i64) -> AppResult<GithubRepoRecord> {
+    db::get_github_repo(&state.db, repo_id)
+        .await?
+        .ok_or(AppError::NotFound {
+            entity: "github_repo",
+            id: repo_id,
+        })
+}
@@ -0,0 +1,32 @@
+//! 月度回顾相关命令
+
+use chrono::Local;
+use tauri::{AppHandle, State};
+
+use crate::{
+    app_state::AppState,
+    db::{list_goal_reviews, GoalReviewRecord},
+    services::{generate_goal_review, GoalReviewResult, ReviewPeriod},
+    AppResult,
+};
+
+/// 立即生成回顾（period 为 YYYY-MM，默认上个月）；同一周期会覆盖之前的记录
+#[tauri::command]
+pub async fn generate_goal_review_command(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    period: Option<String>,
+) -> AppResult<GoalReviewResult> {
+    let period = match period.as_deref() {
+        Some(raw) => ReviewPeriod::parse(raw)?,
+        None => ReviewPeriod::previous_month(Local::now().date_naive()),
+    };
+    Ok(generate_goal_review(&state, &app, &period).await?)
+}
+
+#[tauri::command]
+pub async fn list_goal_reviews_command(
+    state: State<'_, AppState>,
+) -> AppResult<Vec<GoalReviewRecord>> {
+    Ok(list_goal_reviews(&state.db).await?)
+}
@@ -0,0 +1,109 @@
+//! 习惯相关命令
+
+use chrono::{Local, NaiveDate};
+use tauri::State;
+
+use crate::{
+    app_state::AppState,
+    db::{
+        delete_habit_log, get_habit_by_id, insert_habit_log, insert_habit_schedule,
+        soft_delete_node, update_habit_schedule, HabitFrequency, NodeBuilder,
+    },
+    error::AppError,
+    i18n::MessageCode,
+    services::{build_habit_overview, list_habit_overviews, HabitOverview},
+    simple_void_command,
+    utils::validate_title,
+    AppResult,
+};
+
+use super::CreateHabitRequest;
+
+simple_void_command!(soft_delete_habit_command, soft_delete_node, node_id: i64);
+
+fn normalize_target(frequency: HabitFrequency, target_count: Option<i64>) -> AppResult<i64> {
+    match frequency {
+        HabitFrequency::Daily => Ok(1),
+        HabitFrequency::Weekly => {
+            let target = target_count.unwrap_or(1);
+            if !(1..=7).contains(&target) {
+                return Err(AppError::coded(MessageCode::WeeklyTargetOutOfRange));
+            }
+            Ok(target)
+        }
+    }
+}
+
+/// 解析打卡日期，未传时使用本地今天
+fn resolve_log_date(date: Option<&str>) -> AppResult<String> {
+    match date {
+        Some(raw) => NaiveDate::parse_from_str(raw.trim(), "%Y-%m-%d")
+            .map(|d| d.format("%Y-%m-%d").to_string())
+            .map_err(|_| AppError::coded_with(MessageCode::InvalidDate, "value", raw)),
+        None => Ok(Local::now().date_naive().format("%Y-%m-%d").to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn create_habit(
+    state: State<'_, AppState>,
+    payload: CreateHabitRequest,
+) -> AppResult<HabitOverview> {
+    let title = validate_title(&payload.title)?;
+    let frequency = payload.frequency.unwrap_or(HabitFrequency::Daily);
+    let target_count = normalize_target(frequency, payload.target_count)?;
+
+    let node_id = NodeBuilder::habit()
+        .title(title)
+        .summary(payload.summary.as_deref())
+        .insert(&state.db)
+        .await?;
+    insert_habit_schedule(&state.db, node_id, frequency, target_count).await?;
+
+    let habit = get_habit_by_id(&state.db, node_id).await?;
+    Ok(build_habit_overview(&state.db, habit).await?)
+}
+
+#[tauri::command]
+pub async fn list_habits_command(state: State<'_, AppState>) -> AppResult<Vec<HabitOverview>> {
+    Ok(list_habit_overviews(&state.db).await?)
+}
+
+#[tauri::command]
+pub async fn update_habit_schedule_command(
+    state: State<'_, AppState>,
+    node_id: i64,
+    frequency: HabitFrequency,
+    target_count: Option<i64>,
+) -> AppResult<HabitOverview> {
+    let target_count = normalize_target(frequency, target_count)?;
+    update_habit_schedule(&state.db, node_id, frequency, target_count).await?;
+    let habit = get_habit_by_id(&state.db, node_id).await?;
+    Ok(build_habit_overview(&state.db, habit).await?)
+}
+
+/// 打卡（date 格式 YYYY-MM-DD，默认今天），返回最新的连续达成信息
+#[tauri::command]
+pub async fn log_habit(
+    state: State<'_, AppState>,
+    node_id: i64,
+    date: Option<String>,
+) -> AppResult<HabitOverview> {
+    let habit = get_habit_by_id(&state.db, node_id).await?;
+    let log_date = resolve_log_date(date.as_deref())?;
+    insert_habit_log(&state.db, node_id, &log_date).await?;
+    Ok(build_habit_overview(&state.db, habit).await?)
+}
+
+/// 撤销打卡
+#[tauri::command]
+pub async fn unlog_habit(
+    state: State<'_, AppState>,
+    node_id: i64,
+    date: Option<String>,
+) -> AppResult<HabitOverview> {
+    let habit = get_habit_by_id(&state.db, node_id).await?;
+    let log_date = resolve_log_date(date.as_deref())?;
+    delete_habit_log(&state.db, node_id, &log_date).await?;
+    Ok(build_habit_overview(&state.db, habit).await?)
+}
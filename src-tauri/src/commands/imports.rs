@@ -0,0 +1,452 @@
+//! 外部笔记导入命令
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use tauri::{AppHandle, State};
+
+use crate::{
+    app_state::AppState,
+    db::{
+        attach_tag_to_node, find_resource_by_hash, get_node_by_title, get_or_create_tag,
+        insert_edge_if_missing, refresh_node_links_with, set_node_timestamps, update_node_content,
+        update_resource_sync_status, DbPool, EdgeRelationType, NewEdge, NodeBuilder, NodeType,
+        ResourceEmbeddingStatus, ResourceSubtype, SourceMeta, TagSource,
+    },
+    error::AppError,
+    i18n::MessageCode,
+    services::{
+        collect_markdown_files, file_modified_at, link_key, parse_enex, parse_markdown_note,
+        parser::{build_text_title, parse_resource_content},
+        EnexAttachment, EnexNote, JobHandle, JobKind, JobPriority, MarkdownNote,
+    },
+    utils::{
+        compute_sha256, get_assets_dir, html_to_text, parse_file_type_from_extension,
+        resolve_file_path,
+    },
+    AppResult,
+};
+
+use super::{EnexImportSummary, MarkdownImportSummary};
+
+/// 导入 Evernote 导出的 .enex 文件，解析成功后立即返回任务 ID，导入结果见任务状态
+///
+/// 每条笔记成为一个文本资源，附件写入 assets 并作为独立资源与笔记建立 related_to 关系；
+/// 保留创建/更新时间与标签。内容哈希已存在的笔记和附件会跳过。
+#[tauri::command]
+pub async fn import_enex(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    path: String,
+) -> AppResult<i64> {
+    let xml = fs::read_to_string(&path)?;
+    let notes = parse_enex(&xml)
+        .map_err(|e| AppError::coded_with(MessageCode::InvalidEnex, "detail", e))?;
+
+    let state = state.inner().clone();
+    let jobs = state.jobs.clone();
+    let job_app = app.clone();
+    let job_id = jobs.spawn(&app, JobKind::ImportEnex, move |job| async move {
+        import_enex_notes(&job_app, &state, &path, notes, &job).await
+    });
+    Ok(job_id)
+}
+
+/// 逐条导入笔记；取消时停在当前笔记之前，已导入的笔记保留并照常加入 AI 队列
+async fn import_enex_notes(
+    app: &AppHandle,
+    state: &AppState,
+    path: &str,
+    notes: Vec<EnexNote>,
+    job: &JobHandle,
+) -> AppResult<EnexImportSummary> {
+    let mut summary = EnexImportSummary::default();
+    let mut to_enqueue = Vec::new();
+    let total = notes.len();
+    let mut cancelled = false;
+
+    for (index, note) in notes.into_iter().enumerate() {
+        if job.is_cancelled() {
+            cancelled = true;
+            break;
+        }
+        job.progress(index, Some(total));
+        let text = html_to_text(&note.content);
+        let title = match note.title.trim() {
+            "" => build_text_title(&text),
+            title => title.to_string(),
+        };
+        let hash_source = if text.is_empty() { &title } else { &text };
+        let file_hash = compute_sha256(hash_source.as_bytes());
+        if find_resource_by_hash(&state.db, &file_hash)
+            .await?
+            .is_some()
+        {
+            summary.skipped_duplicates += 1;
+            continue;
+        }
+
+        let meta = SourceMeta {
+            url: note.source_url.clone(),
+            window_title: None,
+            process_name: Some("Evernote".to_string()),
+            captured_at: note.created_at.clone(),
+            process_path: None,
+            sender: None,
+            sent_at: None,
+        };
+        let node_id = NodeBuilder::resource()
+            .title(&title)
+            .file_hash(Some(&file_hash))
+            .file_content((!text.is_empty()).then_some(text.as_str()))
+            .resource_subtype(Some(ResourceSubtype::Text))
+            .source_meta(Some(meta))
+            .insert(&state.db)
+            .await?;
+        set_node_timestamps(
+            &state.db,
+            node_id,
+            note.created_at.as_deref(),
+            note.updated_at.as_deref(),
+        )
+        .await?;
+        if !text.is_empty() {
+            to_enqueue.push(node_id);
+        }
+
+        for tag in &note.tags {
+            let tag_id = get_or_create_tag(&state.db, tag).await?;
+            attach_tag_to_node(&state.db, node_id, tag_id, TagSource::Manual).await?;
+        }
+
+        for (index, attachment) in note.attachments.iter().enumerate() {
+            let attachment_hash = compute_sha256(&attachment.data);
+            let attachment_id = match find_resource_by_hash(&state.db, &attachment_hash).await? {
+                Some(existing_id) => {
+                    summary.skipped_duplicates += 1;
+                    existing_id
+                }
+                None => {
+                    let fallback_title = format!("{title} - 附件 {}", index + 1);
+                    let attachment_id = import_attachment(
+                        app,
+                        &state.db,
+                        &note,
+                        attachment,
+                        &attachment_hash,
+                        &fallback_title,
+                        &mut to_enqueue,
+                    )
+                    .await?;
+                    summary.imported_attachments += 1;
+                    attachment_id
+                }
+            };
+            insert_edge_if_missing(
+                &state.db,
+                NewEdge {
+                    source_node_id: node_id.min(attachment_id),
+                    target_node_id: node_id.max(attachment_id),
+                    relation_type: EdgeRelationType::RelatedTo,
+                    confidence_score: None,
+                    is_manual: true,
+                },
+            )
+            .await?;
+        }
+
+        summary.imported_notes += 1;
+        summary.node_ids.push(node_id);
+    }
+
+    for node_id in to_enqueue {
+        if let Err(err) = state
+            .ai_pipeline
+            .enqueue_resource(node_id, JobPriority::Bulk)
+            .await
+        {
+            tracing::warn!(node_id, error = %err, "Enqueue imported resource failed");
+        }
+    }
+
+    tracing::info!(
+        path = %path,
+        notes = summary.imported_notes,
+        attachments = summary.imported_attachments,
+        skipped = summary.skipped_duplicates,
+        cancelled,
+        "ENEX imported"
+    );
+    if cancelled {
+        return Err(AppError::coded(MessageCode::JobCancelled));
+    }
+    job.progress(total, Some(total));
+    Ok(summary)
+}
+
+/// 将附件写入 assets 并创建资源节点，解析出的文本待入 AI 队列
+async fn import_attachment(
+    app: &AppHandle,
+    db: &DbPool,
+    note: &EnexNote,
+    attachment: &EnexAttachment,
+    file_hash: &str,
+    fallback_title: &str,
+    to_enqueue: &mut Vec<i64>,
+) -> AppResult<i64> {
+    let builder = NodeBuilder::resource();
+    let ext = attachment.extension();
+    let file_name = match &ext {
+        Some(ext) => format!("{}.{}", builder.get_uuid(), ext),
+        None => builder.get_uuid().to_string(),
+    };
+    fs::write(get_assets_dir(app)?.join(&file_name), &attachment.data)?;
+    let stored_path = format!("assets/{file_name}");
+
+    let subtype = parse_file_type_from_extension(ext.as_deref());
+    let title = attachment
+        .file_name
+        .as_deref()
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or(fallback_title);
+    let node_id = builder
+        .title(title)
+        .file_hash(Some(file_hash))
+        .file_path(Some(&stored_path))
+        .resource_subtype(Some(subtype))
+        .insert(db)
+        .await?;
+
+    let resolved_path = resolve_file_path(app, &stored_path)?;
+    match parse_resource_content(subtype, None, Some(&resolved_path), None) {
+        Ok(Some(content)) => {
+            update_node_content(db, node_id, Some(&content), Some(file_hash)).await?;
+            if !content.trim().is_empty() {
+                to_enqueue.push(node_id);
+            }
+        }
+        // 本地无法转写的音频交给 AI 管线转写
+        Ok(None) if subtype == ResourceSubtype::Audio => to_enqueue.push(node_id),
+        Ok(None) => {}
+        Err(err) => {
+            update_resource_sync_status(
+                db,
+                node_id,
+                ResourceEmbeddingStatus::Error,
+                None,
+                Some(&err),
+            )
+            .await?;
+        }
+    }
+    set_node_timestamps(
+        db,
+        node_id,
+        note.created_at.as_deref(),
+        note.updated_at.as_deref(),
+    )
+    .await?;
+
+    Ok(node_id)
+}
+
+fn invalid_import_folder(detail: impl ToString) -> AppError {
+    AppError::coded_with(MessageCode::InvalidImportFolder, "detail", detail)
+}
+
+/// 导入 Markdown 文件夹（如 Obsidian 仓库），收集到文件后立即返回任务 ID，导入结果见任务状态
+///
+/// 每个 .md 文件成为一个文本资源，front matter 中的标签作为手动标签导入；正文中的 `[[链接]]`
+/// 先按本次导入的文件名与标题解析，找不到时匹配标题相同的已有资源，写入引用索引（反向链接）。
+/// 内容哈希已存在的笔记跳过，但仍可作为链接目标。
+#[tauri::command]
+pub async fn import_markdown_folder(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    folder: String,
+) -> AppResult<i64> {
+    let root = PathBuf::from(&folder);
+    if !root.is_dir() {
+        return Err(AppError::coded_with(
+            MessageCode::ImportFolderNotDir,
+            "path",
+            &folder,
+        ));
+    }
+    let files = collect_markdown_files(&root).map_err(invalid_import_folder)?;
+    if files.is_empty() {
+        return Err(AppError::coded(MessageCode::ImportFolderEmpty));
+    }
+
+    let state = state.inner().clone();
+    let jobs = state.jobs.clone();
+    let job_id = jobs.spawn(&app, JobKind::ImportMarkdown, move |job| async move {
+        import_markdown_notes(&state, &folder, files, &job).await
+    });
+    Ok(job_id)
+}
+
+/// 先逐个导入笔记，再统一解析链接（链接可能指向后面的文件）；取消时停在当前文件之前，
+/// 已导入的笔记照常建立链接并加入 AI 队列
+async fn import_markdown_notes(
+    state: &AppState,
+    folder: &str,
+    files: Vec<PathBuf>,
+    job: &JobHandle,
+) -> AppResult<MarkdownImportSummary> {
+    let mut summary = MarkdownImportSummary::default();
+    // 文件名与标题（小写）到节点 ID，作为链接目标
+    let mut targets: HashMap<String, i64> = HashMap::new();
+    let mut pending_links: Vec<(i64, MarkdownNote)> = Vec::new();
+    let mut to_enqueue = Vec::new();
+    let total = files.len();
+    let mut cancelled = false;
+
+    for (index, path) in files.iter().enumerate() {
+        if job.is_cancelled() {
+            cancelled = true;
+            break;
+        }
+        job.progress(index, Some(total));
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) => {
+                tracing::warn!(path = %path.display(), error = %err, "Read markdown file failed");
+                summary.failed_files += 1;
+                continue;
+            }
+        };
+        let stem = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let note = parse_markdown_note(&stem, &text);
+        let hash_source = if note.body.is_empty() {
+            &note.title
+        } else {
+            &note.body
+        };
+        let file_hash = compute_sha256(hash_source.as_bytes());
+
+        let (node_id, inserted) = match find_resource_by_hash(&state.db, &file_hash).await? {
+            Some(existing_id) => {
+                summary.skipped_duplicates += 1;
+                (existing_id, false)
+            }
+            None => {
+                let node_id = insert_markdown_note(&state.db, path, &note, &file_hash).await?;
+                if !note.body.is_empty() {
+                    to_enqueue.push(node_id);
+                }
+                summary.imported_notes += 1;
+                summary.node_ids.push(node_id);
+                (node_id, true)
+            }
+        };
+        targets.entry(link_key(&stem)).or_insert(node_id);
+        targets.entry(link_key(&note.title)).or_insert(node_id);
+        if inserted {
+            pending_links.push((node_id, note));
+        }
+    }
+
+    // 插入时只能按完全相同的标题匹配，这里按文件名重建引用
+    for (source_id, note) in pending_links {
+        let mut resolved = HashMap::new();
+        let mut linked = HashSet::new();
+        for link in note.links {
+            let key = link_key(&link.target);
+            let target_id = match targets.get(&key) {
+                Some(&target_id) => Some(target_id),
+                None => {
+                    let existing = get_node_by_title(&state.db, NodeType::Resource, &link.target)
+                        .await?
+                        .map(|node| node.node_id);
+                    if let Some(target_id) = existing {
+                        targets.insert(key, target_id);
+                    }
+                    existing
+                }
+            };
+            match target_id {
+                Some(target_id) => {
+                    if target_id != source_id {
+                        linked.insert(target_id);
+                    }
+                    resolved.insert(link.text, target_id);
+                }
+                None => summary.unresolved_links += 1,
+            }
+        }
+        summary.linked += linked.len();
+        let mut conn = state.db.acquire().await?;
+        refresh_node_links_with(&mut conn, source_id, Some(&note.body), &resolved).await?;
+    }
+
+    for node_id in to_enqueue {
+        if let Err(err) = state
+            .ai_pipeline
+            .enqueue_resource(node_id, JobPriority::Bulk)
+            .await
+        {
+            tracing::warn!(node_id, error = %err, "Enqueue imported resource failed");
+        }
+    }
+
+    tracing::info!(
+        folder = %folder,
+        notes = summary.imported_notes,
+        skipped = summary.skipped_duplicates,
+        failed = summary.failed_files,
+        linked = summary.linked,
+        unresolved = summary.unresolved_links,
+        cancelled,
+        "Markdown folder imported"
+    );
+    if cancelled {
+        return Err(AppError::coded(MessageCode::JobCancelled));
+    }
+    job.progress(total, Some(total));
+    Ok(summary)
+}
+
+/// 创建笔记资源并导入标签；front matter 没有时间时使用文件修改时间
+async fn insert_markdown_note(
+    db: &DbPool,
+    path: &Path,
+    note: &MarkdownNote,
+    file_hash: &str,
+) -> AppResult<i64> {
+    let modified_at = file_modified_at(path);
+    let meta = SourceMeta {
+        url: None,
+        window_title: None,
+        process_name: Some("Markdown".to_string()),
+        captured_at: note.created_at.clone().or_else(|| modified_at.clone()),
+        process_path: None,
+        sender: None,
+        sent_at: None,
+    };
+    let node_id = NodeBuilder::resource()
+        .title(&note.title)
+        .file_hash(Some(file_hash))
+        .file_content((!note.body.is_empty()).then_some(note.body.as_str()))
+        .resource_subtype(Some(ResourceSubtype::Text))
+        .source_meta(Some(meta))
+        .insert(db)
+        .await?;
+    set_node_timestamps(
+        db,
+        node_id,
+        note.created_at.as_deref().or(modified_at.as_deref()),
+        note.updated_at.as_deref().or(modified_at.as_deref()),
+    )
+    .await?;
+
+    for tag in &note.tags {
+        let tag_id = get_or_create_tag(db, tag).await?;
+        attach_tag_to_node(db, node_id, tag_id, TagSource::Manual).await?;
+    }
+    Ok(node_id)
+}
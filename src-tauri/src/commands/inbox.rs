@@ -0,0 +1,28 @@
+//! 收件箱整理命令：生成处理建议并批量执行用户勾选的建议
+
+use tauri::State;
+
+use crate::services::{
+    apply_inbox_triage, plan_inbox_triage, InboxTriagePlan, TriageApplyReport, TriageDecision,
+    MAX_TRIAGE_ITEMS,
+};
+use crate::{AppResult, AppState};
+
+/// 让 LLM 为收件箱中的资源生成处理建议（不修改数据）
+#[tauri::command]
+pub async fn triage_inbox(
+    state: State<'_, AppState>,
+    limit: Option<i64>,
+) -> AppResult<InboxTriagePlan> {
+    let limit = limit.unwrap_or(MAX_TRIAGE_ITEMS).clamp(1, MAX_TRIAGE_ITEMS);
+    Ok(plan_inbox_triage(&state, limit).await?)
+}
+
+/// 逐条执行用户勾选的建议，单条失败不影响其余
+#[tauri::command]
+pub async fn apply_inbox_triage_command(
+    state: State<'_, AppState>,
+    decisions: Vec<TriageDecision>,
+) -> AppResult<TriageApplyReport> {
+    Ok(apply_inbox_triage(&state.db, &decisions).await)
+}
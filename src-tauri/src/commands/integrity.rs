@@ -0,0 +1,33 @@
+//! 文件完整性校验与自检命令
+
+use tauri::{AppHandle, State};
+
+use crate::db::IntegrityCheckTrigger;
+use crate::services::{
+    load_latest_report, run_integrity_check, run_self_checks, IntegrityReport, SelfTestReport,
+};
+use crate::{AppResult, AppState};
+
+/// 立即校验所有资源文件；reparse 为 true 时把哈希不一致的文件按新内容重新解析并入队
+#[tauri::command]
+pub async fn verify_asset_integrity(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    reparse: bool,
+) -> AppResult<IntegrityReport> {
+    run_integrity_check(&app, &state, IntegrityCheckTrigger::Manual, reparse).await
+}
+
+/// 最近一次校验（手动或定期）的报告
+#[tauri::command]
+pub async fn get_latest_integrity_report(
+    state: State<'_, AppState>,
+) -> AppResult<Option<IntegrityReport>> {
+    load_latest_report(&state).await
+}
+
+/// 自检：迁移、模型目录、OCR 模型、LanceDB 写入、本地 whisper
+#[tauri::command]
+pub async fn run_self_test(state: State<'_, AppState>) -> AppResult<SelfTestReport> {
+    run_self_checks(&state).await
+}
@@ -0,0 +1,27 @@
+//! 后台任务命令：查询状态与取消
+
+use tauri::{AppHandle, State};
+
+use crate::services::JobSnapshot;
+use crate::{AppResult, AppState};
+
+#[tauri::command]
+pub async fn get_job_status(state: State<'_, AppState>, job_id: i64) -> AppResult<JobSnapshot> {
+    state.jobs.get(job_id)
+}
+
+/// 全部保留在内存中的任务（运行中与最近结束的），新的在前
+#[tauri::command]
+pub async fn list_jobs(state: State<'_, AppState>) -> AppResult<Vec<JobSnapshot>> {
+    Ok(state.jobs.list())
+}
+
+/// 请求取消任务，任务在下一个检查点停止；已结束的任务原样返回
+#[tauri::command]
+pub async fn cancel_job(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    job_id: i64,
+) -> AppResult<JobSnapshot> {
+    state.jobs.cancel(&app, job_id)
+}
@@ -0,0 +1,56 @@
+//! Slack / Discord 消息转发入口命令
+
+use tauri::State;
+
+use crate::i18n::MessageCode;
+use crate::services::{generate_webhook_token, MessageWebhookStatus};
+use crate::{AppError, AppResult, AppState};
+
+/// 低于该值的端口需要特权，不允许使用
+const MIN_WEBHOOK_PORT: u16 = 1024;
+
+#[tauri::command]
+pub async fn get_message_webhook_status(
+    state: State<'_, AppState>,
+) -> AppResult<MessageWebhookStatus> {
+    let config = state.ai_config.lock().await.load()?.message_webhook;
+    Ok(state.message_capture.status(&config))
+}
+
+/// 启用 / 停用消息入口；首次启用时生成令牌，监听失败时不保存配置
+#[tauri::command]
+pub async fn set_message_webhook(
+    state: State<'_, AppState>,
+    enabled: bool,
+    port: Option<u16>,
+) -> AppResult<MessageWebhookStatus> {
+    let config_service = state.ai_config.lock().await;
+    let mut config = config_service.load()?.message_webhook;
+    if let Some(port) = port {
+        if port < MIN_WEBHOOK_PORT {
+            return Err(AppError::coded(MessageCode::InvalidWebhookPort));
+        }
+        config.port = port;
+    }
+    config.enabled = enabled;
+    if config.token.is_empty() {
+        config.token = generate_webhook_token();
+    }
+
+    state.message_capture.apply_config(&config)?;
+    config_service.set_message_webhook(config.clone())?;
+    Ok(state.message_capture.status(&config))
+}
+
+/// 重新生成令牌，旧令牌立即失效
+#[tauri::command]
+pub async fn regenerate_message_webhook_token(
+    state: State<'_, AppState>,
+) -> AppResult<MessageWebhookStatus> {
+    let config_service = state.ai_config.lock().await;
+    let mut config = config_service.load()?.message_webhook;
+    config.token = generate_webhook_token();
+    config_service.set_message_webhook(config.clone())?;
+    state.message_capture.apply_config(&config)?;
+    Ok(state.message_capture.status(&config))
+}
@@ -0,0 +1,46 @@
+//! 运行指标命令：查询指标快照、开关 Prometheus 端点
+
+use tauri::State;
+
+use crate::i18n::MessageCode;
+use crate::services::{metrics_snapshot, MetricsEndpointStatus, MetricsSnapshot};
+use crate::{AppError, AppResult, AppState};
+
+/// 低于该值的端口需要特权，不允许使用
+const MIN_ENDPOINT_PORT: u16 = 1024;
+
+/// 启动以来的计数器与耗时直方图
+#[tauri::command]
+pub async fn get_metrics() -> AppResult<MetricsSnapshot> {
+    Ok(metrics_snapshot())
+}
+
+#[tauri::command]
+pub async fn get_metrics_endpoint_status(
+    state: State<'_, AppState>,
+) -> AppResult<MetricsEndpointStatus> {
+    let config = state.ai_config.lock().await.load()?.metrics_endpoint;
+    Ok(state.metrics_endpoint.status(&config))
+}
+
+/// 启用 / 停用 Prometheus 端点，监听失败时不保存配置
+#[tauri::command]
+pub async fn set_metrics_endpoint(
+    state: State<'_, AppState>,
+    enabled: bool,
+    port: Option<u16>,
+) -> AppResult<MetricsEndpointStatus> {
+    let config_service = state.ai_config.lock().await;
+    let mut config = config_service.load()?.metrics_endpoint;
+    if let Some(port) = port {
+        if port < MIN_ENDPOINT_PORT {
+            return Err(AppError::coded(MessageCode::InvalidWebhookPort));
+        }
+        config.port = port;
+    }
+    config.enabled = enabled;
+
+    state.metrics_endpoint.apply_config(&config)?;
+    config_service.set_metrics_endpoint(config.clone())?;
+    Ok(state.metrics_endpoint.status(&config))
+}
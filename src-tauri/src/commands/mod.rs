@@ -4,17 +4,46 @@
 //! 按功能分组导出，便于维护和查找。
 
 mod ai_config;
+mod assets_storage;
+mod calendar;
+mod capture_profiles;
 mod chat;
 mod chat_stream;
+mod citations;
 mod clipboard;
 mod dashboard;
+mod data_migrations;
 mod edges;
+mod events;
+mod exports;
+mod file_references;
+mod focus;
+mod github;
+mod goal_reviews;
+mod habits;
+mod imports;
+mod inbox;
+mod integrity;
+mod jobs;
+mod message_capture;
+mod metrics;
+mod model_files;
 mod nodes;
+mod notifications;
+mod pending_changes;
+mod pomodoro;
 mod resources;
+mod retention;
 mod search;
+mod snapshots;
+mod sources;
+mod tags;
 mod tasks;
 mod topics;
 mod types;
+mod usage;
+mod watched_folders;
+mod workspaces;
 
 // ========== 简单命令宏 ==========
 // 这些宏用于生成重复模式的 Tauri 命令，减少样板代码
@@ -66,27 +95,127 @@ pub use types::*;
 
 // ========== 资源命令 ==========
 pub use resources::{
-    capture_resource, get_all_resources, get_assets_path, get_resource_by_id,
-    hard_delete_resource_command, process_pending_resources_command,
-    soft_delete_resource_command, update_resource_content_command,
+    capture_resource, capture_resources_batch, estimate_processing_cost, expand_archive,
+    find_in_notes, get_all_resources, get_assets_path, get_resource_by_id, hard_delete_resource_command,
+    approve_classification, get_embedding_status_report, get_pipeline_status, pause_ai_pipeline, list_resource_revisions,
+    list_resources_by_source_command, list_unreviewed_resources, preview_archive,
+    process_pending_resources_command, reclassify_resource_command, reject_classification,
+    replace_in_notes,
+    requeue_failed_embeddings, restore_resource_revision, retry_failed_resources, resume_ai_pipeline, soft_delete_resource_command, update_resource_content_command,
     update_resource_summary_command, update_resource_title_command,
     update_resource_user_note_command,
 };
 
+// ========== 导入命令 ==========
+pub use imports::{import_enex, import_markdown_folder};
+
+// ========== 日历命令 ==========
+pub use calendar::{
+    add_calendar_subscription, export_tasks_ics, import_ics, list_calendar_subscriptions,
+    remove_calendar_subscription, sync_calendar_subscription,
+};
+
+// ========== GitHub 同步命令 ==========
+pub use github::{add_github_repo, list_github_repos, remove_github_repo, sync_github_repo};
+
+// ========== 资源文件目录命令 ==========
+pub use assets_storage::{get_assets_storage, relocate_assets};
+
+// ========== 模型目录命令 ==========
+pub use model_files::{download_models, get_model_dir_status, set_model_dir};
+
+// ========== 文件完整性校验与自检命令 ==========
+pub use integrity::{get_latest_integrity_report, run_self_test, verify_asset_integrity};
+
+// ========== 导出命令 ==========
+pub use exports::{
+    export_vault, get_export_schedule, list_export_runs, run_export_now, set_export_schedule,
+};
+
+// ========== 后台任务命令 ==========
+pub use jobs::{cancel_job, get_job_status, list_jobs};
+
+// ========== 待确认的 AI 修改命令 ==========
+pub use pending_changes::{approve_change, list_pending_changes, reject_change};
+
+// ========== 用量统计命令 ==========
+pub use usage::{get_model_prices, get_usage_report, set_model_prices};
+
+// ========== 捕获模板命令 ==========
+pub use capture_profiles::{delete_capture_profile, list_capture_profiles, save_capture_profile};
+
+// ========== 监听文件夹命令 ==========
+pub use watched_folders::{add_watched_folder, list_watched_folders, remove_watched_folder};
+
+// ========== 外部文件引用命令 ==========
+pub use file_references::{
+    get_file_reference_status, list_file_reference_statuses, relink_referenced_file,
+};
+
+// ========== 标签命令 ==========
+pub use tags::{
+    accept_suggested_tags, add_tag, list_nodes_by_tag, list_suggested_tags, list_tags, merge_tags,
+    remove_tag, rename_tag,
+};
+
+// ========== 消息转发入口命令 ==========
+pub use message_capture::{
+    get_message_webhook_status, regenerate_message_webhook_token, set_message_webhook,
+};
+
+// ========== 运行指标命令 ==========
+pub use metrics::{get_metrics, get_metrics_endpoint_status, set_metrics_endpoint};
+
+// ========== 保留策略命令 ==========
+pub use retention::{
+    delete_retention_rule, list_retention_rules, preview_retention_rule, run_retention_rules_now,
+    save_retention_rule, set_node_archived,
+};
+
+// ========== 备份查看命令 ==========
+pub use snapshots::{
+    close_snapshot, get_snapshot_node, list_snapshot_nodes, list_snapshot_topic_contents,
+    list_snapshots, open_snapshot, search_snapshot,
+};
+
+// ========== 收件箱整理命令 ==========
+pub use inbox::{apply_inbox_triage_command, triage_inbox};
+
+// ========== 文献命令 ==========
+pub use citations::{
+    export_bibtex, extract_citation_command, get_citation, list_papers, remove_citation_command,
+    update_citation,
+};
+
+// ========== 来源命令 ==========
+pub use sources::{
+    add_source_tag_rule, get_capture_sources_report, list_node_tags,
+    list_source_tag_rules_command, remove_source_tag_rule, set_source_tag_rule_enabled,
+};
+
 // ========== 任务命令 ==========
 pub use tasks::{
-    create_task, get_active_tasks, get_all_tasks, get_tasks_by_date,
-    hard_delete_task_command, mark_task_as_cancelled_command, mark_task_as_done_command,
-    mark_task_as_todo_command, soft_delete_task_command, update_task_description_command,
-    update_task_due_date_command, update_task_priority_command, update_task_summary_command,
-    update_task_title_command,
+    create_recurring_task, create_subtask, create_task, get_active_tasks, get_all_tasks,
+    get_task_board, get_task_reminder_command, get_tasks_by_date, hard_delete_task_command,
+    list_subtasks, mark_task_as_cancelled_command, mark_task_as_done_command,
+    mark_task_as_todo_command, move_task_on_board, reparent_task, set_task_reminder,
+    snooze_task_reminder, soft_delete_task_command, update_task_description_command,
+    update_task_due_date_command, update_task_priority_command, update_task_recurrence,
+    update_task_summary_command, update_task_title_command,
+};
+
+// ========== 习惯命令 ==========
+pub use habits::{
+    create_habit, list_habits_command, log_habit, soft_delete_habit_command, unlog_habit,
+    update_habit_schedule_command,
 };
 
 // ========== 主题命令 ==========
 pub use topics::{
-    create_topic, get_resource_topics_command, get_task_topics_command, get_topic_command,
-    get_topic_resources_command, get_topic_tasks_command, hard_delete_topic_command,
-    link_resource_to_topic_command, link_task_to_topic_command, list_topics_command,
+    apply_topic_restructure_command, create_topic, get_resource_topics_command,
+    get_task_topics_command, get_topic_command, get_topic_resources_command,
+    get_topic_tasks_command, hard_delete_topic_command, link_resource_to_topic_command,
+    link_task_to_topic_command, list_topics_command, plan_topic_restructure_command,
     soft_delete_topic_command, unlink_resource_from_topic_command, unlink_task_from_topic_command,
     update_topic_favourite_command, update_topic_resource_review_status_command,
     update_topic_summary_command, update_topic_title_command,
@@ -95,36 +224,70 @@ pub use topics::{
 // ========== 节点命令 ==========
 pub use nodes::{
     convert_resource_to_task_command, convert_resource_to_topic_command,
-    convert_task_to_topic_command, convert_topic_to_task_command, list_node_revision_logs,
-    list_pinned_nodes, list_unreviewed_nodes, update_node_pinned, update_node_review_status,
+    convert_task_to_topic_command, convert_topic_to_task_command, get_node_by_uuid_command,
+    get_reading_position, list_node_revision_logs, list_pinned_nodes, list_recent_nodes, list_unreviewed_nodes,
+    record_node_opened, set_topic_auto_classify_allowed, update_node_exclude_from_search,
+    update_node_pinned, update_node_review_status, update_reading_position, update_reading_progress,
 };
 
 // ========== 边命令 ==========
 pub use edges::{
-    confirm_edge_command, link_nodes_command, list_all_edges_command,
-    list_edges_for_target_command, list_source_nodes_command, list_target_nodes_command,
-    unlink_nodes_command,
+    confirm_edge_command, export_edges, get_backlinks, get_derivation_sources, get_derivatives, get_graph,
+    import_edges, link_nodes_command, list_all_edges_command, list_edges_for_target_command, list_source_nodes_command,
+    list_target_nodes_command, unlink_nodes_command,
 };
 
 // ========== 搜索命令 ==========
-pub use search::{search_keyword, search_semantic, warmup_embedding};
+pub use search::{
+    get_vector_stats, list_node_chunks, optimize_vector_store, quick_search, rebuild_vector_index,
+    reindex_embeddings, search_by_image, search_keyword, search_nodes_fulltext, search_semantic,
+    search_vault, warmup_embedding,
+};
 
 // ========== 聊天命令 ==========
 pub use chat::{
     add_message_attachments, create_chat_message, create_chat_session, delete_chat_message,
-    delete_chat_session, get_chat_session, list_chat_messages_command, list_chat_sessions,
-    list_message_attachments_command, list_session_bound_resources_command,
-    remove_message_attachment, set_session_bindings_command, update_chat_message,
-    update_chat_session_command,
+    delete_chat_session, fork_chat_session, get_chat_session, list_chat_messages_command,
+    list_chat_sessions, list_message_attachments_command, list_message_sources,
+    list_session_bound_resources_command, remove_message_attachment, set_session_bindings_command,
+    set_session_rag_settings, update_chat_message, update_chat_session_command,
 };
 pub use chat_stream::send_chat_message;
 
 // ========== AI 配置命令 ==========
 pub use ai_config::{
-    get_ai_config_status, remove_api_key, save_api_key, set_classification_mode,
-    set_processing_provider_model,
+    get_ai_config_status, list_local_models, remove_api_key, save_api_key, set_ann_index_config,
+    set_chat_context_config, set_classification_mode, set_language, set_processing_provider_model,
+    set_provider_rate_limit, set_retrieval_config, set_summary_language, set_warmup_on_idle,
+};
+
+// ========== 专注命令 ==========
+pub use focus::{
+    end_focus_session, get_current_focus_session, get_focus_stats, list_focus_sessions,
+    start_focus_session,
+};
+
+// ========== 番茄钟命令 ==========
+pub use pomodoro::{
+    get_pomodoro_state, get_task_timer, get_time_report, list_time_entries, pause_pomodoro,
+    resume_pomodoro, skip_pomodoro_phase, start_pomodoro, start_task_timer, stop_pomodoro,
+    stop_task_timer,
+};
+
+// ========== 通知命令 ==========
+pub use notifications::{
+    delete_notification_command, get_unread_notification_count, list_notifications,
+    mark_all_notifications_read_command, mark_notification_read,
 };
 
+// ========== 回顾命令 ==========
+pub use goal_reviews::{generate_goal_review_command, list_goal_reviews_command};
+
+// ========== 工作区命令 ==========
+pub use workspaces::{delete_workspace, list_workspaces, restore_workspace, save_workspace};
+
 // ========== 其他命令 ==========
-pub use clipboard::read_clipboard;
+pub use clipboard::{preview_clipboard_ocr, read_clipboard};
 pub use dashboard::get_dashboard;
+pub use data_migrations::list_data_migrations_command;
+pub use events::get_event_schema;
@@ -0,0 +1,46 @@
+//! 第三方模型目录命令
+
+use std::path::Path;
+
+use tauri::{AppHandle, Manager, State};
+
+use crate::i18n::MessageCode;
+use crate::services::{
+    apply_model_dir_config, download_missing_models, model_dir_status, ModelDirStatus,
+};
+use crate::{AppError, AppResult, AppState};
+
+#[tauri::command]
+pub fn get_model_dir_status() -> ModelDirStatus {
+    model_dir_status()
+}
+
+/// 设置自定义模型目录（绝对路径），为空时恢复自动查找；立即生效
+#[tauri::command]
+pub async fn set_model_dir(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    path: Option<String>,
+) -> AppResult<ModelDirStatus> {
+    let path = path
+        .map(|path| path.trim().to_string())
+        .filter(|path| !path.is_empty());
+    if let Some(path) = &path {
+        if !Path::new(path).is_absolute() {
+            return Err(AppError::coded(MessageCode::ModelDirNotAbsolute));
+        }
+    }
+
+    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    state.ai_config.lock().await.set_model_dir(path.clone())?;
+    Ok(apply_model_dir_config(&app, &app_data_dir, path.as_deref()))
+}
+
+/// 下载缺失的 OCR 模型文件；进度见 model-download-progress 事件
+#[tauri::command]
+pub async fn download_models(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> AppResult<ModelDirStatus> {
+    download_missing_models(&app, &state).await
+}
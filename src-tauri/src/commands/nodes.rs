@@ -6,8 +6,9 @@ use crate::db::{
     self, convert_resource_to_container, convert_task_to_topic, convert_topic_to_task,
     NodeRecord, NodeType, ReviewStatus,
 };
+use crate::i18n::MessageCode;
 use crate::utils::parse_review_status;
-use crate::{AppResult, AppState};
+use crate::{AppError, AppResult, AppState};
 
 /// 获取所有收藏节点
 #[tauri::command]
@@ -15,6 +16,17 @@ pub async fn list_pinned_nodes(state: State<'_, AppState>) -> AppResult<Vec<Node
     Ok(db::list_pinned_nodes(&state.db).await?)
 }
 
+/// 按 uuid 获取节点（深链接与外部集成使用，uuid 在导出 / 导入后不变）
+#[tauri::command]
+pub async fn get_node_by_uuid_command(
+    state: State<'_, AppState>,
+    uuid: String,
+) -> AppResult<NodeRecord> {
+    db::get_node_by_uuid(&state.db, uuid.trim())
+        .await?
+        .ok_or_else(|| AppError::coded_with(MessageCode::UnknownNodeUuid, "value", &uuid))
+}
+
 /// 获取所有待审核节点
 #[tauri::command]
 pub async fn list_unreviewed_nodes(state: State<'_, AppState>) -> AppResult<Vec<NodeRecord>> {
@@ -44,6 +56,33 @@ pub async fn update_node_pinned(
     Ok(())
 }
 
+/// 设置节点是否排除在语义搜索与对话 RAG 之外；主题被排除时其包含的节点一并排除
+#[tauri::command]
+pub async fn update_node_exclude_from_search(
+    state: State<'_, AppState>,
+    node_id: i64,
+    excluded: bool,
+) -> AppResult<()> {
+    db::update_node_exclude_from_search(&state.db, node_id, excluded).await?;
+    Ok(())
+}
+
+/// 设置主题是否接受 AI 自动归类；关闭后分类不会再把资源放入该主题
+#[tauri::command]
+pub async fn set_topic_auto_classify_allowed(
+    state: State<'_, AppState>,
+    topic_id: i64,
+    allowed: bool,
+) -> AppResult<()> {
+    if !db::update_topic_auto_classify_allowed(&state.db, topic_id, allowed).await? {
+        return Err(AppError::NotFound {
+            entity: "topic",
+            id: topic_id,
+        });
+    }
+    Ok(())
+}
+
 /// 列出节点修订日志
 #[tauri::command]
 pub async fn list_node_revision_logs(
@@ -88,3 +127,60 @@ pub async fn convert_task_to_topic_command(
 ) -> AppResult<NodeRecord> {
     convert_task_to_topic(&state.db, node_id).await
 }
+
+/// 记录节点被打开（用于最近访问）；资源仍在 AI 队列中等待时提到最前
+#[tauri::command]
+pub async fn record_node_opened(state: State<'_, AppState>, node_id: i64) -> AppResult<()> {
+    db::mark_node_opened(&state.db, node_id).await?;
+    state.ai_pipeline.prioritize(node_id).await;
+    Ok(())
+}
+
+/// 最近打开的节点（含继续阅读位置）
+#[tauri::command]
+pub async fn list_recent_nodes(
+    state: State<'_, AppState>,
+    limit: Option<i32>,
+) -> AppResult<Vec<db::RecentNodeRow>> {
+    let limit = limit.unwrap_or(20).clamp(1, 100);
+    Ok(db::list_recently_opened_nodes(&state.db, limit).await?)
+}
+
+/// 保存阅读位置（scroll_position 为 0~1 的滚动比例，page 从 1 开始）
+#[tauri::command]
+pub async fn update_reading_position(
+    state: State<'_, AppState>,
+    node_id: i64,
+    scroll_position: Option<f64>,
+    page: Option<i64>,
+) -> AppResult<()> {
+    if scroll_position.is_some_and(|position| !(0.0..=1.0).contains(&position)) {
+        return Err(AppError::coded(MessageCode::ScrollPositionOutOfRange));
+    }
+    if page.is_some_and(|page| page < 1) {
+        return Err(AppError::coded(MessageCode::PageOutOfRange));
+    }
+    Ok(db::save_reading_position(&state.db, node_id, scroll_position, page).await?)
+}
+
+/// 保存阅读进度（0~1），节点列表中与预计阅读时长一起返回
+#[tauri::command]
+pub async fn update_reading_progress(
+    state: State<'_, AppState>,
+    node_id: i64,
+    progress: f64,
+) -> AppResult<()> {
+    if !(0.0..=1.0).contains(&progress) {
+        return Err(AppError::coded(MessageCode::ReadingProgressOutOfRange));
+    }
+    Ok(db::save_reading_progress(&state.db, node_id, progress).await?)
+}
+
+/// 获取节点的阅读进度，从未打开过时返回 None
+#[tauri::command]
+pub async fn get_reading_position(
+    state: State<'_, AppState>,
+    node_id: i64,
+) -> AppResult<Option<db::ReadingStateRecord>> {
+    Ok(db::get_reading_state(&state.db, node_id).await?)
+}
@@ -0,0 +1,32 @@
+//! 通知中心命令
+
+use tauri::State;
+
+use crate::db::{self, delete_notification, mark_all_notifications_read, NotificationRecord};
+use crate::utils::validate_limit;
+use crate::{simple_void_command, AppResult, AppState};
+
+/// 列出通知（默认最近 50 条）
+#[tauri::command]
+pub async fn list_notifications(
+    state: State<'_, AppState>,
+    unread_only: Option<bool>,
+    limit: Option<i32>,
+) -> AppResult<Vec<NotificationRecord>> {
+    let limit = validate_limit(limit, 50, 200);
+    Ok(db::list_notifications(&state.db, unread_only.unwrap_or(false), limit as i64).await?)
+}
+
+/// 铃铛未读数
+#[tauri::command]
+pub async fn get_unread_notification_count(state: State<'_, AppState>) -> AppResult<i64> {
+    Ok(db::count_unread_notifications(&state.db).await?)
+}
+
+simple_void_command!(mark_notification_read, db::mark_notification_read, notification_id: i64);
+simple_void_command!(delete_notification_command, delete_notification, notification_id: i64);
+
+#[tauri::command]
+pub async fn mark_all_notifications_read_command(state: State<'_, AppState>) -> AppResult<()> {
+    Ok(mark_all_notifications_read(&state.db).await?)
+}
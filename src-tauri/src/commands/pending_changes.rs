@@ -0,0 +1,87 @@
+//! 待确认的 AI 修改命令
+
+use tauri::State;
+
+use crate::db::{
+    decide_pending_change, get_pending_change, list_pending_changes_by_status,
+    reopen_pending_change, PendingChangeKind, PendingChangeRecord, PendingChangeStatus,
+};
+use crate::i18n::MessageCode;
+use crate::services::apply_pending_change;
+use crate::{AppError, AppResult, AppState};
+
+async fn fetch_undecided_change(
+    state: &State<'_, AppState>,
+    change_id: i64,
+) -> AppResult<PendingChangeRecord> {
+    let change = get_pending_change(&state.db, change_id)
+        .await?
+        .ok_or(AppError::NotFound {
+            entity: "pending_change",
+            id: change_id,
+        })?;
+    if change.status != PendingChangeStatus::Pending {
+        return Err(AppError::coded(MessageCode::PendingChangeDecided));
+    }
+    Ok(change)
+}
+
+/// 按状态列出模型提出的修改，默认只列待确认的
+#[tauri::command]
+pub async fn list_pending_changes(
+    state: State<'_, AppState>,
+    status: Option<PendingChangeStatus>,
+) -> AppResult<Vec<PendingChangeRecord>> {
+    let status = status.unwrap_or(PendingChangeStatus::Pending);
+    Ok(list_pending_changes_by_status(&state.db, Some(status)).await?)
+}
+
+/// 批准并执行修改；先认领再执行，避免重复批准时执行两次；执行失败时放回待确认
+#[tauri::command]
+pub async fn approve_change(
+    state: State<'_, AppState>,
+    change_id: i64,
+) -> AppResult<PendingChangeRecord> {
+    let change = fetch_undecided_change(&state, change_id).await?;
+    if !decide_pending_change(&state.db, change_id, PendingChangeStatus::Approved).await? {
+        return Err(AppError::coded(MessageCode::PendingChangeDecided));
+    }
+    if let Err(err) = apply_pending_change(&state.db, &change).await {
+        if let Err(reopen_err) = reopen_pending_change(&state.db, change_id).await {
+            tracing::warn!(change_id, error = %reopen_err, "Failed to reopen pending change");
+        }
+        return Err(err);
+    }
+
+    if change.kind == PendingChangeKind::RenameTopic {
+        if let (Some(ai), Some(topic_id), Some(title)) = (
+            state.ai.try_ready(),
+            change.node_id,
+            change.new_value.as_deref(),
+        ) {
+            if let Err(err) = ai.embedding.upsert_title_embedding(topic_id, title).await {
+                tracing::warn!(
+                    topic_id,
+                    error = %err,
+                    "Failed to upsert renamed topic title embedding"
+                );
+            }
+        }
+    }
+
+    get_pending_change(&state.db, change_id)
+        .await?
+        .ok_or(AppError::NotFound {
+            entity: "pending_change",
+            id: change_id,
+        })
+}
+
+#[tauri::command]
+pub async fn reject_change(state: State<'_, AppState>, change_id: i64) -> AppResult<()> {
+    fetch_undecided_change(&state, change_id).await?;
+    if !decide_pending_change(&state.db, change_id, PendingChangeStatus::Rejected).await? {
+        return Err(AppError::coded(MessageCode::PendingChangeDecided));
+    }
+    Ok(())
+}
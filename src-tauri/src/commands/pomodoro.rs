@@ -0,0 +1,107 @@
+//! 番茄钟与时间记录命令
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::db::{
+    self, list_time_period_totals, list_time_task_totals, TimeEntryRecord, TimeReportPeriod,
+    TimeReportTask,
+};
+use crate::services::{PomodoroConfig, PomodoroState, TaskTimerState};
+use crate::utils::validate_limit;
+use crate::{AppResult, AppState};
+
+/// 时间记录统计
+#[derive(Debug, Serialize)]
+pub struct TimeReportResponse {
+    pub total_seconds: i64,
+    pub daily: Vec<TimeReportPeriod>,
+    /// 按周汇总，period 为该周周一
+    pub weekly: Vec<TimeReportPeriod>,
+    pub tasks: Vec<TimeReportTask>,
+}
+
+/// 开始番茄钟（未传的时长使用默认 25/5/15 分钟、每 4 个番茄一次长休息）
+#[tauri::command]
+pub async fn start_pomodoro(
+    state: State<'_, AppState>,
+    task_id: Option<i64>,
+    config: Option<PomodoroConfig>,
+) -> AppResult<PomodoroState> {
+    Ok(state.pomodoro.start(task_id, config.unwrap_or_default())?)
+}
+
+#[tauri::command]
+pub fn pause_pomodoro(state: State<'_, AppState>) -> AppResult<PomodoroState> {
+    Ok(state.pomodoro.set_paused(true)?)
+}
+
+#[tauri::command]
+pub fn resume_pomodoro(state: State<'_, AppState>) -> AppResult<PomodoroState> {
+    Ok(state.pomodoro.set_paused(false)?)
+}
+
+/// 跳过当前阶段
+#[tauri::command]
+pub async fn skip_pomodoro_phase(state: State<'_, AppState>) -> AppResult<PomodoroState> {
+    Ok(state.pomodoro.skip().await?)
+}
+
+#[tauri::command]
+pub fn stop_pomodoro(state: State<'_, AppState>) -> AppResult<Option<PomodoroState>> {
+    Ok(state.pomodoro.stop()?)
+}
+
+#[tauri::command]
+pub fn get_pomodoro_state(state: State<'_, AppState>) -> Option<PomodoroState> {
+    state.pomodoro.current()
+}
+
+#[tauri::command]
+pub async fn list_time_entries(
+    state: State<'_, AppState>,
+    task_id: Option<i64>,
+    limit: Option<i32>,
+) -> AppResult<Vec<TimeEntryRecord>> {
+    let limit = validate_limit(limit, 50, 500);
+    Ok(db::list_time_entries(&state.db, task_id, limit as i64).await?)
+}
+
+/// 开始为任务计时；已有计时器时先停止并记录
+#[tauri::command]
+pub async fn start_task_timer(
+    state: State<'_, AppState>,
+    task_id: i64,
+) -> AppResult<TaskTimerState> {
+    Ok(state.task_timer.start(task_id).await?)
+}
+
+/// 停止计时并返回写入的时间记录；没有计时器或不足 1 秒时返回 None
+#[tauri::command]
+pub async fn stop_task_timer(state: State<'_, AppState>) -> AppResult<Option<TimeEntryRecord>> {
+    Ok(state.task_timer.stop().await?)
+}
+
+#[tauri::command]
+pub fn get_task_timer(state: State<'_, AppState>) -> Option<TaskTimerState> {
+    state.task_timer.current()
+}
+
+/// 按日 / 周 / 任务统计时间记录（日期格式 YYYY-MM-DD，闭区间，不含进行中的计时器）
+#[tauri::command]
+pub async fn get_time_report(
+    state: State<'_, AppState>,
+    start_date: String,
+    end_date: String,
+) -> AppResult<TimeReportResponse> {
+    let daily = list_time_period_totals(&state.db, &start_date, &end_date, false).await?;
+    let weekly = list_time_period_totals(&state.db, &start_date, &end_date, true).await?;
+    let tasks = list_time_task_totals(&state.db, &start_date, &end_date).await?;
+    let total_seconds = daily.iter().map(|d| d.seconds).sum();
+    Ok(TimeReportResponse {
+        total_seconds,
+        daily,
+        weekly,
+        tasks,
+    })
+}
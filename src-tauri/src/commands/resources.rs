@@ -1,45 +1,105 @@
 //! 资源相关命令
 
-use std::{fs, path::Path};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
-use active_win_pos_rs::get_active_window;
-use serde::Serialize;
-use tauri::{AppHandle, Emitter, State};
+use tauri::{AppHandle, Manager, State};
+use uuid::Uuid;
 
 use crate::{
     app_state::AppState,
     db::{
-        get_node_by_id, hard_delete_node, list_all_resources, soft_delete_node,
-        update_node_content, update_node_summary, update_node_title, update_node_user_note,
-        update_resource_sync_status, NodeBuilder, NodeRecord, ResourceEmbeddingStatus, SourceMeta,
+        confirm_edge, count_embedding_errors, count_pipeline_jobs_by_stage,
+        count_resources_by_embedding_state, delete_edge,
+        find_resource_by_hash, get_content_revision, get_node_by_id, hard_delete_node,
+        insert_edge_if_missing, insert_node_revision_log, list_all_resources,
+        list_content_revisions, list_edges_to, list_failed_embedding_resources,
+        list_resources_by_source, list_text_resources, list_unreviewed_classifications,
+        mark_resource_embedding_dirty, oldest_pending_resource, replace_node_content,
+        reset_resource_embedding_error, resolve_node_ref, resolve_node_refs, soft_delete_node,
+        update_node_text_checked, update_resource_review_status, ContentRevisionReason,
+        ContentRevisionRecord, EdgeRelationType, FileStat, NewEdge, NewNodeRevisionLog,
+        NodeBuilder, NodeRecord, NodeRef, NodeTextField, NodeType, PipelineJobStatus,
+        ResourceSubtype, ReviewStatus, SourceMeta, UnreviewedClassificationRecord,
     },
     error::AppError,
-    services::parser::{build_text_title, parse_resource_content, ProgressCallback},
-    utils::{compute_sha256, get_assets_dir, get_extension, parse_file_type, resolve_file_path, validate_title},
+    i18n::MessageCode,
+    services::{
+        apply_capture_profile, apply_source_tag_rules, build_processing_cost_estimate, emit_event,
+        get_processing_config, hash_reference_file, inspect_reference_file, parse_and_enqueue,
+        parse_deferred,
+        parser::{
+            build_text_title, extract_archive, list_archive_entries, parse_email_file, ParsedEmail,
+            MAX_ARCHIVE_ENTRIES, MAX_ENTRY_BYTES, MAX_TOTAL_BYTES,
+        },
+        reclassify_resource, register_file_reference, ArchiveExpandProgressPayload,
+        CaptureBatchProgressPayload, CaptureProfile, CostEstimateTarget, JobPriority, NoteFindResult,
+        NotePattern, NoteReplaceReport, NoteReplaceResult, ProcessingCostEstimate,
+        ReclassifyOutcome,
+    },
+    utils::{
+        attachment_extension, compute_sha256, get_assets_dir, get_extension, parse_file_type,
+        parse_file_type_from_extension, resolve_file_path, validate_title,
+    },
+    window::{query_frontmost_window, FrontmostWindowCache},
     AppResult,
 };
 
-use super::{CaptureRequest, CaptureResponse};
+use super::{
+    ArchiveExpandSummary, ArchivePreview, CaptureBatchItem, CaptureBatchRequest,
+    CaptureBatchResponse, CaptureRequest, CaptureResponse, EmbeddingStatusReport, FileStorageMode,
+    PipelineStatus, RejectClassificationResponse, RequeueEmbeddingsFilter,
+};
 
 // ========== 内部工具函数 ==========
 
-fn load_or_copy_file_for_capture(
+/// 向量化状态报告中列出的错误信息条数
+const TOP_EMBEDDING_ERRORS: i64 = 5;
+
+/// 待捕获的文件
+struct CapturedFile {
+    file_hash: String,
+    /// assets 相对路径；以引用方式捕获时为原文件的绝对路径
+    stored_path: String,
+    file_name: Option<String>,
+    /// 以引用方式捕获时的原文件路径与状态
+    reference: Option<(PathBuf, FileStat)>,
+}
+
+async fn load_file_for_capture(
     app: &AppHandle,
     source_path: &str,
     resource_uuid: &str,
-) -> AppResult<(Vec<u8>, i64, String, Option<String>)> {
+    storage: FileStorageMode,
+) -> AppResult<CapturedFile> {
     if source_path.starts_with("assets/") {
         let assets_dir = get_assets_dir(app)?;
         let file_name = source_path.strip_prefix("assets/").unwrap_or(source_path);
         let full_path = assets_dir.join(file_name);
 
         let bytes = fs::read(&full_path)?;
-        let size = bytes.len() as i64;
 
-        Ok((bytes, size, source_path.to_string(), Some(file_name.to_string())))
+        Ok(CapturedFile {
+            file_hash: compute_sha256(&bytes),
+            stored_path: source_path.to_string(),
+            file_name: Some(file_name.to_string()),
+            reference: None,
+        })
+    } else if storage == FileStorageMode::Reference {
+        // 原地索引：不复制文件，流式计算哈希避免把大文件读入内存
+        let (path, stat) = inspect_reference_file(source_path)?;
+        let file_hash = hash_reference_file(&path).await?;
+
+        Ok(CapturedFile {
+            file_hash,
+            stored_path: path.to_string_lossy().to_string(),
+            file_name: path.file_name().map(|n| n.to_string_lossy().to_string()),
+            reference: Some((path, stat)),
+        })
     } else {
         let bytes = fs::read(source_path)?;
-        let size = bytes.len() as i64;
 
         let original_name = Path::new(source_path)
             .file_name()
@@ -58,64 +118,41 @@ fn load_or_copy_file_for_capture(
 
         let relative_path = format!("assets/{}", target_filename);
 
-        Ok((bytes, size, relative_path, original_name))
+        Ok(CapturedFile {
+            file_hash: compute_sha256(&bytes),
+            stored_path: relative_path,
+            file_name: original_name,
+            reference: None,
+        })
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
-struct ParseProgressPayload {
-    node_id: i64,
-    status: String,
-    percentage: Option<u8>,
-    error: Option<String>,
-}
-
-fn emit_parse_progress(
-    app: Option<&AppHandle>,
-    node_id: Option<i64>,
-    status: &str,
-    percentage: Option<u8>,
-    error: Option<&str>,
-) {
-    let (app, node_id) = match (app, node_id) {
-        (Some(app), Some(node_id)) => (app, node_id),
-        _ => return,
-    };
-
-    let payload = ParseProgressPayload {
-        node_id,
-        status: status.to_string(),
-        percentage,
-        error: error.map(|message| message.to_string()),
-    };
-    let _ = app.emit("parse-progress", payload);
-}
-
-fn merge_source_meta(payload: Option<super::CaptureSourceMeta>) -> SourceMeta {
+fn merge_source_meta(app: &AppHandle, payload: Option<super::CaptureSourceMeta>) -> SourceMeta {
     let mut meta = SourceMeta {
         url: payload.as_ref().and_then(|m| m.url.clone()),
         window_title: payload.as_ref().and_then(|m| m.window_title.clone()),
         process_name: payload.as_ref().and_then(|m| m.process_name.clone()),
         captured_at: payload.as_ref().and_then(|m| m.captured_at.clone()),
+        process_path: None,
+        sender: None,
+        sent_at: None,
+        auto_title: None,
     };
 
-    if meta.window_title.is_none() || meta.process_name.is_none() {
-        if let Ok(active) = get_active_window() {
-            if meta.window_title.is_none() {
-                meta.window_title = if active.title.is_empty() {
-                    None
-                } else {
-                    Some(active.title)
-                };
-            }
-            if meta.process_name.is_none() {
-                meta.process_name = if active.app_name.is_empty() {
-                    None
-                } else {
-                    Some(active.app_name)
-                };
-            }
+    // HUD 弹出前记录的前台窗口优先；否则实时查询（排除 NeuralVault 自身）
+    let frontmost = app
+        .try_state::<FrontmostWindowCache>()
+        .and_then(|cache| cache.take_recent())
+        .or_else(query_frontmost_window);
+
+    if let Some(active) = frontmost {
+        if meta.window_title.is_none() {
+            meta.window_title = active.window_title;
         }
+        if meta.process_name.is_none() {
+            meta.process_name = active.app_name;
+        }
+        meta.process_path = active.process_path;
     }
 
     if meta.captured_at.is_none() {
@@ -125,6 +162,137 @@ fn merge_source_meta(payload: Option<super::CaptureSourceMeta>) -> SourceMeta {
     meta
 }
 
+/// 解析待捕获的邮件；失败时仍按普通文件捕获，错误在解析阶段记录
+fn read_email_for_capture(path: &str) -> Option<ParsedEmail> {
+    match parse_email_file(path) {
+        Ok(email) => Some(email),
+        Err(err) => {
+            tracing::warn!(path, error = %err, "Parse email for capture failed");
+            None
+        }
+    }
+}
+
+fn email_subject(email: Option<&ParsedEmail>) -> Option<&str> {
+    email.and_then(|email| email.subject.as_deref())
+}
+
+/// 发件人与发送时间写入来源信息
+fn email_source_meta(meta: &SourceMeta, email: &ParsedEmail) -> SourceMeta {
+    SourceMeta {
+        sender: email.from.clone(),
+        sent_at: email.date.clone(),
+        ..meta.clone()
+    }
+}
+
+/// 将邮件附件保存为独立资源，与邮件建立 related_to 关系后解析入队
+///
+/// 内容哈希已存在的附件只建立关系，不重复创建
+async fn import_email_attachments(
+    app: &AppHandle,
+    state: &AppState,
+    email_node_id: i64,
+    email_title: &str,
+    email: &ParsedEmail,
+    meta: &SourceMeta,
+) -> AppResult<()> {
+    for (index, attachment) in email.attachments.iter().enumerate() {
+        let file_hash = compute_sha256(&attachment.data);
+        let attachment_id = match find_resource_by_hash(&state.db, &file_hash).await? {
+            Some(existing_id) => existing_id,
+            None => {
+                let builder = NodeBuilder::resource();
+                let ext = attachment_extension(
+                    attachment.file_name.as_deref(),
+                    attachment.mime.as_deref(),
+                );
+                let file_name = match &ext {
+                    Some(ext) => format!("{}.{ext}", builder.get_uuid()),
+                    None => builder.get_uuid().to_string(),
+                };
+                fs::write(get_assets_dir(app)?.join(&file_name), &attachment.data)?;
+                let stored_path = format!("assets/{file_name}");
+                let subtype = parse_file_type_from_extension(ext.as_deref());
+                let title = attachment
+                    .file_name
+                    .clone()
+                    .unwrap_or_else(|| format!("{email_title} - 附件 {}", index + 1));
+
+                let node_id = builder
+                    .title(title)
+                    .file_hash(Some(&file_hash))
+                    .file_path(Some(&stored_path))
+                    .resource_subtype(Some(subtype))
+                    .source_meta(Some(meta.clone()))
+                    .insert(&state.db)
+                    .await?;
+                let resolved_path = resolve_file_path(app, &stored_path)?;
+                parse_and_enqueue(
+                    app,
+                    state,
+                    node_id,
+                    subtype,
+                    None,
+                    Some(&resolved_path),
+                    &file_hash,
+                    JobPriority::Normal,
+                )
+                .await?;
+                node_id
+            }
+        };
+        insert_edge_if_missing(
+            &state.db,
+            NewEdge {
+                source_node_id: email_node_id.min(attachment_id),
+                target_node_id: email_node_id.max(attachment_id),
+                relation_type: EdgeRelationType::RelatedTo,
+                confidence_score: None,
+                is_manual: true,
+            },
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// 读取捕获模板；模板已被删除时按普通捕获处理
+async fn load_capture_profile(
+    state: &AppState,
+    profile_id: Option<i64>,
+) -> AppResult<Option<CaptureProfile>> {
+    let Some(profile_id) = profile_id else {
+        return Ok(None);
+    };
+    let profiles = state.ai_config.lock().await.load()?.capture_profiles;
+    let profile = profiles
+        .into_iter()
+        .find(|profile| profile.profile_id == profile_id);
+    if profile.is_none() {
+        tracing::warn!(profile_id, "Capture profile not found");
+    }
+    Ok(profile)
+}
+
+/// 应用捕获模板，失败不影响捕获本身
+async fn apply_profile_to_capture(
+    state: &AppState,
+    node_id: i64,
+    profile: Option<&CaptureProfile>,
+) {
+    if let Some(profile) = profile {
+        if let Err(err) = apply_capture_profile(&state.db, node_id, profile).await {
+            tracing::warn!(
+                node_id,
+                profile_id = profile.profile_id,
+                error = %err,
+                "Apply capture profile failed"
+            );
+        }
+    }
+}
+
 // ========== 捕获资源 ==========
 
 #[tauri::command]
@@ -138,36 +306,38 @@ pub async fn capture_resource(
         file_path,
         file_type,
         source_meta,
+        storage,
+        ocr_text,
+        profile_id,
     } = payload;
 
     let subtype = parse_file_type(file_type.as_deref());
+    let profile = load_capture_profile(&state, profile_id).await?;
 
     let builder = NodeBuilder::resource();
     let resource_uuid = builder.get_uuid().to_string();
 
     let file_info = match file_path.as_deref() {
-        Some(source_path) => Some(load_or_copy_file_for_capture(&app, source_path, &resource_uuid)?),
+        Some(source_path) => {
+            Some(load_file_for_capture(&app, source_path, &resource_uuid, storage).await?)
+        }
         None => None,
     };
 
-    let (file_bytes, stored_file_path, file_display_name) = match &file_info {
-        Some((bytes, _size, stored_path, name)) => {
-            (Some(bytes.as_slice()), Some(stored_path.as_str()), name.clone())
-        }
-        None => (None, None, None),
-    };
+    let stored_file_path = file_info.as_ref().map(|file| file.stored_path.as_str());
+    let file_display_name = file_info.as_ref().and_then(|file| file.file_name.clone());
 
     let resolved_path = match stored_file_path {
         Some(path) => Some(resolve_file_path(&app, path)?),
         None => None,
     };
 
-    let file_hash = if let Some(bytes) = file_bytes {
-        compute_sha256(bytes)
+    let file_hash = if let Some(file) = &file_info {
+        file.file_hash.clone()
     } else if let Some(text) = content.as_ref() {
         compute_sha256(text.as_bytes())
     } else {
-        return Err(AppError::Validation("content 或 file_path 至少提供一个".to_string()));
+        return Err(AppError::coded(MessageCode::CaptureInputMissing));
     };
 
     let user_note = if file_info.is_some() {
@@ -176,8 +346,18 @@ pub async fn capture_resource(
         None
     };
 
-    let title = build_resource_title(file_display_name.as_deref(), content.as_deref());
-    let meta = merge_source_meta(source_meta);
+    let email = match (subtype, resolved_path.as_deref()) {
+        (ResourceSubtype::Email, Some(path)) => read_email_for_capture(path),
+        _ => None,
+    };
+    let title = build_resource_title(
+        email_subject(email.as_ref()).or(file_display_name.as_deref()),
+        content.as_deref(),
+    );
+    let mut meta = merge_source_meta(&app, source_meta);
+    if let Some(email) = &email {
+        meta = email_source_meta(&meta, email);
+    }
 
     let node_id = builder
         .title(&title)
@@ -185,71 +365,269 @@ pub async fn capture_resource(
         .file_path(stored_file_path)
         .user_note(user_note.as_deref())
         .resource_subtype(Some(subtype))
-        .source_meta(Some(meta))
+        .source_meta(Some(meta.clone()))
         .insert(&state.db)
         .await?;
 
-    emit_parse_progress(Some(&app), Some(node_id), "parsing", Some(0), None);
+    if let Some((path, stat)) = file_info.as_ref().and_then(|file| file.reference.as_ref()) {
+        register_file_reference(&app, &state, node_id, path, *stat).await?;
+    }
 
-    // Create progress callback for parser
-    let app_clone = app.clone();
-    let progress_callback: ProgressCallback = Box::new(move |status, percentage, error| {
-        emit_parse_progress(Some(&app_clone), Some(node_id), status, percentage, error);
-    });
+    // 来源规则打标签失败不影响捕获本身
+    if let Err(err) = apply_source_tag_rules(&state.db, node_id, &meta).await {
+        tracing::warn!(node_id, error = %err, "Apply source tag rules failed");
+    }
+    apply_profile_to_capture(&state, node_id, profile.as_ref()).await;
 
-    let file_content_result = parse_resource_content(
-        subtype,
-        content.as_deref(),
-        resolved_path.as_deref(),
-        Some(&progress_callback),
-    );
+    if let Some(email) = &email {
+        import_email_attachments(&app, &state, node_id, &title, email, &meta).await?;
+    }
 
-    let mut should_enqueue = false;
-    match file_content_result {
-        Ok(text) => {
-            if let Some(content) = text.as_deref() {
-                tracing::debug!(
-                    node_id,
-                    subtype = ?subtype,
-                    content = %content,
-                    "Parsed resource content"
+    // 图片使用捕获前预览中确认过的 OCR 文本，不再重新识别
+    let parse_input = match subtype {
+        ResourceSubtype::Image => ocr_text.as_deref(),
+        _ => content.as_deref(),
+    };
+    // 推迟处理的模板只解析不入队，资源保持 pending 等待手动处理
+    if profile
+        .as_ref()
+        .is_some_and(|profile| profile.defer_processing)
+    {
+        parse_deferred(
+            &app,
+            &state,
+            node_id,
+            subtype,
+            parse_input,
+            resolved_path.as_deref(),
+            &file_hash,
+        )
+        .await?;
+    } else {
+        parse_and_enqueue(
+            &app,
+            &state,
+            node_id,
+            subtype,
+            parse_input,
+            resolved_path.as_deref(),
+            &file_hash,
+            JobPriority::Interactive,
+        )
+        .await?;
+    }
+
+    Ok(CaptureResponse {
+        node_id,
+        node_uuid: resource_uuid,
+    })
+}
+
+/// 批量捕获中待后台解析的文件
+struct BatchParseJob {
+    index: usize,
+    subtype: ResourceSubtype,
+    title: String,
+    meta: SourceMeta,
+    email: Option<ParsedEmail>,
+    file_path: String,
+    stored_path: String,
+    file_hash: String,
+    reference: Option<(PathBuf, FileStat)>,
+}
+
+/// 批量捕获文件：复制到 assets（或按引用方式原地索引）后在同一事务中创建资源节点，随后在后台逐个解析并入队
+///
+/// 单个文件复制失败不影响其他文件；每个文件的进度通过 capture-batch-progress 事件通知，
+/// 解析细节仍通过 parse-progress 事件按 node_id 通知
+#[tauri::command]
+pub async fn capture_resources_batch(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    payload: CaptureBatchRequest,
+) -> AppResult<CaptureBatchResponse> {
+    let CaptureBatchRequest {
+        file_paths,
+        user_note,
+        source_meta,
+        storage,
+        profile_id,
+    } = payload;
+    if file_paths.is_empty() {
+        return Err(AppError::coded(MessageCode::CaptureInputMissing));
+    }
+    let profile = load_capture_profile(&state, profile_id).await?;
+    let defer_processing = profile
+        .as_ref()
+        .is_some_and(|profile| profile.defer_processing);
+
+    let batch_id = Uuid::new_v4().to_string();
+    let total = file_paths.len();
+    let user_note = user_note.filter(|note| !note.trim().is_empty());
+    let meta = merge_source_meta(&app, source_meta);
+    let emit_progress = |index: usize, item: &CaptureBatchItem, status: &'static str| {
+        emit_event(
+            &app,
+            &CaptureBatchProgressPayload {
+                batch_id: batch_id.clone(),
+                index,
+                total,
+                file_path: item.file_path.clone(),
+                node_id: item.node_id,
+                status,
+                error: item.error.clone(),
+            },
+        );
+    };
+
+    // 1. 复制文件
+    let mut items = Vec::with_capacity(total);
+    let mut builders = Vec::new();
+    let mut copied = Vec::new();
+    for (index, source_path) in file_paths.into_iter().enumerate() {
+        let builder = NodeBuilder::resource();
+        let resource_uuid = builder.get_uuid().to_string();
+        let mut item = CaptureBatchItem {
+            file_path: source_path,
+            node_id: None,
+            node_uuid: None,
+            error: None,
+        };
+        match load_file_for_capture(&app, &item.file_path, &resource_uuid, storage).await {
+            Ok(CapturedFile {
+                file_hash,
+                stored_path,
+                file_name,
+                reference,
+            }) => {
+                let subtype =
+                    parse_file_type_from_extension(get_extension(&item.file_path).as_deref());
+                let email = match subtype {
+                    ResourceSubtype::Email => resolve_file_path(&app, &stored_path)
+                        .ok()
+                        .and_then(|path| read_email_for_capture(&path)),
+                    _ => None,
+                };
+                let title = build_resource_title(
+                    email_subject(email.as_ref()).or(file_name.as_deref()),
+                    None,
+                );
+                let item_meta = match &email {
+                    Some(email) => email_source_meta(&meta, email),
+                    None => meta.clone(),
+                };
+                builders.push(
+                    builder
+                        .title(&title)
+                        .file_hash(Some(&file_hash))
+                        .file_path(Some(&stored_path))
+                        .user_note(user_note.as_deref())
+                        .resource_subtype(Some(subtype))
+                        .source_meta(Some(item_meta.clone())),
                 );
-                update_node_content(&state.db, node_id, Some(content), Some(&file_hash)).await?;
-                should_enqueue = !content.trim().is_empty();
+                copied.push(BatchParseJob {
+                    index,
+                    subtype,
+                    title,
+                    meta: item_meta,
+                    email,
+                    file_path: item.file_path.clone(),
+                    stored_path,
+                    file_hash,
+                    reference,
+                });
+                item.node_uuid = Some(resource_uuid);
+                emit_progress(index, &item, "copied");
+            }
+            Err(err) => {
+                tracing::warn!(path = %item.file_path, error = %err, "Batch capture copy failed");
+                item.error = Some(err.to_string());
+                emit_progress(index, &item, "error");
             }
-            emit_parse_progress(Some(&app), Some(node_id), "done", Some(100), None);
-        }
-        Err(err) => {
-            update_resource_sync_status(
-                &state.db,
-                node_id,
-                ResourceEmbeddingStatus::Error,
-                None,
-                Some(&err),
-            )
-            .await?;
-            emit_parse_progress(Some(&app), Some(node_id), "error", None, Some(&err));
         }
+        items.push(item);
     }
 
-    if should_enqueue {
-        if let Err(err) = state.ai_pipeline.enqueue_resource(node_id).await {
-            update_resource_sync_status(
-                &state.db,
-                node_id,
-                ResourceEmbeddingStatus::Error,
-                None,
-                Some(&err),
-            )
-            .await?;
-            emit_parse_progress(Some(&app), Some(node_id), "error", None, Some(&err));
+    // 2. 同一事务中创建节点
+    let node_ids = NodeBuilder::insert_batch(&builders, &state.db).await?;
+    for (job, &node_id) in copied.iter().zip(&node_ids) {
+        items[job.index].node_id = Some(node_id);
+        if let Some((path, stat)) = &job.reference {
+            register_file_reference(&app, &state, node_id, path, *stat).await?;
+        }
+        // 来源规则打标签失败不影响捕获本身
+        if let Err(err) = apply_source_tag_rules(&state.db, node_id, &job.meta).await {
+            tracing::warn!(node_id, error = %err, "Apply source tag rules failed");
         }
+        apply_profile_to_capture(&state, node_id, profile.as_ref()).await;
     }
+    tracing::info!(batch_id = %batch_id, total, created = copied.len(), "Batch capture inserted");
 
-    Ok(CaptureResponse {
-        node_id,
-        node_uuid: resource_uuid,
-    })
+    // 3. 后台逐个解析并入队
+    let app_handle = app.clone();
+    let state = state.inner().clone();
+    let batch = batch_id.clone();
+    tauri::async_runtime::spawn(async move {
+        for (job, node_id) in copied.into_iter().zip(node_ids) {
+            if let Some(email) = &job.email {
+                if let Err(err) = import_email_attachments(
+                    &app_handle,
+                    &state,
+                    node_id,
+                    &job.title,
+                    email,
+                    &job.meta,
+                )
+                .await
+                {
+                    tracing::warn!(node_id, error = %err, "Import email attachments failed");
+                }
+            }
+            let result = match resolve_file_path(&app_handle, &job.stored_path) {
+                Ok(resolved) if defer_processing => {
+                    parse_deferred(
+                        &app_handle,
+                        &state,
+                        node_id,
+                        job.subtype,
+                        None,
+                        Some(&resolved),
+                        &job.file_hash,
+                    )
+                    .await
+                }
+                Ok(resolved) => {
+                    parse_and_enqueue(
+                        &app_handle,
+                        &state,
+                        node_id,
+                        job.subtype,
+                        None,
+                        Some(&resolved),
+                        &job.file_hash,
+                        JobPriority::Normal,
+                    )
+                    .await
+                }
+                Err(err) => Ok(Some(err)),
+            };
+            let error = result.unwrap_or_else(|err| Some(err.to_string()));
+            emit_event(
+                &app_handle,
+                &CaptureBatchProgressPayload {
+                    batch_id: batch.clone(),
+                    index: job.index,
+                    total,
+                    file_path: job.file_path,
+                    node_id: Some(node_id),
+                    status: if error.is_some() { "error" } else { "done" },
+                    error,
+                },
+            );
+        }
+    });
+
+    Ok(CaptureBatchResponse { batch_id, items })
 }
 
 fn build_resource_title(file_name: Option<&str>, content: Option<&str>) -> String {
@@ -269,6 +647,200 @@ fn build_resource_title(file_name: Option<&str>, content: Option<&str>) -> Strin
     "Untitled".to_string()
 }
 
+// ========== 展开压缩包 ==========
+
+/// 读取压缩包资源的文件路径；非压缩包资源返回 InvalidArchive
+async fn archive_file_path(
+    app: &AppHandle,
+    state: &AppState,
+    node_id: i64,
+) -> AppResult<(NodeRecord, String)> {
+    let node = get_node_by_id(&state.db, node_id).await?;
+    if node.resource_subtype != Some(ResourceSubtype::Archive) {
+        return Err(AppError::coded(MessageCode::NotAnArchive));
+    }
+    let stored_path = node
+        .file_path
+        .clone()
+        .ok_or_else(|| AppError::coded(MessageCode::ResourceFileMissing))?;
+    let resolved = resolve_file_path(app, &stored_path)?;
+    Ok((node, resolved))
+}
+
+/// 列出压缩包条目及展开限制，供前端确认是否展开
+#[tauri::command]
+pub async fn preview_archive(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    node_id: NodeRef,
+) -> AppResult<ArchivePreview> {
+    let node_id = resolve_node_ref(&state.db, &node_id).await?;
+    let (_, path) = archive_file_path(&app, &state, node_id).await?;
+    let entries = list_archive_entries(&path)
+        .map_err(|e| AppError::coded_with(MessageCode::InvalidArchive, "detail", e))?;
+
+    Ok(ArchivePreview {
+        supported_count: entries.iter().filter(|entry| entry.is_supported()).count(),
+        total_size: entries.iter().map(|entry| entry.size).sum(),
+        entries,
+        max_entries: MAX_ARCHIVE_ENTRIES,
+        max_entry_size: MAX_ENTRY_BYTES,
+        max_total_size: MAX_TOTAL_BYTES,
+    })
+}
+
+/// 展开压缩包：解压受支持的条目为子资源，以 contains 关系挂在压缩包下，随后在后台逐个解析并入队
+///
+/// 内容哈希已存在的条目只建立关系；重复展开不会产生重复资源。
+/// 解压与解析进度通过 archive-expand-progress 事件通知
+#[tauri::command]
+pub async fn expand_archive(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    node_id: NodeRef,
+) -> AppResult<ArchiveExpandSummary> {
+    let node_id = resolve_node_ref(&state.db, &node_id).await?;
+    let (archive, path) = archive_file_path(&app, &state, node_id).await?;
+    let assets_dir = get_assets_dir(&app)?;
+    let meta = archive.source_meta.map(|meta| meta.0);
+
+    // 1. 解压到 assets
+    let app_handle = app.clone();
+    let dest_dir = assets_dir.clone();
+    let extraction = tauri::async_runtime::spawn_blocking(move || {
+        let mut processed = 0;
+        extract_archive(&path, &dest_dir, &mut |extracted| {
+            processed += 1;
+            emit_event(
+                &app_handle,
+                &ArchiveExpandProgressPayload {
+                    node_id,
+                    status: "extracting",
+                    processed,
+                    total: None,
+                    entry_path: Some(extracted.entry.path.clone()),
+                    child_node_id: None,
+                    error: None,
+                },
+            );
+        })
+    })
+    .await
+    .map_err(|e| AppError::coded_with(MessageCode::InvalidArchive, "detail", e))?
+    .map_err(|e| AppError::coded_with(MessageCode::InvalidArchive, "detail", e))?;
+
+    let mut summary = ArchiveExpandSummary {
+        skipped_unsupported: extraction.skipped_unsupported,
+        skipped_too_large: extraction.skipped_too_large,
+        truncated: extraction.truncated,
+        ..Default::default()
+    };
+
+    // 2. 已存在的内容只建立关系，其余在同一事务中创建节点
+    let mut child_ids = Vec::new();
+    let mut builders = Vec::new();
+    let mut created = Vec::new();
+    for extracted in extraction.extracted {
+        if let Some(existing_id) = find_resource_by_hash(&state.db, &extracted.file_hash).await? {
+            let _ = fs::remove_file(assets_dir.join(&extracted.file_name));
+            summary.linked_existing += 1;
+            child_ids.push(existing_id);
+            continue;
+        }
+        let stored_path = format!("assets/{}", extracted.file_name);
+        builders.push(
+            NodeBuilder::resource()
+                .uuid(&extracted.uuid)
+                .title(extracted.entry.file_name())
+                .file_hash(Some(&extracted.file_hash))
+                .file_path(Some(&stored_path))
+                .resource_subtype(Some(extracted.entry.subtype))
+                .source_meta(meta.clone()),
+        );
+        created.push((extracted, stored_path));
+    }
+    let node_ids = NodeBuilder::insert_batch(&builders, &state.db).await?;
+    child_ids.extend(&node_ids);
+    for &child_id in &child_ids {
+        if child_id == node_id {
+            continue;
+        }
+        insert_edge_if_missing(
+            &state.db,
+            NewEdge {
+                source_node_id: node_id,
+                target_node_id: child_id,
+                relation_type: EdgeRelationType::Contains,
+                confidence_score: None,
+                is_manual: true,
+            },
+        )
+        .await?;
+    }
+    summary.created_node_ids = node_ids.clone();
+    tracing::info!(
+        node_id,
+        created = node_ids.len(),
+        linked = summary.linked_existing,
+        "Archive expanded"
+    );
+
+    // 3. 后台逐个解析并入队
+    let app_handle = app.clone();
+    let state = state.inner().clone();
+    let total = node_ids.len();
+    tauri::async_runtime::spawn(async move {
+        for (processed, ((extracted, stored_path), child_id)) in
+            created.into_iter().zip(node_ids).enumerate()
+        {
+            let result = match resolve_file_path(&app_handle, &stored_path) {
+                Ok(resolved) => {
+                    parse_and_enqueue(
+                        &app_handle,
+                        &state,
+                        child_id,
+                        extracted.entry.subtype,
+                        None,
+                        Some(&resolved),
+                        &extracted.file_hash,
+                        JobPriority::Bulk,
+                    )
+                    .await
+                }
+                Err(err) => Ok(Some(err)),
+            };
+            let error = result.unwrap_or_else(|err| Some(err.to_string()));
+            let status = if error.is_some() { "error" } else { "importing" };
+            emit_event(
+                &app_handle,
+                &ArchiveExpandProgressPayload {
+                    node_id,
+                    status,
+                    processed: processed + 1,
+                    total: Some(total),
+                    entry_path: Some(extracted.entry.path),
+                    child_node_id: Some(child_id),
+                    error,
+                },
+            );
+        }
+        emit_event(
+            &app_handle,
+            &ArchiveExpandProgressPayload {
+                node_id,
+                status: "done",
+                processed: total,
+                total: Some(total),
+                entry_path: None,
+                child_node_id: None,
+                error: None,
+            },
+        );
+    });
+
+    Ok(summary)
+}
+
 // ========== 查询资源 ==========
 
 #[tauri::command]
@@ -276,6 +848,18 @@ pub async fn get_all_resources(state: State<'_, AppState>) -> AppResult<Vec<Node
     Ok(list_all_resources(&state.db).await?)
 }
 
+/// 按捕获来源筛选资源（如 "Chrome" / "Zotero"）
+#[tauri::command]
+pub async fn list_resources_by_source_command(
+    state: State<'_, AppState>,
+    process_name: Option<String>,
+    window_title: Option<String>,
+) -> AppResult<Vec<NodeRecord>> {
+    let process_name = process_name.as_deref().filter(|v| !v.trim().is_empty());
+    let window_title = window_title.as_deref().filter(|v| !v.trim().is_empty());
+    Ok(list_resources_by_source(&state.db, process_name, window_title).await?)
+}
+
 #[tauri::command]
 pub fn get_assets_path(app: AppHandle) -> AppResult<String> {
     let path = get_assets_dir(&app)?;
@@ -285,56 +869,254 @@ pub fn get_assets_path(app: AppHandle) -> AppResult<String> {
 #[tauri::command]
 pub async fn get_resource_by_id(
     state: State<'_, AppState>,
-    node_id: i64,
+    node_id: NodeRef,
 ) -> AppResult<NodeRecord> {
+    let node_id = resolve_node_ref(&state.db, &node_id).await?;
     Ok(get_node_by_id(&state.db, node_id).await?)
 }
 
 // ========== 更新资源 ==========
 
+/// 保存正文，返回新的版本号；expected_version 与当前版本不一致时拒绝写入
 #[tauri::command]
 pub async fn update_resource_content_command(
     state: State<'_, AppState>,
-    node_id: i64,
+    node_id: NodeRef,
     content: String,
-) -> AppResult<()> {
+    expected_version: Option<i64>,
+) -> AppResult<i64> {
+    let node_id = resolve_node_ref(&state.db, &node_id).await?;
     let file_hash = compute_sha256(content.as_bytes());
-    update_node_content(&state.db, node_id, Some(&content), Some(&file_hash)).await?;
-    state.ai_pipeline.enqueue_resource(node_id).await?;
-    Ok(())
+    let replacement = replace_node_content(
+        &state.db,
+        node_id,
+        &content,
+        Some(&file_hash),
+        ContentRevisionReason::Edit,
+        expected_version,
+    )
+    .await?;
+    state
+        .ai_pipeline
+        .enqueue_resource(node_id, JobPriority::Interactive)
+        .await?;
+    Ok(replacement.version)
+}
+
+// ========== 查找替换 ==========
+
+fn compile_note_pattern(pattern: &str, regex: Option<bool>) -> AppResult<NotePattern> {
+    NotePattern::new(pattern, regex.unwrap_or(false)).map_err(|detail| {
+        AppError::coded_with(MessageCode::InvalidSearchPattern, "detail", detail)
+    })
+}
+
+/// 在所有文本笔记中查找，返回有匹配的节点及匹配预览
+#[tauri::command]
+pub async fn find_in_notes(
+    state: State<'_, AppState>,
+    pattern: String,
+    regex: Option<bool>,
+) -> AppResult<Vec<NoteFindResult>> {
+    let pattern = compile_note_pattern(&pattern, regex)?;
+    let mut results = Vec::new();
+    for node in list_text_resources(&state.db).await? {
+        let Some(content) = node.file_content.as_deref() else {
+            continue;
+        };
+        let (match_count, previews) = pattern.find(content);
+        if match_count > 0 {
+            results.push(NoteFindResult {
+                node_id: node.node_id,
+                title: node.title,
+                match_count,
+                previews,
+            });
+        }
+    }
+    Ok(results)
+}
+
+/// 在选中的文本笔记中替换；每个改动的节点保存正文版本、记录变更并重新生成向量
+#[tauri::command]
+pub async fn replace_in_notes(
+    state: State<'_, AppState>,
+    pattern: String,
+    replacement: String,
+    node_ids: Vec<NodeRef>,
+    regex: Option<bool>,
+) -> AppResult<NoteReplaceReport> {
+    let node_ids = resolve_node_refs(&state.db, &node_ids).await?;
+    let compiled = compile_note_pattern(&pattern, regex)?;
+    let mut report = NoteReplaceReport::default();
+    for node_id in node_ids {
+        let node = get_node_by_id(&state.db, node_id).await?;
+        let replaced = match (&node.resource_subtype, node.file_content.as_deref()) {
+            (Some(ResourceSubtype::Text), Some(content))
+                if node.node_type == NodeType::Resource && !node.is_deleted =>
+            {
+                compiled.replace(content, &replacement)
+            }
+            _ => None,
+        };
+        let Some((content, replacement_count)) = replaced else {
+            report.skipped.push(node_id);
+            continue;
+        };
+
+        let file_hash = compute_sha256(content.as_bytes());
+        // 读取后被修改过的笔记跳过，不覆盖新的内容
+        let saved = match replace_node_content(
+            &state.db,
+            node_id,
+            &content,
+            Some(&file_hash),
+            ContentRevisionReason::Edit,
+            Some(node.version),
+        )
+        .await
+        {
+            Ok(saved) => saved,
+            Err(err) if err.code() == MessageCode::NodeEditConflict => {
+                report.skipped.push(node_id);
+                continue;
+            }
+            Err(err) => return Err(err),
+        };
+        // 记录查找与替换的文本；替换前的正文见 reason 指向的版本
+        let revision = saved
+            .revision_id
+            .map(|revision_id| format!("content_revision:{revision_id}"));
+        insert_node_revision_log(
+            &state.db,
+            NewNodeRevisionLog {
+                node_id,
+                field_name: "find_replace",
+                old_value: Some(&pattern),
+                new_value: Some(&replacement),
+                reason: revision.as_deref(),
+                provider: None,
+                model: None,
+                confidence_score: None,
+            },
+        )
+        .await?;
+        mark_resource_embedding_dirty(&state.db, node_id).await?;
+        state
+            .ai_pipeline
+            .enqueue_resource(node_id, JobPriority::Normal)
+            .await?;
+
+        report.total_replacements += replacement_count;
+        report.replaced.push(NoteReplaceResult {
+            node_id,
+            title: node.title,
+            replacement_count,
+            revision_id: saved.revision_id,
+        });
+    }
+    Ok(report)
+}
+
+/// 资源正文的历史版本（最新的在前，只含开头预览）
+#[tauri::command]
+pub async fn list_resource_revisions(
+    state: State<'_, AppState>,
+    node_id: NodeRef,
+) -> AppResult<Vec<ContentRevisionRecord>> {
+    let node_id = resolve_node_ref(&state.db, &node_id).await?;
+    Ok(list_content_revisions(&state.db, node_id).await?)
+}
+
+/// 恢复正文到指定版本并重新入队生成向量；恢复前的正文同样保存为版本
+#[tauri::command]
+pub async fn restore_resource_revision(
+    state: State<'_, AppState>,
+    revision_id: i64,
+) -> AppResult<NodeRecord> {
+    let revision = get_content_revision(&state.db, revision_id)
+        .await?
+        .ok_or(AppError::NotFound {
+            entity: "content_revision",
+            id: revision_id,
+        })?;
+    let file_hash = revision
+        .content_hash
+        .unwrap_or_else(|| compute_sha256(revision.content.as_bytes()));
+    replace_node_content(
+        &state.db,
+        revision.node_id,
+        &revision.content,
+        Some(&file_hash),
+        ContentRevisionReason::Restore,
+        None,
+    )
+    .await?;
+    state
+        .ai_pipeline
+        .enqueue_resource(revision.node_id, JobPriority::Interactive)
+        .await?;
+    Ok(get_node_by_id(&state.db, revision.node_id).await?)
 }
 
 #[tauri::command]
 pub async fn update_resource_title_command(
     state: State<'_, AppState>,
-    node_id: i64,
+    node_id: NodeRef,
     title: String,
-) -> AppResult<()> {
+    expected_version: Option<i64>,
+) -> AppResult<i64> {
+    let node_id = resolve_node_ref(&state.db, &node_id).await?;
     let title = validate_title(&title)?;
-    Ok(update_node_title(&state.db, node_id, title).await?)
+    update_node_text_checked(
+        &state.db,
+        node_id,
+        NodeTextField::Title,
+        Some(title),
+        expected_version,
+    )
+    .await
 }
 
 #[tauri::command]
 pub async fn update_resource_summary_command(
     state: State<'_, AppState>,
-    node_id: i64,
+    node_id: NodeRef,
     summary: Option<String>,
-) -> AppResult<()> {
-    Ok(update_node_summary(&state.db, node_id, summary.as_deref()).await?)
+    expected_version: Option<i64>,
+) -> AppResult<i64> {
+    let node_id = resolve_node_ref(&state.db, &node_id).await?;
+    update_node_text_checked(
+        &state.db,
+        node_id,
+        NodeTextField::Summary,
+        summary.as_deref(),
+        expected_version,
+    )
+    .await
 }
 
 #[tauri::command]
 pub async fn update_resource_user_note_command(
     state: State<'_, AppState>,
-    node_id: i64,
+    node_id: NodeRef,
     user_note: String,
-) -> AppResult<()> {
+    expected_version: Option<i64>,
+) -> AppResult<i64> {
+    let node_id = resolve_node_ref(&state.db, &node_id).await?;
     let note = if user_note.trim().is_empty() {
         None
     } else {
         Some(user_note.as_str())
     };
-    Ok(update_node_user_note(&state.db, node_id, note).await?)
+    update_node_text_checked(
+        &state.db,
+        node_id,
+        NodeTextField::UserNote,
+        note,
+        expected_version,
+    )
+    .await
 }
 
 // ========== 删除资源 ==========
@@ -342,16 +1124,18 @@ pub async fn update_resource_user_note_command(
 #[tauri::command]
 pub async fn soft_delete_resource_command(
     state: State<'_, AppState>,
-    node_id: i64,
+    node_id: NodeRef,
 ) -> AppResult<()> {
+    let node_id = resolve_node_ref(&state.db, &node_id).await?;
     Ok(soft_delete_node(&state.db, node_id).await?)
 }
 
 #[tauri::command]
 pub async fn hard_delete_resource_command(
     state: State<'_, AppState>,
-    node_id: i64,
+    node_id: NodeRef,
 ) -> AppResult<()> {
+    let node_id = resolve_node_ref(&state.db, &node_id).await?;
     Ok(hard_delete_node(&state.db, node_id).await?)
 }
 
@@ -363,7 +1147,7 @@ pub async fn process_pending_resources_command(state: State<'_, AppState>) -> Ap
         .ai
         .wait_ready()
         .await
-        .map_err(|e| AppError::AiService(format!("AI 服务未就绪: {e}")))?;
+        .map_err(|e| AppError::coded_with(MessageCode::AiNotReady, "detail", e))?;
     let count = state
         .ai_pipeline
         .enqueue_pending_resources(&state.db)
@@ -371,3 +1155,236 @@ pub async fn process_pending_resources_command(state: State<'_, AppState>) -> Ap
         .map_err(|e| AppError::AiService(format!("处理资源失败: {e}")))?;
     Ok(count)
 }
+
+/// 统计资源的向量化状态，供设置页查看处理进度与失败原因
+#[tauri::command]
+pub async fn get_embedding_status_report(
+    state: State<'_, AppState>,
+) -> AppResult<EmbeddingStatusReport> {
+    let counts = count_resources_by_embedding_state(&state.db).await?;
+    let (oldest_pending_at, oldest_pending_age_secs) = oldest_pending_resource(&state.db).await?;
+    let top_errors = count_embedding_errors(&state.db, TOP_EMBEDDING_ERRORS).await?;
+    Ok(EmbeddingStatusReport {
+        total: counts.iter().map(|group| group.count).sum(),
+        counts,
+        oldest_pending_at,
+        oldest_pending_age_secs,
+        top_errors,
+        queue_idle: state.ai_pipeline.is_idle().await,
+    })
+}
+
+/// AI 队列进度：排队 / 处理中的资源数及其处理阶段，供进度面板轮询
+#[tauri::command]
+pub async fn get_pipeline_status(state: State<'_, AppState>) -> AppResult<PipelineStatus> {
+    let stages = count_pipeline_jobs_by_stage(&state.db).await?;
+    let count_status = |status: PipelineJobStatus| -> i64 {
+        stages
+            .iter()
+            .filter(|group| group.status == status)
+            .map(|group| group.count)
+            .sum()
+    };
+    Ok(PipelineStatus {
+        queued: count_status(PipelineJobStatus::Queued),
+        processing: count_status(PipelineJobStatus::Processing),
+        stages,
+        stopped: state.ai_pipeline.is_stopped().await,
+        paused: state.ai_pipeline.is_paused().await,
+    })
+}
+
+/// 暂停 AI 队列（如按量计费的 API Key），正在处理的资源会处理完；排队的资源保留
+#[tauri::command]
+pub async fn pause_ai_pipeline(state: State<'_, AppState>) -> AppResult<()> {
+    state.ai_pipeline.pause().await;
+    Ok(())
+}
+
+/// 恢复 AI 队列，按原顺序继续处理
+#[tauri::command]
+pub async fn resume_ai_pipeline(state: State<'_, AppState>) -> AppResult<()> {
+    state.ai_pipeline.resume().await;
+    Ok(())
+}
+
+/// 把向量化失败的资源恢复为待处理并重新入队（如服务商故障恢复后），返回入队数量
+#[tauri::command]
+pub async fn requeue_failed_embeddings(
+    state: State<'_, AppState>,
+    filter: Option<RequeueEmbeddingsFilter>,
+) -> AppResult<usize> {
+    let filter = filter.unwrap_or_default();
+    let error_contains = filter
+        .error_contains
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+    let node_ids = list_failed_embedding_resources(
+        &state.db,
+        error_contains,
+        filter.failed_since.as_deref(),
+        filter.include_pending,
+    )
+    .await?;
+    for &node_id in &node_ids {
+        reset_resource_embedding_error(&state.db, node_id).await?;
+        state
+            .ai_pipeline
+            .enqueue_resource(node_id, JobPriority::Bulk)
+            .await?;
+    }
+    Ok(node_ids.len())
+}
+
+/// 重试已用完自动重试次数的失败资源：清零尝试次数并重新入队；node_ids 为空时重试全部
+#[tauri::command]
+pub async fn retry_failed_resources(
+    state: State<'_, AppState>,
+    node_ids: Option<Vec<NodeRef>>,
+) -> AppResult<usize> {
+    let node_ids = match node_ids {
+        Some(nodes) => resolve_node_refs(&state.db, &nodes).await?,
+        None => list_failed_embedding_resources(&state.db, None, None, false).await?,
+    };
+    for &node_id in &node_ids {
+        reset_resource_embedding_error(&state.db, node_id).await?;
+        state
+            .ai_pipeline
+            .enqueue_resource(node_id, JobPriority::Normal)
+            .await?;
+    }
+    Ok(node_ids.len())
+}
+
+/// 批量重处理 / 导入前估算 token、LLM 费用与嵌入耗时
+#[tauri::command]
+pub async fn estimate_processing_cost(
+    state: State<'_, AppState>,
+    node_ids: Option<Vec<NodeRef>>,
+    paths: Option<Vec<String>>,
+) -> AppResult<ProcessingCostEstimate> {
+    let node_ids = resolve_node_refs(&state.db, &node_ids.unwrap_or_default()).await?;
+    let targets: Vec<CostEstimateTarget> = node_ids
+        .into_iter()
+        .map(CostEstimateTarget::Node)
+        .chain(paths.unwrap_or_default().into_iter().map(CostEstimateTarget::Path))
+        .collect();
+    if targets.is_empty() {
+        return Err(AppError::coded(MessageCode::CostEstimateInputMissing));
+    }
+
+    let config = state.ai_config.lock().await.load()?;
+    Ok(build_processing_cost_estimate(&state.db, &config, &targets).await?)
+}
+
+/// 立即对单个资源重新分类，hint 为用户给出的提示（如“应归入机器学习”）
+///
+/// 分到新主题后移除其他主题指向该资源的自动 contains 边，手动建立的保留
+#[tauri::command]
+pub async fn reclassify_resource_command(
+    state: State<'_, AppState>,
+    node_id: NodeRef,
+    hint: Option<String>,
+) -> AppResult<ReclassifyOutcome> {
+    let node_id = resolve_node_ref(&state.db, &node_id).await?;
+    let node = get_node_by_id(&state.db, node_id).await?;
+    if node.node_type != NodeType::Resource || node.is_deleted {
+        return Err(AppError::coded(MessageCode::NotAResource));
+    }
+
+    let ai = state.ai.wait_ready().await?;
+    let (provider, model, classification_mode, provider_config) =
+        get_processing_config(&state.ai_config).await?;
+    Ok(reclassify_resource(
+        &state.db,
+        &ai,
+        &provider,
+        &model,
+        &provider_config,
+        classification_mode,
+        &node,
+        hint.as_deref(),
+    )
+    .await?)
+}
+
+/// 待审核的资源分类，附 AI 建议的主题与置信度
+#[tauri::command]
+pub async fn list_unreviewed_resources(
+    state: State<'_, AppState>,
+) -> AppResult<Vec<UnreviewedClassificationRecord>> {
+    Ok(list_unreviewed_classifications(&state.db).await?)
+}
+
+/// 资源上自动建立的 contains 边的主题 ID；指定 topic_id 时只取该主题
+async fn auto_topic_ids(
+    state: &AppState,
+    node_id: i64,
+    topic_id: Option<i64>,
+) -> AppResult<Vec<i64>> {
+    let node = get_node_by_id(&state.db, node_id).await?;
+    if node.node_type != NodeType::Resource || node.is_deleted {
+        return Err(AppError::coded(MessageCode::NotAResource));
+    }
+    let topic_ids = list_edges_to(&state.db, node_id, EdgeRelationType::Contains)
+        .await?
+        .into_iter()
+        .filter(|edge| !edge.is_manual)
+        .map(|edge| edge.source_node_id)
+        .filter(|source_id| topic_id.is_none_or(|topic_id| topic_id == *source_id))
+        .collect();
+    Ok(topic_ids)
+}
+
+/// 接受 AI 的分类：自动 contains 边转为手动（重新分类时不再移除），资源标记为已审核
+#[tauri::command]
+pub async fn approve_classification(
+    state: State<'_, AppState>,
+    node_id: NodeRef,
+    topic_id: Option<NodeRef>,
+) -> AppResult<()> {
+    let node_id = resolve_node_ref(&state.db, &node_id).await?;
+    let topic_id = match topic_id {
+        Some(topic) => Some(resolve_node_ref(&state.db, &topic).await?),
+        None => None,
+    };
+    for source_id in auto_topic_ids(&state, node_id, topic_id).await? {
+        confirm_edge(&state.db, source_id, node_id, EdgeRelationType::Contains).await?;
+    }
+    update_resource_review_status(&state.db, node_id, ReviewStatus::Reviewed).await?;
+    Ok(())
+}
+
+/// 拒绝 AI 的分类：移除自动 contains 边，资源标记为已拒绝
+///
+/// reclassify 为 true 时随后按 hint 重新分类，审核状态由新的分类结果决定
+#[tauri::command]
+pub async fn reject_classification(
+    state: State<'_, AppState>,
+    node_id: NodeRef,
+    topic_id: Option<NodeRef>,
+    reclassify: Option<bool>,
+    hint: Option<String>,
+) -> AppResult<RejectClassificationResponse> {
+    let node_id = resolve_node_ref(&state.db, &node_id).await?;
+    let topic_id = match topic_id {
+        Some(topic) => Some(resolve_node_ref(&state.db, &topic).await?),
+        None => None,
+    };
+    let removed_topic_ids = auto_topic_ids(&state, node_id, topic_id).await?;
+    for source_id in &removed_topic_ids {
+        delete_edge(&state.db, *source_id, node_id, EdgeRelationType::Contains).await?;
+    }
+    update_resource_review_status(&state.db, node_id, ReviewStatus::Rejected).await?;
+
+    let reclassified = if reclassify.unwrap_or(false) {
+        Some(reclassify_resource_command(state, NodeRef::Id(node_id), hint).await?)
+    } else {
+        None
+    };
+    Ok(RejectClassificationResponse {
+        removed_topic_ids,
+        reclassified,
+    })
+}
@@ -0,0 +1,91 @@
+//! 保留策略命令：管理规则、预览与立即执行、手动归档
+
+use tauri::State;
+
+use crate::db::{self, RetentionRuleInput, RetentionRuleRecord};
+use crate::services::{
+    preview_retention, run_retention_rules, validate_retention_rule, RetentionPreview,
+    RetentionRunResult,
+};
+use crate::{AppError, AppResult, AppState};
+
+#[tauri::command]
+pub async fn list_retention_rules(
+    state: State<'_, AppState>,
+) -> AppResult<Vec<RetentionRuleRecord>> {
+    Ok(db::list_retention_rules(&state.db).await?)
+}
+
+/// 新建（rule_id 为空）或更新保留规则
+#[tauri::command]
+pub async fn save_retention_rule(
+    state: State<'_, AppState>,
+    rule_id: Option<i64>,
+    rule: RetentionRuleInput,
+) -> AppResult<RetentionRuleRecord> {
+    let rule = validate_retention_rule(&state.db, rule).await?;
+    let rule_id = match rule_id {
+        Some(id) => {
+            if !db::update_retention_rule(&state.db, id, &rule).await? {
+                return Err(AppError::NotFound {
+                    entity: "retention_rule",
+                    id,
+                });
+            }
+            id
+        }
+        None => db::insert_retention_rule(&state.db, &rule).await?,
+    };
+    db::get_retention_rule(&state.db, rule_id)
+        .await?
+        .ok_or(AppError::NotFound {
+            entity: "retention_rule",
+            id: rule_id,
+        })
+}
+
+/// 删除规则；已归档或移入回收站的资源保持不变
+#[tauri::command]
+pub async fn delete_retention_rule(state: State<'_, AppState>, rule_id: i64) -> AppResult<()> {
+    if !db::delete_retention_rule(&state.db, rule_id).await? {
+        return Err(AppError::NotFound {
+            entity: "retention_rule",
+            id: rule_id,
+        });
+    }
+    Ok(())
+}
+
+/// 预览规则当前会处理的资源（dry run），规则无需先保存
+#[tauri::command]
+pub async fn preview_retention_rule(
+    state: State<'_, AppState>,
+    rule: RetentionRuleInput,
+) -> AppResult<RetentionPreview> {
+    let rule = validate_retention_rule(&state.db, rule).await?;
+    preview_retention(&state.db, &rule).await
+}
+
+/// 立即执行全部启用的规则
+#[tauri::command]
+pub async fn run_retention_rules_now(
+    state: State<'_, AppState>,
+) -> AppResult<Vec<RetentionRunResult>> {
+    run_retention_rules(&state.db).await
+}
+
+/// 手动归档 / 取消归档；归档的节点不参与语义搜索与对话检索
+#[tauri::command]
+pub async fn set_node_archived(
+    state: State<'_, AppState>,
+    node_id: i64,
+    archived: bool,
+) -> AppResult<()> {
+    if !db::update_node_archived(&state.db, node_id, archived).await? {
+        return Err(AppError::NotFound {
+            entity: "node",
+            id: node_id,
+        });
+    }
+    Ok(())
+}
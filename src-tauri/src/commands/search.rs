@@ -2,12 +2,20 @@
 //!
 //! 提供语义搜索和精确搜索两种搜索方式
 
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use crate::db::{self, NodeRecord, NodeType};
+use crate::db::{
+    self, DbPool, NodeFullTextRow, NodeRecord, NodeTitleRow, NodeType, ResourceSubtype,
+};
 use crate::error::AppError;
+use crate::i18n::MessageCode;
+use crate::services::{
+    self, EmbeddingModelSelection, JobKind, SearchResult, Snippet, StoredChunk, VaultSearchFilter,
+    VectorIndexStatus, VectorMaintenanceReport, VectorStats,
+};
 use crate::{AppResult, AppState};
 
 /// 搜索结果节点摘要
@@ -19,6 +27,17 @@ pub struct NodeSearchSummary {
     pub summary: Option<String>,
 }
 
+impl From<NodeTitleRow> for NodeSearchSummary {
+    fn from(row: NodeTitleRow) -> Self {
+        Self {
+            node_id: row.node_id,
+            node_type: row.node_type,
+            title: row.title,
+            summary: row.summary,
+        }
+    }
+}
+
 /// 语义搜索结果项
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SemanticSearchResult {
@@ -26,6 +45,81 @@ pub struct SemanticSearchResult {
     pub score: f64,
 }
 
+/// 全文搜索结果项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FullTextSearchResult {
+    pub node: NodeSearchSummary,
+    /// 命中片段，命中词以 `[` `]` 标出
+    pub snippet: Option<String>,
+    /// bm25 得分，越小越相关
+    pub rank: f64,
+}
+
+impl From<NodeFullTextRow> for FullTextSearchResult {
+    fn from(row: NodeFullTextRow) -> Self {
+        Self {
+            node: NodeSearchSummary {
+                node_id: row.node_id,
+                node_type: row.node_type,
+                title: row.title,
+                summary: row.summary,
+            },
+            snippet: row.snippet,
+            rank: row.rank,
+        }
+    }
+}
+
+/// 统一搜索请求
+#[derive(Debug, Deserialize)]
+pub struct VaultSearchRequest {
+    pub query: String,
+    pub node_type: Option<NodeType>,
+    pub resource_subtype: Option<ResourceSubtype>,
+    /// 创建日期下限 `YYYY-MM-DD`（含）
+    pub created_from: Option<String>,
+    /// 创建日期上限 `YYYY-MM-DD`（含）
+    pub created_to: Option<String>,
+    pub topic_id: Option<i64>,
+    /// 仅搜索同时带有这些标签的节点
+    pub tag_ids: Option<Vec<i64>>,
+    pub exclude_node_ids: Option<Vec<i64>>,
+    /// 排除这些主题及其包含的节点
+    pub exclude_topic_ids: Option<Vec<i64>>,
+    pub limit: Option<i32>,
+}
+
+/// 统一搜索结果项
+#[derive(Debug, Clone, Serialize)]
+pub struct VaultSearchResult {
+    pub node: NodeSearchSummary,
+    /// RRF 融合得分，越大越相关
+    pub score: f64,
+    /// 命中片段与高亮区间（UTF-16 下标）
+    pub snippet: Option<Snippet>,
+    pub keyword_match: bool,
+    pub semantic_match: bool,
+}
+
+/// 快速搜索结果来源
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuickSearchSource {
+    /// 标题匹配
+    Title,
+    /// 之前语义搜索的缓存结果
+    Cached,
+    /// 最近打开或更新（输入为空时）
+    Recent,
+}
+
+/// 快速搜索结果项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuickSearchResult {
+    pub node: NodeSearchSummary,
+    pub source: QuickSearchSource,
+}
+
 /// Embedding 模型预热（搜索用）
 #[tauri::command]
 pub async fn warmup_embedding(state: tauri::State<'_, AppState>) -> AppResult<()> {
@@ -33,7 +127,7 @@ pub async fn warmup_embedding(state: tauri::State<'_, AppState>) -> AppResult<()
         .ai
         .wait_ready()
         .await
-        .map_err(|e| AppError::AiService(format!("AI 服务未就绪: {}", e)))?;
+        .map_err(|e| AppError::coded_with(MessageCode::AiNotReady, "detail", e))?;
 
     ai.embedding
         .warmup_search()
@@ -43,9 +137,86 @@ pub async fn warmup_embedding(state: tauri::State<'_, AppState>) -> AppResult<()
     Ok(())
 }
 
+/// 向量库统计：按 embedding_type / vector_kind / 模型分组的行数与磁盘占用
+#[tauri::command]
+pub async fn get_vector_stats(state: tauri::State<'_, AppState>) -> AppResult<VectorStats> {
+    let ai = state
+        .ai
+        .wait_ready()
+        .await
+        .map_err(|e| AppError::coded_with(MessageCode::AiNotReady, "detail", e))?;
+
+    ai.embedding
+        .vector_stats()
+        .await
+        .map_err(|e| AppError::AiService(format!("读取向量库统计失败: {}", e)))
+}
+
+/// 重建向量列的 ANN 索引（行数不足以训练的列跳过），之后新增的行在下次重建前按暴力检索
+#[tauri::command]
+pub async fn rebuild_vector_index(
+    state: tauri::State<'_, AppState>,
+) -> AppResult<Vec<VectorIndexStatus>> {
+    let ai = state
+        .ai
+        .wait_ready()
+        .await
+        .map_err(|e| AppError::coded_with(MessageCode::AiNotReady, "detail", e))?;
+
+    ai.embedding
+        .rebuild_vector_indexes()
+        .await
+        .map_err(|e| AppError::AiService(format!("重建向量索引失败: {}", e)))
+}
+
+/// 删除已不存在节点的向量并整理向量表（合并 fragment、清理旧版本）；后台每天也会自动执行
+#[tauri::command]
+pub async fn optimize_vector_store(
+    state: tauri::State<'_, AppState>,
+) -> AppResult<VectorMaintenanceReport> {
+    services::run_vector_maintenance(&state).await
+}
+
+/// 换用新的嵌入模型重建全部向量：写入新表，完成后切换配置并删除旧表
+///
+/// 立即返回任务 ID，重建报告见任务结果；进度也见 embedding-reindex-progress 事件
+#[tauri::command]
+pub async fn reindex_embeddings(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    models: EmbeddingModelSelection,
+) -> AppResult<i64> {
+    let state = state.inner().clone();
+    let jobs = state.jobs.clone();
+    let job_app = app.clone();
+    let job_id = jobs.spawn(&app, JobKind::EmbeddingReindex, move |job| async move {
+        services::run_embedding_reindex(&job_app, &state, models, &job).await
+    });
+    Ok(job_id)
+}
+
+/// 节点在向量库中实际存储的分块（不含向量），用于核对索引内容
+#[tauri::command]
+pub async fn list_node_chunks(
+    state: tauri::State<'_, AppState>,
+    node_id: i64,
+) -> AppResult<Vec<StoredChunk>> {
+    let ai = state
+        .ai
+        .wait_ready()
+        .await
+        .map_err(|e| AppError::coded_with(MessageCode::AiNotReady, "detail", e))?;
+
+    ai.embedding
+        .list_node_chunks(node_id)
+        .await
+        .map_err(|e| AppError::AiService(format!("读取向量分块失败: {}", e)))
+}
+
 /// 语义搜索
 ///
-/// 使用 LanceDB 进行混合检索（FTS + dense 向量）
+/// 使用 LanceDB 进行混合检索（FTS + dense 向量）。标记为排除检索的节点、
+/// exclude_node_ids 以及 exclude_topic_ids 中的主题（含其包含的节点）不会出现在结果中
 #[tauri::command]
 pub async fn search_semantic(
     state: tauri::State<'_, AppState>,
@@ -53,8 +224,30 @@ pub async fn search_semantic(
     scope_node_ids: Option<Vec<i64>>,
     embedding_type: Option<String>,
     limit: Option<i32>,
+    exclude_node_ids: Option<Vec<i64>>,
+    exclude_topic_ids: Option<Vec<i64>>,
 ) -> AppResult<Vec<SemanticSearchResult>> {
     let pool = &state.db;
+    let has_query_exclusions = exclude_node_ids.as_ref().is_some_and(|ids| !ids.is_empty())
+        || exclude_topic_ids
+            .as_ref()
+            .is_some_and(|ids| !ids.is_empty());
+    let excluded = db::list_search_excluded_node_ids(
+        pool,
+        exclude_node_ids.as_deref().unwrap_or_default(),
+        exclude_topic_ids.as_deref().unwrap_or_default(),
+    )
+    .await?;
+    let scope_node_ids = match scope_node_ids {
+        Some(mut ids) if !ids.is_empty() => {
+            ids.retain(|id| excluded.binary_search(id).is_err());
+            if ids.is_empty() {
+                return Ok(Vec::new());
+            }
+            Some(ids)
+        }
+        other => other,
+    };
     let embedding_type = embedding_type.unwrap_or_else(|| "content".to_string());
     let limit = limit.unwrap_or(20).max(1) as usize;
     let search_limit = limit as u64;
@@ -62,11 +255,17 @@ pub async fn search_semantic(
         .ai
         .wait_ready()
         .await
-        .map_err(|e| AppError::AiService(format!("AI 服务未就绪: {}", e)))?;
+        .map_err(|e| AppError::coded_with(MessageCode::AiNotReady, "detail", e))?;
 
     let search_response = ai
         .search
-        .search_hybrid(&query, &embedding_type, scope_node_ids.as_deref(), search_limit)
+        .search_hybrid(
+            &query,
+            &embedding_type,
+            scope_node_ids.as_deref(),
+            &excluded,
+            search_limit,
+        )
         .await
         .map_err(|e| AppError::AiService(format!("搜索失败: {}", e)))?;
 
@@ -74,7 +273,77 @@ pub async fn search_semantic(
     // Local scope (有 scope_node_ids): × 1.5
     // Global scope (无 scope_node_ids): × 1.0
     let weight = if scope_node_ids.is_some() { 1.5 } else { 1.0 };
+    let results = rank_nodes(pool, search_response, weight, limit).await?;
+
+    // 全局内容搜索的结果供 quick_search 复用（按查询临时排除的结果不缓存）
+    if scope_node_ids.is_none() && embedding_type == "content" && !has_query_exclusions {
+        let hits = results
+            .iter()
+            .map(|result| (result.node.node_id, result.score))
+            .collect();
+        state.search_cache.store(&query, hits);
+    }
+
+    Ok(results)
+}
+
+/// 以图搜图：按 CLIP 图片向量查找相似的图片资源
+///
+/// 给出 query 时再按文字做一次混合搜索，两路结果按排名（RRF）融合
+#[tauri::command]
+pub async fn search_by_image(
+    state: tauri::State<'_, AppState>,
+    path: String,
+    limit: Option<i32>,
+    query: Option<String>,
+    scope_node_ids: Option<Vec<i64>>,
+    exclude_node_ids: Option<Vec<i64>>,
+    exclude_topic_ids: Option<Vec<i64>>,
+) -> AppResult<Vec<SemanticSearchResult>> {
+    if !std::path::Path::new(&path).is_file() {
+        return Err(AppError::coded_with(
+            MessageCode::ImageNotFound,
+            "path",
+            &path,
+        ));
+    }
+    let pool = &state.db;
+    let excluded = db::list_search_excluded_node_ids(
+        pool,
+        exclude_node_ids.as_deref().unwrap_or_default(),
+        exclude_topic_ids.as_deref().unwrap_or_default(),
+    )
+    .await?;
+    let scope_node_ids = scope_node_ids.filter(|ids| !ids.is_empty());
+    let limit = limit.unwrap_or(20).max(1) as usize;
+    let ai = state
+        .ai
+        .wait_ready()
+        .await
+        .map_err(|e| AppError::coded_with(MessageCode::AiNotReady, "detail", e))?;
+
+    let search_response = ai
+        .search
+        .search_by_image(
+            &path,
+            query.as_deref(),
+            scope_node_ids.as_deref(),
+            &excluded,
+            limit as u64,
+        )
+        .await
+        .map_err(|e| AppError::AiService(format!("搜索失败: {}", e)))?;
 
+    rank_nodes(pool, search_response, 1.0, limit).await
+}
+
+/// 按节点取最高分（乘以 weight），跳过已删除的节点，按分数降序保留前 limit 个
+async fn rank_nodes(
+    pool: &DbPool,
+    search_response: Vec<SearchResult>,
+    weight: f64,
+    limit: usize,
+) -> AppResult<Vec<SemanticSearchResult>> {
     let mut best_scores: HashMap<i64, f64> = HashMap::new();
     for result in search_response {
         let score = result.score * weight;
@@ -112,13 +381,74 @@ pub async fn search_semantic(
 
     results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal));
     results.truncate(limit);
+    Ok(results)
+}
+
+/// 快速搜索（边输入边搜）
+///
+/// 只查询标题和之前语义搜索的缓存结果，不调用 embedding 模型，排除检索或已归档的节点不返回；
+/// 输入为空时返回最近打开或更新的节点。
+/// 完整的混合搜索仍由 `search_semantic` 在回车时执行。
+#[tauri::command]
+pub async fn quick_search(
+    state: tauri::State<'_, AppState>,
+    prefix: String,
+    limit: Option<i32>,
+) -> AppResult<Vec<QuickSearchResult>> {
+    let pool = &state.db;
+    let limit = limit.unwrap_or(10).clamp(1, 50);
+    let prefix = prefix.trim();
+
+    if prefix.is_empty() {
+        let recent = db::list_recent_node_titles(pool, limit).await?;
+        return Ok(recent
+            .into_iter()
+            .map(|row| QuickSearchResult {
+                node: row.into(),
+                source: QuickSearchSource::Recent,
+            })
+            .collect());
+    }
+
+    let mut results: Vec<QuickSearchResult> = db::search_node_titles(pool, prefix, limit)
+        .await?
+        .into_iter()
+        .map(|row| QuickSearchResult {
+            node: row.into(),
+            source: QuickSearchSource::Title,
+        })
+        .collect();
+
+    let remaining = limit as usize - results.len();
+    if remaining > 0 {
+        let seen: HashSet<i64> = results.iter().map(|result| result.node.node_id).collect();
+        let cached_ids: Vec<i64> = state
+            .search_cache
+            .lookup(prefix)
+            .into_iter()
+            .map(|(node_id, _)| node_id)
+            .filter(|node_id| !seen.contains(node_id))
+            .take(remaining)
+            .collect();
+        let mut rows: HashMap<i64, NodeTitleRow> = db::list_node_titles_by_ids(pool, &cached_ids)
+            .await?
+            .into_iter()
+            .map(|row| (row.node_id, row))
+            .collect();
+        results.extend(cached_ids.iter().filter_map(|node_id| {
+            rows.remove(node_id).map(|row| QuickSearchResult {
+                node: row.into(),
+                source: QuickSearchSource::Cached,
+            })
+        }));
+    }
 
     Ok(results)
 }
 
 /// 精确搜索（SQL LIKE）
 ///
-/// 在 title、file_content、user_note 中进行模糊匹配
+/// 在 title、file_content、user_note 中进行模糊匹配；排除检索或已归档的节点不返回
 #[tauri::command]
 pub async fn search_keyword(
     state: tauri::State<'_, AppState>,
@@ -133,6 +463,7 @@ pub async fn search_keyword(
         "topic" => Some(NodeType::Topic),
         "task" => Some(NodeType::Task),
         "resource" => Some(NodeType::Resource),
+        "habit" => Some(NodeType::Habit),
         _ => None,
     });
 
@@ -140,3 +471,69 @@ pub async fn search_keyword(
 
     Ok(results)
 }
+
+/// 全文搜索（SQLite FTS5）
+///
+/// 精确匹配关键词 / 短语（双引号包裹），不调用 embedding 模型；排除检索或已归档的节点不返回
+#[tauri::command]
+pub async fn search_nodes_fulltext(
+    state: tauri::State<'_, AppState>,
+    query: String,
+    node_type: Option<NodeType>,
+    limit: Option<i32>,
+) -> AppResult<Vec<FullTextSearchResult>> {
+    let limit = limit.unwrap_or(20).clamp(1, 100);
+    let rows = db::search_nodes_fulltext(&state.db, &query, node_type, limit).await?;
+    Ok(rows.into_iter().map(Into::into).collect())
+}
+
+/// 统一搜索：全文检索 + 向量混合检索，合并去重并按条件过滤
+///
+/// AI 服务未就绪时只返回关键词结果
+#[tauri::command]
+pub async fn search_vault(
+    state: tauri::State<'_, AppState>,
+    request: VaultSearchRequest,
+) -> AppResult<Vec<VaultSearchResult>> {
+    let filter = VaultSearchFilter {
+        node_type: request.node_type,
+        resource_subtype: request.resource_subtype,
+        created_from: parse_filter_date(request.created_from.as_deref())?,
+        created_to: parse_filter_date(request.created_to.as_deref())?,
+        topic_id: request.topic_id,
+        tag_ids: request.tag_ids.unwrap_or_default(),
+        exclude_node_ids: request.exclude_node_ids.unwrap_or_default(),
+        exclude_topic_ids: request.exclude_topic_ids.unwrap_or_default(),
+    };
+    let limit = request.limit.unwrap_or(20).clamp(1, 100) as usize;
+    let ai = state.ai.try_ready();
+
+    let hits = services::search_vault(&state.db, ai.as_deref(), &request.query, &filter, limit)
+        .await
+        .map_err(|e| AppError::coded_with(MessageCode::SearchFailed, "detail", e))?;
+
+    Ok(hits
+        .into_iter()
+        .map(|hit| VaultSearchResult {
+            node: NodeSearchSummary {
+                node_id: hit.node.node_id,
+                node_type: hit.node.node_type,
+                title: hit.node.title,
+                summary: hit.node.summary,
+            },
+            score: hit.score,
+            snippet: hit.snippet,
+            keyword_match: hit.keyword_match,
+            semantic_match: hit.semantic_match,
+        })
+        .collect())
+}
+
+fn parse_filter_date(raw: Option<&str>) -> AppResult<Option<String>> {
+    match raw.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(value) => NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .map(|date| Some(date.format("%Y-%m-%d").to_string()))
+            .map_err(|_| AppError::coded_with(MessageCode::InvalidDate, "value", value)),
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,82 @@
+//! 备份查看命令：只读打开 archive 导出，浏览与全文检索其中的节点
+
+use std::path::PathBuf;
+
+use tauri::State;
+
+use crate::db::{self, EdgeRelationType, NodeFullTextRow, NodeRecord, NodeType};
+use crate::services::SnapshotInfo;
+use crate::{AppError, AppResult, AppState};
+
+/// 默认返回的检索结果数
+const DEFAULT_SEARCH_LIMIT: i32 = 50;
+
+/// 解压并只读打开 archive 导出，不影响当前数据库
+#[tauri::command]
+pub async fn open_snapshot(state: State<'_, AppState>, path: String) -> AppResult<SnapshotInfo> {
+    state.snapshots.open(&PathBuf::from(path)).await
+}
+
+#[tauri::command]
+pub async fn list_snapshots(state: State<'_, AppState>) -> AppResult<Vec<SnapshotInfo>> {
+    Ok(state.snapshots.list())
+}
+
+/// 关闭备份并删除临时文件
+#[tauri::command]
+pub async fn close_snapshot(state: State<'_, AppState>, session_id: i64) -> AppResult<()> {
+    state.snapshots.close(session_id).await
+}
+
+/// 全文检索备份中的节点（不做语义检索）
+#[tauri::command]
+pub async fn search_snapshot(
+    state: State<'_, AppState>,
+    session_id: i64,
+    query: String,
+    node_type: Option<NodeType>,
+    limit: Option<i32>,
+) -> AppResult<Vec<NodeFullTextRow>> {
+    let pool = state.snapshots.pool(session_id)?;
+    let limit = limit.unwrap_or(DEFAULT_SEARCH_LIMIT).clamp(1, 200);
+    Ok(db::search_nodes_fulltext(&pool, &query, node_type, limit).await?)
+}
+
+/// 备份中某类型的全部未删除节点，最近更新的在前
+#[tauri::command]
+pub async fn list_snapshot_nodes(
+    state: State<'_, AppState>,
+    session_id: i64,
+    node_type: NodeType,
+) -> AppResult<Vec<NodeRecord>> {
+    let pool = state.snapshots.pool(session_id)?;
+    Ok(db::list_nodes_by_type(&pool, node_type, false).await?)
+}
+
+#[tauri::command]
+pub async fn get_snapshot_node(
+    state: State<'_, AppState>,
+    session_id: i64,
+    node_id: i64,
+) -> AppResult<NodeRecord> {
+    let pool = state.snapshots.pool(session_id)?;
+    match db::get_node_by_id(&pool, node_id).await {
+        Ok(node) => Ok(node),
+        Err(sqlx::Error::RowNotFound) => Err(AppError::NotFound {
+            entity: "node",
+            id: node_id,
+        }),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// 备份中主题直接包含的节点
+#[tauri::command]
+pub async fn list_snapshot_topic_contents(
+    state: State<'_, AppState>,
+    session_id: i64,
+    topic_id: i64,
+) -> AppResult<Vec<NodeRecord>> {
+    let pool = state.snapshots.pool(session_id)?;
+    Ok(db::list_target_nodes(&pool, topic_id, EdgeRelationType::Contains).await?)
+}
@@ -0,0 +1,55 @@
+//! 捕获来源统计与来源规则命令
+
+use tauri::State;
+
+use crate::db::{
+    self, delete_source_tag_rule, insert_source_tag_rule, update_source_tag_rule_enabled,
+    SourceTagRuleRecord, TagRecord,
+};
+use crate::services::{build_capture_sources_report, CaptureSourcesReport};
+use crate::utils::{parse_source_rule_field, validate_not_empty};
+use crate::{simple_void_command, AppResult, AppState};
+
+/// 按域名 / 应用 / 窗口标题统计捕获来源
+#[tauri::command]
+pub async fn get_capture_sources_report(
+    state: State<'_, AppState>,
+) -> AppResult<CaptureSourcesReport> {
+    Ok(build_capture_sources_report(&state.db).await?)
+}
+
+/// 新增来源自动打标签规则（如 domain=arxiv.org → papers）
+#[tauri::command]
+pub async fn add_source_tag_rule(
+    state: State<'_, AppState>,
+    match_field: String,
+    pattern: String,
+    tag_name: String,
+) -> AppResult<i64> {
+    let field = parse_source_rule_field(&match_field)?;
+    let pattern = validate_not_empty(&pattern, "pattern")?;
+    let tag_name = validate_not_empty(&tag_name, "tag_name")?;
+    Ok(insert_source_tag_rule(&state.db, field, pattern, tag_name).await?)
+}
+
+#[tauri::command]
+pub async fn list_source_tag_rules_command(
+    state: State<'_, AppState>,
+) -> AppResult<Vec<SourceTagRuleRecord>> {
+    Ok(db::list_source_tag_rules(&state.db).await?)
+}
+
+simple_void_command!(
+    set_source_tag_rule_enabled,
+    update_source_tag_rule_enabled,
+    rule_id: i64,
+    is_enabled: bool
+);
+
+simple_void_command!(remove_source_tag_rule, delete_source_tag_rule, rule_id: i64);
+
+/// 获取节点标签
+#[tauri::command]
+pub async fn list_node_tags(state: State<'_, AppState>, node_id: i64) -> AppResult<Vec<TagRecord>> {
+    Ok(db::list_tags_for_node(&state.db, node_id).await?)
+}
@@ -0,0 +1,113 @@
+//! 标签命令：打标签、重命名与合并
+
+use tauri::State;
+
+use crate::db::{self, NodeRecord, TagRecord, TagSource, TagSuggestionRecord, TagUsageRecord};
+use crate::i18n::MessageCode;
+use crate::utils::validate_not_empty;
+use crate::{AppError, AppResult, AppState};
+
+/// 给节点打标签；同名标签（忽略大小写）复用已有记录
+#[tauri::command]
+pub async fn add_tag(
+    state: State<'_, AppState>,
+    node_id: i64,
+    name: String,
+) -> AppResult<TagRecord> {
+    let name = validate_not_empty(&name, "name")?;
+    let tag_id = db::get_or_create_tag(&state.db, name).await?;
+    db::attach_tag_to_node(&state.db, node_id, tag_id, TagSource::Manual).await?;
+    fetch_tag(&state, tag_id).await
+}
+
+/// 移除节点上的标签；标签本身保留
+#[tauri::command]
+pub async fn remove_tag(state: State<'_, AppState>, node_id: i64, tag_id: i64) -> AppResult<()> {
+    db::detach_tag_from_node(&state.db, node_id, tag_id).await?;
+    Ok(())
+}
+
+/// 全部标签及其使用次数
+#[tauri::command]
+pub async fn list_tags(state: State<'_, AppState>) -> AppResult<Vec<TagUsageRecord>> {
+    Ok(db::list_tags_with_counts(&state.db).await?)
+}
+
+#[tauri::command]
+pub async fn list_nodes_by_tag(
+    state: State<'_, AppState>,
+    tag_id: i64,
+) -> AppResult<Vec<NodeRecord>> {
+    fetch_tag(&state, tag_id).await?;
+    Ok(db::list_nodes_by_tag(&state.db, tag_id).await?)
+}
+
+/// 重命名标签；新名称已被其他标签占用时应改用合并
+#[tauri::command]
+pub async fn rename_tag(
+    state: State<'_, AppState>,
+    tag_id: i64,
+    name: String,
+) -> AppResult<TagRecord> {
+    let name = validate_not_empty(&name, "name")?;
+    fetch_tag(&state, tag_id).await?;
+    if let Some(existing) = db::find_tag_by_name(&state.db, name).await? {
+        if existing.tag_id != tag_id {
+            return Err(AppError::coded_with(
+                MessageCode::TagNameTaken,
+                "name",
+                existing.name,
+            ));
+        }
+    }
+    db::rename_tag(&state.db, tag_id, name).await?;
+    fetch_tag(&state, tag_id).await
+}
+
+/// 把多个标签合并到目标标签，来源标签被删除
+#[tauri::command]
+pub async fn merge_tags(
+    state: State<'_, AppState>,
+    source_tag_ids: Vec<i64>,
+    target_tag_id: i64,
+) -> AppResult<TagRecord> {
+    if source_tag_ids.contains(&target_tag_id) {
+        return Err(AppError::coded(MessageCode::TagMergeIntoSelf));
+    }
+    fetch_tag(&state, target_tag_id).await?;
+    db::merge_tags(&state.db, &source_tag_ids, target_tag_id).await?;
+    fetch_tag(&state, target_tag_id).await
+}
+
+/// AI 处理资源后建议、尚待确认的标签
+#[tauri::command]
+pub async fn list_suggested_tags(
+    state: State<'_, AppState>,
+    node_id: i64,
+) -> AppResult<Vec<TagSuggestionRecord>> {
+    Ok(db::list_pending_tag_suggestions(&state.db, node_id).await?)
+}
+
+/// 采纳或拒绝 AI 建议的标签，返回节点当前的标签
+///
+/// 采纳的建议作为手动标签打到节点上；被拒绝的名称之后不会再建议，未提及的建议保持待确认
+#[tauri::command]
+pub async fn accept_suggested_tags(
+    state: State<'_, AppState>,
+    node_id: i64,
+    accepted_ids: Vec<i64>,
+    rejected_ids: Option<Vec<i64>>,
+) -> AppResult<Vec<TagRecord>> {
+    let rejected_ids = rejected_ids.unwrap_or_default();
+    db::resolve_tag_suggestions(&state.db, node_id, &accepted_ids, &rejected_ids).await?;
+    Ok(db::list_tags_for_node(&state.db, node_id).await?)
+}
+
+async fn fetch_tag(state: &AppState, tag_id: i64) -> AppResult<TagRecord> {
+    db::get_tag(&state.db, tag_id)
+        .await?
+        .ok_or(AppError::NotFound {
+            entity: "tag",
+            id: tag_id,
+        })
+}
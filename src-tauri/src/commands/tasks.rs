@@ -1,21 +1,37 @@
 //! 任务相关命令
 
+use std::collections::HashMap;
+
+use chrono::{Local, NaiveDate};
 use tauri::State;
 
 use crate::{
     app_state::AppState,
     db::{
-        get_node_by_id, hard_delete_node, list_active_tasks, list_all_tasks, list_tasks_by_date,
-        mark_task_cancelled, mark_task_done, mark_task_todo, soft_delete_node,
-        update_node_summary, update_node_title, update_node_user_note, update_task_due_date,
-        update_task_priority, NodeBuilder, NodeRecord, TaskPriority,
+        self, delete_task_reminder, get_node_by_id, get_task_reminder, hard_delete_node,
+        insert_edge_if_missing, list_active_tasks, list_all_tasks, list_board_tasks,
+        list_child_tasks, list_subtask_links, mark_task_cancelled, mark_task_todo,
+        resolve_node_ref, set_parent_task, set_task_reminder_snooze, soft_delete_node,
+        update_node_text_checked, update_task_due_date, update_task_priority,
+        update_task_recurrence_rule, upsert_task_reminder, DbPool, EdgeRelationType, NewEdge,
+        NodeBuilder, NodeRecord, NodeRef, NodeTextField, NodeType, SubtaskLinkRow, TaskPriority,
+        TaskReminderRecord, TaskStatus,
+    },
+    error::AppError,
+    i18n::MessageCode,
+    services::{
+        complete_task, list_tasks_with_occurrences, materialize_next_occurrence, snooze_until,
+        RecurrenceRule, DEFAULT_SNOOZE_MINUTES,
     },
     simple_void_command,
     utils::validate_title,
     AppResult,
 };
 
-use super::{CreateTaskRequest, CreateTaskResponse};
+use super::{
+    CreateRecurringTaskRequest, CreateTaskRequest, CreateTaskResponse, TaskBoardColumn,
+    TaskListItem,
+};
 
 // ========== 简单命令 ==========
 
@@ -46,43 +62,239 @@ pub async fn create_task(
     Ok(CreateTaskResponse { node })
 }
 
+/// 创建重复任务：due_date 为第一次的日期，完成后自动生成下一次
+#[tauri::command]
+pub async fn create_recurring_task(
+    state: State<'_, AppState>,
+    payload: CreateRecurringTaskRequest,
+) -> AppResult<CreateTaskResponse> {
+    let title = validate_title(&payload.title)?;
+    let due_date = payload
+        .due_date
+        .filter(|date| !date.trim().is_empty())
+        .unwrap_or_else(|| Local::now().date_naive().format("%Y-%m-%d").to_string());
+    let rule = normalize_recurrence_rule(&payload.recurrence_rule, &due_date)?;
+
+    let node_id = NodeBuilder::task()
+        .title(title)
+        .priority(payload.priority)
+        .due_date(Some(due_date.as_str()))
+        .user_note(payload.user_note.as_deref())
+        .insert(&state.db)
+        .await?;
+    update_task_recurrence_rule(&state.db, node_id, Some(rule.as_str())).await?;
+
+    let node = get_node_by_id(&state.db, node_id).await?;
+    Ok(CreateTaskResponse { node })
+}
+
+// ========== 子任务 ==========
+
+/// 在父任务下创建子任务
+#[tauri::command]
+pub async fn create_subtask(
+    state: State<'_, AppState>,
+    parent_task_id: NodeRef,
+    payload: CreateTaskRequest,
+) -> AppResult<CreateTaskResponse> {
+    let title = validate_title(&payload.title)?;
+    let parent_task_id = resolve_node_ref(&state.db, &parent_task_id).await?;
+    fetch_task(&state.db, parent_task_id).await?;
+
+    let node_id = NodeBuilder::task()
+        .title(title)
+        .task_status(payload.status)
+        .priority(payload.priority)
+        .due_date(payload.due_date.as_deref())
+        .user_note(payload.user_note.as_deref())
+        .insert(&state.db)
+        .await?;
+    insert_edge_if_missing(
+        &state.db,
+        NewEdge {
+            source_node_id: parent_task_id,
+            target_node_id: node_id,
+            relation_type: EdgeRelationType::Contains,
+            confidence_score: None,
+            is_manual: true,
+        },
+    )
+    .await?;
+
+    let node = get_node_by_id(&state.db, node_id).await?;
+    Ok(CreateTaskResponse { node })
+}
+
+#[tauri::command]
+pub async fn list_subtasks(
+    state: State<'_, AppState>,
+    node_id: NodeRef,
+) -> AppResult<Vec<NodeRecord>> {
+    let node_id = resolve_node_ref(&state.db, &node_id).await?;
+    Ok(list_child_tasks(&state.db, node_id).await?)
+}
+
+/// 把任务移到另一个任务下；parent_task_id 为空时移到顶层
+#[tauri::command]
+pub async fn reparent_task(
+    state: State<'_, AppState>,
+    node_id: NodeRef,
+    parent_task_id: Option<NodeRef>,
+) -> AppResult<()> {
+    let node_id = resolve_node_ref(&state.db, &node_id).await?;
+    let parent_task_id = match parent_task_id {
+        Some(parent) => Some(resolve_node_ref(&state.db, &parent).await?),
+        None => None,
+    };
+    fetch_task(&state.db, node_id).await?;
+    if let Some(parent_task_id) = parent_task_id {
+        fetch_task(&state.db, parent_task_id).await?;
+    }
+    set_parent_task(&state.db, node_id, parent_task_id).await
+}
+
+// ========== 提醒 ==========
+
+#[tauri::command]
+pub async fn get_task_reminder_command(
+    state: State<'_, AppState>,
+    node_id: NodeRef,
+) -> AppResult<Option<TaskReminderRecord>> {
+    let node_id = resolve_node_ref(&state.db, &node_id).await?;
+    Ok(get_task_reminder(&state.db, node_id).await?)
+}
+
+/// 设置截止前多少分钟提醒；minutes_before 为空时关闭提醒
+#[tauri::command]
+pub async fn set_task_reminder(
+    state: State<'_, AppState>,
+    node_id: NodeRef,
+    minutes_before: Option<u32>,
+) -> AppResult<Option<TaskReminderRecord>> {
+    let node_id = resolve_node_ref(&state.db, &node_id).await?;
+    fetch_task(&state.db, node_id).await?;
+    match minutes_before {
+        Some(minutes) => upsert_task_reminder(&state.db, node_id, i64::from(minutes)).await?,
+        None => delete_task_reminder(&state.db, node_id).await?,
+    }
+    Ok(get_task_reminder(&state.db, node_id).await?)
+}
+
+/// 稍后提醒，默认 10 分钟后；任务需已设置提醒
+#[tauri::command]
+pub async fn snooze_task_reminder(
+    state: State<'_, AppState>,
+    node_id: NodeRef,
+    minutes: Option<u32>,
+) -> AppResult<TaskReminderRecord> {
+    let node_id = resolve_node_ref(&state.db, &node_id).await?;
+    let minutes = minutes.map_or(DEFAULT_SNOOZE_MINUTES, |m| i64::from(m.max(1)));
+    if !set_task_reminder_snooze(&state.db, node_id, &snooze_until(minutes)).await? {
+        return Err(AppError::NotFound {
+            entity: "task_reminder",
+            id: node_id,
+        });
+    }
+    get_task_reminder(&state.db, node_id)
+        .await?
+        .ok_or(AppError::NotFound {
+            entity: "task_reminder",
+            id: node_id,
+        })
+}
+
 // ========== 更新任务 ==========
 
 #[tauri::command]
 pub async fn update_task_title_command(
     state: State<'_, AppState>,
-    node_id: i64,
+    node_id: NodeRef,
     title: String,
-) -> AppResult<()> {
+    expected_version: Option<i64>,
+) -> AppResult<i64> {
+    let node_id = resolve_node_ref(&state.db, &node_id).await?;
     let title = validate_title(&title)?;
-    Ok(update_node_title(&state.db, node_id, title).await?)
+    update_node_text_checked(
+        &state.db,
+        node_id,
+        NodeTextField::Title,
+        Some(title),
+        expected_version,
+    )
+    .await
 }
 
 #[tauri::command]
 pub async fn update_task_due_date_command(
     state: State<'_, AppState>,
-    node_id: i64,
+    node_id: NodeRef,
     due_date: Option<String>,
 ) -> AppResult<()> {
+    let node_id = resolve_node_ref(&state.db, &node_id).await?;
     Ok(update_task_due_date(&state.db, node_id, due_date.as_deref()).await?)
 }
 
+/// 设置或取消任务的重复规则；没有截止日期的任务从今天开始重复
+#[tauri::command]
+pub async fn update_task_recurrence(
+    state: State<'_, AppState>,
+    node_id: NodeRef,
+    recurrence_rule: Option<String>,
+) -> AppResult<NodeRecord> {
+    let node_id = resolve_node_ref(&state.db, &node_id).await?;
+    let node = get_node_by_id(&state.db, node_id).await?;
+    match recurrence_rule.filter(|rule| !rule.trim().is_empty()) {
+        Some(raw) => {
+            let due_date = match node.due_date {
+                Some(due_date) => due_date,
+                None => {
+                    let today = Local::now().date_naive().format("%Y-%m-%d").to_string();
+                    update_task_due_date(&state.db, node_id, Some(today.as_str())).await?;
+                    today
+                }
+            };
+            let rule = normalize_recurrence_rule(&raw, &due_date)?;
+            update_task_recurrence_rule(&state.db, node_id, Some(rule.as_str())).await?;
+        }
+        None => update_task_recurrence_rule(&state.db, node_id, None).await?,
+    }
+    Ok(get_node_by_id(&state.db, node_id).await?)
+}
+
 #[tauri::command]
 pub async fn update_task_description_command(
     state: State<'_, AppState>,
-    node_id: i64,
+    node_id: NodeRef,
     description: Option<String>,
-) -> AppResult<()> {
-    Ok(update_node_user_note(&state.db, node_id, description.as_deref()).await?)
+    expected_version: Option<i64>,
+) -> AppResult<i64> {
+    let node_id = resolve_node_ref(&state.db, &node_id).await?;
+    update_node_text_checked(
+        &state.db,
+        node_id,
+        NodeTextField::UserNote,
+        description.as_deref(),
+        expected_version,
+    )
+    .await
 }
 
 #[tauri::command]
 pub async fn update_task_summary_command(
     state: State<'_, AppState>,
-    node_id: i64,
+    node_id: NodeRef,
     summary: Option<String>,
-) -> AppResult<()> {
-    Ok(update_node_summary(&state.db, node_id, summary.as_deref()).await?)
+    expected_version: Option<i64>,
+) -> AppResult<i64> {
+    let node_id = resolve_node_ref(&state.db, &node_id).await?;
+    update_node_text_checked(
+        &state.db,
+        node_id,
+        NodeTextField::Summary,
+        summary.as_deref(),
+        expected_version,
+    )
+    .await
 }
 
 // ========== 状态更新 ==========
@@ -90,16 +302,19 @@ pub async fn update_task_summary_command(
 #[tauri::command]
 pub async fn mark_task_as_done_command(
     state: State<'_, AppState>,
-    node_id: i64,
+    node_id: NodeRef,
 ) -> AppResult<()> {
-    Ok(mark_task_done(&state.db, node_id).await?)
+    let node_id = resolve_node_ref(&state.db, &node_id).await?;
+    complete_task(&state.db, node_id).await?;
+    Ok(())
 }
 
 #[tauri::command]
 pub async fn mark_task_as_todo_command(
     state: State<'_, AppState>,
-    node_id: i64,
+    node_id: NodeRef,
 ) -> AppResult<()> {
+    let node_id = resolve_node_ref(&state.db, &node_id).await?;
     Ok(mark_task_todo(&state.db, node_id).await?)
 }
 
@@ -110,15 +325,129 @@ pub async fn get_tasks_by_date(
     state: State<'_, AppState>,
     date: String,
 ) -> AppResult<Vec<NodeRecord>> {
-    Ok(list_tasks_by_date(&state.db, &date).await?)
+    Ok(list_tasks_with_occurrences(&state.db, &date).await?)
 }
 
+/// 全部任务，附带父任务与子任务完成情况
 #[tauri::command]
-pub async fn get_all_tasks(state: State<'_, AppState>) -> AppResult<Vec<NodeRecord>> {
-    Ok(list_all_tasks(&state.db).await?)
+pub async fn get_all_tasks(state: State<'_, AppState>) -> AppResult<Vec<TaskListItem>> {
+    let tasks = list_all_tasks(&state.db).await?;
+    let links = list_subtask_links(&state.db).await?;
+    Ok(build_task_list(tasks, &links))
 }
 
 #[tauri::command]
 pub async fn get_active_tasks(state: State<'_, AppState>) -> AppResult<Vec<NodeRecord>> {
     Ok(list_active_tasks(&state.db).await?)
 }
+
+// ========== 看板 ==========
+
+/// 按状态分列的看板，每列按 board_position 排序；空列也会返回
+#[tauri::command]
+pub async fn get_task_board(state: State<'_, AppState>) -> AppResult<Vec<TaskBoardColumn>> {
+    load_task_board(&state.db).await
+}
+
+/// 拖拽任务到 status 列的 position 处（从 0 开始，超出则放到末尾），返回移动后的看板
+///
+/// 拖入 done 与 mark_task_as_done 一致，重复任务会生成下一次
+#[tauri::command]
+pub async fn move_task_on_board(
+    state: State<'_, AppState>,
+    node_id: NodeRef,
+    status: TaskStatus,
+    position: usize,
+) -> AppResult<Vec<TaskBoardColumn>> {
+    let node_id = resolve_node_ref(&state.db, &node_id).await?;
+    let previous = db::move_task_on_board(&state.db, node_id, status, position)
+        .await?
+        .ok_or(AppError::NotFound {
+            entity: "task",
+            id: node_id,
+        })?;
+    if status == TaskStatus::Done && previous != TaskStatus::Done {
+        materialize_next_occurrence(&state.db, node_id).await?;
+    }
+    load_task_board(&state.db).await
+}
+
+/// 读取未删除的任务节点，其他类型按不存在处理
+async fn fetch_task(db: &DbPool, node_id: i64) -> AppResult<NodeRecord> {
+    let node = get_node_by_id(db, node_id).await?;
+    if node.node_type != NodeType::Task || node.is_deleted {
+        return Err(AppError::NotFound {
+            entity: "task",
+            id: node_id,
+        });
+    }
+    Ok(node)
+}
+
+async fn load_task_board(db: &DbPool) -> AppResult<Vec<TaskBoardColumn>> {
+    let tasks = list_board_tasks(db).await?;
+    Ok(group_board_columns(tasks))
+}
+
+/// 按固定顺序（todo、done、cancelled）分列，保持查询结果的列内顺序
+fn group_board_columns(tasks: Vec<NodeRecord>) -> Vec<TaskBoardColumn> {
+    let mut columns: Vec<TaskBoardColumn> =
+        [TaskStatus::Todo, TaskStatus::Done, TaskStatus::Cancelled]
+            .into_iter()
+            .map(|status| TaskBoardColumn {
+                status,
+                tasks: Vec::new(),
+            })
+            .collect();
+    for task in tasks {
+        let Some(status) = task.task_status else {
+            continue;
+        };
+        if let Some(column) = columns.iter_mut().find(|column| column.status == status) {
+            column.tasks.push(task);
+        }
+    }
+    columns
+}
+
+/// 为任务附上父任务 ID 与子任务完成数（已取消的子任务不计入）
+fn build_task_list(tasks: Vec<NodeRecord>, links: &[SubtaskLinkRow]) -> Vec<TaskListItem> {
+    let mut parents = HashMap::new();
+    let mut progress: HashMap<i64, (i64, i64)> = HashMap::new();
+    for link in links {
+        parents.entry(link.child_id).or_insert(link.parent_id);
+        if link.child_status == Some(TaskStatus::Cancelled) {
+            continue;
+        }
+        let (total, done) = progress.entry(link.parent_id).or_default();
+        *total += 1;
+        if link.child_status == Some(TaskStatus::Done) {
+            *done += 1;
+        }
+    }
+
+    tasks
+        .into_iter()
+        .map(|node| {
+            let (subtask_total, subtask_done) =
+                progress.get(&node.node_id).copied().unwrap_or_default();
+            TaskListItem {
+                parent_task_id: parents.get(&node.node_id).copied(),
+                subtask_total,
+                subtask_done,
+                node,
+            }
+        })
+        .collect()
+}
+
+/// 校验并规范化重复规则，以 due_date 所在日期为起点
+fn normalize_recurrence_rule(raw: &str, due_date: &str) -> AppResult<String> {
+    let anchor = due_date
+        .get(..10)
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+        .ok_or_else(|| AppError::coded_with(MessageCode::InvalidDate, "value", due_date))?;
+    let rule = RecurrenceRule::parse(raw)
+        .map_err(|e| AppError::coded_with(MessageCode::InvalidRecurrenceRule, "detail", e))?;
+    Ok(rule.anchored(anchor).to_rule_string())
+}
@@ -6,11 +6,14 @@ use tauri::State;
 use crate::{
     app_state::AppState,
     db::{
-        contains_creates_cycle, delete_edge, get_node_by_id, hard_delete_node, insert_edge,
-        list_nodes_by_type, list_source_nodes, list_target_nodes, soft_delete_node,
-        update_node_pinned, update_node_summary, update_node_title, update_resource_review_status,
-        EdgeRelationType, NewEdge, NodeBuilder, NodeRecord, NodeType,
+        apply_topic_operations, contains_creates_cycle, delete_edge, get_node_by_id,
+        hard_delete_node, insert_edge, list_nodes_by_type, list_source_nodes, list_target_nodes,
+        soft_delete_node, update_node_pinned, update_node_text_checked,
+        update_resource_review_status, EdgeRelationType, NewEdge, NodeBuilder, NodeRecord,
+        NodeTextField, NodeType, TopicOperation,
     },
+    i18n::MessageCode,
+    services::{plan_topic_restructure, TopicRestructurePlan},
     simple_void_command,
     utils::{parse_review_status_or_default, validate_title},
     AppError,
@@ -113,9 +116,17 @@ pub async fn update_topic_title_command(
     state: State<'_, AppState>,
     topic_id: i64,
     title: String,
-) -> AppResult<()> {
+    expected_version: Option<i64>,
+) -> AppResult<i64> {
     let title = validate_title(&title)?;
-    update_node_title(&state.db, topic_id, title).await?;
+    let version = update_node_text_checked(
+        &state.db,
+        topic_id,
+        NodeTextField::Title,
+        Some(title),
+        expected_version,
+    )
+    .await?;
     let ai = state.ai.wait_ready().await.map_err(AppError::AiService)?;
     if let Err(err) = ai.embedding.upsert_title_embedding(topic_id, title).await {
         tracing::warn!(
@@ -124,7 +135,7 @@ pub async fn update_topic_title_command(
             "Failed to upsert topic title embedding"
         );
     }
-    Ok(())
+    Ok(version)
 }
 
 #[tauri::command]
@@ -132,8 +143,16 @@ pub async fn update_topic_summary_command(
     state: State<'_, AppState>,
     topic_id: i64,
     summary: Option<String>,
-) -> AppResult<()> {
-    Ok(update_node_summary(&state.db, topic_id, summary.as_deref()).await?)
+    expected_version: Option<i64>,
+) -> AppResult<i64> {
+    update_node_text_checked(
+        &state.db,
+        topic_id,
+        NodeTextField::Summary,
+        summary.as_deref(),
+        expected_version,
+    )
+    .await
 }
 
 #[tauri::command]
@@ -155,7 +174,7 @@ pub async fn link_resource_to_topic_command(
     let review_status = parse_review_status_or_default(payload.review_status.as_deref());
 
     if contains_creates_cycle(&state.db, payload.topic_id, payload.resource_id).await? {
-        return Err("创建 contains 边会形成环".into());
+        return Err(AppError::coded(MessageCode::ContainsCreateCycle));
     }
 
     insert_edge(
@@ -221,7 +240,7 @@ pub async fn link_task_to_topic_command(
     topic_id: i64,
 ) -> AppResult<SuccessResponse> {
     if contains_creates_cycle(&state.db, topic_id, task_id).await? {
-        return Err("创建 contains 边会形成环".into());
+        return Err(AppError::coded(MessageCode::ContainsCreateCycle));
     }
 
     insert_edge(
@@ -265,3 +284,50 @@ pub async fn get_task_topics_command(
     let nodes = list_source_nodes(&state.db, task_id, EdgeRelationType::Contains).await?;
     Ok(NodeListResponse { nodes })
 }
+
+// ========== 层级整理 ==========
+
+/// 让 AI 分析整棵主题树并给出调整建议（只预览，不修改数据）
+#[tauri::command]
+pub async fn plan_topic_restructure_command(
+    state: State<'_, AppState>,
+) -> AppResult<TopicRestructurePlan> {
+    Ok(plan_topic_restructure(&state).await?)
+}
+
+/// 原子地应用用户勾选的调整操作，任一操作失败则全部不生效
+#[tauri::command]
+pub async fn apply_topic_restructure_command(
+    state: State<'_, AppState>,
+    operations: Vec<TopicOperation>,
+) -> AppResult<()> {
+    if operations.is_empty() {
+        return Ok(());
+    }
+    apply_topic_operations(&state.db, &operations).await?;
+
+    let renamed: Vec<(i64, &str)> = operations
+        .iter()
+        .filter_map(|operation| match operation {
+            TopicOperation::Rename {
+                topic_id,
+                new_title,
+            } => Some((*topic_id, new_title.trim())),
+            _ => None,
+        })
+        .collect();
+    if renamed.is_empty() {
+        return Ok(());
+    }
+    let ai = state.ai.wait_ready().await.map_err(AppError::AiService)?;
+    for (topic_id, title) in renamed {
+        if let Err(err) = ai.embedding.upsert_title_embedding(topic_id, title).await {
+            tracing::warn!(
+                topic_id,
+                error = %err,
+                "Failed to upsert topic title embedding"
+            );
+        }
+    }
+    Ok(())
+}
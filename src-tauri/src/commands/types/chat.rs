@@ -2,7 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::db::{BindingType, SessionType};
+use crate::db::{BindingType, NodeRef, SessionType};
 
 /// 创建聊天会话请求
 #[derive(Debug, Deserialize)]
@@ -11,7 +11,7 @@ pub struct CreateChatSessionRequest {
     pub summary: Option<String>,
     pub chat_model: Option<String>,
     pub session_type: Option<SessionType>,
-    pub context_node_ids: Option<Vec<i64>>,
+    pub context_node_ids: Option<Vec<NodeRef>>,
     pub binding_type: Option<BindingType>,
 }
 
@@ -24,7 +24,7 @@ pub struct CreateChatSessionResponse {
 /// 列出聊天会话请求
 #[derive(Debug, Deserialize)]
 pub struct ListChatSessionsRequest {
-    pub node_id: Option<i64>,
+    pub node_id: Option<NodeRef>,
     pub include_deleted: Option<bool>,
 }
 
@@ -43,6 +43,16 @@ pub struct DeleteChatSessionRequest {
     pub session_id: i64,
 }
 
+/// 分叉聊天会话请求
+#[derive(Debug, Deserialize)]
+pub struct ForkChatSessionRequest {
+    pub session_id: i64,
+    /// 复制到这条消息为止（含）
+    pub message_id: i64,
+    /// 为空时沿用原会话标题
+    pub title: Option<String>,
+}
+
 /// 创建聊天消息请求
 #[derive(Debug, Deserialize)]
 pub struct CreateChatMessageRequest {
@@ -51,7 +61,7 @@ pub struct CreateChatMessageRequest {
     pub thinking_summary: Option<String>,
     pub assistant_content: Option<String>,
     pub thinking_effort: Option<String>,
-    pub attachment_node_ids: Option<Vec<i64>>,
+    pub attachment_node_ids: Option<Vec<NodeRef>>,
 }
 
 /// 创建聊天消息响应
@@ -80,21 +90,21 @@ pub struct DeleteChatMessageRequest {
 #[derive(Debug, Deserialize)]
 pub struct AddMessageAttachmentsRequest {
     pub message_id: i64,
-    pub node_ids: Vec<i64>,
+    pub node_ids: Vec<NodeRef>,
 }
 
 /// 移除消息附件请求
 #[derive(Debug, Deserialize)]
 pub struct RemoveMessageAttachmentRequest {
     pub message_id: i64,
-    pub node_id: i64,
+    pub node_id: NodeRef,
 }
 
 /// 设置会话绑定请求
 #[derive(Debug, Deserialize)]
 pub struct SetSessionBindingsRequest {
     pub session_id: i64,
-    pub node_ids: Vec<i64>,
+    pub node_ids: Vec<NodeRef>,
     pub binding_type: BindingType,
 }
 
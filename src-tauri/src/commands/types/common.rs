@@ -2,13 +2,22 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::db::NodeRecord;
+use crate::db::{
+    EdgeRelationType, GraphEdgeRecord, GraphNodeRecord, GraphRelationType, NodeRecord, NodeRef,
+    NodeType, RecentNodeRow, WorkspaceRecord,
+};
+use crate::services::{AssetsStorageStatus, HabitOverview};
 
 /// Dashboard 数据
 #[derive(Debug, Serialize)]
 pub struct DashboardData {
     pub tasks: Vec<NodeRecord>,
     pub resources: Vec<NodeRecord>,
+    /// 今日专注秒数
+    pub focus_seconds_today: i64,
+    pub habits: Vec<HabitOverview>,
+    /// 最近打开的节点（继续阅读）
+    pub recent: Vec<RecentNodeRow>,
 }
 
 /// 节点关联请求
@@ -28,9 +37,63 @@ pub struct LinkNodesResponse {
     pub success: bool,
 }
 
+/// 图谱请求
+#[derive(Debug, Default, Deserialize)]
+pub struct GraphRequest {
+    /// 给出时只返回该节点的邻域
+    pub root_node_id: Option<i64>,
+    /// 邻域步数，默认 2，最多 3
+    pub depth: Option<i64>,
+    /// 默认 topic / resource / task
+    pub node_types: Option<Vec<NodeType>>,
+    /// 默认全部关系类型（含正文引用 references）
+    pub relation_types: Option<Vec<GraphRelationType>>,
+    pub limit: Option<i32>,
+    pub offset: Option<i64>,
+}
+
+/// 图谱响应（一页）
+#[derive(Debug, Serialize)]
+pub struct GraphResponse {
+    pub nodes: Vec<GraphNodeRecord>,
+    /// 本页节点与本页及之前各页节点之间的边
+    pub edges: Vec<GraphEdgeRecord>,
+    pub total: i64,
+    pub has_more: bool,
+}
+
+/// 边导出筛选
+#[derive(Debug, Default, Deserialize)]
+pub struct EdgeExportFilter {
+    /// 默认全部关系类型
+    pub relation_types: Option<Vec<EdgeRelationType>>,
+    /// 只导出手动创建 / 已确认的边
+    #[serde(default)]
+    pub manual_only: bool,
+    /// 给出时只导出与这些节点相连的边
+    pub node_ids: Option<Vec<NodeRef>>,
+}
+
 /// 节点列表响应
 #[derive(Debug, Serialize)]
 pub struct NodeListResponse {
     pub nodes: Vec<NodeRecord>,
 }
 
+/// 恢复工作区的结果：已删除的节点/会话会被跳过
+#[derive(Debug, Serialize)]
+pub struct WorkspaceSnapshot {
+    pub workspace: WorkspaceRecord,
+    pub open_node_ids: Vec<i64>,
+    pub open_session_ids: Vec<i64>,
+}
+
+/// 资源文件目录迁移结果
+#[derive(Debug, Serialize)]
+pub struct AssetsRelocateSummary {
+    pub status: AssetsStorageStatus,
+    pub moved_files: usize,
+    pub moved_bytes: u64,
+    /// 已复制到新目录、但旧文件删除失败的数量
+    pub left_behind: usize,
+}
@@ -13,11 +13,16 @@ mod task;
 
 // 导出资源相关类型
 pub use resource::{
-    CaptureRequest, CaptureResponse, CaptureSourceMeta, ClipboardContent, ReadClipboardResponse,
+    ArchiveExpandSummary, ArchivePreview, CaptureBatchItem, CaptureBatchRequest, CaptureBatchResponse, CaptureRequest, CaptureResponse,
+    CaptureSourceMeta, ClipboardContent, ClipboardOcrPreview, EmbeddingStatusReport, EnexImportSummary, FileStorageMode, MarkdownImportSummary, PipelineStatus,
+    ReadClipboardResponse, RejectClassificationResponse, RequeueEmbeddingsFilter, SaveCaptureProfileRequest, UpdateCitationRequest,
 };
 
 // 导出任务相关类型
-pub use task::{CreateTaskRequest, CreateTaskResponse};
+pub use task::{
+    CreateHabitRequest, CreateRecurringTaskRequest, CreateTaskRequest, CreateTaskResponse,
+    TaskBoardColumn, TaskListItem,
+};
 
 // 导出聊天相关类型
 pub use chat::{
@@ -29,5 +34,7 @@ pub use chat::{
 };
 
 // 导出通用类型
-pub use common::{DashboardData, LinkNodesRequest, LinkNodesResponse, NodeListResponse};
+pub use common::{
+    AssetsRelocateSummary, DashboardData, EdgeExportFilter, GraphRequest, GraphResponse, LinkNodesRequest, LinkNodesResponse, NodeListResponse, WorkspaceSnapshot,
+};
 
@@ -2,6 +2,10 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::db::{EmbeddingErrorCount, EmbeddingStateCount, PipelineStageCount};
+use crate::services::parser::ArchiveEntry;
+use crate::services::ReclassifyOutcome;
+
 /// 资源来源元数据（捕获时传入）
 #[derive(Debug, Deserialize)]
 pub struct CaptureSourceMeta {
@@ -11,6 +15,15 @@ pub struct CaptureSourceMeta {
     pub captured_at: Option<String>,
 }
 
+/// 文件捕获方式：copy 复制到资源文件目录；reference 原地索引，只记录绝对路径（适合大文件）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileStorageMode {
+    #[default]
+    Copy,
+    Reference,
+}
+
 /// 资源捕获请求
 #[derive(Debug, Deserialize)]
 pub struct CaptureRequest {
@@ -18,6 +31,12 @@ pub struct CaptureRequest {
     pub file_path: Option<String>,
     pub file_type: Option<String>,
     pub source_meta: Option<CaptureSourceMeta>,
+    #[serde(default)]
+    pub storage: FileStorageMode,
+    /// 图片捕获前用户确认 / 修改过的 OCR 文本；提供时不再重新识别
+    pub ocr_text: Option<String>,
+    /// 使用的捕获模板，按模板归入主题、打标签或推迟 AI 处理
+    pub profile_id: Option<i64>,
 }
 
 /// 资源捕获响应
@@ -27,6 +46,138 @@ pub struct CaptureResponse {
     pub node_uuid: String,
 }
 
+/// 批量捕获文件请求；user_note 作为每个文件的备注
+#[derive(Debug, Deserialize)]
+pub struct CaptureBatchRequest {
+    pub file_paths: Vec<String>,
+    pub user_note: Option<String>,
+    pub source_meta: Option<CaptureSourceMeta>,
+    #[serde(default)]
+    pub storage: FileStorageMode,
+    pub profile_id: Option<i64>,
+}
+
+/// 新建 / 更新捕获模板请求；profile_id 为空时新建
+#[derive(Debug, Deserialize)]
+pub struct SaveCaptureProfileRequest {
+    pub profile_id: Option<i64>,
+    pub name: String,
+    pub shortcut: Option<String>,
+    pub topic_id: Option<i64>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub defer_processing: bool,
+}
+
+/// 批量捕获中单个文件的结果；复制失败的文件 node_id 为空并带 error
+#[derive(Debug, Serialize)]
+pub struct CaptureBatchItem {
+    pub file_path: String,
+    pub node_id: Option<i64>,
+    pub node_uuid: Option<String>,
+    pub error: Option<String>,
+}
+
+/// 批量捕获响应；解析与入队在后台进行，进度通过 capture-batch-progress 事件通知
+#[derive(Debug, Serialize)]
+pub struct CaptureBatchResponse {
+    pub batch_id: String,
+    pub items: Vec<CaptureBatchItem>,
+}
+
+/// 压缩包展开前的预览
+#[derive(Debug, Serialize)]
+pub struct ArchivePreview {
+    pub entries: Vec<ArchiveEntry>,
+    /// 可展开为资源的条目数
+    pub supported_count: usize,
+    pub total_size: u64,
+    pub max_entries: usize,
+    pub max_entry_size: u64,
+    pub max_total_size: u64,
+}
+
+/// 压缩包展开结果；子资源的解析在后台进行，进度通过 archive-expand-progress 事件通知
+#[derive(Debug, Default, Serialize)]
+pub struct ArchiveExpandSummary {
+    /// 新建的子资源 ID
+    pub created_node_ids: Vec<i64>,
+    /// 内容哈希已存在、只建立关系的资源数
+    pub linked_existing: usize,
+    pub skipped_unsupported: usize,
+    pub skipped_too_large: usize,
+    /// 超过条目数或总大小上限而未展开的条目数
+    pub truncated: usize,
+}
+
+/// 向量化状态报告
+#[derive(Debug, Serialize)]
+pub struct EmbeddingStatusReport {
+    pub total: i64,
+    /// 按 embedding_status 与 processing_stage 分组的资源数
+    pub counts: Vec<EmbeddingStateCount>,
+    /// 等待最久的待处理资源的最后更新时间（UTC）
+    pub oldest_pending_at: Option<String>,
+    pub oldest_pending_age_secs: Option<i64>,
+    /// 最常见的错误信息
+    pub top_errors: Vec<EmbeddingErrorCount>,
+    /// AI 队列当前没有待处理或处理中的资源
+    pub queue_idle: bool,
+}
+
+/// AI 队列进度，供进度面板展示
+#[derive(Debug, Serialize)]
+pub struct PipelineStatus {
+    pub queued: i64,
+    pub processing: i64,
+    /// 按任务状态与资源处理阶段（todo / embedding）分组的任务数
+    pub stages: Vec<PipelineStageCount>,
+    /// AI 服务启动失败，队列不会被处理
+    pub stopped: bool,
+    /// 已暂停，排队的资源在恢复后按原顺序处理
+    pub paused: bool,
+}
+
+/// 重新入队失败资源的筛选条件
+#[derive(Debug, Default, Deserialize)]
+pub struct RequeueEmbeddingsFilter {
+    /// 错误信息包含的文本（忽略大小写）
+    pub error_contains: Option<String>,
+    /// 只重新入队该时间之后失败的资源
+    pub failed_since: Option<String>,
+    /// 同时重新入队尚未处理完的资源
+    #[serde(default)]
+    pub include_pending: bool,
+}
+
+/// ENEX 导入结果
+#[derive(Debug, Default, Serialize)]
+pub struct EnexImportSummary {
+    pub imported_notes: usize,
+    pub imported_attachments: usize,
+    /// 内容哈希已存在而跳过的笔记与附件数
+    pub skipped_duplicates: usize,
+    /// 新建的笔记资源 ID
+    pub node_ids: Vec<i64>,
+}
+
+/// Markdown 文件夹导入结果
+#[derive(Debug, Default, Serialize)]
+pub struct MarkdownImportSummary {
+    pub imported_notes: usize,
+    /// 内容哈希已存在而跳过的笔记数
+    pub skipped_duplicates: usize,
+    /// 读取失败（如不是 UTF-8）的文件数
+    pub failed_files: usize,
+    /// 由 [[链接]] 建立的引用数（写入 node_links，见反向链接）
+    pub linked: usize,
+    /// 找不到目标笔记的链接数
+    pub unresolved_links: usize,
+    /// 新建的笔记资源 ID
+    pub node_ids: Vec<i64>,
+}
+
 /// 剪贴板内容
 #[derive(Debug, Serialize)]
 #[serde(tag = "type", content = "data")]
@@ -35,6 +186,10 @@ pub enum ClipboardContent {
     Files { paths: Vec<String> },
     Text { content: String },
     Html { content: String, plain_text: Option<String> },
+    /// 网页 / 电子表格中复制的表格，首行通常是表头
+    Table { rows: Vec<Vec<String>>, plain_text: Option<String> },
+    /// 识别为代码的文本，language 为猜测的语言（如 rust、python）
+    Code { content: String, language: Option<String> },
     Empty,
 }
 
@@ -44,3 +199,32 @@ pub struct ReadClipboardResponse {
     pub content: ClipboardContent,
 }
 
+/// 剪贴板图片的 OCR 预览；图片已保存到 assets，OCR 失败时 text 为空并带 error
+#[derive(Debug, Serialize)]
+pub struct ClipboardOcrPreview {
+    pub file_path: String,
+    pub file_name: String,
+    pub text: String,
+    pub error: Option<String>,
+}
+
+
+/// 手动编辑文献元数据请求
+#[derive(Debug, Deserialize)]
+pub struct UpdateCitationRequest {
+    pub title: String,
+    #[serde(default)]
+    pub authors: Vec<String>,
+    pub year: Option<i64>,
+    pub venue: Option<String>,
+    pub doi: Option<String>,
+}
+
+/// 拒绝分类的结果
+#[derive(Debug, Serialize)]
+pub struct RejectClassificationResponse {
+    /// 被移除的自动 contains 边的主题 ID
+    pub removed_topic_ids: Vec<i64>,
+    /// 请求了重新分类时的结果
+    pub reclassified: Option<ReclassifyOutcome>,
+}
@@ -2,7 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::db::{NodeRecord, TaskPriority, TaskStatus};
+use crate::db::{HabitFrequency, NodeRecord, TaskPriority, TaskStatus};
 
 /// 创建任务请求
 #[derive(Debug, Deserialize)]
@@ -20,3 +20,41 @@ pub struct CreateTaskResponse {
     pub node: NodeRecord,
 }
 
+/// 任务列表项：附带父任务与子任务完成情况（如 3/5）
+#[derive(Debug, Serialize)]
+pub struct TaskListItem {
+    #[serde(flatten)]
+    pub node: NodeRecord,
+    pub parent_task_id: Option<i64>,
+    /// 子任务数，不含已取消的子任务
+    pub subtask_total: i64,
+    pub subtask_done: i64,
+}
+
+/// 创建重复任务请求；未设置 due_date 时从今天开始
+#[derive(Debug, Deserialize)]
+pub struct CreateRecurringTaskRequest {
+    pub title: String,
+    pub priority: Option<TaskPriority>,
+    pub due_date: Option<String>,
+    pub user_note: Option<String>,
+    /// 类 RRULE 规则，如 FREQ=WEEKLY;BYDAY=MO,WE
+    pub recurrence_rule: String,
+}
+
+/// 看板中的一列：同一状态的任务，按 board_position 排序
+#[derive(Debug, Serialize)]
+pub struct TaskBoardColumn {
+    pub status: TaskStatus,
+    pub tasks: Vec<NodeRecord>,
+}
+
+/// 创建习惯请求
+#[derive(Debug, Deserialize)]
+pub struct CreateHabitRequest {
+    pub title: String,
+    pub summary: Option<String>,
+    pub frequency: Option<HabitFrequency>,
+    /// daily 固定为 1；weekly 表示每周需要打卡的天数
+    pub target_count: Option<i64>,
+}
@@ -0,0 +1,72 @@
+//! LLM 用量与费用报告命令
+
+use chrono::{Duration, Local, NaiveDate};
+use tauri::State;
+
+use crate::i18n::MessageCode;
+use crate::services::{build_usage_report, ModelPrice, UsageReport};
+use crate::{AppError, AppResult, AppState};
+
+/// 未指定起始日期时统计最近 30 天
+const DEFAULT_REPORT_DAYS: i64 = 30;
+
+fn parse_report_date(raw: &str) -> AppResult<NaiveDate> {
+    NaiveDate::parse_from_str(raw.trim(), "%Y-%m-%d")
+        .map_err(|_| AppError::coded_with(MessageCode::InvalidDate, "value", raw))
+}
+
+/// 按天 / 模型 / 用途汇总 [from, to] 的 token 用量与估算费用（本地日期，含两端）
+#[tauri::command]
+pub async fn get_usage_report(
+    state: State<'_, AppState>,
+    from: Option<String>,
+    to: Option<String>,
+) -> AppResult<UsageReport> {
+    let to = match to.as_deref() {
+        Some(raw) => parse_report_date(raw)?,
+        None => Local::now().date_naive(),
+    };
+    let from = match from.as_deref() {
+        Some(raw) => parse_report_date(raw)?,
+        None => to - Duration::days(DEFAULT_REPORT_DAYS - 1),
+    };
+    let prices = state.ai_config.lock().await.load()?.model_prices;
+    Ok(build_usage_report(
+        &state.db,
+        &prices,
+        &from.format("%Y-%m-%d").to_string(),
+        &to.format("%Y-%m-%d").to_string(),
+    )
+    .await?)
+}
+
+#[tauri::command]
+pub async fn get_model_prices(state: State<'_, AppState>) -> AppResult<Vec<ModelPrice>> {
+    Ok(state.ai_config.lock().await.load()?.model_prices)
+}
+
+/// 保存自定义模型价格；空前缀与负价格的条目被丢弃
+#[tauri::command]
+pub async fn set_model_prices(
+    state: State<'_, AppState>,
+    prices: Vec<ModelPrice>,
+) -> AppResult<Vec<ModelPrice>> {
+    let prices: Vec<ModelPrice> = prices
+        .into_iter()
+        .map(|price| ModelPrice {
+            model_prefix: price.model_prefix.trim().to_string(),
+            ..price
+        })
+        .filter(|price| {
+            !price.model_prefix.is_empty()
+                && price.input_per_million >= 0.0
+                && price.output_per_million >= 0.0
+        })
+        .collect();
+    state
+        .ai_config
+        .lock()
+        .await
+        .set_model_prices(prices.clone())?;
+    Ok(prices)
+}
@@ -0,0 +1,87 @@
+//! 监听文件夹命令
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use tauri::State;
+
+use crate::db::{self, WatchedFolderRecord};
+use crate::i18n::MessageCode;
+use crate::{AppError, AppResult, AppState};
+
+/// 添加监听文件夹并立即开始监听；文件夹中已有的文件也会被导入
+///
+/// move_to 可选：导入（或判定重复）后把文件移动到该目录，目录不存在时自动创建，不能与监听文件夹相同。
+/// 重复添加同一文件夹会更新 move_to 并重新启用
+#[tauri::command]
+pub async fn add_watched_folder(
+    state: State<'_, AppState>,
+    path: String,
+    move_to: Option<String>,
+) -> AppResult<WatchedFolderRecord> {
+    let folder = PathBuf::from(path.trim());
+    if !folder.is_dir() {
+        return Err(AppError::coded_with(
+            MessageCode::InvalidWatchFolder,
+            "detail",
+            format!("{} 不是文件夹", folder.display()),
+        ));
+    }
+    let folder = folder.canonicalize()?;
+    let move_to = match move_to.as_deref().map(str::trim) {
+        Some(dir) if !dir.is_empty() => {
+            fs::create_dir_all(dir)?;
+            let dir = Path::new(dir).canonicalize()?;
+            if dir == folder {
+                return Err(AppError::coded_with(
+                    MessageCode::InvalidWatchFolder,
+                    "detail",
+                    "移动目标不能是监听文件夹本身",
+                ));
+            }
+            Some(dir.to_string_lossy().to_string())
+        }
+        _ => None,
+    };
+
+    let folder_id =
+        db::upsert_watched_folder(&state.db, &folder.to_string_lossy(), move_to.as_deref()).await?;
+    if let Err(err) = state.folder_watch.watch(folder_id, &folder) {
+        db::set_watched_folder_error(&state.db, folder_id, &err).await?;
+        return Err(AppError::coded_with(
+            MessageCode::InvalidWatchFolder,
+            "detail",
+            err,
+        ));
+    }
+    fetch_folder(&state, folder_id).await
+}
+
+#[tauri::command]
+pub async fn list_watched_folders(
+    state: State<'_, AppState>,
+) -> AppResult<Vec<WatchedFolderRecord>> {
+    Ok(db::list_watched_folders(&state.db).await?)
+}
+
+/// 停止监听并删除文件夹配置；已导入的资源保留
+#[tauri::command]
+pub async fn remove_watched_folder(state: State<'_, AppState>, id: i64) -> AppResult<()> {
+    state.folder_watch.unwatch(id);
+    if !db::delete_watched_folder(&state.db, id).await? {
+        return Err(AppError::NotFound {
+            entity: "watched_folder",
+            id,
+        });
+    }
+    Ok(())
+}
+
+async fn fetch_folder(state: &AppState, folder_id: i64) -> AppResult<WatchedFolderRecord> {
+    db::get_watched_folder(&state.db, folder_id)
+        .await?
+        .ok_or(AppError::NotFound {
+            entity: "watched_folder",
+            id: folder_id,
+        })
+}
@@ -0,0 +1,113 @@
+//! 工作区快照命令
+
+use tauri::State;
+
+use crate::db::{self, WorkspaceRecord};
+use crate::i18n::MessageCode;
+use crate::utils::validate_not_empty;
+use crate::{AppError, AppResult, AppState};
+
+use super::WorkspaceSnapshot;
+
+/// 保存当前打开的节点、聊天会话和布局为工作区；同名工作区会被覆盖
+#[tauri::command]
+pub async fn save_workspace(
+    state: State<'_, AppState>,
+    name: String,
+    open_node_ids: Vec<i64>,
+    layout_json: Option<String>,
+    open_session_ids: Option<Vec<i64>>,
+) -> AppResult<WorkspaceRecord> {
+    let name = validate_not_empty(&name, "name")?;
+    if let Some(layout) = layout_json.as_deref() {
+        serde_json::from_str::<serde_json::Value>(layout)
+            .map_err(|e| AppError::coded_with(MessageCode::InvalidLayoutJson, "detail", e))?;
+    }
+
+    let node_ids = dedup_ids(&open_node_ids);
+    let session_ids = dedup_ids(&open_session_ids.unwrap_or_default());
+    for &node_id in &node_ids {
+        match db::get_node_by_id(&state.db, node_id).await {
+            Ok(node) if !node.is_deleted => {}
+            Ok(_) | Err(sqlx::Error::RowNotFound) => {
+                return Err(AppError::NotFound {
+                    entity: "node",
+                    id: node_id,
+                })
+            }
+            Err(err) => return Err(err.into()),
+        }
+    }
+    for &session_id in &session_ids {
+        match db::get_chat_session_by_id(&state.db, session_id).await {
+            Ok(session) if !session.is_deleted => {}
+            Ok(_) | Err(sqlx::Error::RowNotFound) => {
+                return Err(AppError::NotFound {
+                    entity: "chat_session",
+                    id: session_id,
+                })
+            }
+            Err(err) => return Err(err.into()),
+        }
+    }
+
+    let workspace_id = db::save_workspace(
+        &state.db,
+        name,
+        &node_ids,
+        &session_ids,
+        layout_json.as_deref(),
+    )
+    .await?;
+    fetch_workspace(&state, workspace_id).await
+}
+
+/// 恢复工作区：返回仍然存在的节点/会话（按保存时的打开顺序）和布局
+#[tauri::command]
+pub async fn restore_workspace(
+    state: State<'_, AppState>,
+    id: i64,
+) -> AppResult<WorkspaceSnapshot> {
+    fetch_workspace(&state, id).await?;
+    db::mark_workspace_restored(&state.db, id).await?;
+    let workspace = fetch_workspace(&state, id).await?;
+
+    let items = db::list_workspace_items(&state.db, id).await?;
+    Ok(WorkspaceSnapshot {
+        workspace,
+        open_node_ids: items.iter().filter_map(|item| item.node_id).collect(),
+        open_session_ids: items.iter().filter_map(|item| item.session_id).collect(),
+    })
+}
+
+/// 列出全部工作区，最近保存的在前
+#[tauri::command]
+pub async fn list_workspaces(state: State<'_, AppState>) -> AppResult<Vec<WorkspaceRecord>> {
+    Ok(db::list_workspaces(&state.db).await?)
+}
+
+#[tauri::command]
+pub async fn delete_workspace(state: State<'_, AppState>, id: i64) -> AppResult<()> {
+    if !db::delete_workspace(&state.db, id).await? {
+        return Err(AppError::NotFound {
+            entity: "workspace",
+            id,
+        });
+    }
+    Ok(())
+}
+
+async fn fetch_workspace(state: &AppState, workspace_id: i64) -> AppResult<WorkspaceRecord> {
+    db::get_workspace(&state.db, workspace_id)
+        .await?
+        .ok_or(AppError::NotFound {
+            entity: "workspace",
+            id: workspace_id,
+        })
+}
+
+/// 去重并保持首次出现的顺序
+fn dedup_ids(ids: &[i64]) -> Vec<i64> {
+    let mut seen = std::collections::HashSet::new();
+    ids.iter().copied().filter(|id| seen.insert(*id)).collect()
+}
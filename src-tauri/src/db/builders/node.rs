@@ -3,11 +3,13 @@
 //! 提供链式 API 简化节点创建
 
 use sqlx::types::Json;
+use sqlx::SqliteConnection;
 use uuid::Uuid;
 
 use crate::db::{
-    insert_node, DbPool, NewNode, NodeType, ResourceEmbeddingStatus, ResourceProcessingStage,
-    ResourceSubtype, ReviewStatus, SourceMeta, TaskPriority, TaskStatus,
+    insert_node, link_derived_node, refresh_node_links, update_node_content_stats, DbPool, NewNode,
+    NodeType, ResourceEmbeddingStatus, ResourceProcessingStage, ResourceSubtype, ReviewStatus,
+    SourceMeta, TaskPriority, TaskStatus,
 };
 
 pub struct NodeBuilder {
@@ -33,13 +35,13 @@ pub struct NodeBuilder {
     last_embedding_error: Option<String>,
     processing_stage: ResourceProcessingStage,
     review_status: ReviewStatus,
+    derived_from: Vec<i64>,
 }
 
 impl NodeBuilder {
     /// 创建资源节点 Builder
     pub fn resource() -> Self {
-        Self::new(NodeType::Resource)
-            .with_review_status(ReviewStatus::Unreviewed)
+        Self::new(NodeType::Resource).with_review_status(ReviewStatus::Unreviewed)
     }
 
     /// 创建任务节点 Builder
@@ -52,8 +54,12 @@ impl NodeBuilder {
 
     /// 创建主题节点 Builder
     pub fn topic() -> Self {
-        Self::new(NodeType::Topic)
-            .with_review_status(ReviewStatus::Reviewed)
+        Self::new(NodeType::Topic).with_review_status(ReviewStatus::Reviewed)
+    }
+
+    /// 创建习惯节点 Builder
+    pub fn habit() -> Self {
+        Self::new(NodeType::Habit).with_review_status(ReviewStatus::Reviewed)
     }
 
     fn new(node_type: NodeType) -> Self {
@@ -80,6 +86,7 @@ impl NodeBuilder {
             last_embedding_error: None,
             processing_stage: ResourceProcessingStage::Todo,
             review_status: ReviewStatus::Unreviewed,
+            derived_from: Vec::new(),
         }
     }
 
@@ -105,7 +112,6 @@ impl NodeBuilder {
     }
 
     /// 设置 UUID（通常由 Builder 自动生成）
-    #[allow(dead_code)]
     pub fn uuid(mut self, uuid: impl Into<String>) -> Self {
         self.uuid = uuid.into();
         self
@@ -245,6 +251,14 @@ impl NodeBuilder {
         self
     }
 
+    // ========== 派生关系 ==========
+
+    /// 标记为由 source_node_id 派生，插入后自动创建 derived_from 边（可多次调用）
+    pub fn derived_from(mut self, source_node_id: i64) -> Self {
+        self.derived_from.push(source_node_id);
+        self
+    }
+
     // ========== 构建方法 ==========
 
     /// 获取生成的 UUID
@@ -280,10 +294,39 @@ impl NodeBuilder {
         }
     }
 
-    /// 插入到数据库并返回 node_id
+    /// 插入到数据库并返回 node_id；节点、内容统计、链接与 derived_from 边在同一事务中写入
     pub async fn insert(self, pool: &DbPool) -> Result<i64, sqlx::Error> {
-        let new_node = self.build();
-        insert_node(pool, new_node).await
+        let mut tx = pool.begin().await?;
+        let node_id = self.insert_in(tx.as_mut()).await?;
+        tx.commit().await?;
+        Ok(node_id)
+    }
+
+    /// 在调用方的连接（通常是事务）中插入，便于与其它写入一起提交
+    pub async fn insert_in(&self, conn: &mut SqliteConnection) -> Result<i64, sqlx::Error> {
+        let node_id = insert_node(&mut *conn, self.build()).await?;
+        if self.file_content.is_some() {
+            update_node_content_stats(&mut *conn, node_id, self.file_content.as_deref()).await?;
+            refresh_node_links(conn, node_id, self.file_content.as_deref()).await?;
+        }
+        for source_node_id in &self.derived_from {
+            link_derived_node(&mut *conn, node_id, *source_node_id).await?;
+        }
+        Ok(node_id)
+    }
+
+    /// 在同一事务中插入多个节点，任一失败则全部回滚；返回的 node_id 与输入顺序一致
+    pub async fn insert_batch(
+        builders: &[NodeBuilder],
+        pool: &DbPool,
+    ) -> Result<Vec<i64>, sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        let mut node_ids = Vec::with_capacity(builders.len());
+        for builder in builders {
+            node_ids.push(builder.insert_in(tx.as_mut()).await?);
+        }
+        tx.commit().await?;
+        Ok(node_ids)
     }
 
     /// 插入到数据库并返回 (node_id, uuid)
@@ -0,0 +1,168 @@
+use super::{
+    CalendarEventRecord, CalendarReminderRow, CalendarSubscriptionRecord, DbPool, NewCalendarEvent,
+};
+
+const SUBSCRIPTION_FIELDS: &str = "subscription_id, name, url, poll_interval_minutes, is_enabled, \
+     last_synced_at, last_error, created_at";
+
+const EVENT_FIELDS: &str =
+    "event_id, subscription_id, uid, node_id, starts_at, ends_at, location, \
+     recurrence_rule, reminder_at, reminder_sent_at";
+
+pub async fn insert_calendar_subscription(
+    pool: &DbPool,
+    name: &str,
+    url: &str,
+    poll_interval_minutes: i64,
+) -> Result<i64, sqlx::Error> {
+    let result = sqlx::query(
+        "INSERT INTO calendar_subscriptions (name, url, poll_interval_minutes) VALUES (?, ?, ?)",
+    )
+    .bind(name)
+    .bind(url)
+    .bind(poll_interval_minutes)
+    .execute(pool)
+    .await?;
+    Ok(result.last_insert_rowid())
+}
+
+pub async fn get_calendar_subscription(
+    pool: &DbPool,
+    subscription_id: i64,
+) -> Result<Option<CalendarSubscriptionRecord>, sqlx::Error> {
+    let sql = format!(
+        "SELECT {SUBSCRIPTION_FIELDS} FROM calendar_subscriptions WHERE subscription_id = ?"
+    );
+    sqlx::query_as::<_, CalendarSubscriptionRecord>(&sql)
+        .bind(subscription_id)
+        .fetch_optional(pool)
+        .await
+}
+
+pub async fn list_calendar_subscriptions(
+    pool: &DbPool,
+) -> Result<Vec<CalendarSubscriptionRecord>, sqlx::Error> {
+    let sql = format!("SELECT {SUBSCRIPTION_FIELDS} FROM calendar_subscriptions ORDER BY name");
+    sqlx::query_as::<_, CalendarSubscriptionRecord>(&sql)
+        .fetch_all(pool)
+        .await
+}
+
+/// 已启用且距上次同步超过轮询间隔的订阅
+pub async fn list_due_calendar_subscriptions(
+    pool: &DbPool,
+) -> Result<Vec<CalendarSubscriptionRecord>, sqlx::Error> {
+    let sql = format!(
+        "SELECT {SUBSCRIPTION_FIELDS} FROM calendar_subscriptions \
+         WHERE is_enabled = 1 AND (last_synced_at IS NULL \
+             OR datetime(last_synced_at, '+' || poll_interval_minutes || ' minutes') <= CURRENT_TIMESTAMP)"
+    );
+    sqlx::query_as::<_, CalendarSubscriptionRecord>(&sql)
+        .fetch_all(pool)
+        .await
+}
+
+/// 删除订阅；已导入的任务保留，仅解除与订阅的关联
+pub async fn delete_calendar_subscription(
+    pool: &DbPool,
+    subscription_id: i64,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM calendar_subscriptions WHERE subscription_id = ?")
+        .bind(subscription_id)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// 记录一次同步结果，error 为 None 表示成功
+pub async fn record_calendar_sync(
+    pool: &DbPool,
+    subscription_id: i64,
+    error: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE calendar_subscriptions SET last_synced_at = CURRENT_TIMESTAMP, last_error = ? \
+         WHERE subscription_id = ?",
+    )
+    .bind(error)
+    .bind(subscription_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn get_calendar_event_by_uid(
+    pool: &DbPool,
+    uid: &str,
+) -> Result<Option<CalendarEventRecord>, sqlx::Error> {
+    let sql = format!("SELECT {EVENT_FIELDS} FROM calendar_events WHERE uid = ?");
+    sqlx::query_as::<_, CalendarEventRecord>(&sql)
+        .bind(uid)
+        .fetch_optional(pool)
+        .await
+}
+
+/// 按 UID 写入事件映射；提醒时间变化时重置已提醒标记
+pub async fn upsert_calendar_event(
+    pool: &DbPool,
+    event: NewCalendarEvent<'_>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO calendar_events \
+             (subscription_id, uid, node_id, starts_at, ends_at, location, recurrence_rule, reminder_at) \
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?) \
+         ON CONFLICT(uid) DO UPDATE SET \
+             subscription_id = COALESCE(excluded.subscription_id, calendar_events.subscription_id), \
+             node_id = excluded.node_id, \
+             starts_at = excluded.starts_at, \
+             ends_at = excluded.ends_at, \
+             location = excluded.location, \
+             recurrence_rule = excluded.recurrence_rule, \
+             reminder_sent_at = CASE WHEN calendar_events.reminder_at IS excluded.reminder_at \
+                 THEN calendar_events.reminder_sent_at ELSE NULL END, \
+             reminder_at = excluded.reminder_at, \
+             updated_at = CURRENT_TIMESTAMP",
+    )
+    .bind(event.subscription_id)
+    .bind(event.uid)
+    .bind(event.node_id)
+    .bind(event.starts_at)
+    .bind(event.ends_at)
+    .bind(event.location)
+    .bind(event.recurrence_rule)
+    .bind(event.reminder_at)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// reminder_at 落在 (since, now] 内、尚未提醒且任务仍待办的事件
+///
+/// 时间均为本地时间 `YYYY-MM-DD HH:MM:SS`；早于 since 的提醒视为错过，不再补发
+pub async fn list_due_calendar_reminders(
+    pool: &DbPool,
+    since: &str,
+    now: &str,
+) -> Result<Vec<CalendarReminderRow>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT e.event_id, e.node_id, n.title, e.starts_at, e.location \
+         FROM calendar_events e JOIN nodes n ON n.node_id = e.node_id \
+         WHERE e.reminder_sent_at IS NULL AND e.reminder_at > ? AND e.reminder_at <= ? \
+           AND n.is_deleted = 0 AND n.task_status = 'todo' \
+         ORDER BY e.reminder_at",
+    )
+    .bind(since)
+    .bind(now)
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn mark_calendar_reminder_sent(pool: &DbPool, event_id: i64) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE calendar_events SET reminder_sent_at = CURRENT_TIMESTAMP WHERE event_id = ?",
+    )
+    .bind(event_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
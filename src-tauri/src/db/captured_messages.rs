@@ -0,0 +1,34 @@
+use super::DbPool;
+
+/// 消息是否已捕获过
+pub async fn is_message_captured(
+    pool: &DbPool,
+    platform: &str,
+    message_key: &str,
+) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM captured_messages WHERE platform = ? AND message_key = ?)",
+    )
+    .bind(platform)
+    .bind(message_key)
+    .fetch_one(pool)
+    .await
+}
+
+/// 记录已捕获的消息；已存在时忽略
+pub async fn insert_captured_message(
+    pool: &DbPool,
+    platform: &str,
+    message_key: &str,
+    node_id: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT OR IGNORE INTO captured_messages (platform, message_key, node_id) VALUES (?, ?, ?)",
+    )
+    .bind(platform)
+    .bind(message_key)
+    .bind(node_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
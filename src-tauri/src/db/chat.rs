@@ -1,9 +1,10 @@
 use serde::Serialize;
+use sqlx::types::Json;
 use sqlx::FromRow;
 
 use super::{
-    BindingType, ChatMessageRecord, ChatSessionRecord, DbPool, NewChatMessage,
-    NewChatSession, NewMessageAttachment, ResourceSubtype,
+    BindingType, ChatMessageRecord, ChatSessionRecord, DbPool, MessageSourceRecord, NewChatMessage,
+    NewChatSession, NewMessageAttachment, NewMessageSource, ResourceSubtype, SessionRagSettings,
 };
 
 /// ChatMessage 表的完整字段列表（用于 SELECT 查询）
@@ -51,7 +52,7 @@ pub async fn get_chat_session_by_id(
     session_id: i64,
 ) -> Result<ChatSessionRecord, sqlx::Error> {
     sqlx::query_as::<_, ChatSessionRecord>(
-        "SELECT session_id, title, summary, chat_model, session_type, created_at, updated_at, is_deleted, deleted_at, user_id \
+        "SELECT session_id, title, summary, chat_model, session_type, created_at, updated_at, is_deleted, deleted_at, user_id, rag_settings, compacted_through_message_id \
          FROM chat_sessions WHERE session_id = ?",
     )
     .bind(session_id)
@@ -65,12 +66,12 @@ pub async fn list_chat_sessions_by_node(
     include_deleted: bool,
 ) -> Result<Vec<ChatSessionRecord>, sqlx::Error> {
     let sql = if include_deleted {
-        "SELECT s.session_id, s.title, s.summary, s.chat_model, s.session_type, s.created_at, s.updated_at, s.is_deleted, s.deleted_at, s.user_id \
+        "SELECT s.session_id, s.title, s.summary, s.chat_model, s.session_type, s.created_at, s.updated_at, s.is_deleted, s.deleted_at, s.user_id, s.rag_settings, s.compacted_through_message_id \
          FROM chat_sessions s \
          INNER JOIN session_bindings sb ON sb.session_id = s.session_id \
          WHERE sb.node_id = ? ORDER BY s.created_at DESC"
     } else {
-        "SELECT s.session_id, s.title, s.summary, s.chat_model, s.session_type, s.created_at, s.updated_at, s.is_deleted, s.deleted_at, s.user_id \
+        "SELECT s.session_id, s.title, s.summary, s.chat_model, s.session_type, s.created_at, s.updated_at, s.is_deleted, s.deleted_at, s.user_id, s.rag_settings, s.compacted_through_message_id \
          FROM chat_sessions s \
          INNER JOIN session_bindings sb ON sb.session_id = s.session_id \
          WHERE sb.node_id = ? AND s.is_deleted = 0 ORDER BY s.created_at DESC"
@@ -106,6 +107,131 @@ pub async fn update_chat_session(
     Ok(())
 }
 
+/// 保存会话 RAG 设置；全部为空时清空，回到全局配置
+pub async fn update_chat_session_rag_settings(
+    pool: &DbPool,
+    session_id: i64,
+    settings: &SessionRagSettings,
+) -> Result<(), sqlx::Error> {
+    let settings = (!settings.is_empty()).then_some(Json(settings));
+    sqlx::query(
+        "UPDATE chat_sessions SET rag_settings = ?, updated_at = CURRENT_TIMESTAMP WHERE session_id = ?",
+    )
+    .bind(settings)
+    .bind(session_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 保存压缩后的历史摘要及其覆盖到的最后一条消息
+pub async fn update_chat_session_compaction(
+    pool: &DbPool,
+    session_id: i64,
+    summary: &str,
+    through_message_id: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE chat_sessions SET summary = ?, compacted_through_message_id = ?, updated_at = CURRENT_TIMESTAMP \
+         WHERE session_id = ?",
+    )
+    .bind(summary)
+    .bind(through_message_id)
+    .bind(session_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 把会话复制到 through_message_id（含）为止成为新会话，返回新会话 ID
+///
+/// 一并复制消息的附件与引用来源、会话的上下文绑定与 RAG 设置；消息保留原来的创建时间。
+/// 历史摘要不复制，新会话按需重新压缩。消息不属于该会话时返回 None。
+pub async fn fork_chat_session(
+    pool: &DbPool,
+    session_id: i64,
+    through_message_id: i64,
+    title: Option<&str>,
+) -> Result<Option<i64>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let exists: Option<i64> =
+        sqlx::query_scalar("SELECT 1 FROM chat_messages WHERE message_id = ? AND session_id = ?")
+            .bind(through_message_id)
+            .bind(session_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+    if exists.is_none() {
+        return Ok(None);
+    }
+
+    let new_session_id: i64 = sqlx::query_scalar(
+        "INSERT INTO chat_sessions (title, chat_model, session_type, user_id, rag_settings) \
+         SELECT COALESCE(?, title), chat_model, session_type, user_id, rag_settings \
+         FROM chat_sessions WHERE session_id = ? RETURNING session_id",
+    )
+    .bind(title)
+    .bind(session_id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let message_ids: Vec<i64> = sqlx::query_scalar(
+        "SELECT message_id FROM chat_messages WHERE session_id = ? AND message_id <= ? \
+         ORDER BY message_id",
+    )
+    .bind(session_id)
+    .bind(through_message_id)
+    .fetch_all(&mut *tx)
+    .await?;
+    for message_id in message_ids {
+        let new_message_id: i64 = sqlx::query_scalar(
+            "INSERT INTO chat_messages (session_id, user_content, thinking_summary, assistant_content, \
+                 thinking_effort, input_tokens, output_tokens, reasoning_tokens, total_tokens, created_at) \
+             SELECT ?, user_content, thinking_summary, assistant_content, thinking_effort, \
+                 input_tokens, output_tokens, reasoning_tokens, total_tokens, created_at \
+             FROM chat_messages WHERE message_id = ? RETURNING message_id",
+        )
+        .bind(new_session_id)
+        .bind(message_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        sqlx::query(
+            "INSERT INTO message_attachments (message_id, node_id) \
+             SELECT ?, node_id FROM message_attachments WHERE message_id = ?",
+        )
+        .bind(new_message_id)
+        .bind(message_id)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            "INSERT INTO message_sources (message_id, node_id, chunk_index, rank, score, snippet) \
+             SELECT ?, node_id, chunk_index, rank, score, snippet FROM message_sources WHERE message_id = ?",
+        )
+        .bind(new_message_id)
+        .bind(message_id)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    sqlx::query(
+        "INSERT INTO session_bindings (session_id, node_id, binding_type) \
+         SELECT ?, node_id, binding_type FROM session_bindings WHERE session_id = ?",
+    )
+    .bind(new_session_id)
+    .bind(session_id)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    tracing::info!(
+        session_id,
+        through_message_id,
+        new_session_id,
+        "Chat session forked"
+    );
+    Ok(Some(new_session_id))
+}
+
 pub async fn soft_delete_chat_session(pool: &DbPool, session_id: i64) -> Result<(), sqlx::Error> {
     sqlx::query!(
         "UPDATE chat_sessions SET is_deleted = 1, deleted_at = CURRENT_TIMESTAMP WHERE session_id = ? AND is_deleted = 0",
@@ -231,6 +357,64 @@ pub async fn list_message_attachments_with_node(
     .await
 }
 
+/// 记录消息注入 prompt 的 RAG 片段，返回带节点标题的记录
+pub async fn insert_message_sources(
+    pool: &DbPool,
+    message_id: i64,
+    sources: &[NewMessageSource<'_>],
+) -> Result<Vec<MessageSourceRecord>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    for source in sources {
+        sqlx::query(
+            "INSERT INTO message_sources (message_id, node_id, chunk_index, rank, score, snippet) \
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(message_id)
+        .bind(source.node_id)
+        .bind(source.chunk_index)
+        .bind(source.rank)
+        .bind(source.score)
+        .bind(source.snippet)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+
+    list_message_sources(pool, message_id).await
+}
+
+pub async fn list_message_sources(
+    pool: &DbPool,
+    message_id: i64,
+) -> Result<Vec<MessageSourceRecord>, sqlx::Error> {
+    sqlx::query_as::<_, MessageSourceRecord>(
+        "SELECT ms.message_id, ms.node_id, n.title AS node_title, ms.chunk_index, ms.rank, ms.score, ms.snippet \
+         FROM message_sources ms \
+         INNER JOIN nodes n ON n.node_id = ms.node_id \
+         WHERE ms.message_id = ? ORDER BY ms.rank",
+    )
+    .bind(message_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// 会话内所有消息的引用来源（按消息、编号排序）
+pub async fn list_session_message_sources(
+    pool: &DbPool,
+    session_id: i64,
+) -> Result<Vec<MessageSourceRecord>, sqlx::Error> {
+    sqlx::query_as::<_, MessageSourceRecord>(
+        "SELECT ms.message_id, ms.node_id, n.title AS node_title, ms.chunk_index, ms.rank, ms.score, ms.snippet \
+         FROM message_sources ms \
+         INNER JOIN chat_messages m ON m.message_id = ms.message_id \
+         INNER JOIN nodes n ON n.node_id = ms.node_id \
+         WHERE m.session_id = ? ORDER BY ms.message_id, ms.rank",
+    )
+    .bind(session_id)
+    .fetch_all(pool)
+    .await
+}
+
 pub async fn delete_message_attachment(
     pool: &DbPool,
     message_id: i64,
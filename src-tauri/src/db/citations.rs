@@ -0,0 +1,72 @@
+use sqlx::types::Json;
+
+use super::{CitationRecord, DbPool, NewCitation};
+
+const CITATION_FIELDS: &str =
+    "c.node_id, c.title, c.authors, c.year, c.venue, c.doi, c.source, c.created_at, c.updated_at";
+
+/// 写入文献元数据；自动抽取（llm）不会覆盖用户手动编辑过的记录
+pub async fn upsert_citation(pool: &DbPool, params: NewCitation<'_>) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "INSERT INTO citations (node_id, title, authors, year, venue, doi, source) \
+         VALUES (?, ?, ?, ?, ?, ?, ?) \
+         ON CONFLICT(node_id) DO UPDATE SET \
+             title = excluded.title, authors = excluded.authors, year = excluded.year, \
+             venue = excluded.venue, doi = excluded.doi, source = excluded.source, \
+             updated_at = CURRENT_TIMESTAMP \
+         WHERE citations.source = 'llm' OR excluded.source = 'manual'",
+    )
+    .bind(params.node_id)
+    .bind(params.title)
+    .bind(Json(params.authors))
+    .bind(params.year)
+    .bind(params.venue)
+    .bind(params.doi)
+    .bind(params.source)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn get_citation_by_node(
+    pool: &DbPool,
+    node_id: i64,
+) -> Result<Option<CitationRecord>, sqlx::Error> {
+    let sql = format!("SELECT {CITATION_FIELDS} FROM citations c WHERE c.node_id = ?");
+    sqlx::query_as::<_, CitationRecord>(&sql)
+        .bind(node_id)
+        .fetch_optional(pool)
+        .await
+}
+
+/// 文献库：所有带引用元数据的资源，可按主题（直接包含）过滤
+pub async fn list_citations(
+    pool: &DbPool,
+    topic_id: Option<i64>,
+) -> Result<Vec<CitationRecord>, sqlx::Error> {
+    let topic_join = if topic_id.is_some() {
+        "INNER JOIN edges e ON e.target_node_id = c.node_id \
+             AND e.source_node_id = ? AND e.relation_type = 'contains' AND e.is_deleted = 0"
+    } else {
+        ""
+    };
+    let sql = format!(
+        "SELECT {CITATION_FIELDS} FROM citations c \
+         INNER JOIN nodes n ON n.node_id = c.node_id AND n.is_deleted = 0 \
+         {topic_join} \
+         ORDER BY c.year DESC, c.title ASC"
+    );
+    let mut query = sqlx::query_as::<_, CitationRecord>(&sql);
+    if let Some(topic_id) = topic_id {
+        query = query.bind(topic_id);
+    }
+    query.fetch_all(pool).await
+}
+
+pub async fn delete_citation(pool: &DbPool, node_id: i64) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM citations WHERE node_id = ?")
+        .bind(node_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use super::{
+    refresh_node_links, update_node_content_stats, ContentRevisionReason, ContentRevisionRecord,
+    DbPool,
+};
+use crate::error::{AppError, AppResult};
+use crate::i18n::MessageCode;
+
+/// 每个资源保留的正文版本数
+const MAX_REVISIONS_PER_NODE: i64 = 50;
+/// 列表中的预览字符数
+const PREVIEW_CHARS: i64 = 200;
+
+/// 被保存为版本的正文
+#[derive(Debug, sqlx::FromRow)]
+pub struct ContentRevisionContent {
+    pub node_id: i64,
+    pub content: String,
+    pub content_hash: Option<String>,
+}
+
+/// 正文替换结果
+#[derive(Debug, Clone, Copy)]
+pub struct ContentReplacement {
+    /// 被替换内容保存成的版本，未保存时为空
+    pub revision_id: Option<i64>,
+    /// 节点新的版本号
+    pub version: i64,
+}
+
+/// 替换资源正文，并把被替换的内容保存为版本；原内容为空或未变化时不保存
+///
+/// 给出 expected_version 时只在节点版本一致时写入，否则返回 [`MessageCode::NodeEditConflict`]
+pub async fn replace_node_content(
+    pool: &DbPool,
+    node_id: i64,
+    content: &str,
+    file_hash: Option<&str>,
+    reason: ContentRevisionReason,
+    expected_version: Option<i64>,
+) -> AppResult<ContentReplacement> {
+    let mut tx = pool.begin().await?;
+
+    let current: Option<(Option<String>, Option<String>, i64)> = sqlx::query_as(
+        "SELECT file_content, file_hash, version FROM nodes \
+         WHERE node_id = ? AND node_type = 'resource'",
+    )
+    .bind(node_id)
+    .fetch_optional(&mut *tx)
+    .await?;
+    let Some((old_content, old_hash, version)) = current else {
+        return Err(AppError::NotFound {
+            entity: "resource",
+            id: node_id,
+        });
+    };
+    if expected_version.is_some_and(|expected| expected != version) {
+        return Err(AppError::coded(MessageCode::NodeEditConflict));
+    }
+
+    let revision_id = match old_content {
+        Some(old_content) if !old_content.is_empty() && old_content != content => {
+            let revision_id: i64 = sqlx::query_scalar(
+                "INSERT INTO node_content_revisions (node_id, content, content_hash, reason) \
+                 VALUES (?, ?, ?, ?) RETURNING revision_id",
+            )
+            .bind(node_id)
+            .bind(&old_content)
+            .bind(old_hash)
+            .bind(reason)
+            .fetch_one(&mut *tx)
+            .await?;
+            sqlx::query(
+                "DELETE FROM node_content_revisions WHERE node_id = ? AND revision_id NOT IN \
+                 (SELECT revision_id FROM node_content_revisions WHERE node_id = ? \
+                  ORDER BY revision_id DESC LIMIT ?)",
+            )
+            .bind(node_id)
+            .bind(node_id)
+            .bind(MAX_REVISIONS_PER_NODE)
+            .execute(&mut *tx)
+            .await?;
+            Some(revision_id)
+        }
+        _ => None,
+    };
+
+    let version: Option<i64> = sqlx::query_scalar(
+        "UPDATE nodes SET file_content = ?, file_hash = COALESCE(?, file_hash), \
+             version = version + 1, updated_at = CURRENT_TIMESTAMP \
+         WHERE node_id = ? AND version = ? RETURNING version",
+    )
+    .bind(content)
+    .bind(file_hash)
+    .bind(node_id)
+    .bind(version)
+    .fetch_optional(&mut *tx)
+    .await?;
+    let Some(version) = version else {
+        return Err(AppError::coded(MessageCode::NodeEditConflict));
+    };
+    update_node_content_stats(&mut *tx, node_id, Some(content)).await?;
+    refresh_node_links(&mut tx, node_id, Some(content)).await?;
+
+    tx.commit().await?;
+    tracing::debug!(node_id, revision_id = ?revision_id, version, "Node content replaced");
+    Ok(ContentReplacement {
+        revision_id,
+        version,
+    })
+}
+
+/// 资源的正文版本，最新的在前
+pub async fn list_content_revisions(
+    pool: &DbPool,
+    node_id: i64,
+) -> Result<Vec<ContentRevisionRecord>, sqlx::Error> {
+    sqlx::query_as::<_, ContentRevisionRecord>(
+        "SELECT revision_id, node_id, substr(content, 1, ?) AS preview, \
+             length(content) AS content_length, content_hash, reason, created_at \
+         FROM node_content_revisions WHERE node_id = ? ORDER BY revision_id DESC",
+    )
+    .bind(PREVIEW_CHARS)
+    .bind(node_id)
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn get_content_revision(
+    pool: &DbPool,
+    revision_id: i64,
+) -> Result<Option<ContentRevisionContent>, sqlx::Error> {
+    sqlx::query_as::<_, ContentRevisionContent>(
+        "SELECT node_id, content, content_hash FROM node_content_revisions WHERE revision_id = ?",
+    )
+    .bind(revision_id)
+    .fetch_optional(pool)
+    .await
+}
@@ -0,0 +1,176 @@
+//! 后台数据迁移的进度记录
+
+use super::{update_node_content_stats, DataMigrationRecord, DataMigrationStatus, DbPool};
+
+const DATA_MIGRATION_FIELDS: &str =
+    "name, status, cursor, processed, total, last_error, started_at, finished_at, updated_at";
+
+pub async fn list_data_migrations(pool: &DbPool) -> Result<Vec<DataMigrationRecord>, sqlx::Error> {
+    let sql = format!("SELECT {DATA_MIGRATION_FIELDS} FROM data_migrations ORDER BY name");
+    sqlx::query_as::<_, DataMigrationRecord>(&sql)
+        .fetch_all(pool)
+        .await
+}
+
+/// 读取迁移进度，首次运行时插入 pending 记录
+pub async fn get_or_create_data_migration(
+    pool: &DbPool,
+    name: &str,
+) -> Result<DataMigrationRecord, sqlx::Error> {
+    sqlx::query("INSERT OR IGNORE INTO data_migrations (name) VALUES (?)")
+        .bind(name)
+        .execute(pool)
+        .await?;
+    let sql = format!("SELECT {DATA_MIGRATION_FIELDS} FROM data_migrations WHERE name = ?");
+    sqlx::query_as::<_, DataMigrationRecord>(&sql)
+        .bind(name)
+        .fetch_one(pool)
+        .await
+}
+
+/// 开始（或恢复）执行：清除上次的错误，total 为本次估算的总量
+pub async fn mark_data_migration_running(
+    pool: &DbPool,
+    name: &str,
+    total: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE data_migrations SET status = 'running', total = ?, last_error = NULL, \
+         started_at = COALESCE(started_at, CURRENT_TIMESTAMP), updated_at = CURRENT_TIMESTAMP \
+         WHERE name = ?",
+    )
+    .bind(total)
+    .bind(name)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn update_data_migration_progress(
+    pool: &DbPool,
+    name: &str,
+    cursor: i64,
+    processed: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE data_migrations SET cursor = ?, processed = ?, updated_at = CURRENT_TIMESTAMP \
+         WHERE name = ?",
+    )
+    .bind(cursor)
+    .bind(processed)
+    .bind(name)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 结束执行；失败时保留 cursor，下次启动从中断处继续
+pub async fn finish_data_migration(
+    pool: &DbPool,
+    name: &str,
+    status: DataMigrationStatus,
+    error: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE data_migrations SET status = ?, last_error = ?, \
+         finished_at = CASE WHEN ? = 'done' THEN CURRENT_TIMESTAMP ELSE finished_at END, \
+         updated_at = CURRENT_TIMESTAMP WHERE name = ?",
+    )
+    .bind(status)
+    .bind(error)
+    .bind(status)
+    .bind(name)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 文本资源（无文件）缺少的 file_hash：node_id > cursor 的前 limit 条
+pub async fn list_text_resources_missing_hash(
+    pool: &DbPool,
+    cursor: i64,
+    limit: i64,
+) -> Result<Vec<(i64, String)>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT node_id, file_content FROM nodes \
+         WHERE node_type = 'resource' AND file_path IS NULL AND file_hash IS NULL \
+           AND file_content IS NOT NULL AND node_id > ? \
+         ORDER BY node_id LIMIT ?",
+    )
+    .bind(cursor)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn count_text_resources_missing_hash(
+    pool: &DbPool,
+    cursor: i64,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT COUNT(*) FROM nodes \
+         WHERE node_type = 'resource' AND file_path IS NULL AND file_hash IS NULL \
+           AND file_content IS NOT NULL AND node_id > ?",
+    )
+    .bind(cursor)
+    .fetch_one(pool)
+    .await
+}
+
+/// 批量写入 file_hash（同一事务），只覆盖仍为空的值
+pub async fn backfill_file_hashes(
+    pool: &DbPool,
+    hashes: &[(i64, String)],
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    for (node_id, hash) in hashes {
+        sqlx::query("UPDATE nodes SET file_hash = ? WHERE node_id = ? AND file_hash IS NULL")
+            .bind(hash)
+            .bind(node_id)
+            .execute(&mut *tx)
+            .await?;
+    }
+    tx.commit().await
+}
+
+/// 有正文但缺少统计（word_count 为空）的节点：node_id > cursor 的前 limit 条
+pub async fn list_nodes_missing_content_stats(
+    pool: &DbPool,
+    cursor: i64,
+    limit: i64,
+) -> Result<Vec<(i64, String)>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT node_id, file_content FROM nodes \
+         WHERE word_count IS NULL AND file_content IS NOT NULL AND node_id > ? \
+         ORDER BY node_id LIMIT ?",
+    )
+    .bind(cursor)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn count_nodes_missing_content_stats(
+    pool: &DbPool,
+    cursor: i64,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT COUNT(*) FROM nodes \
+         WHERE word_count IS NULL AND file_content IS NOT NULL AND node_id > ?",
+    )
+    .bind(cursor)
+    .fetch_one(pool)
+    .await
+}
+
+/// 批量计算并写入正文统计（同一事务）
+pub async fn backfill_content_stats(
+    pool: &DbPool,
+    rows: &[(i64, String)],
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    for (node_id, content) in rows {
+        update_node_content_stats(&mut *tx, *node_id, Some(content)).await?;
+    }
+    tx.commit().await
+}
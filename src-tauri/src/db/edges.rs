@@ -1,6 +1,6 @@
 use sqlx::{Executor, Sqlite};
 
-use super::{DbPool, EdgeRecord, EdgeRelationType, NewEdge, NodeRecord};
+use super::{DbPool, EdgeRecord, EdgeRelationType, EdgeTransferRecord, NewEdge, NodeRecord};
 
 pub async fn contains_creates_cycle<'a, E>(
     executor: E,
@@ -44,10 +44,10 @@ pub async fn insert_edge(pool: &DbPool, params: NewEdge) -> Result<i64, sqlx::Er
     Ok(result.last_insert_rowid())
 }
 
-pub async fn insert_edge_if_missing(
-    pool: &DbPool,
-    params: NewEdge,
-) -> Result<(), sqlx::Error> {
+pub async fn insert_edge_if_missing<'a, E>(executor: E, params: NewEdge) -> Result<(), sqlx::Error>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
     sqlx::query!(
         "INSERT OR IGNORE INTO edges (source_node_id, target_node_id, relation_type, confidence_score, is_manual) \
          VALUES (?, ?, ?, ?, ?)",
@@ -57,12 +57,97 @@ pub async fn insert_edge_if_missing(
         params.confidence_score,
         params.is_manual,
     )
-    .execute(pool)
+    .execute(executor)
     .await?;
 
     Ok(())
 }
 
+/// 两节点间是否已有该类型的边（含已软删除的，受唯一约束限制无法再插入）
+pub async fn edge_exists<'a, E>(
+    executor: E,
+    source_node_id: i64,
+    target_node_id: i64,
+    relation_type: EdgeRelationType,
+) -> Result<bool, sqlx::Error>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM edges \
+         WHERE source_node_id = ? AND target_node_id = ? AND relation_type = ?)",
+    )
+    .bind(source_node_id)
+    .bind(target_node_id)
+    .bind(relation_type)
+    .fetch_one(executor)
+    .await
+}
+
+/// 导出用的边（两端节点以 uuid 表示）；node_ids 非空时只取与这些节点相连的边
+pub async fn list_edge_transfer_records(
+    pool: &DbPool,
+    relation_types: &[EdgeRelationType],
+    manual_only: bool,
+    node_ids: &[i64],
+) -> Result<Vec<EdgeTransferRecord>, sqlx::Error> {
+    if relation_types.is_empty() {
+        return Ok(Vec::new());
+    }
+    let relation_placeholders = vec!["?"; relation_types.len()].join(", ");
+    let mut sql = format!(
+        "SELECT s.uuid AS source, t.uuid AS target, e.relation_type, e.confidence_score, e.is_manual \
+         FROM edges e \
+         JOIN nodes s ON s.node_id = e.source_node_id \
+         JOIN nodes t ON t.node_id = e.target_node_id \
+         WHERE e.is_deleted = 0 AND s.is_deleted = 0 AND t.is_deleted = 0 \
+           AND e.relation_type IN ({relation_placeholders})"
+    );
+    if manual_only {
+        sql.push_str(" AND e.is_manual = 1");
+    }
+    if !node_ids.is_empty() {
+        let node_placeholders = vec!["?"; node_ids.len()].join(", ");
+        sql.push_str(&format!(
+            " AND (e.source_node_id IN ({node_placeholders}) OR e.target_node_id IN ({node_placeholders}))"
+        ));
+    }
+    sql.push_str(" ORDER BY e.edge_id");
+
+    let mut query = sqlx::query_as::<_, EdgeTransferRecord>(&sql);
+    for relation_type in relation_types {
+        query = query.bind(*relation_type);
+    }
+    for _ in 0..2 {
+        for node_id in node_ids {
+            query = query.bind(*node_id);
+        }
+    }
+    query.fetch_all(pool).await
+}
+
+/// 记录派生关系：derived_node_id 由 source_node_id 生成（翻译、转写、保存的回答等）
+pub async fn link_derived_node<'a, E>(
+    executor: E,
+    derived_node_id: i64,
+    source_node_id: i64,
+) -> Result<(), sqlx::Error>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    insert_edge_if_missing(
+        executor,
+        NewEdge {
+            source_node_id: derived_node_id,
+            target_node_id: source_node_id,
+            relation_type: EdgeRelationType::DerivedFrom,
+            confidence_score: None,
+            is_manual: false,
+        },
+    )
+    .await
+}
+
 pub async fn delete_edge(
     pool: &DbPool,
     source_node_id: i64,
@@ -150,7 +235,9 @@ pub async fn list_target_nodes(
     sqlx::query_as::<_, NodeRecord>(
         "SELECT n.node_id, n.uuid, n.user_id, n.title, n.summary, n.node_type, n.task_status, n.priority, n.due_date, n.done_date, \
             n.file_hash, n.file_path, n.file_content, n.user_note, n.resource_subtype, n.source_meta, n.embedded_hash, n.processing_hash, n.embedding_status, \
-            n.last_embedding_at, n.last_embedding_error, n.processing_stage, n.review_status, n.is_pinned, n.pinned_at, n.created_at, n.updated_at, n.is_deleted, n.deleted_at \
+            n.last_embedding_at, n.last_embedding_error, n.processing_stage, n.review_status, n.is_pinned, n.pinned_at, n.created_at, n.updated_at, n.is_deleted, n.deleted_at, n.recurrence_rule, n.exclude_from_search, n.read_time_minutes, \
+            n.word_count, n.char_count, n.token_count, n.content_language, n.processing_attempts, n.auto_classify_allowed, n.version, n.archived_at, \
+            (SELECT progress FROM node_reading_state r WHERE r.node_id = n.node_id) AS reading_progress \
          FROM edges e \
          INNER JOIN nodes n ON n.node_id = e.target_node_id \
          WHERE e.source_node_id = ? AND e.relation_type = ? AND e.is_deleted = 0 AND n.is_deleted = 0",
@@ -169,7 +256,9 @@ pub async fn list_source_nodes(
     sqlx::query_as::<_, NodeRecord>(
         "SELECT n.node_id, n.uuid, n.user_id, n.title, n.summary, n.node_type, n.task_status, n.priority, n.due_date, n.done_date, \
             n.file_hash, n.file_path, n.file_content, n.user_note, n.resource_subtype, n.source_meta, n.embedded_hash, n.processing_hash, n.embedding_status, \
-            n.last_embedding_at, n.last_embedding_error, n.processing_stage, n.review_status, n.is_pinned, n.pinned_at, n.created_at, n.updated_at, n.is_deleted, n.deleted_at \
+            n.last_embedding_at, n.last_embedding_error, n.processing_stage, n.review_status, n.is_pinned, n.pinned_at, n.created_at, n.updated_at, n.is_deleted, n.deleted_at, n.recurrence_rule, n.exclude_from_search, n.read_time_minutes, \
+            n.word_count, n.char_count, n.token_count, n.content_language, n.processing_attempts, n.auto_classify_allowed, n.version, n.archived_at, \
+            (SELECT progress FROM node_reading_state r WHERE r.node_id = n.node_id) AS reading_progress \
          FROM edges e \
          INNER JOIN nodes n ON n.node_id = e.source_node_id \
          WHERE e.target_node_id = ? AND e.relation_type = ? AND e.is_deleted = 0 AND n.is_deleted = 0",
@@ -0,0 +1,144 @@
+use super::{
+    DbPool, ExportFormat, ExportRunCounts, ExportRunRecord, ExportRunStatus, ExportTrigger,
+};
+
+const EXPORT_RUN_FIELDS: &str = "run_id, trigger, format, since, status, file_path, node_count, \
+     file_count, size_bytes, error, started_at, finished_at";
+
+pub async fn create_export_run(
+    pool: &DbPool,
+    trigger: ExportTrigger,
+    format: ExportFormat,
+    since: Option<&str>,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar(
+        "INSERT INTO export_runs (trigger, format, since) VALUES (?, ?, ?) RETURNING run_id",
+    )
+    .bind(trigger)
+    .bind(format)
+    .bind(since)
+    .fetch_one(pool)
+    .await
+}
+
+/// 标记结束；error 非空表示导出失败
+pub async fn finish_export_run(
+    pool: &DbPool,
+    run_id: i64,
+    file_path: Option<&str>,
+    counts: ExportRunCounts,
+    error: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    let status = if error.is_some() {
+        ExportRunStatus::Failed
+    } else {
+        ExportRunStatus::Succeeded
+    };
+    sqlx::query(
+        "UPDATE export_runs SET status = ?, file_path = ?, node_count = ?, file_count = ?, \
+             size_bytes = ?, error = ?, finished_at = CURRENT_TIMESTAMP \
+         WHERE run_id = ?",
+    )
+    .bind(status)
+    .bind(file_path)
+    .bind(counts.node_count)
+    .bind(counts.file_count)
+    .bind(counts.size_bytes)
+    .bind(error)
+    .bind(run_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 上次运行中被中断（退出、崩溃）的导出标记为失败
+pub async fn fail_interrupted_export_runs(pool: &DbPool) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(
+        "UPDATE export_runs SET status = 'failed', error = 'interrupted', \
+             finished_at = CURRENT_TIMESTAMP \
+         WHERE status = 'running'",
+    )
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+pub async fn get_export_run(
+    pool: &DbPool,
+    run_id: i64,
+) -> Result<Option<ExportRunRecord>, sqlx::Error> {
+    let sql = format!("SELECT {EXPORT_RUN_FIELDS} FROM export_runs WHERE run_id = ?");
+    sqlx::query_as::<_, ExportRunRecord>(&sql)
+        .bind(run_id)
+        .fetch_optional(pool)
+        .await
+}
+
+/// 最近一次已结束的定期导出（不论成败），用于判断下次导出时间
+pub async fn get_latest_scheduled_export_run(
+    pool: &DbPool,
+) -> Result<Option<ExportRunRecord>, sqlx::Error> {
+    let sql = format!(
+        "SELECT {EXPORT_RUN_FIELDS} FROM export_runs \
+         WHERE trigger = 'scheduled' AND status != 'running' \
+         ORDER BY run_id DESC LIMIT 1"
+    );
+    sqlx::query_as::<_, ExportRunRecord>(&sql)
+        .fetch_optional(pool)
+        .await
+}
+
+/// 该格式最近一次成功的导出，增量导出从它的开始时间算起
+pub async fn get_latest_succeeded_export_run(
+    pool: &DbPool,
+    format: ExportFormat,
+) -> Result<Option<ExportRunRecord>, sqlx::Error> {
+    let sql = format!(
+        "SELECT {EXPORT_RUN_FIELDS} FROM export_runs \
+         WHERE format = ? AND status = 'succeeded' \
+         ORDER BY run_id DESC LIMIT 1"
+    );
+    sqlx::query_as::<_, ExportRunRecord>(&sql)
+        .bind(format)
+        .fetch_optional(pool)
+        .await
+}
+
+pub async fn list_recent_export_runs(
+    pool: &DbPool,
+    limit: i64,
+) -> Result<Vec<ExportRunRecord>, sqlx::Error> {
+    let sql = format!("SELECT {EXPORT_RUN_FIELDS} FROM export_runs ORDER BY run_id DESC LIMIT ?");
+    sqlx::query_as::<_, ExportRunRecord>(&sql)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+}
+
+/// 只保留最近 keep 条记录
+pub async fn prune_export_runs(pool: &DbPool, keep: i64) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "DELETE FROM export_runs WHERE run_id NOT IN \
+         (SELECT run_id FROM export_runs ORDER BY run_id DESC LIMIT ?)",
+    )
+    .bind(keep)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 把数据库一致地复制到 path（目标文件必须不存在）
+pub async fn snapshot_database(pool: &DbPool, path: &str) -> Result<(), sqlx::Error> {
+    sqlx::query("VACUUM INTO ?")
+        .bind(path)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// 快照中未删除的节点数与最近一次修改时间
+pub async fn get_snapshot_stats(pool: &DbPool) -> Result<(i64, Option<String>), sqlx::Error> {
+    sqlx::query_as("SELECT COUNT(*), MAX(updated_at) FROM nodes WHERE is_deleted = 0")
+        .fetch_one(pool)
+        .await
+}
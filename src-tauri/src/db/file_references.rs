@@ -0,0 +1,112 @@
+use super::{DbPool, FileReferenceRecord, FileStat};
+
+const REFERENCE_FIELDS: &str = "r.node_id, n.title, n.file_path, r.status, r.file_size, \
+     r.modified_at, r.checked_at, r.last_error";
+
+/// 记录以引用方式捕获的文件；节点的 file_path 已是文件的绝对路径
+pub async fn insert_file_reference(
+    pool: &DbPool,
+    node_id: i64,
+    stat: FileStat,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("INSERT INTO file_references (node_id, file_size, modified_at) VALUES (?, ?, ?)")
+        .bind(node_id)
+        .bind(stat.file_size)
+        .bind(stat.modified_at)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+pub async fn get_file_reference(
+    pool: &DbPool,
+    node_id: i64,
+) -> Result<Option<FileReferenceRecord>, sqlx::Error> {
+    let sql = format!(
+        "SELECT {REFERENCE_FIELDS} FROM file_references r JOIN nodes n ON n.node_id = r.node_id \
+         WHERE r.node_id = ? AND n.file_path IS NOT NULL"
+    );
+    sqlx::query_as::<_, FileReferenceRecord>(&sql)
+        .bind(node_id)
+        .fetch_optional(pool)
+        .await
+}
+
+/// 未删除资源的所有引用
+pub async fn list_file_references(pool: &DbPool) -> Result<Vec<FileReferenceRecord>, sqlx::Error> {
+    let sql = format!(
+        "SELECT {REFERENCE_FIELDS} FROM file_references r JOIN nodes n ON n.node_id = r.node_id \
+         WHERE n.is_deleted = 0 AND n.file_path IS NOT NULL ORDER BY r.node_id"
+    );
+    sqlx::query_as::<_, FileReferenceRecord>(&sql)
+        .fetch_all(pool)
+        .await
+}
+
+/// 指向 file_path 的引用（同一文件可能被捕获多次）
+pub async fn find_file_references_by_path(
+    pool: &DbPool,
+    file_path: &str,
+) -> Result<Vec<FileReferenceRecord>, sqlx::Error> {
+    let sql = format!(
+        "SELECT {REFERENCE_FIELDS} FROM file_references r JOIN nodes n ON n.node_id = r.node_id \
+         WHERE n.file_path = ? AND n.is_deleted = 0 ORDER BY r.node_id"
+    );
+    sqlx::query_as::<_, FileReferenceRecord>(&sql)
+        .bind(file_path)
+        .fetch_all(pool)
+        .await
+}
+
+/// 文件可读：记录最新的大小与修改时间
+pub async fn mark_file_reference_online(
+    pool: &DbPool,
+    node_id: i64,
+    stat: FileStat,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE file_references SET status = 'online', file_size = ?, modified_at = ?, \
+             checked_at = CURRENT_TIMESTAMP, last_error = NULL \
+         WHERE node_id = ?",
+    )
+    .bind(stat.file_size)
+    .bind(stat.modified_at)
+    .bind(node_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn mark_file_reference_offline(
+    pool: &DbPool,
+    node_id: i64,
+    error: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE file_references SET status = 'offline', checked_at = CURRENT_TIMESTAMP, \
+             last_error = ? \
+         WHERE node_id = ?",
+    )
+    .bind(error)
+    .bind(node_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 文件被移动或重新指定后更新节点的文件路径
+pub async fn update_file_reference_path(
+    pool: &DbPool,
+    node_id: i64,
+    file_path: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE nodes SET file_path = ?, updated_at = CURRENT_TIMESTAMP \
+         WHERE node_id = ? AND node_id IN (SELECT node_id FROM file_references)",
+    )
+    .bind(file_path)
+    .bind(node_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use super::{DbPool, FocusDailyStat, FocusSessionRecord, FocusSessionStatus, FocusTaskStat};
+
+const FOCUS_FIELDS: &str =
+    "session_id, task_id, planned_seconds, actual_seconds, status, do_not_disturb, started_at, ended_at";
+
+pub async fn insert_focus_session(
+    pool: &DbPool,
+    task_id: Option<i64>,
+    planned_seconds: i64,
+    do_not_disturb: bool,
+) -> Result<i64, sqlx::Error> {
+    let result = sqlx::query(
+        "INSERT INTO focus_sessions (task_id, planned_seconds, do_not_disturb) VALUES (?, ?, ?)",
+    )
+    .bind(task_id)
+    .bind(planned_seconds)
+    .bind(do_not_disturb)
+    .execute(pool)
+    .await?;
+
+    Ok(result.last_insert_rowid())
+}
+
+pub async fn get_focus_session_by_id(
+    pool: &DbPool,
+    session_id: i64,
+) -> Result<FocusSessionRecord, sqlx::Error> {
+    let sql = format!("SELECT {FOCUS_FIELDS} FROM focus_sessions WHERE session_id = ?");
+    sqlx::query_as::<_, FocusSessionRecord>(&sql)
+        .bind(session_id)
+        .fetch_one(pool)
+        .await
+}
+
+pub async fn finish_focus_session(
+    pool: &DbPool,
+    session_id: i64,
+    status: FocusSessionStatus,
+    actual_seconds: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE focus_sessions SET status = ?, actual_seconds = ?, ended_at = CURRENT_TIMESTAMP \
+         WHERE session_id = ? AND status = 'active'",
+    )
+    .bind(status)
+    .bind(actual_seconds)
+    .bind(session_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 应用异常退出后遗留的 active 会话：按开始时间估算时长并标记为 interrupted
+pub async fn close_stale_focus_sessions(pool: &DbPool) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(
+        "UPDATE focus_sessions SET status = 'interrupted', ended_at = CURRENT_TIMESTAMP, \
+         actual_seconds = MIN(planned_seconds, CAST(strftime('%s', 'now') - strftime('%s', started_at) AS INTEGER)) \
+         WHERE status = 'active'",
+    )
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+pub async fn list_focus_sessions(
+    pool: &DbPool,
+    task_id: Option<i64>,
+    limit: i64,
+) -> Result<Vec<FocusSessionRecord>, sqlx::Error> {
+    let filter = if task_id.is_some() {
+        "WHERE task_id = ?"
+    } else {
+        ""
+    };
+    let sql = format!(
+        "SELECT {FOCUS_FIELDS} FROM focus_sessions {filter} ORDER BY started_at DESC LIMIT ?"
+    );
+    let mut query = sqlx::query_as::<_, FocusSessionRecord>(&sql);
+    if let Some(task_id) = task_id {
+        query = query.bind(task_id);
+    }
+    query.bind(limit).fetch_all(pool).await
+}
+
+/// 按本地日期统计专注时长（date 为 YYYY-MM-DD）
+pub async fn list_focus_daily_stats(
+    pool: &DbPool,
+    start_date: &str,
+    end_date: &str,
+) -> Result<Vec<FocusDailyStat>, sqlx::Error> {
+    sqlx::query_as::<_, FocusDailyStat>(
+        "SELECT date(started_at, 'localtime') AS date, \
+                COALESCE(SUM(actual_seconds), 0) AS focus_seconds, \
+                COUNT(*) AS session_count \
+         FROM focus_sessions \
+         WHERE status != 'active' \
+           AND date(started_at, 'localtime') BETWEEN date(?) AND date(?) \
+         GROUP BY date(started_at, 'localtime') \
+         ORDER BY date ASC",
+    )
+    .bind(start_date)
+    .bind(end_date)
+    .fetch_all(pool)
+    .await
+}
+
+/// 按任务统计专注时长
+pub async fn list_focus_task_stats(
+    pool: &DbPool,
+    start_date: &str,
+    end_date: &str,
+) -> Result<Vec<FocusTaskStat>, sqlx::Error> {
+    sqlx::query_as::<_, FocusTaskStat>(
+        "SELECT f.task_id AS task_id, n.title AS title, \
+                COALESCE(SUM(f.actual_seconds), 0) AS focus_seconds, \
+                COUNT(*) AS session_count \
+         FROM focus_sessions f \
+         INNER JOIN nodes n ON n.node_id = f.task_id \
+         WHERE f.status != 'active' \
+           AND date(f.started_at, 'localtime') BETWEEN date(?) AND date(?) \
+         GROUP BY f.task_id \
+         ORDER BY focus_seconds DESC",
+    )
+    .bind(start_date)
+    .bind(end_date)
+    .fetch_all(pool)
+    .await
+}
+
+/// 今日专注秒数（用于 Dashboard）
+pub async fn sum_focus_seconds_today(pool: &DbPool) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT COALESCE(SUM(actual_seconds), 0) FROM focus_sessions \
+         WHERE status != 'active' AND date(started_at, 'localtime') = date('now', 'localtime')",
+    )
+    .fetch_one(pool)
+    .await
+}
@@ -0,0 +1,145 @@
+use super::{DbPool, GithubItemRecord, GithubRepoRecord, NewGithubItem};
+
+const REPO_FIELDS: &str =
+    "repo_id, owner, name, topic_id, token_encrypted IS NOT NULL AS has_token, \
+     poll_interval_minutes, is_enabled, since_cursor, last_synced_at, last_error, created_at";
+
+const ITEM_FIELDS: &str = "item_id, repo_id, number, is_pull_request, task_node_id, body_node_id, \
+     synced_title, github_state, body_hash, github_updated_at";
+
+pub async fn insert_github_repo(
+    pool: &DbPool,
+    owner: &str,
+    name: &str,
+    topic_id: i64,
+    token_encrypted: Option<&[u8]>,
+    poll_interval_minutes: i64,
+) -> Result<i64, sqlx::Error> {
+    let result = sqlx::query(
+        "INSERT INTO github_repos (owner, name, topic_id, token_encrypted, poll_interval_minutes) \
+         VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(owner)
+    .bind(name)
+    .bind(topic_id)
+    .bind(token_encrypted)
+    .bind(poll_interval_minutes)
+    .execute(pool)
+    .await?;
+    Ok(result.last_insert_rowid())
+}
+
+pub async fn get_github_repo(
+    pool: &DbPool,
+    repo_id: i64,
+) -> Result<Option<GithubRepoRecord>, sqlx::Error> {
+    let sql = format!("SELECT {REPO_FIELDS} FROM github_repos WHERE repo_id = ?");
+    sqlx::query_as::<_, GithubRepoRecord>(&sql)
+        .bind(repo_id)
+        .fetch_optional(pool)
+        .await
+}
+
+/// 加密后的访问令牌；未配置时返回 None
+pub async fn get_github_repo_token(
+    pool: &DbPool,
+    repo_id: i64,
+) -> Result<Option<Vec<u8>>, sqlx::Error> {
+    let token: Option<Option<Vec<u8>>> =
+        sqlx::query_scalar("SELECT token_encrypted FROM github_repos WHERE repo_id = ?")
+            .bind(repo_id)
+            .fetch_optional(pool)
+            .await?;
+    Ok(token.flatten())
+}
+
+pub async fn list_github_repos(pool: &DbPool) -> Result<Vec<GithubRepoRecord>, sqlx::Error> {
+    let sql = format!("SELECT {REPO_FIELDS} FROM github_repos ORDER BY owner, name");
+    sqlx::query_as::<_, GithubRepoRecord>(&sql)
+        .fetch_all(pool)
+        .await
+}
+
+/// 已启用且距上次同步超过轮询间隔的仓库
+pub async fn list_due_github_repos(pool: &DbPool) -> Result<Vec<GithubRepoRecord>, sqlx::Error> {
+    let sql = format!(
+        "SELECT {REPO_FIELDS} FROM github_repos \
+         WHERE is_enabled = 1 AND (last_synced_at IS NULL \
+             OR datetime(last_synced_at, '+' || poll_interval_minutes || ' minutes') <= CURRENT_TIMESTAMP)"
+    );
+    sqlx::query_as::<_, GithubRepoRecord>(&sql)
+        .fetch_all(pool)
+        .await
+}
+
+/// 删除仓库及其映射；已同步的任务与正文保留
+pub async fn delete_github_repo(pool: &DbPool, repo_id: i64) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM github_repos WHERE repo_id = ?")
+        .bind(repo_id)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// 记录一次同步结果；cursor 为 None 时保留原游标，error 为 None 表示成功
+pub async fn record_github_sync(
+    pool: &DbPool,
+    repo_id: i64,
+    cursor: Option<&str>,
+    error: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE github_repos SET since_cursor = COALESCE(?, since_cursor), \
+             last_synced_at = CURRENT_TIMESTAMP, last_error = ? \
+         WHERE repo_id = ?",
+    )
+    .bind(cursor)
+    .bind(error)
+    .bind(repo_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn get_github_item(
+    pool: &DbPool,
+    repo_id: i64,
+    number: i64,
+) -> Result<Option<GithubItemRecord>, sqlx::Error> {
+    let sql = format!("SELECT {ITEM_FIELDS} FROM github_items WHERE repo_id = ? AND number = ?");
+    sqlx::query_as::<_, GithubItemRecord>(&sql)
+        .bind(repo_id)
+        .bind(number)
+        .fetch_optional(pool)
+        .await
+}
+
+/// 按 (repo_id, number) 写入映射
+pub async fn upsert_github_item(pool: &DbPool, item: NewGithubItem<'_>) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO github_items \
+             (repo_id, number, is_pull_request, task_node_id, body_node_id, synced_title, \
+              github_state, body_hash, github_updated_at) \
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?) \
+         ON CONFLICT(repo_id, number) DO UPDATE SET \
+             is_pull_request = excluded.is_pull_request, \
+             task_node_id = excluded.task_node_id, \
+             body_node_id = excluded.body_node_id, \
+             synced_title = excluded.synced_title, \
+             github_state = excluded.github_state, \
+             body_hash = excluded.body_hash, \
+             github_updated_at = excluded.github_updated_at",
+    )
+    .bind(item.repo_id)
+    .bind(item.number)
+    .bind(item.is_pull_request)
+    .bind(item.task_node_id)
+    .bind(item.body_node_id)
+    .bind(item.synced_title)
+    .bind(item.github_state)
+    .bind(item.body_hash)
+    .bind(item.github_updated_at)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use super::{DbPool, GoalReviewRecord, ReviewTaskRow, TopicActivityRow};
+
+/// 统计周期内各主题的活动（start_date / end_date 为本地日期 YYYY-MM-DD，闭区间）
+pub async fn list_topic_activity(
+    pool: &DbPool,
+    start_date: &str,
+    end_date: &str,
+) -> Result<Vec<TopicActivityRow>, sqlx::Error> {
+    sqlx::query_as::<_, TopicActivityRow>(
+        "SELECT t.node_id AS topic_id, t.title AS title, t.summary AS summary, \
+                COUNT(DISTINCT CASE WHEN c.node_type = 'resource' \
+                    AND date(c.created_at, 'localtime') BETWEEN date(?1) AND date(?2) \
+                    THEN c.node_id END) AS resources_added, \
+                COUNT(DISTINCT CASE WHEN c.node_type = 'task' AND c.task_status = 'done' \
+                    AND date(c.done_date, 'localtime') BETWEEN date(?1) AND date(?2) \
+                    THEN c.node_id END) AS tasks_completed, \
+                COUNT(DISTINCT CASE WHEN c.node_type = 'task' AND c.task_status = 'todo' \
+                    THEN c.node_id END) AS tasks_open, \
+                COUNT(DISTINCT CASE WHEN c.node_type = 'task' AND c.task_status = 'todo' \
+                    AND date(c.updated_at, 'localtime') < date(?1) \
+                    THEN c.node_id END) AS tasks_stalled \
+         FROM nodes t \
+         INNER JOIN edges e ON e.source_node_id = t.node_id \
+             AND e.relation_type = 'contains' AND e.is_deleted = 0 \
+         INNER JOIN nodes c ON c.node_id = e.target_node_id AND c.is_deleted = 0 \
+         WHERE t.node_type = 'topic' AND t.is_deleted = 0 \
+         GROUP BY t.node_id \
+         HAVING resources_added > 0 OR tasks_completed > 0 OR tasks_open > 0 \
+         ORDER BY resources_added + tasks_completed DESC, t.title ASC",
+    )
+    .bind(start_date)
+    .bind(end_date)
+    .fetch_all(pool)
+    .await
+}
+
+/// 周期内完成的任务，以及周期内无更新的未完成任务
+pub async fn list_review_tasks(
+    pool: &DbPool,
+    start_date: &str,
+    end_date: &str,
+) -> Result<Vec<ReviewTaskRow>, sqlx::Error> {
+    sqlx::query_as::<_, ReviewTaskRow>(
+        "SELECT e.source_node_id AS topic_id, c.title AS title, c.task_status AS task_status \
+         FROM edges e \
+         INNER JOIN nodes t ON t.node_id = e.source_node_id \
+             AND t.node_type = 'topic' AND t.is_deleted = 0 \
+         INNER JOIN nodes c ON c.node_id = e.target_node_id \
+             AND c.node_type = 'task' AND c.is_deleted = 0 \
+         WHERE e.relation_type = 'contains' AND e.is_deleted = 0 \
+           AND ( \
+               (c.task_status = 'done' AND date(c.done_date, 'localtime') BETWEEN date(?1) AND date(?2)) \
+               OR (c.task_status = 'todo' AND date(c.updated_at, 'localtime') < date(?1)) \
+           ) \
+         ORDER BY e.source_node_id, c.task_status, c.title",
+    )
+    .bind(start_date)
+    .bind(end_date)
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn get_goal_review_by_period(
+    pool: &DbPool,
+    period: &str,
+) -> Result<Option<GoalReviewRecord>, sqlx::Error> {
+    sqlx::query_as::<_, GoalReviewRecord>(
+        "SELECT review_id, period, node_id, created_at FROM goal_reviews WHERE period = ?",
+    )
+    .bind(period)
+    .fetch_optional(pool)
+    .await
+}
+
+/// 写入周期回顾；同一周期重新生成时覆盖指向的资源
+pub async fn upsert_goal_review(
+    pool: &DbPool,
+    period: &str,
+    node_id: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO goal_reviews (period, node_id) VALUES (?, ?) \
+         ON CONFLICT(period) DO UPDATE SET node_id = excluded.node_id, created_at = CURRENT_TIMESTAMP",
+    )
+    .bind(period)
+    .bind(node_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn list_goal_reviews(pool: &DbPool) -> Result<Vec<GoalReviewRecord>, sqlx::Error> {
+    sqlx::query_as::<_, GoalReviewRecord>(
+        "SELECT review_id, period, node_id, created_at FROM goal_reviews ORDER BY period DESC",
+    )
+    .fetch_all(pool)
+    .await
+}
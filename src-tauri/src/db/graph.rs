@@ -0,0 +1,162 @@
+//! 知识图谱视图：节点范围、分页与度数
+//!
+//! 关系取自 `graph_edges` CTE：edges 表中未删除的边，加上 node_links 中的正文引用
+//! （关系类型为 references，没有 edge_id）。
+//!
+//! 所有查询共用一个 `scope` CTE，按固定顺序给范围内的节点编号（rank）：
+//! - 全图：给定类型的未删除节点，按 node_id 排序
+//! - 邻域：从根节点出发沿关系（双向）走 depth 步，按距离、node_id 排序
+//!
+//! 分页取 rank 区间；边只在其两端中 rank 较大的一端所在的页返回，
+//! 前端逐页累加即可得到完整子图，不会遗漏跨页的边。
+
+use super::{DbPool, GraphEdgeRecord, GraphNodeRecord, GraphRelationType, NodeType};
+
+/// 未删除的边与正文引用
+const GRAPH_EDGES_CTE: &str = "graph_edges AS ( \
+        SELECT edge_id, source_node_id, target_node_id, relation_type, confidence_score, \
+               is_manual, created_at \
+        FROM edges WHERE is_deleted = 0 \
+        UNION ALL \
+        SELECT NULL, source_node_id, target_node_id, 'references', NULL, 0, created_at \
+        FROM node_links \
+     )";
+
+/// 节点概要与度数（度数统计所有关系，不受关系类型筛选影响）
+const GRAPH_NODE_FIELDS: &str =
+    "n.node_id, n.node_type, n.title, n.resource_subtype, n.task_status, \
+    (SELECT COUNT(*) FROM graph_edges e JOIN nodes o ON o.node_id = e.target_node_id \
+        WHERE e.source_node_id = n.node_id AND o.is_deleted = 0) + \
+    (SELECT COUNT(*) FROM graph_edges e JOIN nodes o ON o.node_id = e.source_node_id \
+        WHERE e.target_node_id = n.node_id AND o.is_deleted = 0) AS degree";
+
+/// 图谱范围
+pub struct GraphScope<'a> {
+    /// 邻域模式的根节点与步数；None 为全图
+    pub root: Option<(i64, i64)>,
+    pub node_types: &'a [NodeType],
+    pub relation_types: &'a [GraphRelationType],
+}
+
+impl GraphScope<'_> {
+    fn cte(&self) -> String {
+        let node_types = vec!["?"; self.node_types.len()].join(", ");
+        if self.root.is_none() {
+            return format!(
+                "WITH {GRAPH_EDGES_CTE}, \
+                 scope AS ( \
+                    SELECT n.node_id, NULL AS depth, ROW_NUMBER() OVER (ORDER BY n.node_id) AS rank \
+                    FROM nodes n WHERE n.is_deleted = 0 AND n.node_type IN ({node_types}) \
+                 )"
+            );
+        }
+
+        let relation_types = vec!["?"; self.relation_types.len()].join(", ");
+        let other = "CASE WHEN e.source_node_id = w.node_id THEN e.target_node_id ELSE e.source_node_id END";
+        format!(
+            "WITH RECURSIVE {GRAPH_EDGES_CTE}, \
+             walk(node_id, depth) AS ( \
+                SELECT ?, 0 \
+                UNION \
+                SELECT {other}, w.depth + 1 FROM walk w \
+                JOIN graph_edges e ON e.source_node_id = w.node_id OR e.target_node_id = w.node_id \
+                JOIN nodes o ON o.node_id = {other} \
+                WHERE w.depth < ? AND e.relation_type IN ({relation_types}) \
+                  AND o.is_deleted = 0 AND o.node_type IN ({node_types}) \
+             ), \
+             scope AS ( \
+                SELECT w.node_id, MIN(w.depth) AS depth, \
+                       ROW_NUMBER() OVER (ORDER BY MIN(w.depth), w.node_id) AS rank \
+                FROM walk w JOIN nodes n ON n.node_id = w.node_id \
+                WHERE n.is_deleted = 0 \
+                GROUP BY w.node_id \
+             )"
+        )
+    }
+}
+
+/// 按 `GraphScope::cte` 中占位符的顺序绑定参数
+macro_rules! bind_scope {
+    ($query:expr, $scope:expr) => {{
+        let mut query = $query;
+        if let Some((root, depth)) = $scope.root {
+            query = query.bind(root).bind(depth);
+            for relation_type in $scope.relation_types {
+                query = query.bind(*relation_type);
+            }
+        }
+        for node_type in $scope.node_types {
+            query = query.bind(*node_type);
+        }
+        query
+    }};
+}
+
+/// 范围内的节点总数
+pub async fn count_graph_nodes(pool: &DbPool, scope: &GraphScope<'_>) -> Result<i64, sqlx::Error> {
+    if scope.node_types.is_empty() {
+        return Ok(0);
+    }
+    let sql = format!("{} SELECT COUNT(*) FROM scope", scope.cte());
+    bind_scope!(sqlx::query_scalar::<_, i64>(&sql), scope)
+        .fetch_one(pool)
+        .await
+}
+
+/// 范围内 rank 在 (offset, offset + limit] 的节点
+pub async fn list_graph_nodes(
+    pool: &DbPool,
+    scope: &GraphScope<'_>,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<GraphNodeRecord>, sqlx::Error> {
+    if scope.node_types.is_empty() {
+        return Ok(Vec::new());
+    }
+    let sql = format!(
+        "{} SELECT {GRAPH_NODE_FIELDS}, s.depth AS depth \
+         FROM scope s JOIN nodes n ON n.node_id = s.node_id \
+         WHERE s.rank > ? AND s.rank <= ? \
+         ORDER BY s.rank",
+        scope.cte()
+    );
+    bind_scope!(sqlx::query_as::<_, GraphNodeRecord>(&sql), scope)
+        .bind(offset)
+        .bind(offset + limit)
+        .fetch_all(pool)
+        .await
+}
+
+/// 两端都在前 offset + limit 个节点内、且至少一端在本页的边
+pub async fn list_graph_edges(
+    pool: &DbPool,
+    scope: &GraphScope<'_>,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<GraphEdgeRecord>, sqlx::Error> {
+    if scope.node_types.is_empty() || scope.relation_types.is_empty() {
+        return Ok(Vec::new());
+    }
+    let relation_types = vec!["?"; scope.relation_types.len()].join(", ");
+    let sql = format!(
+        "{} SELECT e.edge_id, e.source_node_id, e.target_node_id, e.relation_type, \
+            e.confidence_score, e.is_manual, e.created_at \
+         FROM graph_edges e \
+         JOIN scope a ON a.node_id = e.source_node_id \
+         JOIN scope b ON b.node_id = e.target_node_id \
+         WHERE e.relation_type IN ({relation_types}) \
+           AND a.rank <= ? AND b.rank <= ? AND MAX(a.rank, b.rank) > ? \
+         ORDER BY e.edge_id IS NULL, e.edge_id, e.source_node_id, e.target_node_id",
+        scope.cte()
+    );
+    let mut query = bind_scope!(sqlx::query_as::<_, GraphEdgeRecord>(&sql), scope);
+    for relation_type in scope.relation_types {
+        query = query.bind(*relation_type);
+    }
+    query
+        .bind(offset + limit)
+        .bind(offset + limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await
+}
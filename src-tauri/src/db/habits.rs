@@ -0,0 +1,91 @@
+use super::{DbPool, HabitFrequency, HabitRecord};
+
+const HABIT_FIELDS: &str =
+    "n.node_id, n.title, n.summary, h.frequency, h.target_count, n.created_at";
+
+pub async fn insert_habit_schedule(
+    pool: &DbPool,
+    node_id: i64,
+    frequency: HabitFrequency,
+    target_count: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("INSERT INTO habits (node_id, frequency, target_count) VALUES (?, ?, ?)")
+        .bind(node_id)
+        .bind(frequency)
+        .bind(target_count)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+pub async fn update_habit_schedule(
+    pool: &DbPool,
+    node_id: i64,
+    frequency: HabitFrequency,
+    target_count: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE habits SET frequency = ?, target_count = ?, updated_at = CURRENT_TIMESTAMP \
+         WHERE node_id = ?",
+    )
+    .bind(frequency)
+    .bind(target_count)
+    .bind(node_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn get_habit_by_id(pool: &DbPool, node_id: i64) -> Result<HabitRecord, sqlx::Error> {
+    let sql = format!(
+        "SELECT {HABIT_FIELDS} FROM habits h INNER JOIN nodes n ON n.node_id = h.node_id \
+         WHERE h.node_id = ? AND n.is_deleted = 0"
+    );
+    sqlx::query_as::<_, HabitRecord>(&sql)
+        .bind(node_id)
+        .fetch_one(pool)
+        .await
+}
+
+pub async fn list_habits(pool: &DbPool) -> Result<Vec<HabitRecord>, sqlx::Error> {
+    let sql = format!(
+        "SELECT {HABIT_FIELDS} FROM habits h INNER JOIN nodes n ON n.node_id = h.node_id \
+         WHERE n.node_type = 'habit' AND n.is_deleted = 0 ORDER BY n.created_at ASC"
+    );
+    sqlx::query_as::<_, HabitRecord>(&sql).fetch_all(pool).await
+}
+
+/// 打卡（同一天重复打卡忽略）
+pub async fn insert_habit_log(
+    pool: &DbPool,
+    node_id: i64,
+    log_date: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("INSERT OR IGNORE INTO habit_logs (node_id, log_date) VALUES (?, date(?))")
+        .bind(node_id)
+        .bind(log_date)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+pub async fn delete_habit_log(
+    pool: &DbPool,
+    node_id: i64,
+    log_date: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM habit_logs WHERE node_id = ? AND log_date = date(?)")
+        .bind(node_id)
+        .bind(log_date)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// 列出打卡日期（YYYY-MM-DD，升序）
+pub async fn list_habit_log_dates(pool: &DbPool, node_id: i64) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query_scalar("SELECT log_date FROM habit_logs WHERE node_id = ? ORDER BY log_date ASC")
+        .bind(node_id)
+        .fetch_all(pool)
+        .await
+}
@@ -0,0 +1,139 @@
+use super::{
+    AssetFileRow, DbPool, IntegrityCheckCounts, IntegrityCheckRecord, IntegrityCheckTrigger,
+    IntegrityIssueRecord, NewIntegrityIssue,
+};
+
+const CHECK_FIELDS: &str = "check_id, trigger, started_at, finished_at, checked_count, \
+     missing_count, mismatched_count, skipped_count, reparsed_count, error";
+
+/// 所有保存在资源文件目录中的文件资源（未删除；外部引用由引用服务单独检查）
+pub async fn list_asset_files(pool: &DbPool) -> Result<Vec<AssetFileRow>, sqlx::Error> {
+    sqlx::query_as::<_, AssetFileRow>(
+        "SELECT node_id, file_path, file_hash, resource_subtype FROM nodes \
+         WHERE node_type = 'resource' AND is_deleted = 0 AND file_path IS NOT NULL \
+             AND node_id NOT IN (SELECT node_id FROM file_references) \
+         ORDER BY node_id",
+    )
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn create_integrity_check(
+    pool: &DbPool,
+    trigger: IntegrityCheckTrigger,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar("INSERT INTO integrity_checks (trigger) VALUES (?) RETURNING check_id")
+        .bind(trigger)
+        .fetch_one(pool)
+        .await
+}
+
+pub async fn insert_integrity_issue(
+    pool: &DbPool,
+    issue: NewIntegrityIssue<'_>,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar(
+        "INSERT INTO integrity_issues \
+             (check_id, node_id, kind, file_path, expected_hash, actual_hash, reparsed) \
+         VALUES (?, ?, ?, ?, ?, ?, ?) RETURNING issue_id",
+    )
+    .bind(issue.check_id)
+    .bind(issue.node_id)
+    .bind(issue.kind)
+    .bind(issue.file_path)
+    .bind(issue.expected_hash)
+    .bind(issue.actual_hash)
+    .bind(issue.reparsed)
+    .fetch_one(pool)
+    .await
+}
+
+/// 写入统计并标记结束；error 非空表示校验中途失败
+pub async fn finish_integrity_check(
+    pool: &DbPool,
+    check_id: i64,
+    counts: IntegrityCheckCounts,
+    error: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE integrity_checks SET finished_at = CURRENT_TIMESTAMP, checked_count = ?, \
+             missing_count = ?, mismatched_count = ?, skipped_count = ?, reparsed_count = ?, \
+             error = ? \
+         WHERE check_id = ?",
+    )
+    .bind(counts.checked)
+    .bind(counts.missing)
+    .bind(counts.mismatched)
+    .bind(counts.skipped)
+    .bind(counts.reparsed)
+    .bind(error)
+    .bind(check_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn get_integrity_check(
+    pool: &DbPool,
+    check_id: i64,
+) -> Result<Option<IntegrityCheckRecord>, sqlx::Error> {
+    let sql = format!("SELECT {CHECK_FIELDS} FROM integrity_checks WHERE check_id = ?");
+    sqlx::query_as::<_, IntegrityCheckRecord>(&sql)
+        .bind(check_id)
+        .fetch_optional(pool)
+        .await
+}
+
+/// 最近一次已结束的校验
+pub async fn get_latest_integrity_check(
+    pool: &DbPool,
+) -> Result<Option<IntegrityCheckRecord>, sqlx::Error> {
+    let sql = format!(
+        "SELECT {CHECK_FIELDS} FROM integrity_checks WHERE finished_at IS NOT NULL \
+         ORDER BY check_id DESC LIMIT 1"
+    );
+    sqlx::query_as::<_, IntegrityCheckRecord>(&sql)
+        .fetch_optional(pool)
+        .await
+}
+
+pub async fn list_integrity_issues(
+    pool: &DbPool,
+    check_id: i64,
+) -> Result<Vec<IntegrityIssueRecord>, sqlx::Error> {
+    sqlx::query_as::<_, IntegrityIssueRecord>(
+        "SELECT i.issue_id, i.check_id, i.node_id, n.title, i.kind, i.file_path, \
+             i.expected_hash, i.actual_hash, i.reparsed \
+         FROM integrity_issues i JOIN nodes n ON n.node_id = i.node_id \
+         WHERE i.check_id = ? ORDER BY i.issue_id",
+    )
+    .bind(check_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// 只保留最近 keep 次校验
+pub async fn prune_integrity_checks(pool: &DbPool, keep: i64) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "DELETE FROM integrity_checks WHERE check_id NOT IN \
+         (SELECT check_id FROM integrity_checks ORDER BY check_id DESC LIMIT ?)",
+    )
+    .bind(keep)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 文件被替换后以新文件为准
+pub async fn update_node_file_hash(
+    pool: &DbPool,
+    node_id: i64,
+    file_hash: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE nodes SET file_hash = ? WHERE node_id = ?")
+        .bind(file_hash)
+        .bind(node_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
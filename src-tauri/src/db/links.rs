@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use sqlx::SqliteConnection;
+
+use super::{BacklinkRecord, DbPool};
+use crate::utils::extract_node_links;
+
+/// 按正文重建该节点的引用；正文为空时清空。uuid 与标题只匹配未删除的其他节点
+pub async fn refresh_node_links(
+    conn: &mut SqliteConnection,
+    node_id: i64,
+    content: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    refresh_node_links_with(conn, node_id, content, &HashMap::new()).await
+}
+
+/// 同 [`refresh_node_links`]，但 `resolved` 中的 `[[链接]]`（键为链接原文）直接指向给定节点，
+/// 其余仍按标题匹配。用于导入时按文件名解析 Obsidian 风格的链接
+pub async fn refresh_node_links_with(
+    conn: &mut SqliteConnection,
+    node_id: i64,
+    content: Option<&str>,
+    resolved: &HashMap<String, i64>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM node_links WHERE source_node_id = ?")
+        .bind(node_id)
+        .execute(&mut *conn)
+        .await?;
+
+    let mut refs = content.map(extract_node_links).unwrap_or_default();
+    if refs.is_empty() {
+        return Ok(());
+    }
+    let target_ids: Vec<i64> = refs
+        .titles
+        .iter()
+        .filter_map(|title| resolved.get(title).copied())
+        .collect();
+    refs.titles.retain(|title| !resolved.contains_key(title));
+    let uuids = serde_json::to_string(&refs.uuids).unwrap_or_default();
+    let titles = serde_json::to_string(&refs.titles).unwrap_or_default();
+    let target_ids = serde_json::to_string(&target_ids).unwrap_or_default();
+    sqlx::query(
+        "INSERT OR IGNORE INTO node_links (source_node_id, target_node_id, link_kind) \
+         SELECT ?, node_id, 'uuid' FROM nodes \
+         WHERE uuid IN (SELECT value FROM json_each(?)) AND node_id != ? AND is_deleted = 0 \
+         UNION ALL \
+         SELECT ?, node_id, 'title' FROM nodes \
+         WHERE (title IN (SELECT value FROM json_each(?)) \
+                OR node_id IN (SELECT value FROM json_each(?))) \
+           AND node_id != ? AND is_deleted = 0",
+    )
+    .bind(node_id)
+    .bind(&uuids)
+    .bind(node_id)
+    .bind(node_id)
+    .bind(&titles)
+    .bind(&target_ids)
+    .bind(node_id)
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+/// 引用了该节点的未删除节点，最近建立引用的在前
+pub async fn list_backlinks(
+    pool: &DbPool,
+    node_id: i64,
+) -> Result<Vec<BacklinkRecord>, sqlx::Error> {
+    sqlx::query_as::<_, BacklinkRecord>(
+        "SELECT n.node_id, n.uuid, n.title, n.node_type, n.resource_subtype, l.link_kind, \
+             l.created_at AS linked_at \
+         FROM node_links l JOIN nodes n ON n.node_id = l.source_node_id \
+         WHERE l.target_node_id = ? AND n.is_deleted = 0 \
+         ORDER BY l.created_at DESC, n.node_id DESC",
+    )
+    .bind(node_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// 有正文的节点：node_id > cursor 的前 limit 条（回填引用索引用）
+pub async fn list_nodes_with_content(
+    pool: &DbPool,
+    cursor: i64,
+    limit: i64,
+) -> Result<Vec<(i64, String)>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT node_id, file_content FROM nodes \
+         WHERE file_content IS NOT NULL AND node_id > ? \
+         ORDER BY node_id LIMIT ?",
+    )
+    .bind(cursor)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn count_nodes_with_content(pool: &DbPool, cursor: i64) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar("SELECT COUNT(*) FROM nodes WHERE file_content IS NOT NULL AND node_id > ?")
+        .bind(cursor)
+        .fetch_one(pool)
+        .await
+}
+
+/// 批量重建引用（同一事务）
+pub async fn backfill_node_links(pool: &DbPool, rows: &[(i64, String)]) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    for (node_id, content) in rows {
+        refresh_node_links(&mut tx, *node_id, Some(content)).await?;
+    }
+    tx.commit().await
+}
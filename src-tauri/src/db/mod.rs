@@ -1,15 +1,73 @@
 mod builders;
+mod calendar;
+mod captured_messages;
 mod chat;
+mod citations;
+mod content_revisions;
+mod data_migrations;
 mod edges;
+mod export_runs;
+mod file_references;
+mod focus;
+mod github;
+mod goal_reviews;
+mod graph;
+mod habits;
+mod links;
+mod integrity;
 mod nodes;
+mod notifications;
+mod pending_changes;
+mod pipeline_jobs;
 mod pool;
+mod reading_state;
+mod recurrence;
+mod retention;
 mod revisions;
+mod sources;
+mod subtasks;
+mod tags;
+mod task_board;
+mod task_reminders;
+mod time_entries;
 mod types;
+mod usage_log;
+mod watched_folders;
+mod workspaces;
 
 pub use builders::*;
+pub use calendar::*;
+pub use captured_messages::*;
 pub use chat::*;
+pub use citations::*;
+pub use content_revisions::*;
+pub use data_migrations::*;
 pub use edges::*;
+pub use export_runs::*;
+pub use file_references::*;
+pub use focus::*;
+pub use github::*;
+pub use goal_reviews::*;
+pub use graph::*;
+pub use habits::*;
+pub use links::*;
+pub use integrity::*;
 pub use nodes::*;
+pub use notifications::*;
+pub use pending_changes::*;
+pub use pipeline_jobs::*;
 pub use pool::*;
+pub use reading_state::*;
+pub use recurrence::*;
+pub use retention::*;
 pub use revisions::*;
+pub use sources::*;
+pub use subtasks::*;
+pub use tags::*;
+pub use task_board::*;
+pub use task_reminders::*;
+pub use time_entries::*;
 pub use types::*;
+pub use usage_log::*;
+pub use watched_folders::*;
+pub use workspaces::*;
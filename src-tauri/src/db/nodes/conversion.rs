@@ -8,6 +8,7 @@ use crate::db::{
     ResourceEmbeddingStatus, ResourceProcessingStage, ReviewStatus, TaskPriority, TaskStatus,
 };
 use crate::error::{AppError, AppResult};
+use crate::i18n::MessageCode;
 
 /// 边迁移行（内部使用）
 #[derive(Debug, FromRow)]
@@ -62,12 +63,12 @@ pub async fn convert_resource_to_container(
     target_type: NodeType,
 ) -> AppResult<NodeRecord> {
     if !matches!(target_type, NodeType::Topic | NodeType::Task) {
-        return Err(AppError::Validation("目标类型必须是 Topic 或 Task".to_string()));
+        return Err(AppError::coded(MessageCode::InvalidConversionTarget));
     }
 
     let resource = get_node_by_id(pool, node_id).await?;
     if resource.node_type != NodeType::Resource || resource.is_deleted {
-        return Err(AppError::Business("节点不是有效的资源".to_string()));
+        return Err(AppError::coded(MessageCode::NotAResource));
     }
 
     let mut tx = pool.begin().await?;
@@ -117,7 +118,7 @@ pub async fn convert_resource_to_container(
 
     // 创建新容器到原资源的 contains 边
     if contains_creates_cycle(tx.as_mut(), new_node_id, resource.node_id).await? {
-        return Err(AppError::Business("创建 contains 边会形成环".to_string()));
+        return Err(AppError::coded(MessageCode::ContainsCreateCycle));
     }
     sqlx::query!(
         "INSERT OR IGNORE INTO edges (source_node_id, target_node_id, relation_type, confidence_score, is_manual) \
@@ -143,7 +144,7 @@ pub async fn convert_resource_to_container(
 
     for edge in contains_edges {
         if contains_creates_cycle(tx.as_mut(), edge.source_node_id, new_node_id).await? {
-            return Err(AppError::Business("迁移 contains 边会形成环".to_string()));
+            return Err(AppError::coded(MessageCode::ContainsMigrateCycle));
         }
 
         sqlx::query!(
@@ -1,9 +1,18 @@
 //! Basic CRUD operations for nodes
 
+use sqlx::types::Json;
+use sqlx::{Executor, Sqlite};
+
 use super::NODE_FIELDS;
-use crate::db::{DbPool, NewNode, NodeRecord, NodeType};
+use crate::db::{refresh_node_links, DbPool, NewNode, NodeRecord, NodeType, SourceMeta};
+use crate::error::{AppError, AppResult};
+use crate::i18n::MessageCode;
+use crate::utils::compute_content_stats;
 
-pub async fn insert_node(pool: &DbPool, params: NewNode<'_>) -> Result<i64, sqlx::Error> {
+pub async fn insert_node<'a, E>(executor: E, params: NewNode<'_>) -> Result<i64, sqlx::Error>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
     let result = sqlx::query!(
         "INSERT INTO nodes (\
             uuid, user_id, title, summary, node_type, task_status, priority, due_date, done_date, \
@@ -33,7 +42,7 @@ pub async fn insert_node(pool: &DbPool, params: NewNode<'_>) -> Result<i64, sqlx
         params.processing_stage,
         params.review_status,
     )
-    .execute(pool)
+    .execute(executor)
     .await?;
 
     let node_id = result.last_insert_rowid();
@@ -109,22 +118,100 @@ pub async fn update_node_title(pool: &DbPool, node_id: i64, title: &str) -> Resu
     Ok(())
 }
 
-pub async fn update_node_summary(
+pub async fn update_node_source_meta(
     pool: &DbPool,
     node_id: i64,
-    summary: Option<&str>,
+    source_meta: &SourceMeta,
 ) -> Result<(), sqlx::Error> {
-    sqlx::query!(
-        "UPDATE nodes SET summary = ?, updated_at = CURRENT_TIMESTAMP WHERE node_id = ?",
-        summary,
-        node_id,
+    sqlx::query(
+        "UPDATE nodes SET source_meta = ?, updated_at = CURRENT_TIMESTAMP WHERE node_id = ?",
     )
+    .bind(Json(source_meta))
+    .bind(node_id)
     .execute(pool)
     .await?;
-    tracing::debug!(node_id, summary = ?summary, "Node summary updated");
     Ok(())
 }
 
+/// 可按版本号检查后更新的文本字段
+#[derive(Debug, Clone, Copy)]
+pub enum NodeTextField {
+    Title,
+    Summary,
+    UserNote,
+}
+
+/// 更新文本字段并把版本号加一；给出 expected_version 时只在版本一致时写入
+///
+/// 返回新的版本号；版本不一致时返回 [`MessageCode::NodeEditConflict`]
+pub async fn update_node_text_checked(
+    pool: &DbPool,
+    node_id: i64,
+    field: NodeTextField,
+    value: Option<&str>,
+    expected_version: Option<i64>,
+) -> AppResult<i64> {
+    // 每个字段一条 query!，保证 SQL 在编译期校验
+    let version = match field {
+        NodeTextField::Title => {
+            sqlx::query_scalar!(
+                "UPDATE nodes SET title = ?, version = version + 1, updated_at = CURRENT_TIMESTAMP WHERE node_id = ? AND (? IS NULL OR version = ?) RETURNING version",
+                value,
+                node_id,
+                expected_version,
+                expected_version,
+            )
+            .fetch_optional(pool)
+            .await?
+        }
+        NodeTextField::Summary => {
+            sqlx::query_scalar!(
+                "UPDATE nodes SET summary = ?, version = version + 1, updated_at = CURRENT_TIMESTAMP WHERE node_id = ? AND (? IS NULL OR version = ?) RETURNING version",
+                value,
+                node_id,
+                expected_version,
+                expected_version,
+            )
+            .fetch_optional(pool)
+            .await?
+        }
+        NodeTextField::UserNote => {
+            sqlx::query_scalar!(
+                "UPDATE nodes SET user_note = ?, version = version + 1, updated_at = CURRENT_TIMESTAMP WHERE node_id = ? AND (? IS NULL OR version = ?) RETURNING version",
+                value,
+                node_id,
+                expected_version,
+                expected_version,
+            )
+            .fetch_optional(pool)
+            .await?
+        }
+    };
+    match version {
+        Some(version) => {
+            tracing::debug!(node_id, ?field, version, "Node text updated");
+            Ok(version)
+        }
+        None => Err(missing_or_conflict(pool, node_id).await),
+    }
+}
+
+/// 条件更新没有命中时区分节点不存在与版本冲突
+async fn missing_or_conflict(pool: &DbPool, node_id: i64) -> AppError {
+    let exists = sqlx::query_scalar::<_, i64>("SELECT 1 FROM nodes WHERE node_id = ?")
+        .bind(node_id)
+        .fetch_optional(pool)
+        .await;
+    match exists {
+        Ok(Some(_)) => AppError::coded(MessageCode::NodeEditConflict),
+        Ok(None) => AppError::NotFound {
+            entity: "node",
+            id: node_id,
+        },
+        Err(err) => err.into(),
+    }
+}
+
 pub async fn update_node_pinned(
     pool: &DbPool,
     node_id: i64,
@@ -143,6 +230,40 @@ pub async fn update_node_pinned(
     Ok(())
 }
 
+pub async fn update_node_exclude_from_search(
+    pool: &DbPool,
+    node_id: i64,
+    excluded: bool,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE nodes SET exclude_from_search = ?, updated_at = CURRENT_TIMESTAMP WHERE node_id = ?",
+    )
+    .bind(excluded)
+    .bind(node_id)
+    .execute(pool)
+    .await?;
+    tracing::debug!(node_id, excluded, "Node search exclusion updated");
+    Ok(())
+}
+
+/// 只更新主题；返回是否有行被更新
+pub async fn update_topic_auto_classify_allowed(
+    pool: &DbPool,
+    topic_id: i64,
+    allowed: bool,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "UPDATE nodes SET auto_classify_allowed = ?, updated_at = CURRENT_TIMESTAMP \
+         WHERE node_id = ? AND node_type = 'topic' AND is_deleted = 0",
+    )
+    .bind(allowed)
+    .bind(topic_id)
+    .execute(pool)
+    .await?;
+    tracing::debug!(topic_id, allowed, "Topic auto classify flag updated");
+    Ok(result.rows_affected() > 0)
+}
+
 pub async fn update_node_content(
     pool: &DbPool,
     node_id: i64,
@@ -157,6 +278,9 @@ pub async fn update_node_content(
     )
     .execute(pool)
     .await?;
+    update_node_content_stats(pool, node_id, content).await?;
+    let mut conn = pool.acquire().await?;
+    refresh_node_links(&mut conn, node_id, content).await?;
     tracing::debug!(
         node_id,
         content = ?content,
@@ -166,6 +290,32 @@ pub async fn update_node_content(
     Ok(())
 }
 
+/// 按正文重新计算统计（字数、token 数、语言、阅读时长），正文为空时清空
+pub async fn update_node_content_stats<'a, E>(
+    executor: E,
+    node_id: i64,
+    content: Option<&str>,
+) -> Result<(), sqlx::Error>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    let stats = content.map(compute_content_stats);
+    sqlx::query(
+        "UPDATE nodes SET read_time_minutes = ?, word_count = ?, char_count = ?, \
+             token_count = ?, content_language = ? \
+         WHERE node_id = ?",
+    )
+    .bind(stats.and_then(|s| s.read_time_minutes))
+    .bind(stats.map(|s| s.word_count))
+    .bind(stats.map(|s| s.char_count))
+    .bind(stats.map(|s| s.token_count))
+    .bind(stats.and_then(|s| s.language))
+    .bind(node_id)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
 pub async fn update_node_user_note(
     pool: &DbPool,
     node_id: i64,
@@ -181,3 +331,22 @@ pub async fn update_node_user_note(
     tracing::debug!(node_id, note = ?note, "Node user note updated");
     Ok(())
 }
+
+/// 覆盖创建 / 更新时间（导入外部笔记时保留原始时间），None 表示保持不变
+pub async fn set_node_timestamps(
+    pool: &DbPool,
+    node_id: i64,
+    created_at: Option<&str>,
+    updated_at: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE nodes SET created_at = COALESCE(?, created_at), \
+         updated_at = COALESCE(?, updated_at) WHERE node_id = ?",
+    )
+    .bind(created_at)
+    .bind(updated_at)
+    .bind(node_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
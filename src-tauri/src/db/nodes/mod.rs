@@ -5,18 +5,23 @@
 //! - `status`: Status update operations (task status, processing stage, sync status)
 //! - `query`: Query operations (list, search)
 //! - `conversion`: Node type conversion operations
+//! - `restructure`: Topic hierarchy restructuring (rename, move, merge)
 
 mod conversion;
 mod crud;
 mod query;
+mod restructure;
 mod status;
 
 pub use conversion::*;
 pub use crud::*;
 pub use query::*;
+pub use restructure::*;
 pub use status::*;
 
 /// Common fields for SELECT queries
 pub(crate) const NODE_FIELDS: &str = "node_id, uuid, user_id, title, summary, node_type, task_status, priority, due_date, done_date, \
     file_hash, file_path, file_content, user_note, resource_subtype, source_meta, embedded_hash, processing_hash, embedding_status, \
-    last_embedding_at, last_embedding_error, processing_stage, review_status, is_pinned, pinned_at, created_at, updated_at, is_deleted, deleted_at";
+    last_embedding_at, last_embedding_error, processing_stage, review_status, is_pinned, pinned_at, created_at, updated_at, is_deleted, deleted_at, recurrence_rule, \
+    exclude_from_search, read_time_minutes, word_count, char_count, token_count, content_language, processing_attempts, auto_classify_allowed, version, archived_at, \
+    (SELECT progress FROM node_reading_state WHERE node_reading_state.node_id = nodes.node_id) AS reading_progress";
@@ -1,7 +1,14 @@
 //! Query operations for nodes
 
+use sqlx::{Executor, Sqlite};
+
 use super::NODE_FIELDS;
-use crate::db::{DbPool, NodeRecord, NodeType};
+use crate::db::{
+    DbPool, EmbeddingErrorCount, EmbeddingStateCount, NodeFullTextRow, NodeRecord, NodeRef,
+    NodeTitleRow, NodeType, UnreviewedClassificationRecord,
+};
+use crate::error::{AppError, AppResult};
+use crate::i18n::MessageCode;
 
 pub async fn list_nodes_by_type(
     pool: &DbPool,
@@ -53,6 +60,15 @@ pub async fn list_tasks_by_date(pool: &DbPool, date: &str) -> Result<Vec<NodeRec
         .await
 }
 
+/// 有截止日期的未删除任务（含已完成与已取消），按截止日期排序
+pub async fn list_tasks_with_due_date(pool: &DbPool) -> Result<Vec<NodeRecord>, sqlx::Error> {
+    let sql = format!(
+        "SELECT {} FROM nodes WHERE node_type = 'task' AND due_date IS NOT NULL AND is_deleted = 0 ORDER BY due_date ASC, node_id ASC",
+        NODE_FIELDS
+    );
+    sqlx::query_as::<_, NodeRecord>(&sql).fetch_all(pool).await
+}
+
 pub async fn list_all_resources(pool: &DbPool) -> Result<Vec<NodeRecord>, sqlx::Error> {
     let sql = format!(
         "SELECT {} FROM nodes WHERE node_type = 'resource' AND is_deleted = 0 ORDER BY updated_at DESC",
@@ -61,6 +77,49 @@ pub async fn list_all_resources(pool: &DbPool) -> Result<Vec<NodeRecord>, sqlx::
     sqlx::query_as::<_, NodeRecord>(&sql).fetch_all(pool).await
 }
 
+/// 导出用：未删除的主题、资源与任务；给出 since 时只取之后有更新的节点
+pub async fn list_nodes_for_export(
+    pool: &DbPool,
+    since: Option<&str>,
+) -> Result<Vec<NodeRecord>, sqlx::Error> {
+    let sql = format!(
+        "SELECT {} FROM nodes \
+         WHERE node_type IN ('topic', 'resource', 'task') AND is_deleted = 0 \
+         AND (? IS NULL OR updated_at >= ?) \
+         ORDER BY node_id",
+        NODE_FIELDS
+    );
+    sqlx::query_as::<_, NodeRecord>(&sql)
+        .bind(since)
+        .bind(since)
+        .fetch_all(pool)
+        .await
+}
+
+/// 导出用：全部未删除的主题、资源、任务的 (node_id, uuid, title)，用于生成 Markdown 链接
+pub async fn list_export_node_names(
+    pool: &DbPool,
+) -> Result<Vec<(i64, String, String)>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT node_id, uuid, title FROM nodes \
+         WHERE node_type IN ('topic', 'resource', 'task') AND is_deleted = 0",
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// 查找替换用：未删除且有正文的文本笔记
+pub async fn list_text_resources(pool: &DbPool) -> Result<Vec<NodeRecord>, sqlx::Error> {
+    let sql = format!(
+        "SELECT {} FROM nodes \
+         WHERE node_type = 'resource' AND resource_subtype = 'text' AND is_deleted = 0 \
+         AND file_content IS NOT NULL \
+         ORDER BY updated_at DESC",
+        NODE_FIELDS
+    );
+    sqlx::query_as::<_, NodeRecord>(&sql).fetch_all(pool).await
+}
+
 pub async fn list_resources_for_requeue(pool: &DbPool) -> Result<Vec<i64>, sqlx::Error> {
     sqlx::query_scalar(
         "SELECT node_id FROM nodes \
@@ -73,6 +132,144 @@ pub async fn list_resources_for_requeue(pool: &DbPool) -> Result<Vec<i64>, sqlx:
     .await
 }
 
+/// 未删除资源按向量化状态与处理阶段分组计数
+pub async fn count_resources_by_embedding_state(
+    pool: &DbPool,
+) -> Result<Vec<EmbeddingStateCount>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT embedding_status, processing_stage, COUNT(*) AS count FROM nodes \
+         WHERE node_type = 'resource' AND is_deleted = 0 \
+         GROUP BY embedding_status, processing_stage \
+         ORDER BY count DESC",
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// 等待最久的待处理资源：最后更新时间及距今秒数（不含失败的资源）
+pub async fn oldest_pending_resource(
+    pool: &DbPool,
+) -> Result<(Option<String>, Option<i64>), sqlx::Error> {
+    sqlx::query_as(
+        "SELECT MIN(updated_at), \
+             CAST((julianday('now') - julianday(MIN(updated_at))) * 86400 AS INTEGER) \
+         FROM nodes \
+         WHERE node_type = 'resource' AND is_deleted = 0 \
+         AND file_content IS NOT NULL AND length(trim(file_content)) > 0 \
+         AND embedding_status != 'error' \
+         AND (embedding_status IN ('pending', 'dirty') OR processing_stage != 'done')",
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// 向量化失败的资源按错误信息分组，数量多的在前
+pub async fn count_embedding_errors(
+    pool: &DbPool,
+    limit: i64,
+) -> Result<Vec<EmbeddingErrorCount>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT COALESCE(last_embedding_error, '') AS error, COUNT(*) AS count FROM nodes \
+         WHERE node_type = 'resource' AND is_deleted = 0 AND embedding_status = 'error' \
+         GROUP BY error ORDER BY count DESC LIMIT ?",
+    )
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
+
+/// 需要重新入队的失败资源
+///
+/// error_contains 按错误信息筛选（忽略大小写），updated_since 只取该时间之后失败的资源；
+/// include_pending 为 true 时同时包含未处理完的资源
+pub async fn list_failed_embedding_resources(
+    pool: &DbPool,
+    error_contains: Option<&str>,
+    updated_since: Option<&str>,
+    include_pending: bool,
+) -> Result<Vec<i64>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT node_id FROM nodes \
+         WHERE node_type = 'resource' AND is_deleted = 0 \
+         AND file_content IS NOT NULL AND length(trim(file_content)) > 0 \
+         AND (embedding_status = 'error' OR (? AND \
+             (embedding_status IN ('pending', 'dirty') OR processing_stage != 'done'))) \
+         AND (? IS NULL OR instr(lower(last_embedding_error), lower(?)) > 0) \
+         AND (? IS NULL OR updated_at >= datetime(?)) \
+         ORDER BY updated_at DESC",
+    )
+    .bind(include_pending)
+    .bind(error_contains)
+    .bind(error_contains)
+    .bind(updated_since)
+    .bind(updated_since)
+    .fetch_all(pool)
+    .await
+}
+
+/// 按内容哈希查找未删除的资源（导入去重用）
+pub async fn find_resource_by_hash(
+    pool: &DbPool,
+    file_hash: &str,
+) -> Result<Option<i64>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT node_id FROM nodes \
+         WHERE node_type = 'resource' AND is_deleted = 0 AND file_hash = ? LIMIT 1",
+    )
+    .bind(file_hash)
+    .fetch_optional(pool)
+    .await
+}
+
+/// 所有节点 id（含软删除，可恢复的节点仍保留向量）
+pub async fn list_all_node_ids(pool: &DbPool) -> Result<Vec<i64>, sqlx::Error> {
+    sqlx::query_scalar("SELECT node_id FROM nodes")
+        .fetch_all(pool)
+        .await
+}
+
+/// Get node by uuid
+pub async fn get_node_by_uuid(
+    pool: &DbPool,
+    uuid: &str,
+) -> Result<Option<NodeRecord>, sqlx::Error> {
+    let sql = format!("SELECT {} FROM nodes WHERE uuid = ?", NODE_FIELDS);
+    sqlx::query_as::<_, NodeRecord>(&sql)
+        .bind(uuid)
+        .fetch_optional(pool)
+        .await
+}
+
+/// Get node_id by uuid
+pub async fn get_node_id_by_uuid<'a, E>(executor: E, uuid: &str) -> Result<Option<i64>, sqlx::Error>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query_scalar("SELECT node_id FROM nodes WHERE uuid = ?")
+        .bind(uuid.trim())
+        .fetch_optional(executor)
+        .await
+}
+
+/// Resolve a node reference to its node_id; a bare node_id is returned unchanged
+pub async fn resolve_node_ref(pool: &DbPool, node: &NodeRef) -> AppResult<i64> {
+    match node {
+        NodeRef::Id(node_id) => Ok(*node_id),
+        NodeRef::Uuid(uuid) => get_node_id_by_uuid(pool, uuid)
+            .await?
+            .ok_or_else(|| AppError::coded_with(MessageCode::UnknownNodeUuid, "value", uuid)),
+    }
+}
+
+/// Resolve node references in order
+pub async fn resolve_node_refs(pool: &DbPool, nodes: &[NodeRef]) -> AppResult<Vec<i64>> {
+    let mut node_ids = Vec::with_capacity(nodes.len());
+    for node in nodes {
+        node_ids.push(resolve_node_ref(pool, node).await?);
+    }
+    Ok(node_ids)
+}
+
 /// Get all pinned nodes
 pub async fn list_pinned_nodes(pool: &DbPool) -> Result<Vec<NodeRecord>, sqlx::Error> {
     let sql = format!(
@@ -91,6 +288,62 @@ pub async fn list_unreviewed_nodes(pool: &DbPool) -> Result<Vec<NodeRecord>, sql
     sqlx::query_as::<_, NodeRecord>(&sql).fetch_all(pool).await
 }
 
+/// Get unreviewed resources with their proposed topic (highest-confidence auto contains edge)
+pub async fn list_unreviewed_classifications(
+    pool: &DbPool,
+) -> Result<Vec<UnreviewedClassificationRecord>, sqlx::Error> {
+    sqlx::query_as::<_, UnreviewedClassificationRecord>(
+        "SELECT n.node_id, n.title, n.summary, n.resource_subtype, n.created_at, \
+            t.node_id AS topic_id, t.title AS topic_title, e.confidence_score \
+         FROM nodes n \
+         LEFT JOIN edges e ON e.edge_id = ( \
+            SELECT pe.edge_id FROM edges pe \
+            JOIN nodes pt ON pt.node_id = pe.source_node_id \
+            WHERE pe.target_node_id = n.node_id AND pe.relation_type = 'contains' \
+              AND pe.is_manual = 0 AND pe.is_deleted = 0 \
+              AND pt.node_type = 'topic' AND pt.is_deleted = 0 \
+            ORDER BY pe.confidence_score DESC, pe.edge_id DESC LIMIT 1 \
+         ) \
+         LEFT JOIN nodes t ON t.node_id = e.source_node_id \
+         WHERE n.node_type = 'resource' AND n.review_status = 'unreviewed' AND n.is_deleted = 0 \
+         ORDER BY n.created_at DESC",
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// 收件箱中的资源：待审核或不属于任何主题，且未归档；附 AI 建议的主题，新的在前
+pub async fn list_inbox_resources(
+    pool: &DbPool,
+    limit: i64,
+) -> Result<Vec<UnreviewedClassificationRecord>, sqlx::Error> {
+    sqlx::query_as::<_, UnreviewedClassificationRecord>(
+        "SELECT n.node_id, n.title, n.summary, n.resource_subtype, n.created_at, \
+            t.node_id AS topic_id, t.title AS topic_title, e.confidence_score \
+         FROM nodes n \
+         LEFT JOIN edges e ON e.edge_id = ( \
+            SELECT pe.edge_id FROM edges pe \
+            JOIN nodes pt ON pt.node_id = pe.source_node_id \
+            WHERE pe.target_node_id = n.node_id AND pe.relation_type = 'contains' \
+              AND pe.is_manual = 0 AND pe.is_deleted = 0 \
+              AND pt.node_type = 'topic' AND pt.is_deleted = 0 \
+            ORDER BY pe.confidence_score DESC, pe.edge_id DESC LIMIT 1 \
+         ) \
+         LEFT JOIN nodes t ON t.node_id = e.source_node_id \
+         WHERE n.node_type = 'resource' AND n.is_deleted = 0 AND n.archived_at IS NULL \
+         AND (n.review_status = 'unreviewed' OR NOT EXISTS ( \
+            SELECT 1 FROM edges pe \
+            JOIN nodes pt ON pt.node_id = pe.source_node_id \
+            WHERE pe.target_node_id = n.node_id AND pe.relation_type = 'contains' \
+              AND pe.is_deleted = 0 AND pt.node_type IN ('topic', 'task') AND pt.is_deleted = 0 \
+         )) \
+         ORDER BY n.created_at DESC, n.node_id DESC LIMIT ?",
+    )
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
+
 /// SQL LIKE search (title + file_content + user_note)
 pub async fn search_nodes_by_keyword(
     pool: &DbPool,
@@ -104,11 +357,12 @@ pub async fn search_nodes_by_keyword(
         Some(nt) => {
             let sql = format!(
                 "SELECT {} FROM nodes \
-                 WHERE node_type = ? AND is_deleted = 0 \
+                 WHERE node_type = ? AND is_deleted = 0 AND {} \
                  AND (title LIKE ? OR file_content LIKE ? OR user_note LIKE ?) \
                  ORDER BY updated_at DESC \
                  LIMIT ?",
-                NODE_FIELDS
+                NODE_FIELDS,
+                not_search_excluded("node_id")
             );
             sqlx::query_as::<_, NodeRecord>(&sql)
                 .bind(nt)
@@ -122,11 +376,12 @@ pub async fn search_nodes_by_keyword(
         None => {
             let sql = format!(
                 "SELECT {} FROM nodes \
-                 WHERE is_deleted = 0 \
+                 WHERE is_deleted = 0 AND {} \
                  AND (title LIKE ? OR file_content LIKE ? OR user_note LIKE ?) \
                  ORDER BY updated_at DESC \
                  LIMIT ?",
-                NODE_FIELDS
+                NODE_FIELDS,
+                not_search_excluded("node_id")
             );
             sqlx::query_as::<_, NodeRecord>(&sql)
                 .bind(&pattern)
@@ -138,3 +393,289 @@ pub async fn search_nodes_by_keyword(
         }
     }
 }
+
+/// Title-only search for search-as-you-type
+///
+/// Matches the title column of the trigram `nodes_fts` index; prefixes shorter
+/// than a trigram fall back to `LIKE`. Titles starting with `prefix` rank before
+/// titles merely containing it; ties go to the most recently updated node.
+pub async fn search_node_titles(
+    pool: &DbPool,
+    prefix: &str,
+    limit: i32,
+) -> Result<Vec<NodeTitleRow>, sqlx::Error> {
+    let escaped = escape_like(prefix);
+    let (source, filter, needle) = if prefix.chars().count() >= FTS_MIN_TERM_CHARS {
+        (
+            "nodes_fts JOIN nodes n ON n.node_id = nodes_fts.rowid",
+            "nodes_fts MATCH ?1",
+            format!("title : \"{}\"", prefix.replace('"', "\"\"")),
+        )
+    } else {
+        (
+            "nodes n",
+            "n.title LIKE ?1 ESCAPE '\\'",
+            format!("%{escaped}%"),
+        )
+    };
+    let sql = format!(
+        "SELECT n.node_id, n.node_type, n.title, n.summary FROM {source} \
+         WHERE {filter} AND n.is_deleted = 0 AND {} \
+         ORDER BY CASE WHEN n.title LIKE ?2 ESCAPE '\\' THEN 0 ELSE 1 END, n.updated_at DESC \
+         LIMIT ?3",
+        not_search_excluded("n.node_id")
+    );
+    sqlx::query_as(&sql)
+        .bind(needle)
+        .bind(format!("{escaped}%"))
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+}
+
+/// Most recently opened or updated nodes (titles only)
+pub async fn list_recent_node_titles(
+    pool: &DbPool,
+    limit: i32,
+) -> Result<Vec<NodeTitleRow>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT n.node_id, n.node_type, n.title, n.summary FROM nodes n \
+         LEFT JOIN node_reading_state r ON r.node_id = n.node_id AND r.open_count > 0 \
+         WHERE n.is_deleted = 0 \
+         ORDER BY MAX(COALESCE(r.last_opened_at, n.updated_at), n.updated_at) DESC LIMIT ?",
+    )
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
+
+/// Titles for the given node ids (deleted and search-excluded nodes are skipped,
+/// order is not preserved)
+pub async fn list_node_titles_by_ids(
+    pool: &DbPool,
+    node_ids: &[i64],
+) -> Result<Vec<NodeTitleRow>, sqlx::Error> {
+    if node_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let placeholders = vec!["?"; node_ids.len()].join(", ");
+    let sql = format!(
+        "SELECT node_id, node_type, title, summary FROM nodes \
+         WHERE is_deleted = 0 AND node_id IN ({placeholders}) AND {}",
+        not_search_excluded("node_id")
+    );
+    let mut query = sqlx::query_as::<_, NodeTitleRow>(&sql);
+    for node_id in node_ids {
+        query = query.bind(node_id);
+    }
+    query.fetch_all(pool).await
+}
+
+/// 检索时要排除的节点 ID
+///
+/// 包括标记了 exclude_from_search 或已归档的节点与 topic_ids，以及它们通过 contains 边（递归）包含的节点；
+/// node_ids 只排除自身。返回升序去重的 ID
+pub async fn list_search_excluded_node_ids(
+    pool: &DbPool,
+    node_ids: &[i64],
+    topic_ids: &[i64],
+) -> Result<Vec<i64>, sqlx::Error> {
+    let topic_seed = if topic_ids.is_empty() {
+        String::new()
+    } else {
+        let placeholders = vec!["?"; topic_ids.len()].join(", ");
+        format!(" UNION SELECT node_id FROM nodes WHERE node_id IN ({placeholders})")
+    };
+    let sql = search_excluded_sql(&topic_seed);
+    let mut query = sqlx::query_scalar::<_, i64>(&sql);
+    for topic_id in topic_ids {
+        query = query.bind(topic_id);
+    }
+    let mut excluded = query.fetch_all(pool).await?;
+    excluded.extend_from_slice(node_ids);
+    excluded.sort_unstable();
+    excluded.dedup();
+    Ok(excluded)
+}
+
+/// 列出被排除节点 ID 的 SQL；`extra_seed` 追加到递归起点（以 " UNION SELECT ..." 开头）
+fn search_excluded_sql(extra_seed: &str) -> String {
+    format!(
+        "WITH RECURSIVE excluded(node_id) AS ( \
+            SELECT node_id FROM nodes \
+            WHERE (exclude_from_search = 1 OR archived_at IS NOT NULL) AND is_deleted = 0{extra_seed} \
+            UNION SELECT e.target_node_id FROM edges e \
+            JOIN excluded x ON e.source_node_id = x.node_id \
+            WHERE e.relation_type = 'contains' AND e.is_deleted = 0 \
+         ) \
+         SELECT node_id FROM excluded"
+    )
+}
+
+/// 过滤掉检索时要排除的节点的 WHERE 条件（同 [`list_search_excluded_node_ids`]），
+/// `column` 为节点 ID 列
+fn not_search_excluded(column: &str) -> String {
+    format!("{column} NOT IN ({})", search_excluded_sql(""))
+}
+
+/// 按 ID 批量获取未删除的节点（顺序不保证）
+pub async fn list_nodes_by_ids(
+    pool: &DbPool,
+    node_ids: &[i64],
+) -> Result<Vec<NodeRecord>, sqlx::Error> {
+    if node_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let placeholders = vec!["?"; node_ids.len()].join(", ");
+    let sql = format!(
+        "SELECT {NODE_FIELDS} FROM nodes WHERE is_deleted = 0 AND node_id IN ({placeholders})"
+    );
+    let mut query = sqlx::query_as::<_, NodeRecord>(&sql);
+    for node_id in node_ids {
+        query = query.bind(node_id);
+    }
+    query.fetch_all(pool).await
+}
+
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+/// 按捕获来源（前台应用 / 窗口标题）筛选资源，大小写不敏感的包含匹配
+pub async fn list_resources_by_source(
+    pool: &DbPool,
+    process_name: Option<&str>,
+    window_title: Option<&str>,
+) -> Result<Vec<NodeRecord>, sqlx::Error> {
+    let process_pattern = process_name.map(|value| format!("%{}%", value.trim()));
+    let title_pattern = window_title.map(|value| format!("%{}%", value.trim()));
+    let sql = format!(
+        "SELECT {} FROM nodes \
+         WHERE node_type = 'resource' AND is_deleted = 0 AND source_meta IS NOT NULL \
+         AND (? IS NULL OR json_extract(source_meta, '$.process_name') LIKE ?) \
+         AND (? IS NULL OR json_extract(source_meta, '$.window_title') LIKE ?) \
+         ORDER BY created_at DESC",
+        NODE_FIELDS
+    );
+    sqlx::query_as::<_, NodeRecord>(&sql)
+        .bind(&process_pattern)
+        .bind(&process_pattern)
+        .bind(&title_pattern)
+        .bind(&title_pattern)
+        .fetch_all(pool)
+        .await
+}
+
+/// trigram 分词器可索引的最短查询词（字符数）
+const FTS_MIN_TERM_CHARS: usize = 3;
+
+/// 全文搜索（FTS5，title / summary / file_content / user_note）
+///
+/// 空白分隔多个词（AND），双引号包裹的内容作为整体短语。
+/// 不足 3 个字符的词无法走 trigram 索引，改用 LIKE 过滤；
+/// 全部是短词时按更新时间排序，没有 snippet。
+pub async fn search_nodes_fulltext(
+    pool: &DbPool,
+    query: &str,
+    node_type: Option<NodeType>,
+    limit: i32,
+) -> Result<Vec<NodeFullTextRow>, sqlx::Error> {
+    let terms = parse_fulltext_terms(query);
+    if terms.is_empty() {
+        return Ok(Vec::new());
+    }
+    let (long_terms, short_terms): (Vec<&String>, Vec<&String>) = terms
+        .iter()
+        .partition(|term| term.chars().count() >= FTS_MIN_TERM_CHARS);
+    let match_query = (!long_terms.is_empty()).then(|| {
+        long_terms
+            .iter()
+            .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+            .collect::<Vec<_>>()
+            .join(" ")
+    });
+
+    let mut sql = if match_query.is_some() {
+        String::from(
+            "SELECT n.node_id, n.node_type, n.title, n.summary, \
+             snippet(nodes_fts, -1, '[', ']', '…', 16) AS snippet, \
+             bm25(nodes_fts, 10.0, 5.0, 1.0, 2.0) AS rank \
+             FROM nodes_fts JOIN nodes n ON n.node_id = nodes_fts.rowid \
+             WHERE nodes_fts MATCH ? AND n.is_deleted = 0",
+        )
+    } else {
+        String::from(
+            "SELECT n.node_id, n.node_type, n.title, n.summary, \
+             NULL AS snippet, 0.0 AS rank \
+             FROM nodes n WHERE n.is_deleted = 0",
+        )
+    };
+    sql.push_str(" AND ");
+    sql.push_str(&not_search_excluded("n.node_id"));
+    if node_type.is_some() {
+        sql.push_str(" AND n.node_type = ?");
+    }
+    for _ in &short_terms {
+        sql.push_str(
+            " AND (n.title LIKE ? ESCAPE '\\' OR n.summary LIKE ? ESCAPE '\\' \
+             OR n.file_content LIKE ? ESCAPE '\\' OR n.user_note LIKE ? ESCAPE '\\')",
+        );
+    }
+    if match_query.is_some() {
+        sql.push_str(" ORDER BY rank LIMIT ?");
+    } else {
+        sql.push_str(" ORDER BY n.updated_at DESC LIMIT ?");
+    }
+
+    let mut query = sqlx::query_as::<_, NodeFullTextRow>(&sql);
+    if let Some(match_query) = match_query {
+        query = query.bind(match_query);
+    }
+    if let Some(node_type) = node_type {
+        query = query.bind(node_type);
+    }
+    for term in short_terms {
+        let pattern = format!("%{}%", escape_like(term));
+        for _ in 0..4 {
+            query = query.bind(pattern.clone());
+        }
+    }
+    query.bind(limit).fetch_all(pool).await
+}
+
+/// 拆分全文搜索输入：双引号内为一个短语，其余按空白分隔
+pub fn parse_fulltext_terms(input: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    for (index, part) in input.split('"').enumerate() {
+        if index % 2 == 1 {
+            let phrase = part.split_whitespace().collect::<Vec<_>>().join(" ");
+            if !phrase.is_empty() {
+                terms.push(phrase);
+            }
+        } else {
+            terms.extend(part.split_whitespace().map(str::to_string));
+        }
+    }
+    terms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fulltext_terms() {
+        assert_eq!(
+            parse_fulltext_terms(r#"rust  "async  trait" 向量"#),
+            vec!["rust", "async trait", "向量"]
+        );
+        assert_eq!(
+            parse_fulltext_terms(r#"  "unclosed phrase"#),
+            vec!["unclosed phrase"]
+        );
+        assert!(parse_fulltext_terms(r#" "" "#).is_empty());
+    }
+}
@@ -0,0 +1,387 @@
+//! 主题层级调整（重命名 / 移动 / 合并）
+
+use sqlx::{Sqlite, Transaction};
+
+use crate::db::{contains_creates_cycle, DbPool, TopicLinkRow, TopicOperation, TopicTreeRow};
+use crate::error::{AppError, AppResult};
+use crate::i18n::MessageCode;
+
+/// 修订日志中标记层级调整的 reason
+const RESTRUCTURE_REASON: &str = "topic_restructure";
+
+/// 列出全部主题及其直接包含的非主题节点数
+pub async fn list_topic_tree(pool: &DbPool) -> Result<Vec<TopicTreeRow>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT t.node_id, t.title, t.summary, \
+         (SELECT COUNT(*) FROM edges e \
+          JOIN nodes c ON c.node_id = e.target_node_id \
+          WHERE e.source_node_id = t.node_id AND e.relation_type = 'contains' AND e.is_deleted = 0 \
+          AND c.is_deleted = 0 AND c.node_type != 'topic') AS item_count \
+         FROM nodes t WHERE t.node_type = 'topic' AND t.is_deleted = 0 \
+         ORDER BY t.title",
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// 列出主题之间的 contains 边
+pub async fn list_topic_links(pool: &DbPool) -> Result<Vec<TopicLinkRow>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT e.source_node_id AS parent_id, e.target_node_id AS child_id FROM edges e \
+         JOIN nodes p ON p.node_id = e.source_node_id \
+         JOIN nodes c ON c.node_id = e.target_node_id \
+         WHERE e.relation_type = 'contains' AND e.is_deleted = 0 \
+         AND p.node_type = 'topic' AND p.is_deleted = 0 \
+         AND c.node_type = 'topic' AND c.is_deleted = 0",
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// 在同一事务中依次应用层级调整操作，任一操作失败则全部回滚
+///
+/// 每个操作都会写入修订日志（reason = `topic_restructure`）：
+/// - 重命名记录 `title`
+/// - 移动记录 `parent`（旧/新父主题 id，根层级为空）
+/// - 合并在目标主题上记录 `merge`，在被合并主题上记录 `is_deleted`；
+///   被合并主题的子节点与 related_to 关联改挂到目标主题
+pub async fn apply_topic_operations(pool: &DbPool, operations: &[TopicOperation]) -> AppResult<()> {
+    let mut tx = pool.begin().await?;
+
+    for operation in operations {
+        match operation {
+            TopicOperation::Rename {
+                topic_id,
+                new_title,
+            } => {
+                rename_topic(&mut tx, *topic_id, new_title).await?;
+            }
+            TopicOperation::Move {
+                topic_id,
+                new_parent_id,
+            } => {
+                move_topic(&mut tx, *topic_id, *new_parent_id).await?;
+            }
+            TopicOperation::Merge {
+                topic_id,
+                target_topic_id,
+            } => {
+                merge_topic(&mut tx, *topic_id, *target_topic_id).await?;
+            }
+        }
+    }
+
+    tx.commit().await?;
+    tracing::debug!(count = operations.len(), "Topic restructure applied");
+    Ok(())
+}
+
+/// 把资源从原来的主题移到 topic_id 下（删除原有的主题 contains 边），记录修订日志 `parent`
+pub async fn move_resource_to_topic(
+    pool: &DbPool,
+    resource_id: i64,
+    topic_id: i64,
+) -> AppResult<()> {
+    let mut tx = pool.begin().await?;
+    fetch_topic_title(&mut tx, topic_id).await?;
+
+    let old_parents: Vec<i64> = sqlx::query_scalar(
+        "SELECT e.source_node_id FROM edges e \
+         JOIN nodes p ON p.node_id = e.source_node_id AND p.node_type = 'topic' \
+         WHERE e.target_node_id = ? AND e.relation_type = 'contains' AND e.is_deleted = 0",
+    )
+    .bind(resource_id)
+    .fetch_all(tx.as_mut())
+    .await?;
+    if old_parents == [topic_id] {
+        return Ok(());
+    }
+
+    sqlx::query(
+        "DELETE FROM edges WHERE target_node_id = ? AND relation_type = 'contains' \
+         AND source_node_id IN (SELECT node_id FROM nodes WHERE node_type = 'topic')",
+    )
+    .bind(resource_id)
+    .execute(tx.as_mut())
+    .await?;
+    insert_manual_contains(&mut tx, topic_id, resource_id).await?;
+
+    let old_value = old_parents
+        .iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+    let new_value = topic_id.to_string();
+    log_revision(
+        &mut tx,
+        resource_id,
+        "parent",
+        Some(old_value.as_str()).filter(|value| !value.is_empty()),
+        Some(&new_value),
+    )
+    .await?;
+
+    tx.commit().await?;
+    tracing::debug!(resource_id, topic_id, "Resource moved to topic");
+    Ok(())
+}
+
+async fn rename_topic(
+    tx: &mut Transaction<'_, Sqlite>,
+    topic_id: i64,
+    new_title: &str,
+) -> AppResult<()> {
+    let new_title = new_title.trim();
+    if new_title.is_empty() {
+        return Err(AppError::coded(MessageCode::TopicTitleEmpty));
+    }
+
+    let old_title = fetch_topic_title(tx, topic_id).await?;
+    if old_title == new_title {
+        return Ok(());
+    }
+
+    // 提出时已检查过重名，执行前主题可能已变化，这里再检查一次
+    let taken: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM nodes \
+         WHERE node_type = 'topic' AND is_deleted = 0 AND title = ? AND node_id != ?)",
+    )
+    .bind(new_title)
+    .bind(topic_id)
+    .fetch_one(tx.as_mut())
+    .await?;
+    if taken {
+        return Err(AppError::coded_with(
+            MessageCode::TopicTitleTaken,
+            "title",
+            new_title,
+        ));
+    }
+
+    sqlx::query("UPDATE nodes SET title = ?, updated_at = CURRENT_TIMESTAMP WHERE node_id = ?")
+        .bind(new_title)
+        .bind(topic_id)
+        .execute(tx.as_mut())
+        .await?;
+
+    log_revision(tx, topic_id, "title", Some(&old_title), Some(new_title)).await
+}
+
+async fn move_topic(
+    tx: &mut Transaction<'_, Sqlite>,
+    topic_id: i64,
+    new_parent_id: Option<i64>,
+) -> AppResult<()> {
+    fetch_topic_title(tx, topic_id).await?;
+
+    let old_parents: Vec<i64> = sqlx::query_scalar(
+        "SELECT e.source_node_id FROM edges e \
+         JOIN nodes p ON p.node_id = e.source_node_id AND p.node_type = 'topic' \
+         WHERE e.target_node_id = ? AND e.relation_type = 'contains' AND e.is_deleted = 0",
+    )
+    .bind(topic_id)
+    .fetch_all(tx.as_mut())
+    .await?;
+
+    sqlx::query(
+        "DELETE FROM edges WHERE target_node_id = ? AND relation_type = 'contains' \
+         AND source_node_id IN (SELECT node_id FROM nodes WHERE node_type = 'topic')",
+    )
+    .bind(topic_id)
+    .execute(tx.as_mut())
+    .await?;
+
+    if let Some(parent_id) = new_parent_id {
+        fetch_topic_title(tx, parent_id).await?;
+        if contains_creates_cycle(tx.as_mut(), parent_id, topic_id).await? {
+            return Err(AppError::coded(MessageCode::TopicMoveCycle));
+        }
+        insert_manual_contains(tx, parent_id, topic_id).await?;
+    }
+
+    let old_value = old_parents
+        .iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+    let new_value = new_parent_id.map(|id| id.to_string());
+    log_revision(
+        tx,
+        topic_id,
+        "parent",
+        Some(old_value.as_str()).filter(|value| !value.is_empty()),
+        new_value.as_deref(),
+    )
+    .await
+}
+
+async fn merge_topic(
+    tx: &mut Transaction<'_, Sqlite>,
+    topic_id: i64,
+    target_topic_id: i64,
+) -> AppResult<()> {
+    if topic_id == target_topic_id {
+        return Err(AppError::coded(MessageCode::TopicMergeIntoSelf));
+    }
+    let source_title = fetch_topic_title(tx, topic_id).await?;
+    let target_title = fetch_topic_title(tx, target_topic_id).await?;
+
+    // 被合并主题的子节点（contains）与关联（related_to）改挂到目标主题上
+    let edges: Vec<(i64, i64, i64, String)> = sqlx::query_as(
+        "SELECT edge_id, source_node_id, target_node_id, relation_type FROM edges \
+         WHERE is_deleted = 0 AND ((relation_type = 'contains' AND source_node_id = ?) \
+         OR (relation_type = 'related_to' AND (source_node_id = ? OR target_node_id = ?)))",
+    )
+    .bind(topic_id)
+    .bind(topic_id)
+    .bind(topic_id)
+    .fetch_all(tx.as_mut())
+    .await?;
+
+    for (edge_id, source_id, target_id, relation_type) in edges {
+        let (new_source, new_target) = if relation_type == "contains" {
+            (target_topic_id, target_id)
+        } else {
+            // related_to 约束 source < target
+            let other = if source_id == topic_id {
+                target_id
+            } else {
+                source_id
+            };
+            (other.min(target_topic_id), other.max(target_topic_id))
+        };
+        if new_source == new_target {
+            soft_delete_edge(tx, edge_id).await?;
+            continue;
+        }
+        if relation_type == "contains"
+            && contains_creates_cycle(tx.as_mut(), target_topic_id, new_target).await?
+        {
+            return Err(AppError::coded(MessageCode::TopicMergeCycle));
+        }
+        repoint_edge(tx, edge_id, new_source, new_target, &relation_type).await?;
+    }
+
+    sqlx::query(
+        "UPDATE nodes SET is_deleted = 1, deleted_at = CURRENT_TIMESTAMP WHERE node_id = ?",
+    )
+    .bind(topic_id)
+    .execute(tx.as_mut())
+    .await?;
+
+    log_revision(
+        tx,
+        target_topic_id,
+        "merge",
+        Some(&source_title),
+        Some(&target_title),
+    )
+    .await?;
+    log_revision(tx, topic_id, "is_deleted", Some("0"), Some("1")).await
+}
+
+async fn fetch_topic_title(tx: &mut Transaction<'_, Sqlite>, topic_id: i64) -> AppResult<String> {
+    let title: Option<String> = sqlx::query_scalar(
+        "SELECT title FROM nodes WHERE node_id = ? AND node_type = 'topic' AND is_deleted = 0",
+    )
+    .bind(topic_id)
+    .fetch_optional(tx.as_mut())
+    .await?;
+
+    title.ok_or(AppError::NotFound {
+        entity: "topic",
+        id: topic_id,
+    })
+}
+
+/// 把边改到新的端点上；新端点间已有同类型的边（含已软删除的）时恢复那条边并软删除原边
+async fn repoint_edge(
+    tx: &mut Transaction<'_, Sqlite>,
+    edge_id: i64,
+    source_node_id: i64,
+    target_node_id: i64,
+    relation_type: &str,
+) -> AppResult<()> {
+    let existing: Option<i64> = sqlx::query_scalar(
+        "SELECT edge_id FROM edges \
+         WHERE source_node_id = ? AND target_node_id = ? AND relation_type = ?",
+    )
+    .bind(source_node_id)
+    .bind(target_node_id)
+    .bind(relation_type)
+    .fetch_optional(tx.as_mut())
+    .await?;
+
+    match existing {
+        Some(existing_id) => {
+            sqlx::query(
+                "UPDATE edges SET is_deleted = 0, deleted_at = NULL, updated_at = CURRENT_TIMESTAMP \
+                 WHERE edge_id = ? AND is_deleted = 1",
+            )
+            .bind(existing_id)
+            .execute(tx.as_mut())
+            .await?;
+            soft_delete_edge(tx, edge_id).await
+        }
+        None => {
+            sqlx::query(
+                "UPDATE edges SET source_node_id = ?, target_node_id = ?, \
+                 updated_at = CURRENT_TIMESTAMP WHERE edge_id = ?",
+            )
+            .bind(source_node_id)
+            .bind(target_node_id)
+            .bind(edge_id)
+            .execute(tx.as_mut())
+            .await?;
+            Ok(())
+        }
+    }
+}
+
+async fn soft_delete_edge(tx: &mut Transaction<'_, Sqlite>, edge_id: i64) -> AppResult<()> {
+    sqlx::query(
+        "UPDATE edges SET is_deleted = 1, deleted_at = CURRENT_TIMESTAMP, \
+         updated_at = CURRENT_TIMESTAMP WHERE edge_id = ?",
+    )
+    .bind(edge_id)
+    .execute(tx.as_mut())
+    .await?;
+    Ok(())
+}
+
+async fn insert_manual_contains(
+    tx: &mut Transaction<'_, Sqlite>,
+    parent_id: i64,
+    child_id: i64,
+) -> AppResult<()> {
+    sqlx::query(
+        "INSERT OR IGNORE INTO edges (source_node_id, target_node_id, relation_type, confidence_score, is_manual) \
+         VALUES (?, ?, 'contains', NULL, 1)",
+    )
+    .bind(parent_id)
+    .bind(child_id)
+    .execute(tx.as_mut())
+    .await?;
+    Ok(())
+}
+
+async fn log_revision(
+    tx: &mut Transaction<'_, Sqlite>,
+    node_id: i64,
+    field_name: &str,
+    old_value: Option<&str>,
+    new_value: Option<&str>,
+) -> AppResult<()> {
+    sqlx::query(
+        "INSERT INTO node_revision_logs (node_id, field_name, old_value, new_value, reason) \
+         VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(node_id)
+    .bind(field_name)
+    .bind(old_value)
+    .bind(new_value)
+    .bind(RESTRUCTURE_REASON)
+    .execute(tx.as_mut())
+    .await?;
+    Ok(())
+}
@@ -128,6 +128,91 @@ pub async fn update_resource_sync_status(
     Ok(())
 }
 
+/// 失败的资源重新入队前恢复为待处理并清除错误信息
+pub async fn reset_resource_embedding_error(
+    pool: &DbPool,
+    node_id: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE nodes SET embedding_status = 'pending', last_embedding_error = NULL, \
+             processing_attempts = 0 \
+         WHERE node_id = ? AND node_type = 'resource' AND embedding_status = 'error'",
+    )
+    .bind(node_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 临时错误等待重试：保持待处理并记下错误，不标记为 error
+pub async fn mark_resource_retry_pending(
+    pool: &DbPool,
+    node_id: i64,
+    error: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE nodes SET embedding_status = 'pending', processing_stage = 'todo', \
+             last_embedding_error = ?, updated_at = CURRENT_TIMESTAMP \
+         WHERE node_id = ? AND node_type = 'resource'",
+    )
+    .bind(error)
+    .bind(node_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 正文被改写后标记已同步的向量过期，等待重新生成
+pub async fn mark_resource_embedding_dirty(
+    pool: &DbPool,
+    node_id: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE nodes SET embedding_status = 'dirty' \
+         WHERE node_id = ? AND node_type = 'resource' AND embedding_status = 'synced'",
+    )
+    .bind(node_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 已生成过向量的资源（synced / dirty），按 node_id 升序
+pub async fn list_embedded_resource_ids(pool: &DbPool) -> Result<Vec<i64>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT node_id FROM nodes \
+         WHERE node_type = 'resource' AND is_deleted = 0 \
+         AND embedding_status IN ('synced', 'dirty') \
+         ORDER BY node_id",
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// 处理失败后累加尝试次数，返回累加后的值
+pub async fn increment_processing_attempts(
+    pool: &DbPool,
+    node_id: i64,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar(
+        "UPDATE nodes SET processing_attempts = processing_attempts + 1 \
+         WHERE node_id = ? RETURNING processing_attempts",
+    )
+    .bind(node_id)
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn reset_processing_attempts(pool: &DbPool, node_id: i64) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE nodes SET processing_attempts = 0 WHERE node_id = ? AND processing_attempts != 0",
+    )
+    .bind(node_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
 pub async fn update_resource_review_status(
     pool: &DbPool,
     node_id: i64,
@@ -0,0 +1,86 @@
+use super::{DbPool, NewNotification, NotificationRecord};
+
+const NOTIFICATION_FIELDS: &str =
+    "notification_id, kind, title, body, node_id, action, is_read, created_at, read_at";
+
+pub async fn insert_notification(
+    pool: &DbPool,
+    params: NewNotification<'_>,
+) -> Result<NotificationRecord, sqlx::Error> {
+    let result = sqlx::query(
+        "INSERT INTO notifications (kind, title, body, node_id, action) VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(params.kind)
+    .bind(params.title)
+    .bind(params.body)
+    .bind(params.node_id)
+    .bind(params.action)
+    .execute(pool)
+    .await?;
+
+    get_notification_by_id(pool, result.last_insert_rowid()).await
+}
+
+pub async fn get_notification_by_id(
+    pool: &DbPool,
+    notification_id: i64,
+) -> Result<NotificationRecord, sqlx::Error> {
+    let sql = format!("SELECT {NOTIFICATION_FIELDS} FROM notifications WHERE notification_id = ?");
+    sqlx::query_as::<_, NotificationRecord>(&sql)
+        .bind(notification_id)
+        .fetch_one(pool)
+        .await
+}
+
+pub async fn list_notifications(
+    pool: &DbPool,
+    unread_only: bool,
+    limit: i64,
+) -> Result<Vec<NotificationRecord>, sqlx::Error> {
+    let filter = if unread_only { "WHERE is_read = 0" } else { "" };
+    let sql = format!(
+        "SELECT {NOTIFICATION_FIELDS} FROM notifications {filter} \
+         ORDER BY created_at DESC, notification_id DESC LIMIT ?"
+    );
+    sqlx::query_as::<_, NotificationRecord>(&sql)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+}
+
+pub async fn count_unread_notifications(pool: &DbPool) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar("SELECT COUNT(*) FROM notifications WHERE is_read = 0")
+        .fetch_one(pool)
+        .await
+}
+
+pub async fn mark_notification_read(
+    pool: &DbPool,
+    notification_id: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE notifications SET is_read = 1, read_at = CURRENT_TIMESTAMP \
+         WHERE notification_id = ? AND is_read = 0",
+    )
+    .bind(notification_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn mark_all_notifications_read(pool: &DbPool) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE notifications SET is_read = 1, read_at = CURRENT_TIMESTAMP WHERE is_read = 0",
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn delete_notification(pool: &DbPool, notification_id: i64) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM notifications WHERE notification_id = ?")
+        .bind(notification_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
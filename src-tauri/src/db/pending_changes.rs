@@ -0,0 +1,101 @@
+use sqlx::{Executor, Sqlite};
+
+use super::{DbPool, NewPendingChange, PendingChangeRecord, PendingChangeStatus};
+
+const PENDING_CHANGE_QUERY: &str = "SELECT c.change_id, c.kind, c.node_id, \
+     n.title AS node_title, c.old_value, c.new_value, t.title AS target_title, c.reason, \
+     c.session_id, c.status, c.created_at, c.decided_at \
+     FROM pending_changes c \
+     LEFT JOIN nodes n ON n.node_id = c.node_id \
+     LEFT JOIN nodes t ON c.kind = 'move_resource' AND t.node_id = CAST(c.new_value AS INTEGER)";
+
+pub async fn insert_pending_change(
+    pool: &DbPool,
+    params: NewPendingChange<'_>,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar(
+        "INSERT INTO pending_changes (kind, node_id, old_value, new_value, reason, session_id) \
+         VALUES (?, ?, ?, ?, ?, ?) RETURNING change_id",
+    )
+    .bind(params.kind)
+    .bind(params.node_id)
+    .bind(params.old_value)
+    .bind(params.new_value)
+    .bind(params.reason)
+    .bind(params.session_id)
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn get_pending_change(
+    pool: &DbPool,
+    change_id: i64,
+) -> Result<Option<PendingChangeRecord>, sqlx::Error> {
+    let sql = format!("{PENDING_CHANGE_QUERY} WHERE c.change_id = ?");
+    sqlx::query_as::<_, PendingChangeRecord>(&sql)
+        .bind(change_id)
+        .fetch_optional(pool)
+        .await
+}
+
+/// 按状态列出修改，最早提出的在前；status 为空时列出全部（最近的在前）
+pub async fn list_pending_changes_by_status(
+    pool: &DbPool,
+    status: Option<PendingChangeStatus>,
+) -> Result<Vec<PendingChangeRecord>, sqlx::Error> {
+    let sql = match status {
+        Some(_) => format!("{PENDING_CHANGE_QUERY} WHERE c.status = ? ORDER BY c.change_id"),
+        None => format!("{PENDING_CHANGE_QUERY} ORDER BY c.change_id DESC"),
+    };
+    let mut query = sqlx::query_as::<_, PendingChangeRecord>(&sql);
+    if let Some(status) = status {
+        query = query.bind(status);
+    }
+    query.fetch_all(pool).await
+}
+
+/// 记下批准 create_task 后新建的任务
+pub async fn set_pending_change_node<'a, E>(
+    executor: E,
+    change_id: i64,
+    node_id: i64,
+) -> Result<(), sqlx::Error>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    sqlx::query("UPDATE pending_changes SET node_id = ? WHERE change_id = ?")
+        .bind(node_id)
+        .bind(change_id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// 把仍待确认的修改标记为已批准 / 已拒绝；返回是否有行被更新
+pub async fn decide_pending_change(
+    pool: &DbPool,
+    change_id: i64,
+    status: PendingChangeStatus,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "UPDATE pending_changes SET status = ?, decided_at = CURRENT_TIMESTAMP \
+         WHERE change_id = ? AND status = 'pending'",
+    )
+    .bind(status)
+    .bind(change_id)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// 执行失败时把已认领（标记为批准）的修改放回待确认
+pub async fn reopen_pending_change(pool: &DbPool, change_id: i64) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE pending_changes SET status = 'pending', decided_at = NULL \
+         WHERE change_id = ? AND status = 'approved'",
+    )
+    .bind(change_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,97 @@
+//! AI 队列任务的持久化，应用重启后恢复排队中的资源
+
+use super::{DbPool, JobPriority, PipelineJobRecord, PipelineStageCount};
+
+/// 记录排队中的任务；已有记录时更新优先级并重置为 queued
+pub async fn upsert_pipeline_job(
+    pool: &DbPool,
+    node_id: i64,
+    priority: JobPriority,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO pipeline_jobs (node_id, priority) VALUES (?, ?) \
+         ON CONFLICT(node_id) DO UPDATE SET priority = excluded.priority, status = 'queued', \
+             started_at = NULL, retry_after = NULL",
+    )
+    .bind(node_id)
+    .bind(priority)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 等待重试的任务：保留记录并写入重试时间（delay_secs 秒后）
+pub async fn mark_pipeline_job_retry(
+    pool: &DbPool,
+    node_id: i64,
+    priority: JobPriority,
+    delay_secs: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO pipeline_jobs (node_id, priority, retry_after) \
+         VALUES (?, ?, datetime('now', '+' || ? || ' seconds')) \
+         ON CONFLICT(node_id) DO UPDATE SET priority = excluded.priority, status = 'queued', \
+             started_at = NULL, retry_after = excluded.retry_after",
+    )
+    .bind(node_id)
+    .bind(priority)
+    .bind(delay_secs)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn mark_pipeline_job_processing(pool: &DbPool, node_id: i64) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE pipeline_jobs SET status = 'processing', started_at = CURRENT_TIMESTAMP WHERE node_id = ?",
+    )
+    .bind(node_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn delete_pipeline_job(pool: &DbPool, node_id: i64) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM pipeline_jobs WHERE node_id = ?")
+        .bind(node_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// 上次运行中断时处理中的任务重新排队
+pub async fn requeue_interrupted_pipeline_jobs(pool: &DbPool) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(
+        "UPDATE pipeline_jobs SET status = 'queued', started_at = NULL WHERE status = 'processing'",
+    )
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// 未删除资源的任务，按入队顺序
+pub async fn list_pipeline_jobs(pool: &DbPool) -> Result<Vec<PipelineJobRecord>, sqlx::Error> {
+    sqlx::query_as::<_, PipelineJobRecord>(
+        "SELECT j.node_id, j.priority, j.status, j.enqueued_at, j.started_at, j.retry_after \
+         FROM pipeline_jobs j JOIN nodes n ON n.node_id = j.node_id \
+         WHERE n.is_deleted = 0 \
+         ORDER BY j.enqueued_at, j.node_id",
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// 按任务状态与资源处理阶段计数
+pub async fn count_pipeline_jobs_by_stage(
+    pool: &DbPool,
+) -> Result<Vec<PipelineStageCount>, sqlx::Error> {
+    sqlx::query_as::<_, PipelineStageCount>(
+        "SELECT j.status, COALESCE(n.processing_stage, 'todo') AS processing_stage, COUNT(*) AS count \
+         FROM pipeline_jobs j JOIN nodes n ON n.node_id = j.node_id \
+         WHERE n.is_deleted = 0 \
+         GROUP BY j.status, COALESCE(n.processing_stage, 'todo') \
+         ORDER BY j.status, processing_stage",
+    )
+    .fetch_all(pool)
+    .await
+}
@@ -3,8 +3,16 @@ use std::{path::Path, str::FromStr, time::Duration};
 use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteSynchronous};
 
 use super::{DbPool, MIGRATOR};
+use crate::i18n::MessageCode;
+use crate::{AppError, AppResult};
 
-pub async fn init_pool(db_path: impl AsRef<Path>) -> Result<DbPool, sqlx::Error> {
+/// 数据格式版本，保存在 `PRAGMA user_version`
+///
+/// 数据的解释方式发生不兼容变化时递增（即使没有新的表结构迁移）。
+/// 旧版程序打开新版写入的数据库时据此拒绝启动。
+pub const DATA_FORMAT_VERSION: i64 = 1;
+
+pub async fn init_pool(db_path: impl AsRef<Path>) -> AppResult<DbPool> {
     // 任何一种实现了 AsRef<Path> 接口的数据类型
     // 如果类型 A 实现了 AsRef<B>，意思就是" A 可以很容易、很低成本地被借用看作是 B "
     let db_url = format!("sqlite://{}", db_path.as_ref().to_string_lossy());
@@ -24,6 +32,9 @@ pub async fn init_pool(db_path: impl AsRef<Path>) -> Result<DbPool, sqlx::Error>
         .await?;
     // 末尾的 ? 表示：如果连接失败，直接把错误抛出并结束函数
 
+    // 必须在迁移之前检查：旧版迁移器遇到未知的迁移记录会直接报错
+    check_data_format(&pool, db_path.as_ref()).await?;
+
     MIGRATOR.run(&pool).await?;
     // 检查数据库里的 _sqlx_migrations 表，看看哪些 SQL 脚本还没跑过，然后依次执行它们
 
@@ -31,5 +42,99 @@ pub async fn init_pool(db_path: impl AsRef<Path>) -> Result<DbPool, sqlx::Error>
     sqlx::query!("INSERT OR IGNORE INTO users (user_id, user_name) VALUES (1, 'default')")
         .execute(&pool)
         .await?;
+
+    sqlx::query(&format!("PRAGMA user_version = {DATA_FORMAT_VERSION}"))
+        .execute(&pool)
+        .await?;
     Ok(pool)
 }
+
+/// 打开导出快照的临时副本，供只读浏览
+///
+/// 先以读写方式补齐迁移，使旧版本的备份也能按当前表结构查询（只修改临时副本），
+/// 再以只读方式重新打开。
+pub async fn open_snapshot_pool(db_path: impl AsRef<Path>) -> AppResult<DbPool> {
+    let db_url = format!("sqlite://{}", db_path.as_ref().to_string_lossy());
+    let options = SqliteConnectOptions::from_str(&db_url)?
+        .journal_mode(SqliteJournalMode::Delete)
+        .busy_timeout(Duration::from_secs(5))
+        .foreign_keys(true);
+
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(options.clone())
+        .await?;
+    check_data_format(&pool, db_path.as_ref()).await?;
+    MIGRATOR.run(&pool).await?;
+    pool.close().await;
+
+    Ok(SqlitePoolOptions::new()
+        .max_connections(2)
+        .connect_with(options.read_only(true))
+        .await?)
+}
+
+/// 数据库由更新版本的程序写入（数据格式或迁移版本高于当前程序所知）时返回错误
+async fn check_data_format(pool: &DbPool, db_path: &Path) -> AppResult<()> {
+    let format_version: i64 = sqlx::query_scalar("PRAGMA user_version")
+        .fetch_one(pool)
+        .await?;
+    let has_migrations: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = '_sqlx_migrations')",
+    )
+    .fetch_one(pool)
+    .await?;
+    let schema_version: i64 = if has_migrations {
+        sqlx::query_scalar("SELECT COALESCE(MAX(version), 0) FROM _sqlx_migrations")
+            .fetch_one(pool)
+            .await?
+    } else {
+        0
+    };
+    let supported_schema = MIGRATOR
+        .iter()
+        .map(|migration| migration.version)
+        .max()
+        .unwrap_or(0);
+
+    if format_version <= DATA_FORMAT_VERSION && schema_version <= supported_schema {
+        return Ok(());
+    }
+
+    tracing::error!(
+        format_version,
+        schema_version,
+        supported_format = DATA_FORMAT_VERSION,
+        supported_schema,
+        "Database was written by a newer app version"
+    );
+    Err(AppError::Coded {
+        code: MessageCode::DataFormatTooNew,
+        args: vec![
+            ("found_format", format_version.to_string()),
+            ("found_schema", schema_version.to_string()),
+            ("supported_format", DATA_FORMAT_VERSION.to_string()),
+            ("supported_schema", supported_schema.to_string()),
+            ("path", db_path.display().to_string()),
+        ],
+    })
+}
+
+/// 尚未成功执行的迁移版本（记录缺失、执行失败或脚本内容已变化）
+pub async fn list_pending_migrations(pool: &DbPool) -> AppResult<Vec<i64>> {
+    let applied: Vec<(i64, bool, Vec<u8>)> =
+        sqlx::query_as("SELECT version, success, checksum FROM _sqlx_migrations")
+            .fetch_all(pool)
+            .await?;
+    Ok(MIGRATOR
+        .iter()
+        .filter(|migration| {
+            !applied.iter().any(|(version, success, checksum)| {
+                *version == migration.version
+                    && *success
+                    && checksum.as_slice() == &*migration.checksum
+            })
+        })
+        .map(|migration| migration.version)
+        .collect())
+}
@@ -0,0 +1,89 @@
+use super::{DbPool, ReadingStateRecord, RecentNodeRow};
+
+/// 记录一次节点打开（更新最近打开时间并累加次数）
+pub async fn mark_node_opened(pool: &DbPool, node_id: i64) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO node_reading_state (node_id, last_opened_at, open_count) \
+         VALUES (?, CURRENT_TIMESTAMP, 1) \
+         ON CONFLICT(node_id) DO UPDATE SET \
+             last_opened_at = CURRENT_TIMESTAMP, open_count = open_count + 1",
+    )
+    .bind(node_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 保存阅读位置（滚动比例 / 页码），不影响最近打开时间
+pub async fn save_reading_position(
+    pool: &DbPool,
+    node_id: i64,
+    scroll_position: Option<f64>,
+    page: Option<i64>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO node_reading_state (node_id, scroll_position, page, position_updated_at) \
+         VALUES (?, ?, ?, CURRENT_TIMESTAMP) \
+         ON CONFLICT(node_id) DO UPDATE SET \
+             scroll_position = excluded.scroll_position, page = excluded.page, \
+             position_updated_at = CURRENT_TIMESTAMP",
+    )
+    .bind(node_id)
+    .bind(scroll_position)
+    .bind(page)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 保存前端上报的阅读进度（0~1）
+pub async fn save_reading_progress(
+    pool: &DbPool,
+    node_id: i64,
+    progress: f64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO node_reading_state (node_id, progress, progress_updated_at) \
+         VALUES (?, ?, CURRENT_TIMESTAMP) \
+         ON CONFLICT(node_id) DO UPDATE SET \
+             progress = excluded.progress, progress_updated_at = CURRENT_TIMESTAMP",
+    )
+    .bind(node_id)
+    .bind(progress)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn get_reading_state(
+    pool: &DbPool,
+    node_id: i64,
+) -> Result<Option<ReadingStateRecord>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT node_id, last_opened_at, open_count, scroll_position, page, position_updated_at, \
+             progress, progress_updated_at \
+         FROM node_reading_state WHERE node_id = ?",
+    )
+    .bind(node_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// 最近打开的节点（已删除的跳过），最新的在前
+pub async fn list_recently_opened_nodes(
+    pool: &DbPool,
+    limit: i32,
+) -> Result<Vec<RecentNodeRow>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT n.node_id, n.node_type, n.title, n.summary, n.resource_subtype, \
+                r.last_opened_at, r.open_count, r.scroll_position, r.page \
+         FROM node_reading_state r \
+         INNER JOIN nodes n ON n.node_id = r.node_id AND n.is_deleted = 0 \
+         WHERE r.open_count > 0 \
+         ORDER BY r.last_opened_at DESC \
+         LIMIT ?",
+    )
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
@@ -0,0 +1,94 @@
+use uuid::Uuid;
+
+use super::nodes::NODE_FIELDS;
+use super::{DbPool, NodeRecord};
+
+/// 设置或清除任务的重复规则
+pub async fn update_task_recurrence_rule(
+    pool: &DbPool,
+    node_id: i64,
+    rule: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE nodes SET recurrence_rule = ?, updated_at = CURRENT_TIMESTAMP \
+         WHERE node_id = ? AND node_type = 'task'",
+    )
+    .bind(rule)
+    .bind(node_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 未完成且带重复规则的任务
+pub async fn list_open_recurring_tasks(pool: &DbPool) -> Result<Vec<NodeRecord>, sqlx::Error> {
+    let sql = format!(
+        "SELECT {NODE_FIELDS} FROM nodes \
+         WHERE node_type = 'task' AND task_status = 'todo' AND is_deleted = 0 \
+         AND recurrence_rule IS NOT NULL AND due_date IS NOT NULL"
+    );
+    sqlx::query_as::<_, NodeRecord>(&sql).fetch_all(pool).await
+}
+
+/// 为已完成的重复任务生成下一次的任务
+///
+/// 复制标题、摘要、备注、优先级、标签、提醒设置与所属的 contains 父节点，
+/// 重复规则移交给新任务，原任务不再带规则，重复完成也不会生成第二份。
+pub async fn materialize_next_task_occurrence(
+    pool: &DbPool,
+    node_id: i64,
+    next_due_date: &str,
+) -> Result<i64, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let next_id: i64 = sqlx::query_scalar(
+        "INSERT INTO nodes (uuid, user_id, title, summary, node_type, task_status, priority, \
+         due_date, user_note, review_status, recurrence_rule) \
+         SELECT ?, user_id, title, summary, 'task', 'todo', priority, ?, user_note, 'reviewed', recurrence_rule \
+         FROM nodes WHERE node_id = ? AND node_type = 'task' \
+         RETURNING node_id",
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(next_due_date)
+    .bind(node_id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        "INSERT INTO node_tags (node_id, tag_id, source) \
+         SELECT ?, tag_id, source FROM node_tags WHERE node_id = ?",
+    )
+    .bind(next_id)
+    .bind(node_id)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        "INSERT OR IGNORE INTO edges (source_node_id, target_node_id, relation_type, confidence_score, is_manual) \
+         SELECT source_node_id, ?, 'contains', confidence_score, is_manual FROM edges \
+         WHERE target_node_id = ? AND relation_type = 'contains' AND is_deleted = 0",
+    )
+    .bind(next_id)
+    .bind(node_id)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        "INSERT INTO task_reminders (node_id, minutes_before) \
+         SELECT ?, minutes_before FROM task_reminders WHERE node_id = ?",
+    )
+    .bind(next_id)
+    .bind(node_id)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        "UPDATE nodes SET recurrence_rule = NULL, updated_at = CURRENT_TIMESTAMP WHERE node_id = ?",
+    )
+    .bind(node_id)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(next_id)
+}
@@ -0,0 +1,183 @@
+use super::{
+    DbPool, RetentionAction, RetentionCandidateRow, RetentionRuleInput, RetentionRuleRecord,
+};
+
+const RULE_FIELDS: &str = "rule_id, name, topic_id, resource_subtype, max_age_days, action, \
+     skip_linked, is_enabled, last_run_at, last_affected, created_at";
+
+/// 每次更新的节点数，避免过长的 IN 列表
+const UPDATE_BATCH: usize = 500;
+
+pub async fn insert_retention_rule(
+    pool: &DbPool,
+    rule: &RetentionRuleInput,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar(
+        "INSERT INTO retention_rules \
+             (name, topic_id, resource_subtype, max_age_days, action, skip_linked, is_enabled) \
+         VALUES (?, ?, ?, ?, ?, ?, ?) \
+         RETURNING rule_id",
+    )
+    .bind(&rule.name)
+    .bind(rule.topic_id)
+    .bind(rule.resource_subtype)
+    .bind(rule.max_age_days)
+    .bind(rule.action)
+    .bind(rule.skip_linked)
+    .bind(rule.is_enabled)
+    .fetch_one(pool)
+    .await
+}
+
+/// 返回是否有行被更新
+pub async fn update_retention_rule(
+    pool: &DbPool,
+    rule_id: i64,
+    rule: &RetentionRuleInput,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "UPDATE retention_rules SET name = ?, topic_id = ?, resource_subtype = ?, \
+             max_age_days = ?, action = ?, skip_linked = ?, is_enabled = ? \
+         WHERE rule_id = ?",
+    )
+    .bind(&rule.name)
+    .bind(rule.topic_id)
+    .bind(rule.resource_subtype)
+    .bind(rule.max_age_days)
+    .bind(rule.action)
+    .bind(rule.skip_linked)
+    .bind(rule.is_enabled)
+    .bind(rule_id)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn get_retention_rule(
+    pool: &DbPool,
+    rule_id: i64,
+) -> Result<Option<RetentionRuleRecord>, sqlx::Error> {
+    let sql = format!("SELECT {RULE_FIELDS} FROM retention_rules WHERE rule_id = ?");
+    sqlx::query_as::<_, RetentionRuleRecord>(&sql)
+        .bind(rule_id)
+        .fetch_optional(pool)
+        .await
+}
+
+pub async fn list_retention_rules(pool: &DbPool) -> Result<Vec<RetentionRuleRecord>, sqlx::Error> {
+    let sql = format!("SELECT {RULE_FIELDS} FROM retention_rules ORDER BY rule_id");
+    sqlx::query_as::<_, RetentionRuleRecord>(&sql)
+        .fetch_all(pool)
+        .await
+}
+
+/// 删除规则；已归档或删除的资源不受影响
+pub async fn delete_retention_rule(pool: &DbPool, rule_id: i64) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM retention_rules WHERE rule_id = ?")
+        .bind(rule_id)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn record_retention_run(
+    pool: &DbPool,
+    rule_id: i64,
+    affected: usize,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE retention_rules SET last_run_at = CURRENT_TIMESTAMP, last_affected = ? \
+         WHERE rule_id = ?",
+    )
+    .bind(affected as i64)
+    .bind(rule_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 规则当前命中的资源，按创建时间升序
+///
+/// 只包括未删除、未收藏且创建超过 max_age_days 天的资源；归档规则跳过已归档的资源。
+/// skip_linked 时跳过有 related_to / derived_from 边、正文引用（任一方向）或挂在任务下的资源
+pub async fn list_retention_candidates(
+    pool: &DbPool,
+    rule: &RetentionRuleInput,
+) -> Result<Vec<RetentionCandidateRow>, sqlx::Error> {
+    sqlx::query_as::<_, RetentionCandidateRow>(
+        "WITH RECURSIVE scope(node_id) AS ( \
+            SELECT node_id FROM nodes WHERE node_id = ? AND node_type = 'topic' AND is_deleted = 0 \
+            UNION SELECT e.target_node_id FROM edges e \
+            JOIN scope s ON e.source_node_id = s.node_id \
+            WHERE e.relation_type = 'contains' AND e.is_deleted = 0 \
+         ) \
+         SELECT n.node_id, n.title, n.resource_subtype, n.created_at, n.archived_at \
+         FROM nodes n \
+         WHERE n.node_type = 'resource' AND n.is_deleted = 0 AND n.is_pinned = 0 \
+         AND n.created_at <= datetime('now', ?) \
+         AND (? IS NULL OR n.node_id IN (SELECT node_id FROM scope)) \
+         AND (? IS NULL OR n.resource_subtype = ?) \
+         AND (? = 'delete' OR n.archived_at IS NULL) \
+         AND (? = 0 OR NOT ( \
+             EXISTS (SELECT 1 FROM edges e WHERE e.is_deleted = 0 AND ( \
+                 (e.relation_type IN ('related_to', 'derived_from') \
+                     AND (e.source_node_id = n.node_id OR e.target_node_id = n.node_id)) \
+                 OR (e.relation_type = 'contains' AND e.target_node_id = n.node_id \
+                     AND e.source_node_id IN (SELECT node_id FROM nodes WHERE node_type = 'task')))) \
+             OR EXISTS (SELECT 1 FROM node_links l \
+                 WHERE l.source_node_id = n.node_id OR l.target_node_id = n.node_id) \
+         )) \
+         ORDER BY n.created_at, n.node_id",
+    )
+    .bind(rule.topic_id)
+    .bind(format!("-{} days", rule.max_age_days))
+    .bind(rule.topic_id)
+    .bind(rule.resource_subtype)
+    .bind(rule.resource_subtype)
+    .bind(rule.action)
+    .bind(rule.skip_linked)
+    .fetch_all(pool)
+    .await
+}
+
+/// 归档或移入回收站，返回实际处理的节点数
+pub async fn apply_retention_action(
+    pool: &DbPool,
+    action: RetentionAction,
+    node_ids: &[i64],
+) -> Result<usize, sqlx::Error> {
+    let set_clause = match action {
+        RetentionAction::Archive => "archived_at = CURRENT_TIMESTAMP WHERE archived_at IS NULL",
+        RetentionAction::Delete => {
+            "is_deleted = 1, deleted_at = CURRENT_TIMESTAMP WHERE is_deleted = 0"
+        }
+    };
+    let mut affected = 0;
+    for batch in node_ids.chunks(UPDATE_BATCH) {
+        let placeholders = vec!["?"; batch.len()].join(", ");
+        let sql = format!("UPDATE nodes SET {set_clause} AND node_id IN ({placeholders})");
+        let mut query = sqlx::query(&sql);
+        for node_id in batch {
+            query = query.bind(node_id);
+        }
+        affected += query.execute(pool).await?.rows_affected() as usize;
+    }
+    Ok(affected)
+}
+
+/// 归档或取消归档单个节点；返回是否有行被更新
+pub async fn update_node_archived(
+    pool: &DbPool,
+    node_id: i64,
+    archived: bool,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "UPDATE nodes SET archived_at = CASE WHEN ? THEN COALESCE(archived_at, CURRENT_TIMESTAMP) ELSE NULL END \
+         WHERE node_id = ? AND is_deleted = 0",
+    )
+    .bind(archived)
+    .bind(node_id)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
@@ -0,0 +1,70 @@
+use super::{DbPool, ResourceSourceRow, SourceRuleField, SourceTagRuleRecord};
+
+/// 列出所有未删除资源的来源元数据
+pub async fn list_resource_sources(pool: &DbPool) -> Result<Vec<ResourceSourceRow>, sqlx::Error> {
+    sqlx::query_as::<_, ResourceSourceRow>(
+        "SELECT source_meta, created_at FROM nodes \
+         WHERE node_type = 'resource' AND is_deleted = 0 AND source_meta IS NOT NULL",
+    )
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn insert_source_tag_rule(
+    pool: &DbPool,
+    match_field: SourceRuleField,
+    pattern: &str,
+    tag_name: &str,
+) -> Result<i64, sqlx::Error> {
+    let result = sqlx::query(
+        "INSERT INTO source_tag_rules (match_field, pattern, tag_name) VALUES (?, ?, ?)",
+    )
+    .bind(match_field)
+    .bind(pattern)
+    .bind(tag_name)
+    .execute(pool)
+    .await?;
+
+    Ok(result.last_insert_rowid())
+}
+
+pub async fn list_source_tag_rules(pool: &DbPool) -> Result<Vec<SourceTagRuleRecord>, sqlx::Error> {
+    sqlx::query_as::<_, SourceTagRuleRecord>(
+        "SELECT rule_id, match_field, pattern, tag_name, is_enabled, created_at \
+         FROM source_tag_rules ORDER BY created_at ASC",
+    )
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn list_enabled_source_tag_rules(
+    pool: &DbPool,
+) -> Result<Vec<SourceTagRuleRecord>, sqlx::Error> {
+    sqlx::query_as::<_, SourceTagRuleRecord>(
+        "SELECT rule_id, match_field, pattern, tag_name, is_enabled, created_at \
+         FROM source_tag_rules WHERE is_enabled = 1 ORDER BY created_at ASC",
+    )
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn update_source_tag_rule_enabled(
+    pool: &DbPool,
+    rule_id: i64,
+    is_enabled: bool,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE source_tag_rules SET is_enabled = ? WHERE rule_id = ?")
+        .bind(is_enabled)
+        .bind(rule_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+pub async fn delete_source_tag_rule(pool: &DbPool, rule_id: i64) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM source_tag_rules WHERE rule_id = ?")
+        .bind(rule_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
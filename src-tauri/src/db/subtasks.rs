@@ -0,0 +1,82 @@
+//! 子任务：父任务通过 contains 边包含子任务
+
+use super::nodes::NODE_FIELDS;
+use super::{
+    contains_creates_cycle, insert_edge_if_missing, DbPool, EdgeRelationType, NewEdge, NodeRecord,
+    SubtaskLinkRow,
+};
+use crate::error::{AppError, AppResult};
+use crate::i18n::MessageCode;
+
+/// 父任务的直接子任务，按创建时间排序
+pub async fn list_child_tasks(
+    pool: &DbPool,
+    parent_task_id: i64,
+) -> Result<Vec<NodeRecord>, sqlx::Error> {
+    let sql = format!(
+        "SELECT {NODE_FIELDS} FROM nodes \
+         WHERE node_type = 'task' AND is_deleted = 0 AND node_id IN ( \
+            SELECT target_node_id FROM edges \
+            WHERE source_node_id = ? AND relation_type = 'contains' AND is_deleted = 0 \
+         ) \
+         ORDER BY created_at ASC, node_id ASC"
+    );
+    sqlx::query_as::<_, NodeRecord>(&sql)
+        .bind(parent_task_id)
+        .fetch_all(pool)
+        .await
+}
+
+/// 列出未删除任务之间的 contains 边及子任务状态
+pub async fn list_subtask_links(pool: &DbPool) -> Result<Vec<SubtaskLinkRow>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT e.source_node_id AS parent_id, e.target_node_id AS child_id, \
+         c.task_status AS child_status FROM edges e \
+         JOIN nodes p ON p.node_id = e.source_node_id \
+         JOIN nodes c ON c.node_id = e.target_node_id \
+         WHERE e.relation_type = 'contains' AND e.is_deleted = 0 \
+         AND p.node_type = 'task' AND p.is_deleted = 0 \
+         AND c.node_type = 'task' AND c.is_deleted = 0",
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// 调整任务的父任务，new_parent_id 为空时移到顶层
+///
+/// 只替换来自其他任务的 contains 边，所属主题不变；形成环时整体回滚
+pub async fn set_parent_task(
+    pool: &DbPool,
+    task_id: i64,
+    new_parent_id: Option<i64>,
+) -> AppResult<()> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query(
+        "DELETE FROM edges WHERE target_node_id = ? AND relation_type = 'contains' \
+         AND source_node_id IN (SELECT node_id FROM nodes WHERE node_type = 'task')",
+    )
+    .bind(task_id)
+    .execute(&mut *tx)
+    .await?;
+
+    if let Some(parent_id) = new_parent_id {
+        if contains_creates_cycle(&mut *tx, parent_id, task_id).await? {
+            return Err(AppError::coded(MessageCode::SubtaskCycle));
+        }
+        insert_edge_if_missing(
+            &mut *tx,
+            NewEdge {
+                source_node_id: parent_id,
+                target_node_id: task_id,
+                relation_type: EdgeRelationType::Contains,
+                confidence_score: None,
+                is_manual: true,
+            },
+        )
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
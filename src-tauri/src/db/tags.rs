@@ -0,0 +1,342 @@
+use std::collections::HashSet;
+
+use sqlx::{Sqlite, Transaction};
+
+use super::nodes::NODE_FIELDS;
+use super::{
+    DbPool, NodeRecord, TagRecord, TagSource, TagSuggestionRecord, TagSuggestionStatus,
+    TagUsageRecord,
+};
+
+/// 按名称获取标签，不存在则创建（名称忽略大小写）
+pub async fn get_or_create_tag(pool: &DbPool, name: &str) -> Result<i64, sqlx::Error> {
+    sqlx::query("INSERT OR IGNORE INTO tags (name) VALUES (?)")
+        .bind(name)
+        .execute(pool)
+        .await?;
+
+    sqlx::query_scalar("SELECT tag_id FROM tags WHERE name = ?")
+        .bind(name)
+        .fetch_one(pool)
+        .await
+}
+
+/// 给节点打标签（已存在则忽略）
+pub async fn attach_tag_to_node(
+    pool: &DbPool,
+    node_id: i64,
+    tag_id: i64,
+    source: TagSource,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("INSERT OR IGNORE INTO node_tags (node_id, tag_id, source) VALUES (?, ?, ?)")
+        .bind(node_id)
+        .bind(tag_id)
+        .bind(source)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+pub async fn list_tags_for_node(
+    pool: &DbPool,
+    node_id: i64,
+) -> Result<Vec<TagRecord>, sqlx::Error> {
+    sqlx::query_as::<_, TagRecord>(
+        "SELECT t.tag_id, t.name, t.created_at \
+         FROM tags t \
+         INNER JOIN node_tags nt ON nt.tag_id = t.tag_id \
+         WHERE nt.node_id = ? \
+         ORDER BY t.name",
+    )
+    .bind(node_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// 全部节点的 (node_id, 标签名)，按标签名排序
+pub async fn list_all_node_tag_names(pool: &DbPool) -> Result<Vec<(i64, String)>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT nt.node_id, t.name FROM node_tags nt \
+         INNER JOIN tags t ON t.tag_id = nt.tag_id \
+         ORDER BY nt.node_id, t.name",
+    )
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn get_tag(pool: &DbPool, tag_id: i64) -> Result<Option<TagRecord>, sqlx::Error> {
+    sqlx::query_as::<_, TagRecord>("SELECT tag_id, name, created_at FROM tags WHERE tag_id = ?")
+        .bind(tag_id)
+        .fetch_optional(pool)
+        .await
+}
+
+/// 按名称查找标签（忽略大小写）
+pub async fn find_tag_by_name(pool: &DbPool, name: &str) -> Result<Option<TagRecord>, sqlx::Error> {
+    sqlx::query_as::<_, TagRecord>("SELECT tag_id, name, created_at FROM tags WHERE name = ?")
+        .bind(name)
+        .fetch_optional(pool)
+        .await
+}
+
+/// 移除节点上的标签，返回是否存在；标签本身保留
+pub async fn detach_tag_from_node(
+    pool: &DbPool,
+    node_id: i64,
+    tag_id: i64,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM node_tags WHERE node_id = ? AND tag_id = ?")
+        .bind(node_id)
+        .bind(tag_id)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// 所有标签及其未删除节点数，按名称排序
+pub async fn list_tags_with_counts(pool: &DbPool) -> Result<Vec<TagUsageRecord>, sqlx::Error> {
+    sqlx::query_as::<_, TagUsageRecord>(
+        "SELECT t.tag_id, t.name, t.created_at, COUNT(n.node_id) AS node_count \
+         FROM tags t \
+         LEFT JOIN node_tags nt ON nt.tag_id = t.tag_id \
+         LEFT JOIN nodes n ON n.node_id = nt.node_id AND n.is_deleted = 0 \
+         GROUP BY t.tag_id \
+         ORDER BY t.name",
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// 带有该标签的未删除节点，最近更新的在前
+pub async fn list_nodes_by_tag(pool: &DbPool, tag_id: i64) -> Result<Vec<NodeRecord>, sqlx::Error> {
+    let sql = format!(
+        "SELECT {NODE_FIELDS} FROM nodes \
+         WHERE is_deleted = 0 AND node_id IN (SELECT node_id FROM node_tags WHERE tag_id = ?) \
+         ORDER BY updated_at DESC"
+    );
+    sqlx::query_as::<_, NodeRecord>(&sql)
+        .bind(tag_id)
+        .fetch_all(pool)
+        .await
+}
+
+/// 同时带有所有 tag_ids 的节点 ID（用于搜索与首页的标签过滤）
+pub async fn list_node_ids_with_all_tags(
+    pool: &DbPool,
+    tag_ids: &[i64],
+) -> Result<Vec<i64>, sqlx::Error> {
+    if tag_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let placeholders = vec!["?"; tag_ids.len()].join(", ");
+    let sql = format!(
+        "SELECT node_id FROM node_tags WHERE tag_id IN ({placeholders}) \
+         GROUP BY node_id HAVING COUNT(DISTINCT tag_id) = ?"
+    );
+    let mut query = sqlx::query_scalar::<_, i64>(&sql);
+    for tag_id in tag_ids {
+        query = query.bind(tag_id);
+    }
+    let distinct = tag_ids.iter().collect::<HashSet<_>>().len() as i64;
+    query.bind(distinct).fetch_all(pool).await
+}
+
+/// 重命名标签，并同步引用旧名称的来源规则
+pub async fn rename_tag(pool: &DbPool, tag_id: i64, name: &str) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let old_name: String = sqlx::query_scalar("SELECT name FROM tags WHERE tag_id = ?")
+        .bind(tag_id)
+        .fetch_one(&mut *tx)
+        .await?;
+    sqlx::query("UPDATE tags SET name = ? WHERE tag_id = ?")
+        .bind(name)
+        .bind(tag_id)
+        .execute(&mut *tx)
+        .await?;
+    retarget_source_tag_rules(&mut tx, &old_name, name).await?;
+    tx.commit().await?;
+    tracing::debug!(tag_id, from = %old_name, to = %name, "Tag renamed");
+    Ok(())
+}
+
+/// 把 source_tag_ids 的节点关联并入 target_tag_id 后删除这些标签；来源规则改为打目标标签
+pub async fn merge_tags(
+    pool: &DbPool,
+    source_tag_ids: &[i64],
+    target_tag_id: i64,
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let target_name: String = sqlx::query_scalar("SELECT name FROM tags WHERE tag_id = ?")
+        .bind(target_tag_id)
+        .fetch_one(&mut *tx)
+        .await?;
+    for &source_tag_id in source_tag_ids {
+        let Some(source_name) =
+            sqlx::query_scalar::<_, String>("SELECT name FROM tags WHERE tag_id = ?")
+                .bind(source_tag_id)
+                .fetch_optional(&mut *tx)
+                .await?
+        else {
+            continue;
+        };
+        // 两个标签都有的节点保留目标标签原有的记录
+        sqlx::query(
+            "INSERT OR IGNORE INTO node_tags (node_id, tag_id, source, created_at) \
+             SELECT node_id, ?, source, created_at FROM node_tags WHERE tag_id = ?",
+        )
+        .bind(target_tag_id)
+        .bind(source_tag_id)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query("DELETE FROM node_tags WHERE tag_id = ?")
+            .bind(source_tag_id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM tags WHERE tag_id = ?")
+            .bind(source_tag_id)
+            .execute(&mut *tx)
+            .await?;
+        retarget_source_tag_rules(&mut tx, &source_name, &target_name).await?;
+    }
+    tx.commit().await?;
+    tracing::debug!(target_tag_id, merged = source_tag_ids.len(), "Tags merged");
+    Ok(())
+}
+
+/// 节点已有的全部建议名称（含已采纳 / 已拒绝），用于避免重复建议
+pub async fn list_tag_suggestion_names(
+    pool: &DbPool,
+    node_id: i64,
+) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query_scalar("SELECT name FROM tag_suggestions WHERE node_id = ?")
+        .bind(node_id)
+        .fetch_all(pool)
+        .await
+}
+
+/// 用新一轮建议替换节点上仍待确认的建议；已处理过的名称保持原状态
+pub async fn replace_pending_tag_suggestions(
+    pool: &DbPool,
+    node_id: i64,
+    names: &[String],
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("DELETE FROM tag_suggestions WHERE node_id = ? AND status = ?")
+        .bind(node_id)
+        .bind(TagSuggestionStatus::Pending)
+        .execute(&mut *tx)
+        .await?;
+    for name in names {
+        sqlx::query("INSERT OR IGNORE INTO tag_suggestions (node_id, name) VALUES (?, ?)")
+            .bind(node_id)
+            .bind(name)
+            .execute(&mut *tx)
+            .await?;
+    }
+    tx.commit().await
+}
+
+pub async fn list_pending_tag_suggestions(
+    pool: &DbPool,
+    node_id: i64,
+) -> Result<Vec<TagSuggestionRecord>, sqlx::Error> {
+    sqlx::query_as::<_, TagSuggestionRecord>(
+        "SELECT suggestion_id, node_id, name, status, created_at FROM tag_suggestions \
+         WHERE node_id = ? AND status = ? ORDER BY suggestion_id",
+    )
+    .bind(node_id)
+    .bind(TagSuggestionStatus::Pending)
+    .fetch_all(pool)
+    .await
+}
+
+/// 采纳 / 拒绝节点的待确认建议；采纳的建议作为手动标签打到节点上
+///
+/// 不属于该节点或已处理过的建议 ID 会被忽略
+pub async fn resolve_tag_suggestions(
+    pool: &DbPool,
+    node_id: i64,
+    accepted_ids: &[i64],
+    rejected_ids: &[i64],
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    for &suggestion_id in accepted_ids {
+        let Some(name) = sqlx::query_scalar::<_, String>(
+            "SELECT name FROM tag_suggestions \
+             WHERE suggestion_id = ? AND node_id = ? AND status = ?",
+        )
+        .bind(suggestion_id)
+        .bind(node_id)
+        .bind(TagSuggestionStatus::Pending)
+        .fetch_optional(&mut *tx)
+        .await?
+        else {
+            continue;
+        };
+        sqlx::query("INSERT OR IGNORE INTO tags (name) VALUES (?)")
+            .bind(&name)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(
+            "INSERT OR IGNORE INTO node_tags (node_id, tag_id, source) \
+             SELECT ?, tag_id, ? FROM tags WHERE name = ?",
+        )
+        .bind(node_id)
+        .bind(TagSource::Manual)
+        .bind(&name)
+        .execute(&mut *tx)
+        .await?;
+        set_tag_suggestion_status(&mut tx, suggestion_id, TagSuggestionStatus::Accepted).await?;
+    }
+    for &suggestion_id in rejected_ids {
+        sqlx::query(
+            "UPDATE tag_suggestions SET status = ?, decided_at = CURRENT_TIMESTAMP \
+             WHERE suggestion_id = ? AND node_id = ? AND status = ?",
+        )
+        .bind(TagSuggestionStatus::Rejected)
+        .bind(suggestion_id)
+        .bind(node_id)
+        .bind(TagSuggestionStatus::Pending)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await
+}
+
+async fn set_tag_suggestion_status(
+    tx: &mut Transaction<'_, Sqlite>,
+    suggestion_id: i64,
+    status: TagSuggestionStatus,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE tag_suggestions SET status = ?, decided_at = CURRENT_TIMESTAMP \
+         WHERE suggestion_id = ?",
+    )
+    .bind(status)
+    .bind(suggestion_id)
+    .execute(tx.as_mut())
+    .await?;
+    Ok(())
+}
+
+/// 来源规则按名称打标签：改名 / 合并后改写为新名称，已有相同规则时删除重复项
+async fn retarget_source_tag_rules(
+    tx: &mut Transaction<'_, Sqlite>,
+    old_name: &str,
+    new_name: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE OR IGNORE source_tag_rules SET tag_name = ? WHERE tag_name = ? COLLATE NOCASE",
+    )
+    .bind(new_name)
+    .bind(old_name)
+    .execute(tx.as_mut())
+    .await?;
+    if !old_name.eq_ignore_ascii_case(new_name) {
+        sqlx::query("DELETE FROM source_tag_rules WHERE tag_name = ? COLLATE NOCASE")
+            .bind(old_name)
+            .execute(tx.as_mut())
+            .await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,85 @@
+//! 看板：任务在各状态列中的顺序由 board_position 决定
+
+use super::nodes::NODE_FIELDS;
+use super::{DbPool, NodeRecord, TaskStatus};
+
+/// 列内排序：未排过序的任务排在末尾，按创建时间保持稳定
+const BOARD_ORDER: &str = "board_position IS NULL, board_position, created_at, node_id";
+
+/// 看板上的全部任务，按状态、列内位置排序
+pub async fn list_board_tasks(pool: &DbPool) -> Result<Vec<NodeRecord>, sqlx::Error> {
+    let sql = format!(
+        "SELECT {NODE_FIELDS} FROM nodes \
+         WHERE node_type = 'task' AND is_deleted = 0 AND task_status IS NOT NULL \
+         ORDER BY task_status, {BOARD_ORDER}"
+    );
+    sqlx::query_as::<_, NodeRecord>(&sql).fetch_all(pool).await
+}
+
+/// 把任务移到 status 列的 position 处，目标列重新编号；状态与顺序在同一事务内更新
+///
+/// done_date 与 mark_task_* 保持一致：回到 todo 时清空，进入 done/cancelled 时记为当前时间。
+/// 返回移动前的状态，任务不存在时返回 None
+pub async fn move_task_on_board(
+    pool: &DbPool,
+    node_id: i64,
+    status: TaskStatus,
+    position: usize,
+) -> Result<Option<TaskStatus>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    // 先写入再读取，拿到写锁后其他拖拽只能排队，不会基于旧顺序覆盖
+    let detached = sqlx::query(
+        "UPDATE nodes SET board_position = NULL \
+         WHERE node_id = ? AND node_type = 'task' AND is_deleted = 0",
+    )
+    .bind(node_id)
+    .execute(&mut *tx)
+    .await?;
+    if detached.rows_affected() == 0 {
+        return Ok(None);
+    }
+
+    let previous: Option<TaskStatus> =
+        sqlx::query_scalar("SELECT task_status FROM nodes WHERE node_id = ?")
+            .bind(node_id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+    if previous != Some(status) {
+        sqlx::query(
+            "UPDATE nodes SET task_status = ?, \
+             done_date = CASE WHEN ? = 'todo' THEN NULL ELSE CURRENT_TIMESTAMP END, \
+             updated_at = CURRENT_TIMESTAMP WHERE node_id = ?",
+        )
+        .bind(status)
+        .bind(status)
+        .bind(node_id)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    let sql = format!(
+        "SELECT node_id FROM nodes \
+         WHERE node_type = 'task' AND is_deleted = 0 AND task_status = ? AND node_id != ? \
+         ORDER BY {BOARD_ORDER}"
+    );
+    let mut column: Vec<i64> = sqlx::query_scalar(&sql)
+        .bind(status)
+        .bind(node_id)
+        .fetch_all(&mut *tx)
+        .await?;
+    column.insert(position.min(column.len()), node_id);
+
+    for (index, id) in column.iter().enumerate() {
+        sqlx::query("UPDATE nodes SET board_position = ? WHERE node_id = ?")
+            .bind(index as i64)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    tx.commit().await?;
+    tracing::debug!(node_id, status = ?status, position, "Task moved on board");
+    Ok(previous)
+}
@@ -0,0 +1,108 @@
+use super::{DbPool, TaskReminderRecord, TaskReminderRow};
+
+const REMINDER_FIELDS: &str =
+    "node_id, minutes_before, snoozed_until, reminded_due_date, updated_at";
+
+pub async fn get_task_reminder(
+    pool: &DbPool,
+    node_id: i64,
+) -> Result<Option<TaskReminderRecord>, sqlx::Error> {
+    let sql = format!("SELECT {REMINDER_FIELDS} FROM task_reminders WHERE node_id = ?");
+    sqlx::query_as::<_, TaskReminderRecord>(&sql)
+        .bind(node_id)
+        .fetch_optional(pool)
+        .await
+}
+
+/// 全部任务提醒设置
+pub async fn list_task_reminders(pool: &DbPool) -> Result<Vec<TaskReminderRecord>, sqlx::Error> {
+    let sql = format!("SELECT {REMINDER_FIELDS} FROM task_reminders");
+    sqlx::query_as::<_, TaskReminderRecord>(&sql)
+        .fetch_all(pool)
+        .await
+}
+
+/// 设置提醒；修改提前量会清除稍后提醒并重新提醒当前截止时间
+pub async fn upsert_task_reminder(
+    pool: &DbPool,
+    node_id: i64,
+    minutes_before: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO task_reminders (node_id, minutes_before) VALUES (?, ?) \
+         ON CONFLICT(node_id) DO UPDATE SET minutes_before = excluded.minutes_before, \
+         snoozed_until = NULL, reminded_due_date = NULL, updated_at = CURRENT_TIMESTAMP",
+    )
+    .bind(node_id)
+    .bind(minutes_before)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn delete_task_reminder(pool: &DbPool, node_id: i64) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM task_reminders WHERE node_id = ?")
+        .bind(node_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// 稍后提醒；任务没有提醒设置时返回 false
+pub async fn set_task_reminder_snooze(
+    pool: &DbPool,
+    node_id: i64,
+    until: &str,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "UPDATE task_reminders SET snoozed_until = ?, updated_at = CURRENT_TIMESTAMP \
+         WHERE node_id = ?",
+    )
+    .bind(until)
+    .bind(node_id)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// 到期的任务提醒：稍后提醒时间已到，或 `due_date - minutes_before` 落在 (since, now] 内且该截止时间尚未提醒
+///
+/// 时间均为本地时间 `YYYY-MM-DD HH:MM:SS`；只提醒仍待办的任务
+pub async fn list_due_task_reminders(
+    pool: &DbPool,
+    since: &str,
+    now: &str,
+) -> Result<Vec<TaskReminderRow>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT r.node_id, n.title, n.due_date FROM task_reminders r \
+         JOIN nodes n ON n.node_id = r.node_id \
+         WHERE n.node_type = 'task' AND n.task_status = 'todo' AND n.is_deleted = 0 \
+           AND n.due_date IS NOT NULL \
+           AND ((r.snoozed_until IS NOT NULL AND r.snoozed_until <= ?) \
+             OR (r.snoozed_until IS NULL AND r.reminded_due_date IS NOT n.due_date \
+               AND datetime(n.due_date, '-' || r.minutes_before || ' minutes') > ? \
+               AND datetime(n.due_date, '-' || r.minutes_before || ' minutes') <= ?)) \
+         ORDER BY n.due_date",
+    )
+    .bind(now)
+    .bind(since)
+    .bind(now)
+    .fetch_all(pool)
+    .await
+}
+
+/// 记录已提醒的截止时间并清除稍后提醒
+pub async fn mark_task_reminder_sent(
+    pool: &DbPool,
+    node_id: i64,
+    due_date: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE task_reminders SET reminded_due_date = ?, snoozed_until = NULL WHERE node_id = ?",
+    )
+    .bind(due_date)
+    .bind(node_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
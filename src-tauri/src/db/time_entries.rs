@@ -0,0 +1,166 @@
+use super::{
+    DbPool, NewTimeEntry, OpenTimeEntryRow, TimeEntryRecord, TimeEntrySource, TimeReportPeriod,
+    TimeReportTask,
+};
+
+const TIME_ENTRY_FIELDS: &str =
+    "entry_id, task_id, source, started_at, ended_at, duration_seconds, created_at";
+
+/// 把 started_at 换算成本地日期
+const LOCAL_DATE: &str = "date(started_at, 'localtime')";
+
+/// started_at 所在周的周一（本地时间）
+const LOCAL_WEEK: &str = "date(started_at, 'localtime', 'weekday 0', '-6 days')";
+
+pub async fn insert_time_entry(
+    pool: &DbPool,
+    params: NewTimeEntry<'_>,
+) -> Result<i64, sqlx::Error> {
+    let result = sqlx::query(
+        "INSERT INTO time_entries (task_id, source, started_at, ended_at, duration_seconds) \
+         VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(params.task_id)
+    .bind(params.source)
+    .bind(params.started_at)
+    .bind(params.ended_at)
+    .bind(params.duration_seconds)
+    .execute(pool)
+    .await?;
+
+    Ok(result.last_insert_rowid())
+}
+
+/// 写入一条未结束的记录（计时器开始时），返回 entry_id
+pub async fn insert_open_time_entry(
+    pool: &DbPool,
+    task_id: i64,
+    source: TimeEntrySource,
+    started_at: &str,
+) -> Result<i64, sqlx::Error> {
+    let result =
+        sqlx::query("INSERT INTO time_entries (task_id, source, started_at) VALUES (?, ?, ?)")
+            .bind(task_id)
+            .bind(source)
+            .bind(started_at)
+            .execute(pool)
+            .await?;
+
+    Ok(result.last_insert_rowid())
+}
+
+/// 补上未结束记录的结束时间与时长
+pub async fn close_time_entry(
+    pool: &DbPool,
+    entry_id: i64,
+    ended_at: &str,
+    duration_seconds: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE time_entries SET ended_at = ?, duration_seconds = ? \
+         WHERE entry_id = ? AND ended_at IS NULL",
+    )
+    .bind(ended_at)
+    .bind(duration_seconds)
+    .bind(entry_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 删除未结束的记录（计时过短时不保留）
+pub async fn delete_open_time_entry(pool: &DbPool, entry_id: i64) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM time_entries WHERE entry_id = ? AND ended_at IS NULL")
+        .bind(entry_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// 未结束的记录，最近开始的在前
+pub async fn list_open_time_entries(pool: &DbPool) -> Result<Vec<OpenTimeEntryRow>, sqlx::Error> {
+    sqlx::query_as::<_, OpenTimeEntryRow>(
+        "SELECT entry_id, task_id, started_at FROM time_entries \
+         WHERE ended_at IS NULL ORDER BY started_at DESC, entry_id DESC",
+    )
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn get_time_entry_by_id(
+    pool: &DbPool,
+    entry_id: i64,
+) -> Result<TimeEntryRecord, sqlx::Error> {
+    let sql = format!("SELECT {TIME_ENTRY_FIELDS} FROM time_entries WHERE entry_id = ?");
+    sqlx::query_as::<_, TimeEntryRecord>(&sql)
+        .bind(entry_id)
+        .fetch_one(pool)
+        .await
+}
+
+pub async fn list_time_entries(
+    pool: &DbPool,
+    task_id: Option<i64>,
+    limit: i64,
+) -> Result<Vec<TimeEntryRecord>, sqlx::Error> {
+    let filter = if task_id.is_some() {
+        "WHERE ended_at IS NOT NULL AND task_id = ?"
+    } else {
+        "WHERE ended_at IS NOT NULL"
+    };
+    let sql = format!(
+        "SELECT {TIME_ENTRY_FIELDS} FROM time_entries {filter} ORDER BY started_at DESC LIMIT ?"
+    );
+    let mut query = sqlx::query_as::<_, TimeEntryRecord>(&sql);
+    if let Some(task_id) = task_id {
+        query = query.bind(task_id);
+    }
+    query.bind(limit).fetch_all(pool).await
+}
+
+/// 按本地日期（by_week 时按周一）汇总时长，日期范围为 YYYY-MM-DD 闭区间
+pub async fn list_time_period_totals(
+    pool: &DbPool,
+    start_date: &str,
+    end_date: &str,
+    by_week: bool,
+) -> Result<Vec<TimeReportPeriod>, sqlx::Error> {
+    let period = if by_week { LOCAL_WEEK } else { LOCAL_DATE };
+    let sql = format!(
+        "SELECT {period} AS period, \
+                COALESCE(SUM(duration_seconds), 0) AS seconds, \
+                COUNT(*) AS entry_count \
+         FROM time_entries \
+         WHERE ended_at IS NOT NULL AND {LOCAL_DATE} BETWEEN date(?) AND date(?) \
+         GROUP BY period \
+         ORDER BY period ASC"
+    );
+    sqlx::query_as::<_, TimeReportPeriod>(&sql)
+        .bind(start_date)
+        .bind(end_date)
+        .fetch_all(pool)
+        .await
+}
+
+/// 按任务汇总时长（未关联任务的记录不计入）
+pub async fn list_time_task_totals(
+    pool: &DbPool,
+    start_date: &str,
+    end_date: &str,
+) -> Result<Vec<TimeReportTask>, sqlx::Error> {
+    sqlx::query_as::<_, TimeReportTask>(
+        "SELECT t.task_id AS task_id, n.title AS title, \
+                COALESCE(SUM(t.duration_seconds), 0) AS seconds, \
+                COUNT(*) AS entry_count \
+         FROM time_entries t \
+         INNER JOIN nodes n ON n.node_id = t.task_id \
+         WHERE t.ended_at IS NOT NULL \
+           AND date(t.started_at, 'localtime') BETWEEN date(?) AND date(?) \
+         GROUP BY t.task_id \
+         ORDER BY seconds DESC",
+    )
+    .bind(start_date)
+    .bind(end_date)
+    .fetch_all(pool)
+    .await
+}
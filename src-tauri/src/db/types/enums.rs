@@ -10,6 +10,7 @@ pub enum NodeType {
     Topic,
     Task,
     Resource,
+    Habit,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Type, Serialize, Deserialize)]
@@ -39,6 +40,16 @@ pub enum ResourceSubtype {
     Pdf,
     Url,
     Epub,
+    /// Office 文档（docx / pptx / xlsx / odt / odp / ods）
+    Office,
+    /// 音频（内容为转写文本）
+    Audio,
+    /// Jupyter 笔记本（.ipynb）
+    Notebook,
+    /// 邮件（.eml / .msg，附件拆为独立资源）
+    Email,
+    /// 压缩包（.zip / .tar / .tar.gz，内容为条目清单，可展开为子资源）
+    Archive,
     Other,
 }
 
@@ -83,7 +94,25 @@ pub enum ReviewStatus {
 #[serde(rename_all = "lowercase")]
 pub enum EdgeRelationType {
     Contains,
+    #[sqlx(rename = "related_to")]
+    #[serde(rename = "related_to")]
     RelatedTo,
+    /// 派生产物 (source) 指向其来源节点 (target)
+    #[sqlx(rename = "derived_from")]
+    #[serde(rename = "derived_from")]
+    DerivedFrom,
+}
+
+/// 图谱中的关系：edges 表中的三种边，加上正文引用（node_links）
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Type, Serialize, Deserialize)]
+#[sqlx(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum GraphRelationType {
+    Contains,
+    RelatedTo,
+    DerivedFrom,
+    /// 正文中的 uuid 或 [[标题]] 引用 (source) 指向被引用节点 (target)
+    References,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Type, Serialize, Deserialize)]
@@ -102,3 +131,224 @@ pub enum BindingType {
     Implicit,
 }
 
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Type, Serialize, Deserialize)]
+#[sqlx(rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum TagSource {
+    Manual,
+    Rule,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Type, Serialize, Deserialize)]
+#[sqlx(rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum TagSuggestionStatus {
+    Pending,
+    Accepted,
+    Rejected,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Type, Serialize, Deserialize)]
+#[sqlx(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum SourceRuleField {
+    Domain,
+    App,
+    WindowTitle,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Type, Serialize, Deserialize)]
+#[sqlx(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    PipelineError,
+    ReviewNeeded,
+    Backup,
+    System,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Type, Serialize, Deserialize)]
+#[sqlx(rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum FocusSessionStatus {
+    Active,
+    Completed,
+    Interrupted,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Type, Serialize, Deserialize)]
+#[sqlx(rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum TimeEntrySource {
+    Pomodoro,
+    Manual,
+    /// 任务计时器（start_task_timer / stop_task_timer）
+    Timer,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Type, Serialize, Deserialize)]
+#[sqlx(rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum HabitFrequency {
+    Daily,
+    Weekly,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Type, Serialize, Deserialize)]
+#[sqlx(rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum CitationSource {
+    Llm,
+    Manual,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Type, Serialize, Deserialize)]
+#[sqlx(rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum IntegrityCheckTrigger {
+    Manual,
+    Scheduled,
+}
+
+/// 导出格式
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Type, Serialize, Deserialize)]
+#[sqlx(rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// 数据库快照与资源文件，可完整恢复
+    #[default]
+    Archive,
+    /// 每个节点一个 Markdown 文件，附带资源文件
+    Markdown,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Type, Serialize, Deserialize)]
+#[sqlx(rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum ExportTrigger {
+    Manual,
+    Scheduled,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Type, Serialize, Deserialize)]
+#[sqlx(rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum ExportRunStatus {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Type, Serialize, Deserialize)]
+#[sqlx(rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum IntegrityIssueKind {
+    Missing,
+    Mismatch,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Type, Serialize, Deserialize)]
+#[sqlx(rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum ContentRevisionReason {
+    Edit,
+    Restore,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Type, Serialize, Deserialize)]
+#[sqlx(rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum FileReferenceStatus {
+    Online,
+    Offline,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Type, Serialize, Deserialize)]
+#[sqlx(rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum DataMigrationStatus {
+    Pending,
+    Running,
+    Done,
+    Failed,
+}
+
+/// Scheduling priority of a pipeline job
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Type, Serialize, Deserialize)]
+#[sqlx(rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum JobPriority {
+    /// Re-indexing, imports, sync and other bulk work
+    Bulk,
+    /// Resources added in the background (watched folders, webhooks, batch capture)
+    Normal,
+    /// Resources the user just captured, edited or opened
+    Interactive,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Type, Serialize, Deserialize)]
+#[sqlx(rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum PipelineJobStatus {
+    Queued,
+    Processing,
+}
+
+/// LLM 调用用途，用于用量统计
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Type, Serialize, Deserialize)]
+#[sqlx(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum UsageOperation {
+    Chat,
+    Summary,
+    ChatSummary,
+    Classify,
+    Citation,
+    TagSuggestion,
+    Transcribe,
+    GoalReview,
+    Restructure,
+    Title,
+    Triage,
+}
+
+/// 正文引用其他节点的方式
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Type, Serialize, Deserialize)]
+#[sqlx(rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum NodeLinkKind {
+    Uuid,
+    Title,
+}
+
+/// 待确认的 AI 修改类型
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Type, Serialize, Deserialize)]
+#[sqlx(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum PendingChangeKind {
+    RenameTopic,
+    MoveResource,
+    SetDueDate,
+    CreateTask,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Type, Serialize, Deserialize)]
+#[sqlx(rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum PendingChangeStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+/// 保留规则的处理方式
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Type, Serialize, Deserialize)]
+#[sqlx(rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum RetentionAction {
+    /// 归档：不参与语义搜索与对话 RAG
+    Archive,
+    /// 移入回收站
+    Delete,
+}
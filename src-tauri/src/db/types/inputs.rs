@@ -1,6 +1,6 @@
 //! 数据库输入类型定义（用于插入/创建）
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use sqlx::types::Json;
 
@@ -82,6 +82,15 @@ pub struct NewMessageAttachment {
     pub node_id: i64,
 }
 
+/// 新建回答引用来源输入
+pub struct NewMessageSource<'a> {
+    pub node_id: i64,
+    pub chunk_index: i32,
+    pub rank: i64,
+    pub score: f64,
+    pub snippet: &'a str,
+}
+
 /// Embedding 结果块
 #[derive(Debug, Deserialize)]
 pub struct EmbedChunkResult {
@@ -94,3 +103,167 @@ pub struct EmbedChunkResult {
     pub embedding_model: String,
     pub chunk_meta: Option<Value>,
 }
+
+/// 新建通知输入
+pub struct NewNotification<'a> {
+    pub kind: NotificationKind,
+    pub title: &'a str,
+    pub body: Option<&'a str>,
+    pub node_id: Option<i64>,
+    pub action: Option<&'a str>,
+}
+
+/// 新建时间记录输入
+pub struct NewTimeEntry<'a> {
+    pub task_id: Option<i64>,
+    pub source: TimeEntrySource,
+    pub started_at: &'a str,
+    pub ended_at: &'a str,
+    pub duration_seconds: i64,
+}
+
+/// 写入文献元数据输入
+pub struct NewCitation<'a> {
+    pub node_id: i64,
+    pub title: &'a str,
+    pub authors: &'a [String],
+    pub year: Option<i64>,
+    pub venue: Option<&'a str>,
+    pub doi: Option<&'a str>,
+    pub source: CitationSource,
+}
+
+/// 写入日历事件映射输入
+pub struct NewCalendarEvent<'a> {
+    pub subscription_id: Option<i64>,
+    pub uid: &'a str,
+    pub node_id: i64,
+    pub starts_at: &'a str,
+    pub ends_at: Option<&'a str>,
+    pub location: Option<&'a str>,
+    pub recurrence_rule: Option<&'a str>,
+    pub reminder_at: Option<&'a str>,
+}
+
+/// 写入 GitHub issue / PR 映射输入
+pub struct NewGithubItem<'a> {
+    pub repo_id: i64,
+    pub number: i64,
+    pub is_pull_request: bool,
+    pub task_node_id: i64,
+    pub body_node_id: Option<i64>,
+    pub synced_title: &'a str,
+    pub github_state: &'a str,
+    pub body_hash: Option<&'a str>,
+    pub github_updated_at: &'a str,
+}
+
+/// 新建完整性问题输入
+pub struct NewIntegrityIssue<'a> {
+    pub check_id: i64,
+    pub node_id: i64,
+    pub kind: IntegrityIssueKind,
+    pub file_path: &'a str,
+    pub expected_hash: Option<&'a str>,
+    pub actual_hash: Option<&'a str>,
+    pub reparsed: bool,
+}
+
+/// 完整性校验结束时的统计
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IntegrityCheckCounts {
+    pub checked: i64,
+    pub missing: i64,
+    pub mismatched: i64,
+    pub skipped: i64,
+    pub reparsed: i64,
+}
+
+/// 导出结束时的统计
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExportRunCounts {
+    pub node_count: i64,
+    pub file_count: i64,
+    pub size_bytes: i64,
+}
+
+/// 新建 LLM 用量记录输入
+pub struct NewUsageLog<'a> {
+    pub provider: &'a str,
+    pub model: &'a str,
+    pub operation: UsageOperation,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub reasoning_tokens: i64,
+    pub total_tokens: i64,
+    pub session_id: Option<i64>,
+}
+
+/// 引用文件的大小与修改时间（Unix 秒），用于判断文件是否变化
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileStat {
+    pub file_size: i64,
+    pub modified_at: Option<i64>,
+}
+
+/// 主题层级调整操作（plan_topic_restructure 生成，用户确认后批量应用）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum TopicOperation {
+    Rename {
+        topic_id: i64,
+        new_title: String,
+    },
+    /// new_parent_id 为空表示移到根层级
+    Move {
+        topic_id: i64,
+        new_parent_id: Option<i64>,
+    },
+    /// topic_id 下的内容并入 target_topic_id，随后软删除 topic_id
+    Merge {
+        topic_id: i64,
+        target_topic_id: i64,
+    },
+}
+
+/// 节点引用：node_id 或节点 uuid
+///
+/// node_id 在导出 / 导入后会变化，外部集成与深链接应使用 uuid。
+/// 反序列化时数字视为 node_id，字符串视为 uuid。
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum NodeRef {
+    Id(i64),
+    Uuid(String),
+}
+
+/// 模型提出的待确认修改
+#[derive(Debug, Clone)]
+pub struct NewPendingChange<'a> {
+    pub kind: PendingChangeKind,
+    /// create_task 提出时尚无节点
+    pub node_id: Option<i64>,
+    pub old_value: Option<&'a str>,
+    pub new_value: Option<&'a str>,
+    pub reason: Option<&'a str>,
+    pub session_id: Option<i64>,
+}
+
+/// 保留规则定义（新建、更新与预览共用）
+#[derive(Debug, Clone, Deserialize)]
+pub struct RetentionRuleInput {
+    pub name: String,
+    /// 只处理该主题（含子主题）下的资源
+    pub topic_id: Option<i64>,
+    pub resource_subtype: Option<ResourceSubtype>,
+    pub max_age_days: i64,
+    pub action: RetentionAction,
+    /// 跳过有关联的资源
+    pub skip_linked: bool,
+    #[serde(default = "default_rule_enabled")]
+    pub is_enabled: bool,
+}
+
+fn default_rule_enabled() -> bool {
+    true
+}
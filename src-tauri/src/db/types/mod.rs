@@ -19,19 +19,28 @@ pub static MIGRATOR: sqlx::migrate::Migrator = sqlx::migrate!();
 
 // 导出枚举类型
 pub use enums::{
-    BindingType, EdgeRelationType, EmbeddingType, NodeType, ResourceEmbeddingStatus,
-    ResourceProcessingStage, ResourceSubtype, ReviewStatus, SessionType, TaskPriority, TaskStatus,
+    BindingType, CitationSource, ContentRevisionReason, DataMigrationStatus, EdgeRelationType, EmbeddingType, ExportFormat, ExportRunStatus, ExportTrigger, FileReferenceStatus, FocusSessionStatus, GraphRelationType,
+    HabitFrequency, IntegrityCheckTrigger, IntegrityIssueKind, JobPriority, NodeLinkKind, NodeType, NotificationKind, PendingChangeKind, PendingChangeStatus, PipelineJobStatus, ResourceEmbeddingStatus, ResourceProcessingStage,
+    ResourceSubtype, ReviewStatus, SessionType, SourceRuleField, TagSource, TagSuggestionStatus, TaskPriority,
+    TaskStatus, TimeEntrySource, UsageOperation,
 };
 
 // 导出记录类型
 pub use records::{
-    ChatMessageRecord, ChatSessionRecord, EdgeRecord, NodeRecord, NodeRevisionLogRecord,
-    SourceMeta,
+    AssetFileRow, BacklinkRecord, CalendarEventRecord, CalendarReminderRow, CalendarSubscriptionRecord, ChatMessageRecord,
+    ChatSessionRecord, CitationRecord, ContentRevisionRecord, DataMigrationRecord, EdgeRecord, EdgeTransferRecord, EmbeddingErrorCount, EmbeddingStateCount, ExportRunRecord, FileReferenceRecord, FocusDailyStat, FocusSessionRecord, GraphEdgeRecord, GraphNodeRecord,
+    FocusTaskStat, GithubItemRecord, GithubRepoRecord, GoalReviewRecord, HabitRecord,
+    IntegrityCheckRecord, IntegrityIssueRecord,
+    NodeFullTextRow, NodeRecord, NodeRevisionLogRecord, NodeTitleRow, NotificationRecord, OpenTimeEntryRow, PendingChangeRecord, PipelineJobRecord, PipelineStageCount,
+    ReadingStateRecord, RecentNodeRow, ResourceSourceRow, ReviewTaskRow, SourceMeta,
+    SourceTagRuleRecord, SubtaskLinkRow, TagRecord, TaskReminderRecord, TaskReminderRow, TagSuggestionRecord, TagUsageRecord, TimeEntryRecord, TimeReportPeriod, TimeReportTask, TopicActivityRow, TopicLinkRow, TopicTreeRow,
+    UnreviewedClassificationRecord, UsageSummaryRow, WatchedFolderRecord, WorkspaceItemRow, WorkspaceRecord,
 };
 
 // 导出输入类型
 pub use inputs::{
-    EmbedChunkResult, NewChatMessage, NewChatSession, NewEdge, NewMessageAttachment, NewNode,
-    NewNodeRevisionLog,
+    EmbedChunkResult, ExportRunCounts, FileStat, IntegrityCheckCounts, NewCalendarEvent, NewChatMessage, NewChatSession, NewCitation, NewEdge,
+    NewGithubItem, NewIntegrityIssue, NewMessageAttachment, NewNode, NewNodeRevisionLog, NewNotification, NewPendingChange,
+    NewTimeEntry, NewUsageLog, NodeRef, TopicOperation,
 };
 
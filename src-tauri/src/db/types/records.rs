@@ -13,6 +13,18 @@ pub struct SourceMeta {
     pub window_title: Option<String>,
     pub process_name: Option<String>,
     pub captured_at: Option<String>,
+    /// 前台应用的可执行文件路径（用于区分同名应用）
+    #[serde(default)]
+    pub process_path: Option<String>,
+    /// 邮件发件人（`名字 <地址>`）
+    #[serde(default)]
+    pub sender: Option<String>,
+    /// 邮件发送时间（RFC 3339）
+    #[serde(default)]
+    pub sent_at: Option<String>,
+    /// 自动生成标题前的原始名称（截取的正文开头）
+    #[serde(default)]
+    pub auto_title: Option<String>,
 }
 
 /// 节点记录
@@ -47,6 +59,29 @@ pub struct NodeRecord {
     pub updated_at: Option<String>,
     pub is_deleted: bool,
     pub deleted_at: Option<String>,
+    /// 重复任务规则（类 RRULE），仅重复任务有值
+    pub recurrence_rule: Option<String>,
+    /// 不参与语义搜索与对话 RAG
+    pub exclude_from_search: bool,
+    /// 按正文字数估算的阅读分钟数
+    pub read_time_minutes: Option<i64>,
+    /// 正文词数（中日韩文字按字计）
+    pub word_count: Option<i64>,
+    pub char_count: Option<i64>,
+    /// 估算的正文 token 数
+    pub token_count: Option<i64>,
+    /// 检测到的正文语言（ISO 639-1）
+    pub content_language: Option<String>,
+    /// AI 处理连续失败次数
+    pub processing_attempts: i64,
+    /// 主题是否接受 AI 自动归类（仅主题有意义）
+    pub auto_classify_allowed: bool,
+    /// 阅读进度 0~1（来自 node_reading_state）
+    pub reading_progress: Option<f64>,
+    /// 标题、摘要、正文、备注每次修改加一，用于检测并发编辑
+    pub version: i64,
+    /// 归档时间；归档的节点不参与语义搜索与对话 RAG
+    pub archived_at: Option<String>,
 }
 
 /// 边记录
@@ -64,6 +99,56 @@ pub struct EdgeRecord {
     pub deleted_at: Option<String>,
 }
 
+/// 边的导入 / 导出行（JSONL 每行一条），节点以 uuid 引用
+#[derive(Debug, FromRow, Serialize, Deserialize)]
+pub struct EdgeTransferRecord {
+    pub source: String,
+    pub target: String,
+    pub relation_type: String,
+    pub confidence_score: Option<f64>,
+    /// 缺省为 true
+    pub is_manual: Option<bool>,
+}
+
+/// 图谱节点概要
+#[derive(Debug, FromRow, Serialize)]
+pub struct GraphNodeRecord {
+    pub node_id: i64,
+    pub node_type: NodeType,
+    pub title: String,
+    pub resource_subtype: Option<ResourceSubtype>,
+    pub task_status: Option<TaskStatus>,
+    /// 未删除的边与正文引用数（所有关系类型）
+    pub degree: i64,
+    /// 距根节点的步数，全图模式为 None
+    pub depth: Option<i64>,
+}
+
+/// 图谱中的一条关系；正文引用（references）没有 edge_id
+#[derive(Debug, FromRow, Serialize)]
+pub struct GraphEdgeRecord {
+    pub edge_id: Option<i64>,
+    pub source_node_id: i64,
+    pub target_node_id: i64,
+    pub relation_type: GraphRelationType,
+    pub confidence_score: Option<f64>,
+    pub is_manual: bool,
+    pub created_at: Option<String>,
+}
+
+/// 待审核资源及 AI 建议的主题（置信度最高的自动 contains 边）
+#[derive(Debug, FromRow, Serialize)]
+pub struct UnreviewedClassificationRecord {
+    pub node_id: i64,
+    pub title: String,
+    pub summary: Option<String>,
+    pub resource_subtype: Option<ResourceSubtype>,
+    pub created_at: Option<String>,
+    pub topic_id: Option<i64>,
+    pub topic_title: Option<String>,
+    pub confidence_score: Option<f64>,
+}
+
 /// 节点修订日志记录
 #[derive(Debug, FromRow, Serialize)]
 pub struct NodeRevisionLogRecord {
@@ -79,6 +164,19 @@ pub struct NodeRevisionLogRecord {
     pub created_at: Option<String>,
 }
 
+/// 资源正文版本（列表只含开头预览，恢复时再读取全文）
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct ContentRevisionRecord {
+    pub revision_id: i64,
+    pub node_id: i64,
+    pub preview: String,
+    /// 全文字符数
+    pub content_length: i64,
+    pub content_hash: Option<String>,
+    pub reason: ContentRevisionReason,
+    pub created_at: String,
+}
+
 /// 聊天会话记录
 #[derive(Debug, FromRow, Serialize)]
 pub struct ChatSessionRecord {
@@ -92,6 +190,26 @@ pub struct ChatSessionRecord {
     pub is_deleted: bool,
     pub deleted_at: Option<String>,
     pub user_id: i64,
+    pub rag_settings: Option<Json<SessionRagSettings>>,
+    /// summary 已概括到的最后一条消息；为空时 summary 只是会话简介
+    pub compacted_through_message_id: Option<i64>,
+}
+
+/// 会话级 RAG 检索设置，未设置的字段沿用全局检索配置
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionRagSettings {
+    pub top_k: Option<u32>,
+    /// 低于该分数的片段不放入上下文（分数含义取决于结果合并方式）
+    pub score_threshold: Option<f64>,
+    /// 检索摘要还是正文分块，默认正文
+    pub embedding_type: Option<EmbeddingType>,
+}
+
+impl SessionRagSettings {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
 }
 
 /// 聊天消息记录
@@ -110,3 +228,578 @@ pub struct ChatMessageRecord {
     pub created_at: Option<String>,
 }
 
+/// 回答引用来源（注入 prompt 的 RAG 片段）
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct MessageSourceRecord {
+    pub message_id: i64,
+    pub node_id: i64,
+    pub node_title: String,
+    pub chunk_index: i32,
+    /// prompt 中的编号，对应回答里的 [n]
+    pub rank: i64,
+    pub score: f64,
+    pub snippet: String,
+}
+
+/// 标签记录
+#[derive(Debug, FromRow, Serialize)]
+pub struct TagRecord {
+    pub tag_id: i64,
+    pub name: String,
+    pub created_at: Option<String>,
+}
+
+/// 标签及其使用次数（未删除节点）
+#[derive(Debug, FromRow, Serialize)]
+pub struct TagUsageRecord {
+    pub tag_id: i64,
+    pub name: String,
+    pub created_at: Option<String>,
+    pub node_count: i64,
+}
+
+/// AI 建议标签记录
+#[derive(Debug, FromRow, Serialize)]
+pub struct TagSuggestionRecord {
+    pub suggestion_id: i64,
+    pub node_id: i64,
+    pub name: String,
+    pub status: TagSuggestionStatus,
+    pub created_at: Option<String>,
+}
+
+/// 来源自动打标签规则记录
+#[derive(Debug, FromRow, Serialize)]
+pub struct SourceTagRuleRecord {
+    pub rule_id: i64,
+    pub match_field: SourceRuleField,
+    pub pattern: String,
+    pub tag_name: String,
+    pub is_enabled: bool,
+    pub created_at: Option<String>,
+}
+
+/// 资源来源行（用于来源统计）
+#[derive(Debug, FromRow)]
+pub struct ResourceSourceRow {
+    pub source_meta: Option<Json<SourceMeta>>,
+    pub created_at: Option<String>,
+}
+
+/// 应用内通知记录
+#[derive(Debug, FromRow, Serialize, Clone)]
+pub struct NotificationRecord {
+    pub notification_id: i64,
+    pub kind: NotificationKind,
+    pub title: String,
+    pub body: Option<String>,
+    pub node_id: Option<i64>,
+    pub action: Option<String>,
+    pub is_read: bool,
+    pub created_at: Option<String>,
+    pub read_at: Option<String>,
+}
+
+/// 专注会话记录
+#[derive(Debug, FromRow, Serialize, Clone)]
+pub struct FocusSessionRecord {
+    pub session_id: i64,
+    pub task_id: Option<i64>,
+    pub planned_seconds: i64,
+    pub actual_seconds: i64,
+    pub status: FocusSessionStatus,
+    pub do_not_disturb: bool,
+    pub started_at: Option<String>,
+    pub ended_at: Option<String>,
+}
+
+/// 按日期聚合的专注时长
+#[derive(Debug, FromRow, Serialize)]
+pub struct FocusDailyStat {
+    pub date: String,
+    pub focus_seconds: i64,
+    pub session_count: i64,
+}
+
+/// 按任务聚合的专注时长
+#[derive(Debug, FromRow, Serialize)]
+pub struct FocusTaskStat {
+    pub task_id: i64,
+    pub title: String,
+    pub focus_seconds: i64,
+    pub session_count: i64,
+}
+
+/// 时间记录
+#[derive(Debug, FromRow, Serialize)]
+pub struct TimeEntryRecord {
+    pub entry_id: i64,
+    pub task_id: Option<i64>,
+    pub source: TimeEntrySource,
+    pub started_at: String,
+    pub ended_at: String,
+    pub duration_seconds: i64,
+    pub created_at: Option<String>,
+}
+
+/// 未结束的计时记录
+#[derive(Debug, FromRow)]
+pub struct OpenTimeEntryRow {
+    pub entry_id: i64,
+    pub task_id: Option<i64>,
+    pub started_at: String,
+}
+
+/// 按日 / 按周汇总的记录时长（period 为日期或该周周一，YYYY-MM-DD）
+#[derive(Debug, FromRow, Serialize)]
+pub struct TimeReportPeriod {
+    pub period: String,
+    pub seconds: i64,
+    pub entry_count: i64,
+}
+
+/// 按任务汇总的记录时长
+#[derive(Debug, FromRow, Serialize)]
+pub struct TimeReportTask {
+    pub task_id: i64,
+    pub title: String,
+    pub seconds: i64,
+    pub entry_count: i64,
+}
+
+/// 习惯（节点 + 打卡计划）
+#[derive(Debug, FromRow, Serialize)]
+pub struct HabitRecord {
+    pub node_id: i64,
+    pub title: String,
+    pub summary: Option<String>,
+    pub frequency: HabitFrequency,
+    pub target_count: i64,
+    pub created_at: Option<String>,
+}
+
+/// 月度回顾记录
+#[derive(Debug, FromRow, Serialize)]
+pub struct GoalReviewRecord {
+    pub review_id: i64,
+    pub period: String,
+    pub node_id: Option<i64>,
+    pub created_at: Option<String>,
+}
+
+/// 节点标题行（快速搜索用，不含正文）
+#[derive(Debug, FromRow, Serialize, Clone)]
+pub struct NodeTitleRow {
+    pub node_id: i64,
+    pub node_type: NodeType,
+    pub title: String,
+    pub summary: Option<String>,
+}
+
+/// 全文搜索命中行（snippet 中命中词以 `[` `]` 标出）
+#[derive(Debug, FromRow, Serialize, Clone)]
+pub struct NodeFullTextRow {
+    pub node_id: i64,
+    pub node_type: NodeType,
+    pub title: String,
+    pub summary: Option<String>,
+    pub snippet: Option<String>,
+    /// bm25 得分，越小越相关；仅短词匹配时为 0
+    pub rank: f64,
+}
+
+/// 主题树中的单个主题（item_count 为直接包含的非主题节点数）
+#[derive(Debug, FromRow, Serialize, Clone)]
+pub struct TopicTreeRow {
+    pub node_id: i64,
+    pub title: String,
+    pub summary: Option<String>,
+    pub item_count: i64,
+}
+
+/// 主题之间的 contains 关系
+#[derive(Debug, FromRow, Serialize, Clone, Copy)]
+pub struct TopicLinkRow {
+    pub parent_id: i64,
+    pub child_id: i64,
+}
+
+/// 父任务与子任务之间的 contains 关系
+#[derive(Debug, FromRow, Serialize, Clone, Copy)]
+pub struct SubtaskLinkRow {
+    pub parent_id: i64,
+    pub child_id: i64,
+    pub child_status: Option<TaskStatus>,
+}
+
+/// 回顾周期内单个主题的活动统计
+#[derive(Debug, FromRow, Serialize, Clone)]
+pub struct TopicActivityRow {
+    pub topic_id: i64,
+    pub title: String,
+    pub summary: Option<String>,
+    pub resources_added: i64,
+    pub tasks_completed: i64,
+    pub tasks_open: i64,
+    /// 未完成且周期内没有任何更新的任务数
+    pub tasks_stalled: i64,
+}
+
+/// 回顾周期内完成或停滞的任务
+#[derive(Debug, FromRow)]
+pub struct ReviewTaskRow {
+    pub topic_id: i64,
+    pub title: String,
+    pub task_status: TaskStatus,
+}
+
+/// 文献引用元数据
+#[derive(Debug, FromRow, Serialize)]
+pub struct CitationRecord {
+    pub node_id: i64,
+    pub title: String,
+    pub authors: Json<Vec<String>>,
+    pub year: Option<i64>,
+    pub venue: Option<String>,
+    pub doi: Option<String>,
+    pub source: CitationSource,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+/// 节点的最近打开记录与阅读进度
+#[derive(Debug, FromRow, Serialize)]
+pub struct ReadingStateRecord {
+    pub node_id: i64,
+    pub last_opened_at: String,
+    pub open_count: i64,
+    /// 滚动比例 0~1
+    pub scroll_position: Option<f64>,
+    pub page: Option<i64>,
+    pub position_updated_at: Option<String>,
+    /// 阅读进度 0~1
+    pub progress: Option<f64>,
+    pub progress_updated_at: Option<String>,
+}
+
+/// 最近打开的节点（含继续阅读位置）
+#[derive(Debug, FromRow, Serialize)]
+pub struct RecentNodeRow {
+    pub node_id: i64,
+    pub node_type: NodeType,
+    pub title: String,
+    pub summary: Option<String>,
+    pub resource_subtype: Option<ResourceSubtype>,
+    pub last_opened_at: String,
+    pub open_count: i64,
+    pub scroll_position: Option<f64>,
+    pub page: Option<i64>,
+}
+
+/// 工作区快照
+#[derive(Debug, FromRow, Serialize)]
+pub struct WorkspaceRecord {
+    pub workspace_id: i64,
+    pub name: String,
+    /// 前端布局 JSON，后端不解析
+    pub layout_json: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    pub last_restored_at: Option<String>,
+}
+
+/// 工作区中打开的条目（节点或聊天会话二选一）
+#[derive(Debug, FromRow)]
+pub struct WorkspaceItemRow {
+    pub node_id: Option<i64>,
+    pub session_id: Option<i64>,
+}
+
+/// 日历订阅源
+#[derive(Debug, FromRow, Serialize)]
+pub struct CalendarSubscriptionRecord {
+    pub subscription_id: i64,
+    pub name: String,
+    pub url: String,
+    pub poll_interval_minutes: i64,
+    pub is_enabled: bool,
+    pub last_synced_at: Option<String>,
+    pub last_error: Option<String>,
+    pub created_at: String,
+}
+
+/// 日历事件与任务节点的映射
+#[derive(Debug, FromRow)]
+pub struct CalendarEventRecord {
+    pub event_id: i64,
+    pub subscription_id: Option<i64>,
+    pub uid: String,
+    pub node_id: i64,
+    pub starts_at: String,
+    pub ends_at: Option<String>,
+    pub location: Option<String>,
+    pub recurrence_rule: Option<String>,
+    pub reminder_at: Option<String>,
+    pub reminder_sent_at: Option<String>,
+}
+
+/// 到期待发送的日历提醒
+#[derive(Debug, FromRow)]
+pub struct CalendarReminderRow {
+    pub event_id: i64,
+    pub node_id: i64,
+    pub title: String,
+    pub starts_at: String,
+    pub location: Option<String>,
+}
+
+/// 任务提醒设置
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct TaskReminderRecord {
+    pub node_id: i64,
+    pub minutes_before: i64,
+    pub snoozed_until: Option<String>,
+    pub reminded_due_date: Option<String>,
+    pub updated_at: String,
+}
+
+/// 到期待发送的任务提醒
+#[derive(Debug, Clone, FromRow)]
+pub struct TaskReminderRow {
+    pub node_id: i64,
+    pub title: String,
+    pub due_date: String,
+}
+
+/// 同步的 GitHub 仓库（不含令牌）
+#[derive(Debug, FromRow, Serialize)]
+pub struct GithubRepoRecord {
+    pub repo_id: i64,
+    pub owner: String,
+    pub name: String,
+    pub topic_id: i64,
+    pub has_token: bool,
+    pub poll_interval_minutes: i64,
+    pub is_enabled: bool,
+    pub since_cursor: Option<String>,
+    pub last_synced_at: Option<String>,
+    pub last_error: Option<String>,
+    pub created_at: String,
+}
+
+/// 监听文件夹
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct WatchedFolderRecord {
+    pub folder_id: i64,
+    pub path: String,
+    pub move_to: Option<String>,
+    pub is_enabled: bool,
+    pub imported_count: i64,
+    pub last_imported_at: Option<String>,
+    pub last_error: Option<String>,
+    pub created_at: String,
+}
+
+/// 资源文件完整性校验
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct IntegrityCheckRecord {
+    pub check_id: i64,
+    pub trigger: IntegrityCheckTrigger,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+    pub checked_count: i64,
+    pub missing_count: i64,
+    pub mismatched_count: i64,
+    pub skipped_count: i64,
+    pub reparsed_count: i64,
+    pub error: Option<String>,
+}
+
+/// 一次导出的记录
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct ExportRunRecord {
+    pub run_id: i64,
+    pub trigger: ExportTrigger,
+    pub format: ExportFormat,
+    /// 增量导出的起点；全量导出为 None
+    pub since: Option<String>,
+    pub status: ExportRunStatus,
+    pub file_path: Option<String>,
+    pub node_count: i64,
+    pub file_count: i64,
+    pub size_bytes: i64,
+    pub error: Option<String>,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+}
+
+/// 引用了某节点的节点（反向链接）
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct BacklinkRecord {
+    pub node_id: i64,
+    pub uuid: String,
+    pub title: String,
+    pub node_type: NodeType,
+    pub resource_subtype: Option<ResourceSubtype>,
+    pub link_kind: NodeLinkKind,
+    pub linked_at: Option<String>,
+}
+
+/// 待确认的 AI 修改（含节点标题；移动资源时含目标主题标题）
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct PendingChangeRecord {
+    pub change_id: i64,
+    pub kind: PendingChangeKind,
+    /// create_task 批准前为空
+    pub node_id: Option<i64>,
+    pub node_title: Option<String>,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub target_title: Option<String>,
+    pub reason: Option<String>,
+    pub session_id: Option<i64>,
+    pub status: PendingChangeStatus,
+    pub created_at: Option<String>,
+    pub decided_at: Option<String>,
+}
+
+/// 按天 / 模型 / 用途汇总的 LLM 用量
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct UsageSummaryRow {
+    /// 本地日期 YYYY-MM-DD
+    pub day: String,
+    pub provider: String,
+    pub model: String,
+    pub operation: UsageOperation,
+    pub calls: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub reasoning_tokens: i64,
+    pub total_tokens: i64,
+}
+
+/// 校验发现的问题（含资源标题）
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct IntegrityIssueRecord {
+    pub issue_id: i64,
+    pub check_id: i64,
+    pub node_id: i64,
+    pub title: String,
+    pub kind: IntegrityIssueKind,
+    pub file_path: String,
+    pub expected_hash: Option<String>,
+    pub actual_hash: Option<String>,
+    pub reparsed: bool,
+}
+
+/// 待校验的文件资源
+#[derive(Debug, Clone, FromRow)]
+pub struct AssetFileRow {
+    pub node_id: i64,
+    pub file_path: String,
+    pub file_hash: Option<String>,
+    pub resource_subtype: Option<ResourceSubtype>,
+}
+
+/// 外部文件引用（含资源标题与文件路径）
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct FileReferenceRecord {
+    pub node_id: i64,
+    pub title: String,
+    pub file_path: String,
+    pub status: FileReferenceStatus,
+    pub file_size: Option<i64>,
+    pub modified_at: Option<i64>,
+    pub checked_at: String,
+    pub last_error: Option<String>,
+}
+
+/// issue / PR 与节点的映射
+#[derive(Debug, FromRow)]
+pub struct GithubItemRecord {
+    pub item_id: i64,
+    pub repo_id: i64,
+    pub number: i64,
+    pub is_pull_request: bool,
+    pub task_node_id: i64,
+    pub body_node_id: Option<i64>,
+    pub synced_title: String,
+    pub github_state: String,
+    pub body_hash: Option<String>,
+    pub github_updated_at: String,
+}
+
+/// 按向量化状态与处理阶段分组的资源数
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct EmbeddingStateCount {
+    pub embedding_status: ResourceEmbeddingStatus,
+    pub processing_stage: ResourceProcessingStage,
+    pub count: i64,
+}
+
+/// 相同向量化错误信息的资源数
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct EmbeddingErrorCount {
+    pub error: String,
+    pub count: i64,
+}
+
+/// 持久化的 AI 队列任务
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct PipelineJobRecord {
+    pub node_id: i64,
+    pub priority: JobPriority,
+    pub status: PipelineJobStatus,
+    pub enqueued_at: Option<String>,
+    pub started_at: Option<String>,
+    /// 等待重试时的重试时间
+    pub retry_after: Option<String>,
+}
+
+/// 按任务状态与资源处理阶段分组的 AI 队列任务数
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct PipelineStageCount {
+    pub status: PipelineJobStatus,
+    pub processing_stage: ResourceProcessingStage,
+    pub count: i64,
+}
+
+/// 后台数据迁移的进度
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct DataMigrationRecord {
+    pub name: String,
+    pub status: DataMigrationStatus,
+    pub cursor: i64,
+    pub processed: i64,
+    pub total: Option<i64>,
+    pub last_error: Option<String>,
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+/// 资源保留规则
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct RetentionRuleRecord {
+    pub rule_id: i64,
+    pub name: String,
+    pub topic_id: Option<i64>,
+    pub resource_subtype: Option<ResourceSubtype>,
+    pub max_age_days: i64,
+    pub action: RetentionAction,
+    pub skip_linked: bool,
+    pub is_enabled: bool,
+    pub last_run_at: Option<String>,
+    pub last_affected: i64,
+    pub created_at: String,
+}
+
+/// 保留规则命中的资源
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct RetentionCandidateRow {
+    pub node_id: i64,
+    pub title: String,
+    pub resource_subtype: Option<ResourceSubtype>,
+    pub created_at: Option<String>,
+    pub archived_at: Option<String>,
+}
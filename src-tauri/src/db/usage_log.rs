@@ -0,0 +1,44 @@
+//! LLM 用量记录
+
+use super::{DbPool, NewUsageLog, UsageSummaryRow};
+
+pub async fn insert_usage_log(pool: &DbPool, params: NewUsageLog<'_>) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO usage_log (provider, model, operation, input_tokens, output_tokens, \
+             reasoning_tokens, total_tokens, session_id) \
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(params.provider)
+    .bind(params.model)
+    .bind(params.operation)
+    .bind(params.input_tokens)
+    .bind(params.output_tokens)
+    .bind(params.reasoning_tokens)
+    .bind(params.total_tokens)
+    .bind(params.session_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 按本地日期、模型与用途汇总 [from, to] 之间的用量，最近的日期在前
+pub async fn summarize_usage(
+    pool: &DbPool,
+    from: &str,
+    to: &str,
+) -> Result<Vec<UsageSummaryRow>, sqlx::Error> {
+    sqlx::query_as::<_, UsageSummaryRow>(
+        "SELECT date(created_at, 'localtime') AS day, provider, model, operation, \
+             COUNT(*) AS calls, SUM(input_tokens) AS input_tokens, \
+             SUM(output_tokens) AS output_tokens, SUM(reasoning_tokens) AS reasoning_tokens, \
+             SUM(total_tokens) AS total_tokens \
+         FROM usage_log \
+         WHERE date(created_at, 'localtime') BETWEEN date(?) AND date(?) \
+         GROUP BY day, provider, model, operation \
+         ORDER BY day DESC, total_tokens DESC",
+    )
+    .bind(from)
+    .bind(to)
+    .fetch_all(pool)
+    .await
+}
@@ -0,0 +1,78 @@
+use super::{DbPool, WatchedFolderRecord};
+
+const FOLDER_FIELDS: &str = "folder_id, path, move_to, is_enabled, imported_count, \
+     last_imported_at, last_error, created_at";
+
+/// 按路径写入；已存在时更新 move_to 并重新启用
+pub async fn upsert_watched_folder(
+    pool: &DbPool,
+    path: &str,
+    move_to: Option<&str>,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar(
+        "INSERT INTO watched_folders (path, move_to) VALUES (?, ?) \
+         ON CONFLICT(path) DO UPDATE SET move_to = excluded.move_to, is_enabled = 1 \
+         RETURNING folder_id",
+    )
+    .bind(path)
+    .bind(move_to)
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn get_watched_folder(
+    pool: &DbPool,
+    folder_id: i64,
+) -> Result<Option<WatchedFolderRecord>, sqlx::Error> {
+    let sql = format!("SELECT {FOLDER_FIELDS} FROM watched_folders WHERE folder_id = ?");
+    sqlx::query_as::<_, WatchedFolderRecord>(&sql)
+        .bind(folder_id)
+        .fetch_optional(pool)
+        .await
+}
+
+pub async fn list_watched_folders(pool: &DbPool) -> Result<Vec<WatchedFolderRecord>, sqlx::Error> {
+    let sql = format!("SELECT {FOLDER_FIELDS} FROM watched_folders ORDER BY path");
+    sqlx::query_as::<_, WatchedFolderRecord>(&sql)
+        .fetch_all(pool)
+        .await
+}
+
+/// 删除监听文件夹；已导入的资源保留
+pub async fn delete_watched_folder(pool: &DbPool, folder_id: i64) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM watched_folders WHERE folder_id = ?")
+        .bind(folder_id)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// 记录一次成功导入
+pub async fn record_watched_folder_import(
+    pool: &DbPool,
+    folder_id: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE watched_folders SET imported_count = imported_count + 1, \
+             last_imported_at = CURRENT_TIMESTAMP, last_error = NULL \
+         WHERE folder_id = ?",
+    )
+    .bind(folder_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 记录监听或导入错误
+pub async fn set_watched_folder_error(
+    pool: &DbPool,
+    folder_id: i64,
+    error: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE watched_folders SET last_error = ? WHERE folder_id = ?")
+        .bind(error)
+        .bind(folder_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
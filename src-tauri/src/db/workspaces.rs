@@ -0,0 +1,106 @@
+use super::{DbPool, WorkspaceItemRow, WorkspaceRecord};
+
+const WORKSPACE_FIELDS: &str =
+    "workspace_id, name, layout_json, created_at, updated_at, last_restored_at";
+
+/// 保存工作区快照；同名工作区会被覆盖（打开的条目整体替换）
+pub async fn save_workspace(
+    pool: &DbPool,
+    name: &str,
+    node_ids: &[i64],
+    session_ids: &[i64],
+    layout_json: Option<&str>,
+) -> Result<i64, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let workspace_id: i64 = sqlx::query_scalar(
+        "INSERT INTO workspaces (name, layout_json) VALUES (?, ?) \
+         ON CONFLICT(name) DO UPDATE SET \
+             layout_json = excluded.layout_json, updated_at = CURRENT_TIMESTAMP \
+         RETURNING workspace_id",
+    )
+    .bind(name)
+    .bind(layout_json)
+    .fetch_one(tx.as_mut())
+    .await?;
+
+    sqlx::query("DELETE FROM workspace_items WHERE workspace_id = ?")
+        .bind(workspace_id)
+        .execute(tx.as_mut())
+        .await?;
+
+    let items = node_ids
+        .iter()
+        .map(|&id| (Some(id), None))
+        .chain(session_ids.iter().map(|&id| (None, Some(id))));
+    for (position, (node_id, session_id)) in items.enumerate() {
+        sqlx::query(
+            "INSERT INTO workspace_items (workspace_id, node_id, session_id, position) \
+             VALUES (?, ?, ?, ?)",
+        )
+        .bind(workspace_id)
+        .bind(node_id)
+        .bind(session_id)
+        .bind(position as i64)
+        .execute(tx.as_mut())
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(workspace_id)
+}
+
+pub async fn get_workspace(
+    pool: &DbPool,
+    workspace_id: i64,
+) -> Result<Option<WorkspaceRecord>, sqlx::Error> {
+    let sql = format!("SELECT {WORKSPACE_FIELDS} FROM workspaces WHERE workspace_id = ?");
+    sqlx::query_as::<_, WorkspaceRecord>(&sql)
+        .bind(workspace_id)
+        .fetch_optional(pool)
+        .await
+}
+
+/// 列出全部工作区，最近保存的在前
+pub async fn list_workspaces(pool: &DbPool) -> Result<Vec<WorkspaceRecord>, sqlx::Error> {
+    let sql = format!("SELECT {WORKSPACE_FIELDS} FROM workspaces ORDER BY updated_at DESC");
+    sqlx::query_as::<_, WorkspaceRecord>(&sql)
+        .fetch_all(pool)
+        .await
+}
+
+/// 工作区中仍然存在的条目（已删除的节点/会话跳过），按打开顺序
+pub async fn list_workspace_items(
+    pool: &DbPool,
+    workspace_id: i64,
+) -> Result<Vec<WorkspaceItemRow>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT w.node_id, w.session_id FROM workspace_items w \
+         LEFT JOIN nodes n ON n.node_id = w.node_id \
+         LEFT JOIN chat_sessions s ON s.session_id = w.session_id \
+         WHERE w.workspace_id = ? \
+         AND (n.is_deleted = 0 OR s.is_deleted = 0) \
+         ORDER BY w.position",
+    )
+    .bind(workspace_id)
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn mark_workspace_restored(pool: &DbPool, workspace_id: i64) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE workspaces SET last_restored_at = CURRENT_TIMESTAMP WHERE workspace_id = ?",
+    )
+    .bind(workspace_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn delete_workspace(pool: &DbPool, workspace_id: i64) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM workspaces WHERE workspace_id = ?")
+        .bind(workspace_id)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
@@ -6,12 +6,15 @@
 use serde::Serialize;
 use thiserror::Error;
 
+use crate::i18n::{current_language, Language, MessageCode};
+use crate::services::{inc_counter, MetricCounter};
+
 /// 应用级统一错误类型
 #[derive(Debug, Error)]
 pub enum AppError {
-    /// 数据库错误
+    /// 数据库错误（From 实现见下方，转换时计入数据库错误指标）
     #[error("数据库错误: {0}")]
-    Database(#[from] sqlx::Error),
+    Database(#[source] sqlx::Error),
 
     /// 文件操作错误
     #[error("文件操作错误: {0}")]
@@ -37,6 +40,13 @@ pub enum AppError {
     #[error("{0}")]
     Business(String),
 
+    /// 带错误码的消息，按当前界面语言渲染
+    #[error("{}", .code.render(current_language(), .args))]
+    Coded {
+        code: MessageCode,
+        args: Vec<(&'static str, String)>,
+    },
+
     // [error("...")] (实现 Display trait)
     // 语法: #[error("Database error: {0}")]
     // 含义: 自动为这个错误类型实现 std::fmt::Display trait。
@@ -44,19 +54,28 @@ pub enum AppError {
     // 效果: 当你调用 .to_string() 或 println!("{}", e) 时，Rust 会先调用底层 sqlx::Error 的 Display 实现，然后把它填入 {0}，
     // 最终输出类似 "Database error: connection refused" 的字符串。
 
-    // 因为有了 #[from]，这里的一个问号 (?) 自动完成了两件事：
+    // 因为有了 From<sqlx::Error>（#[from] 或下方手写的实现），这里的一个问号 (?) 自动完成了两件事：
     // 1. 捕获 sqlx::Error
     // 2. 调用 AppError::from(sqlx_error) 把它包装成 AppError::Database(sqlx_error)
     // 3. 提前返回 Err
     // let user = sqlx::query_as!(User, "SELECT * FROM users").fetch_one(&pool).await?;
 
-    // 如果没有 #[from]，就不能直接用 ?，必须写成：
+    // 如果没有 From 实现，就不能直接用 ?，必须写成：
     // let user = sqlx::query_as!(...)
     // .fetch_one(&pool)
     // .await
     // .map_err(|e| AppError::Database(e))?;
 }
 
+// ========== From 实现：sqlx::Error ==========
+
+impl From<sqlx::Error> for AppError {
+    fn from(err: sqlx::Error) -> Self {
+        inc_counter(MetricCounter::DbErrors);
+        AppError::Database(err)
+    }
+}
+
 // ========== From 实现：String 和 &str ==========
 
 impl From<String> for AppError {
@@ -94,28 +113,70 @@ impl Serialize for AppError {
         use serde::ser::SerializeStruct;
 
         // 1. 开始构建一个“结构体”（即 JSON 对象）
-        // "AppError" 是名字（通常用于 XML 等，JSON 中忽略），2 是预计字段数量
-        let mut state = serializer.serialize_struct("AppError", 2)?;
-
-        // 根据枚举变体写入 "type" 字段
-        let error_type = match self {
-            AppError::Database(_) => "database",
-            AppError::Io(_) => "io",
-            AppError::Validation(_) => "validation",
-            AppError::NotFound { .. } => "not_found",
-            AppError::Config(_) => "config",
-            AppError::AiService(_) => "ai_service",
-            AppError::Business(_) => "business",
-        };
-        state.serialize_field("type", error_type)?;
+        // "AppError" 是名字（通常用于 XML 等，JSON 中忽略），3 是预计字段数量
+        let mut state = serializer.serialize_struct("AppError", 3)?;
 
-        // 写入 "message" 字段
-        state.serialize_field("message", &self.to_string())?;
+        // "type": 通用错误类型；"code": 具体错误码（无具体错误码时与 type 相同）
+        let code = self.code();
+        state.serialize_field("type", code.category().as_str())?;
+        state.serialize_field("code", code.as_str())?;
+
+        // 写入按当前界面语言渲染的 "message" 字段
+        state.serialize_field("message", &self.localized_message(current_language()))?;
 
         state.end()
     }
 }
 
+impl AppError {
+    /// 带错误码的错误
+    pub fn coded(code: MessageCode) -> Self {
+        AppError::Coded {
+            code,
+            args: Vec::new(),
+        }
+    }
+
+    /// 带错误码和一个模板参数的错误
+    pub fn coded_with(code: MessageCode, name: &'static str, value: impl ToString) -> Self {
+        AppError::Coded {
+            code,
+            args: vec![(name, value.to_string())],
+        }
+    }
+
+    /// 错误码
+    pub fn code(&self) -> MessageCode {
+        match self {
+            AppError::Database(_) => MessageCode::Database,
+            AppError::Io(_) => MessageCode::Io,
+            AppError::Validation(_) => MessageCode::Validation,
+            AppError::NotFound { .. } => MessageCode::NotFound,
+            AppError::Config(_) => MessageCode::Config,
+            AppError::AiService(_) => MessageCode::AiService,
+            AppError::Business(_) => MessageCode::Business,
+            AppError::Coded { code, .. } => *code,
+        }
+    }
+
+    /// 按指定语言渲染错误消息
+    pub fn localized_message(&self, language: Language) -> String {
+        let args = match self {
+            AppError::Database(err) => vec![("detail", err.to_string())],
+            AppError::Io(err) => vec![("detail", err.to_string())],
+            AppError::Validation(detail)
+            | AppError::Config(detail)
+            | AppError::AiService(detail)
+            | AppError::Business(detail) => vec![("detail", detail.clone())],
+            AppError::NotFound { entity, id } => {
+                vec![("entity", entity.to_string()), ("id", id.to_string())]
+            }
+            AppError::Coded { args, .. } => args.clone(),
+        };
+        self.code().render(language, &args)
+    }
+}
+
 /// 应用级 Result 类型别名
 pub type AppResult<T> = Result<T, AppError>;
 
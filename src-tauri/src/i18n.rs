@@ -0,0 +1,571 @@
+//! 后端消息目录（中 / 英）
+//!
+//! 错误按 [`MessageCode`] 编号，前端依据 `code` 做判断，`message` 按当前界面语言渲染。
+//! 当前语言保存在 AI 配置文件中，启动时通过 [`set_current_language`] 载入。
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+/// 界面语言
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    #[default]
+    Zh,
+    En,
+}
+
+static CURRENT_LANGUAGE: AtomicU8 = AtomicU8::new(0);
+
+pub fn current_language() -> Language {
+    match CURRENT_LANGUAGE.load(Ordering::Relaxed) {
+        1 => Language::En,
+        _ => Language::Zh,
+    }
+}
+
+pub fn set_current_language(language: Language) {
+    let value = match language {
+        Language::Zh => 0,
+        Language::En => 1,
+    };
+    CURRENT_LANGUAGE.store(value, Ordering::Relaxed);
+}
+
+/// 消息编号（序列化为 snake_case，作为返回给前端的错误码）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageCode {
+    // 通用错误类型，{detail} 为原始信息
+    Database,
+    Io,
+    Validation,
+    NotFound,
+    Config,
+    AiService,
+    Business,
+
+    // 输入校验
+    TitleEmpty,
+    FieldEmpty,
+    FieldNotPositive,
+    InvalidNodeId,
+    UnknownNodeUuid,
+    InvalidDate,
+    InvalidRecurrenceRule,
+    UnknownRelationType,
+    UnknownReviewStatus,
+    UnknownSourceRuleField,
+    WeeklyTargetOutOfRange,
+    ScrollPositionOutOfRange,
+    PageOutOfRange,
+    ReadingProgressOutOfRange,
+    FocusDurationOutOfRange,
+    PomodoroDurationOutOfRange,
+    PomodoroCyclesOutOfRange,
+    ScoreThresholdInvalid,
+    CaptureInputMissing,
+    CostEstimateInputMissing,
+    BibtexScopeInvalid,
+    PdfOnly,
+    InvalidLayoutJson,
+    InvalidEnex,
+    InvalidIcs,
+    InvalidArchive,
+    InvalidCalendarUrl,
+    InvalidGithubRepo,
+    InvalidWebhookPort,
+    InvalidWatchFolder,
+    InvalidAssetsDir,
+    InvalidExportFolder,
+    InvalidFileReference,
+    InvalidShortcut,
+    InvalidSearchPattern,
+    InvalidRetentionRule,
+    InvalidSnapshotArchive,
+    InvalidImportFolder,
+    InvalidReviewPeriod,
+    ModelDirNotAbsolute,
+    ImageNotFound,
+    AssetsDirNotAbsolute,
+    AssetsDirUnchanged,
+    AssetsDirNested,
+    FileReferenceNotAbsolute,
+    FileReferenceNotFile,
+    ImportFolderNotDir,
+    ImportFolderEmpty,
+
+    // 节点与主题
+    InvalidConversionTarget,
+    NotAResource,
+    NotAnArchive,
+    ResourceFileMissing,
+    ContainsCreateCycle,
+    ContainsMigrateCycle,
+    TopicTitleEmpty,
+    TopicMergeIntoSelf,
+    TopicMoveCycle,
+    TopicMergeCycle,
+    TopicTitleTaken,
+    SubtaskCycle,
+    IntegrityCheckRunning,
+    ExportRunning,
+    EmbeddingReindexRunning,
+    ModelDownloadRunning,
+    VectorMaintenanceRunning,
+    JobCancelled,
+    NodeEditConflict,
+    TagNameTaken,
+    TagMergeIntoSelf,
+    ShortcutTaken,
+    PendingChangeDecided,
+    InvalidPendingChangePayload,
+    WebhookTokenEmpty,
+    WebhookListenFailed,
+    AssetsRelocationRunning,
+    AssetsFileConflict,
+    FocusSessionRunning,
+    TimerTaskOnly,
+    NoReviewActivity,
+    SearchFailed,
+
+    // AI 服务
+    AiNotReady,
+
+    // 启动
+    DataFormatTooNew,
+}
+
+impl MessageCode {
+    /// 错误码字符串，与序列化结果一致
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MessageCode::Database => "database",
+            MessageCode::Io => "io",
+            MessageCode::Validation => "validation",
+            MessageCode::NotFound => "not_found",
+            MessageCode::Config => "config",
+            MessageCode::AiService => "ai_service",
+            MessageCode::Business => "business",
+            MessageCode::TitleEmpty => "title_empty",
+            MessageCode::FieldEmpty => "field_empty",
+            MessageCode::FieldNotPositive => "field_not_positive",
+            MessageCode::InvalidNodeId => "invalid_node_id",
+            MessageCode::UnknownNodeUuid => "unknown_node_uuid",
+            MessageCode::InvalidDate => "invalid_date",
+            MessageCode::InvalidRecurrenceRule => "invalid_recurrence_rule",
+            MessageCode::UnknownRelationType => "unknown_relation_type",
+            MessageCode::UnknownReviewStatus => "unknown_review_status",
+            MessageCode::UnknownSourceRuleField => "unknown_source_rule_field",
+            MessageCode::WeeklyTargetOutOfRange => "weekly_target_out_of_range",
+            MessageCode::ScrollPositionOutOfRange => "scroll_position_out_of_range",
+            MessageCode::PageOutOfRange => "page_out_of_range",
+            MessageCode::ReadingProgressOutOfRange => "reading_progress_out_of_range",
+            MessageCode::FocusDurationOutOfRange => "focus_duration_out_of_range",
+            MessageCode::PomodoroDurationOutOfRange => "pomodoro_duration_out_of_range",
+            MessageCode::PomodoroCyclesOutOfRange => "pomodoro_cycles_out_of_range",
+            MessageCode::ScoreThresholdInvalid => "score_threshold_invalid",
+            MessageCode::CaptureInputMissing => "capture_input_missing",
+            MessageCode::CostEstimateInputMissing => "cost_estimate_input_missing",
+            MessageCode::BibtexScopeInvalid => "bibtex_scope_invalid",
+            MessageCode::PdfOnly => "pdf_only",
+            MessageCode::InvalidLayoutJson => "invalid_layout_json",
+            MessageCode::InvalidEnex => "invalid_enex",
+            MessageCode::InvalidIcs => "invalid_ics",
+            MessageCode::InvalidArchive => "invalid_archive",
+            MessageCode::InvalidCalendarUrl => "invalid_calendar_url",
+            MessageCode::InvalidGithubRepo => "invalid_github_repo",
+            MessageCode::InvalidWebhookPort => "invalid_webhook_port",
+            MessageCode::InvalidWatchFolder => "invalid_watch_folder",
+            MessageCode::InvalidAssetsDir => "invalid_assets_dir",
+            MessageCode::InvalidExportFolder => "invalid_export_folder",
+            MessageCode::InvalidFileReference => "invalid_file_reference",
+            MessageCode::InvalidShortcut => "invalid_shortcut",
+            MessageCode::InvalidSearchPattern => "invalid_search_pattern",
+            MessageCode::InvalidRetentionRule => "invalid_retention_rule",
+            MessageCode::InvalidSnapshotArchive => "invalid_snapshot_archive",
+            MessageCode::InvalidImportFolder => "invalid_import_folder",
+            MessageCode::InvalidReviewPeriod => "invalid_review_period",
+            MessageCode::ModelDirNotAbsolute => "model_dir_not_absolute",
+            MessageCode::ImageNotFound => "image_not_found",
+            MessageCode::AssetsDirNotAbsolute => "assets_dir_not_absolute",
+            MessageCode::AssetsDirUnchanged => "assets_dir_unchanged",
+            MessageCode::AssetsDirNested => "assets_dir_nested",
+            MessageCode::FileReferenceNotAbsolute => "file_reference_not_absolute",
+            MessageCode::FileReferenceNotFile => "file_reference_not_file",
+            MessageCode::ImportFolderNotDir => "import_folder_not_dir",
+            MessageCode::ImportFolderEmpty => "import_folder_empty",
+            MessageCode::InvalidConversionTarget => "invalid_conversion_target",
+            MessageCode::NotAResource => "not_a_resource",
+            MessageCode::NotAnArchive => "not_an_archive",
+            MessageCode::ResourceFileMissing => "resource_file_missing",
+            MessageCode::ContainsCreateCycle => "contains_create_cycle",
+            MessageCode::ContainsMigrateCycle => "contains_migrate_cycle",
+            MessageCode::TopicTitleEmpty => "topic_title_empty",
+            MessageCode::TopicMergeIntoSelf => "topic_merge_into_self",
+            MessageCode::TopicMoveCycle => "topic_move_cycle",
+            MessageCode::TopicMergeCycle => "topic_merge_cycle",
+            MessageCode::TopicTitleTaken => "topic_title_taken",
+            MessageCode::SubtaskCycle => "subtask_cycle",
+            MessageCode::IntegrityCheckRunning => "integrity_check_running",
+            MessageCode::ExportRunning => "export_running",
+            MessageCode::EmbeddingReindexRunning => "embedding_reindex_running",
+            MessageCode::ModelDownloadRunning => "model_download_running",
+            MessageCode::VectorMaintenanceRunning => "vector_maintenance_running",
+            MessageCode::JobCancelled => "job_cancelled",
+            MessageCode::NodeEditConflict => "node_edit_conflict",
+            MessageCode::TagNameTaken => "tag_name_taken",
+            MessageCode::ShortcutTaken => "shortcut_taken",
+            MessageCode::PendingChangeDecided => "pending_change_decided",
+            MessageCode::InvalidPendingChangePayload => "invalid_pending_change_payload",
+            MessageCode::WebhookTokenEmpty => "webhook_token_empty",
+            MessageCode::WebhookListenFailed => "webhook_listen_failed",
+            MessageCode::AssetsRelocationRunning => "assets_relocation_running",
+            MessageCode::AssetsFileConflict => "assets_file_conflict",
+            MessageCode::FocusSessionRunning => "focus_session_running",
+            MessageCode::TimerTaskOnly => "timer_task_only",
+            MessageCode::NoReviewActivity => "no_review_activity",
+            MessageCode::SearchFailed => "search_failed",
+            MessageCode::TagMergeIntoSelf => "tag_merge_into_self",
+            MessageCode::AiNotReady => "ai_not_ready",
+            MessageCode::DataFormatTooNew => "data_format_too_new",
+        }
+    }
+
+    /// 所属的通用错误类型（序列化错误时的 `type` 字段）
+    pub fn category(self) -> MessageCode {
+        match self {
+            MessageCode::Database
+            | MessageCode::Io
+            | MessageCode::Validation
+            | MessageCode::NotFound
+            | MessageCode::Config
+            | MessageCode::AiService
+            | MessageCode::Business => self,
+            MessageCode::InvalidConversionTarget
+            | MessageCode::TopicTitleEmpty
+            | MessageCode::TopicMergeIntoSelf
+            | MessageCode::TagMergeIntoSelf => MessageCode::Validation,
+            MessageCode::NotAResource
+            | MessageCode::ContainsCreateCycle
+            | MessageCode::ContainsMigrateCycle
+            | MessageCode::TopicMoveCycle
+            | MessageCode::TopicMergeCycle
+            | MessageCode::SubtaskCycle
+            | MessageCode::IntegrityCheckRunning
+            | MessageCode::ExportRunning
+            | MessageCode::EmbeddingReindexRunning
+            | MessageCode::ModelDownloadRunning
+            | MessageCode::VectorMaintenanceRunning
+            | MessageCode::JobCancelled
+            | MessageCode::NodeEditConflict
+            | MessageCode::TagNameTaken
+            | MessageCode::ShortcutTaken
+            | MessageCode::PendingChangeDecided
+            | MessageCode::FocusSessionRunning
+            | MessageCode::TimerTaskOnly
+            | MessageCode::NoReviewActivity
+            | MessageCode::SearchFailed
+            | MessageCode::InvalidPendingChangePayload
+            | MessageCode::WebhookTokenEmpty
+            | MessageCode::WebhookListenFailed
+            | MessageCode::AssetsRelocationRunning
+            | MessageCode::AssetsFileConflict
+            | MessageCode::NotAnArchive
+            | MessageCode::ResourceFileMissing
+            | MessageCode::TopicTitleTaken => MessageCode::Business,
+            MessageCode::AiNotReady => MessageCode::AiService,
+            MessageCode::UnknownNodeUuid => MessageCode::NotFound,
+            MessageCode::DataFormatTooNew => MessageCode::Database,
+            _ => MessageCode::Validation,
+        }
+    }
+
+    /// 消息模板，`{name}` 为占位参数
+    pub fn template(self, language: Language) -> &'static str {
+        use Language::{En, Zh};
+        match (self, language) {
+            (MessageCode::Database, Zh) => "数据库错误: {detail}",
+            (MessageCode::Database, En) => "Database error: {detail}",
+            (MessageCode::Io, Zh) => "文件操作错误: {detail}",
+            (MessageCode::Io, En) => "File operation failed: {detail}",
+            (MessageCode::Validation, Zh) => "验证失败: {detail}",
+            (MessageCode::Validation, En) => "Invalid input: {detail}",
+            (MessageCode::NotFound, Zh) => "资源不存在: {entity} (id={id})",
+            (MessageCode::NotFound, En) => "Not found: {entity} (id={id})",
+            (MessageCode::Config, Zh) => "配置错误: {detail}",
+            (MessageCode::Config, En) => "Configuration error: {detail}",
+            (MessageCode::AiService, Zh) => "AI 服务错误: {detail}",
+            (MessageCode::AiService, En) => "AI service error: {detail}",
+            (MessageCode::Business, _) => "{detail}",
+
+            (MessageCode::TitleEmpty, Zh) => "标题不能为空",
+            (MessageCode::TitleEmpty, En) => "Title cannot be empty",
+            (MessageCode::FieldEmpty, Zh) => "{field} 不能为空",
+            (MessageCode::FieldEmpty, En) => "{field} cannot be empty",
+            (MessageCode::FieldNotPositive, Zh) => "{field} 必须大于 0",
+            (MessageCode::FieldNotPositive, En) => "{field} must be greater than 0",
+            (MessageCode::InvalidNodeId, Zh) => "无效的节点 ID",
+            (MessageCode::InvalidNodeId, En) => "Invalid node ID",
+            (MessageCode::UnknownNodeUuid, Zh) => "找不到 uuid 为 {value} 的节点",
+            (MessageCode::UnknownNodeUuid, En) => "No node with uuid {value}",
+            (MessageCode::InvalidDate, Zh) => "无效的日期: {value}",
+            (MessageCode::InvalidDate, En) => "Invalid date: {value}",
+            (MessageCode::InvalidRecurrenceRule, Zh) => "无效的重复规则: {detail}",
+            (MessageCode::InvalidRecurrenceRule, En) => "Invalid recurrence rule: {detail}",
+            (MessageCode::UnknownRelationType, Zh) => "未知的关系类型: {value}",
+            (MessageCode::UnknownRelationType, En) => "Unknown relation type: {value}",
+            (MessageCode::UnknownReviewStatus, Zh) => "未知的审核状态: {value}",
+            (MessageCode::UnknownReviewStatus, En) => "Unknown review status: {value}",
+            (MessageCode::UnknownSourceRuleField, Zh) => "未知的来源规则字段: {value}",
+            (MessageCode::UnknownSourceRuleField, En) => "Unknown source rule field: {value}",
+            (MessageCode::WeeklyTargetOutOfRange, Zh) => "每周目标天数需在 1-7 之间",
+            (MessageCode::WeeklyTargetOutOfRange, En) => {
+                "Weekly target must be between 1 and 7 days"
+            }
+            (MessageCode::ScrollPositionOutOfRange, Zh) => "滚动位置必须在 0 到 1 之间",
+            (MessageCode::ScrollPositionOutOfRange, En) => {
+                "Scroll position must be between 0 and 1"
+            }
+            (MessageCode::PageOutOfRange, Zh) => "页码必须从 1 开始",
+            (MessageCode::PageOutOfRange, En) => "Page numbers start at 1",
+            (MessageCode::ReadingProgressOutOfRange, Zh) => "阅读进度必须在 0 到 1 之间",
+            (MessageCode::ReadingProgressOutOfRange, En) => {
+                "Reading progress must be between 0 and 1"
+            }
+            (MessageCode::FocusDurationOutOfRange, Zh) => "专注时长需在 1-{max} 分钟之间",
+            (MessageCode::FocusDurationOutOfRange, En) => {
+                "Focus length must be between 1 and {max} minutes"
+            }
+            (MessageCode::PomodoroDurationOutOfRange, Zh) => "番茄钟时长需在 1-180 分钟之间",
+            (MessageCode::PomodoroDurationOutOfRange, En) => {
+                "Pomodoro lengths must be between 1 and 180 minutes"
+            }
+            (MessageCode::PomodoroCyclesOutOfRange, Zh) => "长休息间隔至少为 1",
+            (MessageCode::PomodoroCyclesOutOfRange, En) => "Long break interval must be at least 1",
+            (MessageCode::ScoreThresholdInvalid, Zh) => "score_threshold 必须是有限数值",
+            (MessageCode::ScoreThresholdInvalid, En) => "score_threshold must be a finite number",
+            (MessageCode::CaptureInputMissing, Zh) => "content 或 file_path 至少提供一个",
+            (MessageCode::CaptureInputMissing, En) => "Provide content or file_path",
+            (MessageCode::CostEstimateInputMissing, Zh) => "node_ids 或 paths 至少提供一个",
+            (MessageCode::CostEstimateInputMissing, En) => "Provide node_ids or paths",
+            (MessageCode::BibtexScopeInvalid, Zh) => "topic_id 与 node_ids 需且仅需提供一个",
+            (MessageCode::BibtexScopeInvalid, En) => "Provide exactly one of topic_id and node_ids",
+            (MessageCode::PdfOnly, Zh) => "只支持 PDF 资源",
+            (MessageCode::PdfOnly, En) => "Only PDF resources are supported",
+            (MessageCode::InvalidLayoutJson, Zh) => "无效的布局 JSON: {detail}",
+            (MessageCode::InvalidLayoutJson, En) => "Invalid layout JSON: {detail}",
+            (MessageCode::InvalidEnex, Zh) => "无效的 ENEX 文件: {detail}",
+            (MessageCode::InvalidEnex, En) => "Invalid ENEX file: {detail}",
+            (MessageCode::InvalidIcs, Zh) => "无效的 iCalendar 文件: {detail}",
+            (MessageCode::InvalidIcs, En) => "Invalid iCalendar file: {detail}",
+            (MessageCode::InvalidArchive, Zh) => "无法展开压缩包: {detail}",
+            (MessageCode::InvalidArchive, En) => "Cannot expand archive: {detail}",
+            (MessageCode::InvalidCalendarUrl, Zh) => {
+                "日历地址需以 http://、https:// 或 webcal:// 开头"
+            }
+            (MessageCode::InvalidCalendarUrl, En) => {
+                "Calendar URL must start with http://, https:// or webcal://"
+            }
+            (MessageCode::InvalidGithubRepo, Zh) => "仓库需为 owner/name 或 GitHub 仓库地址",
+            (MessageCode::InvalidGithubRepo, En) => {
+                "Repository must be owner/name or a GitHub repository URL"
+            }
+            (MessageCode::InvalidWebhookPort, Zh) => "端口需在 1024-65535 之间",
+            (MessageCode::InvalidWebhookPort, En) => "Port must be between 1024 and 65535",
+            (MessageCode::InvalidWatchFolder, Zh) => "无法监听文件夹: {detail}",
+            (MessageCode::InvalidWatchFolder, En) => "Cannot watch folder: {detail}",
+            (MessageCode::InvalidAssetsDir, Zh) => "无法使用该资源文件目录: {detail}",
+            (MessageCode::InvalidAssetsDir, En) => "Cannot use this assets directory: {detail}",
+            (MessageCode::InvalidExportFolder, Zh) => "无法导出到该文件夹: {detail}",
+            (MessageCode::InvalidExportFolder, En) => "Cannot export to this folder: {detail}",
+            (MessageCode::InvalidFileReference, Zh) => "无法引用该文件: {detail}",
+            (MessageCode::InvalidFileReference, En) => "Cannot reference this file: {detail}",
+            (MessageCode::InvalidShortcut, Zh) => "无效的快捷键: {value}",
+            (MessageCode::InvalidShortcut, En) => "Invalid shortcut: {value}",
+            (MessageCode::InvalidSearchPattern, Zh) => "无效的查找模式: {detail}",
+            (MessageCode::InvalidSearchPattern, En) => "Invalid search pattern: {detail}",
+            (MessageCode::InvalidRetentionRule, Zh) => "无效的保留规则: {detail}",
+            (MessageCode::InvalidRetentionRule, En) => "Invalid retention rule: {detail}",
+            (MessageCode::InvalidSnapshotArchive, Zh) => "无法打开该导出文件: {detail}",
+            (MessageCode::InvalidSnapshotArchive, En) => "Cannot open this export: {detail}",
+            (MessageCode::InvalidImportFolder, Zh) => "无法导入该文件夹: {detail}",
+            (MessageCode::InvalidImportFolder, En) => "Cannot import this folder: {detail}",
+            (MessageCode::InvalidReviewPeriod, Zh) => "无效的回顾周期: {value}（应为 YYYY-MM）",
+            (MessageCode::InvalidReviewPeriod, En) => {
+                "Invalid review period: {value} (expected YYYY-MM)"
+            }
+            (MessageCode::ModelDirNotAbsolute, Zh) => "模型目录需为绝对路径",
+            (MessageCode::ModelDirNotAbsolute, En) => "Model directory must be an absolute path",
+            (MessageCode::ImageNotFound, Zh) => "图片不存在: {path}",
+            (MessageCode::ImageNotFound, En) => "Image not found: {path}",
+            (MessageCode::AssetsDirNotAbsolute, Zh) => "目标目录需为绝对路径",
+            (MessageCode::AssetsDirNotAbsolute, En) => {
+                "The target directory must be an absolute path"
+            }
+            (MessageCode::AssetsDirUnchanged, Zh) => "目标目录与当前目录相同",
+            (MessageCode::AssetsDirUnchanged, En) => {
+                "The target directory is the current directory"
+            }
+            (MessageCode::AssetsDirNested, Zh) => "目标目录不能与当前目录互相包含",
+            (MessageCode::AssetsDirNested, En) => {
+                "The target and current directories cannot contain each other"
+            }
+            (MessageCode::FileReferenceNotAbsolute, Zh) => "引用的文件需为绝对路径",
+            (MessageCode::FileReferenceNotAbsolute, En) => {
+                "The referenced file must be an absolute path"
+            }
+            (MessageCode::FileReferenceNotFile, Zh) => "{path} 不是文件",
+            (MessageCode::FileReferenceNotFile, En) => "{path} is not a file",
+            (MessageCode::ImportFolderNotDir, Zh) => "{path} 不是文件夹",
+            (MessageCode::ImportFolderNotDir, En) => "{path} is not a folder",
+            (MessageCode::ImportFolderEmpty, Zh) => "没有找到 .md 文件",
+            (MessageCode::ImportFolderEmpty, En) => "No .md files found",
+
+            (MessageCode::InvalidConversionTarget, Zh) => "目标类型必须是 Topic 或 Task",
+            (MessageCode::InvalidConversionTarget, En) => "Target type must be Topic or Task",
+            (MessageCode::NotAResource, Zh) => "节点不是有效的资源",
+            (MessageCode::NotAResource, En) => "Node is not a valid resource",
+            (MessageCode::NotAnArchive, Zh) => "节点不是压缩包资源",
+            (MessageCode::NotAnArchive, En) => "Node is not an archive resource",
+            (MessageCode::ResourceFileMissing, Zh) => "资源缺少文件",
+            (MessageCode::ResourceFileMissing, En) => "The resource has no file",
+            (MessageCode::ContainsCreateCycle, Zh) => "创建 contains 边会形成环",
+            (MessageCode::ContainsCreateCycle, En) => {
+                "Creating the contains edge would create a cycle"
+            }
+            (MessageCode::ContainsMigrateCycle, Zh) => "迁移 contains 边会形成环",
+            (MessageCode::ContainsMigrateCycle, En) => {
+                "Moving the contains edges would create a cycle"
+            }
+            (MessageCode::TopicTitleEmpty, Zh) => "主题标题不能为空",
+            (MessageCode::TopicTitleEmpty, En) => "Topic title cannot be empty",
+            (MessageCode::TopicMergeIntoSelf, Zh) => "不能把主题合并到自身",
+            (MessageCode::TopicMergeIntoSelf, En) => "A topic cannot be merged into itself",
+            (MessageCode::TopicMoveCycle, Zh) => "移动主题会形成环",
+            (MessageCode::TopicMoveCycle, En) => "Moving the topic would create a cycle",
+            (MessageCode::TopicMergeCycle, Zh) => "合并主题会形成环",
+            (MessageCode::TopicMergeCycle, En) => "Merging the topics would create a cycle",
+            (MessageCode::TopicTitleTaken, Zh) => "已有名为「{title}」的主题",
+            (MessageCode::TopicTitleTaken, En) => "A topic named \"{title}\" already exists",
+            (MessageCode::SubtaskCycle, Zh) => "不能把任务移到它自己或它的子任务下",
+            (MessageCode::SubtaskCycle, En) => {
+                "A task cannot be moved under itself or its subtasks"
+            }
+            (MessageCode::IntegrityCheckRunning, Zh) => "文件完整性校验正在进行",
+            (MessageCode::IntegrityCheckRunning, En) => "A file integrity check is already running",
+            (MessageCode::ExportRunning, Zh) => "导出正在进行",
+            (MessageCode::ExportRunning, En) => "An export is already running",
+            (MessageCode::EmbeddingReindexRunning, Zh) => "向量重建正在进行",
+            (MessageCode::EmbeddingReindexRunning, En) => {
+                "An embedding re-index is already running"
+            }
+            (MessageCode::ModelDownloadRunning, Zh) => "模型文件正在下载",
+            (MessageCode::ModelDownloadRunning, En) => "Model files are already downloading",
+            (MessageCode::VectorMaintenanceRunning, Zh) => "向量库维护正在进行",
+            (MessageCode::VectorMaintenanceRunning, En) => "Vector maintenance is already running",
+            (MessageCode::JobCancelled, Zh) => "任务已取消",
+            (MessageCode::JobCancelled, En) => "The job was cancelled",
+            (MessageCode::NodeEditConflict, Zh) => "内容已在别处被修改，请重新加载后再保存",
+            (MessageCode::NodeEditConflict, En) => {
+                "This item was changed elsewhere; reload it before saving"
+            }
+            (MessageCode::TagNameTaken, Zh) => "标签「{name}」已存在，可将两个标签合并",
+            (MessageCode::TagNameTaken, En) => {
+                "Tag \"{name}\" already exists; merge the tags instead"
+            }
+            (MessageCode::ShortcutTaken, Zh) => "快捷键 {value} 已被占用",
+            (MessageCode::ShortcutTaken, En) => "Shortcut {value} is already in use",
+            (MessageCode::PendingChangeDecided, Zh) => "该修改已处理过",
+            (MessageCode::PendingChangeDecided, En) => "This change has already been decided",
+            (MessageCode::InvalidPendingChangePayload, Zh) => "该修改的内容无效: {detail}",
+            (MessageCode::InvalidPendingChangePayload, En) => {
+                "This change has an invalid payload: {detail}"
+            }
+            (MessageCode::WebhookTokenEmpty, Zh) => "消息入口令牌为空",
+            (MessageCode::WebhookTokenEmpty, En) => "The message webhook token is empty",
+            (MessageCode::WebhookListenFailed, Zh) => "监听端口 {port} 失败: {detail}",
+            (MessageCode::WebhookListenFailed, En) => "Cannot listen on port {port}: {detail}",
+            (MessageCode::AssetsRelocationRunning, Zh) => "资源文件迁移正在进行",
+            (MessageCode::AssetsRelocationRunning, En) => "An assets relocation is already running",
+            (MessageCode::AssetsFileConflict, Zh) => "目标目录中已有不同的同名文件: {path}",
+            (MessageCode::AssetsFileConflict, En) => {
+                "The target directory already has a different file at {path}"
+            }
+            (MessageCode::FocusSessionRunning, Zh) => "已有进行中的专注会话",
+            (MessageCode::FocusSessionRunning, En) => "A focus session is already running",
+            (MessageCode::TimerTaskOnly, Zh) => "只能为任务计时",
+            (MessageCode::TimerTaskOnly, En) => "Only tasks can be timed",
+            (MessageCode::NoReviewActivity, Zh) => "{period} 没有可回顾的主题活动",
+            (MessageCode::NoReviewActivity, En) => "No topic activity to review for {period}",
+            (MessageCode::SearchFailed, Zh) => "搜索失败: {detail}",
+            (MessageCode::SearchFailed, En) => "Search failed: {detail}",
+            (MessageCode::TagMergeIntoSelf, Zh) => "不能把标签合并到自身",
+            (MessageCode::TagMergeIntoSelf, En) => "A tag cannot be merged into itself",
+
+            (MessageCode::AiNotReady, Zh) => "AI 服务未就绪: {detail}",
+            (MessageCode::AiNotReady, En) => "AI service is not ready: {detail}",
+
+            (MessageCode::DataFormatTooNew, Zh) => {
+                "数据库由更新版本的 NeuralVault 写入（数据格式 {found_format}，\
+                 迁移 {found_schema}；当前程序支持数据格式 {supported_format}，\
+                 迁移 {supported_schema}），为避免损坏数据已停止启动。\
+                 请安装最新版本；如需使用当前版本，请先备份 {path} 后再将其移走"
+            }
+            (MessageCode::DataFormatTooNew, En) => {
+                "The database was written by a newer version of NeuralVault \
+                 (data format {found_format}, migration {found_schema}; this build supports \
+                 data format {supported_format}, migration {supported_schema}). \
+                 Startup was stopped to avoid corrupting it. Install the latest version, \
+                 or back up {path} and move it aside to use this version"
+            }
+        }
+    }
+
+    /// 用参数填充模板
+    pub fn render(self, language: Language, args: &[(&str, String)]) -> String {
+        args.iter().fold(
+            self.template(language).to_string(),
+            |text, (name, value)| text.replace(&format!("{{{name}}}"), value),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_fills_placeholders() {
+        let args = [("field", "pattern".to_string())];
+        assert_eq!(
+            MessageCode::FieldEmpty.render(Language::Zh, &args),
+            "pattern 不能为空"
+        );
+        assert_eq!(
+            MessageCode::FieldEmpty.render(Language::En, &args),
+            "pattern cannot be empty"
+        );
+        assert_eq!(
+            MessageCode::TitleEmpty.render(Language::En, &[]),
+            "Title cannot be empty"
+        );
+    }
+
+    #[test]
+    fn test_code_matches_serialized_name() {
+        for code in [
+            MessageCode::NotFound,
+            MessageCode::AiService,
+            MessageCode::WeeklyTargetOutOfRange,
+            MessageCode::AiNotReady,
+            MessageCode::DataFormatTooNew,
+        ] {
+            assert_eq!(
+                serde_json::to_value(code).unwrap(),
+                serde_json::Value::String(code.as_str().to_string())
+            );
+        }
+    }
+}
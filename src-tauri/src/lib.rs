@@ -1,7 +1,9 @@
 mod app_state;
+mod cli;
 mod commands;
 mod db;
 mod error;
+mod i18n;
 mod services;
 mod utils;
 mod window;
@@ -15,6 +17,7 @@ use tokio::sync::Mutex;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 
 pub use app_state::AppState;
+pub use cli::run_cli_if_requested;
 pub use error::{AppError, AppResult};
 pub use window::{hide_hud, toggle_hud};
 
@@ -22,27 +25,125 @@ pub use window::{hide_hud, toggle_hud};
 
 // 资源命令
 pub use commands::{
-    capture_resource, get_all_resources, get_assets_path, get_resource_by_id,
-    hard_delete_resource_command, process_pending_resources_command,
-    soft_delete_resource_command, update_resource_content_command,
+    capture_resource, capture_resources_batch, estimate_processing_cost, expand_archive,
+    find_in_notes, get_all_resources, get_assets_path, get_resource_by_id, hard_delete_resource_command,
+    approve_classification, get_embedding_status_report, get_pipeline_status, pause_ai_pipeline, list_resource_revisions,
+    list_resources_by_source_command, list_unreviewed_resources, preview_archive,
+    process_pending_resources_command, reclassify_resource_command, reject_classification,
+    replace_in_notes,
+    requeue_failed_embeddings, restore_resource_revision, retry_failed_resources, resume_ai_pipeline, soft_delete_resource_command, update_resource_content_command,
     update_resource_summary_command, update_resource_title_command,
     update_resource_user_note_command,
 };
 
+// 导入命令
+pub use commands::{import_enex, import_markdown_folder};
+
+// 日历命令
+pub use commands::{
+    add_calendar_subscription, export_tasks_ics, import_ics, list_calendar_subscriptions,
+    remove_calendar_subscription, sync_calendar_subscription,
+};
+
+// GitHub 同步命令
+pub use commands::{add_github_repo, list_github_repos, remove_github_repo, sync_github_repo};
+
+// 资源文件目录命令
+pub use commands::{get_assets_storage, relocate_assets};
+
+// 模型目录命令
+pub use commands::{download_models, get_model_dir_status, set_model_dir};
+
+// 文件完整性校验与自检命令
+pub use commands::{get_latest_integrity_report, run_self_test, verify_asset_integrity};
+
+// 导出命令
+pub use commands::{
+    export_vault, get_export_schedule, list_export_runs, run_export_now, set_export_schedule,
+};
+
+// 后台任务命令
+pub use commands::{cancel_job, get_job_status, list_jobs};
+pub use commands::{get_model_prices, get_usage_report, set_model_prices};
+
+// 待确认的 AI 修改命令
+pub use commands::{approve_change, list_pending_changes, reject_change};
+
+// 捕获模板命令
+pub use commands::{delete_capture_profile, list_capture_profiles, save_capture_profile};
+
+// 监听文件夹命令
+pub use commands::{add_watched_folder, list_watched_folders, remove_watched_folder};
+
+// 外部文件引用命令
+pub use commands::{
+    get_file_reference_status, list_file_reference_statuses, relink_referenced_file,
+};
+
+// 标签命令
+pub use commands::{
+    accept_suggested_tags, add_tag, list_nodes_by_tag, list_suggested_tags, list_tags, merge_tags,
+    remove_tag, rename_tag,
+};
+
+// 消息转发入口命令
+pub use commands::{
+    get_message_webhook_status, regenerate_message_webhook_token, set_message_webhook,
+};
+
+// 运行指标命令
+pub use commands::{get_metrics, get_metrics_endpoint_status, set_metrics_endpoint};
+
+// 保留策略命令
+pub use commands::{
+    delete_retention_rule, list_retention_rules, preview_retention_rule, run_retention_rules_now,
+    save_retention_rule, set_node_archived,
+};
+
+// 备份查看命令
+pub use commands::{
+    close_snapshot, get_snapshot_node, list_snapshot_nodes, list_snapshot_topic_contents,
+    list_snapshots, open_snapshot, search_snapshot,
+};
+
+// 收件箱整理命令
+pub use commands::{apply_inbox_triage_command, triage_inbox};
+
+// 文献命令
+pub use commands::{
+    export_bibtex, extract_citation_command, get_citation, list_papers, remove_citation_command,
+    update_citation,
+};
+
+// 来源命令
+pub use commands::{
+    add_source_tag_rule, get_capture_sources_report, list_node_tags,
+    list_source_tag_rules_command, remove_source_tag_rule, set_source_tag_rule_enabled,
+};
+
 // 任务命令
 pub use commands::{
-    create_task, get_active_tasks, get_all_tasks, get_tasks_by_date,
-    hard_delete_task_command, mark_task_as_cancelled_command, mark_task_as_done_command,
-    mark_task_as_todo_command, soft_delete_task_command, update_task_description_command,
-    update_task_due_date_command, update_task_priority_command, update_task_summary_command,
-    update_task_title_command,
+    create_recurring_task, create_subtask, create_task, get_active_tasks, get_all_tasks,
+    get_task_board, get_task_reminder_command, get_tasks_by_date, hard_delete_task_command,
+    list_subtasks, mark_task_as_cancelled_command, mark_task_as_done_command,
+    mark_task_as_todo_command, move_task_on_board, reparent_task, set_task_reminder,
+    snooze_task_reminder, soft_delete_task_command, update_task_description_command,
+    update_task_due_date_command, update_task_priority_command, update_task_recurrence,
+    update_task_summary_command, update_task_title_command,
+};
+
+// 习惯命令
+pub use commands::{
+    create_habit, list_habits_command, log_habit, soft_delete_habit_command, unlog_habit,
+    update_habit_schedule_command,
 };
 
 // 主题命令
 pub use commands::{
-    create_topic, get_resource_topics_command, get_task_topics_command, get_topic_command,
-    get_topic_resources_command, get_topic_tasks_command, hard_delete_topic_command,
-    link_resource_to_topic_command, link_task_to_topic_command, list_topics_command,
+    apply_topic_restructure_command, create_topic, get_resource_topics_command,
+    get_task_topics_command, get_topic_command, get_topic_resources_command,
+    get_topic_tasks_command, hard_delete_topic_command, link_resource_to_topic_command,
+    link_task_to_topic_command, list_topics_command, plan_topic_restructure_command,
     soft_delete_topic_command, unlink_resource_from_topic_command, unlink_task_from_topic_command,
     update_topic_favourite_command, update_topic_resource_review_status_command,
     update_topic_summary_command, update_topic_title_command,
@@ -51,37 +152,73 @@ pub use commands::{
 // 节点命令
 pub use commands::{
     convert_resource_to_task_command, convert_resource_to_topic_command,
-    convert_task_to_topic_command, convert_topic_to_task_command, list_node_revision_logs,
-    list_pinned_nodes, list_unreviewed_nodes, update_node_pinned, update_node_review_status,
+    convert_task_to_topic_command, convert_topic_to_task_command, get_node_by_uuid_command,
+    get_reading_position, list_node_revision_logs, list_pinned_nodes, list_recent_nodes, list_unreviewed_nodes,
+    record_node_opened, set_topic_auto_classify_allowed, update_node_exclude_from_search,
+    update_node_pinned, update_node_review_status, update_reading_position, update_reading_progress,
 };
 
 // 边命令
 pub use commands::{
-    confirm_edge_command, link_nodes_command, list_all_edges_command,
-    list_edges_for_target_command, list_source_nodes_command, list_target_nodes_command,
-    unlink_nodes_command,
+    confirm_edge_command, export_edges, get_backlinks, get_derivation_sources, get_derivatives, get_graph,
+    import_edges, link_nodes_command, list_all_edges_command, list_edges_for_target_command, list_source_nodes_command,
+    list_target_nodes_command, unlink_nodes_command,
 };
 
 // 搜索命令
-pub use commands::{search_keyword, search_semantic, warmup_embedding};
+pub use commands::{
+    get_vector_stats, list_node_chunks, optimize_vector_store, quick_search, rebuild_vector_index,
+    reindex_embeddings, search_by_image, search_keyword, search_nodes_fulltext, search_semantic,
+    search_vault, warmup_embedding,
+};
 
 // 聊天命令
 pub use commands::{
     add_message_attachments, create_chat_message, create_chat_session, delete_chat_message,
-    delete_chat_session, get_chat_session, list_chat_messages_command, list_chat_sessions,
-    list_message_attachments_command, list_session_bound_resources_command,
-    remove_message_attachment, send_chat_message, set_session_bindings_command,
-    update_chat_message, update_chat_session_command,
+    delete_chat_session, fork_chat_session, get_chat_session, list_chat_messages_command,
+    list_chat_sessions, list_message_attachments_command, list_message_sources,
+    list_session_bound_resources_command, remove_message_attachment, send_chat_message,
+    set_session_bindings_command, set_session_rag_settings, update_chat_message,
+    update_chat_session_command,
 };
 
 // AI 配置命令
 pub use commands::{
-    get_ai_config_status, remove_api_key, save_api_key, set_classification_mode,
-    set_processing_provider_model,
+    get_ai_config_status, list_local_models, remove_api_key, save_api_key, set_ann_index_config,
+    set_chat_context_config, set_classification_mode, set_language, set_processing_provider_model,
+    set_provider_rate_limit, set_retrieval_config, set_summary_language, set_warmup_on_idle,
+};
+
+// 专注命令
+pub use commands::{
+    end_focus_session, get_current_focus_session, get_focus_stats, list_focus_sessions,
+    start_focus_session,
 };
 
+// 番茄钟命令
+pub use commands::{
+    get_pomodoro_state, get_task_timer, get_time_report, list_time_entries, pause_pomodoro,
+    resume_pomodoro, skip_pomodoro_phase, start_pomodoro, start_task_timer, stop_pomodoro,
+    stop_task_timer,
+};
+
+// 通知命令
+pub use commands::{
+    delete_notification_command, get_unread_notification_count, list_notifications,
+    mark_all_notifications_read_command, mark_notification_read,
+};
+
+// 回顾命令
+pub use commands::{generate_goal_review_command, list_goal_reviews_command};
+
+// 工作区命令
+pub use commands::{delete_workspace, list_workspaces, restore_workspace, save_workspace};
+
 // 其他命令
-pub use commands::{get_dashboard, read_clipboard};
+pub use commands::{
+    get_dashboard, get_event_schema, list_data_migrations_command, preview_clipboard_ocr,
+    read_clipboard,
+};
 
 // ========== 内部命令 ==========
 
@@ -156,6 +293,7 @@ fn init_tracing(log_dir: &Path) {
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_notification::init())
         .setup(|app| {
             // ========== 数据库初始化 ==========
             let app_dir = app // 整个应用程序的运行时句柄
@@ -173,14 +311,31 @@ pub fn run() {
             fs::create_dir_all(&log_dir)?;
             init_tracing(&log_dir);
 
-            // 安全地将文件名拼接到目录后面，生成数据库文件的完整绝对路径
-            let db_path = app_dir.join("neuralvault.sqlite3");
-            // 强制阻塞当前线程，直到数据库连接池初始化完成
-            let pool = tauri::async_runtime::block_on(db::init_pool(&db_path))?;
-
             // ========== AI 配置服务初始化 ==========
             let ai_config_service = services::AIConfigService::new(&app_dir)
                 .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+            let message_capture = Arc::new(services::MessageCaptureService::new());
+            let metrics_endpoint = Arc::new(services::MetricsEndpoint::new());
+            if let Ok(config) = ai_config_service.load() {
+                i18n::set_current_language(config.language);
+                services::apply_assets_config(&app_dir, config.assets_dir.as_deref());
+                // 消息转发入口：端口被占用等错误不影响启动
+                if let Err(err) = message_capture.apply_config(&config.message_webhook) {
+                    tracing::warn!(error = %err, "Message webhook start failed");
+                }
+                if let Err(err) = metrics_endpoint.apply_config(&config.metrics_endpoint) {
+                    tracing::warn!(error = %err, "Metrics endpoint start failed");
+                }
+            }
+            // 模型目录：自定义目录 → 安装包资源目录 → 应用数据目录下载的模型
+            let model_dir = ai_config_service.load().ok().and_then(|config| config.model_dir);
+            services::apply_model_dir_config(app.handle(), &app_dir, model_dir.as_deref());
+
+            // 安全地将文件名拼接到目录后面，生成数据库文件的完整绝对路径
+            let db_path = app_dir.join("neuralvault.sqlite3");
+            // 强制阻塞当前线程，直到数据库连接池初始化完成；
+            // 在载入界面语言之后进行，数据库版本过新时的错误按用户语言显示
+            let pool = tauri::async_runtime::block_on(db::init_pool(&db_path))?;
 
             // 初始化好的 AppState（包含数据库连接池和 AI 服务）注入到 Tauri 的全局管理器中
             let ai_config = Arc::new(Mutex::new(ai_config_service));
@@ -189,6 +344,7 @@ pub fn run() {
             // 异步初始化 AI 服务
             let ai_handle_init = ai_handle.clone();
             let app_dir_for_ai = app_dir.clone();
+            let pool_for_ai = pool.clone();
             tauri::async_runtime::spawn(async move {
                 let config_service = match services::AIConfigService::new(&app_dir_for_ai) {
                     Ok(service) => service,
@@ -199,7 +355,7 @@ pub fn run() {
                     }
                 };
 
-                match services::AiServices::new(&config_service).await {
+                match services::AiServices::new(&config_service, pool_for_ai).await {
                     Ok(services) => {
                         ai_handle_init.set_ready(Arc::new(services));
                         tracing::info!("AI services ready");
@@ -220,19 +376,83 @@ pub fn run() {
                 app.handle().clone(),
             ));
 
+            // ========== 专注模式 / 番茄钟初始化 ==========
+            let focus = Arc::new(services::FocusService::new(pool.clone(), app.handle().clone()));
+            let pomodoro =
+                Arc::new(services::PomodoroService::new(pool.clone(), app.handle().clone()));
+            let task_timer =
+                Arc::new(services::TaskTimerService::new(pool.clone(), app.handle().clone()));
+            if let Err(err) = tauri::async_runtime::block_on(db::close_stale_focus_sessions(&pool)) {
+                tracing::warn!(error = %err, "Failed to close stale focus sessions");
+            }
+            if let Err(err) = tauri::async_runtime::block_on(task_timer.restore()) {
+                tracing::warn!(error = %err, "Failed to restore task timer");
+            }
+
+            // 只读查看的备份：临时目录在每次启动时清空
+            let snapshots = Arc::new(services::SnapshotSessions::new(app_dir.join("snapshots")));
+            snapshots.clear_stale();
+
             app.manage(AppState {
                 db: pool,
                 ai: ai_handle,
                 ai_config,
                 ai_pipeline,
+                focus,
+                pomodoro,
+                task_timer,
+                search_cache: Arc::new(services::SemanticSearchCache::new()),
+                message_capture,
+                metrics_endpoint,
+                folder_watch: Arc::new(services::FolderWatchService::new()),
+                file_references: Arc::new(services::FileReferenceService::new()),
+                jobs: Arc::new(services::JobRegistry::default()),
+                snapshots,
             });
 
+            // 自定义资源文件目录：允许 asset 协议访问；目录缺失（外接硬盘未连接）时通知用户
+            let assets_status = services::assets_storage_status(&app_dir);
+            if assets_status.is_custom {
+                services::allow_assets_scope(app.handle(), Path::new(&assets_status.path));
+            }
+            if !assets_status.available {
+                let app_handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    let Some(state) = app_handle.try_state::<AppState>() else {
+                        return;
+                    };
+                    let body = format!(
+                        "{} 不存在，相关资源文件暂时无法打开。请连接所在硬盘，或在设置中重新选择目录。",
+                        assets_status.path
+                    );
+                    let params = db::NewNotification {
+                        kind: db::NotificationKind::System,
+                        title: "资源文件目录不可用",
+                        body: Some(&body),
+                        node_id: None,
+                        action: None,
+                    };
+                    let result = services::push_notification(&state.db, &app_handle, params).await;
+                    if let Err(err) = result {
+                        tracing::warn!(error = %err, "Notify missing assets directory failed");
+                    }
+                });
+            }
+
             // 重启后重新入队待处理资源
             let app_handle = app.handle().clone();
             tauri::async_runtime::spawn(async move {
                 if let Some(state) = app_handle.try_state::<AppState>() {
                     let db = state.db.clone();
                     let pipeline = state.ai_pipeline.clone();
+                    match pipeline.restore_jobs().await {
+                        Ok(count) => {
+                            tracing::info!(restored = count, "Restored pipeline jobs after restart");
+                        }
+                        Err(err) => {
+                            tracing::error!(error = %err, "Failed to restore pipeline jobs");
+                        }
+                    }
                     match pipeline.enqueue_pending_resources(&db).await {
                         Ok(count) => {
                             tracing::info!(requeued = count, "Requeued resources after restart");
@@ -244,6 +464,45 @@ pub fn run() {
                 }
             });
 
+            // 月度回顾定时检查
+            services::spawn_goal_review_scheduler(app.handle().clone());
+
+            // 日历订阅轮询与日程提醒
+            services::spawn_calendar_scheduler(app.handle().clone());
+
+            // 任务截止提醒
+            services::spawn_task_reminder_scheduler(app.handle().clone());
+
+            // GitHub 仓库轮询同步
+            services::spawn_github_sync_scheduler(app.handle().clone());
+
+            // 转发消息定期写入资源
+            services::spawn_message_capture_flusher(app.handle().clone());
+
+            // 监听文件夹自动导入
+            services::spawn_folder_watcher(app.handle().clone());
+
+            // 外部引用文件的变化监听与离线检查
+            services::spawn_file_reference_watcher(app.handle().clone());
+
+            // 后台数据迁移（分批回填，可中断后继续）
+            services::spawn_data_migrations(app.handle().clone());
+
+            // 定期校验资源文件完整性
+            services::spawn_integrity_scheduler(app.handle().clone());
+
+            // 定期清理孤立向量并整理向量表
+            services::spawn_vector_maintenance_scheduler(app.handle().clone());
+
+            // 定期自动导出
+            services::spawn_export_scheduler(app.handle().clone());
+
+            // 按保留规则归档或清理旧资源
+            services::spawn_retention_scheduler(app.handle().clone());
+
+            // 空闲时预热搜索模型
+            services::spawn_search_warmup(app.handle().clone());
+
             // ========== HUD 窗口设置 ==========
             window::setup_hud(app)?;
 
@@ -262,21 +521,151 @@ pub fn run() {
             toggle_hud,
             hide_hud,
             read_clipboard,
+            preview_clipboard_ocr,
             get_assets_path,
             get_dashboard,
+            get_event_schema,
+            list_data_migrations_command,
             // 资源
             capture_resource,
+            capture_resources_batch,
             get_all_resources,
             get_resource_by_id,
+            list_resources_by_source_command,
             update_resource_content_command,
+            list_resource_revisions,
+            restore_resource_revision,
             update_resource_title_command,
             update_resource_summary_command,
             update_resource_user_note_command,
             soft_delete_resource_command,
             hard_delete_resource_command,
             process_pending_resources_command,
+            get_embedding_status_report,
+            get_pipeline_status,
+            pause_ai_pipeline,
+            resume_ai_pipeline,
+            requeue_failed_embeddings,
+            retry_failed_resources,
+            estimate_processing_cost,
+            // 查找替换
+            find_in_notes,
+            replace_in_notes,
+            reclassify_resource_command,
+            // 分类审核队列
+            list_unreviewed_resources,
+            approve_classification,
+            reject_classification,
+            preview_archive,
+            expand_archive,
+            // 导入
+            import_enex,
+            import_markdown_folder,
+            // 日历
+            import_ics,
+            export_tasks_ics,
+            add_calendar_subscription,
+            list_calendar_subscriptions,
+            remove_calendar_subscription,
+            sync_calendar_subscription,
+            // GitHub 同步
+            add_github_repo,
+            list_github_repos,
+            remove_github_repo,
+            sync_github_repo,
+            // 消息转发入口
+            get_message_webhook_status,
+            set_message_webhook,
+            regenerate_message_webhook_token,
+            // 运行指标
+            get_metrics,
+            get_metrics_endpoint_status,
+            set_metrics_endpoint,
+            // 保留策略
+            list_retention_rules,
+            save_retention_rule,
+            delete_retention_rule,
+            preview_retention_rule,
+            run_retention_rules_now,
+            set_node_archived,
+            // 备份查看
+            open_snapshot,
+            list_snapshots,
+            close_snapshot,
+            search_snapshot,
+            list_snapshot_nodes,
+            get_snapshot_node,
+            list_snapshot_topic_contents,
+            // 收件箱整理
+            triage_inbox,
+            apply_inbox_triage_command,
+            // 资源文件目录
+            get_assets_storage,
+            relocate_assets,
+            // 模型目录
+            get_model_dir_status,
+            set_model_dir,
+            download_models,
+            // 文件完整性校验与自检
+            verify_asset_integrity,
+            get_latest_integrity_report,
+            run_self_test,
+            // 导出
+            get_export_schedule,
+            set_export_schedule,
+            run_export_now,
+            export_vault,
+            list_export_runs,
+            // 后台任务
+            get_job_status,
+            list_jobs,
+            cancel_job,
+            // 待确认的 AI 修改
+            list_pending_changes,
+            approve_change,
+            reject_change,
+            // 用量统计
+            get_usage_report,
+            get_model_prices,
+            set_model_prices,
+            // 捕获模板
+            list_capture_profiles,
+            save_capture_profile,
+            delete_capture_profile,
+            // 监听文件夹
+            add_watched_folder,
+            list_watched_folders,
+            remove_watched_folder,
+            // 外部文件引用
+            get_file_reference_status,
+            list_file_reference_statuses,
+            relink_referenced_file,
+            // 标签
+            add_tag,
+            remove_tag,
+            list_tags,
+            list_nodes_by_tag,
+            rename_tag,
+            merge_tags,
+            list_suggested_tags,
+            accept_suggested_tags,
+            // 文献
+            get_citation,
+            list_papers,
+            update_citation,
+            extract_citation_command,
+            remove_citation_command,
+            export_bibtex,
+            // 来源
+            get_capture_sources_report,
+            add_source_tag_rule,
+            list_source_tag_rules_command,
+            set_source_tag_rule_enabled,
+            remove_source_tag_rule,
+            list_node_tags,
             // 任务
             create_task,
+            create_recurring_task,
             get_all_tasks,
             get_active_tasks,
             get_tasks_by_date,
@@ -285,11 +674,30 @@ pub fn run() {
             update_task_summary_command,
             update_task_priority_command,
             update_task_due_date_command,
+            update_task_recurrence,
             mark_task_as_done_command,
             mark_task_as_todo_command,
             mark_task_as_cancelled_command,
             soft_delete_task_command,
             hard_delete_task_command,
+            // 子任务
+            create_subtask,
+            list_subtasks,
+            reparent_task,
+            // 任务提醒
+            get_task_reminder_command,
+            set_task_reminder,
+            snooze_task_reminder,
+            // 任务看板
+            get_task_board,
+            move_task_on_board,
+            // 习惯
+            create_habit,
+            list_habits_command,
+            update_habit_schedule_command,
+            log_habit,
+            unlog_habit,
+            soft_delete_habit_command,
             // 主题
             create_topic,
             get_topic_command,
@@ -308,16 +716,26 @@ pub fn run() {
             unlink_task_from_topic_command,
             get_topic_tasks_command,
             get_task_topics_command,
+            plan_topic_restructure_command,
+            apply_topic_restructure_command,
             // 节点
+            get_node_by_uuid_command,
             list_pinned_nodes,
             list_unreviewed_nodes,
             update_node_review_status,
             update_node_pinned,
+            update_node_exclude_from_search,
+            set_topic_auto_classify_allowed,
             list_node_revision_logs,
             convert_resource_to_topic_command,
             convert_resource_to_task_command,
             convert_topic_to_task_command,
             convert_task_to_topic_command,
+            record_node_opened,
+            list_recent_nodes,
+            update_reading_position,
+            update_reading_progress,
+            get_reading_position,
             // 边
             link_nodes_command,
             unlink_nodes_command,
@@ -326,10 +744,25 @@ pub fn run() {
             confirm_edge_command,
             list_edges_for_target_command,
             list_all_edges_command,
+            get_derivatives,
+            get_backlinks,
+            get_derivation_sources,
+            get_graph,
+            export_edges,
+            import_edges,
             // 搜索
             search_semantic,
+            search_by_image,
+            quick_search,
             search_keyword,
+            search_nodes_fulltext,
+            search_vault,
             warmup_embedding,
+            get_vector_stats,
+            rebuild_vector_index,
+            optimize_vector_store,
+            reindex_embeddings,
+            list_node_chunks,
             // 聊天
             send_chat_message,
             create_chat_session,
@@ -337,6 +770,7 @@ pub fn run() {
             list_chat_sessions,
             update_chat_session_command,
             delete_chat_session,
+            fork_chat_session,
             create_chat_message,
             list_chat_messages_command,
             list_message_attachments_command,
@@ -346,13 +780,65 @@ pub fn run() {
             add_message_attachments,
             remove_message_attachment,
             set_session_bindings_command,
+            set_session_rag_settings,
+            list_message_sources,
             // AI 配置
             get_ai_config_status,
             save_api_key,
             remove_api_key,
+            set_provider_rate_limit,
             set_processing_provider_model,
             set_classification_mode,
+            set_warmup_on_idle,
+            set_language,
+            set_summary_language,
+            set_retrieval_config,
+            set_chat_context_config,
+            set_ann_index_config,
+            list_local_models,
+            // 专注
+            start_focus_session,
+            end_focus_session,
+            get_current_focus_session,
+            list_focus_sessions,
+            get_focus_stats,
+            // 番茄钟
+            start_pomodoro,
+            pause_pomodoro,
+            resume_pomodoro,
+            skip_pomodoro_phase,
+            stop_pomodoro,
+            get_pomodoro_state,
+            list_time_entries,
+            start_task_timer,
+            stop_task_timer,
+            get_task_timer,
+            get_time_report,
+            // 通知
+            list_notifications,
+            get_unread_notification_count,
+            mark_notification_read,
+            mark_all_notifications_read_command,
+            delete_notification_command,
+            // 回顾
+            generate_goal_review_command,
+            list_goal_reviews_command,
+            // 工作区
+            save_workspace,
+            restore_workspace,
+            list_workspaces,
+            delete_workspace,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app_handle, event| {
+            // 退出时停止计时器并写入时间记录
+            if let tauri::RunEvent::Exit = event {
+                if let Some(state) = app_handle.try_state::<AppState>() {
+                    if let Err(err) = tauri::async_runtime::block_on(state.task_timer.stop()) {
+                        tracing::warn!(error = %err, "Failed to stop task timer on exit");
+                    }
+                }
+            }
+        });
 }
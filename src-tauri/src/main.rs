@@ -2,5 +2,9 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
+    // `neuralvault add ...` 等子命令不启动窗口，执行完直接退出
+    if let Some(code) = neuralvault_lib::run_cli_if_requested() {
+        std::process::exit(code);
+    }
     neuralvault_lib::run()
 }
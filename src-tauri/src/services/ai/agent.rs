@@ -1,19 +1,74 @@
+use std::future::Future;
 use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
-use crate::services::ProviderConfig;
+use crate::db::{DbPool, UsageOperation};
+use crate::i18n::{current_language, Language};
+use crate::services::{record_usage, ProviderConfig, SummaryLanguageMode};
 
+use super::error::LlmError;
 use super::llm::LlmService;
-use super::types::{ClassifyTopicResponse, CreateNewPayload, NewTopicPayload, TopicCandidate};
+use super::types::{
+    ChatMessage, ChatStreamEvent, CitationDraft, ClassifyTopicResponse, CreateNewPayload,
+    GoalReviewDraft, InboxTriageDraft, NewTopicPayload, ToolCall, ToolResult, ToolSpec,
+    TopicCandidate, TopicOperationDraft,
+};
+
+/// Max model turns per `chat_with_tools` call
+const MAX_TOOL_ROUNDS: usize = 6;
+
+/// Runs the tools offered to the model in `chat_with_tools`
+pub trait ToolExecutor {
+    /// Tools to declare to the model
+    fn specs(&self) -> Vec<ToolSpec>;
+
+    /// Run one tool call. Failures should be reported inside the returned JSON
+    /// (e.g. `{"error": "..."}`) so the model can recover.
+    fn execute(&self, call: &ToolCall) -> impl Future<Output = serde_json::Value> + Send;
+}
+
 pub struct AgentService {
     llm: Arc<LlmService>,
+    /// Token usage of every structured call is written to `usage_log`
+    db: DbPool,
 }
 
 impl AgentService {
-    pub fn new(llm: Arc<LlmService>) -> Self {
-        Self { llm }
+    pub fn new(llm: Arc<LlmService>, db: DbPool) -> Self {
+        Self { llm, db }
+    }
+
+    /// Structured LLM call that records its token usage under `operation`
+    #[allow(clippy::too_many_arguments)]
+    async fn generate_structured_json(
+        &self,
+        operation: UsageOperation,
+        provider: &str,
+        model: &str,
+        provider_config: &ProviderConfig,
+        prompt: &str,
+        schema: serde_json::Value,
+        file_path: Option<&str>,
+        thinking_effort: Option<&str>,
+    ) -> Result<String, LlmError> {
+        let (output, usage) = self
+            .llm
+            .generate_structured_json_with_usage(
+                provider,
+                model,
+                provider_config,
+                prompt,
+                schema,
+                file_path,
+                thinking_effort,
+            )
+            .await?;
+        if let Some(usage) = usage {
+            record_usage(&self.db, provider, model, operation, &usage, None).await;
+        }
+        Ok(output)
     }
 
     pub async fn summarize(
@@ -27,18 +82,29 @@ impl AgentService {
         max_length: i32,
         file_path: Option<&str>,
         resource_subtype: Option<&str>,
-    ) -> Result<String, String> {
+        language_mode: SummaryLanguageMode,
+    ) -> Result<String, LlmError> {
         let content = content.trim();
         let max_length = std::cmp::max(min_length, max_length);
         let should_use_file = file_path.is_some() && resource_subtype != Some("text");
+        let is_notebook = resource_subtype == Some("notebook");
+        let language = current_language();
 
-        let prompt = build_summary_prompt(content, user_note, max_length, should_use_file);
+        let prompt = build_summary_prompt(
+            content,
+            user_note,
+            max_length,
+            should_use_file,
+            is_notebook,
+            language_mode,
+            language,
+        );
         let schema = summary_schema();
 
         let response = if should_use_file {
             match self
-                .llm
                 .generate_structured_json(
+                    UsageOperation::Summary,
                     provider,
                     model,
                     provider_config,
@@ -52,13 +118,20 @@ impl AgentService {
                 Ok(result) => result,
                 Err(err) => {
                     if content.is_empty() {
-                        return Err(format!(
-                            "file upload failed and no content fallback: {err}"
-                        ));
+                        return Err(err.context("file upload failed and no content fallback"));
                     }
-                    let fallback_prompt = build_summary_prompt(content, user_note, max_length, false);
-                    self.llm
+                    let fallback_prompt = build_summary_prompt(
+                        content,
+                        user_note,
+                        max_length,
+                        false,
+                        is_notebook,
+                        language_mode,
+                        language,
+                    );
+                    self
                         .generate_structured_json(
+                            UsageOperation::Summary,
                             provider,
                             model,
                             provider_config,
@@ -68,15 +141,16 @@ impl AgentService {
                             None,
                         )
                         .await
-                        .map_err(|e| format!("summary fallback failed: {e}"))?
+                        .map_err(|e| e.context("summary fallback failed"))?
                 }
             }
         } else {
             if content.is_empty() {
                 return Ok(String::new());
             }
-            self.llm
+            self
                 .generate_structured_json(
+                    UsageOperation::Summary,
                     provider,
                     model,
                     provider_config,
@@ -86,15 +160,20 @@ impl AgentService {
                     None,
                 )
                 .await
-                .map_err(|e| format!("summary request failed: {e}"))?
+                .map_err(|e| e.context("summary request failed"))?
         };
 
         let summary: SummaryResponse = serde_json::from_str(&response)
             .map_err(|e| format!("summary parse failed: {e}"))?;
         let trimmed = summary.summary.trim();
         let mut result = trimmed.to_string();
-        if result.chars().count() > max_length as usize {
-            result = result.chars().take(max_length as usize).collect();
+        // 双语摘要每种语言各占一份长度
+        let limit = match language_mode {
+            SummaryLanguageMode::Bilingual => max_length as usize * 2,
+            _ => max_length as usize,
+        };
+        if result.chars().count() > limit {
+            result = result.chars().take(limit).collect();
         }
         Ok(result)
     }
@@ -120,8 +199,8 @@ impl AgentService {
         let schema = chat_session_schema();
 
         let response = self
-            .llm
             .generate_structured_json(
+                UsageOperation::ChatSummary,
                 provider,
                 model,
                 provider_config,
@@ -142,12 +221,48 @@ impl AgentService {
         Ok((title, summary))
     }
 
+    /// Fold older chat turns (and the previous history summary, if any) into one summary
+    /// that replaces them in later requests
+    pub async fn summarize_chat_history(
+        &self,
+        provider: &str,
+        model: &str,
+        provider_config: &ProviderConfig,
+        previous_summary: Option<&str>,
+        transcript: &str,
+        max_length: i32,
+    ) -> Result<String, String> {
+        let prompt = build_chat_history_prompt(previous_summary, transcript, max_length);
+        let response = self
+            .generate_structured_json(
+                UsageOperation::ChatSummary,
+                provider,
+                model,
+                provider_config,
+                &prompt,
+                summary_schema(),
+                None,
+                None,
+            )
+            .await
+            .map_err(|e| format!("chat history summary request failed: {e}"))?;
+
+        let parsed: SummaryResponse = serde_json::from_str(&response)
+            .map_err(|e| format!("chat history summary parse failed: {e}"))?;
+        let summary = clamp_text(&parsed.summary, max_length);
+        if summary.is_empty() {
+            return Err("chat history summary empty".to_string());
+        }
+        Ok(summary)
+    }
+
     pub async fn classify_topic(
         &self,
         provider: &str,
         model: &str,
         provider_config: &ProviderConfig,
         resource_summary: &str,
+        hint: Option<&str>,
         candidates: Vec<TopicCandidate>,
     ) -> Result<ClassifyTopicResponse, String> {
         let summary = resource_summary.trim();
@@ -164,12 +279,12 @@ impl AgentService {
             });
         }
 
-        let prompt = build_classify_prompt(summary, &candidates);
+        let prompt = build_classify_prompt(summary, hint, &candidates);
         let schema = classify_schema();
 
         let response = self
-            .llm
             .generate_structured_json(
+                UsageOperation::Classify,
                 provider,
                 model,
                 provider_config,
@@ -186,6 +301,305 @@ impl AgentService {
 
         Ok(clamp_confidence(parsed))
     }
+
+    /// 从论文首页文本（或 PDF 文件）中抽取引用元数据
+    pub async fn extract_citation(
+        &self,
+        provider: &str,
+        model: &str,
+        provider_config: &ProviderConfig,
+        content: &str,
+        file_path: Option<&str>,
+    ) -> Result<CitationDraft, String> {
+        let content = content.trim();
+        let use_file = content.is_empty() && file_path.is_some();
+        if content.is_empty() && !use_file {
+            return Err("citation content empty".to_string());
+        }
+
+        let prompt = build_citation_prompt(content, use_file);
+        let response = self
+            .generate_structured_json(
+                UsageOperation::Citation,
+                provider,
+                model,
+                provider_config,
+                &prompt,
+                citation_schema(),
+                if use_file { file_path } else { None },
+                None,
+            )
+            .await
+            .map_err(|e| format!("citation request failed: {e}"))?;
+
+        serde_json::from_str(&response).map_err(|e| format!("citation parse failed: {e}"))
+    }
+
+    /// 根据资源摘要建议 3–5 个标签，优先复用已有标签
+    pub async fn suggest_tags(
+        &self,
+        provider: &str,
+        model: &str,
+        provider_config: &ProviderConfig,
+        resource_summary: &str,
+        existing_tags: &[String],
+    ) -> Result<Vec<String>, String> {
+        let summary = resource_summary.trim();
+        if summary.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let response = self
+            .generate_structured_json(
+                UsageOperation::TagSuggestion,
+                provider,
+                model,
+                provider_config,
+                &build_tag_suggestion_prompt(summary, existing_tags),
+                tag_suggestion_schema(),
+                None,
+                None,
+            )
+            .await
+            .map_err(|e| format!("tag suggestion request failed: {e}"))?;
+
+        let parsed: TagSuggestionResponse = serde_json::from_str(&response)
+            .map_err(|e| format!("tag suggestion parse failed: {e}"))?;
+        Ok(parsed.tags)
+    }
+
+    /// 为没有标题的捕获内容起一个简短的描述性标题
+    pub async fn generate_title(
+        &self,
+        provider: &str,
+        model: &str,
+        provider_config: &ProviderConfig,
+        content: &str,
+        summary: &str,
+    ) -> Result<String, String> {
+        let response = self
+            .generate_structured_json(
+                UsageOperation::Title,
+                provider,
+                model,
+                provider_config,
+                &build_title_prompt(content, summary),
+                title_schema(),
+                None,
+                None,
+            )
+            .await
+            .map_err(|e| format!("title request failed: {e}"))?;
+
+        let parsed: TitleResponse =
+            serde_json::from_str(&response).map_err(|e| format!("title parse failed: {e}"))?;
+        Ok(parsed.title.trim().to_string())
+    }
+
+    /// 上传音频文件并转写为文本（本地 whisper 不可用时使用）
+    pub async fn transcribe_audio(
+        &self,
+        provider: &str,
+        model: &str,
+        provider_config: &ProviderConfig,
+        file_path: &str,
+    ) -> Result<String, LlmError> {
+        let response = self
+            .generate_structured_json(
+                UsageOperation::Transcribe,
+                provider,
+                model,
+                provider_config,
+                &build_transcript_prompt(),
+                transcript_schema(),
+                Some(file_path),
+                None,
+            )
+            .await
+            .map_err(|e| e.context("transcription request failed"))?;
+
+        let parsed: TranscriptResponse = serde_json::from_str(&response)
+            .map_err(|e| format!("transcription parse failed: {e}"))?;
+        Ok(parsed.transcript.trim().to_string())
+    }
+
+    /// 根据周期内的主题活动起草回顾（进展 / 停滞 / 下一步建议）
+    pub async fn draft_goal_review(
+        &self,
+        provider: &str,
+        model: &str,
+        provider_config: &ProviderConfig,
+        period: &str,
+        activity: &str,
+    ) -> Result<GoalReviewDraft, String> {
+        let activity = activity.trim();
+        if activity.is_empty() {
+            return Err("goal review activity empty".to_string());
+        }
+
+        let prompt = build_goal_review_prompt(period, activity);
+        let schema = goal_review_schema();
+
+        let response = self
+            .generate_structured_json(
+                UsageOperation::GoalReview,
+                provider,
+                model,
+                provider_config,
+                &prompt,
+                schema,
+                None,
+                None,
+            )
+            .await
+            .map_err(|e| format!("goal review request failed: {e}"))?;
+
+        let mut parsed: GoalReviewDraft = serde_json::from_str(&response)
+            .map_err(|e| format!("goal review parse failed: {e}"))?;
+        parsed.summary = parsed.summary.trim().to_string();
+        for items in [&mut parsed.progressed, &mut parsed.stalled, &mut parsed.next_actions] {
+            items.retain(|item| !item.trim().is_empty());
+        }
+        Ok(parsed)
+    }
+
+    /// 分析整棵主题树，建议重命名 / 移动 / 合并操作（结果需由调用方校验）
+    pub async fn plan_topic_restructure(
+        &self,
+        provider: &str,
+        model: &str,
+        provider_config: &ProviderConfig,
+        topic_tree: &str,
+    ) -> Result<Vec<TopicOperationDraft>, String> {
+        let topic_tree = topic_tree.trim();
+        if topic_tree.is_empty() {
+            return Err("topic tree empty".to_string());
+        }
+
+        let prompt = build_topic_restructure_prompt(topic_tree);
+        let schema = topic_restructure_schema();
+
+        let response = self
+            .generate_structured_json(
+                UsageOperation::Restructure,
+                provider,
+                model,
+                provider_config,
+                &prompt,
+                schema,
+                None,
+                None,
+            )
+            .await
+            .map_err(|e| format!("topic restructure request failed: {e}"))?;
+
+        let parsed: TopicRestructureResponse = serde_json::from_str(&response)
+            .map_err(|e| format!("topic restructure parse failed: {e}"))?;
+        Ok(parsed.operations)
+    }
+
+    /// 为收件箱中的一批资源建议处理方式（结果需由调用方校验）
+    pub async fn triage_inbox(
+        &self,
+        provider: &str,
+        model: &str,
+        provider_config: &ProviderConfig,
+        topics: &str,
+        items: &str,
+    ) -> Result<Vec<InboxTriageDraft>, String> {
+        let items = items.trim();
+        if items.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let response = self
+            .generate_structured_json(
+                UsageOperation::Triage,
+                provider,
+                model,
+                provider_config,
+                &build_inbox_triage_prompt(topics, items),
+                inbox_triage_schema(),
+                None,
+                None,
+            )
+            .await
+            .map_err(|e| format!("inbox triage request failed: {e}"))?;
+
+        let parsed: InboxTriageResponse = serde_json::from_str(&response)
+            .map_err(|e| format!("inbox triage parse failed: {e}"))?;
+        Ok(parsed.items)
+    }
+
+    /// Streamed chat where the model may call tools.
+    ///
+    /// Each model turn is streamed through `on_event`. When the turn contains tool calls,
+    /// a `ToolCall` event is emitted per call, the tools run, and their results are sent
+    /// back as the next turn. The loop ends when the model answers without calling tools.
+    pub async fn chat_with_tools<E, F, Fut>(
+        &self,
+        provider: &str,
+        model: &str,
+        provider_config: &ProviderConfig,
+        messages: &[ChatMessage],
+        executor: &E,
+        thinking_effort: Option<&str>,
+        mut on_event: F,
+    ) -> Result<(), String>
+    where
+        E: ToolExecutor,
+        F: FnMut(ChatStreamEvent) -> Fut,
+        Fut: Future<Output = Result<(), String>>,
+    {
+        let specs = executor.specs();
+        let mut messages = messages.to_vec();
+
+        for _ in 0..MAX_TOOL_ROUNDS {
+            let turn = self
+                .llm
+                .stream_chat_with_tools(
+                    provider,
+                    model,
+                    provider_config,
+                    &messages,
+                    &specs,
+                    thinking_effort,
+                    &mut on_event,
+                )
+                .await?;
+            if turn.tool_calls.is_empty() {
+                return Ok(());
+            }
+
+            let mut results = Vec::with_capacity(turn.tool_calls.len());
+            for call in &turn.tool_calls {
+                on_event(ChatStreamEvent::ToolCall(call.clone())).await?;
+                results.push(ToolResult {
+                    id: call.id.clone(),
+                    name: call.name.clone(),
+                    response: executor.execute(call).await,
+                });
+            }
+            messages.push(turn);
+            messages.push(ChatMessage::tool_results(results));
+        }
+
+        Err(format!(
+            "model kept calling tools after {MAX_TOOL_ROUNDS} turns"
+        ))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TopicRestructureResponse {
+    #[serde(default)]
+    operations: Vec<TopicOperationDraft>,
+}
+
+#[derive(Debug, Deserialize)]
+struct InboxTriageResponse {
+    #[serde(default)]
+    items: Vec<InboxTriageDraft>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -193,6 +607,22 @@ struct SummaryResponse {
     summary: String,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+struct TranscriptResponse {
+    transcript: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TitleResponse {
+    title: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TagSuggestionResponse {
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct ChatSessionSummaryResponse {
     title: String,
@@ -204,12 +634,24 @@ fn build_summary_prompt(
     user_note: Option<&str>,
     max_length: i32,
     use_file: bool,
+    is_notebook: bool,
+    language_mode: SummaryLanguageMode,
+    language: Language,
 ) -> String {
     let mut lines = vec![
         "你是知识库助手，请根据用户提供的内容生成简洁摘要。".to_string(),
         String::new(),
-        format!("请生成不超过 {} 字的中文摘要。", max_length),
+        summary_language_instruction(language_mode, language, max_length),
+        "如需引用公式或代码，保持 LaTeX 公式（$...$、$$...$$）与代码片段原样完整，不要改写或截断。"
+            .to_string(),
     ];
+    if is_notebook {
+        lines.push(
+            "内容是 Jupyter 笔记本（Markdown 单元格、代码块及其输出摘要）：请说明研究的问题、\
+             使用的数据与方法（关键库、模型或算法）、主要结果与结论；代码只概括其作用，不要逐行复述。"
+                .to_string(),
+        );
+    }
     if let Some(note) = user_note {
         if !note.trim().is_empty() {
             lines.push(format!(
@@ -224,6 +666,30 @@ fn build_summary_prompt(
     lines.join("\n")
 }
 
+fn summary_language_instruction(
+    mode: SummaryLanguageMode,
+    language: Language,
+    max_length: i32,
+) -> String {
+    let language_name = match language {
+        Language::Zh => "中文",
+        Language::En => "英文",
+    };
+    match mode {
+        SummaryLanguageMode::Configured => {
+            format!("请生成不超过 {max_length} 字的{language_name}摘要。")
+        }
+        SummaryLanguageMode::MatchSource => format!(
+            "请使用与原文相同的语言生成不超过 {max_length} 字的摘要；\
+             原文混用多种语言时使用占比最多的语言。"
+        ),
+        SummaryLanguageMode::Bilingual => format!(
+            "请先用原文语言写一段不超过 {max_length} 字的摘要，换行后再写一段相同内容的\
+             {language_name}摘要（同样不超过 {max_length} 字）；原文本身是{language_name}时只写一段。"
+        ),
+    }
+}
+
 fn summary_schema() -> serde_json::Value {
     json!({
         "type": "object",
@@ -254,6 +720,27 @@ fn build_chat_session_prompt(
     lines.join("\n")
 }
 
+fn build_chat_history_prompt(
+    previous_summary: Option<&str>,
+    transcript: &str,
+    max_length: i32,
+) -> String {
+    let mut lines = vec![
+        "你是对话压缩助手，请把下面较早的对话压缩为一段摘要，后续对话只会看到这段摘要。".to_string(),
+        format!("摘要不超过 {} 字，使用对话所用的语言。", max_length),
+        "保留用户的目标与偏好、已确认的事实和结论、做出的决定、仍未解决的问题；\
+         省略寒暄与重复内容。"
+            .to_string(),
+        String::new(),
+    ];
+    if let Some(summary) = previous_summary.filter(|summary| !summary.trim().is_empty()) {
+        lines.push(format!("此前的对话摘要：{}", summary.trim()));
+        lines.push(String::new());
+    }
+    lines.push(format!("需要压缩的对话：\n{}", transcript));
+    lines.join("\n")
+}
+
 fn chat_session_schema() -> serde_json::Value {
     json!({
         "type": "object",
@@ -271,15 +758,22 @@ fn chat_session_schema() -> serde_json::Value {
     })
 }
 
-fn build_classify_prompt(summary: &str, candidates: &[TopicCandidate]) -> String {
+fn build_classify_prompt(
+    summary: &str,
+    hint: Option<&str>,
+    candidates: &[TopicCandidate],
+) -> String {
     let mut lines = vec![
         "你是知识库主题分类助手，根据候选主题判断归属或创建新主题，必要时重构层级。"
             .to_string(),
         String::new(),
         format!("新资源摘要: \"{}\"", summary),
-        String::new(),
-        "候选主题 (node_id, title, summary, parents):".to_string(),
     ];
+    if let Some(hint) = hint.map(str::trim).filter(|hint| !hint.is_empty()) {
+        lines.push(format!("用户提示（优先遵循）: \"{}\"", hint));
+    }
+    lines.push(String::new());
+    lines.push("候选主题 (node_id, title, summary, parents):".to_string());
 
     if candidates.is_empty() {
         lines.push("（无）".to_string());
@@ -375,6 +869,242 @@ fn classify_schema() -> serde_json::Value {
     })
 }
 
+fn build_citation_prompt(content: &str, use_file: bool) -> String {
+    let mut lines = vec![
+        "你是文献管理助手，请判断文档是否为学术文献（论文、预印本、学位论文、技术报告等），".to_string(),
+        "如果是，抽取其引用信息：标题、作者（按署名顺序，保留原文写法）、年份、发表期刊或会议、DOI。".to_string(),
+        "无法确定的字段返回 null，不要猜测；不是学术文献时 is_academic 返回 false。".to_string(),
+    ];
+    if !use_file {
+        lines.push(String::new());
+        lines.push(format!("文档开头：{}", content));
+    }
+    lines.join("\n")
+}
+
+fn citation_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "is_academic": { "type": "boolean" },
+            "title": { "type": ["string", "null"] },
+            "authors": {
+                "type": "array",
+                "items": { "type": "string" }
+            },
+            "year": { "type": ["integer", "null"] },
+            "venue": { "type": ["string", "null"] },
+            "doi": { "type": ["string", "null"] }
+        },
+        "required": ["is_academic", "title", "authors", "year", "venue", "doi"]
+    })
+}
+
+fn build_tag_suggestion_prompt(summary: &str, existing_tags: &[String]) -> String {
+    let mut lines = vec![
+        "你是个人知识库的整理助手，请根据资源摘要为它建议 3 到 5 个标签。".to_string(),
+        "标签应简短（一般不超过 4 个词），概括主题、领域或用途，不要照抄摘要原句。".to_string(),
+        "含义相同时优先使用已有标签的原写法，不要为同一概念另造近义标签。".to_string(),
+    ];
+    if !existing_tags.is_empty() {
+        lines.push(format!("已有标签：{}", existing_tags.join("、")));
+    }
+    lines.push(String::new());
+    lines.push(format!("资源摘要：{}", summary));
+    lines.join("\n")
+}
+
+fn tag_suggestion_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "tags": {
+                "type": "array",
+                "items": { "type": "string" },
+                "description": "3 到 5 个标签"
+            }
+        },
+        "required": ["tags"]
+    })
+}
+
+fn build_title_prompt(content: &str, summary: &str) -> String {
+    let mut lines = vec![
+        "请为下面这段用户随手保存的内容起一个简短的描述性标题。".to_string(),
+        "标题使用内容的原语言，不超过 20 个字（英文不超过 8 个词），概括主题而不是照抄开头，不要加引号或句末标点。"
+            .to_string(),
+        String::new(),
+    ];
+    if !summary.trim().is_empty() {
+        lines.push(format!("摘要：{}", summary.trim()));
+    }
+    lines.push(format!("内容：{}", content));
+    lines.join("\n")
+}
+
+fn title_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "title": { "type": "string" }
+        },
+        "required": ["title"]
+    })
+}
+
+fn build_transcript_prompt() -> String {
+    let lines = [
+        "请逐字转写这段音频中的语音，保持原语言，不要翻译、总结或补充内容。",
+        "按说话的自然停顿分段，每段一行；有多位说话人时可在行首标注（如“说话人 1：”）。",
+        "没有可识别的语音时 transcript 返回空字符串。",
+    ];
+    lines.join("\n")
+}
+
+fn transcript_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "transcript": { "type": "string" }
+        },
+        "required": ["transcript"]
+    })
+}
+
+fn build_goal_review_prompt(period: &str, activity: &str) -> String {
+    let lines = vec![
+        format!("你是用户的个人知识库助手，请根据 {} 的主题活动为用户写一份月度回顾。", period),
+        "回顾包括：一句话总结、取得进展的方面、停滞的方面、下个月建议的具体行动。".to_string(),
+        "只依据给出的数据，不要编造；每条不超过 50 字，建议行动不超过 5 条。".to_string(),
+        String::new(),
+        "主题活动：".to_string(),
+        activity.to_string(),
+    ];
+    lines.join("\n")
+}
+
+fn goal_review_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "summary": { "type": "string", "description": "一句话总结" },
+            "progressed": {
+                "type": "array",
+                "items": { "type": "string" },
+                "description": "取得进展的方面"
+            },
+            "stalled": {
+                "type": "array",
+                "items": { "type": "string" },
+                "description": "停滞的方面"
+            },
+            "next_actions": {
+                "type": "array",
+                "items": { "type": "string" },
+                "description": "建议的下一步行动"
+            }
+        },
+        "required": ["summary", "progressed", "stalled", "next_actions"]
+    })
+}
+
+fn build_topic_restructure_prompt(topic_tree: &str) -> String {
+    let lines = vec![
+        "你是用户的个人知识库助手，请审视下面的主题树，提出让层级更清晰的调整建议。".to_string(),
+        "可用操作：".to_string(),
+        "- rename：主题标题含糊或风格不统一时重命名（填写 new_title）".to_string(),
+        "- move：主题应挂到另一个主题下（填写 new_parent_id，移到根层级则为 null）".to_string(),
+        "- merge：两个主题内容重复时，把 topic_id 合并进 target_topic_id".to_string(),
+        "只使用树中出现的 id；不要为了调整而调整，结构合理时返回空列表；每条操作附一句理由。"
+            .to_string(),
+        String::new(),
+        "主题树（缩进表示层级，括号内为 id 与直接包含的条目数）：".to_string(),
+        topic_tree.to_string(),
+    ];
+    lines.join("\n")
+}
+
+fn topic_restructure_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "operations": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "op": { "type": "string", "enum": ["rename", "move", "merge"] },
+                        "topic_id": { "type": "integer" },
+                        "new_title": { "type": ["string", "null"] },
+                        "new_parent_id": { "type": ["integer", "null"] },
+                        "target_topic_id": { "type": ["integer", "null"] },
+                        "reason": { "type": "string" }
+                    },
+                    "required": [
+                        "op",
+                        "topic_id",
+                        "new_title",
+                        "new_parent_id",
+                        "target_topic_id",
+                        "reason"
+                    ]
+                }
+            }
+        },
+        "required": ["operations"]
+    })
+}
+
+fn build_inbox_triage_prompt(topics: &str, items: &str) -> String {
+    let lines = vec![
+        "你是用户的个人知识库助手，请帮用户清理收件箱：为下面每个资源建议一种处理方式。"
+            .to_string(),
+        "可用操作：".to_string(),
+        "- link_topic：归入一个已有主题（填写 topic_id，只能使用主题列表中的 id）".to_string(),
+        "- convert_to_task：内容是待办事项或需要跟进的事情时转为任务".to_string(),
+        "- archive：有保留价值但已过时、不需要再被检索到".to_string(),
+        "- delete：明显无用（重复、空白、误捕获的片段）".to_string(),
+        "- keep：无法判断或应保持原样".to_string(),
+        "拿不准时选 keep，不要轻易建议 delete；每条附一句理由。".to_string(),
+        String::new(),
+        "主题列表：".to_string(),
+        if topics.trim().is_empty() {
+            "（暂无主题）".to_string()
+        } else {
+            topics.to_string()
+        },
+        String::new(),
+        "收件箱资源：".to_string(),
+        items.to_string(),
+    ];
+    lines.join("\n")
+}
+
+fn inbox_triage_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "items": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "node_id": { "type": "integer" },
+                        "action": {
+                            "type": "string",
+                            "enum": ["link_topic", "convert_to_task", "archive", "delete", "keep"]
+                        },
+                        "topic_id": { "type": ["integer", "null"] },
+                        "reason": { "type": "string" }
+                    },
+                    "required": ["node_id", "action", "topic_id", "reason"]
+                }
+            }
+        },
+        "required": ["items"]
+    })
+}
+
 fn clamp_text(text: &str, max_length: i32) -> String {
     let trimmed = text.trim();
     if trimmed.is_empty() {
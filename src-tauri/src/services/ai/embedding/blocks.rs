@@ -0,0 +1,393 @@
+//! Block-aware chunking
+//!
+//! Fenced code blocks and display formulas (`$$ … $$`, `\[ … \]`,
+//! `\begin{env} … \end{env}`) are treated as atomic: prose around them goes
+//! through the regular splitter, the blocks themselves are never cut in the
+//! middle. Oversized code blocks are split on line boundaries and each part is
+//! re-wrapped in its fence so it still renders as code; other oversized blocks
+//! fall back to the prose splitter.
+//!
+//! An unterminated code fence runs to the end of the text, as in Markdown. An
+//! unterminated formula opener (a stray `$$` in PDF-extracted text) is prose.
+//! `\begin{document}` wraps a whole LaTeX file and is not a block.
+
+/// A region of the source text
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct TextBlock<'a> {
+    pub text: &'a str,
+    pub atomic: bool,
+}
+
+enum Closer {
+    /// Fence char and minimum run length
+    Fence(char, usize),
+    Delimiter(&'static str),
+    End(String),
+}
+
+impl Closer {
+    fn closes(&self, line: &str) -> bool {
+        let trimmed = line.trim();
+        match self {
+            Closer::Fence(ch, len) => {
+                trimmed.chars().count() >= *len && trimmed.chars().all(|c| c == *ch)
+            }
+            Closer::Delimiter(delim) => trimmed.contains(delim),
+            Closer::End(end) => trimmed.contains(end.as_str()),
+        }
+    }
+}
+
+/// Detect a block opener; returns the closer and whether the opening line closes itself
+fn block_opener(line: &str) -> Option<(Closer, bool)> {
+    let trimmed = line.trim_start();
+
+    for fence in ['`', '~'] {
+        let run = trimmed.chars().take_while(|c| *c == fence).count();
+        if run >= 3 {
+            return Some((Closer::Fence(fence, run), false));
+        }
+    }
+
+    for (open, close) in [("$$", "$$"), ("\\[", "\\]")] {
+        if let Some(rest) = trimmed.strip_prefix(open) {
+            return Some((Closer::Delimiter(close), rest.contains(close)));
+        }
+    }
+
+    let env = trimmed
+        .strip_prefix("\\begin{")
+        .and_then(|rest| rest.split_once('}'))
+        .map(|(env, _)| env)
+        .filter(|env| *env != "document")?;
+    let end = format!("\\end{{{env}}}");
+    let closed = trimmed.contains(end.as_str());
+    Some((Closer::End(end), closed))
+}
+
+/// Split text into prose regions and atomic blocks, in source order
+pub(crate) fn split_protected_blocks(text: &str) -> Vec<TextBlock<'_>> {
+    let mut lines = Vec::new();
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        lines.push((offset, offset + line.len()));
+        offset += line.len();
+    }
+
+    let mut blocks = Vec::new();
+    let mut prose_start = 0;
+    let mut idx = 0;
+    while idx < lines.len() {
+        let (start, end) = lines[idx];
+        let Some((closer, closed_inline)) = block_opener(&text[start..end]) else {
+            idx += 1;
+            continue;
+        };
+
+        let close_idx = if closed_inline {
+            Some(idx)
+        } else {
+            (idx + 1..lines.len()).find(|&j| closer.closes(&text[lines[j].0..lines[j].1]))
+        };
+        let close_idx = match (close_idx, &closer) {
+            (Some(close_idx), _) => close_idx,
+            // An unterminated fence runs to the end of the text
+            (None, Closer::Fence(..)) => lines.len() - 1,
+            // An unterminated formula opener is ordinary prose
+            (None, _) => {
+                idx += 1;
+                continue;
+            }
+        };
+        let block_end = lines[close_idx].1;
+
+        push_block(&mut blocks, &text[prose_start..start], false);
+        push_block(&mut blocks, &text[start..block_end], true);
+        prose_start = block_end;
+        idx = close_idx + 1;
+    }
+    push_block(&mut blocks, &text[prose_start..], false);
+
+    blocks
+}
+
+fn push_block<'a>(blocks: &mut Vec<TextBlock<'a>>, text: &'a str, atomic: bool) {
+    let text = text.trim_matches(|c: char| c == '\n' || c == '\r');
+    if !text.trim().is_empty() {
+        blocks.push(TextBlock {
+            text: text.trim_end(),
+            atomic,
+        });
+    }
+}
+
+/// Chunk text without cutting through protected blocks.
+///
+/// `split_prose` chunks plain prose (the configured splitter), `count_tokens`
+/// measures a candidate chunk. Small pieces from *different* regions are merged
+/// up to `max_tokens`; pieces of the same prose region are left as the splitter
+/// produced them so its overlap is not duplicated.
+pub(crate) fn chunk_preserving_blocks<'a, S, C>(
+    text: &'a str,
+    max_tokens: usize,
+    split_prose: S,
+    count_tokens: C,
+) -> Vec<String>
+where
+    S: Fn(&'a str) -> Vec<&'a str>,
+    C: Fn(&str) -> usize,
+{
+    let mut pieces: Vec<(usize, String)> = Vec::new();
+    for (region, block) in split_protected_blocks(text).into_iter().enumerate() {
+        if !block.atomic {
+            pieces.extend(
+                split_prose(block.text)
+                    .into_iter()
+                    .map(|piece| (region, piece.to_string())),
+            );
+        } else if count_tokens(block.text) > max_tokens {
+            if is_fenced_code(block.text) {
+                pieces.extend(
+                    split_code_block(block.text, max_tokens, &count_tokens)
+                        .into_iter()
+                        .map(|piece| (region, piece)),
+                );
+            } else {
+                pieces.extend(
+                    split_prose(block.text)
+                        .into_iter()
+                        .map(|piece| (region, piece.to_string())),
+                );
+            }
+        } else {
+            pieces.push((region, block.text.to_string()));
+        }
+    }
+
+    let mut chunks: Vec<String> = Vec::new();
+    let mut last_region = None;
+    for (region, piece) in pieces {
+        if let (Some(current), Some(prev)) = (chunks.last_mut(), last_region) {
+            if prev != region {
+                let merged = format!("{current}\n\n{piece}");
+                if count_tokens(&merged) <= max_tokens {
+                    *current = merged;
+                    last_region = Some(region);
+                    continue;
+                }
+            }
+        }
+        chunks.push(piece);
+        last_region = Some(region);
+    }
+    chunks
+}
+
+fn is_fenced_code(block: &str) -> bool {
+    let trimmed = block.trim_start();
+    trimmed.starts_with("```") || trimmed.starts_with("~~~")
+}
+
+/// Split an oversized fenced code block on line boundaries, keeping fences on every part
+fn split_code_block<C>(block: &str, max_tokens: usize, count_tokens: &C) -> Vec<String>
+where
+    C: Fn(&str) -> usize,
+{
+    let lines: Vec<&str> = block.lines().collect();
+    let open = lines.first().copied().unwrap_or_default();
+    let fence: String = open
+        .trim_start()
+        .chars()
+        .take_while(|c| *c == '`' || *c == '~')
+        .collect();
+    let body_end = match lines.last() {
+        Some(last) if lines.len() > 1 && last.trim() == fence => lines.len() - 1,
+        _ => lines.len(),
+    };
+    let body = lines.get(1..body_end).unwrap_or_default();
+
+    let wrap = |body: &[&str]| format!("{open}\n{}\n{fence}", body.join("\n"));
+    let mut parts = Vec::new();
+    let mut start = 0;
+    for end in 1..=body.len() {
+        if end - start > 1 && count_tokens(&wrap(&body[start..end])) > max_tokens {
+            parts.push(wrap(&body[start..end - 1]));
+            start = end - 1;
+        }
+    }
+    if start < body.len() || parts.is_empty() {
+        parts.push(wrap(&body[start..]));
+    }
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(text: &str) -> usize {
+        text.split_whitespace().count()
+    }
+
+    /// Naive prose splitter: fixed windows of `n` words, ignoring structure
+    fn split_words(n: usize) -> impl Fn(&str) -> Vec<&str> {
+        move |text: &str| {
+            let mut pieces = Vec::new();
+            let mut start = None;
+            let mut count = 0;
+            let mut last_end = 0;
+            for (idx, word) in text.split_whitespace().map(|w| {
+                let offset = w.as_ptr() as usize - text.as_ptr() as usize;
+                (offset, w)
+            }) {
+                if start.is_none() {
+                    start = Some(idx);
+                }
+                count += 1;
+                last_end = idx + word.len();
+                if count == n {
+                    pieces.push(&text[start.unwrap()..last_end]);
+                    start = None;
+                    count = 0;
+                }
+            }
+            if let Some(start) = start {
+                pieces.push(&text[start..last_end]);
+            }
+            pieces
+        }
+    }
+
+    #[test]
+    fn test_split_protected_blocks_markdown() {
+        let text = "Intro text.\n\n```rust\nfn main() {\n\n    println!(\"hi\");\n}\n```\nAfter code.\n$$\nE = mc^2\n$$\nEnd.";
+        let blocks = split_protected_blocks(text);
+        assert_eq!(
+            blocks,
+            vec![
+                TextBlock {
+                    text: "Intro text.",
+                    atomic: false
+                },
+                TextBlock {
+                    text: "```rust\nfn main() {\n\n    println!(\"hi\");\n}\n```",
+                    atomic: true
+                },
+                TextBlock {
+                    text: "After code.",
+                    atomic: false
+                },
+                TextBlock {
+                    text: "$$\nE = mc^2\n$$",
+                    atomic: true
+                },
+                TextBlock {
+                    text: "End.",
+                    atomic: false
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_protected_blocks_latex_envs() {
+        let text =
+            "See below\n\\begin{align}\na &= b + c \\\\\nd &= e\n\\end{align}\n\\[ x^2 \\]\ntext";
+        let blocks = split_protected_blocks(text);
+        assert_eq!(blocks.len(), 4);
+        assert_eq!(
+            blocks[1].text,
+            "\\begin{align}\na &= b + c \\\\\nd &= e\n\\end{align}"
+        );
+        assert!(blocks[1].atomic);
+        assert_eq!(blocks[2].text, "\\[ x^2 \\]");
+        assert!(blocks[2].atomic);
+    }
+
+    #[test]
+    fn test_unterminated_fence_runs_to_end() {
+        let blocks = split_protected_blocks("text\n```\ncode line\nmore code");
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[1].text, "```\ncode line\nmore code");
+        assert!(blocks[1].atomic);
+    }
+
+    #[test]
+    fn test_unterminated_formula_is_prose() {
+        let text = "Price is $$5 today.\nMore text\n\\[ not closed\n\\begin{proof}\nno end";
+        let blocks = split_protected_blocks(text);
+        assert_eq!(
+            blocks,
+            vec![TextBlock {
+                text,
+                atomic: false
+            }]
+        );
+    }
+
+    #[test]
+    fn test_document_env_is_not_a_block() {
+        let text = "\\begin{document}\nIntro\n$$ x $$\nOutro\n\\end{document}";
+        let blocks = split_protected_blocks(text);
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[1].text, "$$ x $$");
+        assert!(blocks[1].atomic);
+        assert!(!blocks[0].atomic && !blocks[2].atomic);
+    }
+
+    #[test]
+    fn test_stray_dollars_do_not_swallow_the_rest() {
+        let prose = "word ".repeat(20);
+        let text = format!("{prose}\n$$ stray\n{prose}\n{prose}");
+        let chunks = chunk_preserving_blocks(&text, 8, split_words(8), words);
+        assert!(chunks.iter().all(|chunk| words(chunk) <= 8), "{chunks:?}");
+    }
+
+    #[test]
+    fn test_oversized_formula_falls_back_to_prose_splitter() {
+        let body = "a + b ".repeat(10);
+        let text = format!("\\begin{{align}}\n{body}\n\\end{{align}}");
+        let chunks = chunk_preserving_blocks(&text, 8, split_words(8), words);
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(|chunk| words(chunk) <= 8), "{chunks:?}");
+    }
+
+    #[test]
+    fn test_formula_never_split() {
+        // PDF-extracted text: formula in the middle of a long paragraph
+        let prose = "word ".repeat(12);
+        let formula = "$$\n\\mathcal{L} = - \\sum_{i=1}^{N} y_i \\log \\hat{y}_i + \\lambda \\lVert w \\rVert^2\n$$";
+        let text = format!("{prose}\n{formula}\n{prose}");
+        let chunks = chunk_preserving_blocks(&text, 8, split_words(8), words);
+
+        assert!(chunks.iter().any(|chunk| chunk.contains(formula)));
+        for chunk in &chunks {
+            assert_eq!(chunk.matches("$$").count() % 2, 0, "formula split: {chunk}");
+        }
+    }
+
+    #[test]
+    fn test_small_blocks_merge_with_neighbours() {
+        let text = "Short intro.\n```\nx = 1\n```\nShort outro.";
+        let chunks = chunk_preserving_blocks(text, 50, split_words(50), words);
+        assert_eq!(
+            chunks,
+            vec!["Short intro.\n\n```\nx = 1\n```\n\nShort outro."]
+        );
+    }
+
+    #[test]
+    fn test_oversized_code_block_keeps_fences() {
+        let body: Vec<String> = (0..10).map(|i| format!("let v{i} = {i};")).collect();
+        let text = format!("```rust\n{}\n```", body.join("\n"));
+        let chunks = chunk_preserving_blocks(&text, 14, split_words(14), words);
+
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk.starts_with("```rust\n"), "{chunk}");
+            assert!(chunk.ends_with("\n```"), "{chunk}");
+        }
+        let total_lines: usize = chunks.iter().map(|c| c.lines().count() - 2).sum();
+        assert_eq!(total_lines, 10);
+    }
+}
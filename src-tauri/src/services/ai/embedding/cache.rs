@@ -0,0 +1,82 @@
+//! In-memory vector cache keyed by (embedding model, embedding hash)
+//!
+//! Unchanged chunks of a node keep their LanceDB rows (see `diff`), so the cache only serves
+//! chunks that need a new row: identical text recurring across nodes (duplicated imports,
+//! shared boilerplate) is embedded once instead of per node. The model is part of the key
+//! because a reindex after switching models must not reuse vectors from the previous one.
+
+use std::collections::{HashMap, VecDeque};
+
+/// Upper bound on cached vectors (about 6 MB at 384 dimensions)
+pub(crate) const VECTOR_CACHE_CAPACITY: usize = 4096;
+
+type CacheKey = (String, String);
+
+#[derive(Debug)]
+pub(crate) struct VectorCache {
+    capacity: usize,
+    vectors: HashMap<CacheKey, Vec<f32>>,
+    /// Insertion order; the oldest entry is evicted first
+    order: VecDeque<CacheKey>,
+}
+
+impl VectorCache {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            vectors: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    pub(crate) fn get(&self, model: &str, hash: &str) -> Option<&Vec<f32>> {
+        self.vectors.get(&(model.to_string(), hash.to_string()))
+    }
+
+    pub(crate) fn insert(&mut self, model: &str, hash: &str, vector: Vec<f32>) {
+        if self.capacity == 0 {
+            return;
+        }
+        let key = (model.to_string(), hash.to_string());
+        if self.vectors.insert(key.clone(), vector).is_some() {
+            return;
+        }
+        self.order.push_back(key);
+        while self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.vectors.remove(&oldest);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_is_keyed_by_model_and_hash() {
+        let mut cache = VectorCache::new(4);
+        cache.insert("bge-small", "aaaa", vec![1.0]);
+        assert_eq!(cache.get("bge-small", "aaaa"), Some(&vec![1.0]));
+        assert_eq!(cache.get("bge-base", "aaaa"), None);
+        assert_eq!(cache.get("bge-small", "bbbb"), None);
+    }
+
+    #[test]
+    fn test_cache_evicts_oldest_entry() {
+        let mut cache = VectorCache::new(2);
+        cache.insert("m", "a", vec![1.0]);
+        cache.insert("m", "b", vec![2.0]);
+        // Re-inserting does not refresh the position of "a"
+        cache.insert("m", "a", vec![1.5]);
+        cache.insert("m", "c", vec![3.0]);
+        assert_eq!(cache.get("m", "a"), None);
+        assert_eq!(cache.get("m", "b"), Some(&vec![2.0]));
+        assert_eq!(cache.get("m", "c"), Some(&vec![3.0]));
+
+        let mut disabled = VectorCache::new(0);
+        disabled.insert("m", "a", vec![1.0]);
+        assert_eq!(disabled.get("m", "a"), None);
+    }
+}
@@ -0,0 +1,83 @@
+//! Chunk-level diff between stored vectors and freshly chunked text
+//!
+//! A stored row is kept only when the new chunk at the same index has the same embedding
+//! hash. Everything else is re-inserted (the vector itself usually comes from the cache) and
+//! the leftover rows are deleted.
+
+use std::collections::HashMap;
+
+/// Result of [`plan_chunk_sync`], indices refer to the input slices
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct ChunkSyncPlan {
+    /// (new chunk, stored row) pairs that stay untouched
+    pub keep: Vec<(usize, usize)>,
+    /// New chunks that need a row
+    pub insert: Vec<usize>,
+    /// Stored rows that are no longer referenced
+    pub stale: Vec<usize>,
+}
+
+/// Match new chunks against stored rows by (embedding hash, chunk index)
+pub(crate) fn plan_chunk_sync(stored: &[(&str, i32)], fresh: &[(&str, i32)]) -> ChunkSyncPlan {
+    let mut by_key: HashMap<(&str, i32), usize> = HashMap::new();
+    for (idx, key) in stored.iter().enumerate() {
+        by_key.entry(*key).or_insert(idx);
+    }
+
+    let mut plan = ChunkSyncPlan::default();
+    let mut used = vec![false; stored.len()];
+    for (idx, key) in fresh.iter().enumerate() {
+        match by_key.remove(key) {
+            Some(stored_idx) => {
+                used[stored_idx] = true;
+                plan.keep.push((idx, stored_idx));
+            }
+            None => plan.insert.push(idx),
+        }
+    }
+    plan.stale = used
+        .iter()
+        .enumerate()
+        .filter(|(_, used)| !**used)
+        .map(|(idx, _)| idx)
+        .collect();
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_keeps_unchanged_chunks() {
+        let stored = [("a", 0), ("b", 1), ("c", 2)];
+        let fresh = [("a", 0), ("x", 1), ("c", 2), ("d", 3)];
+        assert_eq!(
+            plan_chunk_sync(&stored, &fresh),
+            ChunkSyncPlan {
+                keep: vec![(0, 0), (2, 2)],
+                insert: vec![1, 3],
+                stale: vec![1],
+            }
+        );
+    }
+
+    #[test]
+    fn test_plan_reinserts_shifted_and_duplicate_chunks() {
+        // "b" moved from index 1 to 2; the duplicate row ("a", 0) is dropped
+        let stored = [("a", 0), ("b", 1), ("a", 0)];
+        let fresh = [("a", 0), ("n", 1), ("b", 2)];
+        assert_eq!(
+            plan_chunk_sync(&stored, &fresh),
+            ChunkSyncPlan {
+                keep: vec![(0, 0)],
+                insert: vec![1, 2],
+                stale: vec![1, 2],
+            }
+        );
+
+        let plan = plan_chunk_sync(&stored, &[]);
+        assert_eq!(plan.stale, vec![0, 1, 2]);
+        assert!(plan.keep.is_empty() && plan.insert.is_empty());
+    }
+}
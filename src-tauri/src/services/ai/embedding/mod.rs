@@ -3,17 +3,26 @@
 //! Split into submodules:
 //! - `model`: EmbeddingService struct and embedding methods
 //! - `store`: LanceDB storage operations
+//! - `blocks`: Block-aware chunking (code / formula blocks stay intact)
+//! - `cache`: In-memory vectors keyed by model and chunk hash
+//! - `diff`: Chunk-level diff for incremental re-embedding
 
+mod blocks;
+mod cache;
+mod diff;
 mod model;
 mod store;
 
 pub use model::{EmbeddingService, TextSegment};
-pub use store::SearchResult;
+pub use store::{
+    SearchResult, StoredChunk, VectorGroupCount, VectorIndexStatus, VectorOptimizeStats, VectorStats,
+};
 
 // Column name constants (used by both model and store)
 pub(crate) const VECTOR_KIND_TEXT: &str = "text";
 pub(crate) const VECTOR_KIND_IMAGE: &str = "image";
 pub(crate) const EMBEDDING_TYPE_TITLE: &str = "title";
+pub(crate) const EMBEDDING_TYPE_CONTENT: &str = "content";
 pub(crate) const COLUMN_RELEVANCE_SCORE: &str = "_relevance_score";
 pub(crate) const COLUMN_SCORE: &str = "_score";
 pub(crate) const COLUMN_DISTANCE: &str = "_distance";
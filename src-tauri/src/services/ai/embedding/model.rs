@@ -1,16 +1,19 @@
 //! EmbeddingService - core embedding functionality
 
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use arrow_array::RecordBatchIterator;
 use arrow_schema::Schema;
 use fastembed::{
-    EmbeddingModel, ImageEmbedding, ImageEmbeddingModel, ImageInitOptions, TextEmbedding,
-    TextInitOptions,
+    EmbeddingModel, ImageEmbedding, ImageEmbeddingModel, ImageInitOptions, RerankInitOptions,
+    RerankerModel, TextEmbedding, TextInitOptions, TextRerank,
 };
 use lancedb::index::scalar::FullTextSearchQuery;
-use lancedb::query::{ExecutableQuery, QueryBase, QueryExecutionOptions};
+use lancedb::query::{ExecutableQuery, QueryBase, QueryExecutionOptions, Select, VectorQuery};
+use lancedb::table::OptimizeAction;
 use lancedb::{DistanceType, Table};
 use serde_json::Value;
 use text_splitter::{ChunkConfig, TextSplitter};
@@ -18,30 +21,55 @@ use tokenizers::Tokenizer;
 use tokio::sync::Mutex;
 use uuid::Uuid;
 
+use super::blocks::chunk_preserving_blocks;
+use super::cache::{VectorCache, VECTOR_CACHE_CAPACITY};
+use super::diff::plan_chunk_sync;
 use super::store::{
-    build_filter, build_record_batch, build_schema, collect_search_results, compute_embedding_hash,
-    embedding_type_label, merge_results, normalize_embedding_type, open_or_create_table,
-    LanceChunk, SearchResult,
+    build_filter, build_hash_filter, build_record_batch, build_schema, collect_group_counts,
+    collect_hash_vectors, collect_node_ids, collect_search_results, collect_stored_chunks,
+    compute_embedding_hash, count_vector_rows, create_vector_index, dir_size, embedding_type_label, fuse_weighted_results,
+    indexed_columns, merge_results, normalize_embedding_type, open_or_create_table, LanceChunk,
+    SearchResult, StoredChunk, VectorIndexStatus, VectorOptimizeStats, VectorStats,
 };
 use super::{
-    COLUMN_IMAGE_VECTOR, COLUMN_NODE_ID, COLUMN_TEXT_VECTOR, EMBEDDING_TYPE_TITLE,
-    VECTOR_KIND_IMAGE, VECTOR_KIND_TEXT,
+    COLUMN_CHUNK_INDEX, COLUMN_CHUNK_TEXT, COLUMN_EMBEDDING_HASH, COLUMN_EMBEDDING_MODEL,
+    COLUMN_EMBEDDING_TYPE, COLUMN_IMAGE_VECTOR, COLUMN_NODE_ID, COLUMN_TEXT_VECTOR,
+    COLUMN_TOKEN_COUNT, COLUMN_VECTOR_ID, COLUMN_VECTOR_KIND, EMBEDDING_TYPE_CONTENT,
+    EMBEDDING_TYPE_TITLE, VECTOR_KIND_IMAGE, VECTOR_KIND_TEXT,
 };
 use crate::db::{EmbedChunkResult, EmbeddingType};
-use crate::services::VectorConfig;
+use crate::services::{
+    AnnIndexConfig, FusionStrategy, RetrievalConfig, VectorConfig, VectorDistance,
+    MIN_ANN_INDEX_ROWS,
+};
 
 const MODEL_TTL_SECONDS: u64 = 300;
+/// Vector weights this close to 0.5 use LanceDB's built-in RRF hybrid search
+const EQUAL_WEIGHT_EPSILON: f32 = 0.01;
+/// Rows inserted between checks for a vector column that has outgrown brute-force search
+const ANN_CHECK_INTERVAL_ROWS: usize = 256;
+/// Vector columns and the vector kind of the rows that fill them
+const VECTOR_COLUMNS: [(&str, &str); 2] = [
+    (COLUMN_TEXT_VECTOR, VECTOR_KIND_TEXT),
+    (COLUMN_IMAGE_VECTOR, VECTOR_KIND_IMAGE),
+];
 
 pub struct EmbeddingService {
     dense: Arc<Mutex<TimedModel<TextEmbedding>>>,
     clip_text: Arc<Mutex<TimedModel<TextEmbedding>>>,
     image: Arc<Mutex<TimedModel<ImageEmbedding>>>,
+    reranker: Arc<Mutex<TimedModel<TextRerank>>>,
     tokenizer: Tokenizer,
     splitter: TextSplitter<Tokenizer>,
     table: Table,
     schema: Arc<Schema>,
     config: VectorConfig,
     model_ttl: Duration,
+    /// Dense vectors by (model, embedding hash), see [`VectorCache`]
+    vector_cache: Mutex<VectorCache>,
+    /// ANN settings; can change at runtime, unlike the rest of `config`
+    ann: Mutex<AnnIndexConfig>,
+    rows_since_index_check: AtomicUsize,
 }
 
 pub struct EmbeddingResponse {
@@ -111,25 +139,33 @@ impl EmbeddingService {
         let dense = Arc::new(Mutex::new(TimedModel::new()));
         let clip_text = Arc::new(Mutex::new(TimedModel::new()));
         let image = Arc::new(Mutex::new(TimedModel::new()));
+        let reranker = Arc::new(Mutex::new(TimedModel::new()));
         let model_ttl = Duration::from_secs(MODEL_TTL_SECONDS);
 
         Self::spawn_model_cleanup(
             dense.clone(),
             clip_text.clone(),
             image.clone(),
+            reranker.clone(),
             model_ttl,
         );
+        let ann = config.ann_index.clamped();
 
         Ok(Self {
             dense,
             clip_text,
             image,
+            reranker,
             tokenizer,
             splitter,
             table,
             schema,
             config,
             model_ttl,
+            vector_cache: Mutex::new(VectorCache::new(VECTOR_CACHE_CAPACITY)),
+            ann: Mutex::new(ann),
+            // The first insert checks right away
+            rows_since_index_check: AtomicUsize::new(ANN_CHECK_INTERVAL_ROWS),
         })
     }
 
@@ -143,6 +179,11 @@ impl EmbeddingService {
         Ok(())
     }
 
+    /// Whether both search models are currently loaded (i.e. not evicted)
+    pub async fn is_search_warm(&self) -> bool {
+        self.dense.lock().await.model.is_some() && self.clip_text.lock().await.model.is_some()
+    }
+
     fn init_dense_model(&self) -> Result<TextEmbedding, String> {
         let dense_model: EmbeddingModel = self
             .config
@@ -170,10 +211,21 @@ impl EmbeddingService {
         ImageEmbedding::try_new(ImageInitOptions::new(image_model)).map_err(|e| e.to_string())
     }
 
+    fn init_reranker_model(&self) -> Result<TextRerank, String> {
+        let code = self.config.reranker_model.as_str();
+        let reranker_model: RerankerModel = TextRerank::list_supported_models()
+            .into_iter()
+            .find(|info| info.model_code.eq_ignore_ascii_case(code))
+            .map(|info| info.model)
+            .ok_or_else(|| format!("unsupported reranker model: {code}"))?;
+        TextRerank::try_new(RerankInitOptions::new(reranker_model)).map_err(|e| e.to_string())
+    }
+
     fn spawn_model_cleanup(
         dense: Arc<Mutex<TimedModel<TextEmbedding>>>,
         clip_text: Arc<Mutex<TimedModel<TextEmbedding>>>,
         image: Arc<Mutex<TimedModel<ImageEmbedding>>>,
+        reranker: Arc<Mutex<TimedModel<TextRerank>>>,
         ttl: Duration,
     ) {
         tauri::async_runtime::spawn(async move {
@@ -192,6 +244,10 @@ impl EmbeddingService {
                     let mut model = image.lock().await;
                     model.evict_if_idle(ttl);
                 }
+                {
+                    let mut model = reranker.lock().await;
+                    model.evict_if_idle(ttl);
+                }
             }
         });
     }
@@ -223,27 +279,19 @@ impl EmbeddingService {
         action(model)
     }
 
-    pub async fn embed_text(
-        &self,
-        node_id: i64,
-        embedding_type: EmbeddingType,
-        text: &str,
-        chunk: bool,
-    ) -> Result<EmbeddingResponse, String> {
-        let text = text.trim();
-        if text.is_empty() {
-            return Ok(EmbeddingResponse { chunks: Vec::new() });
-        }
-
-        let segments = [TextSegment {
-            text: text.to_string(),
-            meta: None,
-        }];
-        self.embed_text_segments(node_id, embedding_type, &segments, chunk)
-            .await
+    async fn with_reranker<R, F>(&self, action: F) -> Result<R, String>
+    where
+        F: FnOnce(&mut TextRerank) -> Result<R, String>,
+    {
+        let mut model = self.reranker.lock().await;
+        let model = model.ensure_with(self.model_ttl, || self.init_reranker_model())?;
+        action(model)
     }
 
-    pub async fn embed_text_segments(
+    /// Re-embed a node's text for one embedding type, touching only chunks whose hash or
+    /// position changed. Rows of other models are treated as stale. Returns every current
+    /// chunk, kept ones included, ordered by chunk index.
+    pub async fn sync_text_segments(
         &self,
         node_id: i64,
         embedding_type: EmbeddingType,
@@ -251,8 +299,90 @@ impl EmbeddingService {
         chunk: bool,
     ) -> Result<EmbeddingResponse, String> {
         let type_label = embedding_type_label(embedding_type);
-        self.embed_text_segments_with_label(node_id, type_label, segments, chunk)
-            .await
+        let model_name = self.config.dense_embedding_model.as_str();
+        let stored: Vec<StoredChunk> = self
+            .list_node_chunks(node_id)
+            .await?
+            .into_iter()
+            .filter(|row| row.embedding_type == type_label && row.vector_kind == VECTOR_KIND_TEXT)
+            .collect();
+        let chunks = self.build_chunks_from_segments(segments, chunk);
+
+        let stored_keys: Vec<(&str, i32)> = stored
+            .iter()
+            .map(|row| {
+                // Rows of another model never match a fresh hash
+                let hash = if row.embedding_model == model_name {
+                    row.embedding_hash.as_str()
+                } else {
+                    ""
+                };
+                (hash, row.chunk_index)
+            })
+            .collect();
+        let hashes: Vec<String> = chunks
+            .iter()
+            .map(|chunk| compute_embedding_hash(&chunk.text))
+            .collect();
+        let fresh_keys: Vec<(&str, i32)> = hashes
+            .iter()
+            .zip(&chunks)
+            .map(|(hash, chunk)| (hash.as_str(), chunk.chunk_index))
+            .collect();
+        let plan = plan_chunk_sync(&stored_keys, &fresh_keys);
+
+        let mut results: Vec<EmbedChunkResult> = plan
+            .keep
+            .iter()
+            .map(|&(chunk_idx, stored_idx)| {
+                let chunk = &chunks[chunk_idx];
+                let row = &stored[stored_idx];
+                EmbedChunkResult {
+                    chunk_text: chunk.text.clone(),
+                    chunk_index: chunk.chunk_index,
+                    vector_id: row.vector_id.clone(),
+                    embedding_hash: row.embedding_hash.clone(),
+                    token_count: chunk.token_count,
+                    vector_kind: VECTOR_KIND_TEXT.to_string(),
+                    embedding_model: row.embedding_model.clone(),
+                    chunk_meta: chunk.chunk_meta.clone(),
+                }
+            })
+            .collect();
+
+        // Insert before deleting so the node stays searchable, and so moved chunks still
+        // find their old vectors in the stored-vector lookup
+        let inserted = plan.insert.len();
+        if inserted > 0 {
+            let to_embed = chunks
+                .into_iter()
+                .enumerate()
+                .filter(|(idx, _)| plan.insert.binary_search(idx).is_ok())
+                .map(|(_, chunk)| chunk)
+                .collect();
+            let response = self
+                .embed_text_chunks_with_label(node_id, type_label, to_embed)
+                .await?;
+            results.extend(response.chunks);
+        }
+
+        let stale_ids: Vec<&str> = plan
+            .stale
+            .iter()
+            .map(|&idx| stored[idx].vector_id.as_str())
+            .collect();
+        self.delete_vectors(&stale_ids).await?;
+
+        tracing::debug!(
+            node_id,
+            embedding_type = type_label,
+            kept = plan.keep.len(),
+            inserted,
+            deleted = stale_ids.len(),
+            "Text embeddings synced"
+        );
+        results.sort_by_key(|chunk| chunk.chunk_index);
+        Ok(EmbeddingResponse { chunks: results })
     }
 
     pub async fn upsert_title_embedding(&self, node_id: i64, title: &str) -> Result<(), String> {
@@ -296,21 +426,55 @@ impl EmbeddingService {
         embedding_type: &str,
         chunks: Vec<TextChunk>,
     ) -> Result<EmbeddingResponse, String> {
-        let texts: Vec<&str> = chunks.iter().map(|chunk| chunk.text.as_str()).collect();
-        let dense_vectors = self
-            .with_dense(|model| model.embed(texts.as_slice(), None).map_err(|e| e.to_string()))
-            .await?;
+        let model_name = self.config.dense_embedding_model.as_str();
+        let hashes: Vec<String> = chunks
+            .iter()
+            .map(|chunk| compute_embedding_hash(&chunk.text))
+            .collect();
+        let mut vectors = self.cached_text_vectors(&hashes).await;
+
+        // Only chunks without a cached vector go through the model, each distinct text once
+        let mut missing: Vec<(&str, &str)> = Vec::new();
+        for (chunk, hash) in chunks.iter().zip(&hashes) {
+            if !vectors.contains_key(hash) && !missing.iter().any(|(h, _)| *h == hash.as_str()) {
+                missing.push((hash.as_str(), chunk.text.as_str()));
+            }
+        }
+        if !missing.is_empty() {
+            let texts: Vec<&str> = missing.iter().map(|(_, text)| *text).collect();
+            let dense_vectors = self
+                .with_dense(|model| {
+                    model
+                        .embed(texts.as_slice(), None)
+                        .map_err(|e| e.to_string())
+                })
+                .await?;
+            if dense_vectors.len() != missing.len() {
+                return Err("embedding result count mismatch".to_string());
+            }
 
-        if dense_vectors.len() != chunks.len() {
-            return Err("embedding result count mismatch".to_string());
+            let mut cache = self.vector_cache.lock().await;
+            for ((hash, _), vector) in missing.iter().zip(dense_vectors) {
+                cache.insert(model_name, hash, vector.clone());
+                vectors.insert(hash.to_string(), vector);
+            }
         }
+        tracing::debug!(
+            node_id,
+            chunks = chunks.len(),
+            embedded = missing.len(),
+            "Text chunks embedded"
+        );
 
         let mut rows = Vec::with_capacity(chunks.len());
         let mut results = Vec::with_capacity(chunks.len());
 
-        for (idx, chunk) in chunks.iter().enumerate() {
+        for (chunk, embedding_hash) in chunks.iter().zip(hashes) {
             let vector_id = uuid::Uuid::new_v4().to_string();
-            let embedding_hash = compute_embedding_hash(&chunk.text);
+            let text_vector = vectors
+                .get(&embedding_hash)
+                .cloned()
+                .ok_or_else(|| "embedding vector missing".to_string())?;
             let chunk_text = chunk.text.clone();
 
             rows.push(LanceChunk {
@@ -323,7 +487,7 @@ impl EmbeddingService {
                 chunk_index: chunk.chunk_index,
                 token_count: chunk.token_count,
                 embedding_hash: embedding_hash.clone(),
-                text_vector: Some(dense_vectors[idx].clone()),
+                text_vector: Some(text_vector),
                 image_vector: None,
             });
 
@@ -344,6 +508,58 @@ impl EmbeddingService {
         Ok(EmbeddingResponse { chunks: results })
     }
 
+    /// Dense vectors already computed for these hashes by the current model: the in-memory
+    /// cache first, then rows stored in LanceDB. Lookup failures only mean cache misses.
+    async fn cached_text_vectors(&self, hashes: &[String]) -> HashMap<String, Vec<f32>> {
+        let model_name = self.config.dense_embedding_model.as_str();
+        let mut vectors = HashMap::new();
+        let mut unresolved = Vec::new();
+        {
+            let cache = self.vector_cache.lock().await;
+            for hash in hashes {
+                match cache.get(model_name, hash) {
+                    Some(vector) => {
+                        vectors.insert(hash.clone(), vector.clone());
+                    }
+                    None if !unresolved.contains(hash) => unresolved.push(hash.clone()),
+                    None => {}
+                }
+            }
+        }
+        if unresolved.is_empty() {
+            return vectors;
+        }
+
+        let stored = async {
+            let stream = self
+                .table
+                .query()
+                .only_if(build_hash_filter(&unresolved, VECTOR_KIND_TEXT, model_name))
+                .select(Select::columns(&[
+                    COLUMN_EMBEDDING_HASH,
+                    COLUMN_TEXT_VECTOR,
+                ]))
+                .execute()
+                .await
+                .map_err(|e| e.to_string())?;
+            collect_hash_vectors(stream, COLUMN_TEXT_VECTOR).await
+        }
+        .await;
+        match stored {
+            Ok(stored) => {
+                let mut cache = self.vector_cache.lock().await;
+                for (hash, vector) in stored {
+                    cache.insert(model_name, &hash, vector.clone());
+                    vectors.insert(hash, vector);
+                }
+            }
+            Err(err) => {
+                tracing::warn!(error = %err, "Stored vector lookup failed, embedding all chunks");
+            }
+        }
+        vectors
+    }
+
     pub async fn embed_image(
         &self,
         node_id: i64,
@@ -416,6 +632,169 @@ impl EmbeddingService {
         Ok(())
     }
 
+    /// Delete rows by vector id
+    async fn delete_vectors(&self, vector_ids: &[&str]) -> Result<(), String> {
+        if vector_ids.is_empty() {
+            return Ok(());
+        }
+        let ids = vector_ids
+            .iter()
+            .map(|id| format!("'{}'", id.replace('\'', "''")))
+            .collect::<Vec<_>>()
+            .join(", ");
+        self.table
+            .delete(&format!("{} IN ({})", COLUMN_VECTOR_ID, ids))
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    /// Distinct node ids that have rows in the table
+    pub async fn list_vector_node_ids(&self) -> Result<HashSet<i64>, String> {
+        let stream = self
+            .table
+            .query()
+            .select(Select::columns(&[COLUMN_NODE_ID]))
+            .execute()
+            .await
+            .map_err(|e| e.to_string())?;
+        collect_node_ids(stream).await
+    }
+
+    /// Delete every row of the given nodes; returns the number of deleted rows
+    pub async fn delete_nodes(&self, node_ids: &[i64]) -> Result<usize, String> {
+        if node_ids.is_empty() {
+            return Ok(0);
+        }
+        let ids = node_ids
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let filter = format!("{} IN ({})", COLUMN_NODE_ID, ids);
+        let rows = self
+            .table
+            .count_rows(Some(filter.clone()))
+            .await
+            .map_err(|e| e.to_string())?;
+        self.table
+            .delete(&filter)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(rows)
+    }
+
+    /// Compact small fragments, prune old versions and fold new rows into existing indexes
+    pub async fn optimize(&self) -> Result<VectorOptimizeStats, String> {
+        let stats = self
+            .table
+            .optimize(OptimizeAction::All)
+            .await
+            .map_err(|e| e.to_string())?;
+        let mut result = VectorOptimizeStats::default();
+        if let Some(compaction) = stats.compaction {
+            result.fragments_removed = compaction.fragments_removed;
+            result.fragments_added = compaction.fragments_added;
+        }
+        if let Some(prune) = stats.prune {
+            result.old_versions_removed = prune.old_versions;
+            result.bytes_removed = prune.bytes_removed;
+        }
+        Ok(result)
+    }
+
+    /// Row counts per embedding type / vector kind / model and the table size on disk
+    pub async fn vector_stats(&self) -> Result<VectorStats, String> {
+        let stream = self
+            .table
+            .query()
+            .select(Select::columns(&[
+                COLUMN_EMBEDDING_TYPE,
+                COLUMN_VECTOR_KIND,
+                COLUMN_EMBEDDING_MODEL,
+            ]))
+            .execute()
+            .await
+            .map_err(|e| e.to_string())?;
+        let groups = collect_group_counts(stream).await?;
+
+        let table_dir = std::path::Path::new(&self.config.lancedb_path)
+            .join(format!("{}.lance", self.config.lancedb_table_name));
+        let disk_bytes = tokio::task::spawn_blocking(move || dir_size(&table_dir))
+            .await
+            .map_err(|e| e.to_string())?;
+
+        Ok(VectorStats {
+            table_name: self.config.lancedb_table_name.clone(),
+            total_rows: groups.iter().map(|group| group.count).sum(),
+            groups,
+            disk_bytes,
+        })
+    }
+
+    /// Rebuild the ANN index of every vector column with enough rows to train one
+    ///
+    /// Rows added after an index was built are searched by brute force until the next rebuild.
+    pub async fn rebuild_vector_indexes(&self) -> Result<Vec<VectorIndexStatus>, String> {
+        let mut rows_by_column = Vec::new();
+        for (column, vector_kind) in VECTOR_COLUMNS {
+            let rows = count_vector_rows(&self.table, vector_kind).await?;
+            if rows >= MIN_ANN_INDEX_ROWS {
+                create_vector_index(&self.table, column).await?;
+            }
+            rows_by_column.push((column, rows));
+        }
+
+        let indexed = indexed_columns(&self.table).await?;
+        Ok(rows_by_column
+            .into_iter()
+            .map(|(column, rows)| VectorIndexStatus {
+                column: column.to_string(),
+                rows,
+                indexed: indexed.contains(column),
+            })
+            .collect())
+    }
+
+    /// Apply new ANN settings; a lower `min_rows` is picked up by the next insert
+    pub async fn set_ann_config(&self, ann: AnnIndexConfig) {
+        *self.ann.lock().await = ann.clamped();
+        self.rows_since_index_check
+            .store(ANN_CHECK_INTERVAL_ROWS, Ordering::Relaxed);
+    }
+
+    /// Apply nprobes / refine factor; both are ignored while the column has no index
+    async fn tune_vector_query(&self, query: VectorQuery) -> VectorQuery {
+        let ann = *self.ann.lock().await;
+        let query = query.nprobes(ann.nprobes);
+        match ann.refine_factor {
+            Some(factor) => query.refine_factor(factor),
+            None => query,
+        }
+    }
+
+    /// All chunks stored for a node, without vectors
+    pub async fn list_node_chunks(&self, node_id: i64) -> Result<Vec<StoredChunk>, String> {
+        let stream = self
+            .table
+            .query()
+            .only_if(format!("{} = {}", COLUMN_NODE_ID, node_id))
+            .select(Select::columns(&[
+                COLUMN_VECTOR_ID,
+                COLUMN_EMBEDDING_TYPE,
+                COLUMN_VECTOR_KIND,
+                COLUMN_EMBEDDING_MODEL,
+                COLUMN_CHUNK_INDEX,
+                COLUMN_CHUNK_TEXT,
+                COLUMN_TOKEN_COUNT,
+                COLUMN_EMBEDDING_HASH,
+            ]))
+            .execute()
+            .await
+            .map_err(|e| e.to_string())?;
+        collect_stored_chunks(stream).await
+    }
+
     pub async fn embed_query(&self, text: &str) -> Result<(Vec<f32>, Vec<f32>), String> {
         let text = text.trim();
         if text.is_empty() {
@@ -464,32 +843,185 @@ impl EmbeddingService {
         limit: u64,
     ) -> Result<Vec<SearchResult>, String> {
         let dense_vector = self.embed_dense_query(query).await?;
-        let filter = build_filter(EMBEDDING_TYPE_TITLE, None, VECTOR_KIND_TEXT);
-        self.search_text_vector(dense_vector, filter.as_deref(), limit as usize)
-            .await
+        let filter = build_filter(EMBEDDING_TYPE_TITLE, None, &[], VECTOR_KIND_TEXT);
+        self.search_text_vector(
+            dense_vector,
+            filter.as_deref(),
+            limit as usize,
+            DistanceType::Cosine,
+        )
+        .await
     }
 
+    /// Hybrid search; `excluded_node_ids` are filtered out before ranking
     pub async fn search_hybrid(
         &self,
         query: &str,
         embedding_type: &str,
         node_ids: Option<&[i64]>,
+        excluded_node_ids: &[i64],
         limit: u64,
+    ) -> Result<Vec<SearchResult>, String> {
+        let retrieval = RetrievalConfig {
+            top_k: limit as u32,
+            ..RetrievalConfig::default()
+        };
+        self.search_hybrid_with(
+            query,
+            embedding_type,
+            node_ids,
+            excluded_node_ids,
+            &retrieval,
+        )
+        .await
+    }
+
+    /// Hybrid search with explicit retrieval parameters (top_k, distance, vector weight)
+    pub async fn search_hybrid_with(
+        &self,
+        query: &str,
+        embedding_type: &str,
+        node_ids: Option<&[i64]>,
+        excluded_node_ids: &[i64],
+        retrieval: &RetrievalConfig,
     ) -> Result<Vec<SearchResult>, String> {
         let embedding_type = normalize_embedding_type(embedding_type)?;
         let (dense_vector, clip_text_vector) = self.embed_query(query).await?;
+        let limit = retrieval.top_k as usize;
+        let distance_type = lance_distance_type(retrieval.distance);
+
+        let text_filter = build_filter(
+            embedding_type,
+            node_ids,
+            excluded_node_ids,
+            VECTOR_KIND_TEXT,
+        );
+        let image_filter = build_filter(
+            embedding_type,
+            node_ids,
+            excluded_node_ids,
+            VECTOR_KIND_IMAGE,
+        );
 
-        let text_filter = build_filter(embedding_type, node_ids, VECTOR_KIND_TEXT);
-        let image_filter = build_filter(embedding_type, node_ids, VECTOR_KIND_IMAGE);
+        let text_results = if (retrieval.vector_weight - 0.5).abs() < EQUAL_WEIGHT_EPSILON {
+            self.search_text_hybrid(
+                query,
+                dense_vector,
+                text_filter.as_deref(),
+                limit,
+                distance_type,
+            )
+            .await?
+        } else {
+            let vector_results = self
+                .search_text_vector(dense_vector, text_filter.as_deref(), limit, distance_type)
+                .await?;
+            let fts_results = self
+                .search_text_fts(query, text_filter.as_deref(), limit)
+                .await?;
+            fuse_weighted_results(vector_results, fts_results, retrieval.vector_weight, limit)
+        };
+        let image_results = self
+            .search_image_vector(clip_text_vector, image_filter.as_deref(), limit)
+            .await?;
 
-        let text_results = self
-            .search_text_hybrid(query, dense_vector, text_filter.as_deref(), limit as usize)
+        Ok(merge_results(
+            text_results,
+            image_results,
+            limit,
+            retrieval.fusion,
+        ))
+    }
+
+    /// Reorder candidates by cross-encoder relevance to `query` and keep the best `limit`.
+    /// Scores become reranker logits; candidates without chunk text (images) are dropped.
+    pub async fn rerank(
+        &self,
+        query: &str,
+        candidates: Vec<SearchResult>,
+        limit: usize,
+    ) -> Result<Vec<SearchResult>, String> {
+        let mut candidates: Vec<SearchResult> = candidates
+            .into_iter()
+            .filter(|result| !result.chunk_text.trim().is_empty())
+            .collect();
+        if candidates.is_empty() {
+            return Ok(candidates);
+        }
+        let documents: Vec<&str> = candidates
+            .iter()
+            .map(|result| result.chunk_text.as_str())
+            .collect();
+        let ranked = self
+            .with_reranker(|model| {
+                model
+                    .rerank(query, documents, false, None)
+                    .map_err(|e| e.to_string())
+            })
             .await?;
+
+        let mut scores = vec![f64::NEG_INFINITY; candidates.len()];
+        for item in ranked {
+            if let Some(score) = scores.get_mut(item.index) {
+                *score = item.score as f64;
+            }
+        }
+        for (result, score) in candidates.iter_mut().zip(scores) {
+            result.score = score;
+        }
+        candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+        candidates.truncate(limit);
+        Ok(candidates)
+    }
+
+    /// Stored images closest to an example image; with a text query, fused (RRF) with a
+    /// hybrid search for it so both the picture and the words count
+    pub async fn search_by_image(
+        &self,
+        image_path: &str,
+        query: Option<&str>,
+        node_ids: Option<&[i64]>,
+        excluded_node_ids: &[i64],
+        limit: u64,
+    ) -> Result<Vec<SearchResult>, String> {
+        let image_vector = self
+            .with_image(|model| {
+                model
+                    .embed(vec![image_path], None)
+                    .map_err(|e| e.to_string())
+            })
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| "image query embedding missing".to_string())?;
+        let filter = build_filter(
+            EMBEDDING_TYPE_CONTENT,
+            node_ids,
+            excluded_node_ids,
+            VECTOR_KIND_IMAGE,
+        );
         let image_results = self
-            .search_image_vector(clip_text_vector, image_filter.as_deref(), limit as usize)
+            .search_image_vector(image_vector, filter.as_deref(), limit as usize)
             .await?;
 
-        Ok(merge_results(text_results, image_results, limit as usize))
+        let Some(query) = query.map(str::trim).filter(|query| !query.is_empty()) else {
+            return Ok(image_results);
+        };
+        let text_results = self
+            .search_hybrid(
+                query,
+                EMBEDDING_TYPE_CONTENT,
+                node_ids,
+                excluded_node_ids,
+                limit,
+            )
+            .await?;
+        Ok(merge_results(
+            text_results,
+            image_results,
+            limit as usize,
+            FusionStrategy::Rrf,
+        ))
     }
 
     async fn search_text_hybrid(
@@ -498,6 +1030,7 @@ impl EmbeddingService {
         dense_vector: Vec<f32>,
         filter: Option<&str>,
         limit: usize,
+        distance_type: DistanceType,
     ) -> Result<Vec<SearchResult>, String> {
         let mut query_builder = self
             .table
@@ -506,8 +1039,9 @@ impl EmbeddingService {
             .nearest_to(dense_vector)
             .map_err(|e| e.to_string())?
             .column(COLUMN_TEXT_VECTOR)
-            .distance_type(DistanceType::Cosine) // TODO: 为什么用Cosine？
+            .distance_type(distance_type)
             .limit(limit);
+        query_builder = self.tune_vector_query(query_builder).await;
 
         if let Some(filter) = filter {
             query_builder = query_builder.only_if(filter);
@@ -526,6 +1060,7 @@ impl EmbeddingService {
         dense_vector: Vec<f32>,
         filter: Option<&str>,
         limit: usize,
+        distance_type: DistanceType,
     ) -> Result<Vec<SearchResult>, String> {
         let mut query_builder = self
             .table
@@ -533,7 +1068,28 @@ impl EmbeddingService {
             .nearest_to(dense_vector)
             .map_err(|e| e.to_string())?
             .column(COLUMN_TEXT_VECTOR)
-            .distance_type(DistanceType::Cosine)
+            .distance_type(distance_type)
+            .limit(limit);
+        query_builder = self.tune_vector_query(query_builder).await;
+
+        if let Some(filter) = filter {
+            query_builder = query_builder.only_if(filter);
+        }
+
+        let stream = query_builder.execute().await.map_err(|e| e.to_string())?;
+        collect_search_results(stream).await
+    }
+
+    async fn search_text_fts(
+        &self,
+        query: &str,
+        filter: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<SearchResult>, String> {
+        let mut query_builder = self
+            .table
+            .query()
+            .full_text_search(FullTextSearchQuery::new(query.to_string()))
             .limit(limit);
 
         if let Some(filter) = filter {
@@ -544,20 +1100,22 @@ impl EmbeddingService {
         collect_search_results(stream).await
     }
 
+    /// `query_vector` is a CLIP text or image vector (same space)
     async fn search_image_vector(
         &self,
-        clip_text_vector: Vec<f32>,
+        query_vector: Vec<f32>,
         filter: Option<&str>,
         limit: usize,
     ) -> Result<Vec<SearchResult>, String> {
         let mut query_builder = self
             .table
             .query()
-            .nearest_to(clip_text_vector)
+            .nearest_to(query_vector)
             .map_err(|e| e.to_string())?
             .column(COLUMN_IMAGE_VECTOR)
             .distance_type(DistanceType::Cosine)
             .limit(limit);
+        query_builder = self.tune_vector_query(query_builder).await;
 
         if let Some(filter) = filter {
             query_builder = query_builder.only_if(filter);
@@ -580,6 +1138,40 @@ impl EmbeddingService {
             .await
             .map_err(|e| e.to_string())?;
 
+        self.maybe_build_vector_indexes(rows.len()).await;
+        Ok(())
+    }
+
+    /// Every [`ANN_CHECK_INTERVAL_ROWS`] inserted rows, index vector columns that reached
+    /// `min_rows`; failures only log since search still works without an index
+    async fn maybe_build_vector_indexes(&self, inserted: usize) {
+        let pending = self
+            .rows_since_index_check
+            .fetch_add(inserted, Ordering::Relaxed)
+            + inserted;
+        if pending < ANN_CHECK_INTERVAL_ROWS {
+            return;
+        }
+        self.rows_since_index_check.store(0, Ordering::Relaxed);
+        if let Err(err) = self.build_missing_vector_indexes().await {
+            tracing::warn!(error = %err, "Build vector index failed");
+        }
+    }
+
+    async fn build_missing_vector_indexes(&self) -> Result<(), String> {
+        let min_rows = self.ann.lock().await.min_rows;
+        let indexed = indexed_columns(&self.table).await?;
+        for (column, vector_kind) in VECTOR_COLUMNS {
+            if indexed.contains(column) {
+                continue;
+            }
+            let rows = count_vector_rows(&self.table, vector_kind).await?;
+            if rows < min_rows {
+                continue;
+            }
+            create_vector_index(&self.table, column).await?;
+            tracing::info!(column, rows, "Vector index created");
+        }
         Ok(())
     }
 
@@ -617,18 +1209,23 @@ impl EmbeddingService {
         start_index: i32,
         meta: Option<Value>,
     ) -> Vec<TextChunk> {
-        self.splitter
-            .chunks(text)
-            .enumerate()
-            .map(|(idx, chunk)| {
-                TextChunk::from_text(
-                    chunk,
-                    start_index + idx as i32,
-                    self.token_count(chunk),
-                    meta.clone(),
-                )
-            })
-            .collect()
+        chunk_preserving_blocks(
+            text,
+            self.config.chunk_size,
+            |prose| self.splitter.chunks(prose).collect(),
+            |chunk| self.token_count(chunk).unwrap_or(0) as usize,
+        )
+        .into_iter()
+        .enumerate()
+        .map(|(idx, chunk)| {
+            TextChunk::from_text(
+                &chunk,
+                start_index + idx as i32,
+                self.token_count(&chunk),
+                meta.clone(),
+            )
+        })
+        .collect()
     }
 
     fn token_count(&self, text: &str) -> Option<i32> {
@@ -639,6 +1236,14 @@ impl EmbeddingService {
     }
 }
 
+fn lance_distance_type(distance: VectorDistance) -> DistanceType {
+    match distance {
+        VectorDistance::Cosine => DistanceType::Cosine,
+        VectorDistance::L2 => DistanceType::L2,
+        VectorDistance::Dot => DistanceType::Dot,
+    }
+}
+
 struct TextChunk {
     text: String,
     chunk_index: i32,
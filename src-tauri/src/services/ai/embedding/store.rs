@@ -1,16 +1,22 @@
 //! LanceDB storage operations
 
 use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
 use std::sync::Arc;
 
 use arrow_array::builder::{FixedSizeListBuilder, Float32Builder};
-use arrow_array::{Float32Array, Int32Array, Int64Array, RecordBatch, StringArray};
+use arrow_array::{
+    Array, FixedSizeListArray, Float32Array, Int32Array, Int64Array, RecordBatch, StringArray,
+};
 use arrow_schema::{DataType, Field, Schema};
 use futures_util::TryStreamExt;
 use lancedb::arrow::SendableRecordBatchStream;
 use lancedb::index::scalar::FtsIndexBuilder;
+use lancedb::index::vector::IvfPqIndexBuilder;
 use lancedb::index::Index;
-use lancedb::{connect, Error as LanceError, Table};
+use lancedb::{connect, DistanceType, Error as LanceError, Table};
+use serde::Serialize;
 
 use super::{
     COLUMN_CHUNK_INDEX, COLUMN_CHUNK_TEXT, COLUMN_EMBEDDING_HASH, COLUMN_EMBEDDING_MODEL,
@@ -19,7 +25,7 @@ use super::{
     COLUMN_RELEVANCE_SCORE, COLUMN_SCORE,
 };
 use crate::db::EmbeddingType;
-use crate::services::VectorConfig;
+use crate::services::{FusionStrategy, VectorConfig};
 use crate::utils::compute_sha256;
 
 /// Internal representation of a chunk stored in LanceDB
@@ -101,6 +107,37 @@ pub async fn create_indexes(table: &Table) -> Result<(), String> {
     Ok(())
 }
 
+/// Columns covered by an existing index (FTS included)
+pub async fn indexed_columns(table: &Table) -> Result<HashSet<String>, String> {
+    let indices = table.list_indices().await.map_err(|e| e.to_string())?;
+    Ok(indices
+        .into_iter()
+        .flat_map(|index| index.columns)
+        .collect())
+}
+
+/// Rows of one vector kind (the other vector column is null on those rows)
+pub async fn count_vector_rows(table: &Table, vector_kind: &str) -> Result<usize, String> {
+    table
+        .count_rows(Some(format!("{} = '{}'", COLUMN_VECTOR_KIND, vector_kind)))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Build (or replace) an IVF_PQ index on a vector column
+///
+/// Trained for cosine distance, the default for search; queries with another distance
+/// fall back to a flat scan.
+pub async fn create_vector_index(table: &Table, column: &str) -> Result<(), String> {
+    let builder = IvfPqIndexBuilder::default().distance_type(DistanceType::Cosine);
+    table
+        .create_index(&[column], Index::IvfPq(builder))
+        .replace(true)
+        .execute()
+        .await
+        .map_err(|e| e.to_string())
+}
+
 pub fn build_record_batch(schema: Arc<Schema>, rows: &[LanceChunk]) -> Result<RecordBatch, String> {
     let vector_ids = StringArray::from_iter_values(rows.iter().map(|row| row.vector_id.as_str()));
     let node_ids = Int64Array::from_iter_values(rows.iter().map(|row| row.node_id));
@@ -190,6 +227,183 @@ fn build_vector_column(
     Ok(builder.finish())
 }
 
+/// Row count for one (embedding_type, vector_kind, embedding_model) combination
+#[derive(Debug, Clone, Serialize)]
+pub struct VectorGroupCount {
+    pub embedding_type: String,
+    pub vector_kind: String,
+    pub embedding_model: String,
+    pub count: usize,
+}
+
+/// Summary of what is stored in the vector table
+#[derive(Debug, Clone, Serialize)]
+pub struct VectorStats {
+    pub table_name: String,
+    pub total_rows: usize,
+    pub groups: Vec<VectorGroupCount>,
+    /// Size of the table directory on disk (all versions, indexes included)
+    pub disk_bytes: u64,
+}
+
+/// Result of compacting the table and pruning old versions
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct VectorOptimizeStats {
+    pub fragments_removed: usize,
+    pub fragments_added: usize,
+    pub old_versions_removed: u64,
+    pub bytes_removed: u64,
+}
+
+/// ANN index state of one vector column
+#[derive(Debug, Clone, Serialize)]
+pub struct VectorIndexStatus {
+    pub column: String,
+    pub rows: usize,
+    pub indexed: bool,
+}
+
+/// A stored chunk without its vectors
+#[derive(Debug, Clone, Serialize)]
+pub struct StoredChunk {
+    pub vector_id: String,
+    pub embedding_type: String,
+    pub vector_kind: String,
+    pub embedding_model: String,
+    pub chunk_index: i32,
+    pub chunk_text: String,
+    pub token_count: Option<i32>,
+    pub embedding_hash: String,
+}
+
+fn string_column<'a>(batch: &'a RecordBatch, name: &str) -> Result<&'a StringArray, String> {
+    batch
+        .column_by_name(name)
+        .ok_or_else(|| format!("result missing {name}"))?
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .ok_or_else(|| format!("{name} column type mismatch"))
+}
+
+fn int32_column<'a>(batch: &'a RecordBatch, name: &str) -> Result<&'a Int32Array, String> {
+    batch
+        .column_by_name(name)
+        .ok_or_else(|| format!("result missing {name}"))?
+        .as_any()
+        .downcast_ref::<Int32Array>()
+        .ok_or_else(|| format!("{name} column type mismatch"))
+}
+
+/// Count rows per (embedding_type, vector_kind, embedding_model), largest groups first
+pub async fn collect_group_counts(
+    mut stream: SendableRecordBatchStream,
+) -> Result<Vec<VectorGroupCount>, String> {
+    let mut counts: std::collections::BTreeMap<(String, String, String), usize> =
+        std::collections::BTreeMap::new();
+
+    while let Some(batch) = stream.try_next().await.map_err(|e| e.to_string())? {
+        let embedding_types = string_column(&batch, COLUMN_EMBEDDING_TYPE)?;
+        let vector_kinds = string_column(&batch, COLUMN_VECTOR_KIND)?;
+        let models = string_column(&batch, COLUMN_EMBEDDING_MODEL)?;
+        for row_idx in 0..batch.num_rows() {
+            let key = (
+                embedding_types.value(row_idx).to_string(),
+                vector_kinds.value(row_idx).to_string(),
+                models.value(row_idx).to_string(),
+            );
+            *counts.entry(key).or_default() += 1;
+        }
+    }
+
+    let mut groups: Vec<VectorGroupCount> = counts
+        .into_iter()
+        .map(
+            |((embedding_type, vector_kind, embedding_model), count)| VectorGroupCount {
+                embedding_type,
+                vector_kind,
+                embedding_model,
+                count,
+            },
+        )
+        .collect();
+    groups.sort_by(|a, b| b.count.cmp(&a.count));
+    Ok(groups)
+}
+
+/// Distinct node ids in a stream that selects the node_id column
+pub async fn collect_node_ids(
+    mut stream: SendableRecordBatchStream,
+) -> Result<HashSet<i64>, String> {
+    let mut node_ids = HashSet::new();
+    while let Some(batch) = stream.try_next().await.map_err(|e| e.to_string())? {
+        let column = batch
+            .column_by_name(COLUMN_NODE_ID)
+            .ok_or_else(|| format!("result missing {COLUMN_NODE_ID}"))?
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .ok_or_else(|| format!("{COLUMN_NODE_ID} column type mismatch"))?;
+        node_ids.extend(column.values().iter().copied());
+    }
+    Ok(node_ids)
+}
+
+/// Collect stored chunks ordered by embedding_type, vector_kind and chunk_index
+pub async fn collect_stored_chunks(
+    mut stream: SendableRecordBatchStream,
+) -> Result<Vec<StoredChunk>, String> {
+    let mut chunks = Vec::new();
+
+    while let Some(batch) = stream.try_next().await.map_err(|e| e.to_string())? {
+        let vector_ids = string_column(&batch, COLUMN_VECTOR_ID)?;
+        let embedding_types = string_column(&batch, COLUMN_EMBEDDING_TYPE)?;
+        let vector_kinds = string_column(&batch, COLUMN_VECTOR_KIND)?;
+        let models = string_column(&batch, COLUMN_EMBEDDING_MODEL)?;
+        let chunk_indices = int32_column(&batch, COLUMN_CHUNK_INDEX)?;
+        let chunk_texts = string_column(&batch, COLUMN_CHUNK_TEXT)?;
+        let token_counts = int32_column(&batch, COLUMN_TOKEN_COUNT)?;
+        let hashes = string_column(&batch, COLUMN_EMBEDDING_HASH)?;
+
+        for row_idx in 0..batch.num_rows() {
+            chunks.push(StoredChunk {
+                vector_id: vector_ids.value(row_idx).to_string(),
+                embedding_type: embedding_types.value(row_idx).to_string(),
+                vector_kind: vector_kinds.value(row_idx).to_string(),
+                embedding_model: models.value(row_idx).to_string(),
+                chunk_index: chunk_indices.value(row_idx),
+                chunk_text: chunk_texts.value(row_idx).to_string(),
+                token_count: token_counts
+                    .is_valid(row_idx)
+                    .then(|| token_counts.value(row_idx)),
+                embedding_hash: hashes.value(row_idx).to_string(),
+            });
+        }
+    }
+
+    chunks.sort_by(|a, b| {
+        (&a.embedding_type, &a.vector_kind, a.chunk_index).cmp(&(
+            &b.embedding_type,
+            &b.vector_kind,
+            b.chunk_index,
+        ))
+    });
+    Ok(chunks)
+}
+
+/// Total size of all files under a directory; missing paths count as zero
+pub fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.metadata() {
+            Ok(meta) if meta.is_dir() => dir_size(&entry.path()),
+            Ok(meta) => meta.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
 /// Search result from vector search
 #[derive(Debug, Clone)]
 pub struct SearchResult {
@@ -274,18 +488,39 @@ pub async fn collect_search_results(
     Ok(results)
 }
 
+/// Merge text and image results with the given fusion strategy, deduplicated by chunk
 pub fn merge_results(
+    text_results: Vec<SearchResult>,
+    image_results: Vec<SearchResult>,
+    limit: usize,
+    strategy: FusionStrategy,
+) -> Vec<SearchResult> {
+    let mut merged = match strategy {
+        FusionStrategy::Max => merge_max_score(text_results, image_results),
+        FusionStrategy::Rrf => fuse_ranked([(text_results, 1.0), (image_results, 1.0)]),
+        FusionStrategy::Normalized => merge_normalized(text_results, image_results),
+    };
+    merged.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal));
+    merged.truncate(limit);
+    merged
+}
+
+type ChunkKey = (i64, i32, String);
+
+fn chunk_key(item: &SearchResult) -> ChunkKey {
+    (item.node_id, item.chunk_index, item.chunk_text.clone())
+}
+
+/// Keep the best raw score per chunk; NaN scores lose to any real score
+fn merge_max_score(
     mut text_results: Vec<SearchResult>,
     mut image_results: Vec<SearchResult>,
-    limit: usize,
 ) -> Vec<SearchResult> {
     text_results.append(&mut image_results);
-    let mut best_by_key: std::collections::HashMap<(i64, i32, String), SearchResult> =
-        std::collections::HashMap::new();
+    let mut best_by_key: HashMap<ChunkKey, SearchResult> = HashMap::new();
 
     for item in text_results {
-        let key = (item.node_id, item.chunk_index, item.chunk_text.clone());
-        match best_by_key.get_mut(&key) {
+        match best_by_key.get_mut(&chunk_key(&item)) {
             Some(existing) => {
                 let replace = match (item.score.is_nan(), existing.score.is_nan()) {
                     (true, true) => false,
@@ -298,19 +533,101 @@ pub fn merge_results(
                 }
             }
             None => {
-                best_by_key.insert(key, item);
+                best_by_key.insert(chunk_key(&item), item);
             }
         }
     }
 
-    let mut deduped: Vec<SearchResult> = best_by_key.into_values().collect();
-    deduped.sort_by(|a, b| {
-        b.score
-            .partial_cmp(&a.score)
-            .unwrap_or(Ordering::Equal)
-    });
-    deduped.truncate(limit);
-    deduped
+    best_by_key.into_values().collect()
+}
+
+/// Min-max normalize each list to 0..=1 and sum the scores per chunk (CombSUM)
+///
+/// A list whose scores are all equal maps to 1.0; NaN scores count as 0.
+fn merge_normalized(
+    text_results: Vec<SearchResult>,
+    image_results: Vec<SearchResult>,
+) -> Vec<SearchResult> {
+    let mut fused: HashMap<ChunkKey, SearchResult> = HashMap::new();
+
+    for results in [text_results, image_results] {
+        let scores = results
+            .iter()
+            .map(|item| item.score)
+            .filter(|s| !s.is_nan());
+        let min = scores.clone().fold(f64::INFINITY, f64::min);
+        let max = scores.fold(f64::NEG_INFINITY, f64::max);
+        let range = max - min;
+
+        let mut best_in_list: HashMap<ChunkKey, SearchResult> = HashMap::new();
+        for item in results {
+            let score = if item.score.is_nan() {
+                0.0
+            } else if range > 0.0 {
+                (item.score - min) / range
+            } else {
+                1.0
+            };
+            best_in_list
+                .entry(chunk_key(&item))
+                .and_modify(|existing| existing.score = existing.score.max(score))
+                .or_insert(SearchResult { score, ..item });
+        }
+        for (key, item) in best_in_list {
+            fused
+                .entry(key)
+                .and_modify(|existing| existing.score += item.score)
+                .or_insert(item);
+        }
+    }
+
+    fused.into_values().collect()
+}
+
+/// Smoothing constant for reciprocal rank fusion (same as LanceDB's default reranker)
+const RRF_K: f64 = 60.0;
+
+/// Fuse vector and full-text results with weighted reciprocal rank fusion
+///
+/// `vector_weight` is in 0..=1; the full-text side gets the rest. Weights are doubled so that
+/// an equal split scores like plain RRF.
+pub fn fuse_weighted_results(
+    vector_results: Vec<SearchResult>,
+    fts_results: Vec<SearchResult>,
+    vector_weight: f32,
+    limit: usize,
+) -> Vec<SearchResult> {
+    let vector_weight = f64::from(vector_weight.clamp(0.0, 1.0)) * 2.0;
+    let fts_weight = 2.0 - vector_weight;
+    let mut results = fuse_ranked([(vector_results, vector_weight), (fts_results, fts_weight)]);
+    results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal));
+    results.truncate(limit);
+    results
+}
+
+/// Weighted reciprocal rank fusion; each list is ranked by its own score (best first)
+///
+/// A chunk only counts once per list, at its best rank.
+fn fuse_ranked<const N: usize>(lists: [(Vec<SearchResult>, f64); N]) -> Vec<SearchResult> {
+    let mut fused: HashMap<ChunkKey, SearchResult> = HashMap::new();
+
+    for (mut results, weight) in lists {
+        results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal));
+        let mut seen = HashSet::new();
+        for (rank, item) in results.into_iter().enumerate() {
+            let key = chunk_key(&item);
+            if !seen.insert(key.clone()) {
+                continue;
+            }
+            let score = weight / (RRF_K + rank as f64 + 1.0);
+            fused
+                .entry(key)
+                .and_modify(|existing| existing.score += score)
+                .or_insert(SearchResult { score, ..item });
+        }
+    }
+
+    fused.into_values().collect()
 }
 
 pub fn normalize_embedding_type(value: &str) -> Result<&str, String> {
@@ -320,21 +637,29 @@ pub fn normalize_embedding_type(value: &str) -> Result<&str, String> {
     }
 }
 
-pub fn build_filter(embedding_type: &str, node_ids: Option<&[i64]>, vector_kind: &str) -> Option<String> {
+/// Build the LanceDB `WHERE` clause; `excluded_node_ids` always wins over `node_ids`
+pub fn build_filter(
+    embedding_type: &str,
+    node_ids: Option<&[i64]>,
+    excluded_node_ids: &[i64],
+    vector_kind: &str,
+) -> Option<String> {
     let mut filters = Vec::new();
     filters.push(format!("{} = '{}'", COLUMN_EMBEDDING_TYPE, embedding_type));
     filters.push(format!("{} = '{}'", COLUMN_VECTOR_KIND, vector_kind));
 
     if let Some(node_ids) = node_ids {
         if !node_ids.is_empty() {
-            let values = node_ids
-                .iter()
-                .map(|id| id.to_string())
-                .collect::<Vec<_>>()
-                .join(", ");
-            filters.push(format!("{} IN ({})", COLUMN_NODE_ID, values));
+            filters.push(format!("{} IN ({})", COLUMN_NODE_ID, join_ids(node_ids)));
         }
     }
+    if !excluded_node_ids.is_empty() {
+        filters.push(format!(
+            "{} NOT IN ({})",
+            COLUMN_NODE_ID,
+            join_ids(excluded_node_ids)
+        ));
+    }
 
     if filters.is_empty() {
         None
@@ -343,6 +668,67 @@ pub fn build_filter(embedding_type: &str, node_ids: Option<&[i64]>, vector_kind:
     }
 }
 
+fn join_ids(ids: &[i64]) -> String {
+    ids.iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Filter for stored vectors of the given hashes produced by one model
+pub fn build_hash_filter(hashes: &[String], vector_kind: &str, embedding_model: &str) -> String {
+    let hashes = hashes
+        .iter()
+        .map(|hash| format!("'{}'", hash.replace('\'', "''")))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "{} = '{}' AND {} = '{}' AND {} IN ({})",
+        COLUMN_VECTOR_KIND,
+        vector_kind,
+        COLUMN_EMBEDDING_MODEL,
+        embedding_model.replace('\'', "''"),
+        COLUMN_EMBEDDING_HASH,
+        hashes
+    )
+}
+
+/// First stored vector per embedding hash; rows without a vector are skipped
+pub async fn collect_hash_vectors(
+    mut stream: SendableRecordBatchStream,
+    vector_column: &str,
+) -> Result<HashMap<String, Vec<f32>>, String> {
+    let mut vectors = HashMap::new();
+
+    while let Some(batch) = stream.try_next().await.map_err(|e| e.to_string())? {
+        let hashes = string_column(&batch, COLUMN_EMBEDDING_HASH)?;
+        let lists = batch
+            .column_by_name(vector_column)
+            .ok_or_else(|| format!("result missing {vector_column}"))?
+            .as_any()
+            .downcast_ref::<FixedSizeListArray>()
+            .ok_or_else(|| format!("{vector_column} column type mismatch"))?;
+
+        for row_idx in 0..batch.num_rows() {
+            if lists.is_null(row_idx) {
+                continue;
+            }
+            let hash = hashes.value(row_idx);
+            if vectors.contains_key(hash) {
+                continue;
+            }
+            let values = lists.value(row_idx);
+            let values = values
+                .as_any()
+                .downcast_ref::<Float32Array>()
+                .ok_or_else(|| format!("{vector_column} value type mismatch"))?;
+            vectors.insert(hash.to_string(), values.values().to_vec());
+        }
+    }
+
+    Ok(vectors)
+}
+
 pub fn embedding_type_label(embedding_type: EmbeddingType) -> &'static str {
     match embedding_type {
         EmbeddingType::Summary => "summary",
@@ -354,3 +740,111 @@ pub fn compute_embedding_hash(text: &str) -> String {
     let hash = compute_sha256(text.as_bytes());
     hash.chars().take(16).collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hit(node_id: i64) -> SearchResult {
+        SearchResult {
+            node_id,
+            chunk_index: 0,
+            chunk_text: format!("chunk {node_id}"),
+            score: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_fuse_weighted_results_prefers_weighted_side() {
+        let vector = vec![hit(1), hit(2)];
+        let fts = vec![hit(2), hit(3)];
+
+        let fused = fuse_weighted_results(vector.clone(), fts.clone(), 0.9, 10);
+        assert_eq!(fused[0].node_id, 2);
+        assert_eq!(fused[1].node_id, 1);
+
+        let fused = fuse_weighted_results(vector, fts, 0.0, 2);
+        let ids: Vec<i64> = fused.iter().map(|item| item.node_id).collect();
+        assert_eq!(ids, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_build_hash_filter() {
+        let hashes = vec!["ab12".to_string(), "cd34".to_string()];
+        assert_eq!(
+            build_hash_filter(&hashes, "text", "BAAI/bge-small-en-v1.5"),
+            "vector_kind = 'text' AND embedding_model = 'BAAI/bge-small-en-v1.5' \
+             AND embedding_hash IN ('ab12', 'cd34')"
+        );
+    }
+
+    #[test]
+    fn test_build_filter_excludes_nodes() {
+        let filter = build_filter("content", Some(&[1, 2]), &[2, 3], "text").unwrap();
+        assert_eq!(
+            filter,
+            "embedding_type = 'content' AND vector_kind = 'text' AND node_id IN (1, 2) \
+             AND node_id NOT IN (2, 3)"
+        );
+        let filter = build_filter("summary", None, &[], "image").unwrap();
+        assert!(!filter.contains("node_id"));
+    }
+
+    fn scored(node_id: i64, score: f64) -> SearchResult {
+        SearchResult {
+            score,
+            ..hit(node_id)
+        }
+    }
+
+    fn ids(results: &[SearchResult]) -> Vec<i64> {
+        results.iter().map(|item| item.node_id).collect()
+    }
+
+    #[test]
+    fn test_merge_results_max_keeps_best_raw_score() {
+        let text = vec![scored(1, 0.9), scored(2, f64::NAN)];
+        let image = vec![scored(2, 0.3), scored(3, 0.95)];
+
+        let merged = merge_results(text, image, 10, FusionStrategy::Max);
+        assert_eq!(ids(&merged), vec![3, 1, 2]);
+        assert_eq!(merged[2].score, 0.3);
+    }
+
+    #[test]
+    fn test_merge_results_rrf_ignores_score_scale() {
+        // 文本分数远大于图片分数，RRF 只看排名：两路都出现的 2 排第一，各路第一名并列其次
+        let text = vec![scored(1, 40.0), scored(2, 30.0)];
+        let image = vec![scored(3, 0.2), scored(2, 0.1)];
+
+        let merged = merge_results(text, image, 10, FusionStrategy::Rrf);
+        assert_eq!(merged[0].node_id, 2);
+        assert!((merged[1].score - merged[2].score).abs() < 1e-12);
+        assert_eq!(merged.len(), 3);
+
+        let merged = merge_results(vec![scored(1, 1.0)], Vec::new(), 10, FusionStrategy::Rrf);
+        assert!((merged[0].score - 1.0 / (RRF_K + 1.0)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_merge_results_normalized_scales_each_list() {
+        let text = vec![scored(1, 40.0), scored(2, 30.0), scored(4, 20.0)];
+        let image = vec![scored(3, 0.8), scored(2, 0.6)];
+
+        let merged = merge_results(text, image, 3, FusionStrategy::Normalized);
+        // 1: 1.0；2: 0.5 + 0.0；3: 1.0；4: 0.0（被截断）
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged[2].node_id, 2);
+        assert!((merged[2].score - 0.5).abs() < 1e-12);
+        assert!(merged[..2].iter().all(|item| item.score == 1.0));
+
+        // 只有一个结果（分数相同）时归一化为 1
+        let merged = merge_results(
+            vec![scored(5, 0.2)],
+            Vec::new(),
+            10,
+            FusionStrategy::Normalized,
+        );
+        assert_eq!(merged[0].score, 1.0);
+    }
+}
@@ -0,0 +1,109 @@
+use reqwest::StatusCode;
+use thiserror::Error;
+
+/// Failure of an LLM request, classified where it is produced so callers can decide
+/// whether retrying makes sense without inspecting the message
+#[derive(Debug, Clone, Error)]
+pub enum LlmError {
+    /// HTTP 429
+    #[error("{0}")]
+    RateLimited(String),
+    /// HTTP 5xx
+    #[error("{1}")]
+    Server(u16, String),
+    /// Connection failures, timeouts and interrupted bodies
+    #[error("{0}")]
+    Transport(String),
+    /// Bad request, invalid api key, unparsable response, ...
+    #[error("{0}")]
+    Other(String),
+}
+
+impl LlmError {
+    /// Non-success response; `context` prefixes the message, e.g. "gemini request failed"
+    pub fn from_status(context: &str, status: StatusCode, body: &str) -> Self {
+        let message = format!("{context}: {status} {body}");
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            LlmError::RateLimited(message)
+        } else if status.is_server_error() {
+            LlmError::Server(status.as_u16(), message)
+        } else {
+            LlmError::Other(message)
+        }
+    }
+
+    pub fn from_reqwest(context: &str, err: reqwest::Error) -> Self {
+        if let Some(status) = err.status() {
+            return Self::from_status(context, status, "");
+        }
+        let message = format!("{context}: {err}");
+        if err.is_timeout() || err.is_connect() || err.is_request() || err.is_body() {
+            LlmError::Transport(message)
+        } else {
+            LlmError::Other(message)
+        }
+    }
+
+    /// Rate limits, server errors and network failures are worth retrying
+    pub fn is_transient(&self) -> bool {
+        !matches!(self, LlmError::Other(_))
+    }
+
+    /// Prefix the message, keeping the classification
+    pub fn context(self, context: &str) -> Self {
+        match self {
+            LlmError::RateLimited(m) => LlmError::RateLimited(format!("{context}: {m}")),
+            LlmError::Server(status, m) => LlmError::Server(status, format!("{context}: {m}")),
+            LlmError::Transport(m) => LlmError::Transport(format!("{context}: {m}")),
+            LlmError::Other(m) => LlmError::Other(format!("{context}: {m}")),
+        }
+    }
+}
+
+impl From<String> for LlmError {
+    fn from(message: String) -> Self {
+        LlmError::Other(message)
+    }
+}
+
+impl From<&str> for LlmError {
+    fn from(message: &str) -> Self {
+        LlmError::Other(message.to_string())
+    }
+}
+
+impl From<LlmError> for String {
+    fn from(err: LlmError) -> Self {
+        err.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_status() {
+        let err = LlmError::from_status("gemini request failed", StatusCode::TOO_MANY_REQUESTS, "");
+        assert!(matches!(err, LlmError::RateLimited(_)));
+        assert!(err.is_transient());
+
+        let err = LlmError::from_status("x", StatusCode::SERVICE_UNAVAILABLE, "overloaded");
+        assert!(matches!(err, LlmError::Server(503, _)));
+        assert_eq!(err.to_string(), "x: 503 Service Unavailable overloaded");
+
+        // Error bodies may mention "503" or "timeout" without being transient
+        let err =
+            LlmError::from_status("x", StatusCode::BAD_REQUEST, "API key not valid, retry 503");
+        assert!(!err.is_transient());
+        assert!(!LlmError::from("resource content is empty").is_transient());
+    }
+
+    #[test]
+    fn test_context_keeps_kind() {
+        let err =
+            LlmError::Transport("connection reset".to_string()).context("summary request failed");
+        assert!(err.is_transient());
+        assert_eq!(err.to_string(), "summary request failed: connection reset");
+    }
+}
@@ -7,20 +7,27 @@ use serde::{Deserialize, Serialize};
 use tokio::time::sleep;
 use tracing::debug;
 
-use crate::services::ProviderConfig;
+use crate::services::{start_timer, MetricHistogram, ProviderConfig};
 
-use super::types::{ChatMessage, ChatRole, ChatStreamEvent, ChatUsage};
+use super::error::LlmError;
+use super::ollama::{self, build_ollama_base_url};
+use super::rate_limit::{RateLimiter, RequestClass};
+use super::types::{
+    ChatMessage, ChatRole, ChatStreamEvent, ChatUsage, LocalModel, ToolCall, ToolResult, ToolSpec,
+};
 
 const DEFAULT_GEMINI_BASE_URL: &str = "https://generativelanguage.googleapis.com";
 
 pub struct LlmService {
     client: Client,
+    rate_limiter: RateLimiter,
 }
 
 impl LlmService {
     pub fn new() -> Self {
         Self {
             client: Client::new(),
+            rate_limiter: RateLimiter::new(),
         }
     }
 
@@ -31,13 +38,66 @@ impl LlmService {
         provider_config: &ProviderConfig,
         messages: &[ChatMessage],
         thinking_effort: Option<&str>,
-        mut on_event: F,
+        on_event: F,
     ) -> Result<(), String>
+    where
+        F: FnMut(ChatStreamEvent) -> Fut,
+        Fut: std::future::Future<Output = Result<(), String>>,
+    {
+        self.stream_chat_with_tools(
+            provider,
+            model,
+            provider_config,
+            messages,
+            &[],
+            thinking_effort,
+            on_event,
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// Stream one model turn with the given tools declared.
+    ///
+    /// Returns the model turn (answer text plus any tool calls) so the caller can run the
+    /// tools and continue the conversation. Tools are only supported for Gemini; other
+    /// providers stream a plain answer and never return tool calls.
+    pub async fn stream_chat_with_tools<F, Fut>(
+        &self,
+        provider: &str,
+        model: &str,
+        provider_config: &ProviderConfig,
+        messages: &[ChatMessage],
+        tools: &[ToolSpec],
+        thinking_effort: Option<&str>,
+        mut on_event: F,
+    ) -> Result<ChatMessage, String>
     where
         F: FnMut(ChatStreamEvent) -> Fut,
         Fut: std::future::Future<Output = Result<(), String>>,
     {
         let provider = provider.to_lowercase();
+        self.rate_limiter
+            .acquire(
+                &provider,
+                provider_config.requests_per_minute,
+                RequestClass::Interactive,
+            )
+            .await;
+        let _timer = start_timer(MetricHistogram::LlmChat);
+        if provider == "ollama" {
+            let base_url = build_ollama_base_url(provider_config.base_url.as_deref());
+            ollama::stream_chat(
+                &self.client,
+                &base_url,
+                model,
+                messages,
+                thinking_effort,
+                on_event,
+            )
+            .await?;
+            return Ok(ChatMessage::new(ChatRole::Assistant, ""));
+        }
         if provider != "gemini" && provider != "google" {
             return Err(format!("provider {provider} not supported"));
         }
@@ -69,6 +129,12 @@ impl LlmService {
             if !message.content.trim().is_empty() {
                 parts.push(GeminiPart::text(message.content.clone()));
             }
+            for call in &message.tool_calls {
+                parts.push(GeminiPart::function_call(call));
+            }
+            for result in &message.tool_results {
+                parts.push(GeminiPart::function_response(result));
+            }
 
             if !parts.is_empty() {
                 contents.push(GeminiContent {
@@ -89,9 +155,22 @@ impl LlmService {
                 thinking_config: Some(thinking_config),
             }
         });
+        let tools = (!tools.is_empty()).then(|| {
+            vec![GeminiTool {
+                function_declarations: tools
+                    .iter()
+                    .map(|tool| GeminiFunctionDeclaration {
+                        name: tool.name.to_string(),
+                        description: tool.description.to_string(),
+                        parameters_json_schema: tool.parameters.clone(),
+                    })
+                    .collect(),
+            }]
+        });
         let request = GeminiGenerateRequest {
             contents,
             generation_config,
+            tools,
         };
 
         match serde_json::to_string(&request) {
@@ -139,6 +218,7 @@ impl LlmService {
         let mut answer_text = String::new();
         let mut thinking_text = String::new();
         let mut usage: Option<ChatUsage> = None;
+        let mut tool_calls: Vec<ToolCall> = Vec::new();
 
         while let Some(chunk_result) = stream.next().await {
             let bytes = chunk_result.map_err(|e| format!("gemini stream read error: {e}"))?;
@@ -167,25 +247,24 @@ impl LlmService {
                 let chunk: GeminiStreamResponse = serde_json::from_str(data)
                     .map_err(|e| format!("gemini stream payload invalid: {e}"))?;
 
-                if let Some(metadata) = chunk.usage_metadata {
-                    if let (Some(input), Some(output), Some(total)) = (
-                        metadata.prompt_token_count,
-                        metadata.candidates_token_count,
-                        metadata.total_token_count,
-                    ) {
-                        usage = Some(ChatUsage {
-                            input_tokens: input,
-                            output_tokens: output,
-                            reasoning_tokens: metadata.thoughts_token_count.unwrap_or(0),
-                            total_tokens: total,
-                        });
-                    }
+                if let Some(chunk_usage) =
+                    chunk.usage_metadata.and_then(GeminiUsageMetadata::into_usage)
+                {
+                    usage = Some(chunk_usage);
                 }
 
                 if let Some(candidates) = chunk.candidates {
                     if let Some(candidate) = candidates.first() {
                         if let Some(content) = candidate.content.as_ref() {
                             for part in &content.parts {
+                                if let Some(call) = part.function_call.as_ref() {
+                                    tool_calls.push(ToolCall {
+                                        id: call.id.clone(),
+                                        name: call.name.clone(),
+                                        args: call.args.clone().unwrap_or_default(),
+                                        thought_signature: part.thought_signature.clone(),
+                                    });
+                                }
                                 if let Some(text) = part.text.as_ref() {
                                     if part.thought {
                                         thinking_text.push_str(text);
@@ -202,6 +281,9 @@ impl LlmService {
             }
         }
 
+        let mut turn = ChatMessage::new(ChatRole::Assistant, answer_text.clone());
+        turn.tool_calls = tool_calls;
+
         if !answer_text.is_empty() {
             on_event(ChatStreamEvent::AnswerFullText(answer_text)).await?;
         }
@@ -212,7 +294,7 @@ impl LlmService {
             on_event(ChatStreamEvent::Usage(usage)).await?;
         }
 
-        Ok(())
+        Ok(turn)
     }
 
     pub async fn generate_structured_json(
@@ -224,15 +306,61 @@ impl LlmService {
         schema: serde_json::Value,
         file_path: Option<&str>,
         thinking_effort: Option<&str>,
-    ) -> Result<String, String> {
+    ) -> Result<String, LlmError> {
+        self.generate_structured_json_with_usage(
+            provider,
+            model,
+            provider_config,
+            prompt,
+            schema,
+            file_path,
+            thinking_effort,
+        )
+        .await
+        .map(|(output, _)| output)
+    }
+
+    /// Same as [`Self::generate_structured_json`], also returning the token usage reported
+    /// by the provider (if any)
+    pub async fn generate_structured_json_with_usage(
+        &self,
+        provider: &str,
+        model: &str,
+        provider_config: &ProviderConfig,
+        prompt: &str,
+        schema: serde_json::Value,
+        file_path: Option<&str>,
+        thinking_effort: Option<&str>,
+    ) -> Result<(String, Option<ChatUsage>), LlmError> {
         let provider = provider.to_lowercase();
+        self.rate_limiter
+            .acquire(
+                &provider,
+                provider_config.requests_per_minute,
+                RequestClass::Background,
+            )
+            .await;
+        let _timer = start_timer(MetricHistogram::LlmStructured);
+        if provider == "ollama" {
+            let base_url = build_ollama_base_url(provider_config.base_url.as_deref());
+            return ollama::generate_structured_json(
+                &self.client,
+                &base_url,
+                model,
+                prompt,
+                schema,
+                file_path,
+                thinking_effort,
+            )
+            .await;
+        }
         if provider != "gemini" && provider != "google" {
-            return Err(format!("provider {provider} not supported"));
+            return Err(format!("provider {provider} not supported").into());
         }
 
         let api_key = provider_config.api_key.trim();
         if api_key.is_empty() {
-            return Err("missing api key".to_string());
+            return Err("missing api key".into());
         }
 
         let base_url = build_base_url(provider_config.base_url.as_deref());
@@ -258,6 +386,7 @@ impl LlmService {
         let request = GeminiGenerateRequest {
             contents,
             generation_config: Some(generation_config),
+            tools: None,
         };
 
         match serde_json::to_string(&request) {
@@ -288,18 +417,22 @@ impl LlmService {
             .json(&request)
             .send()
             .await
-            .map_err(|e| format!("gemini request failed: {e}"))?;
+            .map_err(|e| LlmError::from_reqwest("gemini request failed", e))?;
 
         if !response.status().is_success() {
             let status = response.status();
             let body = response.text().await.unwrap_or_default();
-            return Err(format!("gemini request failed: {status} {body}"));
+            return Err(LlmError::from_status(
+                "gemini request failed",
+                status,
+                &body,
+            ));
         }
 
         let response_text = response
             .text()
             .await
-            .map_err(|e| format!("gemini response read failed: {e}"))?;
+            .map_err(|e| LlmError::from_reqwest("gemini response read failed", e))?;
         debug!(
             provider = %provider,
             model = %model,
@@ -309,6 +442,7 @@ impl LlmService {
         let response: GeminiGenerateResponse =
             serde_json::from_str(&response_text).map_err(|e| format!("gemini response invalid: {e}"))?;
 
+        let usage = response.usage_metadata.and_then(GeminiUsageMetadata::into_usage);
         let mut output = String::new();
         if let Some(candidate) = response.candidates.and_then(|mut list| list.pop()) {
             if let Some(content) = candidate.content {
@@ -321,7 +455,7 @@ impl LlmService {
         }
 
         if output.trim().is_empty() {
-            return Err("gemini response missing text".to_string());
+            return Err("gemini response missing text".into());
         }
 
         debug!(
@@ -331,7 +465,16 @@ impl LlmService {
             "Gemini structured output"
         );
 
-        Ok(output)
+        Ok((output, usage))
+    }
+
+    /// List models installed in a local Ollama daemon (`base_url` defaults to localhost)
+    pub async fn list_local_models(
+        &self,
+        base_url: Option<&str>,
+    ) -> Result<Vec<LocalModel>, String> {
+        let base_url = build_ollama_base_url(base_url);
+        ollama::list_models(&self.client, &base_url).await
     }
 
     async fn upload_file(
@@ -339,10 +482,10 @@ impl LlmService {
         base_url: &str,
         api_key: &str,
         file_path: &str,
-    ) -> Result<GeminiFileData, String> {
+    ) -> Result<GeminiFileData, LlmError> {
         let path = Path::new(file_path);
         if !path.exists() {
-            return Err(format!("file not found: {file_path}"));
+            return Err(format!("file not found: {file_path}").into());
         }
 
         let bytes = std::fs::read(path).map_err(|e| format!("read file failed: {e}"))?;
@@ -371,12 +514,16 @@ impl LlmService {
             .json(&start_request)
             .send()
             .await
-            .map_err(|e| format!("gemini upload start failed: {e}"))?;
+            .map_err(|e| LlmError::from_reqwest("gemini upload start failed", e))?;
 
         if !start_response.status().is_success() {
             let status = start_response.status();
             let body = start_response.text().await.unwrap_or_default();
-            return Err(format!("gemini upload start failed: {status} {body}"));
+            return Err(LlmError::from_status(
+                "gemini upload start failed",
+                status,
+                &body,
+            ));
         }
 
         let upload_url = start_response
@@ -396,12 +543,12 @@ impl LlmService {
             .body(bytes)
             .send()
             .await
-            .map_err(|e| format!("gemini upload failed: {e}"))?;
+            .map_err(|e| LlmError::from_reqwest("gemini upload failed", e))?;
 
         if !upload_response.status().is_success() {
             let status = upload_response.status();
             let body = upload_response.text().await.unwrap_or_default();
-            return Err(format!("gemini upload failed: {status} {body}"));
+            return Err(LlmError::from_status("gemini upload failed", status, &body));
         }
 
         let upload_info: GeminiUploadResponse = upload_response
@@ -432,7 +579,7 @@ impl LlmService {
         base_url: &str,
         api_key: &str,
         file_name: &str,
-    ) -> Result<GeminiFileRecord, String> {
+    ) -> Result<GeminiFileRecord, LlmError> {
         let url = format!("{}/v1beta/files/{}", base_url, file_name);
 
         for _ in 0..40 {
@@ -442,12 +589,16 @@ impl LlmService {
                 .header("x-goog-api-key", api_key)
                 .send()
                 .await
-                .map_err(|e| format!("gemini get file failed: {e}"))?;
+                .map_err(|e| LlmError::from_reqwest("gemini get file failed", e))?;
 
             if !response.status().is_success() {
                 let status = response.status();
                 let body = response.text().await.unwrap_or_default();
-                return Err(format!("gemini get file failed: {status} {body}"));
+                return Err(LlmError::from_status(
+                    "gemini get file failed",
+                    status,
+                    &body,
+                ));
             }
 
             let info: GeminiUploadResponse = response
@@ -458,7 +609,7 @@ impl LlmService {
             match file.state.as_deref() {
                 Some("ACTIVE") => return Ok(file),
                 Some("FAILED") => {
-                    return Err(format!("gemini file processing failed: {file_name}"));
+                    return Err(format!("gemini file processing failed: {file_name}").into());
                 }
                 _ => {
                     sleep(Duration::from_millis(500)).await;
@@ -466,7 +617,7 @@ impl LlmService {
             }
         }
 
-        Err(format!("gemini file processing timeout: {file_name}"))
+        Err(format!("gemini file processing timeout: {file_name}").into())
     }
 }
 
@@ -482,7 +633,7 @@ fn build_base_url(base_url: Option<&str>) -> String {
     }
 }
 
-fn guess_mime_type(path: &str) -> String {
+pub(super) fn guess_mime_type(path: &str) -> String {
     let ext = Path::new(path)
         .extension()
         .and_then(|ext| ext.to_str())
@@ -535,6 +686,22 @@ struct GeminiGenerateRequest {
     contents: Vec<GeminiContent>,
     #[serde(rename = "generationConfig", skip_serializing_if = "Option::is_none")]
     generation_config: Option<GeminiGenerationConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<Vec<GeminiTool>>,
+}
+
+#[derive(Serialize)]
+struct GeminiTool {
+    #[serde(rename = "functionDeclarations")]
+    function_declarations: Vec<GeminiFunctionDeclaration>,
+}
+
+#[derive(Serialize)]
+struct GeminiFunctionDeclaration {
+    name: String,
+    description: String,
+    #[serde(rename = "parametersJsonSchema")]
+    parameters_json_schema: serde_json::Value,
 }
 
 #[derive(Serialize, Clone)]
@@ -550,24 +717,80 @@ struct GeminiPart {
     text: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     file_data: Option<GeminiFileData>,
+    #[serde(rename = "functionCall", skip_serializing_if = "Option::is_none")]
+    function_call: Option<GeminiFunctionCall>,
+    #[serde(rename = "functionResponse", skip_serializing_if = "Option::is_none")]
+    function_response: Option<GeminiFunctionResponse>,
+    #[serde(rename = "thoughtSignature", skip_serializing_if = "Option::is_none")]
+    thought_signature: Option<String>,
 }
 
 impl GeminiPart {
+    fn empty() -> Self {
+        Self {
+            text: None,
+            file_data: None,
+            function_call: None,
+            function_response: None,
+            thought_signature: None,
+        }
+    }
+
     fn text(text: String) -> Self {
         Self {
             text: Some(text),
-            file_data: None,
+            ..Self::empty()
         }
     }
 
     fn file(file_data: GeminiFileData) -> Self {
         Self {
-            text: None,
             file_data: Some(file_data),
+            ..Self::empty()
+        }
+    }
+
+    fn function_call(call: &ToolCall) -> Self {
+        Self {
+            function_call: Some(GeminiFunctionCall {
+                id: call.id.clone(),
+                name: call.name.clone(),
+                args: (!call.args.is_null()).then(|| call.args.clone()),
+            }),
+            thought_signature: call.thought_signature.clone(),
+            ..Self::empty()
+        }
+    }
+
+    fn function_response(result: &ToolResult) -> Self {
+        Self {
+            function_response: Some(GeminiFunctionResponse {
+                id: result.id.clone(),
+                name: result.name.clone(),
+                response: result.response.clone(),
+            }),
+            ..Self::empty()
         }
     }
 }
 
+#[derive(Serialize, Deserialize, Clone)]
+struct GeminiFunctionCall {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    args: Option<serde_json::Value>,
+}
+
+#[derive(Serialize, Clone)]
+struct GeminiFunctionResponse {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    name: String,
+    response: serde_json::Value,
+}
+
 #[derive(Serialize, Clone)]
 #[serde(rename_all = "snake_case")]
 struct GeminiFileData {
@@ -610,6 +833,8 @@ struct GeminiFileRecord {
 #[derive(Deserialize)]
 struct GeminiGenerateResponse {
     candidates: Option<Vec<GeminiCandidate>>,
+    #[serde(rename = "usageMetadata")]
+    usage_metadata: Option<GeminiUsageMetadata>,
 }
 
 #[derive(Deserialize)]
@@ -652,6 +877,8 @@ struct GeminiStreamPart {
     text: Option<String>,
     #[serde(default)]
     thought: bool,
+    function_call: Option<GeminiFunctionCall>,
+    thought_signature: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -663,6 +890,17 @@ struct GeminiUsageMetadata {
     total_token_count: Option<i64>,
 }
 
+impl GeminiUsageMetadata {
+    fn into_usage(self) -> Option<ChatUsage> {
+        Some(ChatUsage {
+            input_tokens: self.prompt_token_count?,
+            output_tokens: self.candidates_token_count?,
+            reasoning_tokens: self.thoughts_token_count.unwrap_or(0),
+            total_tokens: self.total_token_count?,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -679,6 +917,7 @@ mod tests {
             api_key: TEST_API_KEY.to_string(),
             base_url: None,
             enabled: true,
+            requests_per_minute: None,
         }
     }
 
@@ -1105,6 +1344,7 @@ mod tests {
             api_key: "".to_string(),
             base_url: None,
             enabled: true,
+            requests_per_minute: None,
         };
 
         let messages = vec![ChatMessage::new(ChatRole::User, "Hello")];
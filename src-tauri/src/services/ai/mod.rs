@@ -1,6 +1,9 @@
 mod agent;
 mod embedding;
+mod error;
 mod llm;
+mod ollama;
+mod rate_limit;
 mod search;
 mod types;
 
@@ -8,10 +11,15 @@ use std::sync::Arc;
 
 use tokio::sync::watch;
 
+use crate::db::DbPool;
 use crate::services::AIConfigService;
 
-pub use agent::AgentService;
-pub use embedding::{EmbeddingService, TextSegment};
+pub use agent::{AgentService, ToolExecutor};
+pub use embedding::{
+    EmbeddingService, SearchResult, StoredChunk, TextSegment, VectorGroupCount, VectorIndexStatus,
+    VectorOptimizeStats, VectorStats,
+};
+pub use error::LlmError;
 pub use llm::LlmService;
 pub use search::SearchService;
 pub use types::*;
@@ -25,11 +33,11 @@ pub struct AiServices {
 }
 
 impl AiServices {
-    pub async fn new(config_service: &AIConfigService) -> Result<Self, String> {
+    pub async fn new(config_service: &AIConfigService, db: DbPool) -> Result<Self, String> {
         let vector_config = config_service.get_vector_config()?;
         let embedding = Arc::new(EmbeddingService::new(vector_config).await?);
         let llm = Arc::new(LlmService::new());
-        let agent = Arc::new(AgentService::new(llm.clone()));
+        let agent = Arc::new(AgentService::new(llm.clone(), db));
         let search = Arc::new(SearchService::new(embedding.clone()));
 
         Ok(Self {
@@ -39,6 +47,16 @@ impl AiServices {
             search,
         })
     }
+
+    /// Same LLM / agent, with search running on another embedding service
+    pub fn with_embedding(&self, embedding: Arc<EmbeddingService>) -> Self {
+        Self {
+            search: Arc::new(SearchService::new(embedding.clone())),
+            embedding,
+            llm: self.llm.clone(),
+            agent: self.agent.clone(),
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -67,6 +85,14 @@ impl AiServicesHandle {
         let _ = self.sender.send(AiServicesStatus::Error(error));
     }
 
+    /// 已就绪时立即返回，初始化中或失败时返回 None（不等待）
+    pub fn try_ready(&self) -> Option<Arc<AiServices>> {
+        match &*self.sender.borrow() {
+            AiServicesStatus::Ready(services) => Some(services.clone()),
+            _ => None,
+        }
+    }
+
     pub async fn wait_ready(&self) -> Result<Arc<AiServices>, String> {
         let mut receiver = self.sender.subscribe();
         loop {
@@ -0,0 +1,424 @@
+//! Ollama provider - local models over the Ollama HTTP API
+//!
+//! Used for fully offline operation. No API key is needed; `base_url` defaults
+//! to the local daemon. Images are sent inline (base64); text files are inlined
+//! into the message. Other binary files (e.g. PDF) are rejected with an error;
+//! there is no fallback to the parsed node content.
+
+use std::path::Path;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use futures_util::StreamExt;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use super::error::LlmError;
+use super::llm::guess_mime_type;
+use super::types::{ChatMessage, ChatRole, ChatStreamEvent, ChatUsage, LocalModel};
+
+const DEFAULT_OLLAMA_BASE_URL: &str = "http://localhost:11434";
+
+pub(super) fn build_ollama_base_url(base_url: Option<&str>) -> String {
+    let base = base_url.unwrap_or_default().trim().trim_end_matches('/');
+    if base.is_empty() {
+        DEFAULT_OLLAMA_BASE_URL.to_string()
+    } else {
+        base.to_string()
+    }
+}
+
+pub(super) async fn stream_chat<F, Fut>(
+    client: &Client,
+    base_url: &str,
+    model: &str,
+    messages: &[ChatMessage],
+    thinking_effort: Option<&str>,
+    mut on_event: F,
+) -> Result<(), String>
+where
+    F: FnMut(ChatStreamEvent) -> Fut,
+    Fut: std::future::Future<Output = Result<(), String>>,
+{
+    let mut ollama_messages = Vec::with_capacity(messages.len());
+    for message in messages {
+        let role = match message.role {
+            ChatRole::User => "user",
+            ChatRole::Assistant => "assistant",
+            ChatRole::System => "system",
+        };
+        let mut content = message.content.clone();
+        for file_path in &message.files {
+            content.push_str(&read_text_attachment(file_path)?);
+        }
+        let images = message
+            .images
+            .iter()
+            .map(|path| encode_image(path))
+            .collect::<Result<Vec<_>, _>>()?;
+        if content.trim().is_empty() && images.is_empty() {
+            continue;
+        }
+        ollama_messages.push(OllamaMessage {
+            role: role.to_string(),
+            content,
+            images,
+        });
+    }
+
+    if ollama_messages.is_empty() {
+        return Err("no messages to send".to_string());
+    }
+
+    let request = OllamaChatRequest {
+        model,
+        messages: ollama_messages,
+        stream: true,
+        format: None,
+        think: build_think(thinking_effort),
+    };
+
+    let response = client
+        .post(format!("{base_url}/api/chat"))
+        .json(&request)
+        .send()
+        .await
+        .map_err(|e| format!("ollama stream request failed: {e}"))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("ollama stream request failed: {status} {body}"));
+    }
+
+    // NDJSON: one chunk per line
+    let mut stream = response.bytes_stream();
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut answer_text = String::new();
+    let mut thinking_text = String::new();
+    let mut usage: Option<ChatUsage> = None;
+
+    while let Some(chunk_result) = stream.next().await {
+        let bytes = chunk_result.map_err(|e| format!("ollama stream read error: {e}"))?;
+        buffer.extend_from_slice(&bytes);
+
+        while let Some(pos) = buffer.iter().position(|&b| b == b'\n') {
+            let line_bytes = buffer[..pos].to_vec();
+            buffer.drain(..pos + 1);
+            let line = String::from_utf8_lossy(&line_bytes).trim().to_string();
+            if line.is_empty() {
+                continue;
+            }
+
+            debug!(chunk_json = %line, "Ollama stream response chunk");
+            let chunk: OllamaChatResponse = serde_json::from_str(&line)
+                .map_err(|e| format!("ollama stream payload invalid: {e}"))?;
+            if let Some(error) = chunk.error {
+                return Err(format!("ollama stream failed: {error}"));
+            }
+
+            if let Some(message) = chunk.message {
+                if let Some(thinking) = message.thinking.filter(|text| !text.is_empty()) {
+                    thinking_text.push_str(&thinking);
+                    on_event(ChatStreamEvent::ThinkingDelta(thinking)).await?;
+                }
+                if !message.content.is_empty() {
+                    answer_text.push_str(&message.content);
+                    on_event(ChatStreamEvent::AnswerDelta(message.content)).await?;
+                }
+            }
+
+            if chunk.done {
+                if let (Some(input), Some(output)) = (chunk.prompt_eval_count, chunk.eval_count) {
+                    usage = Some(ChatUsage {
+                        input_tokens: input,
+                        output_tokens: output,
+                        reasoning_tokens: 0,
+                        total_tokens: input + output,
+                    });
+                }
+            }
+        }
+    }
+
+    if !answer_text.is_empty() {
+        on_event(ChatStreamEvent::AnswerFullText(answer_text)).await?;
+    }
+    if !thinking_text.is_empty() {
+        on_event(ChatStreamEvent::ThinkingFullText(thinking_text)).await?;
+    }
+    if let Some(usage) = usage {
+        on_event(ChatStreamEvent::Usage(usage)).await?;
+    }
+
+    Ok(())
+}
+
+pub(super) async fn generate_structured_json(
+    client: &Client,
+    base_url: &str,
+    model: &str,
+    prompt: &str,
+    schema: serde_json::Value,
+    file_path: Option<&str>,
+    thinking_effort: Option<&str>,
+) -> Result<(String, Option<ChatUsage>), LlmError> {
+    let mut content = prompt.to_string();
+    let mut images = Vec::new();
+    if let Some(path) = file_path {
+        if guess_mime_type(path).starts_with("image/") {
+            images.push(encode_image(path)?);
+        } else {
+            content.push_str(&read_text_attachment(path)?);
+        }
+    }
+
+    let request = OllamaChatRequest {
+        model,
+        messages: vec![OllamaMessage {
+            role: "user".to_string(),
+            content,
+            images,
+        }],
+        stream: false,
+        format: Some(schema),
+        think: build_think(thinking_effort),
+    };
+
+    let response = client
+        .post(format!("{base_url}/api/chat"))
+        .json(&request)
+        .send()
+        .await
+        .map_err(|e| LlmError::from_reqwest("ollama request failed", e))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(LlmError::from_status(
+            "ollama request failed",
+            status,
+            &body,
+        ));
+    }
+
+    let response_text = response
+        .text()
+        .await
+        .map_err(|e| LlmError::from_reqwest("ollama response read failed", e))?;
+    debug!(model = %model, response_json = %response_text, "Ollama structured response");
+
+    let response: OllamaChatResponse = serde_json::from_str(&response_text)
+        .map_err(|e| format!("ollama response invalid: {e}"))?;
+    if let Some(error) = response.error {
+        return Err(format!("ollama request failed: {error}").into());
+    }
+
+    let usage = match (response.prompt_eval_count, response.eval_count) {
+        (Some(input), Some(output)) => Some(ChatUsage {
+            input_tokens: input,
+            output_tokens: output,
+            reasoning_tokens: 0,
+            total_tokens: input + output,
+        }),
+        _ => None,
+    };
+    let output = response
+        .message
+        .map(|message| message.content)
+        .unwrap_or_default();
+    if output.trim().is_empty() {
+        return Err("ollama response missing text".into());
+    }
+    Ok((output, usage))
+}
+
+/// List models installed in the local Ollama daemon
+pub(super) async fn list_models(
+    client: &Client,
+    base_url: &str,
+) -> Result<Vec<LocalModel>, String> {
+    let response = client
+        .get(format!("{base_url}/api/tags"))
+        .send()
+        .await
+        .map_err(|e| format!("ollama not reachable at {base_url}: {e}"))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("ollama list models failed: {status} {body}"));
+    }
+
+    let tags: OllamaTagsResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("ollama list models response invalid: {e}"))?;
+
+    let mut models: Vec<LocalModel> = tags
+        .models
+        .into_iter()
+        .map(|entry| LocalModel {
+            name: entry.name,
+            size_bytes: entry.size,
+            modified_at: entry.modified_at,
+            family: entry.details.family,
+            parameter_size: entry.details.parameter_size,
+            quantization_level: entry.details.quantization_level,
+        })
+        .collect();
+    models.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(models)
+}
+
+/// Thinking is on/off for Ollama; any non-empty effort other than "none" enables it
+fn build_think(thinking_effort: Option<&str>) -> Option<bool> {
+    let effort = thinking_effort?.trim();
+    if effort.is_empty() {
+        return None;
+    }
+    Some(!effort.eq_ignore_ascii_case("none"))
+}
+
+fn encode_image(path: &str) -> Result<String, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("read image failed: {e}"))?;
+    Ok(BASE64.encode(bytes))
+}
+
+fn read_text_attachment(path: &str) -> Result<String, String> {
+    let mime = guess_mime_type(path);
+    if !is_text_mime(&mime) {
+        return Err(format!("ollama does not support {mime} attachments"));
+    }
+    let text = std::fs::read_to_string(path).map_err(|e| format!("read file failed: {e}"))?;
+    let name = Path::new(path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("file");
+    Ok(format!("\n\n[{name}]\n{text}"))
+}
+
+fn is_text_mime(mime: &str) -> bool {
+    mime.starts_with("text/") || mime == "application/json" || mime == "application/xml"
+}
+
+#[derive(Serialize)]
+struct OllamaChatRequest<'a> {
+    model: &'a str,
+    messages: Vec<OllamaMessage>,
+    stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    think: Option<bool>,
+}
+
+#[derive(Serialize)]
+struct OllamaMessage {
+    role: String,
+    content: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    images: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct OllamaChatResponse {
+    message: Option<OllamaResponseMessage>,
+    #[serde(default)]
+    done: bool,
+    prompt_eval_count: Option<i64>,
+    eval_count: Option<i64>,
+    error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct OllamaResponseMessage {
+    #[serde(default)]
+    content: String,
+    thinking: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct OllamaTagsResponse {
+    #[serde(default)]
+    models: Vec<OllamaModelEntry>,
+}
+
+#[derive(Deserialize)]
+struct OllamaModelEntry {
+    name: String,
+    #[serde(default)]
+    size: u64,
+    modified_at: Option<String>,
+    #[serde(default)]
+    details: OllamaModelDetails,
+}
+
+#[derive(Deserialize, Default)]
+struct OllamaModelDetails {
+    family: Option<String>,
+    parameter_size: Option<String>,
+    quantization_level: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_ollama_base_url() {
+        assert_eq!(build_ollama_base_url(None), DEFAULT_OLLAMA_BASE_URL);
+        assert_eq!(build_ollama_base_url(Some("  ")), DEFAULT_OLLAMA_BASE_URL);
+        assert_eq!(
+            build_ollama_base_url(Some("http://gpu-box:11434/")),
+            "http://gpu-box:11434"
+        );
+    }
+
+    #[test]
+    fn test_build_think() {
+        assert_eq!(build_think(None), None);
+        assert_eq!(build_think(Some("")), None);
+        assert_eq!(build_think(Some("high")), Some(true));
+        assert_eq!(build_think(Some("None")), Some(false));
+    }
+
+    #[test]
+    fn test_parse_stream_chunks() {
+        let delta: OllamaChatResponse = serde_json::from_str(
+            r#"{"model":"qwen3","message":{"role":"assistant","content":"Hi","thinking":"hmm"},"done":false}"#,
+        )
+        .unwrap();
+        let message = delta.message.unwrap();
+        assert_eq!(message.content, "Hi");
+        assert_eq!(message.thinking.as_deref(), Some("hmm"));
+        assert!(!delta.done);
+
+        let last: OllamaChatResponse = serde_json::from_str(
+            r#"{"model":"qwen3","message":{"role":"assistant","content":""},"done":true,"prompt_eval_count":12,"eval_count":30}"#,
+        )
+        .unwrap();
+        assert!(last.done);
+        assert_eq!(last.prompt_eval_count, Some(12));
+        assert_eq!(last.eval_count, Some(30));
+    }
+
+    #[test]
+    fn test_parse_tags_response() {
+        let tags: OllamaTagsResponse = serde_json::from_str(
+            r#"{"models":[{"name":"llama3.2:latest","model":"llama3.2:latest","size":2019393189,
+                "modified_at":"2025-01-01T00:00:00Z",
+                "details":{"family":"llama","parameter_size":"3.2B","quantization_level":"Q4_K_M"}},
+               {"name":"bare"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(tags.models.len(), 2);
+        assert_eq!(
+            tags.models[0].details.parameter_size.as_deref(),
+            Some("3.2B")
+        );
+        assert_eq!(tags.models[1].size, 0);
+        assert!(tags.models[1].details.family.is_none());
+    }
+}
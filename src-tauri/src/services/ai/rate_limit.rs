@@ -0,0 +1,167 @@
+//! Per-provider token bucket for outbound LLM requests
+//!
+//! Every chat turn and structured generation takes one token from the bucket of its provider.
+//! Background work (pipeline summaries, classification, re-indexing) leaves a share of the
+//! bucket untouched so that interactive chat still gets through while a bulk job is running.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use tokio::sync::Mutex;
+use tokio::time::sleep;
+
+/// Share of the bucket that background requests may not use
+const INTERACTIVE_RESERVE: f64 = 0.2;
+/// Upper bound for a single wait, so a changed limit is picked up reasonably soon
+const MAX_WAIT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestClass {
+    /// User-facing chat; may drain the whole bucket
+    Interactive,
+    /// Pipeline / bulk work; keeps [`INTERACTIVE_RESERVE`] of the bucket free
+    Background,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    requests_per_minute: u32,
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl TokenBucket {
+    fn new(requests_per_minute: u32, now: Instant) -> Self {
+        Self {
+            requests_per_minute,
+            tokens: requests_per_minute as f64,
+            updated_at: now,
+        }
+    }
+
+    fn capacity(&self) -> f64 {
+        self.requests_per_minute as f64
+    }
+
+    fn refill_per_sec(&self) -> f64 {
+        self.capacity() / 60.0
+    }
+
+    /// Takes a token if one is available above the class's floor, otherwise returns how long
+    /// to wait before trying again
+    fn try_acquire(&mut self, now: Instant, class: RequestClass) -> Result<(), Duration> {
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_sec()).min(self.capacity());
+        self.updated_at = now;
+
+        let floor = match class {
+            RequestClass::Interactive => 0.0,
+            // Buckets under five requests per minute have no room for a reserve
+            RequestClass::Background => (self.capacity() * INTERACTIVE_RESERVE).floor(),
+        };
+        if self.tokens - 1.0 >= floor {
+            self.tokens -= 1.0;
+            return Ok(());
+        }
+        let missing = floor + 1.0 - self.tokens;
+        Err(Duration::from_secs_f64(missing / self.refill_per_sec()).min(MAX_WAIT))
+    }
+}
+
+/// Shared by all LLM calls; unlimited providers never wait
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    buckets: Mutex<HashMap<String, TokenBucket>>,
+}
+
+impl RateLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Waits until the provider's bucket allows another request; `None` or 0 disables the limit
+    pub async fn acquire(
+        &self,
+        provider: &str,
+        requests_per_minute: Option<u32>,
+        class: RequestClass,
+    ) {
+        let Some(requests_per_minute) = requests_per_minute.filter(|rpm| *rpm > 0) else {
+            return;
+        };
+        loop {
+            let wait = {
+                let mut buckets = self.buckets.lock().await;
+                let now = Instant::now();
+                let bucket = buckets
+                    .entry(provider.to_string())
+                    .or_insert_with(|| TokenBucket::new(requests_per_minute, now));
+                if bucket.requests_per_minute != requests_per_minute {
+                    *bucket = TokenBucket::new(requests_per_minute, now);
+                }
+                match bucket.try_acquire(now, class) {
+                    Ok(()) => return,
+                    Err(wait) => wait,
+                }
+            };
+            tracing::debug!(
+                provider,
+                ?class,
+                wait_ms = wait.as_millis() as u64,
+                "LLM request rate limited"
+            );
+            sleep(wait).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_drains_and_refills() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(60, start);
+        for _ in 0..60 {
+            assert!(bucket.try_acquire(start, RequestClass::Interactive).is_ok());
+        }
+        let wait = bucket
+            .try_acquire(start, RequestClass::Interactive)
+            .unwrap_err();
+        assert_eq!(wait, Duration::from_secs(1));
+        assert!(bucket
+            .try_acquire(start + Duration::from_secs(1), RequestClass::Interactive)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_background_keeps_reserve() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(10, start);
+        let mut background = 0;
+        while bucket.try_acquire(start, RequestClass::Background).is_ok() {
+            background += 1;
+        }
+        assert_eq!(background, 8);
+        assert!(bucket.try_acquire(start, RequestClass::Interactive).is_ok());
+        assert!(bucket.try_acquire(start, RequestClass::Interactive).is_ok());
+        assert!(bucket
+            .try_acquire(start, RequestClass::Interactive)
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_unlimited_provider_does_not_wait() {
+        let limiter = RateLimiter::new();
+        for _ in 0..100 {
+            limiter
+                .acquire("gemini", None, RequestClass::Background)
+                .await;
+            limiter
+                .acquire("ollama", Some(0), RequestClass::Background)
+                .await;
+        }
+        assert!(limiter.buckets.lock().await.is_empty());
+    }
+}
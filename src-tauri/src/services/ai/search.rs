@@ -1,6 +1,10 @@
 use std::sync::Arc;
 
 use super::embedding::{EmbeddingService, SearchResult};
+use crate::services::{start_timer, MetricHistogram, RetrievalConfig};
+
+/// Candidates fetched for the cross-encoder before truncating to top_k
+const RERANK_CANDIDATES: u32 = 50;
 
 pub struct SearchService {
     embedding: Arc<EmbeddingService>,
@@ -16,10 +20,69 @@ impl SearchService {
         query: &str,
         embedding_type: &str,
         node_ids: Option<&[i64]>,
+        excluded_node_ids: &[i64],
+        limit: u64,
+    ) -> Result<Vec<SearchResult>, String> {
+        let _timer = start_timer(MetricHistogram::Search);
+        self.embedding
+            .search_hybrid(query, embedding_type, node_ids, excluded_node_ids, limit)
+            .await
+    }
+
+    pub async fn search_by_image(
+        &self,
+        image_path: &str,
+        query: Option<&str>,
+        node_ids: Option<&[i64]>,
+        excluded_node_ids: &[i64],
         limit: u64,
     ) -> Result<Vec<SearchResult>, String> {
+        let _timer = start_timer(MetricHistogram::Search);
+        self.embedding
+            .search_by_image(image_path, query, node_ids, excluded_node_ids, limit)
+            .await
+    }
+
+    /// Hybrid search; with `retrieval.rerank` the top candidates are reordered by the
+    /// cross-encoder before truncating to `top_k`
+    pub async fn search_hybrid_with(
+        &self,
+        query: &str,
+        embedding_type: &str,
+        node_ids: Option<&[i64]>,
+        excluded_node_ids: &[i64],
+        retrieval: &RetrievalConfig,
+    ) -> Result<Vec<SearchResult>, String> {
+        let _timer = start_timer(MetricHistogram::Search);
+        if !retrieval.rerank {
+            return self
+                .embedding
+                .search_hybrid_with(
+                    query,
+                    embedding_type,
+                    node_ids,
+                    excluded_node_ids,
+                    retrieval,
+                )
+                .await;
+        }
+
+        let candidate_retrieval = RetrievalConfig {
+            top_k: retrieval.top_k.max(RERANK_CANDIDATES),
+            ..*retrieval
+        };
+        let candidates = self
+            .embedding
+            .search_hybrid_with(
+                query,
+                embedding_type,
+                node_ids,
+                excluded_node_ids,
+                &candidate_retrieval,
+            )
+            .await?;
         self.embedding
-            .search_hybrid(query, embedding_type, node_ids, limit)
+            .rerank(query, candidates, retrieval.top_k as usize)
             .await
     }
 }
@@ -14,6 +14,12 @@ pub struct ChatMessage {
     pub content: String,
     pub images: Vec<String>,
     pub files: Vec<String>,
+    /// Tool calls requested by the model in this (assistant) turn
+    #[serde(default)]
+    pub tool_calls: Vec<ToolCall>,
+    /// Tool results sent back to the model in this (user) turn
+    #[serde(default)]
+    pub tool_results: Vec<ToolResult>,
 }
 
 impl ChatMessage {
@@ -23,10 +29,49 @@ impl ChatMessage {
             content: content.into(),
             images: Vec::new(),
             files: Vec::new(),
+            tool_calls: Vec::new(),
+            tool_results: Vec::new(),
+        }
+    }
+
+    pub fn tool_results(results: Vec<ToolResult>) -> Self {
+        Self {
+            tool_results: results,
+            ..Self::new(ChatRole::User, "")
         }
     }
 }
 
+/// A tool the model may call, declared as a JSON schema
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolSpec {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub parameters: serde_json::Value,
+}
+
+/// A tool call requested by the model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolCall {
+    #[serde(default)]
+    pub id: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    pub args: serde_json::Value,
+    /// Opaque signature that must be echoed back with the call (Gemini thinking models)
+    #[serde(default)]
+    pub thought_signature: Option<String>,
+}
+
+/// Result of executing a tool call
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolResult {
+    #[serde(default)]
+    pub id: Option<String>,
+    pub name: String,
+    pub response: serde_json::Value,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatUsage {
     pub input_tokens: i64,
@@ -43,6 +88,8 @@ pub enum ChatStreamEvent {
     Error(String),
     AnswerFullText(String),
     ThinkingFullText(String),
+    /// The model called a tool; emitted before the tool runs
+    ToolCall(ToolCall),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -108,3 +155,65 @@ pub enum ClassifyTopicResponse {
         confidence_score: f64,
     },
 }
+
+/// 本地（Ollama）已安装的模型
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalModel {
+    pub name: String,
+    pub size_bytes: u64,
+    pub modified_at: Option<String>,
+    pub family: Option<String>,
+    pub parameter_size: Option<String>,
+    pub quantization_level: Option<String>,
+}
+
+/// LLM 起草的月度回顾
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoalReviewDraft {
+    pub summary: String,
+    #[serde(default)]
+    pub progressed: Vec<String>,
+    #[serde(default)]
+    pub stalled: Vec<String>,
+    #[serde(default)]
+    pub next_actions: Vec<String>,
+}
+
+/// LLM 建议的主题层级调整（未校验，op 为 rename / move / merge）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopicOperationDraft {
+    pub op: String,
+    pub topic_id: i64,
+    #[serde(default)]
+    pub new_title: Option<String>,
+    #[serde(default)]
+    pub new_parent_id: Option<i64>,
+    #[serde(default)]
+    pub target_topic_id: Option<i64>,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// LLM 对收件箱中单个资源的处理建议（未校验，action 为 link_topic / convert_to_task /
+/// archive / delete / keep）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InboxTriageDraft {
+    pub node_id: i64,
+    pub action: String,
+    #[serde(default)]
+    pub topic_id: Option<i64>,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// LLM 抽取的文献元数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CitationDraft {
+    pub is_academic: bool,
+    pub title: Option<String>,
+    #[serde(default)]
+    pub authors: Vec<String>,
+    pub year: Option<i64>,
+    pub venue: Option<String>,
+    pub doi: Option<String>,
+}
@@ -6,6 +6,8 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
+use crate::db::ExportFormat;
+use crate::i18n::Language;
 use crate::utils::crypto::CryptoService;
 
 /// Provider 配置
@@ -14,6 +16,14 @@ pub struct ProviderConfig {
     pub api_key: String,
     pub base_url: Option<String>,
     pub enabled: bool,
+    /// 每分钟最多请求数，对话与后台处理共用；为空时不限制
+    #[serde(default)]
+    pub requests_per_minute: Option<u32>,
+}
+
+/// 本地 provider（Ollama）无需 API Key
+pub fn provider_requires_api_key(provider: &str) -> bool {
+    !provider.eq_ignore_ascii_case("ollama")
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,6 +38,15 @@ pub struct VectorConfig {
     pub image_vector_size: u64,
     pub chunk_size: usize,
     pub chunk_overlap: usize,
+    #[serde(default)]
+    pub ann_index: AnnIndexConfig,
+    /// 检索结果重排使用的 cross-encoder 模型
+    #[serde(default = "default_reranker_model")]
+    pub reranker_model: String,
+}
+
+fn default_reranker_model() -> String {
+    "BAAI/bge-reranker-base".to_string()
 }
 
 impl Default for VectorConfig {
@@ -43,6 +62,8 @@ impl Default for VectorConfig {
             image_vector_size: 512,
             chunk_size: 512,
             chunk_overlap: 50,
+            ann_index: AnnIndexConfig::default(),
+            reranker_model: default_reranker_model(),
         }
     }
 }
@@ -56,6 +77,162 @@ impl VectorConfig {
     }
 }
 
+/// 训练 IVF_PQ 至少需要的行数（PQ 码本有 256 个中心）
+pub const MIN_ANN_INDEX_ROWS: usize = 256;
+pub const MAX_ANN_NPROBES: usize = 1024;
+
+/// 向量列的 ANN 索引（IVF_PQ），没有索引时向量检索为暴力扫描
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AnnIndexConfig {
+    /// 某一向量列的行数达到该值后自动建立索引
+    pub min_rows: usize,
+    /// 查询时探查的分区数，越大召回越高、越慢
+    pub nprobes: usize,
+    /// 取 limit * refine_factor 个候选用原始向量重排；为空时不重排
+    pub refine_factor: Option<u32>,
+}
+
+impl Default for AnnIndexConfig {
+    fn default() -> Self {
+        Self {
+            min_rows: 10_000,
+            nprobes: 20,
+            refine_factor: Some(5),
+        }
+    }
+}
+
+impl AnnIndexConfig {
+    pub fn clamped(self) -> Self {
+        Self {
+            min_rows: self.min_rows.max(MIN_ANN_INDEX_ROWS),
+            nprobes: self.nprobes.clamp(1, MAX_ANN_NPROBES),
+            refine_factor: self.refine_factor.filter(|factor| *factor > 0),
+        }
+    }
+}
+
+/// 向量检索的距离度量
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VectorDistance {
+    Cosine,
+    L2,
+    Dot,
+}
+
+/// 文本与图片检索结果的合并方式
+///
+/// 两路分数量纲不同（混合检索的相关度 vs 1 - 距离），max 直接比较原始分数，
+/// 分类等依赖分数阈值的调用沿用它；rrf 只看排名，normalized 把每路分数缩放到 0–1 后相加
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FusionStrategy {
+    #[default]
+    Max,
+    Rrf,
+    Normalized,
+}
+
+/// 对话 RAG 检索参数
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetrievalConfig {
+    /// 每次检索的分块数
+    pub top_k: u32,
+    pub distance: VectorDistance,
+    /// 混合检索中向量结果的权重（0–1），其余给全文检索；0.5 为等权
+    pub vector_weight: f32,
+    pub fusion: FusionStrategy,
+    /// 先取更多候选，用 cross-encoder 重排后再截取 top_k
+    pub rerank: bool,
+}
+
+pub const MAX_RETRIEVAL_TOP_K: u32 = 50;
+
+impl Default for RetrievalConfig {
+    fn default() -> Self {
+        Self {
+            top_k: 5,
+            distance: VectorDistance::Cosine,
+            vector_weight: 0.5,
+            fusion: FusionStrategy::default(),
+            rerank: false,
+        }
+    }
+}
+
+/// 对话上下文窗口：历史超过 max_input_tokens 时把较早的消息压缩为摘要
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChatContextConfig {
+    /// 发送给模型的历史消息（含摘要）的估算 token 上限
+    pub max_input_tokens: u32,
+    /// 压缩时至少原样保留的最近消息数
+    pub min_recent_messages: u32,
+}
+
+impl Default for ChatContextConfig {
+    fn default() -> Self {
+        Self {
+            max_input_tokens: 32_000,
+            min_recent_messages: 4,
+        }
+    }
+}
+
+impl ChatContextConfig {
+    pub fn clamped(self) -> Self {
+        Self {
+            max_input_tokens: self.max_input_tokens.clamp(2_000, 2_000_000),
+            min_recent_messages: self.min_recent_messages.clamp(1, 50),
+        }
+    }
+}
+
+/// 单个会话对检索参数的临时覆盖，未给出的字段沿用全局配置
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+pub struct RetrievalOverrides {
+    pub top_k: Option<u32>,
+    pub distance: Option<VectorDistance>,
+    pub vector_weight: Option<f32>,
+    pub fusion: Option<FusionStrategy>,
+    pub rerank: Option<bool>,
+}
+
+impl RetrievalConfig {
+    /// 把 top_k 与权重限制在有效范围内
+    pub fn clamped(self) -> Self {
+        let vector_weight = if self.vector_weight.is_finite() {
+            self.vector_weight.clamp(0.0, 1.0)
+        } else {
+            Self::default().vector_weight
+        };
+        Self {
+            top_k: self.top_k.clamp(1, MAX_RETRIEVAL_TOP_K),
+            distance: self.distance,
+            vector_weight,
+            fusion: self.fusion,
+            rerank: self.rerank,
+        }
+    }
+
+    pub fn with_overrides(self, overrides: Option<&RetrievalOverrides>) -> Self {
+        let Some(overrides) = overrides else {
+            return self.clamped();
+        };
+        Self {
+            top_k: overrides.top_k.unwrap_or(self.top_k),
+            distance: overrides.distance.unwrap_or(self.distance),
+            vector_weight: overrides.vector_weight.unwrap_or(self.vector_weight),
+            fusion: overrides.fusion.unwrap_or(self.fusion),
+            rerank: overrides.rerank.unwrap_or(self.rerank),
+        }
+        .clamped()
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ClassificationMode {
@@ -69,6 +246,108 @@ impl Default for ClassificationMode {
     }
 }
 
+/// 摘要使用的语言
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SummaryLanguageMode {
+    /// 与原文语言一致
+    MatchSource,
+    /// 固定使用界面语言
+    #[default]
+    Configured,
+    /// 原文语言 + 界面语言各一份
+    Bilingual,
+}
+
+/// Slack / Discord 消息转发入口（仅监听 127.0.0.1）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageWebhookConfig {
+    pub enabled: bool,
+    pub port: u16,
+    /// 请求需携带的令牌（Authorization: Bearer），首次启用时生成
+    pub token: String,
+}
+
+impl Default for MessageWebhookConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 17830,
+            token: String::new(),
+        }
+    }
+}
+
+/// Prometheus 指标端点（仅监听 127.0.0.1）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricsEndpointConfig {
+    pub enabled: bool,
+    pub port: u16,
+}
+
+impl Default for MetricsEndpointConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 17831,
+        }
+    }
+}
+
+/// 定期自动导出到指定文件夹
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportScheduleConfig {
+    pub enabled: bool,
+    /// 导出目录（绝对路径）
+    pub folder: Option<String>,
+    pub format: ExportFormat,
+    /// 只导出上次成功导出后有变化的节点与文件（archive 格式始终包含完整数据库快照）
+    pub incremental: bool,
+    pub interval_hours: u32,
+    /// 目录中保留的最近导出文件数
+    pub keep: u32,
+}
+
+impl Default for ExportScheduleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            folder: None,
+            format: ExportFormat::default(),
+            incremental: false,
+            interval_hours: 24,
+            keep: 7,
+        }
+    }
+}
+
+/// 自定义模型价格（美元 / 百万 token），按模型名前缀匹配，优先于内置价格表
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelPrice {
+    pub model_prefix: String,
+    pub input_per_million: f64,
+    pub output_per_million: f64,
+}
+
+/// 捕获模板：用独立的全局快捷键唤起 HUD，捕获时自动归入主题、打标签或推迟 AI 处理
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptureProfile {
+    pub profile_id: i64,
+    pub name: String,
+    /// 全局快捷键，如 `Alt+Shift+W`；为空时只能在 HUD 中手动选择
+    #[serde(default)]
+    pub shortcut: Option<String>,
+    /// 捕获的资源自动归入该主题
+    #[serde(default)]
+    pub topic_id: Option<i64>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// 只解析正文，AI 处理留到手动处理待定资源或下次启动
+    #[serde(default)]
+    pub defer_processing: bool,
+}
+
 /// AI 配置数据结构
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AIConfigData {
@@ -80,6 +359,49 @@ pub struct AIConfigData {
     pub classification_mode: ClassificationMode,
     #[serde(default)]
     pub vector_config: VectorConfig,
+    /// 空闲时预热搜索模型（使用电池时跳过）
+    #[serde(default = "default_warmup_on_idle")]
+    pub warmup_on_idle: bool,
+    /// 界面语言（后端错误消息按此渲染）
+    #[serde(default)]
+    pub language: Language,
+    /// 资源摘要的语言
+    #[serde(default)]
+    pub summary_language: SummaryLanguageMode,
+    /// 消息转发入口
+    #[serde(default)]
+    pub message_webhook: MessageWebhookConfig,
+    /// Prometheus 指标端点
+    #[serde(default)]
+    pub metrics_endpoint: MetricsEndpointConfig,
+    /// 自定义资源文件目录（绝对路径）；为空时使用应用数据目录下的 assets
+    #[serde(default)]
+    pub assets_dir: Option<String>,
+    /// 捕获模板
+    #[serde(default)]
+    pub capture_profiles: Vec<CaptureProfile>,
+    /// 对话 RAG 检索参数
+    #[serde(default)]
+    pub retrieval: RetrievalConfig,
+    /// 对话历史压缩
+    #[serde(default)]
+    pub chat_context: ChatContextConfig,
+    /// 定期自动导出
+    #[serde(default)]
+    pub export_schedule: ExportScheduleConfig,
+    /// 用量报告使用的模型价格
+    #[serde(default)]
+    pub model_prices: Vec<ModelPrice>,
+    /// 自定义模型目录（OCR 模型、pdfium、whisper.cpp，绝对路径）；为空时自动查找
+    #[serde(default)]
+    pub model_dir: Option<String>,
+    /// 下载缺失模型文件的地址（目录 URL，文件名拼在后面）；为空时使用默认地址
+    #[serde(default)]
+    pub model_download_url: Option<String>,
+}
+
+fn default_warmup_on_idle() -> bool {
+    true
 }
 
 impl Default for AIConfigData {
@@ -91,6 +413,19 @@ impl Default for AIConfigData {
             processing_model: None,
             classification_mode: ClassificationMode::Manual,
             vector_config: VectorConfig::default(),
+            warmup_on_idle: default_warmup_on_idle(),
+            language: Language::default(),
+            summary_language: SummaryLanguageMode::default(),
+            message_webhook: MessageWebhookConfig::default(),
+            metrics_endpoint: MetricsEndpointConfig::default(),
+            assets_dir: None,
+            capture_profiles: Vec::new(),
+            retrieval: RetrievalConfig::default(),
+            chat_context: ChatContextConfig::default(),
+            export_schedule: ExportScheduleConfig::default(),
+            model_prices: Vec::new(),
+            model_dir: None,
+            model_download_url: None,
         }
     }
 }
@@ -157,6 +492,10 @@ impl AIConfigService {
         base_url: Option<String>,
     ) -> Result<(), String> {
         let mut config = self.load()?;
+        let requests_per_minute = config
+            .providers
+            .get(provider)
+            .and_then(|p| p.requests_per_minute);
 
         config.providers.insert(
             provider.to_string(),
@@ -164,12 +503,28 @@ impl AIConfigService {
                 api_key: api_key.to_string(),
                 base_url,
                 enabled: true,
+                requests_per_minute,
             },
         );
 
         self.save(&config)
     }
 
+    /// 设置 provider 每分钟请求上限（None 为不限制）
+    pub fn set_provider_rate_limit(
+        &self,
+        provider: &str,
+        requests_per_minute: Option<u32>,
+    ) -> Result<(), String> {
+        let mut config = self.load()?;
+        let provider_config = config
+            .providers
+            .get_mut(provider)
+            .ok_or_else(|| format!("provider {provider} not configured"))?;
+        provider_config.requests_per_minute = requests_per_minute.filter(|rpm| *rpm > 0);
+        self.save(&config)
+    }
+
     /// 删除单个 provider 的配置
     pub fn remove_provider(&self, provider: &str) -> Result<(), String> {
         let mut config = self.load()?;
@@ -207,6 +562,12 @@ impl AIConfigService {
         Ok(config.vector_config)
     }
 
+    pub fn set_vector_config(&self, vector_config: VectorConfig) -> Result<(), String> {
+        let mut config = self.load()?;
+        config.vector_config = vector_config;
+        self.save(&config)
+    }
+
     /// 设置processing provider和model
     pub fn set_processing_provider_model(&self, provider: &str, model: &str) -> Result<(), String> {
         let mut config = self.load()?;
@@ -220,4 +581,82 @@ impl AIConfigService {
         config.classification_mode = mode;
         self.save(&config)
     }
+
+    pub fn set_warmup_on_idle(&self, enabled: bool) -> Result<(), String> {
+        let mut config = self.load()?;
+        config.warmup_on_idle = enabled;
+        self.save(&config)
+    }
+
+    pub fn set_language(&self, language: Language) -> Result<(), String> {
+        let mut config = self.load()?;
+        config.language = language;
+        self.save(&config)
+    }
+
+    pub fn set_summary_language(&self, mode: SummaryLanguageMode) -> Result<(), String> {
+        let mut config = self.load()?;
+        config.summary_language = mode;
+        self.save(&config)
+    }
+
+    pub fn set_message_webhook(&self, webhook: MessageWebhookConfig) -> Result<(), String> {
+        let mut config = self.load()?;
+        config.message_webhook = webhook;
+        self.save(&config)
+    }
+
+    pub fn set_metrics_endpoint(&self, endpoint: MetricsEndpointConfig) -> Result<(), String> {
+        let mut config = self.load()?;
+        config.metrics_endpoint = endpoint;
+        self.save(&config)
+    }
+
+    pub fn set_assets_dir(&self, assets_dir: Option<String>) -> Result<(), String> {
+        let mut config = self.load()?;
+        config.assets_dir = assets_dir;
+        self.save(&config)
+    }
+
+    pub fn set_capture_profiles(&self, profiles: Vec<CaptureProfile>) -> Result<(), String> {
+        let mut config = self.load()?;
+        config.capture_profiles = profiles;
+        self.save(&config)
+    }
+
+    pub fn set_retrieval_config(&self, retrieval: RetrievalConfig) -> Result<(), String> {
+        let mut config = self.load()?;
+        config.retrieval = retrieval.clamped();
+        self.save(&config)
+    }
+
+    pub fn set_chat_context_config(&self, chat_context: ChatContextConfig) -> Result<(), String> {
+        let mut config = self.load()?;
+        config.chat_context = chat_context.clamped();
+        self.save(&config)
+    }
+
+    pub fn set_ann_index_config(&self, ann_index: AnnIndexConfig) -> Result<(), String> {
+        let mut config = self.load()?;
+        config.vector_config.ann_index = ann_index.clamped();
+        self.save(&config)
+    }
+
+    pub fn set_export_schedule(&self, schedule: ExportScheduleConfig) -> Result<(), String> {
+        let mut config = self.load()?;
+        config.export_schedule = schedule;
+        self.save(&config)
+    }
+
+    pub fn set_model_dir(&self, model_dir: Option<String>) -> Result<(), String> {
+        let mut config = self.load()?;
+        config.model_dir = model_dir;
+        self.save(&config)
+    }
+
+    pub fn set_model_prices(&self, prices: Vec<ModelPrice>) -> Result<(), String> {
+        let mut config = self.load()?;
+        config.model_prices = prices;
+        self.save(&config)
+    }
 }
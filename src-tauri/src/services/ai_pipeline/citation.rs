@@ -0,0 +1,149 @@
+//! Citation metadata extraction for academic PDFs
+
+use crate::db::{
+    get_citation_by_node, upsert_citation, CitationRecord, CitationSource, DbPool, NewCitation,
+};
+use crate::services::{AiServices, ProviderConfig};
+
+/// Only the opening pages carry title / authors / DOI
+const CITATION_CONTENT_CHARS: usize = 6000;
+
+/// Extract citation metadata and store it on the node.
+///
+/// Returns `None` when the document is not recognised as academic literature.
+/// Manually edited citations are never overwritten.
+pub(crate) async fn extract_and_store_citation(
+    db: &DbPool,
+    ai: &AiServices,
+    provider: &str,
+    model: &str,
+    provider_config: &ProviderConfig,
+    node_id: i64,
+    content: &str,
+    pdf_path: Option<&str>,
+) -> Result<Option<CitationRecord>, String> {
+    let excerpt: String = content
+        .trim()
+        .chars()
+        .take(CITATION_CONTENT_CHARS)
+        .collect();
+    let draft = ai
+        .agent
+        .extract_citation(provider, model, provider_config, &excerpt, pdf_path)
+        .await?;
+
+    let title = draft
+        .title
+        .as_deref()
+        .map(str::trim)
+        .filter(|title| !title.is_empty());
+    let Some(title) = title.filter(|_| draft.is_academic) else {
+        tracing::debug!(node_id, "Resource not recognised as academic literature");
+        return Ok(None);
+    };
+
+    let authors: Vec<String> = draft
+        .authors
+        .iter()
+        .map(|author| author.trim().to_string())
+        .filter(|author| !author.is_empty())
+        .collect();
+    let year = draft.year.filter(|year| (1000..=2100).contains(year));
+    let venue = draft
+        .venue
+        .as_deref()
+        .map(str::trim)
+        .filter(|venue| !venue.is_empty());
+    // LLM often drops or mangles the DOI; fall back to scanning the text
+    let doi = draft
+        .doi
+        .as_deref()
+        .and_then(find_doi)
+        .or_else(|| find_doi(&excerpt));
+
+    upsert_citation(
+        db,
+        NewCitation {
+            node_id,
+            title,
+            authors: &authors,
+            year,
+            venue,
+            doi: doi.as_deref(),
+            source: CitationSource::Llm,
+        },
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    get_citation_by_node(db, node_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Find the first DOI (`10.<registrant>/<suffix>`) in text, lowercased.
+///
+/// Accepts bare DOIs as well as `doi:` / `https://doi.org/` forms.
+pub(crate) fn find_doi(text: &str) -> Option<String> {
+    let bytes = text.as_bytes();
+    let mut search_from = 0;
+    while let Some(offset) = text[search_from..].find("10.") {
+        let start = search_from + offset;
+        search_from = start + 3;
+
+        if start > 0 && bytes[start - 1].is_ascii_alphanumeric() {
+            continue;
+        }
+
+        let registrant_len = text[start + 3..]
+            .bytes()
+            .take_while(|b| b.is_ascii_digit() || *b == b'.')
+            .count();
+        if registrant_len < 4 {
+            continue;
+        }
+        let slash = start + 3 + registrant_len;
+        if bytes.get(slash) != Some(&b'/') {
+            continue;
+        }
+
+        let suffix_len = text[slash + 1..]
+            .find(|c: char| c.is_whitespace() || matches!(c, '"' | '<' | '>' | '{' | '}'))
+            .unwrap_or(text.len() - slash - 1);
+        let candidate = text[start..slash + 1 + suffix_len]
+            .trim_end_matches(|c: char| matches!(c, '.' | ',' | ';' | ':' | ')' | ']'));
+        if candidate.len() > slash + 1 - start {
+            return Some(candidate.to_lowercase());
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_doi_forms() {
+        assert_eq!(
+            find_doi("https://doi.org/10.1145/3292500.3330701").as_deref(),
+            Some("10.1145/3292500.3330701")
+        );
+        assert_eq!(
+            find_doi("DOI: 10.1038/NATURE14539.").as_deref(),
+            Some("10.1038/nature14539")
+        );
+        assert_eq!(
+            find_doi("(see doi:10.18653/v1/N19-1423), p. 3").as_deref(),
+            Some("10.18653/v1/n19-1423")
+        );
+    }
+
+    #[test]
+    fn test_find_doi_rejects_non_doi() {
+        assert_eq!(find_doi("version 10.2 released"), None);
+        assert_eq!(find_doi("price 210.5/month"), None);
+        assert_eq!(find_doi("10.1234/"), None);
+        assert_eq!(find_doi(""), None);
+    }
+}
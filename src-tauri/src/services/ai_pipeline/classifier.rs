@@ -2,6 +2,8 @@
 
 use std::cmp::Ordering;
 use std::collections::HashSet;
+
+use serde::Serialize;
 use uuid::Uuid;
 
 use super::{
@@ -9,17 +11,35 @@ use super::{
     TOPIC_TITLE_SIMILARITY_THRESHOLD,
 };
 use crate::db::{
-    contains_creates_cycle, get_node_by_id, get_node_by_title, insert_edge_if_missing,
-    insert_node, insert_node_revision_log, list_nodes_by_type, list_source_nodes,
-    update_node_summary, update_node_title, update_resource_review_status, DbPool, EdgeRelationType,
-    NewEdge, NewNode, NodeRecord, NodeType, ResourceEmbeddingStatus, ResourceProcessingStage,
-    ReviewStatus,
+    contains_creates_cycle, delete_edge, get_node_by_id, get_node_by_title, insert_edge_if_missing,
+    insert_node, insert_node_revision_log, list_edges_to, list_nodes_by_type,
+    list_search_excluded_node_ids, list_source_nodes, update_node_text_checked,
+    update_resource_review_status, DbPool, EdgeRelationType, NewEdge, NewNode, NodeRecord,
+    NodeTextField, NodeType, ResourceEmbeddingStatus, ResourceProcessingStage, ReviewStatus,
 };
+use crate::i18n::MessageCode;
 use crate::services::{
     AiServices, ClassificationMode, ClassifyTopicResponse, ParentTopicCandidate, ProviderConfig,
     TopicCandidate,
 };
 
+/// Topic a resource was filed under by classification
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TopicLink {
+    pub topic_id: i64,
+    pub review_status: ReviewStatus,
+}
+
+/// Result of an on-demand reclassification
+#[derive(Debug, Clone, Serialize)]
+pub struct ReclassifyOutcome {
+    /// Topic the resource is now filed under; None when classification linked nothing
+    pub topic_id: Option<i64>,
+    pub review_status: Option<ReviewStatus>,
+    /// Topics whose auto-generated contains edge to the resource was removed
+    pub removed_topic_ids: Vec<i64>,
+}
+
 pub(crate) async fn classify_and_link_topic(
     db: &DbPool,
     ai: &AiServices,
@@ -29,13 +49,14 @@ pub(crate) async fn classify_and_link_topic(
     classification_mode: ClassificationMode,
     node: &NodeRecord,
     summary: &str,
-) -> Result<(), String> {
-    let similar_resources = search_similar_resources(ai, summary, node.node_id).await?;
+    hint: Option<&str>,
+) -> Result<Option<TopicLink>, String> {
+    let similar_resources = search_similar_resources(db, ai, summary, node.node_id).await?;
     let candidates = build_topic_candidates(db, &similar_resources).await?;
 
     let response = ai
         .agent
-        .classify_topic(provider, model, provider_config, summary, candidates)
+        .classify_topic(provider, model, provider_config, summary, hint, candidates)
         .await?;
 
     match response {
@@ -49,6 +70,14 @@ pub(crate) async fn classify_and_link_topic(
             if topic.node_type != NodeType::Topic {
                 return Err("assign target is not a topic".to_string());
             }
+            if !topic.auto_classify_allowed {
+                tracing::info!(
+                    node_id = node.node_id,
+                    topic_id = topic.node_id,
+                    "Classification skipped protected topic"
+                );
+                return Ok(None);
+            }
             insert_contains_edge(
                 db,
                 topic.node_id,
@@ -57,19 +86,37 @@ pub(crate) async fn classify_and_link_topic(
                 false,
             )
             .await?;
-            apply_review_status(db, node.node_id, classification_mode, confidence_score).await?;
+            Ok(Some(TopicLink {
+                topic_id: topic.node_id,
+                review_status: apply_review_status(
+                    db,
+                    node.node_id,
+                    classification_mode,
+                    confidence_score,
+                )
+                .await?,
+            }))
         }
         ClassifyTopicResponse::CreateNew {
             payload,
             confidence_score,
         } => {
-            let (topic_id, _) = create_topic_node(
+            let (topic_id, created) = create_topic_node(
                 db,
                 ai,
                 &payload.new_topic.title,
                 payload.new_topic.summary.as_deref(),
             )
             .await?;
+            // The "new" topic may resolve to an existing, protected one
+            if !created && !accepts_auto_classify(db, topic_id).await? {
+                tracing::info!(
+                    node_id = node.node_id,
+                    topic_id,
+                    "Classification skipped protected topic"
+                );
+                return Ok(None);
+            }
             if let Some(parent_id) = payload.parent_topic_id {
                 let parent = get_node_by_id(db, parent_id)
                     .await
@@ -77,7 +124,9 @@ pub(crate) async fn classify_and_link_topic(
                 if parent.node_type != NodeType::Topic {
                     return Err("parent topic id is not a topic".to_string());
                 }
-                insert_contains_edge(db, parent_id, topic_id, None, false).await?;
+                if parent.auto_classify_allowed {
+                    insert_contains_edge(db, parent_id, topic_id, None, false).await?;
+                }
             }
             insert_contains_edge(
                 db,
@@ -87,7 +136,16 @@ pub(crate) async fn classify_and_link_topic(
                 false,
             )
             .await?;
-            apply_review_status(db, node.node_id, classification_mode, confidence_score).await?;
+            Ok(Some(TopicLink {
+                topic_id,
+                review_status: apply_review_status(
+                    db,
+                    node.node_id,
+                    classification_mode,
+                    confidence_score,
+                )
+                .await?,
+            }))
         }
         ClassifyTopicResponse::Restructure {
             payload,
@@ -95,6 +153,9 @@ pub(crate) async fn classify_and_link_topic(
         } => {
             if confidence_score >= REVIEW_CONFIDENCE_THRESHOLD {
                 for revision in &payload.topics_to_revise {
+                    if !accepts_auto_classify(db, revision.topic_id).await? {
+                        continue;
+                    }
                     apply_topic_revision(
                         db,
                         ai,
@@ -111,14 +172,16 @@ pub(crate) async fn classify_and_link_topic(
 
             let mut parent_topic_id = None;
             if let Some(new_parent) = payload.new_parent_topic.as_ref() {
-                let (id, _) = create_topic_node(
+                let (id, created) = create_topic_node(
                     db,
                     ai,
                     &new_parent.title,
                     new_parent.summary.as_deref(),
                 )
                 .await?;
-                parent_topic_id = Some(id);
+                if created || accepts_auto_classify(db, id).await? {
+                    parent_topic_id = Some(id);
+                }
             }
 
             if let Some(parent_id) = parent_topic_id {
@@ -135,22 +198,101 @@ pub(crate) async fn classify_and_link_topic(
                     )
                     .await?;
                 }
-                apply_review_status(db, node.node_id, classification_mode, confidence_score).await?;
+                return Ok(Some(TopicLink {
+                    topic_id: parent_id,
+                    review_status: apply_review_status(
+                        db,
+                        node.node_id,
+                        classification_mode,
+                        confidence_score,
+                    )
+                    .await?,
+                }));
             }
+            Ok(None)
         }
     }
+}
 
-    Ok(())
+/// Re-run classification for a single resource, optionally steered by a user hint.
+///
+/// Once the resource is filed under a topic, auto-generated contains edges from other
+/// topics are removed; manual edges are kept. If classification links nothing, the
+/// previous edges stay as they are.
+pub(crate) async fn reclassify_resource(
+    db: &DbPool,
+    ai: &AiServices,
+    provider: &str,
+    model: &str,
+    provider_config: &ProviderConfig,
+    classification_mode: ClassificationMode,
+    node: &NodeRecord,
+    hint: Option<&str>,
+) -> Result<ReclassifyOutcome, String> {
+    let summary = node.summary.as_deref().map(str::trim).unwrap_or("");
+    if summary.is_empty() {
+        return Err("resource has no summary to classify".to_string());
+    }
+
+    let previous_topic_ids: Vec<i64> = list_edges_to(db, node.node_id, EdgeRelationType::Contains)
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|edge| !edge.is_manual)
+        .map(|edge| edge.source_node_id)
+        .collect();
+
+    let link = classify_and_link_topic(
+        db,
+        ai,
+        provider,
+        model,
+        provider_config,
+        classification_mode,
+        node,
+        summary,
+        hint,
+    )
+    .await?;
+
+    let mut removed_topic_ids = Vec::new();
+    if let Some(link) = link {
+        for topic_id in previous_topic_ids {
+            if topic_id == link.topic_id {
+                continue;
+            }
+            delete_edge(db, topic_id, node.node_id, EdgeRelationType::Contains)
+                .await
+                .map_err(|e| e.to_string())?;
+            removed_topic_ids.push(topic_id);
+        }
+    }
+
+    tracing::info!(
+        node_id = node.node_id,
+        topic_id = ?link.map(|link| link.topic_id),
+        removed = removed_topic_ids.len(),
+        "Resource reclassified"
+    );
+    Ok(ReclassifyOutcome {
+        topic_id: link.map(|link| link.topic_id),
+        review_status: link.map(|link| link.review_status),
+        removed_topic_ids,
+    })
 }
 
 async fn search_similar_resources(
+    db: &DbPool,
     ai: &AiServices,
     summary: &str,
     current_node_id: i64,
 ) -> Result<Vec<i64>, String> {
+    let excluded = list_search_excluded_node_ids(db, &[], &[])
+        .await
+        .map_err(|e| format!("classify exclusion lookup failed: {e}"))?;
     let response = ai
         .search
-        .search_hybrid(summary, "summary", None, CLASSIFY_TOP_K as u64)
+        .search_hybrid(summary, "summary", None, &excluded, CLASSIFY_TOP_K as u64)
         .await
         .map_err(|e| format!("classify search failed: {e}"))?;
 
@@ -184,7 +326,7 @@ async fn build_topic_candidates(
             .map_err(|e| e.to_string())?;
 
         for parent in parents {
-            if parent.node_type != NodeType::Topic {
+            if parent.node_type != NodeType::Topic || !parent.auto_classify_allowed {
                 continue;
             }
             if !seen.insert(parent.node_id) {
@@ -196,7 +338,7 @@ async fn build_topic_candidates(
                 .map_err(|e| e.to_string())?;
             let parent_topics = parent_candidates
                 .into_iter()
-                .filter(|node| node.node_type == NodeType::Topic)
+                .filter(|node| node.node_type == NodeType::Topic && node.auto_classify_allowed)
                 .map(|node| ParentTopicCandidate {
                     node_id: node.node_id,
                     title: node.title,
@@ -216,6 +358,14 @@ async fn build_topic_candidates(
     Ok(candidates)
 }
 
+/// Protected topics (auto_classify_allowed = 0) never receive anything from classification
+async fn accepts_auto_classify(db: &DbPool, topic_id: i64) -> Result<bool, String> {
+    let topic = get_node_by_id(db, topic_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(topic.node_type == NodeType::Topic && topic.auto_classify_allowed)
+}
+
 async fn insert_contains_edge(
     db: &DbPool,
     source_node_id: i64,
@@ -251,7 +401,7 @@ async fn apply_review_status(
     node_id: i64,
     mode: ClassificationMode,
     confidence_score: f64,
-) -> Result<(), String> {
+) -> Result<ReviewStatus, String> {
     let reviewed = matches!(mode, ClassificationMode::Aggressive)
         && confidence_score >= REVIEW_CONFIDENCE_THRESHOLD;
     let status = if reviewed {
@@ -261,7 +411,8 @@ async fn apply_review_status(
     };
     update_resource_review_status(db, node_id, status)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+    Ok(status)
 }
 
 async fn create_topic_node(
@@ -478,8 +629,16 @@ async fn apply_topic_revision(
         return Ok(());
     }
 
+    // Written against the version read above so a rename by the user is not overwritten
+    let mut version = topic.version;
     if let Some(title) = new_title.map(str::trim).filter(|v| !v.is_empty()) {
         if title != topic.title {
+            let Some(next_version) =
+                write_topic_text(db, topic_id, NodeTextField::Title, Some(title), version).await?
+            else {
+                return Ok(());
+            };
+            version = next_version;
             insert_node_revision_log(
                 db,
                 crate::db::NewNodeRevisionLog {
@@ -495,9 +654,6 @@ async fn apply_topic_revision(
             )
             .await
             .map_err(|e| e.to_string())?;
-            update_node_title(db, topic_id, title)
-                .await
-                .map_err(|e| e.to_string())?;
             if let Err(err) = ai.embedding.upsert_title_embedding(topic_id, title).await {
                 tracing::warn!(
                     topic_id,
@@ -512,13 +668,20 @@ async fn apply_topic_revision(
         let current = topic.summary.as_deref().unwrap_or("");
         let next = summary;
         if current != next {
+            let value = if next.is_empty() { None } else { Some(next) };
+            if write_topic_text(db, topic_id, NodeTextField::Summary, value, version)
+                .await?
+                .is_none()
+            {
+                return Ok(());
+            }
             insert_node_revision_log(
                 db,
                 crate::db::NewNodeRevisionLog {
                     node_id: topic_id,
                     field_name: "summary",
                     old_value: if current.is_empty() { None } else { Some(current) },
-                    new_value: if next.is_empty() { None } else { Some(next) },
+                    new_value: value,
                     reason: Some("ai_restructure"),
                     provider: Some(provider),
                     model: Some(model),
@@ -527,11 +690,30 @@ async fn apply_topic_revision(
             )
             .await
             .map_err(|e| e.to_string())?;
-            update_node_summary(db, topic_id, if next.is_empty() { None } else { Some(next) })
-                .await
-                .map_err(|e| e.to_string())?;
         }
     }
 
     Ok(())
 }
+
+/// Returns the new version, or `None` when the topic changed since it was read
+async fn write_topic_text(
+    db: &DbPool,
+    topic_id: i64,
+    field: NodeTextField,
+    value: Option<&str>,
+    version: i64,
+) -> Result<Option<i64>, String> {
+    match update_node_text_checked(db, topic_id, field, value, Some(version)).await {
+        Ok(version) => Ok(Some(version)),
+        Err(err) if err.code() == MessageCode::NodeEditConflict => {
+            tracing::info!(
+                topic_id,
+                ?field,
+                "Topic edited during restructure, skipping revision"
+            );
+            Ok(None)
+        }
+        Err(err) => Err(err.to_string()),
+    }
+}
@@ -3,19 +3,30 @@
 //! Handles resource processing including summarization, embedding, and topic classification.
 //!
 //! Split into submodules:
-//! - `queue`: Pipeline job queue management
+//! - `queue`: Pipeline job queue management (persisted in `pipeline_jobs`)
 //! - `processor`: Resource processing logic
+//! - `retry`: Exponential backoff for transient failures
 //! - `classifier`: Topic classification logic
+//! - `citation`: Citation metadata extraction for academic PDFs
+//! - `tag_suggestions`: AI tag suggestions awaiting user confirmation
+//! - `title`: Descriptive titles for untitled text captures
 
+mod citation;
 mod classifier;
 mod processor;
 mod queue;
+mod retry;
+mod tag_suggestions;
+mod title;
 
+pub use classifier::ReclassifyOutcome;
+pub use crate::db::JobPriority;
 pub use queue::AiPipeline;
-pub(crate) use processor::get_processing_config;
+pub(crate) use citation::extract_and_store_citation;
+pub(crate) use classifier::reclassify_resource;
+pub(crate) use processor::{get_processing_config, reembed_resource};
 
 // Constants
-pub(crate) const AI_QUEUE_BUFFER: usize = 32;
 pub(crate) const SUMMARY_MAX_LENGTH: i32 = 100;
 pub(crate) const SUMMARY_MIN_LENGTH: i32 = 10;
 pub(crate) const CLASSIFY_TOP_K: i32 = 10;
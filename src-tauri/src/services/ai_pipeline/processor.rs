@@ -6,18 +6,35 @@ use tokio::sync::Mutex;
 
 use serde_json::json;
 
+use super::citation::extract_and_store_citation;
 use super::classifier::classify_and_link_topic;
+use super::retry::RetryPolicy;
+use super::tag_suggestions::suggest_and_store_tags;
+use super::title::{is_auto_titled, retitle_untitled_resource};
 use super::{SUMMARY_MAX_LENGTH, SUMMARY_MIN_LENGTH};
 use crate::db::{
-    delete_context_chunks_by_type, get_node_by_id, insert_context_chunks,
-    update_node_summary, update_resource_processing_stage, update_resource_sync_status,
-    DbPool, EmbedChunkResult, EmbeddingType, NodeRecord, NodeType, ResourceEmbeddingStatus,
-    ResourceProcessingStage, ResourceSubtype,
+    delete_context_chunks_by_type, get_node_by_id, insert_context_chunks, list_edges_to,
+    mark_resource_retry_pending, update_node_content, update_node_text_checked,
+    update_resource_processing_stage, update_resource_sync_status, DbPool, EdgeRelationType,
+    EmbedChunkResult, EmbeddingType, NodeRecord, NodeTextField, NodeType, ResourceEmbeddingStatus,
+    ResourceProcessingStage, ResourceSubtype, ReviewStatus,
 };
+use crate::i18n::MessageCode;
 use crate::services::{
-    parser::parse_pdf_pages_with_fallback, AiServices, AIConfigService, ClassificationMode,
+    parser::{parse_notebook_cells, parse_pdf_pages_with_fallback},
+    provider_requires_api_key, AIConfigService, AiServices, ClassificationMode, LlmError,
     ProviderConfig, TextSegment,
 };
+use crate::utils::resolve_stored_path;
+
+/// Result of a processed resource job
+#[derive(Debug, Default)]
+pub(crate) struct ProcessOutcome {
+    /// Classification linked a topic but left the resource unreviewed
+    pub needs_review: bool,
+    /// Number of pending tag suggestions stored for the user
+    pub suggested_tags: usize,
+}
 
 pub(crate) async fn process_resource_job(
     db: &DbPool,
@@ -25,11 +42,11 @@ pub(crate) async fn process_resource_job(
     ai_config: &Arc<Mutex<AIConfigService>>,
     app_data_dir: &Path,
     node_id: i64,
-) -> Result<(), String> {
+) -> Result<ProcessOutcome, LlmError> {
     // 1. Get node
-    let node = get_node_by_id(db, node_id).await.map_err(|e| e.to_string())?;
+    let mut node = get_node_by_id(db, node_id).await.map_err(|e| e.to_string())?;
     if node.node_type != NodeType::Resource || node.is_deleted {
-        return Ok(());
+        return Ok(ProcessOutcome::default());
     }
 
     tracing::info!(node_id, "AiPipeline processing resource");
@@ -40,27 +57,32 @@ pub(crate) async fn process_resource_job(
         ResourceSubtype::Pdf => "pdf",
         ResourceSubtype::Url => "url",
         ResourceSubtype::Epub => "epub",
+        ResourceSubtype::Office => "office",
+        ResourceSubtype::Audio => "audio",
+        ResourceSubtype::Notebook => "notebook",
+        ResourceSubtype::Email => "email",
+        ResourceSubtype::Archive => "archive",
         ResourceSubtype::Other => "other",
     });
 
-    // Non-Text types pass file_path for summary
+    // Non-Text types pass file_path for summary (Office files, audio, notebooks, emails and
+    // archive listings are summarized from extracted text / transcripts)
     let file_path_for_summary = match node.resource_subtype {
-        Some(ResourceSubtype::Text) | None => None,
+        Some(
+            ResourceSubtype::Text
+            | ResourceSubtype::Office
+            | ResourceSubtype::Audio
+            | ResourceSubtype::Notebook
+            | ResourceSubtype::Email
+            | ResourceSubtype::Archive,
+        )
+        | None => None,
         _ => node.file_path.as_deref(),
     }
     .map(|path| resolve_resource_path(app_data_dir, path));
 
-    let image_path_for_embedding = match node.resource_subtype {
-        Some(ResourceSubtype::Image) => node.file_path.as_deref(),
-        _ => None,
-    }
-    .map(|path| resolve_resource_path(app_data_dir, path));
-
-    let pdf_path_for_embedding = match node.resource_subtype {
-        Some(ResourceSubtype::Pdf) => node.file_path.as_deref(),
-        _ => None,
-    }
-    .map(|path| resolve_resource_path(app_data_dir, path));
+    let (image_path_for_embedding, segment_path_for_embedding) =
+        embedding_source_paths(&node, app_data_dir);
 
     // 2. Ensure content is not empty (unless file fallback available)
     let content = node
@@ -69,13 +91,28 @@ pub(crate) async fn process_resource_job(
         .unwrap_or("")
         .trim()
         .to_string();
+
+    // 2a. Audio without a local transcript: transcribe through the processing model
+    let content = match (&node.resource_subtype, node.file_path.as_deref()) {
+        (Some(ResourceSubtype::Audio), Some(path)) if content.is_empty() => {
+            let audio_path = resolve_resource_path(app_data_dir, path);
+            match transcribe_with_model(db, ai, ai_config, node_id, &audio_path).await {
+                Ok(transcript) => transcript,
+                Err(err) => {
+                    fail_resource_job(db, node_id, &node, &err).await?;
+                    return Err(err);
+                }
+            }
+        }
+        _ => content,
+    };
     if content.is_empty() && file_path_for_summary.is_none() {
         tracing::warn!(node_id, "Resource content empty and no file fallback");
         mark_resource_error(db, node_id, &node, "resource content is empty").await?;
-        return Ok(());
+        return Ok(ProcessOutcome::default());
     }
 
-    let processing_result: Result<(String, String, ClassificationMode, ProviderConfig, String), String> = async {
+    let processing_result: Result<(String, String, ClassificationMode, ProviderConfig, String), LlmError> = async {
         // 3. Update status to Pending
         update_resource_sync_status(
             db,
@@ -92,6 +129,7 @@ pub(crate) async fn process_resource_job(
             get_processing_config(ai_config).await?;
 
         // 5. Generate summary
+        let summary_language = ai_config.lock().await.load()?.summary_language;
         let summary = ai
             .agent
             .summarize(
@@ -104,18 +142,10 @@ pub(crate) async fn process_resource_job(
                 SUMMARY_MAX_LENGTH,
                 file_path_for_summary.as_deref(),
                 resource_subtype_str,
+                summary_language,
             )
             .await?;
-        let summary = summary.trim().to_string();
-        if summary.is_empty() {
-            update_node_summary(db, node_id, None)
-                .await
-                .map_err(|e| e.to_string())?;
-        } else {
-            update_node_summary(db, node_id, Some(&summary))
-                .await
-                .map_err(|e| e.to_string())?;
-        }
+        let summary = store_generated_summary(db, &node, summary.trim()).await?;
 
         // 6. Update processing stage to Embedding
         update_resource_processing_stage(db, node_id, ResourceProcessingStage::Embedding, node.file_hash.as_deref())
@@ -132,7 +162,7 @@ pub(crate) async fn process_resource_job(
             summary.as_str(),
             false,
             None,
-            pdf_path_for_embedding.as_deref(),
+            segment_path_for_embedding.as_deref(),
         )
         .await?;
         sync_embeddings_for_type(
@@ -144,7 +174,7 @@ pub(crate) async fn process_resource_job(
             content.as_str(),
             true,
             image_path_for_embedding.as_deref(),
-            pdf_path_for_embedding.as_deref(),
+            segment_path_for_embedding.as_deref(),
         )
         .await?;
 
@@ -169,14 +199,76 @@ pub(crate) async fn process_resource_job(
     let (provider, model, classification_mode, provider_config, summary) = match processing_result {
         Ok(data) => data,
         Err(err) => {
-            mark_resource_error(db, node_id, &node, &err).await?;
+            fail_resource_job(db, node_id, &node, &err).await?;
             return Err(err);
         }
     };
 
-    // 9. Classify
+    // 8a. Replace the truncated name of untitled snippets (best effort)
+    if is_auto_titled(&node) {
+        match retitle_untitled_resource(
+            db,
+            ai,
+            &provider,
+            &model,
+            &provider_config,
+            &node,
+            &content,
+            &summary,
+        )
+        .await
+        {
+            Ok(Some(title)) => node.title = title,
+            Ok(None) => {}
+            Err(err) => tracing::warn!(node_id, error = %err, "AiPipeline retitle failed"),
+        }
+    }
+
+    // 9. Extract citation metadata for PDFs (best effort)
+    if node.resource_subtype == Some(ResourceSubtype::Pdf) {
+        if let Err(err) = extract_and_store_citation(
+            db,
+            ai,
+            &provider,
+            &model,
+            &provider_config,
+            node_id,
+            &content,
+            segment_path_for_embedding.as_deref(),
+        )
+        .await
+        {
+            tracing::warn!(node_id, error = %err, "AiPipeline citation extraction failed");
+        }
+    }
+
+    // 10. Suggest tags for the user to accept or reject (best effort)
+    let mut outcome = ProcessOutcome::default();
     if !summary.is_empty() {
-        if let Err(err) = classify_and_link_topic(
+        match suggest_and_store_tags(
+            db,
+            ai,
+            &provider,
+            &model,
+            &provider_config,
+            node_id,
+            &summary,
+        )
+        .await
+        {
+            Ok(count) => outcome.suggested_tags = count,
+            Err(err) => tracing::warn!(node_id, error = %err, "AiPipeline tag suggestion failed"),
+        }
+    }
+
+    // 11. Classify (skip resources already filed under a topic by hand, e.g. synced items)
+    let manually_filed = list_edges_to(db, node_id, EdgeRelationType::Contains)
+        .await
+        .map_err(|e| e.to_string())?
+        .iter()
+        .any(|edge| edge.is_manual);
+    if !summary.is_empty() && !manually_filed {
+        match classify_and_link_topic(
             db,
             ai,
             &provider,
@@ -185,18 +277,84 @@ pub(crate) async fn process_resource_job(
             classification_mode,
             &node,
             &summary,
+            None,
         )
         .await
         {
-            tracing::warn!(
-                node_id,
-                error = %err,
-                "AiPipeline topic classify failed"
-            );
+            Ok(link) => {
+                outcome.needs_review =
+                    link.is_some_and(|link| link.review_status == ReviewStatus::Unreviewed)
+            }
+            Err(err) => {
+                tracing::warn!(
+                    node_id,
+                    error = %err,
+                    "AiPipeline topic classify failed"
+                );
+            }
         }
     }
 
-    Ok(())
+    Ok(outcome)
+}
+
+/// Image file for the image vector, and the original file for page / cell-wise embedding
+fn embedding_source_paths(
+    node: &NodeRecord,
+    app_data_dir: &Path,
+) -> (Option<String>, Option<String>) {
+    let image_path = match node.resource_subtype {
+        Some(ResourceSubtype::Image) => node.file_path.as_deref(),
+        _ => None,
+    }
+    .map(|path| resolve_resource_path(app_data_dir, path));
+
+    // PDFs are embedded page-wise and notebooks cell-wise from the original file
+    let segment_path = match node.resource_subtype {
+        Some(ResourceSubtype::Pdf | ResourceSubtype::Notebook) => node.file_path.as_deref(),
+        _ => None,
+    }
+    .map(|path| resolve_resource_path(app_data_dir, path));
+
+    (image_path, segment_path)
+}
+
+/// Rebuild summary and content embeddings of an already processed resource from its stored
+/// summary and content, without calling the LLM (used when switching embedding models)
+pub(crate) async fn reembed_resource(
+    db: &DbPool,
+    ai: &AiServices,
+    app_data_dir: &Path,
+    node: &NodeRecord,
+) -> Result<(), String> {
+    let (image_path, segment_path) = embedding_source_paths(node, app_data_dir);
+    let summary = node.summary.as_deref().unwrap_or("").trim();
+    let content = node.file_content.as_deref().unwrap_or("").trim();
+
+    sync_embeddings_for_type(
+        db,
+        ai,
+        node.node_id,
+        node.resource_subtype,
+        EmbeddingType::Summary,
+        summary,
+        false,
+        None,
+        segment_path.as_deref(),
+    )
+    .await?;
+    sync_embeddings_for_type(
+        db,
+        ai,
+        node.node_id,
+        node.resource_subtype,
+        EmbeddingType::Content,
+        content,
+        true,
+        image_path.as_deref(),
+        segment_path.as_deref(),
+    )
+    .await
 }
 
 pub(crate) async fn sync_embeddings_for_type(
@@ -208,59 +366,38 @@ pub(crate) async fn sync_embeddings_for_type(
     text: &str,
     chunk: bool,
     image_path: Option<&str>,
-    pdf_path: Option<&str>,
+    segment_path: Option<&str>,
 ) -> Result<(), String> {
     delete_context_chunks_by_type(db, node_id, embedding_type)
         .await
         .map_err(|e| e.to_string())?;
 
+    // Text vectors are diffed chunk by chunk below; the image vector is always rebuilt
     ai.embedding
-        .delete_by_node(node_id, Some(embedding_type_label(embedding_type)), None)
+        .delete_by_node(
+            node_id,
+            Some(embedding_type_label(embedding_type)),
+            Some("image"),
+        )
         .await?;
 
-    let mut chunks: Vec<EmbedChunkResult> = Vec::new();
-    let mut used_segment_embedding = false;
-
-    if embedding_type == EmbeddingType::Content
-        && matches!(resource_subtype, Some(ResourceSubtype::Pdf))
-    {
-        if let Some(pdf_path) = pdf_path {
-            match parse_pdf_pages_with_fallback(pdf_path, None) {
-                Ok(pages) => {
-                    let segments: Vec<TextSegment> = pages
-                        .into_iter()
-                        .map(|page| TextSegment {
-                            text: page.text,
-                            meta: Some(json!({ "page": page.page_number })),
-                        })
-                        .collect();
-                    if !segments.is_empty() {
-                        let response = ai
-                            .embedding
-                            .embed_text_segments(node_id, embedding_type, &segments, chunk)
-                            .await?;
-                        chunks.extend(response.chunks);
-                        used_segment_embedding = true;
-                    }
-                }
-                Err(err) => {
-                    tracing::warn!(
-                        node_id,
-                        error = %err,
-                        "PDF page parse failed, fallback to stored content"
-                    );
-                }
-            }
-        }
-    }
-
-    if !used_segment_embedding && !text.trim().is_empty() {
-        let response = ai
-            .embedding
-            .embed_text(node_id, embedding_type, text, chunk)
-            .await?;
-        chunks.extend(response.chunks);
-    }
+    let source_segments = if embedding_type == EmbeddingType::Content {
+        build_content_segments(node_id, resource_subtype, segment_path)
+            .filter(|segments| !segments.is_empty())
+    } else {
+        None
+    };
+    let segments = source_segments.unwrap_or_else(|| {
+        vec![TextSegment {
+            text: text.to_string(),
+            meta: None,
+        }]
+    });
+    let mut chunks: Vec<EmbedChunkResult> = ai
+        .embedding
+        .sync_text_segments(node_id, embedding_type, &segments, chunk)
+        .await?
+        .chunks;
 
     if embedding_type == EmbeddingType::Content {
         if let Some(image_path) = image_path {
@@ -284,6 +421,65 @@ pub(crate) async fn sync_embeddings_for_type(
     Ok(())
 }
 
+/// Source segments for content embedding: PDF pages or notebook cells, with their position
+/// as chunk metadata. `None` falls back to embedding the stored content.
+fn build_content_segments(
+    node_id: i64,
+    resource_subtype: Option<ResourceSubtype>,
+    segment_path: Option<&str>,
+) -> Option<Vec<TextSegment>> {
+    let path = segment_path?;
+    let segments = match resource_subtype? {
+        ResourceSubtype::Pdf => parse_pdf_pages_with_fallback(path, None).map(|pages| {
+            pages
+                .into_iter()
+                .map(|page| TextSegment {
+                    text: page.text,
+                    meta: Some(json!({ "page": page.page_number })),
+                })
+                .collect()
+        }),
+        ResourceSubtype::Notebook => parse_notebook_cells(path).map(|cells| {
+            cells
+                .into_iter()
+                .map(|cell| TextSegment {
+                    text: cell.text,
+                    meta: Some(json!({ "cell": cell.index, "cell_type": cell.kind.as_str() })),
+                })
+                .collect()
+        }),
+        _ => return None,
+    };
+    match segments {
+        Ok(segments) => Some(segments),
+        Err(err) => {
+            tracing::warn!(
+                node_id,
+                error = %err,
+                "Source segment parse failed, fallback to stored content"
+            );
+            None
+        }
+    }
+}
+
+/// Marks a failed job: transient errors with attempts left stay pending for a retry
+async fn fail_resource_job(
+    db: &DbPool,
+    node_id: i64,
+    node: &NodeRecord,
+    error: &LlmError,
+) -> Result<(), String> {
+    let message = error.to_string();
+    if RetryPolicy::DEFAULT.should_retry(error, node.processing_attempts + 1) {
+        mark_resource_retry_pending(db, node_id, &message)
+            .await
+            .map_err(|e| e.to_string())
+    } else {
+        mark_resource_error(db, node_id, node, &message).await
+    }
+}
+
 pub(crate) async fn mark_resource_error(
     db: &DbPool,
     node_id: i64,
@@ -304,6 +500,74 @@ pub(crate) async fn mark_resource_error(
     .map_err(|e| e.to_string())
 }
 
+/// Write a generated summary unless the user edited it while the job was running.
+///
+/// Re-reads the node first and writes only against the version it saw, so an edit saved
+/// during generation is kept. Returns the summary now stored on the node.
+async fn store_generated_summary(
+    db: &DbPool,
+    node: &NodeRecord,
+    summary: &str,
+) -> Result<String, String> {
+    let current = get_node_by_id(db, node.node_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    if current.summary != node.summary {
+        tracing::info!(
+            node_id = node.node_id,
+            "Summary edited during processing, keeping it"
+        );
+        return Ok(current.summary.unwrap_or_default());
+    }
+
+    let value = Some(summary).filter(|summary| !summary.is_empty());
+    match update_node_text_checked(
+        db,
+        node.node_id,
+        NodeTextField::Summary,
+        value,
+        Some(current.version),
+    )
+    .await
+    {
+        Ok(_) => Ok(summary.to_string()),
+        Err(err) if err.code() == MessageCode::NodeEditConflict => {
+            let current = get_node_by_id(db, node.node_id)
+                .await
+                .map_err(|e| e.to_string())?;
+            tracing::info!(
+                node_id = node.node_id,
+                "Node changed while storing summary, keeping it"
+            );
+            Ok(current.summary.unwrap_or_default())
+        }
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+/// Transcribe an audio resource with the processing model and store it as file_content
+async fn transcribe_with_model(
+    db: &DbPool,
+    ai: &AiServices,
+    ai_config: &Arc<Mutex<AIConfigService>>,
+    node_id: i64,
+    audio_path: &str,
+) -> Result<String, LlmError> {
+    let (provider, model, _, provider_config) = get_processing_config(ai_config).await?;
+    let transcript = ai
+        .agent
+        .transcribe_audio(&provider, &model, &provider_config, audio_path)
+        .await?;
+    if transcript.is_empty() {
+        return Err("no speech recognized in audio".into());
+    }
+    update_node_content(db, node_id, Some(&transcript), None)
+        .await
+        .map_err(|e| e.to_string())?;
+    tracing::info!(node_id, chars = transcript.chars().count(), "Audio transcribed by model");
+    Ok(transcript)
+}
+
 pub(crate) async fn get_processing_config(
     ai_config: &Arc<Mutex<AIConfigService>>,
 ) -> Result<(String, String, ClassificationMode, ProviderConfig), String> {
@@ -323,7 +587,7 @@ pub(crate) async fn get_processing_config(
         .get(&provider)
         .ok_or_else(|| format!("provider {provider} not configured"))?
         .clone();
-    if provider_config.api_key.is_empty() {
+    if provider_config.api_key.is_empty() && provider_requires_api_key(&provider) {
         return Err(format!("provider {provider} missing api key"));
     }
     if !provider_config.enabled {
@@ -334,11 +598,9 @@ pub(crate) async fn get_processing_config(
 }
 
 pub(crate) fn resolve_resource_path(app_data_dir: &Path, file_path: &str) -> String {
-    let path = Path::new(file_path);
-    if path.is_absolute() {
-        return path.to_string_lossy().to_string();
-    }
-    app_data_dir.join(path).to_string_lossy().to_string()
+    resolve_stored_path(app_data_dir, file_path)
+        .to_string_lossy()
+        .to_string()
 }
 
 fn build_image_preview(text: &str) -> String {
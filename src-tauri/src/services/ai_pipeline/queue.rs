@@ -1,26 +1,113 @@
 //! Pipeline job queue management
+//!
+//! Jobs are kept in a priority queue: higher [`JobPriority`] first, FIFO within the same
+//! priority. Re-enqueueing a waiting resource with a higher priority moves it up; the old
+//! heap entry is left in place and skipped when popped.
+//!
+//! Queued and processing jobs are mirrored to the `pipeline_jobs` table so the queue
+//! survives the app being killed; [`AiPipeline::restore_jobs`] reloads it on startup. The
+//! table is only written while holding the queue lock, so a late upsert can never revive the
+//! row of a job that has already finished.
+//!
+//! While paused, the worker finishes the job in hand and then waits; enqueueing still
+//! works, and on resume jobs are picked up in the same order.
+//!
+//! Transient failures are re-enqueued after a backoff delay (see [`RetryPolicy`]); the
+//! user is only notified once the attempts are used up. A job waiting for its retry keeps
+//! its row with `retry_after`, so a restart during the backoff resumes the wait.
 
-use std::collections::HashSet;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use serde::Serialize;
-use tauri::{AppHandle, Emitter};
-use tokio::sync::{mpsc, Mutex};
+use chrono::{NaiveDateTime, Utc};
+
+use tauri::AppHandle;
+use tokio::sync::{Mutex, Notify};
 
 use super::processor::process_resource_job;
-use super::AI_QUEUE_BUFFER;
-use crate::db::{list_resources_for_requeue, DbPool};
-use crate::services::{AiServices, AiServicesHandle, AIConfigService};
+use super::retry::RetryPolicy;
+use crate::db::{
+    delete_pipeline_job, get_node_by_id, increment_processing_attempts, list_pipeline_jobs,
+    list_resources_for_requeue, mark_pipeline_job_processing, mark_pipeline_job_retry,
+    requeue_interrupted_pipeline_jobs, reset_processing_attempts, upsert_pipeline_job, DbPool,
+    JobPriority, NewNotification, NotificationKind,
+};
+use crate::services::{
+    emit_event, inc_counter, observe_duration, push_notification, AIConfigService, AiServices,
+    AiServicesHandle, EmbeddingStatus, EmbeddingStatusPayload, MetricCounter, MetricHistogram,
+    TagSuggestionsPayload,
+};
 
 #[derive(Debug)]
 pub(crate) struct AiPipelineJob {
     pub node_id: i64,
+    pub priority: JobPriority,
+    /// Enqueue order, keeps jobs of the same priority FIFO
+    seq: u64,
+}
+
+impl PartialEq for AiPipelineJob {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for AiPipelineJob {}
+
+impl PartialOrd for AiPipelineJob {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for AiPipelineJob {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+#[derive(Default)]
+struct QueueState {
+    heap: BinaryHeap<AiPipelineJob>,
+    /// Queued resources with their current priority; `None` while being processed
+    inflight: HashMap<i64, Option<JobPriority>>,
+    next_seq: u64,
+    stopped: bool,
+    paused: bool,
+}
+
+impl QueueState {
+    fn push(&mut self, node_id: i64, priority: JobPriority) {
+        self.inflight.insert(node_id, Some(priority));
+        self.heap.push(AiPipelineJob {
+            node_id,
+            priority,
+            seq: self.next_seq,
+        });
+        self.next_seq += 1;
+    }
+
+    /// Pops the next live job and marks it as processing
+    fn pop(&mut self) -> Option<AiPipelineJob> {
+        while let Some(job) = self.heap.pop() {
+            if self.inflight.get(&job.node_id) == Some(&Some(job.priority)) {
+                self.inflight.insert(job.node_id, None);
+                return Some(job);
+            }
+        }
+        None
+    }
 }
 
 #[derive(Clone)]
 pub struct AiPipeline {
-    sender: mpsc::Sender<AiPipelineJob>,
-    inflight: Arc<Mutex<HashSet<i64>>>,
+    state: Arc<Mutex<QueueState>>,
+    notify: Arc<Notify>,
+    db: DbPool,
 }
 
 impl AiPipeline {
@@ -31,27 +118,31 @@ impl AiPipeline {
         app_data_dir: std::path::PathBuf,
         app_handle: AppHandle,
     ) -> Self {
-        let (sender, receiver) = mpsc::channel(AI_QUEUE_BUFFER);
-        let inflight = Arc::new(Mutex::new(HashSet::new()));
-        let inflight_worker = inflight.clone();
+        let pipeline = Self {
+            state: Arc::new(Mutex::new(QueueState::default())),
+            notify: Arc::new(Notify::new()),
+            db: db.clone(),
+        };
+        let worker = pipeline.clone();
         let app_handle = app_handle.clone();
 
         tauri::async_runtime::spawn(async move {
-            let ai_services = match ai.wait_ready().await {
+            let initial_services = match ai.wait_ready().await {
                 Ok(services) => services,
                 Err(err) => {
                     tracing::error!(
                         error = %err,
                         "AI services init failed; pipeline stopped"
                     );
+                    worker.state.lock().await.stopped = true;
                     return;
                 }
             };
             run_pipeline(
-                receiver,
-                inflight_worker,
+                worker,
                 db,
-                ai_services,
+                ai,
+                initial_services,
                 ai_config,
                 app_data_dir,
                 app_handle,
@@ -59,26 +150,107 @@ impl AiPipeline {
             .await;
         });
 
-        Self { sender, inflight }
+        pipeline
+    }
+
+    /// Adds a resource to the queue; a waiting resource is moved up if `priority` is higher
+    pub async fn enqueue_resource(
+        &self,
+        node_id: i64,
+        priority: JobPriority,
+    ) -> Result<(), String> {
+        {
+            let mut state = self.state.lock().await;
+            if state.stopped {
+                return Err("AI pipeline stopped".to_string());
+            }
+            match state.inflight.get(&node_id).copied() {
+                Some(Some(queued)) if queued >= priority => {
+                    tracing::debug!(node_id, "AiPipeline job already queued");
+                    return Ok(());
+                }
+                Some(None) => {
+                    tracing::debug!(node_id, "AiPipeline job already processing");
+                    return Ok(());
+                }
+                _ => state.push(node_id, priority),
+            }
+            self.persist_job(node_id, priority).await;
+        }
+        self.notify.notify_one();
+
+        tracing::debug!(node_id, ?priority, "AiPipeline job enqueued");
+        Ok(())
     }
 
-    pub async fn enqueue_resource(&self, node_id: i64) -> Result<(), String> {
+    /// Moves a waiting resource to the interactive lane; returns false if it is not waiting
+    pub async fn prioritize(&self, node_id: i64) -> bool {
         {
-            let mut inflight = self.inflight.lock().await;
-            if inflight.contains(&node_id) {
-                tracing::debug!(node_id, "AiPipeline job already inflight");
-                return Ok(());
+            let mut state = self.state.lock().await;
+            match state.inflight.get(&node_id).copied() {
+                Some(Some(JobPriority::Interactive)) => return true,
+                Some(Some(_)) => state.push(node_id, JobPriority::Interactive),
+                _ => return false,
             }
-            inflight.insert(node_id);
+            self.persist_job(node_id, JobPriority::Interactive).await;
         }
+        self.notify.notify_one();
+        tracing::debug!(node_id, "AiPipeline job prioritized");
+        true
+    }
 
-        self.sender
-            .send(AiPipelineJob { node_id })
-            .await
-            .map_err(|_| "AI pipeline stopped".to_string())?;
+    /// 队列中没有待处理或处理中的资源
+    pub async fn is_idle(&self) -> bool {
+        self.state.lock().await.inflight.is_empty()
+    }
 
-        tracing::debug!(node_id, "AiPipeline job enqueued");
-        Ok(())
+    /// Stops picking up new jobs; the job being processed is finished
+    pub async fn pause(&self) {
+        self.state.lock().await.paused = true;
+        tracing::info!("AiPipeline paused");
+    }
+
+    pub async fn resume(&self) {
+        self.state.lock().await.paused = false;
+        self.notify.notify_one();
+        tracing::info!("AiPipeline resumed");
+    }
+
+    pub async fn is_paused(&self) -> bool {
+        self.state.lock().await.paused
+    }
+
+    /// AI services failed to start; nothing will be processed
+    pub async fn is_stopped(&self) -> bool {
+        self.state.lock().await.stopped
+    }
+
+    /// Reloads jobs persisted by a previous run; interrupted jobs are queued again and jobs
+    /// still waiting for a retry are enqueued once the rest of their backoff has passed
+    pub async fn restore_jobs(&self) -> Result<usize, String> {
+        requeue_interrupted_pipeline_jobs(&self.db)
+            .await
+            .map_err(|e| e.to_string())?;
+        let jobs = list_pipeline_jobs(&self.db).await.map_err(|e| e.to_string())?;
+        let mut restored = 0;
+        {
+            let mut state = self.state.lock().await;
+            for job in jobs {
+                if state.inflight.contains_key(&job.node_id) {
+                    continue;
+                }
+                if let Some(delay) = job.retry_after.as_deref().and_then(remaining_delay) {
+                    self.schedule_retry(job.node_id, job.priority, delay);
+                } else {
+                    state.push(job.node_id, job.priority);
+                }
+                restored += 1;
+            }
+        }
+        if restored > 0 {
+            self.notify.notify_one();
+        }
+        Ok(restored)
     }
 
     pub async fn enqueue_pending_resources(&self, db: &DbPool) -> Result<usize, String> {
@@ -87,61 +259,218 @@ impl AiPipeline {
             .map_err(|e| e.to_string())?;
         let mut enqueued = 0;
         for node_id in node_ids {
-            self.enqueue_resource(node_id).await?;
+            self.enqueue_resource(node_id, JobPriority::Bulk).await?;
             enqueued += 1;
         }
         Ok(enqueued)
     }
+
+    /// Waits for the next job; blocks while paused
+    async fn next_job(&self) -> AiPipelineJob {
+        loop {
+            {
+                let mut state = self.state.lock().await;
+                let job = if state.paused { None } else { state.pop() };
+                if let Some(job) = job {
+                    if let Err(err) = mark_pipeline_job_processing(&self.db, job.node_id).await {
+                        tracing::warn!(
+                            node_id = job.node_id,
+                            error = %err,
+                            "Failed to persist pipeline job state"
+                        );
+                    }
+                    return job;
+                }
+            }
+            self.notify.notified().await;
+        }
+    }
+
+    /// Enqueues the resource again once `delay` has passed
+    fn schedule_retry(&self, node_id: i64, priority: JobPriority, delay: Duration) {
+        let pipeline = self.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(delay).await;
+            if let Err(err) = pipeline.enqueue_resource(node_id, priority).await {
+                tracing::warn!(node_id, error = %err, "Failed to enqueue pipeline retry");
+            }
+        });
+    }
+
+    /// Marks a job as finished; with `retry_delay` its row is kept until the retry is due.
+    /// Returns true if nothing else is queued or the queue is paused
+    async fn finish_job(&self, job: &AiPipelineJob, retry_delay: Option<Duration>) -> bool {
+        let node_id = job.node_id;
+        let mut state = self.state.lock().await;
+        let persisted = match retry_delay {
+            Some(delay) => {
+                let delay_secs = i64::try_from(delay.as_secs()).unwrap_or(i64::MAX);
+                mark_pipeline_job_retry(&self.db, node_id, job.priority, delay_secs).await
+            }
+            None => delete_pipeline_job(&self.db, node_id).await,
+        };
+        if let Err(err) = persisted {
+            tracing::warn!(node_id, error = %err, "Failed to persist finished pipeline job");
+        }
+        state.inflight.remove(&node_id);
+        state.inflight.is_empty() || state.paused
+    }
+
+    /// Mirrors a queued job to the database; failures only cost the job on a crash.
+    /// Callers hold the queue lock
+    async fn persist_job(&self, node_id: i64, priority: JobPriority) {
+        if let Err(err) = upsert_pipeline_job(&self.db, node_id, priority).await {
+            tracing::warn!(node_id, error = %err, "Failed to persist pipeline job");
+        }
+    }
 }
 
 async fn run_pipeline(
-    mut receiver: mpsc::Receiver<AiPipelineJob>,
-    inflight: Arc<Mutex<HashSet<i64>>>,
+    pipeline: AiPipeline,
     db: DbPool,
-    ai: Arc<AiServices>,
+    ai_handle: AiServicesHandle,
+    initial_services: Arc<AiServices>,
     ai_config: Arc<Mutex<AIConfigService>>,
     app_data_dir: std::path::PathBuf,
     app_handle: AppHandle,
 ) {
     let mut is_processing = false;
-    while let Some(job) = receiver.recv().await {
+    loop {
+        let job = pipeline.next_job().await;
+        // Services are swapped when the vault is re-indexed with another embedding model
+        let ai = ai_handle
+            .try_ready()
+            .unwrap_or_else(|| initial_services.clone());
         if !is_processing {
             is_processing = true;
-            emit_embedding_status(&app_handle, "processing");
+            emit_embedding_status(&app_handle, EmbeddingStatus::Processing);
         }
 
-        if let Err(err) =
-            process_resource_job(&db, &ai, &ai_config, &app_data_dir, job.node_id).await
-        {
-            tracing::error!(
-                node_id = job.node_id,
-                error = %err,
-                "AiPipeline job failed"
-            );
+        let started = Instant::now();
+        let result = process_resource_job(&db, &ai, &ai_config, &app_data_dir, job.node_id).await;
+        observe_duration(MetricHistogram::PipelineJob, started.elapsed());
+        let mut retry_delay = None;
+        match result {
+            Ok(outcome) => {
+                if let Err(err) = reset_processing_attempts(&db, job.node_id).await {
+                    tracing::warn!(node_id = job.node_id, error = %err, "Failed to reset attempts");
+                }
+                if outcome.suggested_tags > 0 {
+                    emit_event(
+                        &app_handle,
+                        &TagSuggestionsPayload {
+                            node_id: job.node_id,
+                            count: outcome.suggested_tags,
+                        },
+                    );
+                }
+                if outcome.needs_review {
+                    notify_job_result(&db, &app_handle, job.node_id, None).await;
+                }
+            }
+            Err(err) => {
+                inc_counter(MetricCounter::PipelineJobFailures);
+                let attempt = increment_processing_attempts(&db, job.node_id)
+                    .await
+                    .unwrap_or(RetryPolicy::DEFAULT.max_attempts);
+                if RetryPolicy::DEFAULT.should_retry(&err, attempt) {
+                    let delay = RetryPolicy::DEFAULT.delay_for(attempt);
+                    tracing::warn!(
+                        node_id = job.node_id,
+                        attempt,
+                        delay_secs = delay.as_secs(),
+                        error = %err,
+                        "AiPipeline job failed, retrying"
+                    );
+                    retry_delay = Some(delay);
+                } else {
+                    tracing::error!(
+                        node_id = job.node_id,
+                        priority = ?job.priority,
+                        attempt,
+                        error = %err,
+                        "AiPipeline job failed"
+                    );
+                    notify_job_result(&db, &app_handle, job.node_id, Some(&err.to_string())).await;
+                }
+            }
         }
 
-        let mut inflight = inflight.lock().await;
-        inflight.remove(&job.node_id);
-
-        if receiver.is_empty() && is_processing {
+        let idle = pipeline.finish_job(&job, retry_delay).await;
+        if let Some(delay) = retry_delay {
+            pipeline.schedule_retry(job.node_id, job.priority, delay);
+        }
+        if idle && is_processing {
             is_processing = false;
-            emit_embedding_status(&app_handle, "idle");
+            emit_embedding_status(&app_handle, EmbeddingStatus::Idle);
         }
     }
+}
+
+/// Time left until a persisted `retry_after` (SQLite `YYYY-MM-DD HH:MM:SS`, UTC); `None`
+/// once it has passed
+fn remaining_delay(retry_after: &str) -> Option<Duration> {
+    let retry_after = NaiveDateTime::parse_from_str(retry_after, "%Y-%m-%d %H:%M:%S").ok()?;
+    (retry_after - Utc::now().naive_utc())
+        .to_std()
+        .ok()
+        .filter(|delay| !delay.is_zero())
+}
 
-    if is_processing {
-        emit_embedding_status(&app_handle, "idle");
+/// 处理失败或分类待确认时写入通知中心
+async fn notify_job_result(db: &DbPool, app_handle: &AppHandle, node_id: i64, error: Option<&str>) {
+    let node_title = get_node_by_id(db, node_id)
+        .await
+        .map(|node| node.title)
+        .unwrap_or_default();
+
+    let (kind, title, action) = match error {
+        Some(_) => (NotificationKind::PipelineError, "资源处理失败", "retry_resource"),
+        None => (NotificationKind::ReviewNeeded, "分类结果待确认", "open_inbox"),
+    };
+    let body = match error {
+        Some(err) => format!("{node_title}: {err}"),
+        None => node_title,
+    };
+
+    if let Err(err) = push_notification(
+        db,
+        app_handle,
+        NewNotification {
+            kind,
+            title,
+            body: Some(&body),
+            node_id: Some(node_id),
+            action: Some(action),
+        },
+    )
+    .await
+    {
+        tracing::warn!(node_id, error = %err, "Failed to push pipeline notification");
     }
 }
 
-#[derive(Debug, Serialize, Clone)]
-struct EmbeddingStatusPayload {
-    status: String,
+fn emit_embedding_status(app_handle: &AppHandle, status: EmbeddingStatus) {
+    emit_event(app_handle, &EmbeddingStatusPayload { status });
 }
 
-fn emit_embedding_status(app_handle: &AppHandle, status: &str) {
-    let payload = EmbeddingStatusPayload {
-        status: status.to_string(),
-    };
-    let _ = app_handle.emit("embedding-status", payload);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remaining_delay() {
+        let format = "%Y-%m-%d %H:%M:%S";
+        let later = (Utc::now() + chrono::Duration::seconds(120))
+            .format(format)
+            .to_string();
+        let delay = remaining_delay(&later).unwrap();
+        assert!(delay > Duration::from_secs(100) && delay <= Duration::from_secs(120));
+
+        let earlier = (Utc::now() - chrono::Duration::seconds(5))
+            .format(format)
+            .to_string();
+        assert_eq!(remaining_delay(&earlier), None);
+        assert_eq!(remaining_delay("not a date"), None);
+    }
 }
@@ -0,0 +1,57 @@
+//! Retry policy for transient pipeline failures
+//!
+//! Rate limits, server errors and network failures are retried with exponential backoff; anything
+//! else (bad API key, unsupported file, empty content) fails the resource right away. Errors are
+//! classified by [`LlmError`] where the request fails (HTTP status / reqwest error kind), never by
+//! message text. The attempt count lives in `nodes.processing_attempts` so it survives restarts.
+
+use std::time::Duration;
+
+use crate::services::LlmError;
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RetryPolicy {
+    /// Total attempts including the first one
+    pub max_attempts: i64,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl RetryPolicy {
+    pub const DEFAULT: RetryPolicy = RetryPolicy {
+        max_attempts: 5,
+        base_delay: Duration::from_secs(30),
+        max_delay: Duration::from_secs(30 * 60),
+    };
+
+    /// Whether a failure on the given (1-based) attempt should be retried
+    pub fn should_retry(&self, error: &LlmError, attempt: i64) -> bool {
+        attempt < self.max_attempts && error.is_transient()
+    }
+
+    /// Delay before the attempt following `attempt`: base * 2^(attempt - 1), capped
+    pub fn delay_for(&self, attempt: i64) -> Duration {
+        let exponent = attempt.saturating_sub(1).clamp(0, 16) as u32;
+        self.base_delay
+            .saturating_mul(1 << exponent)
+            .min(self.max_delay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_policy() {
+        let policy = RetryPolicy::DEFAULT;
+        assert_eq!(policy.delay_for(1), Duration::from_secs(30));
+        assert_eq!(policy.delay_for(2), Duration::from_secs(60));
+        assert_eq!(policy.delay_for(4), Duration::from_secs(240));
+        assert_eq!(policy.delay_for(20), Duration::from_secs(30 * 60));
+        let unavailable = LlmError::Server(503, "503 unavailable".to_string());
+        assert!(policy.should_retry(&unavailable, 1));
+        assert!(!policy.should_retry(&unavailable, 5));
+        assert!(!policy.should_retry(&LlmError::from("invalid api key"), 1));
+    }
+}
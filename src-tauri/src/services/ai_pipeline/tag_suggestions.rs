@@ -0,0 +1,110 @@
+//! AI tag suggestions proposed after summarization
+
+use std::collections::HashSet;
+
+use crate::db::{
+    list_tag_suggestion_names, list_tags_for_node, list_tags_with_counts,
+    replace_pending_tag_suggestions, DbPool,
+};
+use crate::services::{AiServices, ProviderConfig};
+
+/// Suggestions kept per resource
+const MAX_SUGGESTED_TAGS: usize = 5;
+/// Longer "tags" are usually sentences copied from the summary
+const MAX_TAG_CHARS: usize = 32;
+/// Most-used existing tags offered to the model for reuse
+const EXISTING_TAGS_IN_PROMPT: usize = 50;
+
+/// Ask the model for tags and store them as pending suggestions.
+///
+/// Tags already on the node and names the user has accepted or rejected
+/// before are dropped, so reprocessing never re-asks the same question.
+/// Returns the number of stored suggestions.
+pub(crate) async fn suggest_and_store_tags(
+    db: &DbPool,
+    ai: &AiServices,
+    provider: &str,
+    model: &str,
+    provider_config: &ProviderConfig,
+    node_id: i64,
+    summary: &str,
+) -> Result<usize, String> {
+    let mut existing = list_tags_with_counts(db).await.map_err(|e| e.to_string())?;
+    existing.sort_by(|a, b| b.node_count.cmp(&a.node_count));
+    let existing_names: Vec<String> = existing
+        .into_iter()
+        .filter(|tag| tag.node_count > 0)
+        .take(EXISTING_TAGS_IN_PROMPT)
+        .map(|tag| tag.name)
+        .collect();
+
+    let raw = ai
+        .agent
+        .suggest_tags(provider, model, provider_config, summary, &existing_names)
+        .await?;
+
+    let mut known: Vec<String> = list_tags_for_node(db, node_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|tag| tag.name)
+        .collect();
+    known.extend(
+        list_tag_suggestion_names(db, node_id)
+            .await
+            .map_err(|e| e.to_string())?,
+    );
+
+    let names = normalize_tag_suggestions(&raw, &known);
+    replace_pending_tag_suggestions(db, node_id, &names)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(names.len())
+}
+
+/// Trim, strip `#`, drop empty / overlong / duplicate (case-insensitive) names
+/// and names in `known`, keeping at most `MAX_SUGGESTED_TAGS`.
+fn normalize_tag_suggestions(raw: &[String], known: &[String]) -> Vec<String> {
+    let mut seen: HashSet<String> = known.iter().map(|name| name.to_lowercase()).collect();
+    raw.iter()
+        .map(|name| name.trim().trim_start_matches('#').trim())
+        .filter(|name| !name.is_empty() && name.chars().count() <= MAX_TAG_CHARS)
+        .filter(|name| seen.insert(name.to_lowercase()))
+        .take(MAX_SUGGESTED_TAGS)
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(|item| item.to_string()).collect()
+    }
+
+    #[test]
+    fn test_normalize_tag_suggestions() {
+        let raw = strings(&[
+            " #Rust ",
+            "rust",
+            "",
+            "async",
+            "Databases",
+            "papers",
+            "ml",
+            "extra",
+        ]);
+        let known = strings(&["Papers"]);
+        assert_eq!(
+            normalize_tag_suggestions(&raw, &known),
+            strings(&["Rust", "async", "Databases", "ml", "extra"])
+        );
+    }
+
+    #[test]
+    fn test_normalize_tag_suggestions_drops_sentences() {
+        let raw = strings(&["a very long sentence copied straight from the summary"]);
+        assert!(normalize_tag_suggestions(&raw, &[]).is_empty());
+    }
+}
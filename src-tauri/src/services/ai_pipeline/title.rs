@@ -0,0 +1,201 @@
+//! Descriptive titles for untitled text captures
+
+use crate::db::{
+    get_node_by_id, insert_node_revision_log, update_node_source_meta, update_node_text_checked,
+    DbPool, NewNodeRevisionLog, NodeRecord, NodeTextField, ResourceSubtype, SourceMeta,
+};
+use crate::i18n::MessageCode;
+use crate::services::parser::build_text_title;
+use crate::services::{AiServices, ProviderConfig};
+
+/// Content sent to the model; the opening is enough to name a snippet
+const TITLE_CONTENT_CHARS: usize = 2000;
+/// Longer titles are usually a sentence copied from the content
+const MAX_TITLE_CHARS: usize = 60;
+const UNTITLED: &str = "Untitled";
+
+/// Whether the resource still carries the name derived at capture time: a text snippet
+/// without a file whose title is the truncated opening of its content
+pub(crate) fn is_auto_titled(node: &NodeRecord) -> bool {
+    if node.file_path.is_some() || node.resource_subtype != Some(ResourceSubtype::Text) {
+        return false;
+    }
+    let already_retitled = node
+        .source_meta
+        .as_ref()
+        .is_some_and(|meta| meta.auto_title.is_some());
+    if already_retitled {
+        return false;
+    }
+    let content = node.file_content.as_deref().unwrap_or("");
+    node.title == UNTITLED || node.title == build_text_title(content)
+}
+
+/// Replace the auto-derived name of an untitled capture.
+///
+/// Asks the model first and falls back to the first line of the content when the call
+/// fails. The old name is kept in `source_meta.auto_title` and the change is written to
+/// the revision log. Returns the new title, or `None` when nothing better was found.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn retitle_untitled_resource(
+    db: &DbPool,
+    ai: &AiServices,
+    provider: &str,
+    model: &str,
+    provider_config: &ProviderConfig,
+    node: &NodeRecord,
+    content: &str,
+    summary: &str,
+) -> Result<Option<String>, String> {
+    let excerpt: String = content.trim().chars().take(TITLE_CONTENT_CHARS).collect();
+    let generated = match ai
+        .agent
+        .generate_title(provider, model, provider_config, &excerpt, summary)
+        .await
+    {
+        Ok(raw) => normalize_title(&raw).map(|title| (title, true)),
+        Err(err) => {
+            tracing::warn!(node_id = node.node_id, error = %err, "AiPipeline title generation failed");
+            None
+        }
+    };
+    let Some((title, from_model)) =
+        generated.or_else(|| heuristic_title(content).map(|title| (title, false)))
+    else {
+        return Ok(None);
+    };
+    if title == node.title {
+        return Ok(None);
+    }
+
+    // The model call takes a while: keep a name the user typed in the meantime
+    let current = get_node_by_id(db, node.node_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    if current.title != node.title {
+        return Ok(None);
+    }
+    match update_node_text_checked(
+        db,
+        node.node_id,
+        NodeTextField::Title,
+        Some(title.as_str()),
+        Some(current.version),
+    )
+    .await
+    {
+        Ok(_) => {}
+        Err(err) if err.code() == MessageCode::NodeEditConflict => return Ok(None),
+        Err(err) => return Err(err.to_string()),
+    }
+
+    let mut meta = current
+        .source_meta
+        .as_ref()
+        .map(|meta| meta.0.clone())
+        .unwrap_or_else(empty_source_meta);
+    meta.auto_title = Some(node.title.clone());
+    update_node_source_meta(db, node.node_id, &meta)
+        .await
+        .map_err(|e| e.to_string())?;
+    insert_node_revision_log(
+        db,
+        NewNodeRevisionLog {
+            node_id: node.node_id,
+            field_name: "title",
+            old_value: Some(node.title.as_str()),
+            new_value: Some(title.as_str()),
+            reason: Some(if from_model {
+                "ai_auto_title"
+            } else {
+                "auto_title"
+            }),
+            provider: from_model.then_some(provider),
+            model: from_model.then_some(model),
+            confidence_score: None,
+        },
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    tracing::info!(node_id = node.node_id, old = %node.title, new = %title, "Untitled resource retitled");
+    Ok(Some(title))
+}
+
+/// Offline title: the first non-empty line with Markdown markers stripped
+fn heuristic_title(content: &str) -> Option<String> {
+    let line = content
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())?;
+    let line = line.trim_start_matches(['#', '>', '-', '*', ' ']);
+    normalize_title(line)
+}
+
+/// Single line, no wrapping quotes or trailing punctuation, cut at `MAX_TITLE_CHARS`
+fn normalize_title(raw: &str) -> Option<String> {
+    let line = raw.lines().map(str::trim).find(|line| !line.is_empty())?;
+    let line = line
+        .trim_matches(|c: char| matches!(c, '"' | '\'' | '“' | '”' | '「' | '」' | '《' | '》'))
+        .trim_end_matches(|c: char| {
+            matches!(
+                c,
+                '.' | '。' | '!' | '！' | '?' | '？' | ':' | '：' | ',' | '，'
+            )
+        })
+        .trim();
+    if line.is_empty() {
+        return None;
+    }
+    if line.chars().count() <= MAX_TITLE_CHARS {
+        return Some(line.to_string());
+    }
+    let cut: String = line.chars().take(MAX_TITLE_CHARS).collect();
+    // Prefer ending on a word boundary for space-separated languages
+    let cut = match cut.rfind(' ') {
+        Some(pos) if pos > cut.len() / 2 => cut[..pos].to_string(),
+        _ => cut,
+    };
+    Some(format!("{}…", cut.trim_end()))
+}
+
+fn empty_source_meta() -> SourceMeta {
+    SourceMeta {
+        url: None,
+        window_title: None,
+        process_name: None,
+        captured_at: None,
+        process_path: None,
+        sender: None,
+        sent_at: None,
+        auto_title: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_title() {
+        assert_eq!(
+            normalize_title("  \"Rust 异步运行时对比\"。\n").as_deref(),
+            Some("Rust 异步运行时对比")
+        );
+        assert_eq!(normalize_title("\n\n"), None);
+        let long = "word ".repeat(30);
+        let title = normalize_title(&long).unwrap();
+        assert!(title.ends_with('…'));
+        assert!(title.chars().count() <= MAX_TITLE_CHARS + 1);
+        assert!(!title.contains("wo…"));
+    }
+
+    #[test]
+    fn test_heuristic_title_uses_first_line() {
+        assert_eq!(
+            heuristic_title("\n## Meeting notes\nsecond line").as_deref(),
+            Some("Meeting notes")
+        );
+        assert_eq!(heuristic_title("   "), None);
+    }
+}
@@ -0,0 +1,277 @@
+//! 资源文件目录的位置与迁移
+//!
+//! 节点中的资源文件统一以 `assets/{file}` 相对路径保存，实际目录由 utils 中的资源根目录决定，
+//! 因此迁移目录只需复制文件并更新配置，不需要改写数据库中的路径。
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::i18n::MessageCode;
+use crate::utils::{assets_root, compute_file_sha256, custom_assets_root, set_custom_assets_root};
+use crate::{AppError, AppResult};
+
+/// 同一时间只允许一次迁移
+static RELOCATING: AtomicBool = AtomicBool::new(false);
+
+/// 资源文件目录状态
+#[derive(Debug, Clone, Serialize)]
+pub struct AssetsStorageStatus {
+    pub path: String,
+    /// 是否为自定义目录（否则为应用数据目录下的 assets）
+    pub is_custom: bool,
+    /// 目录是否存在；自定义目录所在硬盘未连接时为 false
+    pub available: bool,
+}
+
+pub fn assets_storage_status(app_data_dir: &Path) -> AssetsStorageStatus {
+    let is_custom = custom_assets_root().is_some();
+    let path = assets_root(app_data_dir);
+    AssetsStorageStatus {
+        path: path.to_string_lossy().to_string(),
+        is_custom,
+        // 默认目录在首次写入时创建，缺失不算不可用
+        available: !is_custom || path.is_dir(),
+    }
+}
+
+/// 启动时按配置设置资源文件目录，返回当前状态；自定义目录缺失时仍保留配置，等待硬盘重新连接
+pub fn apply_assets_config(app_data_dir: &Path, assets_dir: Option<&str>) -> AssetsStorageStatus {
+    let root = assets_dir
+        .map(str::trim)
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from);
+    set_custom_assets_root(root);
+    let status = assets_storage_status(app_data_dir);
+    if !status.available {
+        tracing::error!(path = %status.path, "Custom assets directory is missing");
+    }
+    status
+}
+
+/// 允许前端通过 asset 协议读取资源文件目录（默认 scope 仅覆盖应用数据目录）
+pub fn allow_assets_scope(app: &AppHandle, root: &Path) {
+    if let Err(err) = app.asset_protocol_scope().allow_directory(root, true) {
+        tracing::warn!(path = %root.display(), error = %err, "Allow assets scope failed");
+    }
+}
+
+/// 迁移锁，drop 时释放
+pub struct RelocationGuard(());
+
+impl RelocationGuard {
+    pub fn acquire() -> Option<Self> {
+        RELOCATING
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .ok()
+            .map(|_| Self(()))
+    }
+}
+
+impl Drop for RelocationGuard {
+    fn drop(&mut self) {
+        RELOCATING.store(false, Ordering::Release);
+    }
+}
+
+/// 检查迁移目标：需为绝对路径，且不能与当前目录相同或互相包含
+pub fn validate_relocation_target(current: &Path, target: &Path) -> AppResult<()> {
+    if !target.is_absolute() {
+        return Err(AppError::coded(MessageCode::AssetsDirNotAbsolute));
+    }
+    if target == current {
+        return Err(AppError::coded(MessageCode::AssetsDirUnchanged));
+    }
+    if target.starts_with(current) || current.starts_with(target) {
+        return Err(AppError::coded(MessageCode::AssetsDirNested));
+    }
+    Ok(())
+}
+
+/// 文件操作失败，detail 带上出错的路径
+fn file_error(path: &Path, err: impl std::fmt::Display) -> AppError {
+    AppError::coded_with(
+        MessageCode::InvalidAssetsDir,
+        "detail",
+        format!("{}: {err}", path.display()),
+    )
+}
+
+/// 复制结果；files 为已在目标目录中就绪的文件（相对路径），迁移完成后据此删除旧文件
+#[derive(Debug, Default)]
+pub struct AssetsCopy {
+    pub files: Vec<PathBuf>,
+    pub copied: usize,
+    /// 目标中已存在且内容一致（上次中断的迁移）
+    pub skipped_existing: usize,
+    pub bytes: u64,
+}
+
+/// 把 from 中的文件复制到 to，每处理一个文件调用一次 on_progress(已处理, 总数)
+///
+/// 目标中已存在的同名文件：内容一致视为已复制，否则中止。失败时删除本次复制的文件
+pub fn copy_assets(
+    from: &Path,
+    to: &Path,
+    on_progress: &mut dyn FnMut(usize, usize),
+) -> AppResult<AssetsCopy> {
+    let mut files = Vec::new();
+    collect_files(from, Path::new(""), &mut files)?;
+
+    let total = files.len();
+    let mut result = AssetsCopy::default();
+    let mut created = Vec::new();
+    for (index, relative) in files.into_iter().enumerate() {
+        match copy_one(from, to, &relative) {
+            Ok(Some(bytes)) => {
+                created.push(to.join(&relative));
+                result.copied += 1;
+                result.bytes += bytes;
+            }
+            Ok(None) => result.skipped_existing += 1,
+            Err(err) => {
+                for path in &created {
+                    let _ = fs::remove_file(path);
+                }
+                return Err(err);
+            }
+        }
+        result.files.push(relative);
+        on_progress(index + 1, total);
+    }
+    Ok(result)
+}
+
+/// 删除已迁移的旧文件与随之变空的子目录，返回删除失败的文件数
+pub fn remove_old_assets(from: &Path, files: &[PathBuf]) -> usize {
+    let mut failed = 0;
+    for relative in files {
+        if let Err(err) = fs::remove_file(from.join(relative)) {
+            tracing::warn!(file = %relative.display(), error = %err, "Remove old asset failed");
+            failed += 1;
+        }
+    }
+    remove_empty_dirs(from);
+    failed
+}
+
+fn copy_one(from: &Path, to: &Path, relative: &Path) -> AppResult<Option<u64>> {
+    let source = from.join(relative);
+    let target = to.join(relative);
+    let size = fs::metadata(&source)
+        .map_err(|e| file_error(&source, e))?
+        .len();
+    if let Ok(existing) = fs::metadata(&target) {
+        // 大小相同不代表是同一文件，内容一致才视为已复制，否则删除源文件会丢数据
+        if existing.len() == size && same_content(&source, &target)? {
+            return Ok(None);
+        }
+        return Err(AppError::coded_with(
+            MessageCode::AssetsFileConflict,
+            "path",
+            target.display(),
+        ));
+    }
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent).map_err(|e| file_error(parent, e))?;
+    }
+    fs::copy(&source, &target)
+        .map_err(|e| file_error(&source, e))
+        .map(Some)
+}
+
+fn same_content(a: &Path, b: &Path) -> AppResult<bool> {
+    let hash = |path: &Path| compute_file_sha256(path).map_err(|e| file_error(path, e));
+    Ok(hash(a)? == hash(b)?)
+}
+
+fn collect_files(root: &Path, relative: &Path, files: &mut Vec<PathBuf>) -> AppResult<()> {
+    let dir = root.join(relative);
+    let entries = fs::read_dir(&dir).map_err(|e| file_error(&dir, e))?;
+    for entry in entries {
+        let entry = entry.map_err(|e| file_error(&dir, e))?;
+        let path = relative.join(entry.file_name());
+        let file_type = entry
+            .file_type()
+            .map_err(|e| file_error(&entry.path(), e))?;
+        if file_type.is_dir() {
+            collect_files(root, &path, files)?;
+        } else if file_type.is_file() {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn remove_empty_dirs(dir: &Path) {
+    if let Ok(entries) = fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            if entry.file_type().is_ok_and(|t| t.is_dir()) {
+                remove_empty_dirs(&entry.path());
+            }
+        }
+    }
+    // 非空目录删除失败，忽略
+    let _ = fs::remove_dir(dir);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_nested_or_relative_targets() {
+        let current = Path::new("/data/assets");
+        assert!(validate_relocation_target(current, Path::new("relative")).is_err());
+        assert!(validate_relocation_target(current, current).is_err());
+        assert!(validate_relocation_target(current, Path::new("/data/assets/sub")).is_err());
+        assert!(validate_relocation_target(current, Path::new("/data")).is_err());
+        assert!(validate_relocation_target(current, Path::new("/mnt/drive/assets")).is_ok());
+    }
+
+    #[test]
+    fn copies_and_removes_assets() {
+        let from = tempfile::tempdir().unwrap();
+        let to = tempfile::tempdir().unwrap();
+        fs::write(from.path().join("a.pdf"), b"pdf").unwrap();
+        fs::create_dir(from.path().join("nested")).unwrap();
+        fs::write(from.path().join("nested/b.png"), b"png!").unwrap();
+        // 上次中断时已复制的文件
+        fs::write(to.path().join("a.pdf"), b"pdf").unwrap();
+
+        let mut last = (0, 0);
+        let result = copy_assets(from.path(), to.path(), &mut |done, total| {
+            last = (done, total)
+        })
+        .unwrap();
+        assert_eq!(last, (2, 2));
+        assert_eq!(result.copied, 1);
+        assert_eq!(result.skipped_existing, 1);
+        assert_eq!(result.bytes, 4);
+        assert_eq!(fs::read(to.path().join("nested/b.png")).unwrap(), b"png!");
+
+        assert_eq!(remove_old_assets(from.path(), &result.files), 0);
+        assert!(!from.path().join("nested").exists());
+    }
+
+    #[test]
+    fn aborts_on_conflicting_file() {
+        let from = tempfile::tempdir().unwrap();
+        let to = tempfile::tempdir().unwrap();
+        fs::write(from.path().join("a.pdf"), b"pdf").unwrap();
+        fs::write(to.path().join("a.pdf"), b"different").unwrap();
+
+        assert!(copy_assets(from.path(), to.path(), &mut |_, _| {}).is_err());
+        assert_eq!(fs::read(to.path().join("a.pdf")).unwrap(), b"different");
+
+        // 大小相同但内容不同也算冲突
+        fs::write(to.path().join("a.pdf"), b"PDF").unwrap();
+        assert!(copy_assets(from.path(), to.path(), &mut |_, _| {}).is_err());
+        assert_eq!(fs::read(to.path().join("a.pdf")).unwrap(), b"PDF");
+    }
+}
@@ -0,0 +1,269 @@
+//! BibTeX 导出
+//!
+//! 将资源的文献元数据渲染为 .bib 文本；没有元数据的资源跳过并在结果中列出。
+
+use std::collections::HashSet;
+
+use serde::Serialize;
+
+use crate::db::{
+    get_citation_by_node, get_node_by_id, list_target_nodes, CitationRecord, DbPool,
+    EdgeRelationType, NodeRecord, NodeType,
+};
+
+/// 跳过的资源（没有引用元数据）
+#[derive(Debug, Serialize)]
+pub struct SkippedCitation {
+    pub node_id: i64,
+    pub title: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BibtexExport {
+    pub content: String,
+    pub entry_count: usize,
+    pub skipped: Vec<SkippedCitation>,
+}
+
+/// 导出范围：主题下直接包含的资源，或指定资源
+pub enum BibtexScope {
+    Topic(i64),
+    Nodes(Vec<i64>),
+}
+
+pub async fn build_bibtex_export(db: &DbPool, scope: BibtexScope) -> Result<BibtexExport, String> {
+    let nodes: Vec<NodeRecord> = match scope {
+        BibtexScope::Topic(topic_id) => list_target_nodes(db, topic_id, EdgeRelationType::Contains)
+            .await
+            .map_err(|e| e.to_string())?,
+        BibtexScope::Nodes(node_ids) => {
+            let mut nodes = Vec::with_capacity(node_ids.len());
+            for node_id in node_ids {
+                let node = get_node_by_id(db, node_id)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                if !node.is_deleted {
+                    nodes.push(node);
+                }
+            }
+            nodes
+        }
+    };
+
+    let mut citations = Vec::new();
+    let mut skipped = Vec::new();
+    for node in nodes
+        .into_iter()
+        .filter(|n| n.node_type == NodeType::Resource)
+    {
+        match get_citation_by_node(db, node.node_id)
+            .await
+            .map_err(|e| e.to_string())?
+        {
+            Some(citation) => citations.push(citation),
+            None => skipped.push(SkippedCitation {
+                node_id: node.node_id,
+                title: node.title,
+            }),
+        }
+    }
+
+    Ok(BibtexExport {
+        content: render_bibtex(&citations),
+        entry_count: citations.len(),
+        skipped,
+    })
+}
+
+/// 渲染多条引用，重复的 key 依次追加 a / b / c ...
+pub fn render_bibtex(citations: &[CitationRecord]) -> String {
+    let mut used = HashSet::new();
+    citations
+        .iter()
+        .map(|citation| {
+            let base = citation_key(citation);
+            let mut key = base.clone();
+            let mut suffix = b'a';
+            while !used.insert(key.clone()) {
+                key = if suffix <= b'z' {
+                    format!("{base}{}", suffix as char)
+                } else {
+                    format!("{base}{}", used.len())
+                };
+                suffix = suffix.saturating_add(1);
+            }
+            render_entry(citation, &key)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 形如 `vaswani2017attention`：第一作者姓 + 年份 + 标题首个实词
+fn citation_key(citation: &CitationRecord) -> String {
+    let last_name = citation
+        .authors
+        .first()
+        .map(|author| match author.split_once(',') {
+            // "Vaswani, Ashish"
+            Some((last, _)) => last.to_string(),
+            // "Ashish Vaswani"
+            None => author.split_whitespace().last().unwrap_or("").to_string(),
+        })
+        .map(|name| ascii_key_part(&name))
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "anon".to_string());
+    let year = citation
+        .year
+        .map(|year| year.to_string())
+        .unwrap_or_else(|| "nd".to_string());
+    let word = citation
+        .title
+        .split_whitespace()
+        .map(ascii_key_part)
+        .find(|word| word.len() >= 3 && !KEY_STOP_WORDS.contains(&word.as_str()))
+        .unwrap_or_default();
+    format!("{last_name}{year}{word}")
+}
+
+const KEY_STOP_WORDS: &[&str] = &["the", "and", "for", "with", "from", "towards", "toward"];
+
+fn ascii_key_part(raw: &str) -> String {
+    raw.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+fn render_entry(citation: &CitationRecord, key: &str) -> String {
+    let venue = citation.venue.as_deref();
+    let (entry_type, venue_field) = match venue {
+        Some(venue) if is_proceedings(venue) => ("inproceedings", "booktitle"),
+        Some(_) => ("article", "journal"),
+        None => ("misc", ""),
+    };
+
+    let mut fields = vec![("title", format!("{{{}}}", escape_bibtex(&citation.title)))];
+    if !citation.authors.is_empty() {
+        let authors = citation
+            .authors
+            .iter()
+            .map(|author| escape_bibtex(author))
+            .collect::<Vec<_>>()
+            .join(" and ");
+        fields.push(("author", authors));
+    }
+    if let Some(year) = citation.year {
+        fields.push(("year", year.to_string()));
+    }
+    if let Some(venue) = venue {
+        fields.push((venue_field, escape_bibtex(venue)));
+    }
+    if let Some(doi) = citation.doi.as_deref() {
+        fields.push(("doi", doi.to_string()));
+    }
+
+    let body = fields
+        .iter()
+        .map(|(name, value)| format!("  {name} = {{{value}}}"))
+        .collect::<Vec<_>>()
+        .join(",\n");
+    format!("@{entry_type}{{{key},\n{body}\n}}\n")
+}
+
+fn is_proceedings(venue: &str) -> bool {
+    let venue = venue.to_lowercase();
+    ["proceedings", "conference", "workshop", "symposium"]
+        .iter()
+        .any(|marker| venue.contains(marker))
+}
+
+fn escape_bibtex(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.trim().chars() {
+        match c {
+            '&' | '%' | '$' | '#' | '_' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '{' | '}' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::CitationSource;
+    use sqlx::types::Json;
+
+    fn citation(
+        title: &str,
+        authors: &[&str],
+        year: Option<i64>,
+        venue: Option<&str>,
+    ) -> CitationRecord {
+        CitationRecord {
+            node_id: 1,
+            title: title.to_string(),
+            authors: Json(authors.iter().map(|a| a.to_string()).collect()),
+            year,
+            venue: venue.map(str::to_string),
+            doi: None,
+            source: CitationSource::Llm,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_citation_key() {
+        let c = citation(
+            "Attention Is All You Need",
+            &["Ashish Vaswani"],
+            Some(2017),
+            None,
+        );
+        assert_eq!(citation_key(&c), "vaswani2017attention");
+        let c = citation("The Art of Programming", &["Knuth, Donald"], None, None);
+        assert_eq!(citation_key(&c), "knuthndart");
+        let c = citation("深度学习", &[], Some(2016), None);
+        assert_eq!(citation_key(&c), "anon2016");
+    }
+
+    #[test]
+    fn test_render_entry_types_and_escaping() {
+        let c = citation(
+            "Deep Residual Learning",
+            &["Kaiming He", "Xiangyu Zhang"],
+            Some(2016),
+            Some("Proceedings of CVPR"),
+        );
+        let rendered = render_bibtex(&[c]);
+        assert!(rendered.starts_with("@inproceedings{he2016deep,\n"));
+        assert!(rendered.contains("  title = {{Deep Residual Learning}}"));
+        assert!(rendered.contains("  author = {Kaiming He and Xiangyu Zhang}"));
+        assert!(rendered.contains("  booktitle = {Proceedings of CVPR}"));
+
+        let c = citation("R&D in 50% of cases", &[], None, Some("Journal of A_B"));
+        let rendered = render_bibtex(&[c]);
+        assert!(rendered.starts_with("@article{"));
+        assert!(rendered.contains("{{R\\&D in 50\\% of cases}}"));
+        assert!(rendered.contains("journal = {Journal of A\\_B}"));
+    }
+
+    #[test]
+    fn test_duplicate_keys_get_suffix() {
+        let a = citation(
+            "Attention Is All You Need",
+            &["Ashish Vaswani"],
+            Some(2017),
+            None,
+        );
+        let b = citation("Attention Again", &["Ashish Vaswani"], Some(2017), None);
+        let rendered = render_bibtex(&[a, b]);
+        assert!(rendered.contains("@misc{vaswani2017attention,"));
+        assert!(rendered.contains("@misc{vaswani2017attentiona,"));
+    }
+}
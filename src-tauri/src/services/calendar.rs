@@ -0,0 +1,352 @@
+//! 日历同步
+//!
+//! 把 .ics 事件同步为任务：同一 UID 始终对应同一个任务节点，重复同步只更新有变化的字段。
+//! 重复事件以今天起的下一次发生时间作为截止时间，每次同步时前移；对单次发生的改动
+//! （带 RECURRENCE-ID 的移动或取消）与系列同 UID，不影响系列对应的任务，同步时跳过。
+//! 后台任务按各订阅的轮询间隔拉取 URL，并在 VALARM 提醒时间到达时推送通知。
+//! 反方向上，有截止日期的任务可导出为 .ics，供日历应用订阅。
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{Local, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use futures_util::StreamExt;
+use reqwest::Client;
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use super::{
+    parse_ics, push_notification, write_ics, IcsEvent, IcsTime, Recurrence, RecurrenceRule,
+};
+use crate::app_state::AppState;
+use crate::db::{
+    get_calendar_event_by_uid, get_node_by_id, list_due_calendar_reminders,
+    list_due_calendar_subscriptions, list_task_reminders, list_tasks_with_due_date,
+    mark_calendar_reminder_sent, mark_task_cancelled, record_calendar_sync, update_node_title,
+    update_node_user_note, update_task_due_date, upsert_calendar_event, CalendarSubscriptionRecord,
+    DbPool, NewCalendarEvent, NewNotification, NodeBuilder, NodeRecord, NotificationKind,
+    TaskStatus,
+};
+
+const SCHEDULER_INITIAL_DELAY: Duration = Duration::from_secs(30);
+const SCHEDULER_INTERVAL: Duration = Duration::from_secs(60);
+const FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+/// 日历文件大小上限，超出时整体拒绝（截断的 .ics 无法正确解析）
+const FETCH_MAX_BYTES: usize = 10 * 1024 * 1024;
+/// 超过该时长仍未发送的提醒视为错过（如应用未运行），不再补发
+const REMINDER_GRACE_MINUTES: i64 = 60;
+const UNTITLED_EVENT: &str = "(无标题日程)";
+const EXPORT_CALENDAR_NAME: &str = "NeuralVault";
+
+/// 一次同步的统计
+#[derive(Debug, Default, Serialize)]
+pub struct CalendarSyncSummary {
+    pub created: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    /// 已取消且从未导入的事件、对应任务已被删除的事件，或重复事件单次发生的改动
+    pub skipped: usize,
+}
+
+/// 将事件写入任务；subscription_id 为 None 表示来自文件导入
+pub async fn sync_calendar_events(
+    db: &DbPool,
+    events: &[IcsEvent],
+    subscription_id: Option<i64>,
+) -> Result<CalendarSyncSummary, sqlx::Error> {
+    let today = Local::now()
+        .date_naive()
+        .and_hms_opt(0, 0, 0)
+        .expect("midnight");
+    let mut summary = CalendarSyncSummary::default();
+
+    for event in events {
+        if event.recurrence_id.is_some() {
+            summary.skipped += 1;
+            continue;
+        }
+        let occurrence = event
+            .rrule
+            .as_deref()
+            .and_then(Recurrence::parse)
+            .and_then(|rule| rule.next_occurrence(event.start.naive(), today))
+            .map_or(event.start, |next| event.start.with(next));
+        let shift = occurrence.naive() - event.start.naive();
+        let starts_at = occurrence.to_db_string();
+        let ends_at = event
+            .end
+            .map(|end| end.with(end.naive() + shift).to_db_string());
+        let reminder_at = event
+            .reminder_minutes
+            .map(|minutes| format_local(occurrence.naive() - chrono::Duration::minutes(minutes)));
+        let title = match event.summary.trim() {
+            "" => UNTITLED_EVENT,
+            title => title,
+        };
+        let note = build_task_note(event);
+
+        let mapping = |node_id| NewCalendarEvent {
+            subscription_id,
+            uid: &event.uid,
+            node_id,
+            starts_at: &starts_at,
+            ends_at: ends_at.as_deref(),
+            location: event.location.as_deref(),
+            recurrence_rule: event.rrule.as_deref(),
+            reminder_at: reminder_at.as_deref(),
+        };
+
+        let Some(existing) = get_calendar_event_by_uid(db, &event.uid).await? else {
+            if event.cancelled {
+                summary.skipped += 1;
+                continue;
+            }
+            let node_id = NodeBuilder::task()
+                .title(title)
+                .due_date(Some(starts_at.as_str()))
+                .user_note(note.as_deref())
+                .insert(db)
+                .await?;
+            upsert_calendar_event(db, mapping(node_id)).await?;
+            summary.created += 1;
+            continue;
+        };
+
+        let node = get_node_by_id(db, existing.node_id).await?;
+        if node.is_deleted {
+            summary.skipped += 1;
+            continue;
+        }
+
+        let mut changed = false;
+        if node.title != title {
+            update_node_title(db, node.node_id, title).await?;
+            changed = true;
+        }
+        if node.due_date.as_deref() != Some(starts_at.as_str()) {
+            update_task_due_date(db, node.node_id, Some(&starts_at)).await?;
+            changed = true;
+        }
+        if node.user_note != note {
+            update_node_user_note(db, node.node_id, note.as_deref()).await?;
+            changed = true;
+        }
+        if event.cancelled && node.task_status == Some(TaskStatus::Todo) {
+            mark_task_cancelled(db, node.node_id).await?;
+            changed = true;
+        }
+
+        let mapping_changed = existing.starts_at != starts_at
+            || existing.ends_at != ends_at
+            || existing.location != event.location
+            || existing.recurrence_rule != event.rrule
+            || existing.reminder_at != reminder_at
+            || (subscription_id.is_some() && existing.subscription_id != subscription_id);
+        if mapping_changed {
+            upsert_calendar_event(db, mapping(node.node_id)).await?;
+        }
+
+        if changed || mapping_changed {
+            summary.updated += 1;
+        } else {
+            summary.unchanged += 1;
+        }
+    }
+
+    Ok(summary)
+}
+
+/// 拉取订阅 URL 并同步，结果（含错误）记录到订阅上
+pub async fn sync_calendar_subscription(
+    db: &DbPool,
+    subscription: &CalendarSubscriptionRecord,
+) -> Result<CalendarSyncSummary, String> {
+    let result = async {
+        let text = fetch_calendar(&subscription.url).await?;
+        let events = parse_ics(&text)?;
+        sync_calendar_events(db, &events, Some(subscription.subscription_id))
+            .await
+            .map_err(|e| e.to_string())
+    }
+    .await;
+
+    let error = result.as_ref().err().map(String::as_str);
+    record_calendar_sync(db, subscription.subscription_id, error)
+        .await
+        .map_err(|e| e.to_string())?;
+    result
+}
+
+/// 有截止日期的任务导出为 .ics 文本，返回文本与事件数
+///
+/// UID 取节点 uuid，重新导出后日历应用会更新同一事件；只有待办任务带提醒，
+/// 已取消的任务标记为 `STATUS:CANCELLED`
+pub async fn build_tasks_ics(db: &DbPool) -> Result<(String, usize), sqlx::Error> {
+    let tasks = list_tasks_with_due_date(db).await?;
+    let reminders: HashMap<i64, i64> = list_task_reminders(db)
+        .await?
+        .into_iter()
+        .map(|reminder| (reminder.node_id, reminder.minutes_before))
+        .collect();
+
+    let events: Vec<IcsEvent> = tasks
+        .iter()
+        .filter_map(|task| task_to_event(task, reminders.get(&task.node_id).copied()))
+        .collect();
+    let text = write_ics(EXPORT_CALENDAR_NAME, &events, Utc::now().naive_utc());
+    Ok((text, events.len()))
+}
+
+async fn fetch_calendar(url: &str) -> Result<String, String> {
+    // webcal:// 是 http(s) 的别名
+    const WEBCAL: &str = "webcal://";
+    let url = match url.get(..WEBCAL.len()) {
+        Some(scheme) if scheme.eq_ignore_ascii_case(WEBCAL) => {
+            format!("https://{}", &url[WEBCAL.len()..])
+        }
+        _ => url.to_string(),
+    };
+    let client = Client::builder()
+        .timeout(FETCH_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| format!("拉取日历失败: {e}"))?;
+    let mut body = Vec::new();
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| format!("读取日历失败: {e}"))?;
+        body.extend_from_slice(&chunk);
+        if body.len() > FETCH_MAX_BYTES {
+            return Err(format!(
+                "日历文件超过 {} MB",
+                FETCH_MAX_BYTES / (1024 * 1024)
+            ));
+        }
+    }
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
+
+/// 任务备注：地点 + 事件描述
+fn build_task_note(event: &IcsEvent) -> Option<String> {
+    let mut parts = Vec::new();
+    if let Some(location) = event.location.as_deref() {
+        parts.push(format!("地点：{location}"));
+    }
+    if let Some(description) = event.description.as_deref() {
+        parts.push(description.to_string());
+    }
+    (!parts.is_empty()).then(|| parts.join("\n\n"))
+}
+
+fn task_to_event(task: &NodeRecord, reminder_minutes: Option<i64>) -> Option<IcsEvent> {
+    let start = parse_due_date(task.due_date.as_deref()?)?;
+    let all_day = matches!(start, IcsTime::Date(_));
+    let rrule = task
+        .recurrence_rule
+        .as_deref()
+        .and_then(|rule| RecurrenceRule::parse(rule).ok())
+        .map(|rule| rule.to_ics_rrule(all_day));
+    let is_todo = task.task_status == Some(TaskStatus::Todo);
+    Some(IcsEvent {
+        uid: format!("{}@neuralvault", task.uuid),
+        summary: task.title.clone(),
+        description: task
+            .user_note
+            .clone()
+            .filter(|note| !note.trim().is_empty()),
+        location: None,
+        start,
+        end: None,
+        rrule,
+        recurrence_id: None,
+        cancelled: task.task_status == Some(TaskStatus::Cancelled),
+        reminder_minutes: reminder_minutes.filter(|_| is_todo),
+    })
+}
+
+/// due_date 转为事件时间：只有日期（或时间无法解析）时为全天事件
+fn parse_due_date(due_date: &str) -> Option<IcsTime> {
+    let date = NaiveDate::parse_from_str(due_date.get(..10)?, "%Y-%m-%d").ok()?;
+    let time = due_date[10..].trim_start_matches(['T', ' ']).trim();
+    let time = NaiveTime::parse_from_str(time, "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(time, "%H:%M"))
+        .ok();
+    Some(time.map_or(IcsTime::Date(date), |time| {
+        IcsTime::DateTime(date.and_time(time))
+    }))
+}
+
+fn format_local(dt: NaiveDateTime) -> String {
+    dt.format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+async fn send_due_reminders(db: &DbPool, app: &AppHandle) -> Result<(), String> {
+    let now = Local::now().naive_local();
+    let since = now - chrono::Duration::minutes(REMINDER_GRACE_MINUTES);
+    let reminders = list_due_calendar_reminders(db, &format_local(since), &format_local(now))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    for reminder in reminders {
+        let body = match reminder.location.as_deref() {
+            Some(location) => format!("{} · {location}", reminder.starts_at),
+            None => reminder.starts_at.clone(),
+        };
+        push_notification(
+            db,
+            app,
+            NewNotification {
+                kind: NotificationKind::System,
+                title: &format!("日程提醒：{}", reminder.title),
+                body: Some(&body),
+                node_id: Some(reminder.node_id),
+                action: Some("open_task"),
+            },
+        )
+        .await?;
+        mark_calendar_reminder_sent(db, reminder.event_id)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// 后台轮询到期的订阅并发送日程提醒
+pub fn spawn_calendar_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(SCHEDULER_INITIAL_DELAY).await;
+        loop {
+            if let Some(state) = app.try_state::<AppState>() {
+                match list_due_calendar_subscriptions(&state.db).await {
+                    Ok(subscriptions) => {
+                        for subscription in subscriptions {
+                            match sync_calendar_subscription(&state.db, &subscription).await {
+                                Ok(summary) => tracing::debug!(
+                                    subscription_id = subscription.subscription_id,
+                                    created = summary.created,
+                                    updated = summary.updated,
+                                    "Calendar subscription synced"
+                                ),
+                                Err(err) => tracing::warn!(
+                                    subscription_id = subscription.subscription_id,
+                                    error = %err,
+                                    "Calendar subscription sync failed"
+                                ),
+                            }
+                        }
+                    }
+                    Err(err) => tracing::warn!(error = %err, "Query calendar subscriptions failed"),
+                }
+                if let Err(err) = send_due_reminders(&state.db, &app).await {
+                    tracing::warn!(error = %err, "Send calendar reminders failed");
+                }
+            }
+            tokio::time::sleep(SCHEDULER_INTERVAL).await;
+        }
+    });
+}
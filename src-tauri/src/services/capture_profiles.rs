@@ -0,0 +1,77 @@
+//! 捕获模板：校验模板并在捕获后应用（归入主题、打标签）
+
+use std::collections::HashSet;
+
+use super::CaptureProfile;
+use crate::db::{
+    attach_tag_to_node, get_node_by_id, get_or_create_tag, insert_edge_if_missing, DbPool,
+    EdgeRelationType, NewEdge, NodeType, TagSource,
+};
+
+/// 去掉空白与重复（忽略大小写）的标签
+pub fn normalize_profile_tags(tags: &[String]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    tags.iter()
+        .map(|tag| tag.trim().trim_start_matches('#').trim())
+        .filter(|tag| !tag.is_empty() && seen.insert(tag.to_lowercase()))
+        .map(str::to_string)
+        .collect()
+}
+
+/// 把捕获的资源归入模板的主题并打上模板标签
+///
+/// 主题在模板保存后被删除时跳过归类，不影响捕获本身
+pub async fn apply_capture_profile(
+    db: &DbPool,
+    node_id: i64,
+    profile: &CaptureProfile,
+) -> Result<(), sqlx::Error> {
+    if let Some(topic_id) = profile.topic_id {
+        let topic_exists = match get_node_by_id(db, topic_id).await {
+            Ok(topic) => topic.node_type == NodeType::Topic && !topic.is_deleted,
+            Err(sqlx::Error::RowNotFound) => false,
+            Err(err) => return Err(err),
+        };
+        if topic_exists {
+            insert_edge_if_missing(
+                db,
+                NewEdge {
+                    source_node_id: topic_id,
+                    target_node_id: node_id,
+                    relation_type: EdgeRelationType::Contains,
+                    confidence_score: None,
+                    is_manual: true,
+                },
+            )
+            .await?;
+        } else {
+            tracing::warn!(
+                profile_id = profile.profile_id,
+                topic_id,
+                "Capture profile topic missing"
+            );
+        }
+    }
+
+    for name in &profile.tags {
+        let tag_id = get_or_create_tag(db, name).await?;
+        attach_tag_to_node(db, node_id, tag_id, TagSource::Rule).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_profile_tags() {
+        let tags = vec![
+            " work ".to_string(),
+            "#Work".to_string(),
+            "".to_string(),
+            "reading".to_string(),
+        ];
+        assert_eq!(normalize_profile_tags(&tags), vec!["work", "reading"]);
+    }
+}
@@ -0,0 +1,182 @@
+//! 对话上下文窗口管理
+//!
+//! 发送消息前按 `estimate_tokens` 估算历史长度，超过 [`ChatContextConfig::max_input_tokens`]
+//! 时把较早的消息连同已有的历史摘要交给 AgentService 压缩，写入会话的 summary，并记录摘要覆盖到的
+//! 最后一条消息；之后只发送摘要和其后的消息。压缩失败时不更新摘要，只发送放得下的最近消息。
+
+use std::sync::Arc;
+
+use tokio::sync::Mutex;
+use tracing::warn;
+
+use crate::db::{update_chat_session_compaction, ChatMessageRecord, ChatSessionRecord, DbPool};
+use crate::services::{get_processing_config, AIConfigService, AiServices, ChatContextConfig};
+use crate::utils::estimate_tokens;
+
+/// 历史摘要的字数上限
+const HISTORY_SUMMARY_MAX_CHARS: i32 = 1200;
+
+/// 发送给模型的历史
+#[derive(Debug)]
+pub struct CompactedHistory {
+    /// 较早对话的摘要
+    pub summary: Option<String>,
+    /// 原样发送的第一条消息在消息列表中的位置
+    pub start: usize,
+}
+
+fn message_tokens(message: &ChatMessageRecord) -> i64 {
+    estimate_tokens(&message.user_content)
+        + message
+            .assistant_content
+            .as_deref()
+            .map(estimate_tokens)
+            .unwrap_or(0)
+}
+
+/// 需要并入摘要的消息数（从 tokens 开头算起），0 表示不需要压缩
+///
+/// 超出上限时从最新的消息往前保留：至少 min_recent 条，其余在不超过上限一半时继续保留，
+/// 给新的摘要留出空间
+pub fn messages_to_compact(
+    tokens: &[i64],
+    summary_tokens: i64,
+    max_input_tokens: i64,
+    min_recent: usize,
+) -> usize {
+    let total = summary_tokens + tokens.iter().sum::<i64>();
+    if total <= max_input_tokens {
+        return 0;
+    }
+    let budget = max_input_tokens / 2;
+    let mut kept = 0;
+    let mut kept_tokens = 0;
+    for &count in tokens.iter().rev() {
+        if kept >= min_recent && kept_tokens + count > budget {
+            break;
+        }
+        kept += 1;
+        kept_tokens += count;
+    }
+    tokens.len() - kept
+}
+
+fn build_transcript(messages: &[ChatMessageRecord]) -> String {
+    let mut lines = Vec::with_capacity(messages.len() * 2);
+    for message in messages {
+        if !message.user_content.trim().is_empty() {
+            lines.push(format!("用户：{}", message.user_content.trim()));
+        }
+        if let Some(answer) = message
+            .assistant_content
+            .as_deref()
+            .filter(|answer| !answer.trim().is_empty())
+        {
+            lines.push(format!("助手：{}", answer.trim()));
+        }
+    }
+    lines.join("\n")
+}
+
+/// 必要时压缩较早的消息，返回需要发送的摘要与消息起点；messages 需按时间升序
+pub async fn compact_chat_history(
+    db: &DbPool,
+    ai: &AiServices,
+    ai_config: &Arc<Mutex<AIConfigService>>,
+    session: &ChatSessionRecord,
+    messages: &[ChatMessageRecord],
+    config: ChatContextConfig,
+) -> CompactedHistory {
+    let (summary, start) = match session.compacted_through_message_id {
+        Some(through) => (
+            session
+                .summary
+                .clone()
+                .filter(|summary| !summary.is_empty()),
+            messages
+                .iter()
+                .position(|message| message.message_id > through)
+                .unwrap_or(messages.len()),
+        ),
+        None => (None, 0),
+    };
+
+    let recent = &messages[start..];
+    let tokens: Vec<i64> = recent.iter().map(message_tokens).collect();
+    let summary_tokens = summary.as_deref().map(estimate_tokens).unwrap_or(0);
+    let count = messages_to_compact(
+        &tokens,
+        summary_tokens,
+        i64::from(config.max_input_tokens),
+        config.min_recent_messages as usize,
+    );
+    if count == 0 {
+        return CompactedHistory { summary, start };
+    }
+
+    let compacted = &recent[..count];
+    let through = compacted[count - 1].message_id;
+    let result = match get_processing_config(ai_config).await {
+        Ok((provider, model, _mode, provider_config)) => {
+            ai.agent
+                .summarize_chat_history(
+                    &provider,
+                    &model,
+                    &provider_config,
+                    summary.as_deref(),
+                    &build_transcript(compacted),
+                    HISTORY_SUMMARY_MAX_CHARS,
+                )
+                .await
+        }
+        Err(err) => Err(err),
+    };
+
+    match result {
+        Ok(new_summary) => {
+            if let Err(err) =
+                update_chat_session_compaction(db, session.session_id, &new_summary, through).await
+            {
+                warn!(session_id = session.session_id, error = %err, "Save chat history summary failed");
+            }
+            CompactedHistory {
+                summary: Some(new_summary),
+                start: start + count,
+            }
+        }
+        Err(err) => {
+            warn!(
+                session_id = session.session_id,
+                error = %err,
+                dropped = count,
+                "Chat history compaction failed, sending recent messages only"
+            );
+            CompactedHistory {
+                summary,
+                start: start + count,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_messages_to_compact_within_limit() {
+        assert_eq!(messages_to_compact(&[100, 100, 100], 0, 1000, 2), 0);
+        assert_eq!(messages_to_compact(&[100, 100, 100], 700, 1000, 2), 0);
+        assert_eq!(messages_to_compact(&[], 0, 1000, 2), 0);
+    }
+
+    #[test]
+    fn test_messages_to_compact_keeps_recent_half() {
+        // 最近的消息在不超过 500 时保留：300 + 200
+        assert_eq!(messages_to_compact(&[400, 400, 300, 200], 0, 1000, 1), 2);
+        // 至少保留 min_recent 条，即使超过一半上限
+        assert_eq!(messages_to_compact(&[400, 400, 600, 600], 0, 1000, 2), 2);
+        // 已有摘要计入总量
+        assert_eq!(messages_to_compact(&[300, 300, 300], 200, 1000, 1), 2);
+    }
+}
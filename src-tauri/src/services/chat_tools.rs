@@ -0,0 +1,498 @@
+//! 聊天中可供模型调用的工具
+//!
+//! - `search_vault`: 在知识库中做混合搜索
+//! - `fetch_url`: 抓取网页正文，只允许用户在对话中输入过的链接，
+//!   且每一跳（含重定向）都拒绝本机、内网、链路本地地址
+//! - `propose_rename_topic` / `propose_move_resource` / `propose_due_date` /
+//!   `propose_create_task`: 提出修改，进入待确认队列，用户批准后才执行
+//!
+//! 工具失败时返回 `{"error": ...}`，由模型自行决定如何继续。
+
+use std::collections::HashSet;
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use reqwest::{redirect, Client, Url};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::db::{get_node_by_id, list_search_excluded_node_ids, DbPool};
+use crate::services::{
+    propose_change, AiServices, ProposedChange, ProposedTask, ToolCall, ToolExecutor, ToolSpec,
+};
+use crate::utils::html_to_text;
+
+const SEARCH_DEFAULT_LIMIT: u64 = 5;
+const SEARCH_MAX_LIMIT: u64 = 10;
+const FETCH_TIMEOUT: Duration = Duration::from_secs(15);
+/// 下载网页的最大字节数
+const FETCH_MAX_BYTES: usize = 2 * 1024 * 1024;
+/// 返回给模型的网页正文最大字符数
+const FETCH_MAX_CHARS: usize = 8000;
+/// 最多跟随的重定向次数
+const FETCH_MAX_REDIRECTS: usize = 5;
+
+pub struct VaultTools<'a> {
+    db: &'a DbPool,
+    ai: &'a AiServices,
+    /// 提出的修改关联到该会话
+    session_id: i64,
+    /// 用户在对话中输入过的链接，`fetch_url` 只能抓取这些
+    allowed_urls: HashSet<String>,
+}
+
+impl<'a> VaultTools<'a> {
+    /// `user_messages` 为用户在该会话中发送的消息，从中提取允许抓取的链接
+    pub fn new<'m>(
+        db: &'a DbPool,
+        ai: &'a AiServices,
+        session_id: i64,
+        user_messages: impl IntoIterator<Item = &'m str>,
+    ) -> Self {
+        let allowed_urls = user_messages.into_iter().flat_map(extract_urls).collect();
+        Self {
+            db,
+            ai,
+            session_id,
+            allowed_urls,
+        }
+    }
+
+    async fn search_vault(&self, args: SearchVaultArgs) -> Result<Value, String> {
+        let query = args.query.trim();
+        if query.is_empty() {
+            return Err("query is empty".to_string());
+        }
+        let limit = args
+            .limit
+            .unwrap_or(SEARCH_DEFAULT_LIMIT)
+            .clamp(1, SEARCH_MAX_LIMIT);
+
+        let excluded = list_search_excluded_node_ids(self.db, &[], &[])
+            .await
+            .map_err(|e| e.to_string())?;
+        let hits = self
+            .ai
+            .search
+            .search_hybrid(query, "content", None, &excluded, limit * 2)
+            .await?;
+
+        let mut seen = HashSet::new();
+        let mut results = Vec::new();
+        for hit in hits {
+            if results.len() as u64 >= limit {
+                break;
+            }
+            if !seen.insert(hit.node_id) {
+                continue;
+            }
+            let Ok(node) = get_node_by_id(self.db, hit.node_id).await else {
+                continue;
+            };
+            if node.is_deleted {
+                continue;
+            }
+            results.push(json!({
+                "node_id": node.node_id,
+                "node_type": node.node_type,
+                "title": node.title,
+                "summary": node.summary,
+                "excerpt": hit.chunk_text,
+                "score": hit.score,
+            }));
+        }
+        Ok(json!({ "results": results }))
+    }
+
+    async fn fetch_url(&self, args: FetchUrlArgs) -> Result<Value, String> {
+        let url = Url::parse(args.url.trim()).map_err(|e| format!("invalid url: {e}"))?;
+        if !self.allowed_urls.contains(url.as_str()) {
+            return Err("only urls the user typed in this conversation can be fetched".to_string());
+        }
+
+        // 自行跟随重定向：每一跳都重新解析并校验地址，并把连接固定到校验过的地址上
+        let mut url = url;
+        let mut redirects = 0;
+        let response = loop {
+            let client = public_client(&url).await?;
+            let response = client
+                .get(url.clone())
+                .send()
+                .await
+                .map_err(|e| format!("request failed: {e}"))?;
+            if !response.status().is_redirection() {
+                break response;
+            }
+            if redirects >= FETCH_MAX_REDIRECTS {
+                return Err("too many redirects".to_string());
+            }
+            redirects += 1;
+            let location = response
+                .headers()
+                .get(reqwest::header::LOCATION)
+                .and_then(|value| value.to_str().ok())
+                .ok_or_else(|| format!("request failed: {}", response.status()))?;
+            url = url
+                .join(location)
+                .map_err(|e| format!("invalid redirect: {e}"))?;
+        };
+        if !response.status().is_success() {
+            return Err(format!("request failed: {}", response.status()));
+        }
+        let is_html = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_none_or(|value| value.contains("html"));
+
+        let mut body = Vec::new();
+        let mut stream = response.bytes_stream();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|e| format!("read failed: {e}"))?;
+            body.extend_from_slice(&chunk);
+            if body.len() >= FETCH_MAX_BYTES {
+                body.truncate(FETCH_MAX_BYTES);
+                break;
+            }
+        }
+
+        let raw = String::from_utf8_lossy(&body);
+        let text = if is_html {
+            html_to_text(&raw)
+        } else {
+            raw.into_owned()
+        };
+        let truncated = text.chars().count() > FETCH_MAX_CHARS;
+        let content: String = text.chars().take(FETCH_MAX_CHARS).collect();
+        Ok(json!({ "url": url.as_str(), "content": content, "truncated": truncated }))
+    }
+
+    async fn propose(&self, change: ProposedChange, reason: Option<&str>) -> Result<Value, String> {
+        let change_id = propose_change(self.db, change, reason, Some(self.session_id))
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(json!({
+            "change_id": change_id,
+            "status": "pending_approval",
+            "note": "The change is queued; it takes effect only after the user approves it.",
+        }))
+    }
+}
+
+impl ToolExecutor for VaultTools<'_> {
+    fn specs(&self) -> Vec<ToolSpec> {
+        vec![
+            ToolSpec {
+                name: "search_vault",
+                description: "Search the user's knowledge base (resources, topics, tasks) \
+                              and return the most relevant nodes with excerpts.",
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "query": { "type": "string", "description": "Search query" },
+                        "limit": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "maximum": SEARCH_MAX_LIMIT,
+                            "description": "Max results, default 5"
+                        }
+                    },
+                    "required": ["query"]
+                }),
+            },
+            ToolSpec {
+                name: "fetch_url",
+                description: "Fetch a web page the user linked in this conversation and return \
+                              its text content (truncated). Other URLs are refused.",
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "url": { "type": "string", "description": "http(s) URL" }
+                    },
+                    "required": ["url"]
+                }),
+            },
+            ToolSpec {
+                name: "propose_rename_topic",
+                description: "Propose renaming a topic. The change is queued for the user's \
+                              approval and is not applied immediately.",
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "topic_id": { "type": "integer" },
+                        "title": { "type": "string", "description": "New topic title" },
+                        "reason": { "type": "string", "description": "Why, shown to the user" }
+                    },
+                    "required": ["topic_id", "title"]
+                }),
+            },
+            ToolSpec {
+                name: "propose_move_resource",
+                description: "Propose moving a resource into another topic (removing it from \
+                              its current topics). Queued for the user's approval.",
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "resource_id": { "type": "integer" },
+                        "topic_id": { "type": "integer", "description": "Target topic" },
+                        "reason": { "type": "string", "description": "Why, shown to the user" }
+                    },
+                    "required": ["resource_id", "topic_id"]
+                }),
+            },
+            ToolSpec {
+                name: "propose_due_date",
+                description: "Propose setting or clearing a task's due date. Queued for the \
+                              user's approval.",
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "task_id": { "type": "integer" },
+                        "due_date": {
+                            "type": "string",
+                            "description": "YYYY-MM-DD; omit to clear the due date"
+                        },
+                        "reason": { "type": "string", "description": "Why, shown to the user" }
+                    },
+                    "required": ["task_id"]
+                }),
+            },
+            ToolSpec {
+                name: "propose_create_task",
+                description: "Propose creating a task in the user's task list. Queued for the \
+                              user's approval.",
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "title": { "type": "string" },
+                        "due_date": { "type": "string", "description": "YYYY-MM-DD" },
+                        "note": { "type": "string", "description": "Task description" },
+                        "reason": { "type": "string", "description": "Why, shown to the user" }
+                    },
+                    "required": ["title"]
+                }),
+            },
+        ]
+    }
+
+    async fn execute(&self, call: &ToolCall) -> Value {
+        let result = match call.name.as_str() {
+            "search_vault" => match parse_args(&call.args) {
+                Ok(args) => self.search_vault(args).await,
+                Err(err) => Err(err),
+            },
+            "fetch_url" => match parse_args(&call.args) {
+                Ok(args) => self.fetch_url(args).await,
+                Err(err) => Err(err),
+            },
+            "propose_rename_topic" => match parse_args::<ProposeRenameTopicArgs>(&call.args) {
+                Ok(args) => {
+                    let change = ProposedChange::RenameTopic {
+                        topic_id: args.topic_id,
+                        title: args.title,
+                    };
+                    self.propose(change, args.reason.as_deref()).await
+                }
+                Err(err) => Err(err),
+            },
+            "propose_move_resource" => match parse_args::<ProposeMoveResourceArgs>(&call.args) {
+                Ok(args) => {
+                    let change = ProposedChange::MoveResource {
+                        resource_id: args.resource_id,
+                        topic_id: args.topic_id,
+                    };
+                    self.propose(change, args.reason.as_deref()).await
+                }
+                Err(err) => Err(err),
+            },
+            "propose_due_date" => match parse_args::<ProposeDueDateArgs>(&call.args) {
+                Ok(args) => {
+                    let change = ProposedChange::SetDueDate {
+                        task_id: args.task_id,
+                        due_date: args.due_date,
+                    };
+                    self.propose(change, args.reason.as_deref()).await
+                }
+                Err(err) => Err(err),
+            },
+            "propose_create_task" => match parse_args::<ProposeCreateTaskArgs>(&call.args) {
+                Ok(args) => {
+                    let change = ProposedChange::CreateTask(ProposedTask {
+                        title: args.title,
+                        due_date: args.due_date,
+                        note: args.note,
+                    });
+                    self.propose(change, args.reason.as_deref()).await
+                }
+                Err(err) => Err(err),
+            },
+            other => Err(format!("unknown tool: {other}")),
+        };
+
+        tracing::debug!(tool = %call.name, ok = result.is_ok(), "Chat tool executed");
+        result.unwrap_or_else(|error| json!({ "error": error }))
+    }
+}
+
+/// 校验 url 的主机只解析到公网地址，返回把该主机固定到这些地址、不自动重定向的客户端
+async fn public_client(url: &Url) -> Result<Client, String> {
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err("only http and https urls are supported".to_string());
+    }
+    let host = url.host_str().ok_or("url has no host")?;
+    let port = url.port_or_known_default().ok_or("url has no port")?;
+    // IPv6 字面量在 url 中带方括号
+    let lookup_host = host.trim_start_matches('[').trim_end_matches(']');
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((lookup_host, port))
+        .await
+        .map_err(|e| format!("dns lookup failed: {e}"))?
+        .collect();
+    if addrs.is_empty() {
+        return Err(format!("dns lookup failed: {host}"));
+    }
+    if let Some(addr) = addrs.iter().find(|addr| !is_public_ip(addr.ip())) {
+        return Err(format!("address not allowed: {}", addr.ip()));
+    }
+    Client::builder()
+        .timeout(FETCH_TIMEOUT)
+        .redirect(redirect::Policy::none())
+        .resolve_to_addrs(host, &addrs)
+        .build()
+        .map_err(|e| format!("request failed: {e}"))
+}
+
+/// 拒绝本机、内网、链路本地、未指定等非公网地址
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                // 100.64.0.0/10 运营商级 NAT
+                || (a == 100 && (b & 0xc0) == 64))
+        }
+        IpAddr::V6(ip) => {
+            if let Some(v4) = ip.to_ipv4_mapped() {
+                return is_public_ip(IpAddr::V4(v4));
+            }
+            let first = ip.segments()[0];
+            !(ip.is_loopback()
+                || ip.is_unspecified()
+                // fc00::/7 唯一本地地址
+                || (first & 0xfe00) == 0xfc00
+                // fe80::/10 链路本地地址
+                || (first & 0xffc0) == 0xfe80)
+        }
+    }
+}
+
+/// 提取文本中的 http(s) 链接（去掉句尾标点），按 `Url` 规范化
+fn extract_urls(text: &str) -> Vec<String> {
+    const TRAILING: &[char] = &[
+        '.', ',', ';', ':', '!', '?', ')', ']', '}', '>', '"', '\'', '。', '，', '；', '：', '！',
+        '？', '）', '」', '》',
+    ];
+    text.split(|c: char| c.is_whitespace() || matches!(c, '<' | '"' | '(' | '（'))
+        .filter(|token| token.starts_with("http://") || token.starts_with("https://"))
+        .filter_map(|token| Url::parse(token.trim_end_matches(TRAILING)).ok())
+        .map(String::from)
+        .collect()
+}
+
+fn parse_args<T: for<'de> Deserialize<'de>>(args: &Value) -> Result<T, String> {
+    let args = if args.is_null() {
+        json!({})
+    } else {
+        args.clone()
+    };
+    serde_json::from_value(args).map_err(|e| format!("invalid arguments: {e}"))
+}
+
+#[derive(Deserialize)]
+struct SearchVaultArgs {
+    query: String,
+    limit: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct FetchUrlArgs {
+    url: String,
+}
+
+#[derive(Deserialize)]
+struct ProposeRenameTopicArgs {
+    topic_id: i64,
+    title: String,
+    reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ProposeMoveResourceArgs {
+    resource_id: i64,
+    topic_id: i64,
+    reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ProposeDueDateArgs {
+    task_id: i64,
+    due_date: Option<String>,
+    reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ProposeCreateTaskArgs {
+    title: String,
+    due_date: Option<String>,
+    note: Option<String>,
+    reason: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_urls() {
+        let urls =
+            extract_urls("see https://example.com/a?b=1, and (http://Example.org). not ftp://x.y");
+        assert_eq!(
+            urls,
+            vec![
+                "https://example.com/a?b=1".to_string(),
+                "http://example.org/".to_string(),
+            ]
+        );
+        assert_eq!(
+            extract_urls("看看 https://z.cn。"),
+            vec!["https://z.cn/".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_is_public_ip() {
+        for blocked in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "0.0.0.0",
+            "100.64.0.1",
+            "::1",
+            "::",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+        ] {
+            assert!(!is_public_ip(blocked.parse().unwrap()), "{blocked}");
+        }
+        for allowed in ["93.184.216.34", "2606:2800:220:1::1"] {
+            assert!(is_public_ip(allowed.parse().unwrap()), "{allowed}");
+        }
+    }
+}
@@ -0,0 +1,311 @@
+//! 批量处理前的成本估算
+//!
+//! 只做粗略估算：token 数按字符启发式计算，价格表按模型前缀匹配，
+//! 嵌入耗时按本地 CPU 上的经验值计算。
+
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::db::{get_node_by_id, DbPool, NodeType, ResourceSubtype};
+use crate::services::{
+    parser::{parse_archive_file, parse_email_file, parse_notebook_file},
+    AIConfigData,
+};
+use crate::utils::{estimate_tokens, get_extension, parse_file_type_from_extension};
+
+/// 摘要 prompt 自身的 token 开销
+const SUMMARY_PROMPT_TOKENS: i64 = 400;
+/// 主题分类 prompt 的 token 开销（含候选主题列表）
+const CLASSIFY_PROMPT_TOKENS: i64 = 600;
+/// 摘要 + 分类输出 token
+const OUTPUT_TOKENS_PER_RESOURCE: i64 = 300;
+/// Gemini 对单张图片 / 单页 PDF 的计费 token
+const TOKENS_PER_IMAGE: i64 = 258;
+/// 无法解析 PDF 时按文件大小估算：每页约 50KB
+const PDF_BYTES_PER_PAGE: u64 = 50 * 1024;
+/// Gemini 音频按秒计费的 token
+const TOKENS_PER_AUDIO_SECOND: i64 = 32;
+/// 按 128kbps 压缩音频估算时长
+const AUDIO_BYTES_PER_SECOND: u64 = 16 * 1024;
+/// 转写文本每秒约 4 token
+const TRANSCRIPT_TOKENS_PER_SECOND: i64 = 4;
+/// 本地嵌入模型单个 chunk 的耗时（毫秒）
+const EMBED_MS_PER_TEXT_CHUNK: f64 = 40.0;
+const EMBED_MS_PER_IMAGE: f64 = 150.0;
+
+/// 模型价格（美元 / 百万 token），按前缀匹配，越具体越靠前
+const MODEL_PRICING: &[(&str, f64, f64)] = &[
+    ("gemini-2.5-flash-lite", 0.10, 0.40),
+    ("gemini-2.5-flash", 0.30, 2.50),
+    ("gemini-2.5-pro", 1.25, 10.00),
+    ("gemini-2.0-flash-lite", 0.075, 0.30),
+    ("gemini-2.0-flash", 0.10, 0.40),
+    ("gemini-3-flash", 0.50, 3.00),
+    ("gemini-3-pro", 2.00, 12.00),
+];
+
+/// 估算目标
+#[derive(Debug)]
+pub enum CostEstimateTarget {
+    Node(i64),
+    Path(String),
+}
+
+/// 单个条目的估算结果
+#[derive(Debug, Serialize)]
+pub struct CostEstimateItem {
+    pub node_id: Option<i64>,
+    pub path: Option<String>,
+    pub resource_subtype: Option<ResourceSubtype>,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub embedding_chunks: i64,
+    pub skipped_reason: Option<String>,
+}
+
+/// 汇总估算结果
+#[derive(Debug, Serialize)]
+pub struct ProcessingCostEstimate {
+    pub provider: Option<String>,
+    pub model: Option<String>,
+    pub total_input_tokens: i64,
+    pub total_output_tokens: i64,
+    /// 未知模型时为 None
+    pub estimated_llm_cost_usd: Option<f64>,
+    pub total_embedding_chunks: i64,
+    pub estimated_embedding_seconds: f64,
+    pub items: Vec<CostEstimateItem>,
+}
+
+/// 按模型前缀查询价格（输入, 输出）
+pub fn model_pricing(model: &str) -> Option<(f64, f64)> {
+    let model = model.trim().to_lowercase();
+    let model = model.strip_prefix("models/").unwrap_or(&model);
+    MODEL_PRICING
+        .iter()
+        .find(|(prefix, _, _)| model.starts_with(prefix))
+        .map(|(_, input, output)| (*input, *output))
+}
+
+fn embedding_chunks_for(tokens: i64, chunk_size: usize, chunk_overlap: usize) -> i64 {
+    if tokens <= 0 {
+        return 0;
+    }
+    let step = chunk_size.saturating_sub(chunk_overlap).max(1) as i64;
+    (tokens + step - 1) / step
+}
+
+pub async fn build_processing_cost_estimate(
+    db: &DbPool,
+    config: &AIConfigData,
+    targets: &[CostEstimateTarget],
+) -> Result<ProcessingCostEstimate, String> {
+    let chunk_size = config.vector_config.chunk_size;
+    let chunk_overlap = config.vector_config.chunk_overlap;
+
+    let mut items = Vec::with_capacity(targets.len());
+    let mut image_count = 0i64;
+
+    for target in targets {
+        let (node_id, path, subtype, content_tokens, file_tokens, skipped_reason) = match target {
+            CostEstimateTarget::Node(node_id) => match get_node_by_id(db, *node_id).await {
+                Ok(node) if node.node_type != NodeType::Resource || node.is_deleted => (
+                    Some(*node_id),
+                    None,
+                    node.resource_subtype,
+                    0,
+                    0,
+                    Some("not an active resource".to_string()),
+                ),
+                Ok(node) => {
+                    // 解析时已写入正文 token 数，旧数据回退到现场估算
+                    let content_tokens = node.token_count.unwrap_or_else(|| {
+                        estimate_tokens(node.file_content.as_deref().unwrap_or(""))
+                    });
+                    let note = node.user_note.as_deref().unwrap_or("");
+                    let file_tokens = match node.resource_subtype {
+                        Some(ResourceSubtype::Image) => TOKENS_PER_IMAGE,
+                        _ => 0,
+                    };
+                    (
+                        Some(*node_id),
+                        None,
+                        node.resource_subtype,
+                        content_tokens + estimate_tokens(note),
+                        file_tokens,
+                        None,
+                    )
+                }
+                Err(sqlx::Error::RowNotFound) => (
+                    Some(*node_id),
+                    None,
+                    None,
+                    0,
+                    0,
+                    Some("node not found".to_string()),
+                ),
+                Err(err) => return Err(err.to_string()),
+            },
+            CostEstimateTarget::Path(path) => {
+                let (subtype, content_tokens, file_tokens, reason) = estimate_path(path);
+                (
+                    None,
+                    Some(path.clone()),
+                    subtype,
+                    content_tokens,
+                    file_tokens,
+                    reason,
+                )
+            }
+        };
+
+        let item = if skipped_reason.is_some() {
+            CostEstimateItem {
+                node_id,
+                path,
+                resource_subtype: subtype,
+                input_tokens: 0,
+                output_tokens: 0,
+                embedding_chunks: 0,
+                skipped_reason,
+            }
+        } else {
+            if subtype == Some(ResourceSubtype::Image) {
+                image_count += 1;
+            }
+            CostEstimateItem {
+                node_id,
+                path,
+                resource_subtype: subtype,
+                input_tokens: content_tokens
+                    + file_tokens
+                    + SUMMARY_PROMPT_TOKENS
+                    + CLASSIFY_PROMPT_TOKENS,
+                output_tokens: OUTPUT_TOKENS_PER_RESOURCE,
+                // +1: 摘要向量
+                embedding_chunks: embedding_chunks_for(content_tokens, chunk_size, chunk_overlap)
+                    + 1,
+                skipped_reason: None,
+            }
+        };
+        items.push(item);
+    }
+
+    let total_input_tokens: i64 = items.iter().map(|item| item.input_tokens).sum();
+    let total_output_tokens: i64 = items.iter().map(|item| item.output_tokens).sum();
+    let total_embedding_chunks: i64 = items.iter().map(|item| item.embedding_chunks).sum();
+
+    let estimated_llm_cost_usd = config
+        .processing_model
+        .as_deref()
+        .and_then(model_pricing)
+        .map(|(input_price, output_price)| {
+            (total_input_tokens as f64 * input_price + total_output_tokens as f64 * output_price)
+                / 1_000_000.0
+        });
+
+    let embedding_ms = total_embedding_chunks as f64 * EMBED_MS_PER_TEXT_CHUNK
+        + image_count as f64 * EMBED_MS_PER_IMAGE;
+
+    Ok(ProcessingCostEstimate {
+        provider: config.processing_provider.clone(),
+        model: config.processing_model.clone(),
+        total_input_tokens,
+        total_output_tokens,
+        estimated_llm_cost_usd,
+        total_embedding_chunks,
+        estimated_embedding_seconds: embedding_ms / 1000.0,
+        items,
+    })
+}
+
+/// 估算尚未导入的文件：返回 (subtype, 内容 token, 文件附加 token, 跳过原因)
+fn estimate_path(path: &str) -> (Option<ResourceSubtype>, i64, i64, Option<String>) {
+    let metadata = match fs::metadata(Path::new(path)) {
+        Ok(metadata) if metadata.is_file() => metadata,
+        Ok(_) => return (None, 0, 0, Some("not a file".to_string())),
+        Err(err) => return (None, 0, 0, Some(err.to_string())),
+    };
+    let subtype = parse_file_type_from_extension(get_extension(path).as_deref());
+
+    match subtype {
+        ResourceSubtype::Text => {
+            let tokens = fs::read_to_string(path)
+                .map(|text| estimate_tokens(&text))
+                .unwrap_or_else(|_| (metadata.len() / 4) as i64);
+            (Some(subtype), tokens, 0, None)
+        }
+        ResourceSubtype::Pdf => {
+            let pages = (metadata.len() / PDF_BYTES_PER_PAGE).max(1) as i64;
+            // 每页约 500 token 文本 + Gemini 按页计费
+            (Some(subtype), pages * 500, pages * TOKENS_PER_IMAGE, None)
+        }
+        ResourceSubtype::Image => (Some(subtype), 0, TOKENS_PER_IMAGE, None),
+        ResourceSubtype::Notebook => {
+            // 按渲染后的单元格文本估算（输出已截断，图片不上传）
+            let tokens = parse_notebook_file(path)
+                .map(|text| estimate_tokens(&text))
+                .unwrap_or_else(|_| (metadata.len() / 4) as i64);
+            (Some(subtype), tokens, 0, None)
+        }
+        ResourceSubtype::Email => {
+            // 只估算邮件正文，附件拆为独立资源后另行处理
+            let tokens = parse_email_file(path)
+                .map(|email| estimate_tokens(&email.to_text()))
+                .unwrap_or_else(|_| (metadata.len() / 4) as i64);
+            (Some(subtype), tokens, 0, None)
+        }
+        ResourceSubtype::Archive => {
+            // 只估算条目清单，展开后的子资源另行处理
+            let tokens = parse_archive_file(path)
+                .map(|listing| estimate_tokens(&listing))
+                .unwrap_or(0);
+            (Some(subtype), tokens, 0, None)
+        }
+        ResourceSubtype::Audio => {
+            // 按未在本地转写估算：上传音频转写，再对转写文本摘要
+            let seconds = (metadata.len() / AUDIO_BYTES_PER_SECOND).max(1) as i64;
+            (
+                Some(subtype),
+                seconds * TRANSCRIPT_TOKENS_PER_SECOND,
+                seconds * TOKENS_PER_AUDIO_SECOND,
+                None,
+            )
+        }
+        _ => (
+            Some(subtype),
+            0,
+            0,
+            Some("unsupported file type".to_string()),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcd"), 1);
+        assert_eq!(estimate_tokens("你好"), 2);
+        assert_eq!(estimate_tokens("你好 abcd"), 4);
+    }
+
+    #[test]
+    fn test_model_pricing() {
+        assert_eq!(model_pricing("gemini-2.5-flash-lite"), Some((0.10, 0.40)));
+        assert_eq!(model_pricing("models/gemini-2.5-flash"), Some((0.30, 2.50)));
+        assert_eq!(model_pricing("unknown-model"), None);
+    }
+
+    #[test]
+    fn test_embedding_chunks_for() {
+        assert_eq!(embedding_chunks_for(0, 512, 50), 0);
+        assert_eq!(embedding_chunks_for(462, 512, 50), 1);
+        assert_eq!(embedding_chunks_for(463, 512, 50), 2);
+    }
+}
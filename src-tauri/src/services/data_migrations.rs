@@ -0,0 +1,177 @@
+//! 后台数据迁移
+//!
+//! 与 sqlx 的表结构迁移不同，这里的数据回填在启动后于后台分批执行，不阻塞启动：
+//! 每批处理完写入 cursor 与 processed 并发送 `data-migration-progress`，
+//! 退出或失败后下次启动从 cursor 继续。进度写入前中断会重跑该批，因此每批必须幂等。
+//!
+//! 新增迁移：在 [`DataMigration`] 中加一个分支并放进 `ALL`，名称发布后不可再改。
+
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager};
+
+use super::{emit_event, DataMigrationProgressPayload};
+use crate::app_state::AppState;
+use crate::db::{
+    backfill_content_stats, backfill_file_hashes, backfill_node_links,
+    count_nodes_missing_content_stats, count_nodes_with_content, count_text_resources_missing_hash,
+    finish_data_migration, get_or_create_data_migration, list_nodes_missing_content_stats,
+    list_nodes_with_content, list_text_resources_missing_hash, mark_data_migration_running,
+    update_data_migration_progress, DataMigrationStatus, DbPool,
+};
+use crate::utils::compute_sha256;
+
+const INITIAL_DELAY: Duration = Duration::from_secs(15);
+const BATCH_SIZE: i64 = 200;
+/// 批次之间让出数据库，避免拖慢前台操作
+const BATCH_PAUSE: Duration = Duration::from_millis(50);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataMigration {
+    /// 早期文本资源没有 file_hash，捕获去重时找不到它们
+    TextResourceHashes,
+    /// 正文统计（字数、token 数、语言、阅读时长）是后加的，已有正文需要补算
+    ContentStats,
+    /// 反向链接索引是后加的，已有正文中的引用需要补建
+    NodeLinks,
+}
+
+impl DataMigration {
+    /// 按顺序执行
+    pub const ALL: &'static [DataMigration] = &[
+        DataMigration::TextResourceHashes,
+        DataMigration::ContentStats,
+        DataMigration::NodeLinks,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::TextResourceHashes => "text_resource_hashes",
+            Self::ContentStats => "content_stats",
+            Self::NodeLinks => "node_links",
+        }
+    }
+
+    /// cursor 之后还需处理的条数（用于进度估算）
+    async fn remaining(self, db: &DbPool, cursor: i64) -> Result<i64, sqlx::Error> {
+        match self {
+            Self::TextResourceHashes => count_text_resources_missing_hash(db, cursor).await,
+            Self::ContentStats => count_nodes_missing_content_stats(db, cursor).await,
+            Self::NodeLinks => count_nodes_with_content(db, cursor).await,
+        }
+    }
+
+    /// 处理 cursor 之后的一批，返回新的 cursor 与本批条数；条数为 0 表示已完成
+    async fn run_batch(self, db: &DbPool, cursor: i64) -> Result<(i64, i64), sqlx::Error> {
+        match self {
+            Self::TextResourceHashes => {
+                let rows = list_text_resources_missing_hash(db, cursor, BATCH_SIZE).await?;
+                let Some(&(last_id, _)) = rows.last() else {
+                    return Ok((cursor, 0));
+                };
+                let hashes: Vec<(i64, String)> = rows
+                    .iter()
+                    .map(|(node_id, content)| (*node_id, compute_sha256(content.as_bytes())))
+                    .collect();
+                backfill_file_hashes(db, &hashes).await?;
+                Ok((last_id, rows.len() as i64))
+            }
+            Self::ContentStats => {
+                let rows = list_nodes_missing_content_stats(db, cursor, BATCH_SIZE).await?;
+                let Some(&(last_id, _)) = rows.last() else {
+                    return Ok((cursor, 0));
+                };
+                backfill_content_stats(db, &rows).await?;
+                Ok((last_id, rows.len() as i64))
+            }
+            Self::NodeLinks => {
+                let rows = list_nodes_with_content(db, cursor, BATCH_SIZE).await?;
+                let Some(&(last_id, _)) = rows.last() else {
+                    return Ok((cursor, 0));
+                };
+                backfill_node_links(db, &rows).await?;
+                Ok((last_id, rows.len() as i64))
+            }
+        }
+    }
+}
+
+/// 启动后在后台依次执行未完成的数据迁移
+pub fn spawn_data_migrations(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(INITIAL_DELAY).await;
+        let Some(state) = app.try_state::<AppState>() else {
+            return;
+        };
+        for &migration in DataMigration::ALL {
+            if let Err(err) = run_data_migration(&state.db, &app, migration).await {
+                let message = err.to_string();
+                tracing::error!(name = migration.name(), error = %message, "Data migration failed");
+                if let Err(err) = finish_data_migration(
+                    &state.db,
+                    migration.name(),
+                    DataMigrationStatus::Failed,
+                    Some(&message),
+                )
+                .await
+                {
+                    tracing::warn!(error = %err, "Record data migration failure failed");
+                }
+                emit_event(
+                    &app,
+                    &DataMigrationProgressPayload {
+                        name: migration.name(),
+                        status: DataMigrationStatus::Failed,
+                        processed: 0,
+                        total: None,
+                        error: Some(message),
+                    },
+                );
+            }
+        }
+    });
+}
+
+async fn run_data_migration(
+    db: &DbPool,
+    app: &AppHandle,
+    migration: DataMigration,
+) -> Result<(), sqlx::Error> {
+    let name = migration.name();
+    let record = get_or_create_data_migration(db, name).await?;
+    if record.status == DataMigrationStatus::Done {
+        return Ok(());
+    }
+
+    let mut cursor = record.cursor;
+    let mut processed = record.processed;
+    let total = processed + migration.remaining(db, cursor).await?;
+    mark_data_migration_running(db, name, total).await?;
+    tracing::info!(name, cursor, processed, total, "Data migration started");
+
+    let progress = |status, processed| DataMigrationProgressPayload {
+        name,
+        status,
+        processed,
+        total: Some(total),
+        error: None,
+    };
+    emit_event(app, &progress(DataMigrationStatus::Running, processed));
+
+    loop {
+        let (next_cursor, count) = migration.run_batch(db, cursor).await?;
+        if count == 0 {
+            break;
+        }
+        cursor = next_cursor;
+        processed += count;
+        update_data_migration_progress(db, name, cursor, processed).await?;
+        emit_event(app, &progress(DataMigrationStatus::Running, processed));
+        tokio::time::sleep(BATCH_PAUSE).await;
+    }
+
+    finish_data_migration(db, name, DataMigrationStatus::Done, None).await?;
+    emit_event(app, &progress(DataMigrationStatus::Done, processed));
+    tracing::info!(name, processed, "Data migration finished");
+    Ok(())
+}
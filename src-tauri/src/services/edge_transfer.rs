@@ -0,0 +1,196 @@
+//! 边的 JSONL 导入 / 导出
+//!
+//! 每行一条 [`EdgeTransferRecord`]，节点以 uuid 引用（node_id 在导出 / 导入后会变化）：
+//! `{"source":"<uuid>","target":"<uuid>","relation_type":"contains","confidence_score":null,"is_manual":true}`
+//!
+//! 导入在一个事务中逐行校验并插入，预演（dry_run）时最后回滚，
+//! 因此预演结果与实际导入一致（包括同一文件内的重复与 contains 环）。
+
+use serde::Serialize;
+
+use crate::db::{
+    contains_creates_cycle, edge_exists, get_node_id_by_uuid, insert_edge_if_missing,
+    list_edge_transfer_records, DbPool, EdgeRelationType, EdgeTransferRecord, NewEdge,
+};
+use crate::utils::parse_relation_type;
+use crate::AppResult;
+
+/// 导入结果中每行的状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EdgeImportStatus {
+    Created,
+    /// 已有相同的边（含已软删除的）
+    Exists,
+    InvalidLine,
+    UnknownRelationType,
+    MissingNode,
+    SelfLoop,
+    /// contains 边会形成环
+    Cycle,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EdgeImportItem {
+    /// 行号，从 1 开始
+    pub line: usize,
+    pub source: Option<String>,
+    pub target: Option<String>,
+    pub relation_type: Option<String>,
+    pub status: EdgeImportStatus,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EdgeImportReport {
+    pub dry_run: bool,
+    pub created: usize,
+    pub skipped: usize,
+    pub items: Vec<EdgeImportItem>,
+}
+
+/// 导出边为 JSONL 文本，返回文本与条数
+pub async fn build_edge_export(
+    db: &DbPool,
+    relation_types: &[EdgeRelationType],
+    manual_only: bool,
+    node_ids: &[i64],
+) -> AppResult<(String, usize)> {
+    let records = list_edge_transfer_records(db, relation_types, manual_only, node_ids).await?;
+    let mut text = String::new();
+    for record in &records {
+        text.push_str(&serde_json::to_string(record).map_err(|e| e.to_string())?);
+        text.push('\n');
+    }
+    Ok((text, records.len()))
+}
+
+/// related_to 是无向边，按 node_id 升序存储
+fn normalize_endpoints(relation_type: EdgeRelationType, source: i64, target: i64) -> (i64, i64) {
+    if relation_type == EdgeRelationType::RelatedTo && source > target {
+        (target, source)
+    } else {
+        (source, target)
+    }
+}
+
+/// 逐行导入 JSONL；空行忽略
+pub async fn import_edges_jsonl(
+    db: &DbPool,
+    text: &str,
+    dry_run: bool,
+) -> Result<EdgeImportReport, sqlx::Error> {
+    let mut tx = db.begin().await?;
+    let mut items = Vec::new();
+
+    for (index, raw) in text.lines().enumerate() {
+        let raw = raw.trim();
+        if raw.is_empty() {
+            continue;
+        }
+        let line = index + 1;
+        let Ok(record) = serde_json::from_str::<EdgeTransferRecord>(raw) else {
+            items.push(EdgeImportItem {
+                line,
+                source: None,
+                target: None,
+                relation_type: None,
+                status: EdgeImportStatus::InvalidLine,
+            });
+            continue;
+        };
+
+        let status = import_edge(&mut tx, &record).await?;
+        items.push(EdgeImportItem {
+            line,
+            source: Some(record.source),
+            target: Some(record.target),
+            relation_type: Some(record.relation_type),
+            status,
+        });
+    }
+
+    if dry_run {
+        tx.rollback().await?;
+    } else {
+        tx.commit().await?;
+    }
+
+    let created = items
+        .iter()
+        .filter(|item| item.status == EdgeImportStatus::Created)
+        .count();
+    tracing::info!(dry_run, created, total = items.len(), "Edges imported");
+    Ok(EdgeImportReport {
+        dry_run,
+        created,
+        skipped: items.len() - created,
+        items,
+    })
+}
+
+async fn import_edge(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    record: &EdgeTransferRecord,
+) -> Result<EdgeImportStatus, sqlx::Error> {
+    let Ok(relation_type) = parse_relation_type(record.relation_type.trim()) else {
+        return Ok(EdgeImportStatus::UnknownRelationType);
+    };
+    let (Some(source), Some(target)) = (
+        get_node_id_by_uuid(&mut **tx, &record.source).await?,
+        get_node_id_by_uuid(&mut **tx, &record.target).await?,
+    ) else {
+        return Ok(EdgeImportStatus::MissingNode);
+    };
+    if source == target {
+        return Ok(EdgeImportStatus::SelfLoop);
+    }
+
+    let (source, target) = normalize_endpoints(relation_type, source, target);
+    if edge_exists(&mut **tx, source, target, relation_type).await? {
+        return Ok(EdgeImportStatus::Exists);
+    }
+    if relation_type == EdgeRelationType::Contains
+        && contains_creates_cycle(&mut **tx, source, target).await?
+    {
+        return Ok(EdgeImportStatus::Cycle);
+    }
+
+    insert_edge_if_missing(
+        &mut **tx,
+        NewEdge {
+            source_node_id: source,
+            target_node_id: target,
+            relation_type,
+            confidence_score: record.confidence_score,
+            is_manual: record.is_manual.unwrap_or(true),
+        },
+    )
+    .await?;
+    Ok(EdgeImportStatus::Created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_endpoints_orders_related_to_only() {
+        assert_eq!(
+            normalize_endpoints(EdgeRelationType::RelatedTo, 9, 3),
+            (3, 9)
+        );
+        assert_eq!(
+            normalize_endpoints(EdgeRelationType::Contains, 9, 3),
+            (9, 3)
+        );
+    }
+
+    #[test]
+    fn test_edge_line_defaults() {
+        let record: EdgeTransferRecord =
+            serde_json::from_str(r#"{"source":"a","target":"b","relation_type":"contains"}"#)
+                .unwrap();
+        assert_eq!(record.confidence_score, None);
+        assert_eq!(record.is_manual, None);
+    }
+}
@@ -0,0 +1,294 @@
+//! 换用新的嵌入模型重建全部向量
+//!
+//! 新模型写入新的 LanceDB 表：已生成过向量的资源按已保存的摘要与正文重新嵌入（不调用 LLM），
+//! 主题重新生成标题向量。全部写完后才保存新的向量配置并切换 AI 服务，期间搜索继续使用旧表；
+//! 中途失败或取消时删除新表，配置不变。重建期间暂停 AI 管道，切换后旧表被删除。
+
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::app_state::AppState;
+use crate::db::{
+    get_node_by_id, list_embedded_resource_ids, list_nodes_by_type, mark_resource_embedding_dirty,
+    NodeType,
+};
+use crate::i18n::MessageCode;
+use crate::services::{
+    emit_event, reembed_resource, AiServices, EmbeddingReindexProgressPayload, EmbeddingService,
+    JobHandle, JobPriority, VectorConfig,
+};
+use crate::{AppError, AppResult};
+
+/// 每批处理的节点数，每批结束发送一次进度
+const REINDEX_BATCH_SIZE: usize = 16;
+
+/// 同一时间只允许一次重建
+static RUNNING: AtomicBool = AtomicBool::new(false);
+
+struct RunningGuard;
+
+impl RunningGuard {
+    fn acquire() -> Option<Self> {
+        RUNNING
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .ok()
+            .map(|_| Self)
+    }
+}
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        RUNNING.store(false, Ordering::Release);
+    }
+}
+
+/// 重建使用的模型；向量维度需与模型输出一致
+#[derive(Debug, Clone, Deserialize)]
+pub struct EmbeddingModelSelection {
+    pub dense_embedding_model: String,
+    pub dense_vector_size: u64,
+    pub clip_text_embedding_model: String,
+    pub clip_text_vector_size: u64,
+    pub image_embedding_model: String,
+    pub image_vector_size: u64,
+}
+
+impl EmbeddingModelSelection {
+    /// 在当前配置上替换模型与表名
+    fn apply(&self, current: &VectorConfig, table_name: String) -> AppResult<VectorConfig> {
+        let models = [
+            ("dense_embedding_model", &self.dense_embedding_model),
+            ("clip_text_embedding_model", &self.clip_text_embedding_model),
+            ("image_embedding_model", &self.image_embedding_model),
+        ];
+        for (field, model) in models {
+            if model.trim().is_empty() {
+                return Err(AppError::coded_with(
+                    MessageCode::FieldEmpty,
+                    "field",
+                    field,
+                ));
+            }
+        }
+        let sizes = [
+            ("dense_vector_size", self.dense_vector_size),
+            ("clip_text_vector_size", self.clip_text_vector_size),
+            ("image_vector_size", self.image_vector_size),
+        ];
+        for (field, size) in sizes {
+            if size == 0 {
+                return Err(AppError::coded_with(
+                    MessageCode::FieldNotPositive,
+                    "field",
+                    field,
+                ));
+            }
+        }
+
+        Ok(VectorConfig {
+            lancedb_table_name: table_name,
+            dense_embedding_model: self.dense_embedding_model.trim().to_string(),
+            dense_vector_size: self.dense_vector_size,
+            clip_text_embedding_model: self.clip_text_embedding_model.trim().to_string(),
+            clip_text_vector_size: self.clip_text_vector_size,
+            image_embedding_model: self.image_embedding_model.trim().to_string(),
+            image_vector_size: self.image_vector_size,
+            ..current.clone()
+        })
+    }
+}
+
+/// 重建结果
+#[derive(Debug, Serialize)]
+pub struct EmbeddingReindexReport {
+    /// 切换后使用的表
+    pub table_name: String,
+    pub resources: usize,
+    pub topics: usize,
+    /// 重新嵌入失败的资源，切换后已重新加入 AI 管道
+    pub failed_node_ids: Vec<i64>,
+}
+
+/// 新表名：去掉当前表名末尾的 `_<时间戳>` 后追加新的时间戳
+pub fn reindex_table_name(current: &str, timestamp: i64) -> String {
+    let base = match current.rsplit_once('_') {
+        Some((base, suffix))
+            if !base.is_empty()
+                && !suffix.is_empty()
+                && suffix.chars().all(|c| c.is_ascii_digit()) =>
+        {
+            base
+        }
+        _ => current,
+    };
+    format!("{base}_{timestamp}")
+}
+
+/// 用新模型重建所有向量并切换到新表
+pub async fn run_embedding_reindex(
+    app: &AppHandle,
+    state: &AppState,
+    models: EmbeddingModelSelection,
+    job: &JobHandle,
+) -> AppResult<EmbeddingReindexReport> {
+    let _guard = RunningGuard::acquire()
+        .ok_or_else(|| AppError::coded(MessageCode::EmbeddingReindexRunning))?;
+    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+
+    let current = state.ai.wait_ready().await?;
+    let old_config = current.embedding.config().clone();
+    let table_name = reindex_table_name(&old_config.lancedb_table_name, Utc::now().timestamp());
+    let mut new_config = models.apply(&old_config, table_name.clone())?;
+    // ANN 参数可能在运行时改过，以保存的配置为准
+    new_config.ann_index = state.ai_config.lock().await.get_vector_config()?.ann_index;
+
+    let embedding = Arc::new(EmbeddingService::new(new_config.clone()).await?);
+    let services = current.with_embedding(embedding);
+
+    let was_paused = state.ai_pipeline.is_paused().await;
+    if !was_paused {
+        state.ai_pipeline.pause().await;
+    }
+    let result = reindex_nodes(app, state, &services, &app_data_dir, &table_name, job).await;
+    if !was_paused {
+        state.ai_pipeline.resume().await;
+    }
+
+    let (resources, topics, failed_node_ids) = match result {
+        Ok(counts) => counts,
+        Err(err) => {
+            remove_table_dir(&new_config);
+            return Err(err);
+        }
+    };
+
+    state.ai_config.lock().await.set_vector_config(new_config)?;
+    state.ai.set_ready(Arc::new(services));
+    state.search_cache.clear();
+    remove_table_dir(&old_config);
+    tracing::info!(
+        from = %old_config.lancedb_table_name,
+        to = %table_name,
+        resources,
+        topics,
+        failed = failed_node_ids.len(),
+        "Embedding reindex finished"
+    );
+
+    for node_id in &failed_node_ids {
+        if let Err(err) = mark_resource_embedding_dirty(&state.db, *node_id).await {
+            tracing::warn!(node_id, error = %err, "Mark reindex failure dirty failed");
+        }
+        if let Err(err) = state
+            .ai_pipeline
+            .enqueue_resource(*node_id, JobPriority::Normal)
+            .await
+        {
+            tracing::warn!(node_id, error = %err, "Enqueue reindex failure failed");
+        }
+    }
+
+    Ok(EmbeddingReindexReport {
+        table_name,
+        resources,
+        topics,
+        failed_node_ids,
+    })
+}
+
+/// 返回 (资源数, 主题数, 失败的资源)；单个资源失败不中断，主题标题向量失败或取消则中断
+async fn reindex_nodes(
+    app: &AppHandle,
+    state: &AppState,
+    services: &AiServices,
+    app_data_dir: &Path,
+    table_name: &str,
+    job: &JobHandle,
+) -> AppResult<(usize, usize, Vec<i64>)> {
+    // 先加载模型，模型名无效时尽早失败
+    services.embedding.warmup_search().await?;
+
+    let resource_ids = list_embedded_resource_ids(&state.db).await?;
+    let topics = list_nodes_by_type(&state.db, NodeType::Topic, false).await?;
+    let total = resource_ids.len() + topics.len();
+    let mut processed = 0;
+    let mut failed = Vec::new();
+
+    for batch in resource_ids.chunks(REINDEX_BATCH_SIZE) {
+        job.check_cancelled()?;
+        for &node_id in batch {
+            let node = match get_node_by_id(&state.db, node_id).await {
+                Ok(node) => node,
+                Err(sqlx::Error::RowNotFound) => continue,
+                Err(err) => return Err(err.into()),
+            };
+            if let Err(err) = reembed_resource(&state.db, services, app_data_dir, &node).await {
+                tracing::warn!(node_id, error = %err, "Reindex resource failed");
+                failed.push(node_id);
+            }
+        }
+        processed += batch.len();
+        emit_progress(app, table_name, processed, total);
+        job.progress(processed, Some(total));
+    }
+
+    for batch in topics.chunks(REINDEX_BATCH_SIZE) {
+        job.check_cancelled()?;
+        for topic in batch {
+            services
+                .embedding
+                .upsert_title_embedding(topic.node_id, &topic.title)
+                .await?;
+        }
+        processed += batch.len();
+        emit_progress(app, table_name, processed, total);
+        job.progress(processed, Some(total));
+    }
+
+    Ok((resource_ids.len(), topics.len(), failed))
+}
+
+fn emit_progress(app: &AppHandle, table_name: &str, processed: usize, total: usize) {
+    emit_event(
+        app,
+        &EmbeddingReindexProgressPayload {
+            table_name: table_name.to_string(),
+            processed,
+            total,
+        },
+    );
+}
+
+/// 删除表目录（与 `vector_stats` 统计磁盘占用的路径一致）；失败只记录
+fn remove_table_dir(config: &VectorConfig) {
+    let table_dir =
+        Path::new(&config.lancedb_path).join(format!("{}.lance", config.lancedb_table_name));
+    if let Err(err) = std::fs::remove_dir_all(&table_dir) {
+        if err.kind() != std::io::ErrorKind::NotFound {
+            tracing::warn!(path = %table_dir.display(), error = %err, "Remove vector table failed");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reindex_table_name_replaces_timestamp() {
+        assert_eq!(
+            reindex_table_name("neuralvault_vectors", 1700000000),
+            "neuralvault_vectors_1700000000"
+        );
+        assert_eq!(
+            reindex_table_name("neuralvault_vectors_1700000000", 1800000000),
+            "neuralvault_vectors_1800000000"
+        );
+        assert_eq!(reindex_table_name("vectors_", 5), "vectors__5");
+    }
+}
@@ -0,0 +1,202 @@
+//! Evernote ENEX 导入
+//!
+//! 解析 .enex 导出文件：每条笔记的标题、ENML 正文、创建/更新时间、标签、来源 URL，
+//! 以及 base64 编码的附件。写入数据库由 `import_enex` 命令完成。
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chrono::NaiveDateTime;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use crate::utils::attachment_extension;
+
+/// ENEX 中的一条笔记
+#[derive(Debug, Default)]
+pub struct EnexNote {
+    pub title: String,
+    /// ENML 正文（HTML 子集）
+    pub content: String,
+    /// `YYYY-MM-DD HH:MM:SS`（UTC）
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub tags: Vec<String>,
+    pub source_url: Option<String>,
+    pub attachments: Vec<EnexAttachment>,
+}
+
+/// 笔记附件
+#[derive(Debug, Default)]
+pub struct EnexAttachment {
+    pub data: Vec<u8>,
+    pub mime: Option<String>,
+    pub file_name: Option<String>,
+}
+
+impl EnexAttachment {
+    /// 附件扩展名：优先用文件名，其次按 MIME 推断
+    pub fn extension(&self) -> Option<String> {
+        attachment_extension(self.file_name.as_deref(), self.mime.as_deref())
+    }
+}
+
+/// 解析 ENEX 文本，错误信息为解析失败的原因
+pub fn parse_enex(xml: &str) -> Result<Vec<EnexNote>, String> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    let mut notes = Vec::new();
+    let mut path: Vec<String> = Vec::new();
+    let mut note: Option<EnexNote> = None;
+    let mut attachment: Option<EnexAttachment> = None;
+    let mut data_base64 = String::new();
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| format!("位置 {}: {e}", reader.buffer_position()))?;
+        match event {
+            Event::Start(start) => {
+                let name = String::from_utf8_lossy(start.local_name().as_ref()).to_string();
+                match name.as_str() {
+                    "note" => note = Some(EnexNote::default()),
+                    "resource" if note.is_some() => {
+                        attachment = Some(EnexAttachment::default());
+                        data_base64.clear();
+                    }
+                    _ => {}
+                }
+                path.push(name);
+            }
+            Event::End(_) => match path.pop().as_deref() {
+                Some("note") => {
+                    if let Some(note) = note.take() {
+                        notes.push(note);
+                    }
+                }
+                Some("resource") => {
+                    if let (Some(note), Some(mut done)) = (note.as_mut(), attachment.take()) {
+                        let compact: String =
+                            data_base64.chars().filter(|c| !c.is_whitespace()).collect();
+                        done.data = BASE64
+                            .decode(compact)
+                            .map_err(|e| format!("附件 base64 解码失败: {e}"))?;
+                        if !done.data.is_empty() {
+                            note.attachments.push(done);
+                        }
+                    }
+                }
+                _ => {}
+            },
+            Event::Text(text) => {
+                let value = text.unescape().map_err(|e| e.to_string())?;
+                apply_text(&path, &mut note, &mut attachment, &mut data_base64, &value);
+            }
+            Event::CData(data) => {
+                let value = String::from_utf8_lossy(&data.into_inner()).to_string();
+                apply_text(&path, &mut note, &mut attachment, &mut data_base64, &value);
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(notes)
+}
+
+fn apply_text(
+    path: &[String],
+    note: &mut Option<EnexNote>,
+    attachment: &mut Option<EnexAttachment>,
+    data_base64: &mut String,
+    value: &str,
+) {
+    let Some(note) = note.as_mut() else {
+        return;
+    };
+    let current = path.last().map(String::as_str).unwrap_or("");
+    if let Some(attachment) = attachment.as_mut() {
+        match current {
+            "data" => data_base64.push_str(value),
+            "mime" => attachment.mime = Some(value.trim().to_string()),
+            "file-name" => attachment.file_name = Some(value.trim().to_string()),
+            _ => {}
+        }
+        return;
+    }
+    match current {
+        "title" => note.title.push_str(value),
+        "content" => note.content.push_str(value),
+        "created" => note.created_at = parse_enex_date(value),
+        "updated" => note.updated_at = parse_enex_date(value),
+        "tag" => {
+            let tag = value.trim();
+            if !tag.is_empty() {
+                note.tags.push(tag.to_string());
+            }
+        }
+        "source-url" => note.source_url = Some(value.trim().to_string()),
+        _ => {}
+    }
+}
+
+/// ENEX 时间格式 `20230115T083000Z` 转为 SQLite DATETIME 格式
+pub fn parse_enex_date(raw: &str) -> Option<String> {
+    NaiveDateTime::parse_from_str(raw.trim(), "%Y%m%dT%H%M%SZ")
+        .ok()
+        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_enex() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE en-export SYSTEM "http://xml.evernote.com/pub/evernote-export3.dtd">
+<en-export export-date="20240101T000000Z" application="Evernote">
+  <note>
+    <title>Rust &amp; SQLite</title>
+    <content><![CDATA[<?xml version="1.0" encoding="UTF-8"?><en-note><div>Hello</div></en-note>]]></content>
+    <created>20230115T083000Z</created>
+    <updated>20230116T090000Z</updated>
+    <tag>rust</tag>
+    <tag>db</tag>
+    <note-attributes><source-url>https://example.com</source-url></note-attributes>
+    <resource>
+      <data encoding="base64">aGVs
+bG8=</data>
+      <mime>image/png</mime>
+      <resource-attributes><file-name>shot.png</file-name></resource-attributes>
+    </resource>
+  </note>
+  <note><title>Empty</title><content></content></note>
+</en-export>"#;
+
+        let notes = parse_enex(xml).unwrap();
+        assert_eq!(notes.len(), 2);
+        let note = &notes[0];
+        assert_eq!(note.title, "Rust & SQLite");
+        assert!(note.content.contains("<div>Hello</div>"));
+        assert_eq!(note.created_at.as_deref(), Some("2023-01-15 08:30:00"));
+        assert_eq!(note.updated_at.as_deref(), Some("2023-01-16 09:00:00"));
+        assert_eq!(note.tags, vec!["rust", "db"]);
+        assert_eq!(note.source_url.as_deref(), Some("https://example.com"));
+        assert_eq!(note.attachments.len(), 1);
+        assert_eq!(note.attachments[0].data, b"hello");
+        assert_eq!(note.attachments[0].extension().as_deref(), Some("png"));
+        assert!(notes[1].attachments.is_empty());
+    }
+
+    #[test]
+    fn test_attachment_extension_from_mime() {
+        let attachment = EnexAttachment {
+            data: Vec::new(),
+            mime: Some("application/pdf".to_string()),
+            file_name: None,
+        };
+        assert_eq!(attachment.extension().as_deref(), Some("pdf"));
+        assert_eq!(parse_enex_date("bad"), None);
+    }
+}
@@ -0,0 +1,477 @@
+//! 前端事件 payload 定义与版本
+//!
+//! 所有 `app.emit` 的事件都在这里声明为带类型的 payload，并实现 [`AppEvent`]：
+//! - `NAME`: 事件名（前端 `listen` 的名字）
+//! - `VERSION`: 该事件 payload 的版本，字段有不兼容变更时递增
+//!
+//! 通过 [`emit_event`] 发送时，payload 会被展开并附加 `schema_version` 字段，
+//! 已有字段位置不变，旧的前端监听代码无需修改。
+//! `get_event_schema` 命令返回 [`event_schema`]，供前端和外部消费者做兼容判断。
+
+use std::any::type_name;
+
+use serde::Serialize;
+use tauri::{Emitter, Runtime};
+
+use super::{ChatUsage, FocusState, JobSnapshot, PomodoroPhase, PomodoroState, TaskTimerState};
+use crate::db::{
+    DataMigrationStatus, FileReferenceStatus, MessageSourceRecord, NotificationRecord,
+};
+
+/// 事件 schema 整体版本：新增/删除事件或调整信封格式时递增
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
+
+/// 可发送给前端的事件
+pub trait AppEvent: Serialize + Clone {
+    /// 事件名
+    const NAME: &'static str;
+    /// payload 版本
+    const VERSION: u32;
+    /// 事件说明
+    const DESCRIPTION: &'static str;
+}
+
+/// 事件信封：payload 字段展开到顶层，附加版本号
+#[derive(Serialize, Clone)]
+struct EventEnvelope<'a, E> {
+    schema_version: u32,
+    #[serde(flatten)]
+    payload: &'a E,
+}
+
+/// 发送带版本号的事件；发送失败（窗口已关闭等）直接忽略
+pub fn emit_event<R: Runtime, E: AppEvent>(emitter: &impl Emitter<R>, payload: &E) {
+    let envelope = EventEnvelope {
+        schema_version: E::VERSION,
+        payload,
+    };
+    if let Err(err) = emitter.emit(E::NAME, envelope) {
+        tracing::debug!(event = E::NAME, error = %err, "Emit event failed");
+    }
+}
+
+// ========== 聊天 ==========
+
+/// 聊天流式输出事件
+#[derive(Debug, Clone, Serialize)]
+pub struct ChatStreamPayload {
+    pub session_id: i64,
+    #[serde(flatten)]
+    pub event: ChatStreamEventPayload,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ChatStreamEventPayload {
+    AnswerDelta {
+        delta: String,
+    },
+    ThinkingDelta {
+        delta: String,
+    },
+    Usage {
+        usage: ChatUsage,
+    },
+    ToolCall {
+        name: String,
+        args: serde_json::Value,
+    },
+    /// RAG 片段注入 prompt 后、模型开始输出前发送
+    Sources {
+        message_id: i64,
+        sources: Vec<MessageSourceRecord>,
+    },
+    Error {
+        message: String,
+    },
+}
+
+impl AppEvent for ChatStreamPayload {
+    const NAME: &'static str = "chat-stream";
+    const VERSION: u32 = 2;
+    const DESCRIPTION: &'static str =
+        "聊天流式输出：answer_delta / thinking_delta / usage / tool_call / sources / error";
+}
+
+// ========== 资源处理 ==========
+
+/// 资源解析进度事件
+#[derive(Debug, Clone, Serialize)]
+pub struct ParseProgressPayload {
+    pub node_id: i64,
+    pub status: String,
+    pub percentage: Option<u8>,
+    pub error: Option<String>,
+}
+
+impl AppEvent for ParseProgressPayload {
+    const NAME: &'static str = "parse-progress";
+    const VERSION: u32 = 1;
+    const DESCRIPTION: &'static str = "资源捕获后的解析进度";
+}
+
+/// 批量捕获中单个文件的进度事件
+#[derive(Debug, Clone, Serialize)]
+pub struct CaptureBatchProgressPayload {
+    pub batch_id: String,
+    /// 文件在请求中的序号（从 0 开始）
+    pub index: usize,
+    pub total: usize,
+    pub file_path: String,
+    pub node_id: Option<i64>,
+    /// copied / done / error
+    pub status: &'static str,
+    pub error: Option<String>,
+}
+
+impl AppEvent for CaptureBatchProgressPayload {
+    const NAME: &'static str = "capture-batch-progress";
+    const VERSION: u32 = 1;
+    const DESCRIPTION: &'static str = "批量捕获时每个文件的复制、解析与入队进度";
+}
+
+/// 压缩包展开进度
+#[derive(Debug, Clone, Serialize)]
+pub struct ArchiveExpandProgressPayload {
+    /// 压缩包资源 ID
+    pub node_id: i64,
+    /// extracting / importing / done / error
+    pub status: &'static str,
+    /// 已处理条目数；importing 阶段 total 为新建子资源数
+    pub processed: usize,
+    pub total: Option<usize>,
+    pub entry_path: Option<String>,
+    pub child_node_id: Option<i64>,
+    pub error: Option<String>,
+}
+
+impl AppEvent for ArchiveExpandProgressPayload {
+    const NAME: &'static str = "archive-expand-progress";
+    const VERSION: u32 = 1;
+    const DESCRIPTION: &'static str = "压缩包展开时的解压与子资源解析进度";
+}
+
+/// 监听文件夹导入结果
+#[derive(Debug, Clone, Serialize)]
+pub struct FolderImportPayload {
+    pub folder_id: i64,
+    pub file_path: String,
+    pub node_id: Option<i64>,
+    /// imported / duplicate / error
+    pub status: &'static str,
+    pub error: Option<String>,
+}
+
+impl AppEvent for FolderImportPayload {
+    const NAME: &'static str = "folder-import";
+    const VERSION: u32 = 1;
+    const DESCRIPTION: &'static str = "监听文件夹中的文件被导入、判定重复或导入失败";
+}
+
+/// 外部文件引用状态变化
+#[derive(Debug, Clone, Serialize)]
+pub struct FileReferenceStatusPayload {
+    pub node_id: i64,
+    pub file_path: String,
+    pub status: FileReferenceStatus,
+    /// 文件内容变化，已按新内容重新解析
+    pub reparsed: bool,
+}
+
+impl AppEvent for FileReferenceStatusPayload {
+    const NAME: &'static str = "file-reference-status";
+    const VERSION: u32 = 1;
+    const DESCRIPTION: &'static str = "引用文件离线、恢复在线、被移动或内容变化后重新解析";
+}
+
+/// 文件完整性校验进度事件
+#[derive(Debug, Clone, Serialize)]
+pub struct IntegrityCheckProgressPayload {
+    pub check_id: i64,
+    pub processed: usize,
+    pub total: usize,
+}
+
+impl AppEvent for IntegrityCheckProgressPayload {
+    const NAME: &'static str = "integrity-check-progress";
+    const VERSION: u32 = 1;
+    const DESCRIPTION: &'static str = "资源文件完整性校验进度";
+}
+
+/// 资源文件目录迁移进度事件
+#[derive(Debug, Clone, Serialize)]
+pub struct AssetsRelocateProgressPayload {
+    /// copying / removing / done
+    pub status: &'static str,
+    pub processed: usize,
+    pub total: usize,
+}
+
+impl AppEvent for AssetsRelocateProgressPayload {
+    const NAME: &'static str = "assets-relocate-progress";
+    const VERSION: u32 = 1;
+    const DESCRIPTION: &'static str = "资源文件迁移到新目录的复制与清理进度";
+}
+
+/// 向量重建进度事件
+#[derive(Debug, Clone, Serialize)]
+pub struct EmbeddingReindexProgressPayload {
+    /// 正在写入的新表
+    pub table_name: String,
+    pub processed: usize,
+    pub total: usize,
+}
+
+impl AppEvent for EmbeddingReindexProgressPayload {
+    const NAME: &'static str = "embedding-reindex-progress";
+    const VERSION: u32 = 1;
+    const DESCRIPTION: &'static str = "换用新嵌入模型重建向量时每批完成";
+}
+
+/// 模型文件下载进度事件
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelDownloadProgressPayload {
+    pub file_name: String,
+    pub downloaded_bytes: u64,
+    /// 服务器未返回长度时为空
+    pub total_bytes: Option<u64>,
+}
+
+impl AppEvent for ModelDownloadProgressPayload {
+    const NAME: &'static str = "model-download-progress";
+    const VERSION: u32 = 1;
+    const DESCRIPTION: &'static str = "下载缺失的模型文件时每 1 MB 及每个文件完成时";
+}
+
+impl AppEvent for JobSnapshot {
+    const NAME: &'static str = "job-progress";
+    const VERSION: u32 = 1;
+    const DESCRIPTION: &'static str = "后台任务开始、进度（节流）、请求取消与结束";
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmbeddingStatus {
+    Processing,
+    Idle,
+}
+
+/// AI 管道忙碌状态事件
+#[derive(Debug, Clone, Serialize)]
+pub struct EmbeddingStatusPayload {
+    pub status: EmbeddingStatus,
+}
+
+impl AppEvent for EmbeddingStatusPayload {
+    const NAME: &'static str = "embedding-status";
+    const VERSION: u32 = 1;
+    const DESCRIPTION: &'static str = "AI 管道开始处理 / 队列清空";
+}
+
+/// AI 为资源生成了待确认的建议标签
+#[derive(Debug, Clone, Serialize)]
+pub struct TagSuggestionsPayload {
+    pub node_id: i64,
+    pub count: usize,
+}
+
+impl AppEvent for TagSuggestionsPayload {
+    const NAME: &'static str = "tag-suggestions";
+    const VERSION: u32 = 1;
+    const DESCRIPTION: &'static str = "资源处理完成后 AI 建议了待确认的标签";
+}
+
+/// 后台数据迁移进度
+#[derive(Debug, Clone, Serialize)]
+pub struct DataMigrationProgressPayload {
+    pub name: &'static str,
+    pub status: DataMigrationStatus,
+    pub processed: i64,
+    pub total: Option<i64>,
+    pub error: Option<String>,
+}
+
+impl AppEvent for DataMigrationProgressPayload {
+    const NAME: &'static str = "data-migration-progress";
+    const VERSION: u32 = 1;
+    const DESCRIPTION: &'static str = "后台数据迁移每批完成、结束或失败";
+}
+
+// ========== 专注与番茄钟 ==========
+
+impl AppEvent for FocusState {
+    const NAME: &'static str = "focus-tick";
+    const VERSION: u32 = 1;
+    const DESCRIPTION: &'static str = "专注倒计时，每秒一次";
+}
+
+/// 专注会话状态变更事件
+#[derive(Debug, Clone, Serialize)]
+pub struct FocusStatusPayload {
+    pub session_id: i64,
+    pub status: &'static str,
+}
+
+impl AppEvent for FocusStatusPayload {
+    const NAME: &'static str = "focus-status";
+    const VERSION: u32 = 1;
+    const DESCRIPTION: &'static str = "专注会话完成或取消";
+}
+
+impl AppEvent for PomodoroState {
+    const NAME: &'static str = "pomodoro-tick";
+    const VERSION: u32 = 1;
+    const DESCRIPTION: &'static str = "番茄钟状态，每秒及暂停/跳过时发送";
+}
+
+/// 番茄钟阶段结束事件
+#[derive(Debug, Clone, Serialize)]
+pub struct PomodoroPhasePayload {
+    pub task_id: Option<i64>,
+    pub finished_phase: PomodoroPhase,
+    pub next_phase: PomodoroPhase,
+    pub skipped: bool,
+    pub completed_pomodoros: u32,
+}
+
+impl AppEvent for PomodoroPhasePayload {
+    const NAME: &'static str = "pomodoro-phase";
+    const VERSION: u32 = 1;
+    const DESCRIPTION: &'static str = "番茄钟阶段结束（完成或跳过）";
+}
+
+impl AppEvent for TaskTimerState {
+    const NAME: &'static str = "task-timer";
+    const VERSION: u32 = 1;
+    const DESCRIPTION: &'static str = "任务计时器开始或停止（不逐秒发送）";
+}
+
+// ========== 通知与窗口 ==========
+
+impl AppEvent for NotificationRecord {
+    const NAME: &'static str = "notification-created";
+    const VERSION: u32 = 1;
+    const DESCRIPTION: &'static str = "通知中心新增通知（免打扰时不发送）";
+}
+
+/// HUD 显示后请求前端聚焦输入框
+#[derive(Debug, Clone, Serialize)]
+pub struct HudFocusPayload {
+    /// 通过捕获模板快捷键唤起时的模板 ID，默认快捷键为 None
+    pub profile_id: Option<i64>,
+}
+
+impl AppEvent for HudFocusPayload {
+    const NAME: &'static str = "hud-focus";
+    const VERSION: u32 = 2;
+    const DESCRIPTION: &'static str = "HUD 窗口显示后聚焦输入框";
+}
+
+// ========== Schema 描述 ==========
+
+/// 单个事件的描述
+#[derive(Debug, Clone, Serialize)]
+pub struct EventDescriptor {
+    pub name: &'static str,
+    pub version: u32,
+    /// payload 的 Rust 类型名（不含模块路径）
+    pub payload: &'static str,
+    pub description: &'static str,
+}
+
+/// 全部事件的描述
+#[derive(Debug, Clone, Serialize)]
+pub struct EventSchema {
+    pub schema_version: u32,
+    pub events: Vec<EventDescriptor>,
+}
+
+fn describe<E: AppEvent>() -> EventDescriptor {
+    let full_name = type_name::<E>();
+    EventDescriptor {
+        name: E::NAME,
+        version: E::VERSION,
+        payload: full_name.rsplit("::").next().unwrap_or(full_name),
+        description: E::DESCRIPTION,
+    }
+}
+
+/// 当前后端会发送的全部事件
+pub fn event_schema() -> EventSchema {
+    EventSchema {
+        schema_version: EVENT_SCHEMA_VERSION,
+        events: vec![
+            describe::<ChatStreamPayload>(),
+            describe::<ParseProgressPayload>(),
+            describe::<CaptureBatchProgressPayload>(),
+            describe::<ArchiveExpandProgressPayload>(),
+            describe::<FolderImportPayload>(),
+            describe::<FileReferenceStatusPayload>(),
+            describe::<AssetsRelocateProgressPayload>(),
+            describe::<IntegrityCheckProgressPayload>(),
+            describe::<EmbeddingReindexProgressPayload>(),
+            describe::<ModelDownloadProgressPayload>(),
+            describe::<JobSnapshot>(),
+            describe::<EmbeddingStatusPayload>(),
+            describe::<TagSuggestionsPayload>(),
+            describe::<DataMigrationProgressPayload>(),
+            describe::<FocusState>(),
+            describe::<FocusStatusPayload>(),
+            describe::<PomodoroState>(),
+            describe::<PomodoroPhasePayload>(),
+            describe::<TaskTimerState>(),
+            describe::<NotificationRecord>(),
+            describe::<HudFocusPayload>(),
+        ],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_envelope_flattens_payload() {
+        let payload = ChatStreamPayload {
+            session_id: 7,
+            event: ChatStreamEventPayload::AnswerDelta {
+                delta: "hi".to_string(),
+            },
+        };
+        let envelope = EventEnvelope {
+            schema_version: ChatStreamPayload::VERSION,
+            payload: &payload,
+        };
+        assert_eq!(
+            serde_json::to_value(&envelope).unwrap(),
+            serde_json::json!({
+                "schema_version": 2,
+                "session_id": 7,
+                "type": "answer_delta",
+                "delta": "hi",
+            })
+        );
+
+        let envelope = EventEnvelope {
+            schema_version: HudFocusPayload::VERSION,
+            payload: &HudFocusPayload { profile_id: None },
+        };
+        assert_eq!(
+            serde_json::to_value(&envelope).unwrap(),
+            serde_json::json!({ "schema_version": 2, "profile_id": null })
+        );
+    }
+
+    #[test]
+    fn test_event_names_are_unique() {
+        let schema = event_schema();
+        let mut names: Vec<_> = schema.events.iter().map(|event| event.name).collect();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), schema.events.len());
+        assert!(schema
+            .events
+            .iter()
+            .any(|event| event.name == "chat-stream" && event.payload == "ChatStreamPayload"));
+    }
+}
@@ -0,0 +1,473 @@
+//! 外部文件引用
+//!
+//! 以引用方式捕获的文件不复制到资源文件目录，节点的 file_path 直接保存其绝对路径。
+//! 引用文件所在的目录各对应一个 notify watcher（不递归）：文件内容变化后按新哈希重新解析入队，
+//! 被删除或移走时标记为离线；同一监听目录中出现大小与哈希都一致的文件时视为被重命名，跟随更新路径。
+//! 后台任务定期复查所有引用，外接硬盘重新连接后自动恢复在线。
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use tauri::{AppHandle, Manager};
+
+use super::{emit_event, parse_and_enqueue, FileReferenceStatusPayload, JobPriority};
+use crate::app_state::AppState;
+use crate::db::{
+    find_file_references_by_path, get_file_reference, get_node_by_id, insert_file_reference,
+    list_file_references, mark_file_reference_offline, mark_file_reference_online,
+    update_file_reference_path, update_node_file_hash, FileReferenceRecord, FileReferenceStatus,
+    FileStat,
+};
+use crate::i18n::MessageCode;
+use crate::utils::compute_file_sha256;
+use crate::{AppError, AppResult};
+
+const SCAN_INTERVAL: Duration = Duration::from_secs(2);
+/// 文件最后一次变化后等待的静默期（大文件写入可能持续较久）
+const SETTLE_DELAY: Duration = Duration::from_secs(5);
+/// 定期复查所有引用的间隔
+const RECHECK_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+pub struct FileReferenceService {
+    watchers: Mutex<HashMap<PathBuf, RecommendedWatcher>>,
+    pending: Arc<Mutex<HashMap<PathBuf, Instant>>>,
+}
+
+impl FileReferenceService {
+    pub fn new() -> Self {
+        Self {
+            watchers: Mutex::new(HashMap::new()),
+            pending: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// 监听目录（已在监听时忽略）
+    pub fn watch_dir(&self, dir: &Path) -> Result<(), String> {
+        let mut watchers = self.watchers.lock().expect("file reference lock");
+        if watchers.contains_key(dir) {
+            return Ok(());
+        }
+        let pending = self.pending.clone();
+        let mut watcher = notify::recommended_watcher(
+            move |result: notify::Result<notify::Event>| match result {
+                Ok(event)
+                    if matches!(
+                        event.kind,
+                        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+                    ) =>
+                {
+                    let mut pending = pending.lock().expect("file reference lock");
+                    for path in event.paths {
+                        pending.insert(path, Instant::now());
+                    }
+                }
+                Ok(_) => {}
+                Err(err) => tracing::warn!(error = %err, "File reference watch event failed"),
+            },
+        )
+        .map_err(|e| format!("创建文件监听失败: {e}"))?;
+        watcher
+            .watch(dir, RecursiveMode::NonRecursive)
+            .map_err(|e| format!("监听 {} 失败: {e}", dir.display()))?;
+        watchers.insert(dir.to_path_buf(), watcher);
+        tracing::debug!(dir = %dir.display(), "Watching file reference directory");
+        Ok(())
+    }
+
+    /// 只保留 dirs 中的监听，并补上尚未建立的监听（目录缺失时等待下次复查）
+    fn sync_watches(&self, dirs: &HashSet<PathBuf>) {
+        self.watchers
+            .lock()
+            .expect("file reference lock")
+            .retain(|dir, _| dirs.contains(dir));
+        for dir in dirs.iter().filter(|dir| dir.is_dir()) {
+            if let Err(err) = self.watch_dir(dir) {
+                tracing::debug!(error = %err, "Watch file reference directory failed");
+            }
+        }
+    }
+
+    /// 取出静默期内没有再变化的路径
+    fn take_settled(&self) -> Vec<PathBuf> {
+        let now = Instant::now();
+        let mut pending = self.pending.lock().expect("file reference lock");
+        let settled: Vec<PathBuf> = pending
+            .iter()
+            .filter(|(_, last_seen)| now.duration_since(**last_seen) >= SETTLE_DELAY)
+            .map(|(path, _)| path.clone())
+            .collect();
+        for path in &settled {
+            pending.remove(path);
+        }
+        settled
+    }
+}
+
+impl Default for FileReferenceService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn file_stat(metadata: &fs::Metadata) -> FileStat {
+    FileStat {
+        file_size: metadata.len() as i64,
+        modified_at: metadata
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|duration| duration.as_secs() as i64),
+    }
+}
+
+/// 检查待引用的文件：需为可读的普通文件，返回规范化后的绝对路径、大小与修改时间
+pub fn inspect_reference_file(path: &str) -> AppResult<(PathBuf, FileStat)> {
+    let path = Path::new(path.trim());
+    if !path.is_absolute() {
+        return Err(AppError::coded(MessageCode::FileReferenceNotAbsolute));
+    }
+    let path = path.canonicalize().map_err(|e| {
+        AppError::coded_with(
+            MessageCode::InvalidFileReference,
+            "detail",
+            format!("{}: {e}", path.display()),
+        )
+    })?;
+    let metadata = fs::metadata(&path)?;
+    if !metadata.is_file() {
+        return Err(AppError::coded_with(
+            MessageCode::FileReferenceNotFile,
+            "path",
+            path.display(),
+        ));
+    }
+    Ok((path, file_stat(&metadata)))
+}
+
+/// 在后台线程中流式计算文件哈希，不把大文件读入内存
+pub async fn hash_reference_file(path: &Path) -> AppResult<String> {
+    let path = path.to_path_buf();
+    let hash = tauri::async_runtime::spawn_blocking(move || compute_file_sha256(&path))
+        .await
+        .map_err(|e| e.to_string())??;
+    Ok(hash)
+}
+
+/// 记录新建的引用：允许前端读取该文件并监听其所在目录
+pub async fn register_file_reference(
+    app: &AppHandle,
+    state: &AppState,
+    node_id: i64,
+    path: &Path,
+    stat: FileStat,
+) -> AppResult<()> {
+    insert_file_reference(&state.db, node_id, stat).await?;
+    allow_reference_scope(app, path);
+    if let Some(dir) = path.parent() {
+        if let Err(err) = state.file_references.watch_dir(dir) {
+            tracing::warn!(node_id, error = %err, "Watch file reference directory failed");
+        }
+    }
+    Ok(())
+}
+
+/// 把离线或已移动的引用指向 new_path；内容与原文件不同时重新解析入队
+pub async fn relink_file_reference(
+    app: &AppHandle,
+    state: &AppState,
+    node_id: i64,
+    new_path: &str,
+) -> AppResult<FileReferenceRecord> {
+    let not_found = || AppError::NotFound {
+        entity: "file_reference",
+        id: node_id,
+    };
+    get_file_reference(&state.db, node_id)
+        .await?
+        .ok_or_else(not_found)?;
+    let (path, stat) = inspect_reference_file(new_path)?;
+    let hash = hash_reference_file(&path).await?;
+    let file_path = path.to_string_lossy().to_string();
+
+    update_file_reference_path(&state.db, node_id, &file_path).await?;
+    let reparsed = reparse_if_changed(app, state, node_id, &file_path, &hash).await?;
+    mark_file_reference_online(&state.db, node_id, stat).await?;
+    allow_reference_scope(app, &path);
+    if let Some(dir) = path.parent() {
+        if let Err(err) = state.file_references.watch_dir(dir) {
+            tracing::warn!(node_id, error = %err, "Watch file reference directory failed");
+        }
+    }
+    tracing::info!(node_id, path = %file_path, reparsed, "File reference relinked");
+    emit_status(
+        app,
+        node_id,
+        &file_path,
+        FileReferenceStatus::Online,
+        reparsed,
+    );
+
+    get_file_reference(&state.db, node_id)
+        .await?
+        .ok_or_else(not_found)
+}
+
+fn allow_reference_scope(app: &AppHandle, path: &Path) {
+    if let Err(err) = app.asset_protocol_scope().allow_file(path) {
+        tracing::warn!(path = %path.display(), error = %err, "Allow file reference scope failed");
+    }
+}
+
+fn emit_status(
+    app: &AppHandle,
+    node_id: i64,
+    file_path: &str,
+    status: FileReferenceStatus,
+    reparsed: bool,
+) {
+    emit_event(
+        app,
+        &FileReferenceStatusPayload {
+            node_id,
+            file_path: file_path.to_string(),
+            status,
+            reparsed,
+        },
+    );
+}
+
+/// 文件内容与节点记录的哈希不同时以新内容为准重新解析，返回是否重新解析
+async fn reparse_if_changed(
+    app: &AppHandle,
+    state: &AppState,
+    node_id: i64,
+    file_path: &str,
+    hash: &str,
+) -> AppResult<bool> {
+    let node = get_node_by_id(&state.db, node_id).await?;
+    if node.file_hash.as_deref() == Some(hash) {
+        return Ok(false);
+    }
+    let Some(subtype) = node.resource_subtype else {
+        return Ok(false);
+    };
+    update_node_file_hash(&state.db, node_id, hash).await?;
+    let parse_error = parse_and_enqueue(
+        app,
+        state,
+        node_id,
+        subtype,
+        None,
+        Some(file_path),
+        hash,
+        JobPriority::Normal,
+    )
+    .await?;
+    if let Some(err) = &parse_error {
+        tracing::warn!(node_id, error = %err, "Reparse referenced file failed");
+    }
+    Ok(true)
+}
+
+/// 按文件当前状态更新引用：缺失时标记离线；大小或修改时间变化时重新计算哈希
+async fn refresh_reference(
+    app: &AppHandle,
+    state: &AppState,
+    reference: &FileReferenceRecord,
+) -> AppResult<()> {
+    let node_id = reference.node_id;
+    let path = Path::new(&reference.file_path);
+    let metadata = match fs::metadata(path) {
+        Ok(metadata) if metadata.is_file() => metadata,
+        result => {
+            let error = match result {
+                Ok(_) => "不是文件".to_string(),
+                Err(err) => err.to_string(),
+            };
+            mark_file_reference_offline(&state.db, node_id, &error).await?;
+            if reference.status == FileReferenceStatus::Online {
+                tracing::info!(node_id, path = %reference.file_path, "File reference offline");
+                emit_status(
+                    app,
+                    node_id,
+                    &reference.file_path,
+                    FileReferenceStatus::Offline,
+                    false,
+                );
+            }
+            return Ok(());
+        }
+    };
+
+    let stat = file_stat(&metadata);
+    let stored = FileStat {
+        file_size: reference.file_size.unwrap_or(-1),
+        modified_at: reference.modified_at,
+    };
+    if reference.status == FileReferenceStatus::Online && stat == stored {
+        return Ok(());
+    }
+    let reparsed = if stat == stored {
+        false
+    } else {
+        let hash = hash_reference_file(path).await?;
+        reparse_if_changed(app, state, node_id, &reference.file_path, &hash).await?
+    };
+    mark_file_reference_online(&state.db, node_id, stat).await?;
+    if reference.status == FileReferenceStatus::Offline || reparsed {
+        tracing::info!(node_id, path = %reference.file_path, reparsed, "File reference refreshed");
+        emit_status(
+            app,
+            node_id,
+            &reference.file_path,
+            FileReferenceStatus::Online,
+            reparsed,
+        );
+    }
+    Ok(())
+}
+
+/// 路径不对应任何引用时，检查它是否是某个已不在原位置的引用文件（被重命名或移入监听目录）
+async fn follow_moved_file(app: &AppHandle, state: &AppState, path: &Path) -> AppResult<()> {
+    let Ok(metadata) = fs::metadata(path) else {
+        return Ok(());
+    };
+    if !metadata.is_file() {
+        return Ok(());
+    }
+    let stat = file_stat(&metadata);
+    let candidates: Vec<FileReferenceRecord> = list_file_references(&state.db)
+        .await?
+        .into_iter()
+        .filter(|reference| {
+            reference.file_size == Some(stat.file_size) && !Path::new(&reference.file_path).exists()
+        })
+        .collect();
+    if candidates.is_empty() {
+        return Ok(());
+    }
+
+    let hash = hash_reference_file(path).await?;
+    let file_path = path.to_string_lossy().to_string();
+    for reference in candidates {
+        let node = get_node_by_id(&state.db, reference.node_id).await?;
+        if node.file_hash.as_deref() != Some(hash.as_str()) {
+            continue;
+        }
+        update_file_reference_path(&state.db, reference.node_id, &file_path).await?;
+        mark_file_reference_online(&state.db, reference.node_id, stat).await?;
+        allow_reference_scope(app, path);
+        tracing::info!(
+            node_id = reference.node_id,
+            from = %reference.file_path,
+            to = %file_path,
+            "File reference followed move"
+        );
+        emit_status(
+            app,
+            reference.node_id,
+            &file_path,
+            FileReferenceStatus::Online,
+            false,
+        );
+    }
+    Ok(())
+}
+
+async fn handle_changed_path(app: &AppHandle, state: &AppState, path: &Path) -> AppResult<()> {
+    let file_path = path.to_string_lossy().to_string();
+    let references = find_file_references_by_path(&state.db, &file_path).await?;
+    if references.is_empty() {
+        return follow_moved_file(app, state, path).await;
+    }
+    for reference in &references {
+        refresh_reference(app, state, reference).await?;
+    }
+    Ok(())
+}
+
+/// 复查所有引用，并按引用文件所在目录重建监听
+async fn recheck_references(app: &AppHandle, state: &AppState) -> AppResult<()> {
+    let references = list_file_references(&state.db).await?;
+    let mut dirs = HashSet::new();
+    for reference in &references {
+        if let Err(err) = refresh_reference(app, state, reference).await {
+            tracing::warn!(
+                node_id = reference.node_id,
+                error = %err,
+                "Refresh file reference failed"
+            );
+        }
+        let path = Path::new(&reference.file_path);
+        if let Some(dir) = path.parent() {
+            dirs.insert(dir.to_path_buf());
+        }
+    }
+    state.file_references.sync_watches(&dirs);
+    Ok(())
+}
+
+/// 启动时允许前端读取所有引用文件并复查状态，之后监听变化并定期复查
+pub fn spawn_file_reference_watcher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        if let Some(state) = app.try_state::<AppState>() {
+            match list_file_references(&state.db).await {
+                Ok(references) => {
+                    for reference in &references {
+                        allow_reference_scope(&app, Path::new(&reference.file_path));
+                    }
+                }
+                Err(err) => tracing::warn!(error = %err, "Load file references failed"),
+            }
+        }
+
+        let mut last_recheck: Option<Instant> = None;
+        loop {
+            let Some(state) = app.try_state::<AppState>() else {
+                tokio::time::sleep(SCAN_INTERVAL).await;
+                continue;
+            };
+            if !matches!(last_recheck, Some(at) if at.elapsed() < RECHECK_INTERVAL) {
+                if let Err(err) = recheck_references(&app, &state).await {
+                    tracing::warn!(error = %err, "Recheck file references failed");
+                }
+                last_recheck = Some(Instant::now());
+            }
+            for path in state.file_references.take_settled() {
+                if let Err(err) = handle_changed_path(&app, &state, &path).await {
+                    tracing::warn!(
+                        path = %path.display(),
+                        error = %err,
+                        "Handle file reference change failed"
+                    );
+                }
+            }
+            tokio::time::sleep(SCAN_INTERVAL).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inspects_regular_files_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("movie.mkv");
+        fs::write(&file, b"frames").unwrap();
+
+        let (path, stat) = inspect_reference_file(&file.to_string_lossy()).unwrap();
+        assert_eq!(path, file.canonicalize().unwrap());
+        assert_eq!(stat.file_size, 6);
+        assert!(stat.modified_at.is_some());
+
+        assert!(inspect_reference_file(&dir.path().to_string_lossy()).is_err());
+        assert!(inspect_reference_file("relative/movie.mkv").is_err());
+        assert!(inspect_reference_file(&dir.path().join("missing").to_string_lossy()).is_err());
+    }
+}
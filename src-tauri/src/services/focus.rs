@@ -0,0 +1,262 @@
+//! 专注模式
+//!
+//! 倒计时由 Rust 侧维护，前端只订阅 `focus-tick` / `focus-status` 事件；
+//! 开启免打扰时，通知中心仍会落库，但不会推送 `notification-created` 事件。
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::async_runtime::JoinHandle;
+use tauri::AppHandle;
+
+use crate::db::{
+    finish_focus_session, get_focus_session_by_id, get_node_by_id, insert_focus_session, DbPool,
+    FocusSessionRecord, FocusSessionStatus, NewNotification, NodeType, NotificationKind,
+};
+use crate::error::{AppError, AppResult};
+use crate::i18n::MessageCode;
+use crate::services::{emit_event, push_notification, FocusStatusPayload};
+
+const TICK_INTERVAL: Duration = Duration::from_secs(1);
+const MAX_FOCUS_MINUTES: i64 = 240;
+
+/// 当前专注状态（事件 payload 与查询返回共用）
+#[derive(Debug, Clone, Serialize)]
+pub struct FocusState {
+    pub session_id: i64,
+    pub task_id: Option<i64>,
+    pub planned_seconds: i64,
+    pub remaining_seconds: i64,
+    pub do_not_disturb: bool,
+}
+
+struct ActiveFocus {
+    session_id: i64,
+    task_id: Option<i64>,
+    planned: Duration,
+    started: Instant,
+    do_not_disturb: bool,
+    countdown: Option<JoinHandle<()>>,
+}
+
+impl ActiveFocus {
+    fn state(&self) -> FocusState {
+        FocusState {
+            session_id: self.session_id,
+            task_id: self.task_id,
+            planned_seconds: self.planned.as_secs() as i64,
+            remaining_seconds: self
+                .planned
+                .saturating_sub(self.started.elapsed())
+                .as_secs() as i64,
+            do_not_disturb: self.do_not_disturb,
+        }
+    }
+
+    fn elapsed_seconds(&self) -> i64 {
+        self.started.elapsed().min(self.planned).as_secs() as i64
+    }
+}
+
+pub struct FocusService {
+    db: DbPool,
+    app: AppHandle,
+    active: Mutex<Option<ActiveFocus>>,
+}
+
+impl FocusService {
+    pub fn new(db: DbPool, app: AppHandle) -> Self {
+        Self {
+            db,
+            app,
+            active: Mutex::new(None),
+        }
+    }
+
+    /// 当前是否处于免打扰
+    pub fn is_do_not_disturb(&self) -> bool {
+        self.active
+            .lock()
+            .map(|guard| guard.as_ref().is_some_and(|focus| focus.do_not_disturb))
+            .unwrap_or(false)
+    }
+
+    pub fn current(&self) -> Option<FocusState> {
+        self.active.lock().ok()?.as_ref().map(ActiveFocus::state)
+    }
+
+    pub async fn start(
+        self: &Arc<Self>,
+        task_id: Option<i64>,
+        duration_minutes: i64,
+        do_not_disturb: bool,
+    ) -> AppResult<FocusState> {
+        if !(1..=MAX_FOCUS_MINUTES).contains(&duration_minutes) {
+            return Err(AppError::coded_with(
+                MessageCode::FocusDurationOutOfRange,
+                "max",
+                MAX_FOCUS_MINUTES,
+            ));
+        }
+        if self.current().is_some() {
+            return Err(AppError::coded(MessageCode::FocusSessionRunning));
+        }
+        if let Some(task_id) = task_id {
+            match get_node_by_id(&self.db, task_id).await {
+                Ok(node) if node.node_type == NodeType::Task && !node.is_deleted => {}
+                Ok(_) | Err(sqlx::Error::RowNotFound) => {
+                    return Err(AppError::NotFound {
+                        entity: "task",
+                        id: task_id,
+                    })
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        let planned_seconds = duration_minutes * 60;
+        let session_id =
+            insert_focus_session(&self.db, task_id, planned_seconds, do_not_disturb).await?;
+
+        let focus = ActiveFocus {
+            session_id,
+            task_id,
+            planned: Duration::from_secs(planned_seconds as u64),
+            started: Instant::now(),
+            do_not_disturb,
+            countdown: None,
+        };
+        let state = focus.state();
+
+        let conflict = {
+            let mut guard = self.active.lock().map_err(|e| e.to_string())?;
+            if guard.is_some() {
+                true
+            } else {
+                *guard = Some(focus);
+                false
+            }
+        };
+        if conflict {
+            finish_focus_session(&self.db, session_id, FocusSessionStatus::Interrupted, 0).await?;
+            return Err(AppError::coded(MessageCode::FocusSessionRunning));
+        }
+
+        // 先登记会话再启动倒计时，避免首个 tick 读不到当前会话
+        let handle = self.spawn_countdown(session_id);
+        if let Ok(mut guard) = self.active.lock() {
+            match guard.as_mut() {
+                Some(focus) if focus.session_id == session_id => focus.countdown = Some(handle),
+                _ => handle.abort(),
+            }
+        }
+
+        tracing::info!(
+            session_id,
+            ?task_id,
+            duration_minutes,
+            "Focus session started"
+        );
+        self.emit_status(session_id, "started");
+        Ok(state)
+    }
+
+    /// 用户主动结束；未到时间记为 interrupted
+    pub async fn end(&self) -> Result<Option<FocusSessionRecord>, String> {
+        let focus = {
+            let mut guard = self.active.lock().map_err(|e| e.to_string())?;
+            guard.take()
+        };
+        let Some(mut focus) = focus else {
+            return Ok(None);
+        };
+        if let Some(handle) = focus.countdown.take() {
+            handle.abort();
+        }
+
+        let status = if focus.started.elapsed() >= focus.planned {
+            FocusSessionStatus::Completed
+        } else {
+            FocusSessionStatus::Interrupted
+        };
+        self.finish(&focus, status).await.map(Some)
+    }
+
+    async fn complete(&self, session_id: i64) -> Result<(), String> {
+        let focus = {
+            let mut guard = self.active.lock().map_err(|e| e.to_string())?;
+            match guard.as_ref() {
+                Some(focus) if focus.session_id == session_id => guard.take(),
+                _ => None,
+            }
+        };
+        let Some(focus) = focus else {
+            return Ok(());
+        };
+
+        self.finish(&focus, FocusSessionStatus::Completed).await?;
+        push_notification(
+            &self.db,
+            &self.app,
+            NewNotification {
+                kind: NotificationKind::System,
+                title: "专注结束",
+                body: Some(&format!("本次专注 {} 分钟", focus.planned.as_secs() / 60)),
+                node_id: focus.task_id,
+                action: Some("open_focus_stats"),
+            },
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn finish(
+        &self,
+        focus: &ActiveFocus,
+        status: FocusSessionStatus,
+    ) -> Result<FocusSessionRecord, String> {
+        finish_focus_session(&self.db, focus.session_id, status, focus.elapsed_seconds())
+            .await
+            .map_err(|e| e.to_string())?;
+
+        tracing::info!(
+            session_id = focus.session_id,
+            ?status,
+            "Focus session finished"
+        );
+        let label = match status {
+            FocusSessionStatus::Completed => "completed",
+            _ => "interrupted",
+        };
+        self.emit_status(focus.session_id, label);
+
+        get_focus_session_by_id(&self.db, focus.session_id)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    fn spawn_countdown(self: &Arc<Self>, session_id: i64) -> JoinHandle<()> {
+        let service = Arc::clone(self);
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(TICK_INTERVAL);
+            loop {
+                interval.tick().await;
+                let Some(state) = service.current().filter(|s| s.session_id == session_id) else {
+                    return;
+                };
+                emit_event(&service.app, &state);
+                if state.remaining_seconds <= 0 {
+                    if let Err(err) = service.complete(session_id).await {
+                        tracing::error!(session_id, error = %err, "Focus session complete failed");
+                    }
+                    return;
+                }
+            }
+        })
+    }
+
+    fn emit_status(&self, session_id: i64, status: &'static str) {
+        emit_event(&self.app, &FocusStatusPayload { session_id, status });
+    }
+}
@@ -0,0 +1,388 @@
+//! 监听文件夹自动导入
+//!
+//! 每个启用的监听文件夹对应一个 notify watcher（不递归子目录）。文件新建或修改后先进入待导入表，
+//! 后台任务定期取出静默期内没有再变化的文件（避免导入下载到一半的文件），按内容哈希去重后捕获为资源，
+//! 可选在导入后移动到指定目录。监听开始时文件夹中已有的文件同样会被导入。
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use tauri::{AppHandle, Manager};
+
+use super::{
+    apply_source_tag_rules, emit_event, parse_and_enqueue, FolderImportPayload, JobPriority,
+};
+use crate::app_state::AppState;
+use crate::db::{
+    find_resource_by_hash, get_watched_folder, list_watched_folders, record_watched_folder_import,
+    set_watched_folder_error, NodeBuilder, ResourceSubtype, SourceMeta, WatchedFolderRecord,
+};
+use crate::utils::{
+    compute_sha256, get_assets_dir, get_extension, parse_file_type_from_extension,
+    resolve_file_path,
+};
+use crate::AppResult;
+
+const SCAN_INTERVAL: Duration = Duration::from_secs(2);
+/// 文件最后一次变化后等待的静默期
+const SETTLE_DELAY: Duration = Duration::from_secs(3);
+/// 写入 source_meta.process_name，可用于来源打标签规则
+pub const FOLDER_WATCH_SOURCE: &str = "Folder Watch";
+
+#[derive(Debug, Clone, Copy)]
+struct PendingFile {
+    folder_id: i64,
+    last_seen: Instant,
+}
+
+enum ImportOutcome {
+    Imported(i64),
+    Duplicate(i64),
+    /// 文件已不存在、不是普通文件或类型不受支持
+    Skipped,
+}
+
+pub struct FolderWatchService {
+    watchers: Mutex<HashMap<i64, RecommendedWatcher>>,
+    pending: Arc<Mutex<HashMap<PathBuf, PendingFile>>>,
+}
+
+impl FolderWatchService {
+    pub fn new() -> Self {
+        Self {
+            watchers: Mutex::new(HashMap::new()),
+            pending: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// 开始监听文件夹（已在监听时替换原 watcher），并把其中已有的文件加入待导入表
+    pub fn watch(&self, folder_id: i64, path: &Path) -> Result<(), String> {
+        let pending = self.pending.clone();
+        let mut watcher = notify::recommended_watcher(
+            move |result: notify::Result<notify::Event>| match result {
+                Ok(event) if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) => {
+                    let mut pending = pending.lock().expect("folder watch lock");
+                    for path in event.paths {
+                        mark_pending(&mut pending, folder_id, path);
+                    }
+                }
+                Ok(_) => {}
+                Err(err) => tracing::warn!(folder_id, error = %err, "Folder watch event failed"),
+            },
+        )
+        .map_err(|e| format!("创建文件监听失败: {e}"))?;
+        watcher
+            .watch(path, RecursiveMode::NonRecursive)
+            .map_err(|e| format!("监听 {} 失败: {e}", path.display()))?;
+
+        let entries = fs::read_dir(path).map_err(|e| format!("读取文件夹失败: {e}"))?;
+        {
+            let mut pending = self.pending.lock().expect("folder watch lock");
+            for entry in entries.flatten() {
+                mark_pending(&mut pending, folder_id, entry.path());
+            }
+        }
+
+        self.watchers
+            .lock()
+            .expect("folder watch lock")
+            .insert(folder_id, watcher);
+        tracing::info!(folder_id, path = %path.display(), "Watching folder");
+        Ok(())
+    }
+
+    /// 停止监听并丢弃该文件夹尚未导入的文件
+    pub fn unwatch(&self, folder_id: i64) {
+        if self
+            .watchers
+            .lock()
+            .expect("folder watch lock")
+            .remove(&folder_id)
+            .is_some()
+        {
+            tracing::info!(folder_id, "Stopped watching folder");
+        }
+        self.pending
+            .lock()
+            .expect("folder watch lock")
+            .retain(|_, file| file.folder_id != folder_id);
+    }
+
+    /// 取出静默期内没有再变化的文件
+    fn take_settled(&self) -> Vec<(i64, PathBuf)> {
+        let now = Instant::now();
+        let mut pending = self.pending.lock().expect("folder watch lock");
+        let settled: Vec<(i64, PathBuf)> = pending
+            .iter()
+            .filter(|(_, file)| now.duration_since(file.last_seen) >= SETTLE_DELAY)
+            .map(|(path, file)| (file.folder_id, path.clone()))
+            .collect();
+        for (_, path) in &settled {
+            pending.remove(path);
+        }
+        settled
+    }
+}
+
+impl Default for FolderWatchService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn mark_pending(pending: &mut HashMap<PathBuf, PendingFile>, folder_id: i64, path: PathBuf) {
+    if is_ignored_file(&path) {
+        return;
+    }
+    pending.insert(
+        path,
+        PendingFile {
+            folder_id,
+            last_seen: Instant::now(),
+        },
+    );
+}
+
+/// 隐藏文件、Office 锁文件与浏览器 / 下载工具的临时文件
+fn is_ignored_file(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return true;
+    };
+    name.starts_with('.')
+        || name.starts_with("~$")
+        || matches!(
+            get_extension(name).as_deref(),
+            Some("crdownload" | "part" | "partial" | "download" | "tmp")
+        )
+}
+
+/// 目标目录中不冲突的文件路径：重名时追加 ` (n)`
+fn unique_target(dir: &Path, file_name: &str) -> PathBuf {
+    let target = dir.join(file_name);
+    if !target.exists() {
+        return target;
+    }
+    let (stem, ext) = match file_name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => (stem, Some(ext)),
+        _ => (file_name, None),
+    };
+    (1..)
+        .map(|n| match ext {
+            Some(ext) => dir.join(format!("{stem} ({n}).{ext}")),
+            None => dir.join(format!("{stem} ({n})")),
+        })
+        .find(|candidate| !candidate.exists())
+        .expect("unbounded candidates")
+}
+
+/// 移动文件；跨磁盘时退化为复制后删除
+fn move_file(path: &Path, dir: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(dir)?;
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let target = unique_target(dir, &file_name);
+    if fs::rename(path, &target).is_err() {
+        fs::copy(path, &target)?;
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+async fn import_file(
+    app: &AppHandle,
+    state: &AppState,
+    folder: &WatchedFolderRecord,
+    path: &Path,
+) -> AppResult<ImportOutcome> {
+    if !path.is_file() {
+        return Ok(ImportOutcome::Skipped);
+    }
+    let source_path = path.to_string_lossy().to_string();
+    let ext = get_extension(&source_path);
+    let subtype = parse_file_type_from_extension(ext.as_deref());
+    if subtype == ResourceSubtype::Other {
+        return Ok(ImportOutcome::Skipped);
+    }
+
+    let bytes = fs::read(path)?;
+    let file_hash = compute_sha256(&bytes);
+    let outcome = match find_resource_by_hash(&state.db, &file_hash).await? {
+        Some(existing_id) => ImportOutcome::Duplicate(existing_id),
+        None => {
+            let builder = NodeBuilder::resource();
+            let file_name = match &ext {
+                Some(ext) => format!("{}.{ext}", builder.get_uuid()),
+                None => builder.get_uuid().to_string(),
+            };
+            fs::write(get_assets_dir(app)?.join(&file_name), &bytes)?;
+            let stored_path = format!("assets/{file_name}");
+            let title = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| "Untitled".to_string());
+            let meta = SourceMeta {
+                url: None,
+                window_title: None,
+                process_name: Some(FOLDER_WATCH_SOURCE.to_string()),
+                captured_at: Some(chrono::Utc::now().to_rfc3339()),
+                process_path: Some(folder.path.clone()),
+                sender: None,
+                sent_at: None,
+                auto_title: None,
+            };
+
+            let node_id = builder
+                .title(title)
+                .file_hash(Some(&file_hash))
+                .file_path(Some(&stored_path))
+                .resource_subtype(Some(subtype))
+                .source_meta(Some(meta.clone()))
+                .insert(&state.db)
+                .await?;
+            if let Err(err) = apply_source_tag_rules(&state.db, node_id, &meta).await {
+                tracing::warn!(node_id, error = %err, "Apply source tag rules failed");
+            }
+            let resolved_path = resolve_file_path(app, &stored_path)?;
+            parse_and_enqueue(
+                app,
+                state,
+                node_id,
+                subtype,
+                None,
+                Some(&resolved_path),
+                &file_hash,
+                JobPriority::Normal,
+            )
+            .await?;
+            ImportOutcome::Imported(node_id)
+        }
+    };
+
+    // 重复的文件同样移走，保持收件箱清空
+    if let Some(move_to) = folder.move_to.as_deref() {
+        move_file(path, Path::new(move_to))?;
+    }
+    Ok(outcome)
+}
+
+async fn import_pending_file(app: &AppHandle, state: &AppState, folder_id: i64, path: &Path) {
+    let folder = match get_watched_folder(&state.db, folder_id).await {
+        Ok(Some(folder)) if folder.is_enabled => folder,
+        // 已删除或停用
+        Ok(_) => return,
+        Err(err) => {
+            tracing::warn!(folder_id, error = %err, "Load watched folder failed");
+            return;
+        }
+    };
+
+    let file_path = path.to_string_lossy().to_string();
+    let (node_id, status, error) = match import_file(app, state, &folder, path).await {
+        Ok(ImportOutcome::Skipped) => return,
+        Ok(ImportOutcome::Imported(node_id)) => (Some(node_id), "imported", None),
+        Ok(ImportOutcome::Duplicate(node_id)) => (Some(node_id), "duplicate", None),
+        Err(err) => (None, "error", Some(err.to_string())),
+    };
+
+    let recorded = match (&error, status) {
+        (Some(err), _) => {
+            tracing::warn!(folder_id, path = %file_path, error = %err, "Folder import failed");
+            set_watched_folder_error(&state.db, folder_id, err).await
+        }
+        (None, "imported") => {
+            tracing::info!(folder_id, path = %file_path, "Imported watched file");
+            record_watched_folder_import(&state.db, folder_id).await
+        }
+        _ => Ok(()),
+    };
+    if let Err(err) = recorded {
+        tracing::warn!(folder_id, error = %err, "Record folder import failed");
+    }
+
+    emit_event(
+        app,
+        &FolderImportPayload {
+            folder_id,
+            file_path,
+            node_id,
+            status,
+            error,
+        },
+    );
+}
+
+/// 恢复已启用的监听；失败记录在文件夹的 last_error 中
+async fn restore_watches(state: &AppState) {
+    let folders = match list_watched_folders(&state.db).await {
+        Ok(folders) => folders,
+        Err(err) => {
+            tracing::warn!(error = %err, "Load watched folders failed");
+            return;
+        }
+    };
+    for folder in folders.iter().filter(|folder| folder.is_enabled) {
+        let folder_id = folder.folder_id;
+        if let Err(err) = state.folder_watch.watch(folder_id, Path::new(&folder.path)) {
+            tracing::warn!(folder_id, error = %err, "Restore folder watch failed");
+            if let Err(err) = set_watched_folder_error(&state.db, folder_id, &err).await {
+                tracing::warn!(folder_id, error = %err, "Record folder watch error failed");
+            }
+        }
+    }
+}
+
+/// 恢复已启用的监听，并在后台定期导入静默下来的文件
+pub fn spawn_folder_watcher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        if let Some(state) = app.try_state::<AppState>() {
+            restore_watches(&state).await;
+        }
+
+        loop {
+            tokio::time::sleep(SCAN_INTERVAL).await;
+            let Some(state) = app.try_state::<AppState>() else {
+                continue;
+            };
+            for (folder_id, path) in state.folder_watch.take_settled() {
+                import_pending_file(&app, &state, folder_id, &path).await;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ignores_hidden_and_partial_downloads() {
+        assert!(is_ignored_file(Path::new("/inbox/.DS_Store")));
+        assert!(is_ignored_file(Path::new("/inbox/~$report.docx")));
+        assert!(is_ignored_file(Path::new("/inbox/paper.pdf.crdownload")));
+        assert!(is_ignored_file(Path::new("/inbox/video.mp4.part")));
+        assert!(!is_ignored_file(Path::new("/inbox/paper.pdf")));
+    }
+
+    #[test]
+    fn picks_unused_target_name() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(unique_target(dir.path(), "a.pdf"), dir.path().join("a.pdf"));
+        fs::write(dir.path().join("a.pdf"), b"1").unwrap();
+        fs::write(dir.path().join("a (1).pdf"), b"2").unwrap();
+        assert_eq!(
+            unique_target(dir.path(), "a.pdf"),
+            dir.path().join("a (2).pdf")
+        );
+        fs::write(dir.path().join("notes"), b"3").unwrap();
+        assert_eq!(
+            unique_target(dir.path(), "notes"),
+            dir.path().join("notes (1)")
+        );
+    }
+}
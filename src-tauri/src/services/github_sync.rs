@@ -0,0 +1,516 @@
+//! GitHub issues / PR 同步
+//!
+//! 每个仓库绑定一个项目主题：issue 与 PR 同步为主题下的任务，状态映射到 task_status，
+//! 非空正文另存为文本资源并入队生成向量，便于检索。同一编号始终对应同一个任务。
+//! 只有 GitHub 侧的标题、状态或正文相对上次同步发生变化时才覆盖本地节点，保留用户在本地的修改。
+
+use std::time::Duration;
+
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tokio::sync::Mutex;
+
+use super::{AiPipeline, JobPriority};
+use crate::app_state::AppState;
+use crate::db::{
+    get_github_item, get_github_repo_token, get_node_by_id, insert_edge_if_missing,
+    list_due_github_repos, mark_task_cancelled, mark_task_done, mark_task_todo, record_github_sync,
+    update_node_content, update_node_title, upsert_github_item, DbPool, EdgeRelationType,
+    GithubItemRecord, GithubRepoRecord, NewEdge, NewGithubItem, NodeBuilder, ResourceSubtype,
+    ReviewStatus, SourceMeta, TaskStatus,
+};
+use crate::utils::compute_sha256;
+use crate::utils::crypto::CryptoService;
+
+const API_BASE: &str = "https://api.github.com";
+const USER_AGENT: &str = "NeuralVault";
+const PER_PAGE: usize = 100;
+/// 单次同步最多拉取的页数，其余条目由下一次同步继续
+const MAX_PAGES: u32 = 10;
+const FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+const SCHEDULER_INITIAL_DELAY: Duration = Duration::from_secs(45);
+const SCHEDULER_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 手动同步与后台同步互斥，避免同一条目被并发创建
+static SYNC_LOCK: Mutex<()> = Mutex::const_new(());
+
+#[derive(Debug, Deserialize)]
+struct GithubIssue {
+    number: i64,
+    title: String,
+    body: Option<String>,
+    state: String,
+    state_reason: Option<String>,
+    html_url: String,
+    updated_at: String,
+    pull_request: Option<GithubPullRequestRef>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubPullRequestRef {
+    merged_at: Option<String>,
+}
+
+/// 一次同步的统计
+#[derive(Debug, Default, Serialize)]
+pub struct GithubSyncSummary {
+    pub created: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+}
+
+/// 解析 `owner/name` 或 GitHub 仓库 URL，返回 (owner, name)
+pub fn parse_repo_slug(input: &str) -> Option<(String, String)> {
+    let trimmed = input.trim().trim_end_matches('/');
+    let path = ["https://github.com/", "http://github.com/", "github.com/"]
+        .iter()
+        .find_map(|prefix| {
+            trimmed
+                .get(..prefix.len())
+                .filter(|head| head.eq_ignore_ascii_case(prefix))
+                .map(|_| &trimmed[prefix.len()..])
+        })
+        .unwrap_or(trimmed);
+
+    let mut parts = path.split('/');
+    let owner = parts.next()?;
+    let name = parts.next()?;
+    let name = name.strip_suffix(".git").unwrap_or(name);
+    let valid = |s: &str| {
+        !s.is_empty()
+            && s.chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    };
+    // URL 可以带 /issues 等后缀，纯 slug 只允许两段
+    if !valid(owner) || !valid(name) || (path == trimmed && parts.next().is_some()) {
+        return None;
+    }
+    Some((owner.to_string(), name.to_string()))
+}
+
+/// GitHub 状态映射：返回 (github_state, 任务状态)
+///
+/// 未合并即关闭的 PR 与 not_planned 的 issue 视为取消，其余关闭视为完成
+fn item_state(issue: &GithubIssue) -> (&'static str, TaskStatus) {
+    if issue.state != "closed" {
+        return ("open", TaskStatus::Todo);
+    }
+    match &issue.pull_request {
+        Some(pr) if pr.merged_at.is_some() => ("merged", TaskStatus::Done),
+        Some(_) => ("closed", TaskStatus::Cancelled),
+        None if issue.state_reason.as_deref() == Some("not_planned") => {
+            ("not_planned", TaskStatus::Cancelled)
+        }
+        None => ("closed", TaskStatus::Done),
+    }
+}
+
+/// 拉取仓库自上次游标以来更新的 issue / PR 并同步，结果（含错误）记录到仓库上
+pub async fn sync_github_repo(
+    db: &DbPool,
+    pipeline: &AiPipeline,
+    repo: &GithubRepoRecord,
+) -> Result<GithubSyncSummary, String> {
+    let _guard = SYNC_LOCK.lock().await;
+    // 按 updated_at 升序处理，游标随已处理的条目前移；中途失败时下次从失败处继续
+    let mut cursor: Option<String> = None;
+    let result = async {
+        let token = load_token(db, repo.repo_id).await?;
+        let client = Client::builder()
+            .timeout(FETCH_TIMEOUT)
+            .build()
+            .map_err(|e| e.to_string())?;
+        let mut summary = GithubSyncSummary::default();
+
+        for page in 1..=MAX_PAGES {
+            let issues = fetch_issues_page(&client, repo, token.as_deref(), page).await?;
+            for issue in &issues {
+                sync_issue(db, pipeline, repo, issue, &mut summary)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                if cursor
+                    .as_deref()
+                    .map_or(true, |c| issue.updated_at.as_str() > c)
+                {
+                    cursor = Some(issue.updated_at.clone());
+                }
+            }
+            if issues.len() < PER_PAGE {
+                break;
+            }
+        }
+        Ok(summary)
+    }
+    .await;
+
+    let error = result.as_ref().err().map(String::as_str);
+    record_github_sync(db, repo.repo_id, cursor.as_deref(), error)
+        .await
+        .map_err(|e| e.to_string())?;
+    result
+}
+
+async fn load_token(db: &DbPool, repo_id: i64) -> Result<Option<String>, String> {
+    let Some(encrypted) = get_github_repo_token(db, repo_id)
+        .await
+        .map_err(|e| e.to_string())?
+    else {
+        return Ok(None);
+    };
+    let plain = CryptoService::new()?.decrypt(&encrypted)?;
+    String::from_utf8(plain)
+        .map(Some)
+        .map_err(|e| format!("令牌解码失败: {e}"))
+}
+
+async fn fetch_issues_page(
+    client: &Client,
+    repo: &GithubRepoRecord,
+    token: Option<&str>,
+    page: u32,
+) -> Result<Vec<GithubIssue>, String> {
+    let url = format!("{API_BASE}/repos/{}/{}/issues", repo.owner, repo.name);
+    let mut query = vec![
+        ("state", "all".to_string()),
+        ("sort", "updated".to_string()),
+        ("direction", "asc".to_string()),
+        ("per_page", PER_PAGE.to_string()),
+        ("page", page.to_string()),
+    ];
+    if let Some(since) = repo.since_cursor.as_deref() {
+        query.push(("since", since.to_string()));
+    }
+
+    let mut request = client
+        .get(&url)
+        .query(&query)
+        .header(reqwest::header::USER_AGENT, USER_AGENT)
+        .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+        .header("X-GitHub-Api-Version", "2022-11-28");
+    if let Some(token) = token {
+        request = request.bearer_auth(token);
+    }
+    let response = request
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| format!("拉取 GitHub 条目失败: {e}"))?;
+    response
+        .json()
+        .await
+        .map_err(|e| format!("解析 GitHub 响应失败: {e}"))
+}
+
+async fn sync_issue(
+    db: &DbPool,
+    pipeline: &AiPipeline,
+    repo: &GithubRepoRecord,
+    issue: &GithubIssue,
+    summary: &mut GithubSyncSummary,
+) -> Result<(), sqlx::Error> {
+    let (github_state, status) = item_state(issue);
+    let title = format!("#{} {}", issue.number, issue.title.trim());
+    let body = issue
+        .body
+        .as_deref()
+        .map(str::trim)
+        .filter(|body| !body.is_empty());
+    let body_hash = body.map(|body| compute_sha256(body.as_bytes()));
+
+    let existing = get_github_item(db, repo.repo_id, issue.number).await?;
+    let Some(existing) = existing else {
+        let task_id = NodeBuilder::task()
+            .title(title.as_str())
+            .user_note(Some(issue.html_url.as_str()))
+            .insert(db)
+            .await?;
+        apply_task_status(db, task_id, status).await?;
+        link_contains(db, repo.topic_id, task_id).await?;
+        let body_node_id = match body {
+            Some(body) => Some(create_body_node(db, pipeline, repo, issue, task_id, body).await?),
+            None => None,
+        };
+        upsert_github_item(
+            db,
+            mapping(
+                repo,
+                issue,
+                task_id,
+                body_node_id,
+                github_state,
+                body_hash.as_deref(),
+            ),
+        )
+        .await?;
+        summary.created += 1;
+        return Ok(());
+    };
+
+    // since 参数是闭区间，游标处的条目会被重复返回
+    if issue.updated_at <= existing.github_updated_at {
+        summary.unchanged += 1;
+        return Ok(());
+    }
+
+    let node = get_node_by_id(db, existing.task_node_id).await?;
+    let mut changed = false;
+    if !node.is_deleted {
+        if issue.title != existing.synced_title && node.title != title {
+            update_node_title(db, node.node_id, &title).await?;
+            changed = true;
+        }
+        if github_state != existing.github_state && node.task_status != Some(status) {
+            apply_task_status(db, node.node_id, status).await?;
+            changed = true;
+        }
+    }
+
+    let mut body_node_id = existing.body_node_id;
+    if body_hash != existing.body_hash {
+        // 正文在 GitHub 上被清空时保留已有资源
+        if let Some(body) = body {
+            body_node_id = Some(
+                update_body_node(db, pipeline, repo, issue, &existing, node.node_id, body).await?,
+            );
+            changed = true;
+        }
+    }
+
+    upsert_github_item(
+        db,
+        mapping(
+            repo,
+            issue,
+            existing.task_node_id,
+            body_node_id,
+            github_state,
+            body_hash.as_deref(),
+        ),
+    )
+    .await?;
+    if changed {
+        summary.updated += 1;
+    } else {
+        summary.unchanged += 1;
+    }
+    Ok(())
+}
+
+fn mapping<'a>(
+    repo: &GithubRepoRecord,
+    issue: &'a GithubIssue,
+    task_node_id: i64,
+    body_node_id: Option<i64>,
+    github_state: &'a str,
+    body_hash: Option<&'a str>,
+) -> NewGithubItem<'a> {
+    NewGithubItem {
+        repo_id: repo.repo_id,
+        number: issue.number,
+        is_pull_request: issue.pull_request.is_some(),
+        task_node_id,
+        body_node_id,
+        synced_title: &issue.title,
+        github_state,
+        body_hash,
+        github_updated_at: &issue.updated_at,
+    }
+}
+
+async fn apply_task_status(
+    db: &DbPool,
+    node_id: i64,
+    status: TaskStatus,
+) -> Result<(), sqlx::Error> {
+    match status {
+        TaskStatus::Todo => mark_task_todo(db, node_id).await,
+        TaskStatus::Done => mark_task_done(db, node_id).await,
+        TaskStatus::Cancelled => mark_task_cancelled(db, node_id).await,
+    }
+}
+
+async fn link_contains(db: &DbPool, topic_id: i64, node_id: i64) -> Result<(), sqlx::Error> {
+    insert_edge_if_missing(
+        db,
+        NewEdge {
+            source_node_id: topic_id,
+            target_node_id: node_id,
+            relation_type: EdgeRelationType::Contains,
+            confidence_score: None,
+            is_manual: true,
+        },
+    )
+    .await?;
+    Ok(())
+}
+
+/// 正文资源：归入项目主题并与任务互相关联，入队生成摘要与向量
+async fn create_body_node(
+    db: &DbPool,
+    pipeline: &AiPipeline,
+    repo: &GithubRepoRecord,
+    issue: &GithubIssue,
+    task_id: i64,
+    body: &str,
+) -> Result<i64, sqlx::Error> {
+    let node_id = NodeBuilder::resource()
+        .title(format!(
+            "{}/{}#{} {}",
+            repo.owner,
+            repo.name,
+            issue.number,
+            issue.title.trim()
+        ))
+        .file_content(Some(body))
+        .file_hash(Some(compute_sha256(body.as_bytes())))
+        .resource_subtype(Some(ResourceSubtype::Text))
+        .source_meta(Some(SourceMeta {
+            url: Some(issue.html_url.clone()),
+            window_title: None,
+            process_name: Some("GitHub".to_string()),
+            captured_at: None,
+            process_path: None,
+            sender: None,
+            sent_at: None,
+            auto_title: None,
+        }))
+        .review_status(ReviewStatus::Reviewed)
+        .insert(db)
+        .await?;
+    link_contains(db, repo.topic_id, node_id).await?;
+    insert_edge_if_missing(
+        db,
+        NewEdge {
+            source_node_id: task_id.min(node_id),
+            target_node_id: task_id.max(node_id),
+            relation_type: EdgeRelationType::RelatedTo,
+            confidence_score: None,
+            is_manual: true,
+        },
+    )
+    .await?;
+    enqueue(pipeline, node_id).await;
+    Ok(node_id)
+}
+
+/// 更新正文资源；资源已被删除时重新创建
+async fn update_body_node(
+    db: &DbPool,
+    pipeline: &AiPipeline,
+    repo: &GithubRepoRecord,
+    issue: &GithubIssue,
+    existing: &GithubItemRecord,
+    task_id: i64,
+    body: &str,
+) -> Result<i64, sqlx::Error> {
+    if let Some(node_id) = existing.body_node_id {
+        if !get_node_by_id(db, node_id).await?.is_deleted {
+            let hash = compute_sha256(body.as_bytes());
+            update_node_content(db, node_id, Some(body), Some(&hash)).await?;
+            enqueue(pipeline, node_id).await;
+            return Ok(node_id);
+        }
+    }
+    create_body_node(db, pipeline, repo, issue, task_id, body).await
+}
+
+async fn enqueue(pipeline: &AiPipeline, node_id: i64) {
+    if let Err(err) = pipeline.enqueue_resource(node_id, JobPriority::Bulk).await {
+        tracing::warn!(node_id, error = %err, "Enqueue GitHub body resource failed");
+    }
+}
+
+/// 后台轮询到期的仓库
+pub fn spawn_github_sync_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(SCHEDULER_INITIAL_DELAY).await;
+        loop {
+            if let Some(state) = app.try_state::<AppState>() {
+                match list_due_github_repos(&state.db).await {
+                    Ok(repos) => {
+                        for repo in repos {
+                            match sync_github_repo(&state.db, &state.ai_pipeline, &repo).await {
+                                Ok(summary) => tracing::debug!(
+                                    repo_id = repo.repo_id,
+                                    created = summary.created,
+                                    updated = summary.updated,
+                                    "GitHub repo synced"
+                                ),
+                                Err(err) => tracing::warn!(
+                                    repo_id = repo.repo_id,
+                                    error = %err,
+                                    "GitHub repo sync failed"
+                                ),
+                            }
+                        }
+                    }
+                    Err(err) => tracing::warn!(error = %err, "Query GitHub repos failed"),
+                }
+            }
+            tokio::time::sleep(SCHEDULER_INTERVAL).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(state: &str, state_reason: Option<&str>, pr: Option<Option<&str>>) -> GithubIssue {
+        GithubIssue {
+            number: 1,
+            title: "t".to_string(),
+            body: None,
+            state: state.to_string(),
+            state_reason: state_reason.map(str::to_string),
+            html_url: String::new(),
+            updated_at: String::new(),
+            pull_request: pr.map(|merged_at| GithubPullRequestRef {
+                merged_at: merged_at.map(str::to_string),
+            }),
+        }
+    }
+
+    #[test]
+    fn parses_repo_slugs() {
+        let expected = Some(("rust-lang".to_string(), "rust".to_string()));
+        assert_eq!(parse_repo_slug("rust-lang/rust"), expected);
+        assert_eq!(
+            parse_repo_slug("https://github.com/rust-lang/rust.git"),
+            expected
+        );
+        assert_eq!(
+            parse_repo_slug("github.com/rust-lang/rust/issues/"),
+            expected
+        );
+        assert_eq!(parse_repo_slug("rust-lang"), None);
+        assert_eq!(parse_repo_slug("rust-lang/rust/extra"), None);
+        assert_eq!(parse_repo_slug("https://gitlab.com/a/b"), None);
+    }
+
+    #[test]
+    fn maps_states_to_task_status() {
+        assert_eq!(item_state(&issue("open", None, None)).1, TaskStatus::Todo);
+        assert_eq!(
+            item_state(&issue("closed", Some("completed"), None)).1,
+            TaskStatus::Done
+        );
+        assert_eq!(
+            item_state(&issue("closed", Some("not_planned"), None)),
+            ("not_planned", TaskStatus::Cancelled)
+        );
+        assert_eq!(
+            item_state(&issue("closed", None, Some(Some("2024-01-01T00:00:00Z")))),
+            ("merged", TaskStatus::Done)
+        );
+        assert_eq!(
+            item_state(&issue("closed", None, Some(None))).1,
+            TaskStatus::Cancelled
+        );
+        assert_eq!(
+            item_state(&issue("open", None, Some(None))).1,
+            TaskStatus::Todo
+        );
+    }
+}
@@ -0,0 +1,292 @@
+//! 月度目标回顾
+//!
+//! 汇总上个月各主题的活动（新增资源、完成 / 停滞任务），交给 LLM 起草回顾，
+//! 保存为文本资源并推送通知。后台任务定期检查，每个周期只自动生成一次。
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{Datelike, Local, NaiveDate};
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::app_state::AppState;
+use crate::db::{
+    get_goal_review_by_period, list_review_tasks, list_topic_activity, upsert_goal_review,
+    NewNotification, NodeBuilder, NotificationKind, ResourceSubtype, ReviewStatus, ReviewTaskRow,
+    TaskStatus, TopicActivityRow,
+};
+use crate::error::{AppError, AppResult};
+use crate::i18n::MessageCode;
+use crate::services::{get_processing_config, push_notification, GoalReviewDraft, JobPriority};
+use crate::utils::compute_sha256;
+
+const SCHEDULER_INITIAL_DELAY: Duration = Duration::from_secs(60);
+const SCHEDULER_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+/// 每个主题最多列出的任务数，避免 prompt 过长
+const MAX_TASKS_PER_TOPIC: usize = 8;
+
+/// 回顾周期（自然月）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewPeriod {
+    /// YYYY-MM
+    pub label: String,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl ReviewPeriod {
+    fn month(year: i32, month: u32) -> Option<Self> {
+        let start = NaiveDate::from_ymd_opt(year, month, 1)?;
+        let next = if month == 12 {
+            NaiveDate::from_ymd_opt(year + 1, 1, 1)?
+        } else {
+            NaiveDate::from_ymd_opt(year, month + 1, 1)?
+        };
+        Some(Self {
+            label: format!("{year:04}-{month:02}"),
+            start,
+            end: next.pred_opt()?,
+        })
+    }
+
+    /// today 所在月份的上一个月
+    pub fn previous_month(today: NaiveDate) -> Self {
+        let (year, month) = if today.month() == 1 {
+            (today.year() - 1, 12)
+        } else {
+            (today.year(), today.month() - 1)
+        };
+        Self::month(year, month).expect("valid previous month")
+    }
+
+    /// 解析 YYYY-MM
+    pub fn parse(raw: &str) -> AppResult<Self> {
+        let invalid = || AppError::coded_with(MessageCode::InvalidReviewPeriod, "value", raw);
+        let (year, month) = raw.trim().split_once('-').ok_or_else(invalid)?;
+        let year: i32 = year.parse().map_err(|_| invalid())?;
+        let month: u32 = month.parse().map_err(|_| invalid())?;
+        Self::month(year, month).ok_or_else(invalid)
+    }
+}
+
+/// 回顾生成结果
+#[derive(Debug, Serialize)]
+pub struct GoalReviewResult {
+    pub period: String,
+    pub node_id: i64,
+    pub draft: GoalReviewDraft,
+    pub topics: Vec<TopicActivityRow>,
+}
+
+/// 生成指定周期的回顾：保存为资源、记录到 goal_reviews 并推送通知
+pub async fn generate_goal_review(
+    state: &AppState,
+    app: &AppHandle,
+    period: &ReviewPeriod,
+) -> AppResult<GoalReviewResult> {
+    let start = period.start.format("%Y-%m-%d").to_string();
+    let end = period.end.format("%Y-%m-%d").to_string();
+
+    let topics = list_topic_activity(&state.db, &start, &end).await?;
+    if topics.is_empty() {
+        return Err(AppError::coded_with(
+            MessageCode::NoReviewActivity,
+            "period",
+            &period.label,
+        ));
+    }
+    let tasks = list_review_tasks(&state.db, &start, &end).await?;
+
+    let ai = state.ai.wait_ready().await?;
+    let (provider, model, _, provider_config) = get_processing_config(&state.ai_config).await?;
+    let activity = build_activity_context(&topics, &tasks);
+    let draft = ai
+        .agent
+        .draft_goal_review(
+            &provider,
+            &model,
+            &provider_config,
+            &period.label,
+            &activity,
+        )
+        .await?;
+
+    let content = render_goal_review(&period.label, &draft);
+    let file_hash = compute_sha256(content.as_bytes());
+    let builder = NodeBuilder::resource()
+        .title(format!("{} 月度回顾", period.label))
+        .file_content(Some(content.as_str()))
+        .file_hash(Some(file_hash))
+        .resource_subtype(Some(ResourceSubtype::Text))
+        .review_status(ReviewStatus::Reviewed);
+    let node_id = topics
+        .iter()
+        .fold(builder, |builder, topic| {
+            builder.derived_from(topic.topic_id)
+        })
+        .insert(&state.db)
+        .await?;
+    upsert_goal_review(&state.db, &period.label, node_id).await?;
+
+    // 入队生成摘要与向量，便于之后检索；失败不影响回顾本身
+    if let Err(err) = state
+        .ai_pipeline
+        .enqueue_resource(node_id, JobPriority::Normal)
+        .await
+    {
+        tracing::warn!(node_id, error = %err, "Enqueue goal review resource failed");
+    }
+
+    push_notification(
+        &state.db,
+        app,
+        NewNotification {
+            kind: NotificationKind::System,
+            title: &format!("{} 月度回顾已生成", period.label),
+            body: Some(&draft.summary),
+            node_id: Some(node_id),
+            action: Some("open_resource"),
+        },
+    )
+    .await?;
+
+    Ok(GoalReviewResult {
+        period: period.label.clone(),
+        node_id,
+        draft,
+        topics,
+    })
+}
+
+/// 后台定期检查上个月的回顾是否已生成
+pub fn spawn_goal_review_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(SCHEDULER_INITIAL_DELAY).await;
+        loop {
+            if let Some(state) = app.try_state::<AppState>() {
+                let period = ReviewPeriod::previous_month(Local::now().date_naive());
+                match get_goal_review_by_period(&state.db, &period.label).await {
+                    Ok(Some(_)) => {}
+                    Ok(None) => match generate_goal_review(&state, &app, &period).await {
+                        Ok(result) => {
+                            tracing::info!(
+                                period = %result.period,
+                                node_id = result.node_id,
+                                "Goal review generated"
+                            );
+                        }
+                        Err(err) => {
+                            tracing::debug!(period = %period.label, error = %err, "Goal review skipped");
+                        }
+                    },
+                    Err(err) => {
+                        tracing::warn!(error = %err, "Query goal review failed");
+                    }
+                }
+            }
+            tokio::time::sleep(SCHEDULER_INTERVAL).await;
+        }
+    });
+}
+
+fn build_activity_context(topics: &[TopicActivityRow], tasks: &[ReviewTaskRow]) -> String {
+    let mut tasks_by_topic: HashMap<i64, Vec<&ReviewTaskRow>> = HashMap::new();
+    for task in tasks {
+        tasks_by_topic.entry(task.topic_id).or_default().push(task);
+    }
+
+    let mut lines = Vec::new();
+    for topic in topics {
+        lines.push(format!(
+            "## {}（新增资源 {}，完成任务 {}，未完成任务 {}，其中停滞 {}）",
+            topic.title,
+            topic.resources_added,
+            topic.tasks_completed,
+            topic.tasks_open,
+            topic.tasks_stalled
+        ));
+        if let Some(summary) = topic.summary.as_deref().filter(|s| !s.trim().is_empty()) {
+            lines.push(format!("简介：{}", summary.trim()));
+        }
+        for task in tasks_by_topic
+            .get(&topic.topic_id)
+            .into_iter()
+            .flatten()
+            .take(MAX_TASKS_PER_TOPIC)
+        {
+            let label = if task.task_status == TaskStatus::Done {
+                "已完成"
+            } else {
+                "停滞"
+            };
+            lines.push(format!("- [{}] {}", label, task.title));
+        }
+    }
+    lines.join("\n")
+}
+
+fn render_goal_review(period: &str, draft: &GoalReviewDraft) -> String {
+    let mut lines = vec![format!("# {} 月度回顾", period), String::new()];
+    if !draft.summary.is_empty() {
+        lines.push(draft.summary.clone());
+        lines.push(String::new());
+    }
+    for (heading, items) in [
+        ("进展", &draft.progressed),
+        ("停滞", &draft.stalled),
+        ("下一步", &draft.next_actions),
+    ] {
+        if items.is_empty() {
+            continue;
+        }
+        lines.push(format!("## {}", heading));
+        lines.extend(items.iter().map(|item| format!("- {}", item.trim())));
+        lines.push(String::new());
+    }
+    lines.join("\n").trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_previous_month() {
+        let period = ReviewPeriod::previous_month(d("2025-03-15"));
+        assert_eq!(period.label, "2025-02");
+        assert_eq!(period.start, d("2025-02-01"));
+        assert_eq!(period.end, d("2025-02-28"));
+
+        let period = ReviewPeriod::previous_month(d("2025-01-01"));
+        assert_eq!(period.label, "2024-12");
+        assert_eq!(period.end, d("2024-12-31"));
+    }
+
+    #[test]
+    fn test_parse_period() {
+        let period = ReviewPeriod::parse("2024-02").unwrap();
+        assert_eq!(period.end, d("2024-02-29"));
+        assert!(ReviewPeriod::parse("2024-13").is_err());
+        assert!(ReviewPeriod::parse("202402").is_err());
+    }
+
+    #[test]
+    fn test_render_goal_review_skips_empty_sections() {
+        let draft = GoalReviewDraft {
+            summary: "稳步推进".to_string(),
+            progressed: vec!["完成论文初稿".to_string()],
+            stalled: Vec::new(),
+            next_actions: vec!["投稿".to_string()],
+        };
+        let rendered = render_goal_review("2025-01", &draft);
+        assert!(rendered.starts_with("# 2025-01 月度回顾"));
+        assert!(rendered.contains("## 进展\n- 完成论文初稿"));
+        assert!(!rendered.contains("## 停滞"));
+        assert!(rendered.ends_with("- 投稿"));
+    }
+}
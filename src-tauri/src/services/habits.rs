@@ -0,0 +1,177 @@
+//! 习惯打卡：连续达成（streak）计算与概览
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::{Datelike, Local, NaiveDate};
+use serde::Serialize;
+
+use crate::db::{list_habit_log_dates, list_habits, DbPool, HabitFrequency, HabitRecord};
+
+/// 习惯概览（Dashboard / 习惯列表共用）
+#[derive(Debug, Serialize)]
+pub struct HabitOverview {
+    #[serde(flatten)]
+    pub habit: HabitRecord,
+    /// 当前周期（今天 / 本周）已打卡次数
+    pub period_count: i64,
+    pub completed_today: bool,
+    pub current_streak: i64,
+    pub longest_streak: i64,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct HabitStreak {
+    pub current: i64,
+    pub longest: i64,
+}
+
+/// 日期所属周期编号：daily 为天序号，weekly 为周序号（周一开始）
+fn period_index(frequency: HabitFrequency, date: NaiveDate) -> i64 {
+    let days = i64::from(date.num_days_from_ce());
+    match frequency {
+        HabitFrequency::Daily => days,
+        HabitFrequency::Weekly => {
+            let monday = days - i64::from(date.weekday().num_days_from_monday());
+            monday.div_euclid(7)
+        }
+    }
+}
+
+fn period_counts(frequency: HabitFrequency, dates: &[NaiveDate]) -> BTreeMap<i64, i64> {
+    let mut counts = BTreeMap::new();
+    for date in dates.iter().collect::<BTreeSet<_>>() {
+        *counts.entry(period_index(frequency, *date)).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// 计算当前与最长连续达成周期数
+///
+/// 当前周期尚未达成时不算断签，从上一个周期开始往前数。
+pub fn compute_streaks(
+    frequency: HabitFrequency,
+    target_count: i64,
+    dates: &[NaiveDate],
+    today: NaiveDate,
+) -> HabitStreak {
+    let target = target_count.max(1);
+    let achieved: BTreeSet<i64> = period_counts(frequency, dates)
+        .into_iter()
+        .filter(|(_, count)| *count >= target)
+        .map(|(period, _)| period)
+        .collect();
+
+    let current_period = period_index(frequency, today);
+    let mut cursor = if achieved.contains(&current_period) {
+        current_period
+    } else {
+        current_period - 1
+    };
+    let mut current = 0;
+    while achieved.contains(&cursor) {
+        current += 1;
+        cursor -= 1;
+    }
+
+    let mut longest = 0;
+    let mut run = 0;
+    let mut previous: Option<i64> = None;
+    for period in &achieved {
+        run = match previous {
+            Some(prev) if prev + 1 == *period => run + 1,
+            _ => 1,
+        };
+        longest = longest.max(run);
+        previous = Some(*period);
+    }
+
+    HabitStreak { current, longest }
+}
+
+pub async fn build_habit_overview(
+    db: &DbPool,
+    habit: HabitRecord,
+) -> Result<HabitOverview, String> {
+    let today = Local::now().date_naive();
+    let dates: Vec<NaiveDate> = list_habit_log_dates(db, habit.node_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .iter()
+        .filter_map(|raw| NaiveDate::parse_from_str(raw, "%Y-%m-%d").ok())
+        .collect();
+
+    let streak = compute_streaks(habit.frequency, habit.target_count, &dates, today);
+    let period_count = period_counts(habit.frequency, &dates)
+        .get(&period_index(habit.frequency, today))
+        .copied()
+        .unwrap_or(0);
+
+    Ok(HabitOverview {
+        period_count,
+        completed_today: dates.contains(&today),
+        current_streak: streak.current,
+        longest_streak: streak.longest,
+        habit,
+    })
+}
+
+pub async fn list_habit_overviews(db: &DbPool) -> Result<Vec<HabitOverview>, String> {
+    let habits = list_habits(db).await.map_err(|e| e.to_string())?;
+    let mut overviews = Vec::with_capacity(habits.len());
+    for habit in habits {
+        overviews.push(build_habit_overview(db, habit).await?);
+    }
+    Ok(overviews)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(raw: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(raw, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_daily_streak() {
+        let dates = [
+            d("2025-01-01"),
+            d("2025-01-02"),
+            d("2025-01-03"),
+            d("2025-01-05"),
+            d("2025-01-06"),
+        ];
+        // 今天未打卡，从昨天往前数
+        let streak = compute_streaks(HabitFrequency::Daily, 1, &dates, d("2025-01-07"));
+        assert_eq!(
+            streak,
+            HabitStreak {
+                current: 2,
+                longest: 3
+            }
+        );
+
+        let streak = compute_streaks(HabitFrequency::Daily, 1, &dates, d("2025-01-08"));
+        assert_eq!(streak.current, 0);
+    }
+
+    #[test]
+    fn test_weekly_streak_with_target() {
+        // 2025-01-06 与 2025-01-13 均为周一
+        let dates = [
+            d("2025-01-06"),
+            d("2025-01-08"),
+            d("2025-01-13"),
+            d("2025-01-14"),
+            d("2025-01-20"),
+        ];
+        let streak = compute_streaks(HabitFrequency::Weekly, 2, &dates, d("2025-01-21"));
+        assert_eq!(
+            streak,
+            HabitStreak {
+                current: 2,
+                longest: 2
+            }
+        );
+    }
+}
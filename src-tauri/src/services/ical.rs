@@ -0,0 +1,770 @@
+//! iCalendar (.ics) 解析与导出
+//!
+//! 只解析 VEVENT 的 UID、SUMMARY、DESCRIPTION、LOCATION、DTSTART/DTEND、RRULE、RECURRENCE-ID、
+//! STATUS，以及其中 VALARM 的 TRIGGER。UTC 时间（`Z` 结尾）换算为本地时间，带 TZID 的时间按本地时间处理。
+//! 重复规则支持 FREQ/INTERVAL/COUNT/UNTIL，BYDAY 等细分规则忽略（按 DTSTART 的节奏重复）。
+//!
+//! 导出写出同样的字段，时间为不带时区的浮动时间，由日历应用按本地时区显示。
+
+use chrono::{Datelike, Local, Months, NaiveDate, NaiveDateTime, TimeZone, Utc};
+
+/// 事件时间：全天事件只有日期
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IcsTime {
+    Date(NaiveDate),
+    /// 本地时间
+    DateTime(NaiveDateTime),
+}
+
+impl IcsTime {
+    pub fn naive(&self) -> NaiveDateTime {
+        match self {
+            Self::Date(date) => date.and_hms_opt(0, 0, 0).expect("midnight"),
+            Self::DateTime(dt) => *dt,
+        }
+    }
+
+    /// 同类型的另一个时间（全天事件仍只保留日期）
+    pub fn with(&self, dt: NaiveDateTime) -> Self {
+        match self {
+            Self::Date(_) => Self::Date(dt.date()),
+            Self::DateTime(_) => Self::DateTime(dt),
+        }
+    }
+
+    /// 与 due_date 一致的存储格式
+    pub fn to_db_string(&self) -> String {
+        match self {
+            Self::Date(date) => date.format("%Y-%m-%d").to_string(),
+            Self::DateTime(dt) => dt.format("%Y-%m-%d %H:%M:%S").to_string(),
+        }
+    }
+}
+
+/// 日历中的一个事件
+#[derive(Debug, Clone, PartialEq)]
+pub struct IcsEvent {
+    pub uid: String,
+    pub summary: String,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub start: IcsTime,
+    pub end: Option<IcsTime>,
+    /// 原始 RRULE 值
+    pub rrule: Option<String>,
+    /// 重复事件中单次发生的改动（移动或取消），与所属系列同 UID，值为原本的发生时间
+    pub recurrence_id: Option<IcsTime>,
+    pub cancelled: bool,
+    /// 提醒相对开始时间的提前量（分钟）；多个 VALARM 取最早的一个
+    pub reminder_minutes: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// 简化的 RRULE
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recurrence {
+    pub frequency: Frequency,
+    pub interval: u32,
+    pub count: Option<u32>,
+    pub until: Option<NaiveDateTime>,
+}
+
+impl Recurrence {
+    /// 解析 RRULE 值；不支持的频率（如 HOURLY）返回 None
+    pub fn parse(rule: &str) -> Option<Self> {
+        let mut frequency = None;
+        let mut interval = 1;
+        let mut count = None;
+        let mut until = None;
+        for part in rule.split(';') {
+            let Some((key, value)) = part.split_once('=') else {
+                continue;
+            };
+            match key.trim().to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.trim().to_ascii_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        _ => return None,
+                    })
+                }
+                "INTERVAL" => interval = value.trim().parse::<u32>().ok().filter(|&n| n > 0)?,
+                "COUNT" => count = value.trim().parse::<u32>().ok(),
+                "UNTIL" => {
+                    until = parse_time(value, &[]).map(|time| match time {
+                        IcsTime::Date(date) => date.and_hms_opt(23, 59, 59).expect("end of day"),
+                        IcsTime::DateTime(dt) => dt,
+                    })
+                }
+                _ => {}
+            }
+        }
+        Some(Self {
+            frequency: frequency?,
+            interval,
+            count,
+            until,
+        })
+    }
+
+    /// 第 n 次（从 0 开始）发生时间
+    fn nth(&self, start: NaiveDateTime, n: u32) -> Option<NaiveDateTime> {
+        let steps = n.checked_mul(self.interval)?;
+        match self.frequency {
+            Frequency::Daily => start.checked_add_days(chrono::Days::new(steps.into())),
+            Frequency::Weekly => {
+                start.checked_add_days(chrono::Days::new(u64::from(steps).checked_mul(7)?))
+            }
+            Frequency::Monthly => start.checked_add_months(Months::new(steps)),
+            Frequency::Yearly => start.checked_add_months(Months::new(steps.checked_mul(12)?)),
+        }
+    }
+
+    /// 不早于 after 的第一次发生时间；系列已结束返回 None
+    pub fn next_occurrence(
+        &self,
+        start: NaiveDateTime,
+        after: NaiveDateTime,
+    ) -> Option<NaiveDateTime> {
+        // 先估算一个不超过目标的次数，再逐次前移
+        let mut n = if after <= start {
+            0
+        } else {
+            let estimate = match self.frequency {
+                Frequency::Daily | Frequency::Weekly => {
+                    let days_per_step = if self.frequency == Frequency::Daily {
+                        1
+                    } else {
+                        7
+                    };
+                    (after - start).num_days() / (days_per_step * i64::from(self.interval))
+                }
+                Frequency::Monthly | Frequency::Yearly => {
+                    let months_per_step = if self.frequency == Frequency::Monthly {
+                        1
+                    } else {
+                        12
+                    };
+                    let months = i64::from(after.year() - start.year()) * 12
+                        + i64::from(after.month())
+                        - i64::from(start.month());
+                    months / (months_per_step * i64::from(self.interval))
+                }
+            };
+            u32::try_from((estimate - 1).max(0)).ok()?
+        };
+        loop {
+            if self.count.is_some_and(|count| n >= count) {
+                return None;
+            }
+            let occurrence = self.nth(start, n)?;
+            if self.until.is_some_and(|until| occurrence > until) {
+                return None;
+            }
+            if occurrence >= after {
+                return Some(occurrence);
+            }
+            n += 1;
+        }
+    }
+}
+
+/// VEVENT 解析中间态
+#[derive(Default)]
+struct EventDraft {
+    uid: Option<String>,
+    summary: Option<String>,
+    description: Option<String>,
+    location: Option<String>,
+    start: Option<IcsTime>,
+    end: Option<IcsTime>,
+    rrule: Option<String>,
+    recurrence_id: Option<IcsTime>,
+    cancelled: bool,
+    triggers: Vec<Trigger>,
+}
+
+enum Trigger {
+    /// 相对开始（或结束）时间的偏移分钟数，负数表示之前
+    Relative {
+        minutes: i64,
+        from_end: bool,
+    },
+    Absolute(NaiveDateTime),
+}
+
+impl EventDraft {
+    fn finish(self) -> Option<IcsEvent> {
+        let start = self.start?;
+        let start_dt = start.naive();
+        let end_dt = self.end.map(|end| end.naive()).unwrap_or(start_dt);
+        let reminder_minutes = self
+            .triggers
+            .iter()
+            .map(|trigger| match trigger {
+                Trigger::Relative { minutes, from_end } => {
+                    let base = if *from_end { end_dt } else { start_dt };
+                    (start_dt - base).num_minutes() - minutes
+                }
+                Trigger::Absolute(at) => (start_dt - *at).num_minutes(),
+            })
+            .max();
+        Some(IcsEvent {
+            uid: self.uid.filter(|uid| !uid.trim().is_empty())?,
+            summary: self.summary.unwrap_or_default(),
+            description: self.description.filter(|text| !text.trim().is_empty()),
+            location: self.location.filter(|text| !text.trim().is_empty()),
+            start,
+            end: self.end,
+            rrule: self.rrule,
+            recurrence_id: self.recurrence_id,
+            cancelled: self.cancelled,
+            reminder_minutes,
+        })
+    }
+}
+
+/// 解析 .ics 文本；缺少 UID 或 DTSTART 的事件跳过
+pub fn parse_ics(text: &str) -> Result<Vec<IcsEvent>, String> {
+    let lines = unfold_lines(text.trim_start_matches('\u{feff}'));
+    if !lines
+        .iter()
+        .any(|line| line.eq_ignore_ascii_case("BEGIN:VCALENDAR"))
+    {
+        return Err("缺少 BEGIN:VCALENDAR".to_string());
+    }
+
+    let mut events = Vec::new();
+    let mut draft: Option<EventDraft> = None;
+    let mut in_alarm = false;
+
+    for line in &lines {
+        let Some((name, params, value)) = parse_content_line(line) else {
+            continue;
+        };
+        match (name.as_str(), value.trim().to_ascii_uppercase().as_str()) {
+            ("BEGIN", "VEVENT") => {
+                draft = Some(EventDraft::default());
+                continue;
+            }
+            ("END", "VEVENT") => {
+                if let Some(event) = draft.take().and_then(EventDraft::finish) {
+                    events.push(event);
+                }
+                continue;
+            }
+            ("BEGIN", "VALARM") => {
+                in_alarm = true;
+                continue;
+            }
+            ("END", "VALARM") => {
+                in_alarm = false;
+                continue;
+            }
+            _ => {}
+        }
+        let Some(draft) = draft.as_mut() else {
+            continue;
+        };
+
+        if in_alarm {
+            if name == "TRIGGER" {
+                if let Some(trigger) = parse_trigger(&params, value) {
+                    draft.triggers.push(trigger);
+                }
+            }
+            continue;
+        }
+        match name.as_str() {
+            "UID" => draft.uid = Some(value.trim().to_string()),
+            "SUMMARY" => draft.summary = Some(unescape_text(value)),
+            "DESCRIPTION" => draft.description = Some(unescape_text(value)),
+            "LOCATION" => draft.location = Some(unescape_text(value)),
+            "DTSTART" => draft.start = parse_time(value, &params),
+            "DTEND" => draft.end = parse_time(value, &params),
+            "RRULE" => draft.rrule = Some(value.trim().to_string()),
+            "RECURRENCE-ID" => draft.recurrence_id = parse_time(value, &params),
+            "STATUS" => draft.cancelled = value.trim().eq_ignore_ascii_case("CANCELLED"),
+            _ => {}
+        }
+    }
+
+    Ok(events)
+}
+
+/// 展开折行（以空格或制表符开头的行接续上一行）
+fn unfold_lines(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in text.split('\n') {
+        let raw = raw.strip_suffix('\r').unwrap_or(raw);
+        if let (Some(rest), Some(last)) = (raw.strip_prefix([' ', '\t']), lines.last_mut()) {
+            last.push_str(rest);
+        } else if !raw.trim().is_empty() {
+            lines.push(raw.to_string());
+        }
+    }
+    lines
+}
+
+/// `NAME;PARAM=V:VALUE` 拆为大写属性名、参数与值（引号内的 `:` / `;` 不作分隔）
+fn parse_content_line(line: &str) -> Option<(String, Vec<(String, String)>, &str)> {
+    let mut in_quotes = false;
+    let mut separators = Vec::new();
+    let mut colon = None;
+    for (idx, c) in line.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            ';' if !in_quotes => separators.push(idx),
+            ':' if !in_quotes => {
+                colon = Some(idx);
+                break;
+            }
+            _ => {}
+        }
+    }
+    let colon = colon?;
+    let mut bounds = separators;
+    bounds.push(colon);
+
+    let name = line[..bounds[0]].trim().to_ascii_uppercase();
+    let params = bounds
+        .windows(2)
+        .filter_map(|pair| {
+            let (key, value) = line[pair[0] + 1..pair[1]].split_once('=')?;
+            Some((
+                key.trim().to_ascii_uppercase(),
+                value.trim().trim_matches('"').to_string(),
+            ))
+        })
+        .collect();
+    Some((name, params, &line[colon + 1..]))
+}
+
+fn param<'a>(params: &'a [(String, String)], key: &str) -> Option<&'a str> {
+    params
+        .iter()
+        .find(|(name, _)| name == key)
+        .map(|(_, value)| value.as_str())
+}
+
+/// 解析 DATE / DATE-TIME 值
+fn parse_time(value: &str, params: &[(String, String)]) -> Option<IcsTime> {
+    let value = value.trim();
+    if param(params, "VALUE").is_some_and(|v| v.eq_ignore_ascii_case("DATE")) || value.len() == 8 {
+        return NaiveDate::parse_from_str(value, "%Y%m%d")
+            .ok()
+            .map(IcsTime::Date);
+    }
+    match value.strip_suffix(['Z', 'z']) {
+        Some(utc) => {
+            let dt = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+            let local = Utc.from_utc_datetime(&dt).with_timezone(&Local);
+            Some(IcsTime::DateTime(local.naive_local()))
+        }
+        None => NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")
+            .ok()
+            .map(IcsTime::DateTime),
+    }
+}
+
+fn parse_trigger(params: &[(String, String)], value: &str) -> Option<Trigger> {
+    if param(params, "VALUE").is_some_and(|v| v.eq_ignore_ascii_case("DATE-TIME")) {
+        return parse_time(value, &[]).map(|time| Trigger::Absolute(time.naive()));
+    }
+    Some(Trigger::Relative {
+        minutes: parse_duration_minutes(value)?,
+        from_end: param(params, "RELATED").is_some_and(|v| v.eq_ignore_ascii_case("END")),
+    })
+}
+
+/// 解析 ISO 8601 时长（如 `-PT15M`、`-P1DT2H`、`P1W`），返回带符号的分钟数
+pub fn parse_duration_minutes(raw: &str) -> Option<i64> {
+    let raw = raw.trim();
+    let (sign, rest) = match raw.as_bytes().first()? {
+        b'-' => (-1, &raw[1..]),
+        b'+' => (1, &raw[1..]),
+        _ => (1, raw),
+    };
+    let rest = rest.strip_prefix(['P', 'p'])?;
+
+    let mut total_seconds: i64 = 0;
+    let mut number = String::new();
+    let mut in_time = false;
+    for c in rest.chars() {
+        match c.to_ascii_uppercase() {
+            '0'..='9' => number.push(c),
+            'T' if number.is_empty() => in_time = true,
+            unit => {
+                let n: i64 = number.parse().ok()?;
+                number.clear();
+                let unit_seconds = match (unit, in_time) {
+                    ('W', false) => 7 * 86_400,
+                    ('D', false) => 86_400,
+                    ('H', true) => 3_600,
+                    ('M', true) => 60,
+                    ('S', true) => 1,
+                    _ => return None,
+                };
+                total_seconds = total_seconds.checked_add(n.checked_mul(unit_seconds)?)?;
+            }
+        }
+    }
+    if !number.is_empty() {
+        return None;
+    }
+    Some(sign * total_seconds / 60)
+}
+
+/// TEXT 值反转义：`\n` `\,` `\;` `\\`
+fn unescape_text(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out.trim().to_string()
+}
+
+const PRODID: &str = "-//NeuralVault//Tasks//ZH";
+/// 内容行最长 75 字节（不含 CRLF），超出部分折到下一行
+const MAX_LINE_OCTETS: usize = 75;
+
+/// 生成 .ics 文本：CRLF 换行，长行按 RFC 5545 折行
+///
+/// stamp 为 DTSTAMP（UTC）；全天事件缺少结束时间时补上次日的 DTEND
+pub fn write_ics(calendar_name: &str, events: &[IcsEvent], stamp: NaiveDateTime) -> String {
+    let stamp = stamp.format("%Y%m%dT%H%M%SZ").to_string();
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:{PRODID}"),
+        "CALSCALE:GREGORIAN".to_string(),
+        format!("X-WR-CALNAME:{}", escape_text(calendar_name)),
+    ];
+
+    for event in events {
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}", event.uid));
+        lines.push(format!("DTSTAMP:{stamp}"));
+        lines.push(format_time("DTSTART", event.start));
+        let end = event.end.or(match event.start {
+            IcsTime::Date(date) => date.succ_opt().map(IcsTime::Date),
+            IcsTime::DateTime(_) => None,
+        });
+        if let Some(end) = end {
+            lines.push(format_time("DTEND", end));
+        }
+        lines.push(format!("SUMMARY:{}", escape_text(&event.summary)));
+        if let Some(description) = &event.description {
+            lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+        }
+        if let Some(location) = &event.location {
+            lines.push(format!("LOCATION:{}", escape_text(location)));
+        }
+        if let Some(rrule) = &event.rrule {
+            lines.push(format!("RRULE:{rrule}"));
+        }
+        if let Some(recurrence_id) = event.recurrence_id {
+            lines.push(format_time("RECURRENCE-ID", recurrence_id));
+        }
+        if event.cancelled {
+            lines.push("STATUS:CANCELLED".to_string());
+        }
+        if let Some(minutes) = event.reminder_minutes {
+            lines.push("BEGIN:VALARM".to_string());
+            lines.push("ACTION:DISPLAY".to_string());
+            lines.push(format!("DESCRIPTION:{}", escape_text(&event.summary)));
+            lines.push(format!("TRIGGER:{}", format_trigger(minutes)));
+            lines.push("END:VALARM".to_string());
+        }
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut out = String::new();
+    for line in &lines {
+        fold_line(line, &mut out);
+    }
+    out
+}
+
+fn format_time(name: &str, time: IcsTime) -> String {
+    match time {
+        IcsTime::Date(date) => format!("{name};VALUE=DATE:{}", date.format("%Y%m%d")),
+        IcsTime::DateTime(dt) => format!("{name}:{}", dt.format("%Y%m%dT%H%M%S")),
+    }
+}
+
+/// 提前 minutes 分钟的 TRIGGER 值（如 `-PT15M`），负数表示开始之后
+fn format_trigger(minutes: i64) -> String {
+    if minutes >= 0 {
+        format!("-PT{minutes}M")
+    } else {
+        format!("PT{}M", -minutes)
+    }
+}
+
+/// TEXT 值转义，与 unescape_text 相反
+fn escape_text(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            ';' => out.push_str("\\;"),
+            ',' => out.push_str("\\,"),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// 写出一行并按字节折行，不拆开多字节字符；续行以空格开头
+fn fold_line(line: &str, out: &mut String) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > MAX_LINE_OCTETS {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dt(raw: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(raw, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn test_parse_ics() {
+        let ics = "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+BEGIN:VEVENT\r\n\
+UID:evt-1@example.com\r\n\
+SUMMARY:Team sync\\, weekly\r\n\
+DESCRIPTION:Agenda:\\nreview PRs and plan the next \r\n sprint\r\n\
+LOCATION;ALTREP=\"https://example.com:8080/room\":Room 1\r\n\
+DTSTART;TZID=\"Europe/Berlin\":20240105T090000\r\n\
+DTEND;TZID=Europe/Berlin:20240105T100000\r\n\
+RRULE:FREQ=WEEKLY;COUNT=10\r\n\
+BEGIN:VALARM\r\n\
+TRIGGER:-PT15M\r\n\
+ACTION:DISPLAY\r\n\
+END:VALARM\r\n\
+BEGIN:VALARM\r\n\
+TRIGGER;RELATED=END:-PT90M\r\n\
+END:VALARM\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:evt-2\r\n\
+SUMMARY:Holiday\r\n\
+DTSTART;VALUE=DATE:20240201\r\n\
+STATUS:CANCELLED\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+SUMMARY:No uid\r\n\
+DTSTART:20240201T100000\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+        let events = parse_ics(ics).unwrap();
+        assert_eq!(events.len(), 2);
+
+        let event = &events[0];
+        assert_eq!(event.uid, "evt-1@example.com");
+        assert_eq!(event.summary, "Team sync, weekly");
+        assert_eq!(
+            event.description.as_deref(),
+            Some("Agenda:\nreview PRs and plan the next sprint")
+        );
+        assert_eq!(event.location.as_deref(), Some("Room 1"));
+        assert_eq!(event.start, IcsTime::DateTime(dt("2024-01-05 09:00")));
+        assert_eq!(event.start.to_db_string(), "2024-01-05 09:00:00");
+        assert_eq!(event.rrule.as_deref(), Some("FREQ=WEEKLY;COUNT=10"));
+        // 结束前 90 分钟 = 开始前 30 分钟，比 15 分钟更早
+        assert_eq!(event.reminder_minutes, Some(30));
+        assert!(!event.cancelled);
+
+        let holiday = &events[1];
+        assert_eq!(holiday.start.to_db_string(), "2024-02-01");
+        assert!(holiday.cancelled);
+        assert_eq!(holiday.reminder_minutes, None);
+
+        assert!(parse_ics("not a calendar").is_err());
+    }
+
+    #[test]
+    fn test_parse_cancelled_occurrence() {
+        let ics = "BEGIN:VCALENDAR\r\n\
+BEGIN:VEVENT\r\n\
+UID:standup\r\n\
+SUMMARY:Standup\r\n\
+DTSTART:20240101T090000\r\n\
+RRULE:FREQ=DAILY\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:standup\r\n\
+SUMMARY:Standup\r\n\
+RECURRENCE-ID:20240103T090000\r\n\
+DTSTART:20240103T090000\r\n\
+STATUS:CANCELLED\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:standup\r\n\
+SUMMARY:Standup (moved)\r\n\
+RECURRENCE-ID;VALUE=DATE-TIME:20240104T090000\r\n\
+DTSTART:20240104T140000\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+        let events = parse_ics(ics).unwrap();
+        assert_eq!(events.len(), 3);
+        assert!(events.iter().all(|event| event.uid == "standup"));
+
+        let series = &events[0];
+        assert_eq!(series.recurrence_id, None);
+        assert!(!series.cancelled);
+
+        let cancelled = &events[1];
+        assert_eq!(
+            cancelled.recurrence_id,
+            Some(IcsTime::DateTime(dt("2024-01-03 09:00")))
+        );
+        assert!(cancelled.cancelled);
+
+        let moved = &events[2];
+        assert_eq!(
+            moved.recurrence_id,
+            Some(IcsTime::DateTime(dt("2024-01-04 09:00")))
+        );
+        assert_eq!(moved.start, IcsTime::DateTime(dt("2024-01-04 14:00")));
+        assert!(!moved.cancelled);
+    }
+
+    #[test]
+    fn test_parse_utc_time() {
+        let time = parse_time("20240105T090000Z", &[]).unwrap();
+        let expected = Utc
+            .from_utc_datetime(&dt("2024-01-05 09:00"))
+            .with_timezone(&Local)
+            .naive_local();
+        assert_eq!(time, IcsTime::DateTime(expected));
+    }
+
+    #[test]
+    fn test_parse_duration_minutes() {
+        assert_eq!(parse_duration_minutes("-PT15M"), Some(-15));
+        assert_eq!(parse_duration_minutes("-P1DT2H"), Some(-(24 + 2) * 60));
+        assert_eq!(parse_duration_minutes("P1W"), Some(7 * 24 * 60));
+        assert_eq!(parse_duration_minutes("PT0S"), Some(0));
+        assert_eq!(parse_duration_minutes("+PT30M"), Some(30));
+        assert_eq!(parse_duration_minutes("P1M"), None);
+        assert_eq!(parse_duration_minutes("15M"), None);
+        assert_eq!(parse_duration_minutes("P99999999999999999W"), None);
+        assert_eq!(
+            parse_duration_minutes("P9999999999999W9999999999999W"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_recurrence_next_occurrence() {
+        let weekly = Recurrence::parse("FREQ=WEEKLY;INTERVAL=2").unwrap();
+        let start = dt("2024-01-01 09:00");
+        assert_eq!(
+            weekly.next_occurrence(start, dt("2024-01-10 00:00")),
+            Some(dt("2024-01-15 09:00"))
+        );
+        assert_eq!(
+            weekly.next_occurrence(start, dt("2023-12-01 00:00")),
+            Some(start)
+        );
+
+        let monthly = Recurrence::parse("FREQ=MONTHLY").unwrap();
+        assert_eq!(
+            monthly.next_occurrence(dt("2024-01-31 08:00"), dt("2024-02-10 00:00")),
+            Some(dt("2024-02-29 08:00"))
+        );
+
+        let counted = Recurrence::parse("FREQ=DAILY;COUNT=3").unwrap();
+        assert_eq!(
+            counted.next_occurrence(start, dt("2024-01-03 00:00")),
+            Some(dt("2024-01-03 09:00"))
+        );
+        assert_eq!(counted.next_occurrence(start, dt("2024-01-04 00:00")), None);
+
+        let until = Recurrence::parse("FREQ=YEARLY;UNTIL=20241231").unwrap();
+        assert_eq!(until.next_occurrence(start, dt("2024-06-01 00:00")), None);
+
+        assert!(Recurrence::parse("FREQ=HOURLY").is_none());
+        assert!(Recurrence::parse("INTERVAL=2").is_none());
+    }
+
+    #[test]
+    fn test_write_ics_round_trip() {
+        let events = vec![
+            IcsEvent {
+                uid: "task-1@neuralvault".to_string(),
+                summary: "周会；整理 backlog, 并且写一段足够长的标题来触发折行逻辑的测试"
+                    .to_string(),
+                description: Some("line 1\nline 2, with \\ backslash".to_string()),
+                location: None,
+                start: IcsTime::DateTime(dt("2024-01-05 09:00")),
+                end: None,
+                rrule: Some("FREQ=WEEKLY;BYDAY=FR".to_string()),
+                recurrence_id: None,
+                cancelled: false,
+                reminder_minutes: Some(15),
+            },
+            IcsEvent {
+                uid: "task-2@neuralvault".to_string(),
+                summary: "Holiday".to_string(),
+                description: None,
+                location: None,
+                start: IcsTime::Date(NaiveDate::from_ymd_opt(2024, 2, 1).unwrap()),
+                end: None,
+                rrule: None,
+                recurrence_id: None,
+                cancelled: true,
+                reminder_minutes: None,
+            },
+        ];
+
+        let text = write_ics("Tasks", &events, dt("2024-01-01 00:00"));
+        assert!(text.ends_with("END:VCALENDAR\r\n"));
+        assert!(text.split("\r\n").all(|line| line.len() <= MAX_LINE_OCTETS));
+        assert!(text.contains("DTSTART:20240105T090000\r\n"));
+        assert!(text.contains("DTEND;VALUE=DATE:20240202\r\n"));
+        assert!(text.contains("TRIGGER:-PT15M\r\n"));
+
+        let parsed = parse_ics(&text).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0], events[0]);
+        assert_eq!(parsed[1].start, events[1].start);
+        assert!(parsed[1].cancelled);
+    }
+}
@@ -0,0 +1,364 @@
+//! 收件箱整理
+//!
+//! 把待审核或不属于任何主题的资源分批交给 LLM，为每个资源建议一种处理方式：归入主题、
+//! 转为任务、归档或移入回收站。建议经过校验（资源必须在本批中、主题必须存在）后连同差异描述
+//! 返回给前端；用户勾选后由 [`apply_inbox_triage`] 逐条执行，单条失败不影响其余。
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::app_state::AppState;
+use crate::db::{
+    convert_resource_to_container, list_inbox_resources, list_topic_tree, move_resource_to_topic,
+    soft_delete_node, update_node_archived, update_resource_review_status, DbPool, NodeType,
+    ReviewStatus, TopicTreeRow, UnreviewedClassificationRecord,
+};
+use crate::services::{get_processing_config, InboxTriageDraft};
+use crate::{AppError, AppResult};
+
+/// 每次 LLM 请求包含的资源数
+const TRIAGE_BATCH_SIZE: usize = 20;
+/// 单次整理最多扫描的资源数
+pub const MAX_TRIAGE_ITEMS: i64 = 200;
+/// 资源摘要在 prompt 中的最大字符数
+const ITEM_SUMMARY_MAX_CHARS: usize = 120;
+
+/// 对单个资源的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum TriageAction {
+    /// 移到该主题下（替换原有的主题归属），并标记为已审核
+    LinkTopic {
+        topic_id: i64,
+    },
+    ConvertToTask,
+    Archive,
+    /// 移入回收站
+    Delete,
+}
+
+/// 校验后的单条建议
+#[derive(Debug, Clone, Serialize)]
+pub struct TriageSuggestion {
+    pub node_id: i64,
+    pub title: String,
+    #[serde(flatten)]
+    pub action: TriageAction,
+    pub reason: Option<String>,
+    /// 差异描述，例如「归入主题「读书」」
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InboxTriagePlan {
+    /// 扫描的资源数
+    pub scanned: usize,
+    pub suggestions: Vec<TriageSuggestion>,
+    /// 被校验丢弃的建议数（不含建议保持原样的）
+    pub discarded: usize,
+    /// 请求失败的批次数，这些资源没有建议
+    pub failed_batches: usize,
+}
+
+/// 用户确认执行的一条处理
+#[derive(Debug, Clone, Deserialize)]
+pub struct TriageDecision {
+    pub node_id: i64,
+    #[serde(flatten)]
+    pub action: TriageAction,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TriageFailure {
+    pub node_id: i64,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TriageApplyReport {
+    pub applied: usize,
+    pub failed: Vec<TriageFailure>,
+}
+
+/// 分批请求 LLM 生成处理建议（不修改数据）；单批失败只记录日志
+pub async fn plan_inbox_triage(state: &AppState, limit: i64) -> Result<InboxTriagePlan, String> {
+    let items = list_inbox_resources(&state.db, limit)
+        .await
+        .map_err(|e| e.to_string())?;
+    if items.is_empty() {
+        return Ok(InboxTriagePlan {
+            scanned: 0,
+            suggestions: Vec::new(),
+            discarded: 0,
+            failed_batches: 0,
+        });
+    }
+    let topics = list_topic_tree(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let ai = state.ai.wait_ready().await?;
+    let (provider, model, _, provider_config) = get_processing_config(&state.ai_config).await?;
+    let topic_list = render_topic_list(&topics);
+
+    let mut plan = InboxTriagePlan {
+        scanned: items.len(),
+        suggestions: Vec::new(),
+        discarded: 0,
+        failed_batches: 0,
+    };
+    for batch in items.chunks(TRIAGE_BATCH_SIZE) {
+        let drafts = match ai
+            .agent
+            .triage_inbox(
+                &provider,
+                &model,
+                &provider_config,
+                &topic_list,
+                &render_inbox_items(batch),
+            )
+            .await
+        {
+            Ok(drafts) => drafts,
+            Err(err) => {
+                tracing::warn!(error = %err, "Inbox triage batch failed");
+                plan.failed_batches += 1;
+                continue;
+            }
+        };
+        let (suggestions, discarded) = validate_triage_drafts(drafts, batch, &topics);
+        plan.suggestions.extend(suggestions);
+        plan.discarded += discarded;
+    }
+    Ok(plan)
+}
+
+fn render_topic_list(topics: &[TopicTreeRow]) -> String {
+    topics
+        .iter()
+        .map(|topic| format!("- {} (id={})", topic.title, topic.node_id))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 每个资源一行：id、类型、创建时间、标题、摘要与 AI 建议的主题
+fn render_inbox_items(items: &[UnreviewedClassificationRecord]) -> String {
+    items
+        .iter()
+        .map(|item| {
+            let subtype = item
+                .resource_subtype
+                .and_then(|subtype| serde_json::to_value(subtype).ok())
+                .and_then(|value| value.as_str().map(str::to_string))
+                .unwrap_or_else(|| "resource".to_string());
+            let mut line = format!(
+                "- id={} [{}] {} {}",
+                item.node_id,
+                subtype,
+                item.created_at.as_deref().unwrap_or_default(),
+                item.title
+            );
+            if let Some(summary) = item
+                .summary
+                .as_deref()
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+            {
+                let summary: String = summary.chars().take(ITEM_SUMMARY_MAX_CHARS).collect();
+                line.push_str(&format!("：{summary}"));
+            }
+            if let Some(topic_title) = &item.topic_title {
+                line.push_str(&format!("（自动分类建议：{topic_title}）"));
+            }
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 校验 LLM 建议并生成差异描述，返回建议与丢弃数
+///
+/// 丢弃：不在本批中的资源、同一资源的重复建议、不存在的主题与无法识别的操作；
+/// keep 表示保持原样，不计入丢弃数。
+fn validate_triage_drafts(
+    drafts: Vec<InboxTriageDraft>,
+    items: &[UnreviewedClassificationRecord],
+    topics: &[TopicTreeRow],
+) -> (Vec<TriageSuggestion>, usize) {
+    let titles: HashMap<i64, &str> = items
+        .iter()
+        .map(|item| (item.node_id, item.title.as_str()))
+        .collect();
+    let topic_titles: HashMap<i64, &str> = topics
+        .iter()
+        .map(|topic| (topic.node_id, topic.title.as_str()))
+        .collect();
+    let mut seen = HashSet::new();
+    let mut suggestions = Vec::new();
+    let mut discarded = 0;
+
+    for draft in drafts {
+        let Some(title) = titles.get(&draft.node_id) else {
+            discarded += 1;
+            continue;
+        };
+        if !seen.insert(draft.node_id) {
+            discarded += 1;
+            continue;
+        }
+        let (action, description) = match draft.action.trim() {
+            "keep" => continue,
+            "link_topic" => match draft
+                .topic_id
+                .and_then(|topic_id| Some((topic_id, topic_titles.get(&topic_id)?)))
+            {
+                Some((topic_id, topic_title)) => (
+                    TriageAction::LinkTopic { topic_id },
+                    format!("归入主题「{topic_title}」"),
+                ),
+                None => {
+                    discarded += 1;
+                    continue;
+                }
+            },
+            "convert_to_task" => (TriageAction::ConvertToTask, "转为任务".to_string()),
+            "archive" => (TriageAction::Archive, "归档".to_string()),
+            "delete" => (TriageAction::Delete, "移入回收站".to_string()),
+            _ => {
+                discarded += 1;
+                continue;
+            }
+        };
+        suggestions.push(TriageSuggestion {
+            node_id: draft.node_id,
+            title: title.to_string(),
+            action,
+            reason: draft
+                .reason
+                .map(|reason| reason.trim().to_string())
+                .filter(|reason| !reason.is_empty()),
+            description,
+        });
+    }
+    (suggestions, discarded)
+}
+
+/// 逐条执行用户确认的处理，返回成功数与失败明细
+pub async fn apply_inbox_triage(db: &DbPool, decisions: &[TriageDecision]) -> TriageApplyReport {
+    let mut report = TriageApplyReport {
+        applied: 0,
+        failed: Vec::new(),
+    };
+    for decision in decisions {
+        match apply_decision(db, decision).await {
+            Ok(()) => report.applied += 1,
+            Err(err) => {
+                tracing::warn!(node_id = decision.node_id, error = %err, "Inbox triage action failed");
+                report.failed.push(TriageFailure {
+                    node_id: decision.node_id,
+                    error: err.to_string(),
+                });
+            }
+        }
+    }
+    tracing::debug!(
+        applied = report.applied,
+        failed = report.failed.len(),
+        "Inbox triage applied"
+    );
+    report
+}
+
+async fn apply_decision(db: &DbPool, decision: &TriageDecision) -> AppResult<()> {
+    let node_id = decision.node_id;
+    match decision.action {
+        TriageAction::LinkTopic { topic_id } => {
+            move_resource_to_topic(db, node_id, topic_id).await?;
+            update_resource_review_status(db, node_id, ReviewStatus::Reviewed).await?;
+        }
+        TriageAction::ConvertToTask => {
+            convert_resource_to_container(db, node_id, NodeType::Task).await?;
+        }
+        TriageAction::Archive => {
+            if !update_node_archived(db, node_id, true).await? {
+                return Err(AppError::NotFound {
+                    entity: "node",
+                    id: node_id,
+                });
+            }
+            update_resource_review_status(db, node_id, ReviewStatus::Reviewed).await?;
+        }
+        TriageAction::Delete => soft_delete_node(db, node_id).await?,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(node_id: i64, title: &str) -> UnreviewedClassificationRecord {
+        UnreviewedClassificationRecord {
+            node_id,
+            title: title.to_string(),
+            summary: None,
+            resource_subtype: None,
+            created_at: None,
+            topic_id: None,
+            topic_title: None,
+            confidence_score: None,
+        }
+    }
+
+    fn draft(node_id: i64, action: &str, topic_id: Option<i64>) -> InboxTriageDraft {
+        InboxTriageDraft {
+            node_id,
+            action: action.to_string(),
+            topic_id,
+            reason: Some(" 理由 ".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_validate_triage_drafts() {
+        let items = [item(1, "a"), item(2, "b"), item(3, "c"), item(4, "d")];
+        let topics = [TopicTreeRow {
+            node_id: 10,
+            title: "读书".to_string(),
+            summary: None,
+            item_count: 0,
+        }];
+        let drafts = vec![
+            draft(1, "link_topic", Some(10)),
+            draft(1, "delete", None),
+            draft(2, "link_topic", Some(99)),
+            draft(3, "keep", None),
+            draft(4, "archive", None),
+            draft(5, "delete", None),
+            draft(4, "explode", None),
+        ];
+
+        let (suggestions, discarded) = validate_triage_drafts(drafts, &items, &topics);
+        assert_eq!(discarded, 4);
+        assert_eq!(suggestions.len(), 2);
+        assert_eq!(
+            suggestions[0].action,
+            TriageAction::LinkTopic { topic_id: 10 }
+        );
+        assert_eq!(suggestions[0].description, "归入主题「读书」");
+        assert_eq!(suggestions[0].reason.as_deref(), Some("理由"));
+        assert_eq!(suggestions[1].node_id, 4);
+        assert_eq!(suggestions[1].action, TriageAction::Archive);
+    }
+
+    #[test]
+    fn test_triage_decision_deserializes_flat_action() {
+        let decision: TriageDecision =
+            serde_json::from_str(r#"{"node_id":3,"action":"link_topic","topic_id":7}"#).unwrap();
+        assert_eq!(decision.action, TriageAction::LinkTopic { topic_id: 7 });
+        let decision: TriageDecision =
+            serde_json::from_str(r#"{"node_id":3,"action":"convert_to_task"}"#).unwrap();
+        assert_eq!(decision.action, TriageAction::ConvertToTask);
+    }
+}
@@ -0,0 +1,321 @@
+//! 资源文件完整性校验
+//!
+//! 重新计算资源文件的哈希并与节点 file_hash 比较，记录缺失与不一致（位衰减、被外部修改）的文件。
+//! 可选把不一致的文件按新内容重新解析入队。接通电源时每周自动校验一次（只报告），发现问题时发送通知。
+
+use std::{
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use chrono::{NaiveDateTime, Utc};
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::app_state::AppState;
+use crate::db::{
+    create_integrity_check, finish_integrity_check, get_integrity_check,
+    get_latest_integrity_check, get_node_by_id, insert_integrity_issue, list_asset_files,
+    list_integrity_issues, prune_integrity_checks, update_node_file_hash, AssetFileRow,
+    IntegrityCheckCounts, IntegrityCheckRecord, IntegrityCheckTrigger, IntegrityIssueKind,
+    IntegrityIssueRecord, NewIntegrityIssue, NewNotification, NotificationKind,
+};
+use crate::i18n::MessageCode;
+use crate::services::{
+    emit_event, parse_and_enqueue, push_notification, IntegrityCheckProgressPayload, JobPriority,
+};
+use crate::utils::{
+    compute_file_sha256, compute_sha256, get_assets_dir, is_on_battery, resolve_stored_path,
+};
+use crate::{AppError, AppResult};
+
+const SCHEDULER_INITIAL_DELAY: Duration = Duration::from_secs(10 * 60);
+const SCHEDULER_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+/// 自动校验间隔
+const CHECK_PERIOD_DAYS: i64 = 7;
+/// 保留的校验记录数
+const KEEP_CHECKS: i64 = 20;
+
+/// 同一时间只允许一次校验
+static RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// 校验报告
+#[derive(Debug, Serialize)]
+pub struct IntegrityReport {
+    pub check: IntegrityCheckRecord,
+    pub issues: Vec<IntegrityIssueRecord>,
+}
+
+struct RunningGuard;
+
+impl RunningGuard {
+    fn acquire() -> Option<Self> {
+        RUNNING
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .ok()
+            .map(|_| Self)
+    }
+}
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        RUNNING.store(false, Ordering::Release);
+    }
+}
+
+/// 校验所有带文件的资源；reparse 为 true 时把哈希不一致的文件重新解析并入队
+pub async fn run_integrity_check(
+    app: &AppHandle,
+    state: &AppState,
+    trigger: IntegrityCheckTrigger,
+    reparse: bool,
+) -> AppResult<IntegrityReport> {
+    let _guard = RunningGuard::acquire()
+        .ok_or_else(|| AppError::coded(MessageCode::IntegrityCheckRunning))?;
+    // 自定义资源目录缺失时所有文件都会被判定为缺失，直接报错
+    get_assets_dir(app)?;
+    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+
+    let check_id = create_integrity_check(&state.db, trigger).await?;
+    let mut counts = IntegrityCheckCounts::default();
+    let result = check_files(app, state, &app_data_dir, check_id, reparse, &mut counts).await;
+    let error = result.as_ref().err().map(|err| err.to_string());
+    finish_integrity_check(&state.db, check_id, counts, error.as_deref()).await?;
+    result?;
+
+    if let Err(err) = prune_integrity_checks(&state.db, KEEP_CHECKS).await {
+        tracing::warn!(error = %err, "Prune integrity checks failed");
+    }
+    tracing::info!(
+        check_id,
+        checked = counts.checked,
+        missing = counts.missing,
+        mismatched = counts.mismatched,
+        "Integrity check finished"
+    );
+    load_report(state, check_id)
+        .await?
+        .ok_or(AppError::NotFound {
+            entity: "integrity_check",
+            id: check_id,
+        })
+}
+
+/// 读取指定校验的报告
+pub async fn load_report(state: &AppState, check_id: i64) -> AppResult<Option<IntegrityReport>> {
+    let Some(check) = get_integrity_check(&state.db, check_id).await? else {
+        return Ok(None);
+    };
+    let issues = list_integrity_issues(&state.db, check_id).await?;
+    Ok(Some(IntegrityReport { check, issues }))
+}
+
+/// 最近一次已结束校验的报告
+pub async fn load_latest_report(state: &AppState) -> AppResult<Option<IntegrityReport>> {
+    match get_latest_integrity_check(&state.db).await? {
+        Some(check) => load_report(state, check.check_id).await,
+        None => Ok(None),
+    }
+}
+
+async fn check_files(
+    app: &AppHandle,
+    state: &AppState,
+    app_data_dir: &Path,
+    check_id: i64,
+    reparse: bool,
+    counts: &mut IntegrityCheckCounts,
+) -> AppResult<()> {
+    let files = list_asset_files(&state.db).await?;
+    let total = files.len();
+    for (index, file) in files.into_iter().enumerate() {
+        let path = resolve_stored_path(app_data_dir, &file.file_path);
+        check_file(app, state, check_id, file, path, reparse, counts).await?;
+        emit_event(
+            app,
+            &IntegrityCheckProgressPayload {
+                check_id,
+                processed: index + 1,
+                total,
+            },
+        );
+    }
+    Ok(())
+}
+
+async fn check_file(
+    app: &AppHandle,
+    state: &AppState,
+    check_id: i64,
+    file: AssetFileRow,
+    path: PathBuf,
+    reparse: bool,
+    counts: &mut IntegrityCheckCounts,
+) -> AppResult<()> {
+    counts.checked += 1;
+    let Some(expected) = file.file_hash.as_deref() else {
+        counts.skipped += 1;
+        return Ok(());
+    };
+
+    let hash_path = path.clone();
+    let actual = tauri::async_runtime::spawn_blocking(move || compute_file_sha256(&hash_path))
+        .await
+        .map_err(|e| e.to_string())?;
+    let actual = match actual {
+        Ok(hash) if hash == expected => return Ok(()),
+        Ok(hash) => hash,
+        Err(err) => {
+            tracing::warn!(
+                node_id = file.node_id,
+                path = %path.display(),
+                error = %err,
+                "Asset file unreadable"
+            );
+            counts.missing += 1;
+            insert_integrity_issue(
+                &state.db,
+                NewIntegrityIssue {
+                    check_id,
+                    node_id: file.node_id,
+                    kind: IntegrityIssueKind::Missing,
+                    file_path: &file.file_path,
+                    expected_hash: Some(expected),
+                    actual_hash: None,
+                    reparsed: false,
+                },
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    // 在应用内编辑过内容的资源，file_hash 记录的是文本哈希，无法与文件比较
+    let node = get_node_by_id(&state.db, file.node_id).await?;
+    if node
+        .file_content
+        .as_deref()
+        .is_some_and(|content| compute_sha256(content.as_bytes()) == expected)
+    {
+        counts.skipped += 1;
+        return Ok(());
+    }
+
+    counts.mismatched += 1;
+    let reparsed = match (reparse, file.resource_subtype) {
+        (true, Some(subtype)) => {
+            update_node_file_hash(&state.db, file.node_id, &actual).await?;
+            let resolved = path.to_string_lossy().to_string();
+            let parse_error = parse_and_enqueue(
+                app,
+                state,
+                file.node_id,
+                subtype,
+                None,
+                Some(&resolved),
+                &actual,
+                JobPriority::Bulk,
+            )
+            .await?;
+            if let Some(err) = &parse_error {
+                tracing::warn!(node_id = file.node_id, error = %err, "Reparse changed asset failed");
+            }
+            parse_error.is_none()
+        }
+        _ => false,
+    };
+    if reparsed {
+        counts.reparsed += 1;
+    }
+    insert_integrity_issue(
+        &state.db,
+        NewIntegrityIssue {
+            check_id,
+            node_id: file.node_id,
+            kind: IntegrityIssueKind::Mismatch,
+            file_path: &file.file_path,
+            expected_hash: Some(expected),
+            actual_hash: Some(&actual),
+            reparsed,
+        },
+    )
+    .await?;
+    Ok(())
+}
+
+/// 距上次校验是否已满一个周期；时间为 SQLite CURRENT_TIMESTAMP（UTC）
+fn is_check_due(last_finished_at: Option<&str>, now: NaiveDateTime) -> bool {
+    let Some(last) = last_finished_at
+        .and_then(|value| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S").ok())
+    else {
+        return true;
+    };
+    now - last >= chrono::Duration::days(CHECK_PERIOD_DAYS)
+}
+
+async fn run_scheduled_check(app: &AppHandle, state: &AppState) -> AppResult<()> {
+    let last = get_latest_integrity_check(&state.db).await?;
+    let last_finished = last.as_ref().and_then(|check| check.finished_at.as_deref());
+    if !is_check_due(last_finished, Utc::now().naive_utc()) {
+        return Ok(());
+    }
+
+    let report = run_integrity_check(app, state, IntegrityCheckTrigger::Scheduled, false).await?;
+    let check = &report.check;
+    if check.missing_count == 0 && check.mismatched_count == 0 {
+        return Ok(());
+    }
+    let body = format!(
+        "{} 个文件缺失，{} 个文件与记录的哈希不一致",
+        check.missing_count, check.mismatched_count
+    );
+    push_notification(
+        &state.db,
+        app,
+        NewNotification {
+            kind: NotificationKind::System,
+            title: "资源文件完整性校验发现问题",
+            body: Some(&body),
+            node_id: None,
+            action: None,
+        },
+    )
+    .await?;
+    Ok(())
+}
+
+/// 定期校验资源文件，使用电池时跳过
+pub fn spawn_integrity_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(SCHEDULER_INITIAL_DELAY).await;
+        loop {
+            if !is_on_battery() {
+                if let Some(state) = app.try_state::<AppState>() {
+                    if let Err(err) = run_scheduled_check(&app, &state).await {
+                        tracing::debug!(error = %err, "Scheduled integrity check skipped");
+                    }
+                }
+            }
+            tokio::time::sleep(SCHEDULER_INTERVAL).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn check_is_due_weekly() {
+        let now = at("2025-01-15 12:00:00");
+        assert!(is_check_due(None, now));
+        assert!(is_check_due(Some("not a date"), now));
+        assert!(!is_check_due(Some("2025-01-10 12:00:00"), now));
+        assert!(is_check_due(Some("2025-01-08 12:00:00"), now));
+    }
+}
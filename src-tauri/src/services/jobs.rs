@@ -0,0 +1,312 @@
+//! 长时间运行的后台任务（导出、整库 Markdown 导出、ENEX 导入、向量重建）
+//!
+//! 命令启动任务后立即返回 job_id，进度、结果与错误通过 job-progress 事件发送，也可用
+//! `get_job_status` 查询。取消只设置标记，任务在下一个检查点（每批 / 每个文件）停止并以
+//! [`MessageCode::JobCancelled`] 结束，已写入的部分按各任务自己的失败路径清理。
+//! 任务状态只保存在内存中，保留最近 [`KEEP_FINISHED`] 个已结束的任务。
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::i18n::MessageCode;
+use crate::services::emit_event;
+use crate::{AppError, AppResult};
+
+/// 保留的已结束任务数
+const KEEP_FINISHED: usize = 50;
+/// 两次进度事件的最小间隔；完成全部时总会发送
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobKind {
+    Export,
+    /// 整库导出为 Markdown 文件夹
+    VaultExport,
+    ImportEnex,
+    /// 导入 Markdown 文件夹（Obsidian 仓库）
+    ImportMarkdown,
+    EmbeddingReindex,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+/// 任务当前状态
+#[derive(Debug, Clone, Serialize)]
+pub struct JobSnapshot {
+    pub job_id: i64,
+    pub kind: JobKind,
+    pub status: JobStatus,
+    pub processed: usize,
+    /// 总数未知时为空
+    pub total: Option<usize>,
+    /// 已请求取消、任务尚未停止
+    pub cancel_requested: bool,
+    /// 成功时的结果，与对应同步接口的返回值结构相同
+    pub result: Option<serde_json::Value>,
+    pub error: Option<String>,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+}
+
+struct JobEntry {
+    snapshot: JobSnapshot,
+    cancelled: Arc<AtomicBool>,
+}
+
+/// 任务表，保存在 `AppState.jobs`
+#[derive(Default)]
+pub struct JobRegistry {
+    next_id: AtomicI64,
+    jobs: Mutex<HashMap<i64, JobEntry>>,
+}
+
+/// 传给任务的句柄：汇报进度、检查是否已取消
+#[derive(Clone)]
+pub struct JobHandle {
+    id: i64,
+    app: AppHandle,
+    registry: Arc<JobRegistry>,
+    cancelled: Arc<AtomicBool>,
+    last_emit: Arc<Mutex<Option<Instant>>>,
+}
+
+fn now() -> String {
+    Utc::now().format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+impl JobRegistry {
+    /// 启动任务并立即返回 job_id
+    pub fn spawn<T, F, Fut>(self: &Arc<Self>, app: &AppHandle, kind: JobKind, run: F) -> i64
+    where
+        T: Serialize,
+        F: FnOnce(JobHandle) -> Fut,
+        Fut: Future<Output = AppResult<T>> + Send + 'static,
+    {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let snapshot = JobSnapshot {
+            job_id: id,
+            kind,
+            status: JobStatus::Running,
+            processed: 0,
+            total: None,
+            cancel_requested: false,
+            result: None,
+            error: None,
+            started_at: now(),
+            finished_at: None,
+        };
+        emit_event(app, &snapshot);
+        self.lock().insert(
+            id,
+            JobEntry {
+                snapshot,
+                cancelled: cancelled.clone(),
+            },
+        );
+
+        let handle = JobHandle {
+            id,
+            app: app.clone(),
+            registry: self.clone(),
+            cancelled,
+            last_emit: Arc::new(Mutex::new(None)),
+        };
+        let future = run(handle.clone());
+        tauri::async_runtime::spawn(async move {
+            let result = future
+                .await
+                .and_then(|value| serde_json::to_value(value).map_err(|e| e.to_string().into()));
+            handle.finish(result);
+        });
+        id
+    }
+
+    pub fn get(&self, job_id: i64) -> AppResult<JobSnapshot> {
+        self.lock()
+            .get(&job_id)
+            .map(|entry| entry.snapshot.clone())
+            .ok_or(AppError::NotFound {
+                entity: "job",
+                id: job_id,
+            })
+    }
+
+    /// 全部任务，新的在前
+    pub fn list(&self) -> Vec<JobSnapshot> {
+        let mut jobs: Vec<_> = self
+            .lock()
+            .values()
+            .map(|entry| entry.snapshot.clone())
+            .collect();
+        jobs.sort_by(|a, b| b.job_id.cmp(&a.job_id));
+        jobs
+    }
+
+    /// 请求取消；已结束的任务原样返回
+    pub fn cancel(&self, app: &AppHandle, job_id: i64) -> AppResult<JobSnapshot> {
+        let snapshot = {
+            let mut jobs = self.lock();
+            let entry = jobs.get_mut(&job_id).ok_or(AppError::NotFound {
+                entity: "job",
+                id: job_id,
+            })?;
+            if entry.snapshot.status != JobStatus::Running {
+                return Ok(entry.snapshot.clone());
+            }
+            entry.cancelled.store(true, Ordering::Release);
+            entry.snapshot.cancel_requested = true;
+            entry.snapshot.clone()
+        };
+        emit_event(app, &snapshot);
+        Ok(snapshot)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<i64, JobEntry>> {
+        self.jobs.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn update(&self, job_id: i64, apply: impl FnOnce(&mut JobSnapshot)) -> Option<JobSnapshot> {
+        let mut jobs = self.lock();
+        let entry = jobs.get_mut(&job_id)?;
+        apply(&mut entry.snapshot);
+        Some(entry.snapshot.clone())
+    }
+}
+
+impl JobHandle {
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+    }
+
+    /// 已取消时返回 [`MessageCode::JobCancelled`]
+    pub fn check_cancelled(&self) -> AppResult<()> {
+        if self.is_cancelled() {
+            return Err(AppError::coded(MessageCode::JobCancelled));
+        }
+        Ok(())
+    }
+
+    /// 更新进度；事件按 [`PROGRESS_INTERVAL`] 节流
+    pub fn progress(&self, processed: usize, total: Option<usize>) {
+        let Some(snapshot) = self.registry.update(self.id, |snapshot| {
+            snapshot.processed = processed;
+            snapshot.total = total;
+        }) else {
+            return;
+        };
+        let done = total.is_some_and(|total| processed >= total);
+        let mut last_emit = self.last_emit.lock().unwrap_or_else(|e| e.into_inner());
+        if done || last_emit.is_none_or(|last| last.elapsed() >= PROGRESS_INTERVAL) {
+            *last_emit = Some(Instant::now());
+            emit_event(&self.app, &snapshot);
+        }
+    }
+
+    fn finish(&self, result: AppResult<serde_json::Value>) {
+        let status = finished_status(&result);
+        match &result {
+            Err(err) if status == JobStatus::Failed => {
+                tracing::warn!(job_id = self.id, error = %err, "Job failed");
+            }
+            _ => tracing::info!(job_id = self.id, ?status, "Job finished"),
+        }
+        let snapshot = self.registry.update(self.id, |snapshot| {
+            snapshot.status = status;
+            snapshot.finished_at = Some(now());
+            match result {
+                Ok(value) => snapshot.result = Some(value),
+                Err(err) => snapshot.error = Some(err.to_string()),
+            }
+        });
+        prune_finished(&mut self.registry.lock(), KEEP_FINISHED);
+        if let Some(snapshot) = snapshot {
+            emit_event(&self.app, &snapshot);
+        }
+    }
+}
+
+fn finished_status<T>(result: &AppResult<T>) -> JobStatus {
+    match result {
+        Ok(_) => JobStatus::Succeeded,
+        Err(err) if err.code() == MessageCode::JobCancelled => JobStatus::Cancelled,
+        Err(_) => JobStatus::Failed,
+    }
+}
+
+/// 只保留最近 keep 个已结束的任务，运行中的任务不受影响
+fn prune_finished(jobs: &mut HashMap<i64, JobEntry>, keep: usize) {
+    let mut finished: Vec<i64> = jobs
+        .values()
+        .filter(|entry| entry.snapshot.status != JobStatus::Running)
+        .map(|entry| entry.snapshot.job_id)
+        .collect();
+    if finished.len() <= keep {
+        return;
+    }
+    finished.sort_unstable();
+    for job_id in &finished[..finished.len() - keep] {
+        jobs.remove(job_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(job_id: i64, status: JobStatus) -> JobEntry {
+        JobEntry {
+            snapshot: JobSnapshot {
+                job_id,
+                kind: JobKind::Export,
+                status,
+                processed: 0,
+                total: None,
+                cancel_requested: false,
+                result: None,
+                error: None,
+                started_at: now(),
+                finished_at: None,
+            },
+            cancelled: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    #[test]
+    fn test_prune_keeps_running_and_latest_finished() {
+        let mut jobs = HashMap::new();
+        jobs.insert(1, entry(1, JobStatus::Running));
+        jobs.insert(2, entry(2, JobStatus::Succeeded));
+        jobs.insert(3, entry(3, JobStatus::Failed));
+        jobs.insert(4, entry(4, JobStatus::Cancelled));
+
+        prune_finished(&mut jobs, 2);
+        let mut ids: Vec<_> = jobs.keys().copied().collect();
+        ids.sort_unstable();
+        assert_eq!(ids, vec![1, 3, 4]);
+    }
+
+    #[test]
+    fn test_finished_status() {
+        assert_eq!(finished_status(&AppResult::Ok(())), JobStatus::Succeeded);
+        let cancelled: AppResult<()> = Err(AppError::coded(MessageCode::JobCancelled));
+        assert_eq!(finished_status(&cancelled), JobStatus::Cancelled);
+        let failed: AppResult<()> = Err("boom".into());
+        assert_eq!(finished_status(&failed), JobStatus::Failed);
+    }
+}
@@ -0,0 +1,272 @@
+//! Markdown 文件夹（Obsidian 仓库）导入
+//!
+//! 递归收集文件夹中的 .md 文件（跳过 `.obsidian`、`.trash` 等隐藏目录），解析 front matter 中的
+//! 标题、标签与时间，以及正文中的 `[[链接]]`。写入数据库由 `import_markdown_folder` 命令完成。
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+
+use crate::utils::extract_node_links;
+
+/// 单次导入最多处理的文件数
+pub const MAX_MARKDOWN_FILES: usize = 20_000;
+
+/// 解析后的一篇笔记
+#[derive(Debug, Default, PartialEq)]
+pub struct MarkdownNote {
+    /// front matter 中的 title，没有时为文件名
+    pub title: String,
+    /// 去掉 front matter 的正文
+    pub body: String,
+    pub tags: Vec<String>,
+    /// `YYYY-MM-DD HH:MM:SS`
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    /// 正文中的 `[[链接]]`，按原文去重
+    pub links: Vec<MarkdownLink>,
+}
+
+/// 正文中的一个 `[[链接]]`
+#[derive(Debug, PartialEq)]
+pub struct MarkdownLink {
+    /// `|` 之前的原文，与 `extract_node_links` 返回的标题一致
+    pub text: String,
+    /// 指向的笔记名，见 [`link_target`]
+    pub target: String,
+}
+
+/// 按路径排序的 .md 文件；超过 [`MAX_MARKDOWN_FILES`] 时截断
+pub fn collect_markdown_files(root: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(&dir)?.flatten() {
+            let path = entry.path();
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                dirs.push(path);
+            } else if file_type.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("md"))
+            {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    files.truncate(MAX_MARKDOWN_FILES);
+    Ok(files)
+}
+
+/// 解析一篇笔记；stem 为不含扩展名的文件名
+pub fn parse_markdown_note(stem: &str, text: &str) -> MarkdownNote {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let (front_matter, body) = split_front_matter(text);
+    let mut note = MarkdownNote {
+        body: body.trim().to_string(),
+        ..Default::default()
+    };
+    if let Some(front_matter) = front_matter {
+        apply_front_matter(&mut note, front_matter);
+    }
+    if note.title.is_empty() {
+        note.title = stem.trim().to_string();
+    }
+    note.links = extract_node_links(&note.body)
+        .titles
+        .into_iter()
+        .filter_map(|text| {
+            Some(MarkdownLink {
+                target: link_target(&text)?,
+                text,
+            })
+        })
+        .collect();
+    note
+}
+
+/// 链接指向的笔记名：去掉 `#标题` / `^块` 引用、目录与 .md 后缀
+///
+/// Obsidian 按文件名解析链接，因此 `[[笔记/想法.md#小结]]` 与 `[[想法]]` 指向同一篇笔记。
+pub fn link_target(target: &str) -> Option<String> {
+    let target = target.split(['#', '^']).next().unwrap_or_default();
+    let name = target.rsplit(['/', '\\']).next().unwrap_or_default().trim();
+    let name = match name.len().checked_sub(3) {
+        Some(split)
+            if name.is_char_boundary(split) && name[split..].eq_ignore_ascii_case(".md") =>
+        {
+            &name[..split]
+        }
+        _ => name,
+    };
+    let name = name.trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// 按笔记名查找时使用的键，与 Obsidian 一样不区分大小写
+pub fn link_key(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+/// 文件修改时间，格式同 SQLite DATETIME
+pub fn file_modified_at(path: &Path) -> Option<String> {
+    let modified = fs::metadata(path).and_then(|meta| meta.modified()).ok()?;
+    Some(
+        DateTime::<Utc>::from(modified)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string(),
+    )
+}
+
+/// 拆出开头 `---` 之间的 front matter；没有或未闭合时整篇都是正文
+fn split_front_matter(text: &str) -> (Option<&str>, &str) {
+    let Some(rest) = text
+        .strip_prefix("---\n")
+        .or_else(|| text.strip_prefix("---\r\n"))
+    else {
+        return (None, text);
+    };
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return (Some(&rest[..offset]), &rest[offset + line.len()..]);
+        }
+        offset += line.len();
+    }
+    (None, text)
+}
+
+/// 只识别 title、tags / tag、created / date、updated / modified，其余字段忽略
+fn apply_front_matter(note: &mut MarkdownNote, front_matter: &str) {
+    let mut list_key: Option<String> = None;
+    for line in front_matter.lines() {
+        if let Some(item) = line.trim_start().strip_prefix("- ") {
+            if matches!(list_key.as_deref(), Some("tags" | "tag")) {
+                push_tag(&mut note.tags, item);
+            }
+            continue;
+        }
+        if line.starts_with(char::is_whitespace) {
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let key = key.trim().to_lowercase();
+        let value = value.trim();
+        list_key = value.is_empty().then(|| key.clone());
+        match key.as_str() {
+            "title" => {
+                let title = unquote(value);
+                if !title.trim().is_empty() {
+                    note.title = title.trim().to_string();
+                }
+            }
+            "tags" | "tag" => {
+                let value = value
+                    .strip_prefix('[')
+                    .and_then(|v| v.strip_suffix(']'))
+                    .unwrap_or(value);
+                for tag in value.split(',') {
+                    push_tag(&mut note.tags, tag);
+                }
+            }
+            "created" | "date" => note.created_at = parse_front_matter_date(&unquote(value)),
+            "updated" | "modified" => {
+                note.updated_at = parse_front_matter_date(&unquote(value));
+            }
+            _ => {}
+        }
+    }
+}
+
+fn push_tag(tags: &mut Vec<String>, raw: &str) {
+    let tag = unquote(raw.trim());
+    let tag = tag.trim().trim_start_matches('#').trim();
+    if !tag.is_empty() && !tags.iter().any(|t| t == tag) {
+        tags.push(tag.to_string());
+    }
+}
+
+/// 去掉 YAML 标量两侧的引号；双引号按 JSON 字符串解析转义
+fn unquote(value: &str) -> String {
+    if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+        if let Ok(parsed) = serde_json::from_str::<String>(value) {
+            return parsed;
+        }
+    }
+    value
+        .strip_prefix('\'')
+        .and_then(|v| v.strip_suffix('\''))
+        .or_else(|| value.strip_prefix('"').and_then(|v| v.strip_suffix('"')))
+        .unwrap_or(value)
+        .to_string()
+}
+
+/// 支持 `YYYY-MM-DD HH:MM:SS`、`YYYY-MM-DDTHH:MM:SS` 与 `YYYY-MM-DD`
+fn parse_front_matter_date(raw: &str) -> Option<String> {
+    let raw = raw.trim();
+    let datetime = ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(raw, format).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })?;
+    Some(datetime.format("%Y-%m-%d %H:%M:%S").to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_markdown_note() {
+        let text = "---\ntitle: \"Rust \\\"异步\\\"\"\ntags:\n  - rust\n  - '#编程'\n\
+                    aliases: [async]\ncreated: 2024-03-01\nupdated: 2024-03-02 10:20:30\n---\n\
+                    # 标题\n见 [[所有权]]、[[笔记/借用.md#规则|借用]] 与 [[所有权]]。\n";
+        let note = parse_markdown_note("rust-async", text);
+        assert_eq!(note.title, "Rust \"异步\"");
+        assert_eq!(note.tags, vec!["rust", "编程"]);
+        assert_eq!(note.created_at.as_deref(), Some("2024-03-01 00:00:00"));
+        assert_eq!(note.updated_at.as_deref(), Some("2024-03-02 10:20:30"));
+        let targets: Vec<&str> = note.links.iter().map(|link| link.target.as_str()).collect();
+        assert_eq!(targets, vec!["所有权", "借用"]);
+        assert_eq!(note.links[1].text, "笔记/借用.md#规则");
+        assert!(note.body.starts_with("# 标题"));
+    }
+
+    #[test]
+    fn test_parse_markdown_note_without_front_matter() {
+        let note = parse_markdown_note("想法", "---\n没有闭合\n");
+        assert_eq!(note.title, "想法");
+        assert_eq!(note.body, "---\n没有闭合");
+        assert!(note.tags.is_empty());
+
+        let note = parse_markdown_note("x", "---\ntags: [a, \"b\", a]\n---\n正文");
+        assert_eq!(note.title, "x");
+        assert_eq!(note.tags, vec!["a", "b"]);
+        assert_eq!(note.body, "正文");
+    }
+
+    #[test]
+    fn test_link_target() {
+        assert_eq!(
+            link_target("Notes/Idea.MD#Summary").as_deref(),
+            Some("Idea")
+        );
+        assert_eq!(link_target("想法^abc123").as_deref(), Some("想法"));
+        assert_eq!(link_target("#只有标题"), None);
+        assert_eq!(link_key(" Idea "), "idea");
+    }
+}
@@ -0,0 +1,575 @@
+//! Slack / Discord 消息捕获
+//!
+//! 在 127.0.0.1 上监听 `POST /messages`，接收机器人转发的消息（正文、作者、频道、permalink）。
+//! 消息先进入缓冲区，后台任务定期按 (平台, 频道) 合并为一个文本资源并入队处理，
+//! 团队讨论与决定因此可以在知识库中检索。已捕获的消息记录在 captured_messages 表中，重复转发会被跳过。
+
+use std::collections::{BTreeMap, HashSet};
+use std::io::Read;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use super::{apply_source_tag_rules, AiPipeline, JobPriority, MessageWebhookConfig};
+use crate::app_state::AppState;
+use crate::db::{
+    insert_captured_message, is_message_captured, DbPool, NodeBuilder, ResourceSubtype, SourceMeta,
+};
+use crate::error::{AppError, AppResult};
+use crate::i18n::MessageCode;
+use crate::utils::compute_sha256;
+
+const FLUSH_INTERVAL: Duration = Duration::from_secs(15);
+/// 请求体上限
+const MAX_BODY_BYTES: u64 = 1024 * 1024;
+/// 缓冲区上限，超过后拒绝新消息直到下一次写入
+const MAX_BUFFERED_MESSAGES: usize = 2000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MessagePlatform {
+    Slack,
+    Discord,
+}
+
+impl MessagePlatform {
+    fn as_str(self) -> &'static str {
+        match self {
+            MessagePlatform::Slack => "slack",
+            MessagePlatform::Discord => "discord",
+        }
+    }
+
+    fn display_name(self) -> &'static str {
+        match self {
+            MessagePlatform::Slack => "Slack",
+            MessagePlatform::Discord => "Discord",
+        }
+    }
+}
+
+/// 机器人转发的一条消息
+#[derive(Debug, Clone, Deserialize)]
+pub struct ForwardedMessage {
+    /// 平台消息 ID（Slack ts / Discord snowflake）
+    #[serde(default)]
+    pub id: Option<String>,
+    pub text: String,
+    #[serde(default)]
+    pub author: Option<String>,
+    #[serde(default)]
+    pub channel: Option<String>,
+    #[serde(default)]
+    pub permalink: Option<String>,
+    #[serde(default)]
+    pub timestamp: Option<String>,
+}
+
+/// 请求体：单条消息或一批消息
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum WebhookBody {
+    Batch {
+        platform: MessagePlatform,
+        messages: Vec<ForwardedMessage>,
+    },
+    Single {
+        platform: MessagePlatform,
+        #[serde(flatten)]
+        message: ForwardedMessage,
+    },
+}
+
+#[derive(Debug, Clone)]
+struct PendingMessage {
+    platform: MessagePlatform,
+    key: String,
+    message: ForwardedMessage,
+}
+
+/// 入口运行状态
+#[derive(Debug, Clone, Serialize)]
+pub struct MessageWebhookStatus {
+    pub enabled: bool,
+    pub port: u16,
+    pub token: String,
+    pub running: bool,
+    pub endpoint: String,
+}
+
+struct RunningServer {
+    server: Arc<Server>,
+    worker: thread::JoinHandle<()>,
+    port: u16,
+    /// 工作线程每次请求时读取，换令牌不必重新监听
+    token: Arc<Mutex<String>>,
+}
+
+pub struct MessageCaptureService {
+    server: Mutex<Option<RunningServer>>,
+    buffer: Arc<Mutex<Vec<PendingMessage>>>,
+}
+
+impl MessageCaptureService {
+    pub fn new() -> Self {
+        Self {
+            server: Mutex::new(None),
+            buffer: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// 按配置启动或停止监听
+    ///
+    /// 端口不变时只替换令牌；端口变化时先监听新端口，成功后才停止旧的，
+    /// 监听失败时原来的入口继续运行。
+    pub fn apply_config(&self, config: &MessageWebhookConfig) -> AppResult<()> {
+        if !config.enabled {
+            self.stop();
+            return Ok(());
+        }
+        if config.token.is_empty() {
+            return Err(AppError::coded(MessageCode::WebhookTokenEmpty));
+        }
+
+        {
+            let running = self.server.lock().expect("message webhook lock");
+            if let Some(running) = running.as_ref().filter(|r| r.port == config.port) {
+                *running.token.lock().expect("message webhook token lock") = config.token.clone();
+                return Ok(());
+            }
+        }
+
+        let server = Server::http(("127.0.0.1", config.port))
+            .map(Arc::new)
+            .map_err(|e| AppError::Coded {
+                code: MessageCode::WebhookListenFailed,
+                args: vec![("port", config.port.to_string()), ("detail", e.to_string())],
+            })?;
+        let listener = server.clone();
+        let buffer = self.buffer.clone();
+        let token = Arc::new(Mutex::new(config.token.clone()));
+        let worker_token = token.clone();
+        let worker = thread::Builder::new()
+            .name("message-webhook".to_string())
+            .spawn(move || {
+                for request in listener.incoming_requests() {
+                    let token = worker_token
+                        .lock()
+                        .expect("message webhook token lock")
+                        .clone();
+                    handle_request(request, &token, &buffer);
+                }
+            })?;
+
+        self.stop();
+        tracing::info!(port = config.port, "Message webhook listening");
+        *self.server.lock().expect("message webhook lock") = Some(RunningServer {
+            server,
+            worker,
+            port: config.port,
+            token,
+        });
+        Ok(())
+    }
+
+    /// 停止监听；等待工作线程退出以释放端口
+    pub fn stop(&self) {
+        let running = self.server.lock().expect("message webhook lock").take();
+        if let Some(running) = running {
+            running.server.unblock();
+            drop(running.server);
+            let _ = running.worker.join();
+            tracing::info!(port = running.port, "Message webhook stopped");
+        }
+    }
+
+    pub fn status(&self, config: &MessageWebhookConfig) -> MessageWebhookStatus {
+        let running = self.server.lock().expect("message webhook lock").is_some();
+        MessageWebhookStatus {
+            enabled: config.enabled,
+            port: config.port,
+            token: config.token.clone(),
+            running,
+            endpoint: format!("http://127.0.0.1:{}/messages", config.port),
+        }
+    }
+
+    fn take_pending(&self) -> Vec<PendingMessage> {
+        std::mem::take(&mut *self.buffer.lock().expect("message buffer lock"))
+    }
+
+    /// 写入失败时放回缓冲区，排在期间新到的消息之前；仍受缓冲区上限约束
+    fn restore_pending(&self, mut messages: Vec<PendingMessage>) {
+        let mut buffer = self.buffer.lock().expect("message buffer lock");
+        for pending in buffer.drain(..) {
+            let duplicate = messages
+                .iter()
+                .any(|m| m.platform == pending.platform && m.key == pending.key);
+            if !duplicate {
+                messages.push(pending);
+            }
+        }
+        if messages.len() > MAX_BUFFERED_MESSAGES {
+            tracing::warn!(
+                dropped = messages.len() - MAX_BUFFERED_MESSAGES,
+                "Message buffer full, dropping newest forwarded messages"
+            );
+            messages.truncate(MAX_BUFFERED_MESSAGES);
+        }
+        *buffer = messages;
+    }
+}
+
+impl Default for MessageCaptureService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 生成入口令牌（32 字节随机数的十六进制）
+pub fn generate_webhook_token() -> String {
+    use rand::RngCore;
+
+    let mut bytes = [0u8; 32];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn handle_request(mut request: Request, token: &str, buffer: &Mutex<Vec<PendingMessage>>) {
+    let method = request.method().clone();
+    let path = request.url().split('?').next().unwrap_or("").to_string();
+    let (status, body) = match (method, path.as_str()) {
+        (Method::Get, "/health") => (200, r#"{"status":"ok"}"#.to_string()),
+        (Method::Post, "/messages") if !is_authorized(&request, token) => {
+            (401, r#"{"error":"unauthorized"}"#.to_string())
+        }
+        (Method::Post, "/messages") => accept_messages(&mut request, buffer),
+        (_, "/messages") => (405, r#"{"error":"method not allowed"}"#.to_string()),
+        _ => (404, r#"{"error":"not found"}"#.to_string()),
+    };
+
+    let response = Response::from_string(body)
+        .with_status_code(status)
+        .with_header(
+            Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+                .expect("static header"),
+        );
+    if let Err(err) = request.respond(response) {
+        tracing::debug!(error = %err, "Message webhook respond failed");
+    }
+}
+
+fn is_authorized(request: &Request, token: &str) -> bool {
+    request.headers().iter().any(|header| {
+        let value = header.value.as_str().trim();
+        if header.field.equiv("Authorization") {
+            value
+                .strip_prefix("Bearer ")
+                .is_some_and(|value| constant_time_eq(value.trim(), token))
+        } else if header.field.equiv("X-NeuralVault-Token") {
+            constant_time_eq(value, token)
+        } else {
+            false
+        }
+    })
+}
+
+/// 比较耗时只取决于长度，不随首个不同字节的位置变化
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0u8, |diff, (x, y)| diff | (x ^ y))
+            == 0
+}
+
+fn accept_messages(request: &mut Request, buffer: &Mutex<Vec<PendingMessage>>) -> (u16, String) {
+    let mut bytes = Vec::new();
+    if let Err(err) = request
+        .as_reader()
+        .take(MAX_BODY_BYTES + 1)
+        .read_to_end(&mut bytes)
+    {
+        return (400, error_json(&format!("read body failed: {err}")));
+    }
+    if bytes.len() as u64 > MAX_BODY_BYTES {
+        return (413, r#"{"error":"payload too large"}"#.to_string());
+    }
+
+    let messages = match parse_webhook_body(&bytes) {
+        Ok(messages) => messages,
+        Err(err) => return (400, error_json(&err)),
+    };
+
+    let mut buffer = buffer.lock().expect("message buffer lock");
+    if buffer.len() + messages.len() > MAX_BUFFERED_MESSAGES {
+        return (503, r#"{"error":"buffer full, retry later"}"#.to_string());
+    }
+    let mut queued = 0;
+    let mut duplicates = 0;
+    for message in messages {
+        let duplicate = buffer
+            .iter()
+            .any(|pending| pending.platform == message.platform && pending.key == message.key);
+        if duplicate {
+            duplicates += 1;
+        } else {
+            buffer.push(message);
+            queued += 1;
+        }
+    }
+    (
+        202,
+        serde_json::json!({ "queued": queued, "duplicates": duplicates }).to_string(),
+    )
+}
+
+fn error_json(message: &str) -> String {
+    serde_json::json!({ "error": message }).to_string()
+}
+
+/// 解析请求体，丢弃空消息并为每条消息计算去重键
+fn parse_webhook_body(bytes: &[u8]) -> Result<Vec<PendingMessage>, String> {
+    let body: WebhookBody =
+        serde_json::from_slice(bytes).map_err(|e| format!("invalid payload: {e}"))?;
+    let (platform, messages) = match body {
+        WebhookBody::Batch { platform, messages } => (platform, messages),
+        WebhookBody::Single { platform, message } => (platform, vec![message]),
+    };
+
+    let mut seen = HashSet::new();
+    Ok(messages
+        .into_iter()
+        .filter(|message| !message.text.trim().is_empty())
+        .map(|message| PendingMessage {
+            platform,
+            key: message_key(&message),
+            message,
+        })
+        .filter(|pending| seen.insert(pending.key.clone()))
+        .collect())
+}
+
+/// 去重键：消息 ID > permalink > 内容哈希
+fn message_key(message: &ForwardedMessage) -> String {
+    let non_empty = |value: &Option<String>| {
+        value
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    if let Some(id) = non_empty(&message.id) {
+        let channel = non_empty(&message.channel).unwrap_or_default();
+        return format!("id:{channel}:{id}");
+    }
+    if let Some(permalink) = non_empty(&message.permalink) {
+        return format!("url:{permalink}");
+    }
+    let fingerprint = format!(
+        "{}\n{}\n{}\n{}",
+        message.channel.as_deref().unwrap_or(""),
+        message.author.as_deref().unwrap_or(""),
+        message.timestamp.as_deref().unwrap_or(""),
+        message.text.trim()
+    );
+    format!("sha256:{}", compute_sha256(fingerprint.as_bytes()))
+}
+
+/// 按 (平台, 频道) 分组，保持到达顺序
+fn group_by_channel(
+    messages: Vec<PendingMessage>,
+) -> BTreeMap<(MessagePlatform, String), Vec<PendingMessage>> {
+    let mut groups: BTreeMap<_, Vec<_>> = BTreeMap::new();
+    for pending in messages {
+        let channel = pending
+            .message
+            .channel
+            .as_deref()
+            .map(|channel| channel.trim().trim_start_matches('#'))
+            .filter(|channel| !channel.is_empty())
+            .unwrap_or("direct")
+            .to_string();
+        groups
+            .entry((pending.platform, channel))
+            .or_default()
+            .push(pending);
+    }
+    groups
+}
+
+/// 渲染为 Markdown：每条消息一段，作者与时间作为标题行，permalink 附在正文后
+fn render_messages(messages: &[PendingMessage]) -> String {
+    messages
+        .iter()
+        .map(|pending| {
+            let message = &pending.message;
+            let mut header = format!("**{}**", message.author.as_deref().unwrap_or("unknown"));
+            if let Some(timestamp) = message.timestamp.as_deref() {
+                header.push_str(&format!(" · {timestamp}"));
+            }
+            let mut block = format!("{header}\n{}", message.text.trim());
+            if let Some(permalink) = message.permalink.as_deref() {
+                block.push_str(&format!("\n{permalink}"));
+            }
+            block
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// 将缓冲区中的消息写入资源，返回新建的资源数
+async fn flush_messages(
+    db: &DbPool,
+    pipeline: &AiPipeline,
+    messages: Vec<PendingMessage>,
+) -> Result<usize, sqlx::Error> {
+    let mut created = 0;
+    for ((platform, channel), group) in group_by_channel(messages) {
+        let mut fresh = Vec::with_capacity(group.len());
+        for pending in group {
+            if !is_message_captured(db, platform.as_str(), &pending.key).await? {
+                fresh.push(pending);
+            }
+        }
+        if fresh.is_empty() {
+            continue;
+        }
+
+        let content = render_messages(&fresh);
+        let file_hash = compute_sha256(content.as_bytes());
+        let meta = SourceMeta {
+            url: fresh
+                .iter()
+                .find_map(|pending| pending.message.permalink.clone()),
+            window_title: Some(format!("#{channel}")),
+            process_name: Some(platform.display_name().to_string()),
+            captured_at: Some(chrono::Utc::now().to_rfc3339()),
+            process_path: None,
+            sender: None,
+            sent_at: None,
+            auto_title: None,
+        };
+        let title = format!(
+            "{} #{channel} · {}",
+            platform.display_name(),
+            Local::now().format("%Y-%m-%d %H:%M")
+        );
+        let node_id = NodeBuilder::resource()
+            .title(title)
+            .file_content(Some(content.as_str()))
+            .file_hash(Some(file_hash.as_str()))
+            .resource_subtype(Some(ResourceSubtype::Text))
+            .source_meta(Some(meta.clone()))
+            .insert(db)
+            .await?;
+        for pending in &fresh {
+            insert_captured_message(db, platform.as_str(), &pending.key, node_id).await?;
+        }
+
+        if let Err(err) = apply_source_tag_rules(db, node_id, &meta).await {
+            tracing::warn!(node_id, error = %err, "Apply source tag rules failed");
+        }
+        if let Err(err) = pipeline
+            .enqueue_resource(node_id, JobPriority::Normal)
+            .await
+        {
+            tracing::warn!(node_id, error = %err, "Enqueue captured messages failed");
+        }
+        tracing::info!(
+            node_id,
+            platform = platform.as_str(),
+            channel = %channel,
+            messages = fresh.len(),
+            "Captured forwarded messages"
+        );
+        created += 1;
+    }
+    Ok(created)
+}
+
+/// 后台定期把缓冲区中的消息写入资源
+pub fn spawn_message_capture_flusher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(FLUSH_INTERVAL).await;
+            let Some(state) = app.try_state::<AppState>() else {
+                continue;
+            };
+            let messages = state.message_capture.take_pending();
+            if messages.is_empty() {
+                continue;
+            }
+            // 已写入的消息记录在 captured_messages 中，重试时会被跳过
+            if let Err(err) = flush_messages(&state.db, &state.ai_pipeline, messages.clone()).await
+            {
+                tracing::warn!(error = %err, "Flush forwarded messages failed, will retry");
+                state.message_capture.restore_pending(messages);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_single_and_batch_payloads() {
+        let single = br#"{"platform":"slack","id":"1700000000.0001","text":"Ship it","author":"ana","channel":"general"}"#;
+        let messages = parse_webhook_body(single).unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].platform, MessagePlatform::Slack);
+        assert_eq!(messages[0].key, "id:general:1700000000.0001");
+
+        let batch = br#"{"platform":"discord","messages":[
+            {"text":"a","permalink":"https://discord.com/channels/1/2/3"},
+            {"text":"a","permalink":"https://discord.com/channels/1/2/3"},
+            {"text":"   "},
+            {"text":"b","author":"bo"}
+        ]}"#;
+        let messages = parse_webhook_body(batch).unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].key, "url:https://discord.com/channels/1/2/3");
+        assert!(messages[1].key.starts_with("sha256:"));
+
+        assert!(parse_webhook_body(br#"{"platform":"teams","text":"x"}"#).is_err());
+    }
+
+    #[test]
+    fn groups_and_renders_by_channel() {
+        let pending = |channel: Option<&str>, text: &str| PendingMessage {
+            platform: MessagePlatform::Slack,
+            key: text.to_string(),
+            message: ForwardedMessage {
+                id: None,
+                text: text.to_string(),
+                author: Some("ana".to_string()),
+                channel: channel.map(str::to_string),
+                permalink: None,
+                timestamp: None,
+            },
+        };
+        let groups = group_by_channel(vec![
+            pending(Some("#eng"), "one"),
+            pending(None, "two"),
+            pending(Some("eng"), "three"),
+        ]);
+        assert_eq!(groups.len(), 2);
+        let eng = &groups[&(MessagePlatform::Slack, "eng".to_string())];
+        assert_eq!(render_messages(eng), "**ana**\none\n\n**ana**\nthree");
+        assert!(groups.contains_key(&(MessagePlatform::Slack, "direct".to_string())));
+    }
+
+    #[test]
+    fn compares_tokens() {
+        assert!(constant_time_eq("abc123", "abc123"));
+        assert!(!constant_time_eq("abc123", "abc124"));
+        assert!(!constant_time_eq("abc", "abc123"));
+    }
+}
@@ -0,0 +1,448 @@
+//! 运行指标
+//!
+//! 进程内的计数器与直方图：AI 流水线任务耗时、LLM 请求延迟、检索延迟、数据库错误数。
+//! 指标只保存在内存中，重启后清零。`get_metrics` 返回快照；启用 Prometheus 端点后，也可以从
+//! `http://127.0.0.1:<port>/metrics` 抓取文本格式的指标。端点只读且仅监听本机，不需要令牌。
+
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use super::MetricsEndpointConfig;
+
+/// Prometheus 指标名前缀
+const METRIC_PREFIX: &str = "neuralvault_";
+/// 直方图各桶的上限（秒），另有一个 +Inf 桶
+const BUCKETS: [f64; 12] = [
+    0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricCounter {
+    /// 转换为 AppError 的数据库错误
+    DbErrors,
+    /// 失败的流水线任务（包括之后会重试的）
+    PipelineJobFailures,
+}
+
+impl MetricCounter {
+    const ALL: [MetricCounter; 2] = [MetricCounter::DbErrors, MetricCounter::PipelineJobFailures];
+
+    fn name(self) -> &'static str {
+        match self {
+            MetricCounter::DbErrors => "db_errors_total",
+            MetricCounter::PipelineJobFailures => "pipeline_job_failures_total",
+        }
+    }
+
+    fn help(self) -> &'static str {
+        match self {
+            MetricCounter::DbErrors => "Database errors surfaced as AppError",
+            MetricCounter::PipelineJobFailures => "Failed AI pipeline jobs, including retried ones",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricHistogram {
+    /// 单个资源的流水线处理（摘要、向量、分类）
+    PipelineJob,
+    /// 一轮流式对话请求
+    LlmChat,
+    /// 结构化 JSON 生成请求（摘要、分类、标题等后台任务）
+    LlmStructured,
+    /// 混合检索与以图搜图
+    Search,
+}
+
+impl MetricHistogram {
+    const ALL: [MetricHistogram; 4] = [
+        MetricHistogram::PipelineJob,
+        MetricHistogram::LlmChat,
+        MetricHistogram::LlmStructured,
+        MetricHistogram::Search,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            MetricHistogram::PipelineJob => "pipeline_job_duration_seconds",
+            MetricHistogram::LlmChat => "llm_chat_duration_seconds",
+            MetricHistogram::LlmStructured => "llm_structured_duration_seconds",
+            MetricHistogram::Search => "search_duration_seconds",
+        }
+    }
+
+    fn help(self) -> &'static str {
+        match self {
+            MetricHistogram::PipelineJob => "AI pipeline job duration per resource",
+            MetricHistogram::LlmChat => "Streaming chat request duration per model turn",
+            MetricHistogram::LlmStructured => "Structured JSON generation request duration",
+            MetricHistogram::Search => "Hybrid and image search duration",
+        }
+    }
+}
+
+struct HistogramCell {
+    /// 各桶的（非累计）次数，最后一个是 +Inf
+    buckets: [AtomicU64; BUCKETS.len() + 1],
+    sum_micros: AtomicU64,
+}
+
+impl HistogramCell {
+    const fn new() -> Self {
+        Self {
+            buckets: [const { AtomicU64::new(0) }; BUCKETS.len() + 1],
+            sum_micros: AtomicU64::new(0),
+        }
+    }
+}
+
+static COUNTERS: [AtomicU64; MetricCounter::ALL.len()] =
+    [const { AtomicU64::new(0) }; MetricCounter::ALL.len()];
+static HISTOGRAMS: [HistogramCell; MetricHistogram::ALL.len()] =
+    [const { HistogramCell::new() }; MetricHistogram::ALL.len()];
+
+pub fn inc_counter(counter: MetricCounter) {
+    COUNTERS[counter as usize].fetch_add(1, Ordering::Relaxed);
+}
+
+pub fn observe_duration(histogram: MetricHistogram, duration: Duration) {
+    let cell = &HISTOGRAMS[histogram as usize];
+    cell.buckets[bucket_index(duration.as_secs_f64())].fetch_add(1, Ordering::Relaxed);
+    cell.sum_micros
+        .fetch_add(duration.as_micros() as u64, Ordering::Relaxed);
+}
+
+/// 耗时落入的桶
+fn bucket_index(secs: f64) -> usize {
+    BUCKETS
+        .iter()
+        .position(|&le| secs <= le)
+        .unwrap_or(BUCKETS.len())
+}
+
+/// 计时器，drop 时记录耗时；提前返回或出错时同样记录
+pub struct MetricTimer {
+    histogram: MetricHistogram,
+    started: Instant,
+}
+
+pub fn start_timer(histogram: MetricHistogram) -> MetricTimer {
+    MetricTimer {
+        histogram,
+        started: Instant::now(),
+    }
+}
+
+impl Drop for MetricTimer {
+    fn drop(&mut self) {
+        observe_duration(self.histogram, self.started.elapsed());
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CounterSnapshot {
+    pub name: &'static str,
+    pub help: &'static str,
+    pub value: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HistogramBucket {
+    /// 桶上限（秒）
+    pub le: f64,
+    /// 耗时不超过 le 的累计次数
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HistogramSnapshot {
+    pub name: &'static str,
+    pub help: &'static str,
+    pub count: u64,
+    pub sum_secs: f64,
+    /// 不含 +Inf 桶，其次数即 count
+    pub buckets: Vec<HistogramBucket>,
+    /// 按桶上限估算的分位数；没有数据或落在最后一个桶之外时为空
+    pub p50_secs: Option<f64>,
+    pub p95_secs: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricsSnapshot {
+    pub counters: Vec<CounterSnapshot>,
+    pub histograms: Vec<HistogramSnapshot>,
+}
+
+pub fn metrics_snapshot() -> MetricsSnapshot {
+    let counters = MetricCounter::ALL
+        .iter()
+        .map(|&counter| CounterSnapshot {
+            name: counter.name(),
+            help: counter.help(),
+            value: COUNTERS[counter as usize].load(Ordering::Relaxed),
+        })
+        .collect();
+    let histograms = MetricHistogram::ALL
+        .iter()
+        .map(|&histogram| {
+            let cell = &HISTOGRAMS[histogram as usize];
+            let counts: Vec<u64> = cell
+                .buckets
+                .iter()
+                .map(|bucket| bucket.load(Ordering::Relaxed))
+                .collect();
+            let sum_secs = cell.sum_micros.load(Ordering::Relaxed) as f64 / 1_000_000.0;
+            histogram_snapshot(histogram.name(), histogram.help(), &counts, sum_secs)
+        })
+        .collect();
+    MetricsSnapshot {
+        counters,
+        histograms,
+    }
+}
+
+/// 由各桶（非累计）次数生成快照；counts 的最后一项是 +Inf 桶
+fn histogram_snapshot(
+    name: &'static str,
+    help: &'static str,
+    counts: &[u64],
+    sum_secs: f64,
+) -> HistogramSnapshot {
+    let mut cumulative = 0;
+    let buckets = BUCKETS
+        .iter()
+        .zip(counts)
+        .map(|(&le, &count)| {
+            cumulative += count;
+            HistogramBucket {
+                le,
+                count: cumulative,
+            }
+        })
+        .collect::<Vec<_>>();
+    let count = counts.iter().sum();
+    HistogramSnapshot {
+        name,
+        help,
+        count,
+        sum_secs,
+        p50_secs: estimate_quantile(&buckets, count, 0.5),
+        p95_secs: estimate_quantile(&buckets, count, 0.95),
+        buckets,
+    }
+}
+
+/// 第一个累计次数达到 q × count 的桶的上限
+fn estimate_quantile(buckets: &[HistogramBucket], count: u64, q: f64) -> Option<f64> {
+    if count == 0 {
+        return None;
+    }
+    let rank = (q * count as f64).ceil() as u64;
+    buckets
+        .iter()
+        .find(|bucket| bucket.count >= rank)
+        .map(|bucket| bucket.le)
+}
+
+/// Prometheus 文本格式（0.0.4）
+pub fn render_prometheus(snapshot: &MetricsSnapshot) -> String {
+    let mut out = String::new();
+    for counter in &snapshot.counters {
+        let name = format!("{METRIC_PREFIX}{}", counter.name);
+        let _ = writeln!(out, "# HELP {name} {}", counter.help);
+        let _ = writeln!(out, "# TYPE {name} counter");
+        let _ = writeln!(out, "{name} {}", counter.value);
+    }
+    for histogram in &snapshot.histograms {
+        let name = format!("{METRIC_PREFIX}{}", histogram.name);
+        let _ = writeln!(out, "# HELP {name} {}", histogram.help);
+        let _ = writeln!(out, "# TYPE {name} histogram");
+        for bucket in &histogram.buckets {
+            let _ = writeln!(
+                out,
+                "{name}_bucket{{le=\"{}\"}} {}",
+                bucket.le, bucket.count
+            );
+        }
+        let _ = writeln!(out, "{name}_bucket{{le=\"+Inf\"}} {}", histogram.count);
+        let _ = writeln!(out, "{name}_sum {}", histogram.sum_secs);
+        let _ = writeln!(out, "{name}_count {}", histogram.count);
+    }
+    out
+}
+
+/// Prometheus 端点运行状态
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricsEndpointStatus {
+    pub enabled: bool,
+    pub port: u16,
+    pub running: bool,
+    pub endpoint: String,
+}
+
+struct RunningServer {
+    server: Arc<Server>,
+    worker: thread::JoinHandle<()>,
+    port: u16,
+}
+
+/// 本机 Prometheus 抓取端点
+pub struct MetricsEndpoint {
+    server: Mutex<Option<RunningServer>>,
+}
+
+impl MetricsEndpoint {
+    pub fn new() -> Self {
+        Self {
+            server: Mutex::new(None),
+        }
+    }
+
+    /// 按配置启动或停止监听；端口变化时重启
+    pub fn apply_config(&self, config: &MetricsEndpointConfig) -> Result<(), String> {
+        self.stop();
+        if !config.enabled {
+            return Ok(());
+        }
+
+        let server = Server::http(("127.0.0.1", config.port))
+            .map(Arc::new)
+            .map_err(|e| format!("监听端口 {} 失败: {e}", config.port))?;
+        let listener = server.clone();
+        let worker = thread::Builder::new()
+            .name("metrics-endpoint".to_string())
+            .spawn(move || {
+                for request in listener.incoming_requests() {
+                    handle_request(request);
+                }
+            })
+            .map_err(|e| e.to_string())?;
+
+        tracing::info!(port = config.port, "Metrics endpoint listening");
+        *self.server.lock().expect("metrics endpoint lock") = Some(RunningServer {
+            server,
+            worker,
+            port: config.port,
+        });
+        Ok(())
+    }
+
+    /// 停止监听；等待工作线程退出以释放端口
+    pub fn stop(&self) {
+        let running = self.server.lock().expect("metrics endpoint lock").take();
+        if let Some(running) = running {
+            running.server.unblock();
+            drop(running.server);
+            let _ = running.worker.join();
+            tracing::info!(port = running.port, "Metrics endpoint stopped");
+        }
+    }
+
+    pub fn status(&self, config: &MetricsEndpointConfig) -> MetricsEndpointStatus {
+        let running = self.server.lock().expect("metrics endpoint lock").is_some();
+        MetricsEndpointStatus {
+            enabled: config.enabled,
+            port: config.port,
+            running,
+            endpoint: format!("http://127.0.0.1:{}/metrics", config.port),
+        }
+    }
+}
+
+impl Default for MetricsEndpoint {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn handle_request(request: Request) {
+    let path = request.url().split('?').next().unwrap_or("").to_string();
+    let (status, content_type, body) = match (request.method(), path.as_str()) {
+        (Method::Get, "/metrics") => (
+            200,
+            "text/plain; version=0.0.4",
+            render_prometheus(&metrics_snapshot()),
+        ),
+        (_, "/metrics") => (405, "text/plain", "method not allowed".to_string()),
+        _ => (404, "text/plain", "not found".to_string()),
+    };
+
+    let response = Response::from_string(body)
+        .with_status_code(status)
+        .with_header(
+            Header::from_bytes(&b"Content-Type"[..], content_type.as_bytes())
+                .expect("static header"),
+        );
+    if let Err(err) = request.respond(response) {
+        tracing::debug!(error = %err, "Metrics endpoint respond failed");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_index() {
+        assert_eq!(bucket_index(0.0), 0);
+        assert_eq!(bucket_index(0.01), 0);
+        assert_eq!(bucket_index(0.011), 1);
+        assert_eq!(bucket_index(60.0), BUCKETS.len() - 1);
+        assert_eq!(bucket_index(61.0), BUCKETS.len());
+    }
+
+    #[test]
+    fn test_histogram_snapshot_cumulative_and_quantiles() {
+        let mut counts = [0; BUCKETS.len() + 1];
+        counts[0] = 5; // ≤ 0.01s
+        counts[3] = 4; // ≤ 0.1s
+        counts[BUCKETS.len()] = 1; // +Inf
+        let snapshot = histogram_snapshot("test", "test", &counts, 70.0);
+
+        assert_eq!(snapshot.count, 10);
+        assert_eq!(snapshot.buckets[0].count, 5);
+        assert_eq!(snapshot.buckets[3].count, 9);
+        assert_eq!(snapshot.buckets.last().unwrap().count, 9);
+        assert_eq!(snapshot.p50_secs, Some(0.01));
+        // 第 10 次落在 +Inf 桶，无法估算
+        assert_eq!(snapshot.p95_secs, None);
+
+        let empty = histogram_snapshot("test", "test", &[0; BUCKETS.len() + 1], 0.0);
+        assert_eq!(empty.p50_secs, None);
+    }
+
+    #[test]
+    fn test_render_prometheus() {
+        let mut counts = [0; BUCKETS.len() + 1];
+        counts[1] = 2;
+        let snapshot = MetricsSnapshot {
+            counters: vec![CounterSnapshot {
+                name: "db_errors_total",
+                help: "Database errors",
+                value: 3,
+            }],
+            histograms: vec![histogram_snapshot(
+                "search_duration_seconds",
+                "Search duration",
+                &counts,
+                0.04,
+            )],
+        };
+        let text = render_prometheus(&snapshot);
+
+        assert!(text.contains("# TYPE neuralvault_db_errors_total counter\n"));
+        assert!(text.contains("neuralvault_db_errors_total 3\n"));
+        assert!(text.contains("# TYPE neuralvault_search_duration_seconds histogram\n"));
+        assert!(text.contains("neuralvault_search_duration_seconds_bucket{le=\"0.01\"} 0\n"));
+        assert!(text.contains("neuralvault_search_duration_seconds_bucket{le=\"0.025\"} 2\n"));
+        assert!(text.contains("neuralvault_search_duration_seconds_bucket{le=\"+Inf\"} 2\n"));
+        assert!(text.contains("neuralvault_search_duration_seconds_sum 0.04\n"));
+        assert!(text.contains("neuralvault_search_duration_seconds_count 2\n"));
+    }
+}
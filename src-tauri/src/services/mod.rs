@@ -1,8 +1,98 @@
 mod ai;
 mod ai_config;
 mod ai_pipeline;
+mod asset_storage;
+mod bibtex;
+mod calendar;
+mod capture_profiles;
+mod chat_context;
+mod chat_tools;
+mod cost_estimate;
+mod data_migrations;
+mod edge_transfer;
+mod embedding_reindex;
+mod enex;
+mod events;
+mod file_references;
+mod focus;
+mod folder_watch;
+mod github_sync;
+mod goal_review;
+mod habits;
+mod ical;
+mod inbox_triage;
+mod integrity;
+mod jobs;
+mod markdown_import;
+mod message_capture;
+mod metrics;
+mod model_files;
+mod note_replace;
+mod notifications;
 pub mod parser;
+mod pending_changes;
+mod pomodoro;
+mod recurrence;
+mod resource_ingest;
+mod retention;
+mod search_cache;
+mod search_warmup;
+mod self_test;
+mod snapshot_sessions;
+mod source_tagging;
+mod task_reminders;
+mod task_timer;
+mod topic_restructure;
+mod usage_report;
+mod vault_export;
+mod vault_search;
+mod vector_maintenance;
 
 pub use ai::*;
 pub use ai_config::*;
 pub use ai_pipeline::*;
+pub use asset_storage::*;
+pub use bibtex::*;
+pub use calendar::*;
+pub use capture_profiles::*;
+pub use chat_context::*;
+pub use chat_tools::*;
+pub use cost_estimate::*;
+pub use data_migrations::*;
+pub use edge_transfer::*;
+pub use embedding_reindex::*;
+pub use enex::*;
+pub use events::*;
+pub use file_references::*;
+pub use focus::*;
+pub use folder_watch::*;
+pub use github_sync::*;
+pub use goal_review::*;
+pub use habits::*;
+pub use ical::*;
+pub use inbox_triage::*;
+pub use integrity::*;
+pub use jobs::*;
+pub use markdown_import::*;
+pub use message_capture::*;
+pub use metrics::*;
+pub use model_files::*;
+pub use note_replace::*;
+pub use notifications::*;
+pub use pending_changes::*;
+pub use pomodoro::*;
+pub use recurrence::*;
+pub use resource_ingest::*;
+pub use retention::*;
+pub use search_cache::*;
+pub use search_warmup::*;
+pub use self_test::*;
+pub use snapshot_sessions::*;
+pub use source_tagging::*;
+pub use task_reminders::*;
+pub use task_timer::*;
+pub use topic_restructure::*;
+pub use usage_report::*;
+pub use vault_export::*;
+pub use vault_search::*;
+pub use vector_maintenance::*;
@@ -0,0 +1,206 @@
+//! 第三方模型目录的定位与下载
+//!
+//! 启动时按配置定位模型目录（顺序见 `parser::resolve_model_dir`），安装包内置的资源目录优先于
+//! 应用数据目录。缺失的模型文件可以下载：已配置自定义目录时下载到该目录，否则下载到应用数据目录下的
+//! third_party_model。每个文件先写入 `.part` 再改名，中途失败不会留下不完整的模型文件。
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use reqwest::Client;
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::app_state::AppState;
+use crate::i18n::MessageCode;
+use crate::services::parser::{
+    downloaded_model_dir, missing_model_files, resolve_model_dir, resolved_model_dir,
+    set_resolved_model_dir, ModelDirSource,
+};
+use crate::services::{emit_event, ModelDownloadProgressPayload};
+use crate::{AppError, AppResult};
+
+/// 默认下载地址：与 Cargo.toml 中 ocr-rs 依赖同一版本的模型目录
+const DEFAULT_MODEL_DOWNLOAD_URL: &str =
+    "https://github.com/Asnly1/rust-paddle-ocr/raw/9016512/models";
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+/// 每下载这么多字节发送一次进度
+const PROGRESS_STEP_BYTES: u64 = 1024 * 1024;
+
+/// 同一时间只允许一次下载
+static DOWNLOADING: AtomicBool = AtomicBool::new(false);
+
+struct DownloadGuard;
+
+impl DownloadGuard {
+    fn acquire() -> Option<Self> {
+        DOWNLOADING
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .ok()
+            .map(|_| Self)
+    }
+}
+
+impl Drop for DownloadGuard {
+    fn drop(&mut self) {
+        DOWNLOADING.store(false, Ordering::Release);
+    }
+}
+
+/// 模型目录状态
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelDirStatus {
+    pub path: String,
+    pub source: ModelDirSource,
+    /// 缺失的 OCR 模型文件
+    pub missing: Vec<String>,
+}
+
+pub fn model_dir_status() -> ModelDirStatus {
+    let resolved = resolved_model_dir();
+    ModelDirStatus {
+        path: resolved.path.to_string_lossy().to_string(),
+        source: resolved.source,
+        missing: missing_model_files(&resolved.path)
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+    }
+}
+
+/// 按配置重新定位模型目录，返回当前状态；文件缺失只记录，由用户选择目录或下载
+pub fn apply_model_dir_config(
+    app: &AppHandle,
+    app_data_dir: &Path,
+    model_dir: Option<&str>,
+) -> ModelDirStatus {
+    let custom = model_dir
+        .map(str::trim)
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from);
+    let bundled = app.path().resource_dir().ok();
+    set_resolved_model_dir(resolve_model_dir(
+        custom.as_deref(),
+        bundled.as_deref(),
+        app_data_dir,
+    ));
+
+    let status = model_dir_status();
+    if !status.missing.is_empty() {
+        tracing::warn!(
+            path = %status.path,
+            missing = ?status.missing,
+            "Model files missing"
+        );
+    }
+    status
+}
+
+/// 下载缺失的模型文件并重新定位模型目录
+pub async fn download_missing_models(
+    app: &AppHandle,
+    state: &AppState,
+) -> AppResult<ModelDirStatus> {
+    let _guard = DownloadGuard::acquire()
+        .ok_or_else(|| AppError::coded(MessageCode::ModelDownloadRunning))?;
+    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let config = state.ai_config.lock().await.load()?;
+
+    let resolved = resolved_model_dir();
+    let target = match resolved.source {
+        ModelDirSource::Custom => resolved.path,
+        _ => downloaded_model_dir(&app_data_dir),
+    };
+    let missing = missing_model_files(&target);
+    if !missing.is_empty() {
+        fs::create_dir_all(&target)?;
+        let base_url = config
+            .model_download_url
+            .as_deref()
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .unwrap_or(DEFAULT_MODEL_DOWNLOAD_URL)
+            .trim_end_matches('/');
+        let client = Client::builder()
+            .timeout(DOWNLOAD_TIMEOUT)
+            .build()
+            .map_err(|e| e.to_string())?;
+        for file_name in missing {
+            let url = format!("{base_url}/{file_name}");
+            download_file(app, &client, &url, &target, file_name).await?;
+            tracing::info!(file_name, dir = %target.display(), "Model file downloaded");
+        }
+    }
+
+    Ok(apply_model_dir_config(
+        app,
+        &app_data_dir,
+        config.model_dir.as_deref(),
+    ))
+}
+
+async fn download_file(
+    app: &AppHandle,
+    client: &Client,
+    url: &str,
+    dir: &Path,
+    file_name: &str,
+) -> AppResult<()> {
+    let part_path = dir.join(format!("{file_name}.part"));
+    let result = write_download(app, client, url, &part_path, file_name).await;
+    match result {
+        Ok(()) => {
+            fs::rename(&part_path, dir.join(file_name))?;
+            Ok(())
+        }
+        Err(err) => {
+            let _ = fs::remove_file(&part_path);
+            Err(err)
+        }
+    }
+}
+
+async fn write_download(
+    app: &AppHandle,
+    client: &Client,
+    url: &str,
+    path: &Path,
+    file_name: &str,
+) -> AppResult<()> {
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| format!("下载 {file_name} 失败: {e}"))?;
+    if !response.status().is_success() {
+        return Err(format!("下载 {file_name} 失败: HTTP {}", response.status()).into());
+    }
+
+    let total_bytes = response.content_length();
+    let mut file = fs::File::create(path)?;
+    let mut stream = response.bytes_stream();
+    let mut downloaded_bytes = 0u64;
+    let mut next_report = PROGRESS_STEP_BYTES;
+    let progress = |downloaded_bytes| ModelDownloadProgressPayload {
+        file_name: file_name.to_string(),
+        downloaded_bytes,
+        total_bytes,
+    };
+
+    while let Some(chunk) = stream.next().await {
+        let bytes = chunk.map_err(|e| format!("下载 {file_name} 失败: {e}"))?;
+        file.write_all(&bytes)?;
+        downloaded_bytes += bytes.len() as u64;
+        if downloaded_bytes >= next_report {
+            emit_event(app, &progress(downloaded_bytes));
+            next_report = downloaded_bytes + PROGRESS_STEP_BYTES;
+        }
+    }
+    file.sync_all()?;
+    emit_event(app, &progress(downloaded_bytes));
+    Ok(())
+}
@@ -0,0 +1,184 @@
+//! 文本笔记的查找与替换
+//!
+//! 查找返回每处匹配所在行的上下文预览；替换只作用于用户确认的节点。
+//! 普通模式按字面匹配，替换文本中的 `$` 不展开；正则模式支持 `$1` 等捕获组引用。
+
+use regex::{NoExpand, Regex};
+use serde::Serialize;
+
+/// 预览中匹配前后各保留的字符数（不跨行）
+const PREVIEW_CONTEXT_CHARS: usize = 40;
+/// 每个节点最多返回的预览条数
+pub const MAX_PREVIEWS_PER_NODE: usize = 20;
+
+/// 一处匹配的预览
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NoteMatchPreview {
+    /// 从 1 开始的行号
+    pub line: usize,
+    pub before: String,
+    pub matched: String,
+    pub after: String,
+}
+
+/// 单个节点的查找结果
+#[derive(Debug, Serialize)]
+pub struct NoteFindResult {
+    pub node_id: i64,
+    pub title: String,
+    pub match_count: usize,
+    /// 最多 MAX_PREVIEWS_PER_NODE 条
+    pub previews: Vec<NoteMatchPreview>,
+}
+
+/// 单个节点的替换结果
+#[derive(Debug, Serialize)]
+pub struct NoteReplaceResult {
+    pub node_id: i64,
+    pub title: String,
+    pub replacement_count: usize,
+    /// 替换前正文保存的版本
+    pub revision_id: Option<i64>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct NoteReplaceReport {
+    pub replaced: Vec<NoteReplaceResult>,
+    pub total_replacements: usize,
+    /// 不是文本笔记、已删除或没有匹配的节点
+    pub skipped: Vec<i64>,
+}
+
+/// 编译后的查找模式
+#[derive(Debug, Clone)]
+pub struct NotePattern {
+    regex: Regex,
+    is_regex: bool,
+}
+
+impl NotePattern {
+    /// 空模式或能匹配空文本的正则视为无效，返回原因
+    pub fn new(pattern: &str, is_regex: bool) -> Result<Self, String> {
+        if pattern.is_empty() {
+            return Err("查找内容为空".to_string());
+        }
+        let regex = if is_regex {
+            Regex::new(pattern).map_err(|e| e.to_string())?
+        } else {
+            Regex::new(&regex::escape(pattern)).map_err(|e| e.to_string())?
+        };
+        if regex.is_match("") {
+            return Err("模式不能匹配空文本".to_string());
+        }
+        Ok(Self { regex, is_regex })
+    }
+
+    /// 匹配总数与前 MAX_PREVIEWS_PER_NODE 处的预览
+    pub fn find(&self, text: &str) -> (usize, Vec<NoteMatchPreview>) {
+        let mut count = 0;
+        let mut previews = Vec::new();
+        let mut line = 1;
+        let mut scanned = 0;
+        for m in self.regex.find_iter(text) {
+            count += 1;
+            if previews.len() >= MAX_PREVIEWS_PER_NODE {
+                continue;
+            }
+            line += text[scanned..m.start()].matches('\n').count();
+            scanned = m.start();
+
+            let line_start = text[..m.start()].rfind('\n').map_or(0, |i| i + 1);
+            let line_end = text[m.end()..]
+                .find('\n')
+                .map_or(text.len(), |i| m.end() + i);
+            let before = &text[line_start..m.start()];
+            let skip = before.chars().count().saturating_sub(PREVIEW_CONTEXT_CHARS);
+            previews.push(NoteMatchPreview {
+                line,
+                before: before.chars().skip(skip).collect(),
+                matched: m.as_str().to_string(),
+                after: text[m.end()..line_end]
+                    .chars()
+                    .take(PREVIEW_CONTEXT_CHARS)
+                    .collect(),
+            });
+        }
+        (count, previews)
+    }
+
+    /// 替换全部匹配，返回新正文与替换次数；没有匹配时返回 None
+    pub fn replace(&self, text: &str, replacement: &str) -> Option<(String, usize)> {
+        let count = self.regex.find_iter(text).count();
+        if count == 0 {
+            return None;
+        }
+        let replaced = if self.is_regex {
+            self.regex.replace_all(text, replacement)
+        } else {
+            self.regex.replace_all(text, NoExpand(replacement))
+        };
+        Some((replaced.into_owned(), count))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_note_pattern_new() {
+        assert!(NotePattern::new("", false).is_err());
+        assert!(NotePattern::new("a*", true).is_err());
+        assert!(NotePattern::new("(unclosed", true).is_err());
+        assert!(NotePattern::new("(unclosed", false).is_ok());
+    }
+
+    #[test]
+    fn test_find_previews() {
+        let pattern = NotePattern::new("rust", false).unwrap();
+        let (count, previews) = pattern.find("I like rust.\n用 rust 写笔记\nno match");
+        assert_eq!(count, 2);
+        assert_eq!(
+            previews[0],
+            NoteMatchPreview {
+                line: 1,
+                before: "I like ".to_string(),
+                matched: "rust".to_string(),
+                after: ".".to_string(),
+            }
+        );
+        assert_eq!(previews[1].line, 2);
+        assert_eq!(previews[1].before, "用 ");
+        assert_eq!(previews[1].after, " 写笔记");
+
+        let long = format!("{}rust{}", "x".repeat(100), "y".repeat(100));
+        let (_, previews) = pattern.find(&long);
+        assert_eq!(previews[0].before.len(), PREVIEW_CONTEXT_CHARS);
+        assert_eq!(previews[0].after.len(), PREVIEW_CONTEXT_CHARS);
+    }
+
+    #[test]
+    fn test_find_caps_previews() {
+        let pattern = NotePattern::new("a", false).unwrap();
+        let (count, previews) = pattern.find(&"a\n".repeat(30));
+        assert_eq!(count, 30);
+        assert_eq!(previews.len(), MAX_PREVIEWS_PER_NODE);
+        assert_eq!(previews[19].line, 20);
+    }
+
+    #[test]
+    fn test_replace() {
+        let literal = NotePattern::new("a.b", false).unwrap();
+        assert_eq!(
+            literal.replace("a.b axb a.b", "$1"),
+            Some(("$1 axb $1".to_string(), 2))
+        );
+        assert_eq!(literal.replace("nothing", "x"), None);
+
+        let regex = NotePattern::new(r"(\d{4})-(\d{2})", true).unwrap();
+        assert_eq!(
+            regex.replace("2024-05 and 2025-01", "$2/$1"),
+            Some(("05/2024 and 01/2025".to_string(), 2))
+        );
+    }
+}
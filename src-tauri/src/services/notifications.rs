@@ -0,0 +1,49 @@
+//! 应用内通知中心
+//!
+//! 写入 notifications 表后发送 `notification-created` 事件，前端据此刷新铃铛未读数。
+//! 专注模式开启免打扰时只落库、不推送事件，也不弹出系统通知。
+
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::app_state::AppState;
+use crate::db::{insert_notification, DbPool, NewNotification, NotificationRecord};
+use crate::services::emit_event;
+
+pub async fn push_notification(
+    db: &DbPool,
+    app: &AppHandle,
+    params: NewNotification<'_>,
+) -> Result<NotificationRecord, String> {
+    let record = insert_notification(db, params)
+        .await
+        .map_err(|e| e.to_string())?;
+    if !is_do_not_disturb(app) {
+        emit_event(app, &record);
+    }
+    Ok(record)
+}
+
+/// 写入通知中心并弹出系统通知，用于需要用户立即看到的提醒
+pub async fn push_system_notification(
+    db: &DbPool,
+    app: &AppHandle,
+    params: NewNotification<'_>,
+) -> Result<NotificationRecord, String> {
+    let record = push_notification(db, app, params).await?;
+    if !is_do_not_disturb(app) {
+        let mut builder = app.notification().builder().title(&record.title);
+        if let Some(body) = record.body.as_deref() {
+            builder = builder.body(body);
+        }
+        if let Err(err) = builder.show() {
+            tracing::warn!(error = %err, "Show system notification failed");
+        }
+    }
+    Ok(record)
+}
+
+fn is_do_not_disturb(app: &AppHandle) -> bool {
+    app.try_state::<AppState>()
+        .is_some_and(|state| state.focus.is_do_not_disturb())
+}
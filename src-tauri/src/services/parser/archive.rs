@@ -0,0 +1,317 @@
+//! 压缩包（.zip / .tar / .tar.gz / .tgz）解析
+//!
+//! 捕获时只列出条目作为资源内容；展开时把受支持类型的条目解压到目标目录，
+//! 由调用方为每个文件创建子资源。展开受条目数与解压大小限制，超出部分跳过并计数。
+
+use std::{
+    fs::{self, File},
+    io::Read,
+    path::Path,
+};
+
+use flate2::read::GzDecoder;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::db::ResourceSubtype;
+use crate::utils::{compute_sha256, get_extension, parse_file_type_from_extension};
+
+/// 单次展开最多创建的子资源数
+pub const MAX_ARCHIVE_ENTRIES: usize = 500;
+/// 单个条目解压后的大小上限
+pub const MAX_ENTRY_BYTES: u64 = 100 * 1024 * 1024;
+/// 单次展开解压后的总大小上限
+pub const MAX_TOTAL_BYTES: u64 = 1024 * 1024 * 1024;
+/// 资源内容中最多列出的条目数
+const LISTING_MAX_ENTRIES: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArchiveKind {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+/// 压缩包中的文件条目；subtype 为 Other 表示展开时跳过
+#[derive(Debug, Clone, Serialize)]
+pub struct ArchiveEntry {
+    pub path: String,
+    pub size: u64,
+    pub subtype: ResourceSubtype,
+}
+
+impl ArchiveEntry {
+    fn new(path: &str, size: u64) -> Self {
+        Self {
+            path: path.to_string(),
+            size,
+            subtype: parse_file_type_from_extension(get_extension(path).as_deref()),
+        }
+    }
+
+    pub fn is_supported(&self) -> bool {
+        self.subtype != ResourceSubtype::Other
+    }
+
+    /// 条目文件名（不含目录）
+    pub fn file_name(&self) -> &str {
+        self.path.rsplit('/').next().unwrap_or(&self.path)
+    }
+}
+
+/// 已解压到目标目录的条目，文件名为 `{uuid}.{ext}`
+#[derive(Debug, Clone)]
+pub struct ExtractedEntry {
+    pub entry: ArchiveEntry,
+    pub uuid: String,
+    pub file_name: String,
+    pub file_hash: String,
+}
+
+/// 展开结果
+#[derive(Debug, Default)]
+pub struct ArchiveExtraction {
+    pub extracted: Vec<ExtractedEntry>,
+    /// 不支持的文件类型
+    pub skipped_unsupported: usize,
+    /// 超过单条目大小上限
+    pub skipped_too_large: usize,
+    /// 超过条目数或总大小上限
+    pub truncated: usize,
+}
+
+/// 列出压缩包中的文件条目（跳过目录与系统元数据文件）
+pub fn list_archive_entries(path: &str) -> Result<Vec<ArchiveEntry>, String> {
+    let mut entries = Vec::new();
+    for_each_entry(path, &mut |name, size, _reader| {
+        entries.push(ArchiveEntry::new(name, size));
+        Ok(())
+    })?;
+    Ok(entries)
+}
+
+/// 解析压缩包为条目清单文本，作为资源内容
+pub fn parse_archive_file(path: &str) -> Result<String, String> {
+    Ok(render_listing(&list_archive_entries(path)?))
+}
+
+/// 将受支持的条目解压到 dest_dir，每解压一个条目调用一次 on_extracted
+pub fn extract_archive(
+    path: &str,
+    dest_dir: &Path,
+    on_extracted: &mut dyn FnMut(&ExtractedEntry),
+) -> Result<ArchiveExtraction, String> {
+    let mut result = ArchiveExtraction::default();
+    let mut total_bytes = 0u64;
+
+    for_each_entry(path, &mut |name, size, reader| {
+        let entry = ArchiveEntry::new(name, size);
+        if !entry.is_supported() {
+            result.skipped_unsupported += 1;
+            return Ok(());
+        }
+        if size > MAX_ENTRY_BYTES {
+            result.skipped_too_large += 1;
+            return Ok(());
+        }
+        if result.extracted.len() >= MAX_ARCHIVE_ENTRIES || total_bytes + size > MAX_TOTAL_BYTES {
+            result.truncated += 1;
+            return Ok(());
+        }
+
+        // 头部记录的大小不可信，按上限截断读取
+        let mut data = Vec::with_capacity(size as usize);
+        reader
+            .take(MAX_ENTRY_BYTES + 1)
+            .read_to_end(&mut data)
+            .map_err(|e| format!("读取压缩包条目失败 {name}: {e}"))?;
+        if data.len() as u64 > MAX_ENTRY_BYTES {
+            result.skipped_too_large += 1;
+            return Ok(());
+        }
+        total_bytes += data.len() as u64;
+
+        let uuid = Uuid::new_v4().to_string();
+        let file_name = match get_extension(name) {
+            Some(ext) => format!("{uuid}.{ext}"),
+            None => uuid.clone(),
+        };
+        fs::write(dest_dir.join(&file_name), &data)
+            .map_err(|e| format!("写入解压文件失败: {e}"))?;
+
+        let extracted = ExtractedEntry {
+            entry: ArchiveEntry {
+                size: data.len() as u64,
+                ..entry
+            },
+            uuid,
+            file_name,
+            file_hash: compute_sha256(&data),
+        };
+        on_extracted(&extracted);
+        result.extracted.push(extracted);
+        Ok(())
+    })?;
+
+    Ok(result)
+}
+
+type EntryVisitor<'a> = dyn FnMut(&str, u64, &mut dyn Read) -> Result<(), String> + 'a;
+
+fn archive_kind(path: &str) -> Result<ArchiveKind, String> {
+    match get_extension(path).as_deref() {
+        Some("zip") => Ok(ArchiveKind::Zip),
+        Some("tar") => Ok(ArchiveKind::Tar),
+        // 捕获时只保留最后一段扩展名，.tar.gz 以 .gz 存储
+        Some("gz" | "tgz") => Ok(ArchiveKind::TarGz),
+        _ => Err("不支持的压缩包格式".to_string()),
+    }
+}
+
+fn for_each_entry(path: &str, visit: &mut EntryVisitor<'_>) -> Result<(), String> {
+    let file = File::open(path).map_err(|e| format!("打开压缩包失败: {e}"))?;
+    match archive_kind(path)? {
+        ArchiveKind::Zip => {
+            let mut archive =
+                zip::ZipArchive::new(file).map_err(|e| format!("无法读取 zip: {e}"))?;
+            for index in 0..archive.len() {
+                let mut entry = archive
+                    .by_index(index)
+                    .map_err(|e| format!("读取 zip 条目失败: {e}"))?;
+                if entry.is_dir() {
+                    continue;
+                }
+                let name = entry.name().to_string();
+                if is_metadata_entry(&name) {
+                    continue;
+                }
+                let size = entry.size();
+                visit(&name, size, &mut entry)?;
+            }
+            Ok(())
+        }
+        ArchiveKind::Tar => visit_tar(tar::Archive::new(file), visit),
+        ArchiveKind::TarGz => visit_tar(tar::Archive::new(GzDecoder::new(file)), visit),
+    }
+}
+
+fn visit_tar<R: Read>(
+    mut archive: tar::Archive<R>,
+    visit: &mut EntryVisitor<'_>,
+) -> Result<(), String> {
+    let entries = archive
+        .entries()
+        .map_err(|e| format!("无法读取 tar: {e}"))?;
+    for entry in entries {
+        let mut entry = entry.map_err(|e| format!("读取 tar 条目失败: {e}"))?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let name = entry
+            .path()
+            .map_err(|e| format!("读取 tar 条目路径失败: {e}"))?
+            .to_string_lossy()
+            .into_owned();
+        if is_metadata_entry(&name) {
+            continue;
+        }
+        let size = entry.size();
+        visit(&name, size, &mut entry)?;
+    }
+    Ok(())
+}
+
+/// macOS 打包时附带的资源分叉与目录元数据
+fn is_metadata_entry(path: &str) -> bool {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    path.starts_with("__MACOSX/") || name.starts_with("._") || name == ".DS_Store"
+}
+
+fn render_listing(entries: &[ArchiveEntry]) -> String {
+    let supported = entries.iter().filter(|entry| entry.is_supported()).count();
+    let mut text = format!(
+        "压缩包内容（{} 个文件，其中 {supported} 个可展开为资源）：",
+        entries.len()
+    );
+    for entry in entries.iter().take(LISTING_MAX_ENTRIES) {
+        text.push_str(&format!("\n- {} ({})", entry.path, format_size(entry.size)));
+    }
+    if entries.len() > LISTING_MAX_ENTRIES {
+        text.push_str(&format!(
+            "\n…（其余 {} 个文件未列出）",
+            entries.len() - LISTING_MAX_ENTRIES
+        ));
+    }
+    text
+}
+
+fn format_size(bytes: u64) -> String {
+    const KB: u64 = 1024;
+    const MB: u64 = KB * 1024;
+    match bytes {
+        b if b >= MB => format!("{:.1} MB", b as f64 / MB as f64),
+        b if b >= KB => format!("{:.1} KB", b as f64 / KB as f64),
+        b => format!("{b} B"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn write_zip(dir: &Path, files: &[(&str, &[u8])]) -> String {
+        let path = dir.join("bundle.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&path).unwrap());
+        let options = zip::write::SimpleFileOptions::default();
+        for (name, data) in files {
+            writer.start_file(*name, options).unwrap();
+            writer.write_all(data).unwrap();
+        }
+        writer.finish().unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn lists_and_extracts_supported_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = write_zip(
+            dir.path(),
+            &[
+                ("docs/readme.md", b"# Hello"),
+                ("bin/tool.exe", b"MZ"),
+                ("__MACOSX/docs/._readme.md", b"junk"),
+            ],
+        );
+
+        let entries = list_archive_entries(&archive).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].file_name(), "readme.md");
+        assert_eq!(entries[0].subtype, ResourceSubtype::Text);
+        assert!(!entries[1].is_supported());
+
+        let out = tempfile::tempdir().unwrap();
+        let mut seen = 0;
+        let result = extract_archive(&archive, out.path(), &mut |_| seen += 1).unwrap();
+        assert_eq!(seen, 1);
+        assert_eq!(result.skipped_unsupported, 1);
+        let extracted = &result.extracted[0];
+        assert!(extracted.file_name.ends_with(".md"));
+        assert_eq!(
+            fs::read(out.path().join(&extracted.file_name)).unwrap(),
+            b"# Hello"
+        );
+    }
+
+    #[test]
+    fn renders_listing_with_sizes() {
+        let entries = vec![
+            ArchiveEntry::new("a.pdf", 2048),
+            ArchiveEntry::new("b.bin", 12),
+        ];
+        assert_eq!(
+            render_listing(&entries),
+            "压缩包内容（2 个文件，其中 1 个可展开为资源）：\n- a.pdf (2.0 KB)\n- b.bin (12 B)"
+        );
+    }
+}
@@ -0,0 +1,377 @@
+//! 邮件（.eml / .msg）解析
+//!
+//! - eml: MIME 邮件，用 mail-parser 解析；只有 HTML 正文时转为纯文本
+//! - msg: Outlook 的 OLE 复合文档，按 MAPI 属性流读取主题、发件人、正文与附件
+//!
+//! 附件原样返回，由调用方保存为独立资源；正文渲染为「邮件头 + 正文」的文本。
+
+use std::fs;
+use std::io::{Read, Seek};
+
+use cfb::CompoundFile;
+use chrono::{DateTime, Utc};
+use mail_parser::{Addr, Address, MessageParser};
+
+use crate::utils::{get_extension, html_to_text};
+
+/// MAPI 属性 ID
+const PR_SUBJECT: u16 = 0x0037;
+const PR_TRANSPORT_HEADERS: u16 = 0x007D;
+const PR_SENDER_NAME: u16 = 0x0C1A;
+const PR_SENDER_EMAIL: u16 = 0x0C1F;
+const PR_DISPLAY_CC: u16 = 0x0E03;
+const PR_DISPLAY_TO: u16 = 0x0E04;
+const PR_BODY: u16 = 0x1000;
+const PR_HTML: u16 = 0x1013;
+const PR_SENDER_SMTP: u16 = 0x5D01;
+const PR_ATTACH_DATA: u16 = 0x3701;
+const PR_ATTACH_FILENAME: u16 = 0x3704;
+const PR_ATTACH_LONG_FILENAME: u16 = 0x3707;
+const PR_ATTACH_MIME_TAG: u16 = 0x370E;
+/// 发送时间 / 送达时间（PT_SYSTIME）
+const PR_CLIENT_SUBMIT_TIME: u16 = 0x0039;
+const PR_MESSAGE_DELIVERY_TIME: u16 = 0x0E06;
+const PT_SYSTIME: u16 = 0x0040;
+const ATTACHMENT_STORAGE_PREFIX: &str = "__attach_version1.0_#";
+/// 顶层属性流的头部长度（附件等子对象为 8 字节）
+const TOP_LEVEL_PROPERTY_HEADER: usize = 32;
+/// 1601-01-01 到 1970-01-01 的秒数
+const FILETIME_UNIX_OFFSET_SECS: i64 = 11_644_473_600;
+
+/// 解析后的邮件
+#[derive(Debug, Default)]
+pub struct ParsedEmail {
+    pub subject: Option<String>,
+    /// `名字 <地址>` 或地址
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub cc: Option<String>,
+    /// RFC 3339
+    pub date: Option<String>,
+    pub body: String,
+    pub attachments: Vec<EmailAttachment>,
+}
+
+/// 邮件附件
+#[derive(Debug, Default)]
+pub struct EmailAttachment {
+    pub file_name: Option<String>,
+    pub mime: Option<String>,
+    pub data: Vec<u8>,
+}
+
+impl ParsedEmail {
+    /// 渲染为资源正文：邮件头、正文与附件清单
+    pub fn to_text(&self) -> String {
+        let headers = [
+            ("主题", &self.subject),
+            ("发件人", &self.from),
+            ("收件人", &self.to),
+            ("抄送", &self.cc),
+            ("日期", &self.date),
+        ];
+        let mut lines: Vec<String> = headers
+            .iter()
+            .filter_map(|(label, value)| value.as_deref().map(|v| format!("{label}：{v}")))
+            .collect();
+        if !self.body.is_empty() {
+            lines.push(String::new());
+            lines.push(self.body.clone());
+        }
+        let names: Vec<&str> = self
+            .attachments
+            .iter()
+            .filter_map(|attachment| attachment.file_name.as_deref())
+            .collect();
+        if !names.is_empty() {
+            lines.push(String::new());
+            lines.push(format!("附件：{}", names.join(", ")));
+        }
+        lines.join("\n")
+    }
+}
+
+/// 按扩展名解析邮件
+pub fn parse_email_file(path: &str) -> Result<ParsedEmail, String> {
+    match get_extension(path).as_deref() {
+        Some("eml") => {
+            let bytes = fs::read(path).map_err(|e| format!("读取邮件失败: {e}"))?;
+            parse_eml(&bytes)
+        }
+        Some("msg") => {
+            let mut file = cfb::open(path).map_err(|e| format!("无法解析 Outlook 邮件: {e}"))?;
+            parse_msg(&mut file)
+        }
+        other => Err(format!("不支持的邮件格式: {}", other.unwrap_or("未知"))),
+    }
+}
+
+fn parse_eml(bytes: &[u8]) -> Result<ParsedEmail, String> {
+    let message = MessageParser::default()
+        .parse(bytes)
+        .ok_or_else(|| "无法解析邮件".to_string())?;
+
+    let body = match message.body_text(0) {
+        Some(text) => text.into_owned(),
+        None => message
+            .body_html(0)
+            .map(|html| html_to_text(&html))
+            .unwrap_or_default(),
+    };
+    // 没有文件名的部分多为正文内嵌图片（签名、logo），不作为附件
+    let attachments = message
+        .attachments()
+        .filter_map(|part| {
+            let file_name = part.attachment_name()?.trim();
+            (!file_name.is_empty()).then(|| EmailAttachment {
+                file_name: Some(file_name.to_string()),
+                mime: part.content_type().map(|ct| match ct.subtype() {
+                    Some(subtype) => format!("{}/{subtype}", ct.ctype()),
+                    None => ct.ctype().to_string(),
+                }),
+                data: part.contents().to_vec(),
+            })
+        })
+        .collect();
+
+    Ok(ParsedEmail {
+        subject: non_empty(message.subject()),
+        from: message
+            .from()
+            .and_then(|from| from.first())
+            .map(format_addr),
+        to: message.to().and_then(format_address_list),
+        cc: message.cc().and_then(format_address_list),
+        // 与 msg 统一为 chrono 的 RFC 3339 格式
+        date: message
+            .date()
+            .and_then(|date| DateTime::parse_from_rfc3339(&date.to_rfc3339()).ok())
+            .map(|date| date.to_rfc3339()),
+        body: body.trim().to_string(),
+        attachments,
+    })
+}
+
+fn format_addr(addr: &Addr) -> String {
+    match (addr.name(), addr.address()) {
+        (Some(name), Some(address)) if name != address => format!("{name} <{address}>"),
+        (_, Some(address)) => address.to_string(),
+        (Some(name), None) => name.to_string(),
+        (None, None) => String::new(),
+    }
+}
+
+fn format_address_list(address: &Address) -> Option<String> {
+    let list: Vec<String> = address
+        .iter()
+        .map(format_addr)
+        .filter(|addr| !addr.is_empty())
+        .collect();
+    (!list.is_empty()).then(|| list.join(", "))
+}
+
+fn parse_msg<F: Read + Seek>(file: &mut CompoundFile<F>) -> Result<ParsedEmail, String> {
+    let root = "";
+    let body = match read_msg_string(file, root, PR_BODY) {
+        Some(text) if !text.trim().is_empty() => text,
+        _ => read_msg_binary(file, root, PR_HTML)
+            .map(|html| html_to_text(&String::from_utf8_lossy(&html)))
+            .or_else(|| read_msg_string(file, root, PR_HTML).map(|html| html_to_text(&html)))
+            .unwrap_or_default(),
+    };
+
+    let sender_name = read_msg_string(file, root, PR_SENDER_NAME);
+    let sender_address = read_msg_string(file, root, PR_SENDER_SMTP)
+        .or_else(|| read_msg_string(file, root, PR_SENDER_EMAIL))
+        // Exchange 内部地址（/O=...）不是邮箱
+        .filter(|address| address.contains('@'));
+    let from = match (sender_name, sender_address) {
+        (Some(name), Some(address)) if name != address => Some(format!("{name} <{address}>")),
+        (name, address) => address.or(name),
+    };
+
+    let date = read_msg_string(file, root, PR_TRANSPORT_HEADERS)
+        .and_then(|headers| date_from_headers(&headers))
+        .or_else(|| read_msg_time(file, PR_CLIENT_SUBMIT_TIME))
+        .or_else(|| read_msg_time(file, PR_MESSAGE_DELIVERY_TIME));
+
+    let attachment_dirs: Vec<String> = file
+        .read_root_storage()
+        .filter(|entry| entry.is_storage() && entry.name().starts_with(ATTACHMENT_STORAGE_PREFIX))
+        .map(|entry| format!("/{}", entry.name()))
+        .collect();
+    let attachments = attachment_dirs
+        .iter()
+        .filter_map(|dir| {
+            // 嵌入的 Outlook 邮件等对象没有数据流，跳过
+            let data = read_msg_binary(file, dir, PR_ATTACH_DATA)?;
+            Some(EmailAttachment {
+                file_name: read_msg_string(file, dir, PR_ATTACH_LONG_FILENAME)
+                    .or_else(|| read_msg_string(file, dir, PR_ATTACH_FILENAME)),
+                mime: read_msg_string(file, dir, PR_ATTACH_MIME_TAG),
+                data,
+            })
+        })
+        .collect();
+
+    Ok(ParsedEmail {
+        subject: read_msg_string(file, root, PR_SUBJECT),
+        from,
+        to: read_msg_string(file, root, PR_DISPLAY_TO),
+        cc: read_msg_string(file, root, PR_DISPLAY_CC),
+        date,
+        body: body.trim().to_string(),
+        attachments,
+    })
+}
+
+fn read_msg_stream<F: Read + Seek>(file: &mut CompoundFile<F>, path: &str) -> Option<Vec<u8>> {
+    let mut stream = file.open_stream(path).ok()?;
+    let mut buf = Vec::new();
+    stream.read_to_end(&mut buf).ok()?;
+    Some(buf)
+}
+
+/// 字符串属性：优先 Unicode（001F，UTF-16LE），其次 8 位编码（001E）
+fn read_msg_string<F: Read + Seek>(
+    file: &mut CompoundFile<F>,
+    dir: &str,
+    prop: u16,
+) -> Option<String> {
+    let text =
+        if let Some(bytes) = read_msg_stream(file, &format!("{dir}/__substg1.0_{prop:04X}001F")) {
+            decode_utf16le(&bytes)
+        } else {
+            let bytes = read_msg_stream(file, &format!("{dir}/__substg1.0_{prop:04X}001E"))?;
+            String::from_utf8_lossy(&bytes).into_owned()
+        };
+    non_empty(Some(text.trim_end_matches('\0')))
+}
+
+fn read_msg_binary<F: Read + Seek>(
+    file: &mut CompoundFile<F>,
+    dir: &str,
+    prop: u16,
+) -> Option<Vec<u8>> {
+    read_msg_stream(file, &format!("{dir}/__substg1.0_{prop:04X}0102"))
+}
+
+/// 从顶层定长属性流读取时间属性
+fn read_msg_time<F: Read + Seek>(file: &mut CompoundFile<F>, prop: u16) -> Option<String> {
+    let stream = read_msg_stream(file, "/__properties_version1.0")?;
+    find_systime(&stream, prop)
+}
+
+/// 属性流条目为 16 字节：tag（低 16 位类型、高 16 位 ID）、flags、8 字节值
+fn find_systime(stream: &[u8], prop: u16) -> Option<String> {
+    let tag = (u32::from(prop) << 16) | u32::from(PT_SYSTIME);
+    stream
+        .get(TOP_LEVEL_PROPERTY_HEADER..)?
+        .chunks_exact(16)
+        .find(|entry| u32::from_le_bytes([entry[0], entry[1], entry[2], entry[3]]) == tag)
+        .and_then(|entry| {
+            let filetime = u64::from_le_bytes(entry[8..16].try_into().ok()?);
+            filetime_to_rfc3339(filetime)
+        })
+}
+
+fn filetime_to_rfc3339(filetime: u64) -> Option<String> {
+    let secs = (filetime / 10_000_000) as i64 - FILETIME_UNIX_OFFSET_SECS;
+    let nanos = (filetime % 10_000_000) as u32 * 100;
+    DateTime::<Utc>::from_timestamp(secs, nanos).map(|date| date.to_rfc3339())
+}
+
+/// 从原始邮件头中取 Date 字段
+fn date_from_headers(headers: &str) -> Option<String> {
+    let value = headers.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.trim()
+            .eq_ignore_ascii_case("date")
+            .then(|| value.trim())
+    })?;
+    DateTime::parse_from_rfc2822(value)
+        .ok()
+        .map(|date| date.to_rfc3339())
+}
+
+fn decode_utf16le(bytes: &[u8]) -> String {
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_eml_headers_body_and_attachments() {
+        let raw = concat!(
+            "From: Alice <alice@example.com>\r\n",
+            "To: Bob <bob@example.com>, carol@example.com\r\n",
+            "Subject: Quarterly report\r\n",
+            "Date: Tue, 14 Jan 2025 09:30:00 +0000\r\n",
+            "MIME-Version: 1.0\r\n",
+            "Content-Type: multipart/mixed; boundary=\"b1\"\r\n",
+            "\r\n",
+            "--b1\r\n",
+            "Content-Type: text/html; charset=utf-8\r\n",
+            "\r\n",
+            "<p>Numbers are <b>up</b>.</p>\r\n",
+            "--b1\r\n",
+            "Content-Type: text/plain; name=\"notes.txt\"\r\n",
+            "Content-Disposition: attachment; filename=\"notes.txt\"\r\n",
+            "\r\n",
+            "hello\r\n",
+            "--b1--\r\n",
+        );
+
+        let email = parse_eml(raw.as_bytes()).unwrap();
+        assert_eq!(email.subject.as_deref(), Some("Quarterly report"));
+        assert_eq!(email.from.as_deref(), Some("Alice <alice@example.com>"));
+        assert_eq!(
+            email.to.as_deref(),
+            Some("Bob <bob@example.com>, carol@example.com")
+        );
+        assert_eq!(email.date.as_deref(), Some("2025-01-14T09:30:00+00:00"));
+        assert!(email.body.contains("Numbers are"));
+        assert_eq!(email.attachments.len(), 1);
+        assert_eq!(email.attachments[0].file_name.as_deref(), Some("notes.txt"));
+        assert!(email.to_text().ends_with("附件：notes.txt"));
+    }
+
+    #[test]
+    fn reads_date_from_transport_headers() {
+        let headers = "Received: from mx\r\nDate: Mon, 13 Jan 2025 08:00:00 +0800\r\n";
+        assert_eq!(
+            date_from_headers(headers).as_deref(),
+            Some("2025-01-13T08:00:00+08:00")
+        );
+    }
+
+    #[test]
+    fn finds_systime_property() {
+        let mut stream = vec![0u8; TOP_LEVEL_PROPERTY_HEADER];
+        let tag = (u32::from(PR_CLIENT_SUBMIT_TIME) << 16) | u32::from(PT_SYSTIME);
+        stream.extend_from_slice(&tag.to_le_bytes());
+        stream.extend_from_slice(&[0; 4]);
+        // 2025-01-01T00:00:00Z
+        let filetime = (1_735_689_600 + FILETIME_UNIX_OFFSET_SECS as u64) * 10_000_000;
+        stream.extend_from_slice(&filetime.to_le_bytes());
+
+        assert_eq!(
+            find_systime(&stream, PR_CLIENT_SUBMIT_TIME).as_deref(),
+            Some("2025-01-01T00:00:00+00:00")
+        );
+        assert_eq!(find_systime(&stream, PR_MESSAGE_DELIVERY_TIME), None);
+    }
+}
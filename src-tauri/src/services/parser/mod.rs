@@ -4,27 +4,43 @@
 //! - Text files
 //! - Images (via OCR)
 //! - PDFs (text extraction + OCR fallback)
+//! - Office documents (docx / pptx / xlsx / odt / odp / ods)
+//! - Audio (transcribed with local whisper.cpp, or later by the AI pipeline)
+//! - Jupyter notebooks (markdown / code cells with summarized outputs)
+//! - Emails (.eml / .msg headers and body; attachments are returned for the caller to store)
+//! - Archives (.zip / .tar / .tar.gz entry listings; supported entries can be extracted as resources)
 
+mod archive;
+mod email;
+mod model_dir;
+mod notebook;
 mod ocr;
+mod office;
 mod pdf;
 mod text;
+mod transcribe;
 
-pub use ocr::{parse_image_file};
+pub use archive::{
+    extract_archive, list_archive_entries, parse_archive_file, ArchiveEntry, ArchiveExtraction,
+    ExtractedEntry, MAX_ARCHIVE_ENTRIES, MAX_ENTRY_BYTES, MAX_TOTAL_BYTES,
+};
+pub use email::{parse_email_file, EmailAttachment, ParsedEmail};
+pub use model_dir::{
+    downloaded_model_dir, missing_model_files, resolve_model_dir, resolved_model_dir,
+    set_resolved_model_dir, third_party_model_dir, ModelDirSource, ResolvedModelDir,
+    REQUIRED_MODEL_FILES,
+};
+pub use notebook::{
+    parse_notebook_cells, parse_notebook_file, NotebookCell, NotebookCellKind,
+};
+pub use ocr::{build_ocr_engine, parse_image_file, preview_image_text};
+pub use office::parse_office_file;
 pub use pdf::{parse_pdf_file, parse_pdf_pages_with_fallback};
 pub use text::{build_text_title, parse_text_file};
-
-use std::path::PathBuf;
+pub use transcribe::{local_whisper_binary, transcribe_audio_file};
 
 use crate::db::ResourceSubtype;
 
-/// Get the third-party model directory path
-pub fn third_party_model_dir() -> PathBuf {
-    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-        .parent()
-        .expect("Missing project root")
-        .join("third_party_model")
-}
-
 /// Progress callback for long-running parse operations
 pub type ProgressCallback = Box<dyn Fn(&str, Option<u8>, Option<&str>) + Send + Sync>;
 
@@ -50,6 +66,10 @@ pub fn parse_resource_content(
             Ok(Some(parse_pdf_file(path, progress_callback)?))
         }
         ResourceSubtype::Image => {
+            // Text confirmed by the user in the capture preview replaces OCR
+            if let Some(text) = content.filter(|text| !text.trim().is_empty()) {
+                return Ok(Some(text.to_string()));
+            }
             let path = file_path.ok_or_else(|| "缺少图片路径".to_string())?;
             if let Some(cb) = progress_callback {
                 cb("ocr", Some(0), None);
@@ -60,6 +80,27 @@ pub fn parse_resource_content(
             }
             Ok(Some(text))
         }
+        ResourceSubtype::Office => {
+            let path = file_path.ok_or_else(|| "缺少文档路径".to_string())?;
+            Ok(Some(parse_office_file(path)?))
+        }
+        ResourceSubtype::Audio => {
+            let path = file_path.ok_or_else(|| "缺少音频路径".to_string())?;
+            // None when local whisper is unavailable; the AI pipeline transcribes it instead
+            transcribe_audio_file(path, progress_callback)
+        }
+        ResourceSubtype::Notebook => {
+            let path = file_path.ok_or_else(|| "缺少笔记本路径".to_string())?;
+            Ok(Some(parse_notebook_file(path)?))
+        }
+        ResourceSubtype::Email => {
+            let path = file_path.ok_or_else(|| "缺少邮件路径".to_string())?;
+            Ok(Some(parse_email_file(path)?.to_text()))
+        }
+        ResourceSubtype::Archive => {
+            let path = file_path.ok_or_else(|| "缺少压缩包路径".to_string())?;
+            Ok(Some(parse_archive_file(path)?))
+        }
         ResourceSubtype::Url => Ok(content.map(|c| c.to_string())),
         ResourceSubtype::Epub | ResourceSubtype::Other => Err("暂不支持该类型".to_string()),
     }
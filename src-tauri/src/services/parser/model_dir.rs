@@ -0,0 +1,165 @@
+//! Location of third-party model files (OCR models, pdfium, whisper.cpp)
+//!
+//! Resolved once at startup and again after the user picks a directory or downloads the
+//! models. Candidates in order: the configured directory, the bundled resource directory,
+//! `third_party_model/` under the app data directory (download target), and in debug builds
+//! the repository checkout.
+
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use serde::Serialize;
+
+/// Files OCR needs; a candidate directory is used only when all of them exist
+pub const REQUIRED_MODEL_FILES: &[&str] = &[
+    "PP-OCRv5_mobile_det.mnn",
+    "PP-OCRv5_mobile_rec.mnn",
+    "ppocr_keys_v5.txt",
+];
+
+/// Where the model directory came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelDirSource {
+    /// Configured by the user
+    Custom,
+    /// Shipped with the app (`bundle.resources`)
+    Bundled,
+    /// `third_party_model/` under the app data directory
+    Downloaded,
+    /// Repository checkout, debug builds only
+    Development,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ResolvedModelDir {
+    pub path: PathBuf,
+    pub source: ModelDirSource,
+}
+
+static MODEL_DIR: RwLock<Option<ResolvedModelDir>> = RwLock::new(None);
+
+/// Get the third-party model directory path
+pub fn third_party_model_dir() -> PathBuf {
+    resolved_model_dir().path
+}
+
+/// Current model directory; before startup resolution this is the development checkout
+pub fn resolved_model_dir() -> ResolvedModelDir {
+    MODEL_DIR
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_else(|| ResolvedModelDir {
+            path: development_model_dir(),
+            source: ModelDirSource::Development,
+        })
+}
+
+pub fn set_resolved_model_dir(dir: ResolvedModelDir) {
+    *MODEL_DIR.write().unwrap_or_else(|e| e.into_inner()) = Some(dir);
+}
+
+/// Download target under the app data directory
+pub fn downloaded_model_dir(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join("third_party_model")
+}
+
+fn development_model_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .map(|root| root.join("third_party_model"))
+        .unwrap_or_default()
+}
+
+/// Required files that are not present in `dir`
+pub fn missing_model_files(dir: &Path) -> Vec<&'static str> {
+    REQUIRED_MODEL_FILES
+        .iter()
+        .filter(|name| !dir.join(name).is_file())
+        .copied()
+        .collect()
+}
+
+/// Pick the model directory
+///
+/// A configured directory always wins, even when incomplete, so the user sees what is
+/// missing there. Otherwise the first complete candidate is used, falling back to the
+/// download target.
+pub fn resolve_model_dir(
+    custom: Option<&Path>,
+    bundled: Option<&Path>,
+    app_data_dir: &Path,
+) -> ResolvedModelDir {
+    if let Some(path) = custom {
+        return ResolvedModelDir {
+            path: path.to_path_buf(),
+            source: ModelDirSource::Custom,
+        };
+    }
+
+    let downloaded = downloaded_model_dir(app_data_dir);
+    let mut candidates = Vec::new();
+    if let Some(path) = bundled {
+        candidates.push((path.to_path_buf(), ModelDirSource::Bundled));
+    }
+    candidates.push((downloaded.clone(), ModelDirSource::Downloaded));
+    if cfg!(debug_assertions) {
+        candidates.push((development_model_dir(), ModelDirSource::Development));
+    }
+
+    candidates
+        .into_iter()
+        .find(|(path, _)| missing_model_files(path).is_empty())
+        .map(|(path, source)| ResolvedModelDir { path, source })
+        .unwrap_or(ResolvedModelDir {
+            path: downloaded,
+            source: ModelDirSource::Downloaded,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_models(dir: &Path) {
+        std::fs::create_dir_all(dir).unwrap();
+        for name in REQUIRED_MODEL_FILES {
+            std::fs::write(dir.join(name), b"model").unwrap();
+        }
+    }
+
+    #[test]
+    fn test_resolve_prefers_custom_then_complete_candidates() {
+        let root = tempfile::tempdir().unwrap();
+        let app_data = root.path().join("data");
+        let bundled = root.path().join("resources");
+        let custom = root.path().join("custom");
+
+        let resolved = resolve_model_dir(Some(&custom), Some(&bundled), &app_data);
+        assert_eq!(resolved.source, ModelDirSource::Custom);
+        assert_eq!(resolved.path, custom);
+
+        // An incomplete bundled directory is skipped (the checkout may still match in debug)
+        std::fs::create_dir_all(&bundled).unwrap();
+        let resolved = resolve_model_dir(None, Some(&bundled), &app_data);
+        assert_ne!(resolved.source, ModelDirSource::Bundled);
+
+        write_models(&downloaded_model_dir(&app_data));
+        let resolved = resolve_model_dir(None, Some(&bundled), &app_data);
+        assert_eq!(resolved.source, ModelDirSource::Downloaded);
+
+        write_models(&bundled);
+        let resolved = resolve_model_dir(None, Some(&bundled), &app_data);
+        assert_eq!(resolved.source, ModelDirSource::Bundled);
+        assert_eq!(resolved.path, bundled);
+    }
+
+    #[test]
+    fn test_missing_model_files() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(missing_model_files(dir.path()), REQUIRED_MODEL_FILES);
+        write_models(dir.path());
+        assert!(missing_model_files(dir.path()).is_empty());
+    }
+}
@@ -0,0 +1,234 @@
+//! Jupyter 笔记本（.ipynb）解析
+//!
+//! 按单元格顺序渲染为 Markdown：
+//! - markdown 单元格原样保留
+//! - code 单元格放进带语言标记的代码块，输出只保留摘要（文本截断、图片占位、错误一行）
+//! - raw 单元格按纯文本保留
+//!
+//! `parse_notebook_cells` 供按单元格切分向量使用，单元格序号从 0 开始，与 Jupyter 中的顺序一致。
+
+use std::fs;
+
+use serde_json::Value;
+
+/// 单个输出最多保留的行数 / 字符数
+const OUTPUT_MAX_LINES: usize = 20;
+const OUTPUT_MAX_CHARS: usize = 2000;
+const DEFAULT_LANGUAGE: &str = "python";
+
+/// 单元格类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotebookCellKind {
+    Markdown,
+    Code,
+    Raw,
+}
+
+impl NotebookCellKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Markdown => "markdown",
+            Self::Code => "code",
+            Self::Raw => "raw",
+        }
+    }
+}
+
+/// 渲染后的单元格；空单元格会被跳过，但 index 仍为原始序号
+#[derive(Debug, Clone)]
+pub struct NotebookCell {
+    pub index: usize,
+    pub kind: NotebookCellKind,
+    pub text: String,
+}
+
+/// 解析笔记本为完整文本，单元格之间空一行
+pub fn parse_notebook_file(path: &str) -> Result<String, String> {
+    let cells = parse_notebook_cells(path)?;
+    Ok(cells
+        .into_iter()
+        .map(|cell| cell.text)
+        .collect::<Vec<_>>()
+        .join("\n\n"))
+}
+
+/// 解析笔记本为逐个单元格
+pub fn parse_notebook_cells(path: &str) -> Result<Vec<NotebookCell>, String> {
+    let raw = fs::read_to_string(path).map_err(|e| format!("读取笔记本失败: {e}"))?;
+    let notebook: Value =
+        serde_json::from_str(&raw).map_err(|e| format!("无法解析 Jupyter 笔记本: {e}"))?;
+    render_cells(&notebook)
+}
+
+fn render_cells(notebook: &Value) -> Result<Vec<NotebookCell>, String> {
+    let cells = notebook
+        .get("cells")
+        .and_then(Value::as_array)
+        .ok_or_else(|| "笔记本缺少 cells（仅支持 nbformat 4）".to_string())?;
+    let language = notebook_language(notebook);
+
+    Ok(cells
+        .iter()
+        .enumerate()
+        .filter_map(|(index, cell)| {
+            let source = multiline_text(cell.get("source"));
+            let source = source.trim();
+            let (kind, text) = match cell.get("cell_type").and_then(Value::as_str) {
+                Some("markdown") => (NotebookCellKind::Markdown, source.to_string()),
+                Some("code") => (
+                    NotebookCellKind::Code,
+                    render_code_cell(cell, source, language),
+                ),
+                _ => (NotebookCellKind::Raw, source.to_string()),
+            };
+            (!text.is_empty()).then_some(NotebookCell { index, kind, text })
+        })
+        .collect())
+}
+
+/// 内核语言：kernelspec.language → language_info.name → python
+fn notebook_language(notebook: &Value) -> &str {
+    let metadata = notebook.get("metadata");
+    metadata
+        .and_then(|m| m.pointer("/kernelspec/language"))
+        .or_else(|| metadata.and_then(|m| m.pointer("/language_info/name")))
+        .and_then(Value::as_str)
+        .filter(|lang| !lang.is_empty())
+        .unwrap_or(DEFAULT_LANGUAGE)
+}
+
+fn render_code_cell(cell: &Value, source: &str, language: &str) -> String {
+    let outputs: Vec<String> = cell
+        .get("outputs")
+        .and_then(Value::as_array)
+        .map(|outputs| outputs.iter().filter_map(summarize_output).collect())
+        .unwrap_or_default();
+    if source.is_empty() && outputs.is_empty() {
+        return String::new();
+    }
+
+    let mut text = format!("```{language}\n{source}\n```");
+    if !outputs.is_empty() {
+        text.push_str("\n\n输出:\n");
+        text.push_str(&outputs.join("\n"));
+    }
+    text
+}
+
+/// 输出摘要：文本截断，图片等二进制输出只留占位，错误只留异常名与信息
+fn summarize_output(output: &Value) -> Option<String> {
+    match output.get("output_type").and_then(Value::as_str)? {
+        "stream" => truncate_output(&multiline_text(output.get("text"))),
+        "execute_result" | "display_data" => {
+            let data = output.get("data")?.as_object()?;
+            if let Some(text) = data.get("text/plain") {
+                return truncate_output(&multiline_text(Some(text)));
+            }
+            data.keys()
+                .find(|mime| mime.starts_with("image/"))
+                .or_else(|| data.keys().next())
+                .map(|mime| format!("[{mime} 输出]"))
+        }
+        "error" => {
+            let name = output
+                .get("ename")
+                .and_then(Value::as_str)
+                .unwrap_or("Error");
+            match output.get("evalue").and_then(Value::as_str) {
+                Some(value) if !value.is_empty() => Some(format!("{name}: {value}")),
+                _ => Some(name.to_string()),
+            }
+        }
+        _ => None,
+    }
+}
+
+fn truncate_output(text: &str) -> Option<String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    let lines: Vec<&str> = text.lines().collect();
+    let mut kept: String = lines
+        .iter()
+        .take(OUTPUT_MAX_LINES)
+        .copied()
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut truncated = lines.len() > OUTPUT_MAX_LINES;
+    if kept.chars().count() > OUTPUT_MAX_CHARS {
+        kept = kept.chars().take(OUTPUT_MAX_CHARS).collect();
+        truncated = true;
+    }
+    if truncated {
+        kept.push_str("\n…（输出已截断）");
+    }
+    Some(kept)
+}
+
+/// nbformat 中 source / text 可以是字符串或字符串数组（数组元素自带换行）
+fn multiline_text(value: Option<&Value>) -> String {
+    match value {
+        Some(Value::String(text)) => text.clone(),
+        Some(Value::Array(parts)) => parts.iter().filter_map(Value::as_str).collect(),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn renders_markdown_and_code_cells_with_outputs() {
+        let notebook = json!({
+            "metadata": { "kernelspec": { "language": "python" } },
+            "nbformat": 4,
+            "cells": [
+                { "cell_type": "markdown", "source": ["# Title\n", "Intro"] },
+                { "cell_type": "code", "source": "", "outputs": [] },
+                {
+                    "cell_type": "code",
+                    "source": ["import numpy as np\n", "np.arange(3)"],
+                    "outputs": [
+                        { "output_type": "execute_result", "data": { "text/plain": ["array([0, 1, 2])"] } },
+                        { "output_type": "display_data", "data": { "image/png": "iVBOR..." } },
+                        { "output_type": "error", "ename": "ValueError", "evalue": "bad input" }
+                    ]
+                }
+            ]
+        });
+
+        let cells = render_cells(&notebook).unwrap();
+        assert_eq!(cells.len(), 2);
+        assert_eq!(cells[0].kind, NotebookCellKind::Markdown);
+        assert_eq!(cells[0].text, "# Title\nIntro");
+        assert_eq!(cells[1].index, 2);
+        assert_eq!(
+            cells[1].text,
+            "```python\nimport numpy as np\nnp.arange(3)\n```\n\n输出:\n\
+             array([0, 1, 2])\n[image/png 输出]\nValueError: bad input"
+        );
+    }
+
+    #[test]
+    fn truncates_long_outputs() {
+        let long = (0..50)
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let kept = truncate_output(&long).unwrap();
+        assert!(kept.starts_with("0\n1\n"));
+        assert!(kept.ends_with("…（输出已截断）"));
+        assert_eq!(kept.lines().count(), OUTPUT_MAX_LINES + 1);
+        assert_eq!(truncate_output("  \n"), None);
+    }
+
+    #[test]
+    fn falls_back_to_language_info_name() {
+        let notebook = json!({ "metadata": { "language_info": { "name": "julia" } } });
+        assert_eq!(notebook_language(&notebook), "julia");
+        assert_eq!(notebook_language(&json!({})), DEFAULT_LANGUAGE);
+    }
+}
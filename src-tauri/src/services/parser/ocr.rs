@@ -1,10 +1,13 @@
 //! OCR (Optical Character Recognition) utilities
 
-use image::DynamicImage;
+use image::{imageops::FilterType, DynamicImage};
 use ocr_rs::OcrEngine;
 
 use super::third_party_model_dir;
 
+/// Preview OCR downsizes images whose longer side exceeds this (screenshots on HiDPI displays)
+const PREVIEW_MAX_SIDE: u32 = 2048;
+
 /// Build OCR engine using models from third_party_model directory
 pub fn build_ocr_engine() -> Result<OcrEngine, String> {
     let model_dir = third_party_model_dir();
@@ -49,3 +52,15 @@ pub fn parse_image_file(path: &str) -> Result<String, String> {
         Ok(text)
     }
 }
+
+/// Fast OCR for previews: large images are downscaled first; empty text is not an error
+pub fn preview_image_text(path: &str) -> Result<String, String> {
+    let image = image::open(path).map_err(|e| e.to_string())?;
+    let image = if image.width().max(image.height()) > PREVIEW_MAX_SIDE {
+        image.resize(PREVIEW_MAX_SIDE, PREVIEW_MAX_SIDE, FilterType::Triangle)
+    } else {
+        image
+    };
+    let engine = build_ocr_engine()?;
+    ocr_image_with_engine(&engine, &image)
+}
@@ -0,0 +1,418 @@
+//! Office 文档文本提取
+//!
+//! OOXML（docx / pptx / xlsx）与 ODF（odt / odp / ods）都是 zip 包内的 XML：
+//! - docx: `word/document.xml`，每个段落一行
+//! - pptx: `ppt/slides/slideN.xml`，按幻灯片编号顺序，幻灯片之间空一行
+//! - xlsx: 各工作表按工作簿顺序输出为 `## 表名` + 行，单元格以制表符分隔
+//! - odt / odp / ods: `content.xml` 中的段落（表格单元格各占一行）
+
+use std::fs::File;
+use std::io::{Read, Seek};
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use zip::result::ZipError;
+use zip::ZipArchive;
+
+use super::archive::MAX_ENTRY_BYTES;
+use crate::utils::get_extension;
+
+/// 段落元素（OOXML 的 w:p / a:p，ODF 的 text:p / text:h）
+const PARAGRAPH_TAGS: &[&[u8]] = &[b"p", b"h"];
+/// OOXML 的文本元素（w:t / a:t）
+const OOXML_TEXT_TAGS: &[&[u8]] = &[b"t"];
+
+/// 文本节点的收集范围
+#[derive(Clone, Copy)]
+enum TextScope {
+    /// 只收集这些元素内的文本
+    Elements(&'static [&'static [u8]]),
+    /// 收集段落内的全部文本（ODF 文本可嵌套在 text:span 等元素中）
+    Paragraphs,
+}
+
+impl TextScope {
+    fn contains(self, name: &[u8]) -> bool {
+        match self {
+            Self::Elements(tags) => tags.contains(&name),
+            Self::Paragraphs => PARAGRAPH_TAGS.contains(&name),
+        }
+    }
+}
+
+/// 按扩展名解析 Office 文档
+pub fn parse_office_file(path: &str) -> Result<String, String> {
+    let file = File::open(path).map_err(|e| format!("打开文件失败: {e}"))?;
+    let mut archive = ZipArchive::new(file).map_err(|e| format!("无法解析 Office 文档: {e}"))?;
+
+    match get_extension(path).as_deref() {
+        Some("docx") => {
+            let xml = read_entry(&mut archive, "word/document.xml")?;
+            extract_paragraphs(&xml, TextScope::Elements(OOXML_TEXT_TAGS))
+        }
+        Some("pptx") => parse_pptx(&mut archive),
+        Some("xlsx") => parse_xlsx(&mut archive),
+        Some("odt" | "odp" | "ods") => {
+            let xml = read_entry(&mut archive, "content.xml")?;
+            extract_paragraphs(&xml, TextScope::Paragraphs)
+        }
+        other => Err(format!("不支持的 Office 格式: {}", other.unwrap_or("未知"))),
+    }
+}
+
+fn read_entry<R: Read + Seek>(archive: &mut ZipArchive<R>, name: &str) -> Result<String, String> {
+    read_optional_entry(archive, name)?.ok_or_else(|| format!("文档缺少 {name}"))
+}
+
+fn read_optional_entry<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    name: &str,
+) -> Result<Option<String>, String> {
+    let mut entry = match archive.by_name(name) {
+        Ok(entry) => entry,
+        Err(ZipError::FileNotFound) => return Ok(None),
+        Err(err) => return Err(format!("读取 {name} 失败: {err}")),
+    };
+    // 头部记录的大小不可信，按上限截断读取
+    let mut data = Vec::new();
+    entry
+        .take(MAX_ENTRY_BYTES + 1)
+        .read_to_end(&mut data)
+        .map_err(|e| format!("读取 {name} 失败: {e}"))?;
+    if data.len() as u64 > MAX_ENTRY_BYTES {
+        return Err(format!(
+            "{name} 超过 {} MB",
+            MAX_ENTRY_BYTES / (1024 * 1024)
+        ));
+    }
+    String::from_utf8(data)
+        .map(Some)
+        .map_err(|e| format!("读取 {name} 失败: {e}"))
+}
+
+fn xml_error(err: impl std::fmt::Display) -> String {
+    format!("XML 解析失败: {err}")
+}
+
+/// 取属性值（按本地名匹配，忽略命名空间前缀）
+fn attr(element: &BytesStart, local_name: &str) -> Option<String> {
+    element
+        .attributes()
+        .flatten()
+        .find(|attr| attr.key.local_name().as_ref() == local_name.as_bytes())
+        .and_then(|attr| attr.unescape_value().ok())
+        .map(|value| value.into_owned())
+}
+
+/// 提取段落文本，每个非空段落一行
+fn extract_paragraphs(xml: &str, scope: TextScope) -> Result<String, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+    // 当前位于几层可收集文本的元素内
+    let mut text_depth = 0usize;
+
+    loop {
+        match reader.read_event().map_err(xml_error)? {
+            Event::Start(start) => {
+                if scope.contains(start.local_name().as_ref()) {
+                    text_depth += 1;
+                }
+            }
+            Event::End(end) => {
+                let name = end.local_name();
+                if scope.contains(name.as_ref()) {
+                    text_depth = text_depth.saturating_sub(1);
+                }
+                if PARAGRAPH_TAGS.contains(&name.as_ref()) {
+                    flush_line(&mut line, &mut lines);
+                }
+            }
+            Event::Empty(empty) => match empty.local_name().as_ref() {
+                b"tab" => line.push('\t'),
+                b"br" | b"line-break" => line.push('\n'),
+                // ODF 的连续空格 <text:s text:c="3"/>
+                b"s" => {
+                    let count = attr(&empty, "c").and_then(|c| c.parse().ok()).unwrap_or(1);
+                    line.push_str(&" ".repeat(count));
+                }
+                _ => {}
+            },
+            Event::Text(text) if text_depth > 0 => {
+                line.push_str(&text.unescape().map_err(xml_error)?);
+            }
+            Event::CData(data) if text_depth > 0 => {
+                line.push_str(&String::from_utf8_lossy(&data.into_inner()));
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    flush_line(&mut line, &mut lines);
+    Ok(lines.join("\n"))
+}
+
+fn flush_line(line: &mut String, lines: &mut Vec<String>) {
+    let trimmed = line.trim();
+    if !trimmed.is_empty() {
+        lines.push(trimmed.to_string());
+    }
+    line.clear();
+}
+
+fn parse_pptx<R: Read + Seek>(archive: &mut ZipArchive<R>) -> Result<String, String> {
+    let mut slides: Vec<(u32, String)> = archive
+        .file_names()
+        .filter_map(|name| {
+            let number = name
+                .strip_prefix("ppt/slides/slide")?
+                .strip_suffix(".xml")?
+                .parse()
+                .ok()?;
+            Some((number, name.to_string()))
+        })
+        .collect();
+    slides.sort();
+
+    let mut parts = Vec::new();
+    for (_, name) in slides {
+        let xml = read_entry(archive, &name)?;
+        let text = extract_paragraphs(&xml, TextScope::Elements(OOXML_TEXT_TAGS))?;
+        if !text.is_empty() {
+            parts.push(text);
+        }
+    }
+    Ok(parts.join("\n\n"))
+}
+
+fn parse_xlsx<R: Read + Seek>(archive: &mut ZipArchive<R>) -> Result<String, String> {
+    let shared_strings = match read_optional_entry(archive, "xl/sharedStrings.xml")? {
+        Some(xml) => parse_shared_strings(&xml)?,
+        None => Vec::new(),
+    };
+
+    let mut parts = Vec::new();
+    for (name, path) in list_xlsx_sheets(archive)? {
+        let Some(xml) = read_optional_entry(archive, &path)? else {
+            continue;
+        };
+        let rows = parse_sheet_rows(&xml, &shared_strings)?;
+        if !rows.is_empty() {
+            parts.push(format!("## {name}\n{}", rows.join("\n")));
+        }
+    }
+    Ok(parts.join("\n\n"))
+}
+
+/// 工作表（名称，zip 内路径），按工作簿中的顺序
+fn list_xlsx_sheets<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+) -> Result<Vec<(String, String)>, String> {
+    let workbook = read_optional_entry(archive, "xl/workbook.xml")?;
+    let rels = read_optional_entry(archive, "xl/_rels/workbook.xml.rels")?;
+    if let (Some(workbook), Some(rels)) = (workbook, rels) {
+        let targets = collect_elements(&rels, b"Relationship", &["Id", "Target"])?;
+        let sheets: Vec<(String, String)> = collect_elements(&workbook, b"sheet", &["name", "id"])?
+            .into_iter()
+            .filter_map(|sheet| {
+                let [name, rel_id] = <[String; 2]>::try_from(sheet).ok()?;
+                let target = targets.iter().find(|rel| rel[0] == rel_id)?.get(1)?;
+                let path = match target.strip_prefix('/') {
+                    Some(absolute) => absolute.to_string(),
+                    None => format!("xl/{target}"),
+                };
+                Some((name, path))
+            })
+            .collect();
+        if !sheets.is_empty() {
+            return Ok(sheets);
+        }
+    }
+
+    // 缺少工作簿信息时按文件编号排序
+    let mut sheets: Vec<(u32, String)> = archive
+        .file_names()
+        .filter_map(|name| {
+            let number = name
+                .strip_prefix("xl/worksheets/sheet")?
+                .strip_suffix(".xml")?
+                .parse()
+                .ok()?;
+            Some((number, name.to_string()))
+        })
+        .collect();
+    sheets.sort();
+    Ok(sheets
+        .into_iter()
+        .map(|(number, path)| (format!("Sheet{number}"), path))
+        .collect())
+}
+
+/// 收集指定元素的若干属性（全部存在才收集）
+fn collect_elements(xml: &str, element: &[u8], attrs: &[&str]) -> Result<Vec<Vec<String>>, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut found = Vec::new();
+    loop {
+        match reader.read_event().map_err(xml_error)? {
+            Event::Start(start) | Event::Empty(start) if start.local_name().as_ref() == element => {
+                let values: Option<Vec<String>> =
+                    attrs.iter().map(|name| attr(&start, name)).collect();
+                if let Some(values) = values {
+                    found.push(values);
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(found)
+}
+
+/// 共享字符串表：每个 `<si>` 一项，富文本的多个 `<t>` 拼接（忽略注音 `<rPh>`）
+fn parse_shared_strings(xml: &str) -> Result<Vec<String>, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut strings = Vec::new();
+    let mut current = String::new();
+    let mut in_text = false;
+    let mut in_phonetic = false;
+
+    loop {
+        match reader.read_event().map_err(xml_error)? {
+            Event::Start(start) => match start.local_name().as_ref() {
+                b"si" => current.clear(),
+                b"t" => in_text = true,
+                b"rPh" => in_phonetic = true,
+                _ => {}
+            },
+            Event::End(end) => match end.local_name().as_ref() {
+                b"si" => strings.push(std::mem::take(&mut current)),
+                b"t" => in_text = false,
+                b"rPh" => in_phonetic = false,
+                _ => {}
+            },
+            Event::Empty(empty) if empty.local_name().as_ref() == b"si" => {
+                strings.push(String::new());
+            }
+            Event::Text(text) if in_text && !in_phonetic => {
+                current.push_str(&text.unescape().map_err(xml_error)?);
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(strings)
+}
+
+/// 工作表的非空行，单元格以制表符连接
+fn parse_sheet_rows(xml: &str, shared_strings: &[String]) -> Result<Vec<String>, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut rows = Vec::new();
+    let mut cells: Vec<String> = Vec::new();
+    let mut cell_type: Option<String> = None;
+    let mut value = String::new();
+    let mut in_value = false;
+
+    loop {
+        match reader.read_event().map_err(xml_error)? {
+            Event::Start(start) => match start.local_name().as_ref() {
+                b"c" => {
+                    cell_type = attr(&start, "t");
+                    value.clear();
+                }
+                // <v> 为值，<is><t> 为内联字符串
+                b"v" | b"t" => in_value = true,
+                _ => {}
+            },
+            Event::End(end) => match end.local_name().as_ref() {
+                b"v" | b"t" => in_value = false,
+                b"c" => {
+                    let text = resolve_cell(cell_type.as_deref(), &value, shared_strings);
+                    if !text.is_empty() {
+                        cells.push(text);
+                    }
+                }
+                b"row" => {
+                    if !cells.is_empty() {
+                        rows.push(cells.join("\t"));
+                        cells.clear();
+                    }
+                }
+                _ => {}
+            },
+            Event::Text(text) if in_value => {
+                value.push_str(&text.unescape().map_err(xml_error)?);
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(rows)
+}
+
+fn resolve_cell(cell_type: Option<&str>, value: &str, shared_strings: &[String]) -> String {
+    let value = value.trim();
+    match cell_type {
+        Some("s") => value
+            .parse::<usize>()
+            .ok()
+            .and_then(|index| shared_strings.get(index))
+            .map(|text| text.trim().to_string())
+            .unwrap_or_default(),
+        Some("b") => match value {
+            "1" => "TRUE".to_string(),
+            "0" => "FALSE".to_string(),
+            other => other.to_string(),
+        },
+        _ => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_docx_paragraphs() {
+        let xml = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+<w:p><w:pPr><w:tabs><w:tab w:val="left" w:pos="720"/></w:tabs></w:pPr><w:r><w:t>Hello</w:t></w:r><w:r><w:t xml:space="preserve"> world &amp; more</w:t></w:r></w:p>
+<w:p></w:p>
+<w:p><w:r><w:t>A</w:t><w:tab/><w:t>B</w:t><w:instrText>PAGE</w:instrText></w:r></w:p>
+</w:body></w:document>"#;
+        let text = extract_paragraphs(xml, TextScope::Elements(OOXML_TEXT_TAGS)).unwrap();
+        assert_eq!(text, "Hello world & more\nA\tB");
+    }
+
+    #[test]
+    fn test_extract_odf_paragraphs() {
+        let xml = r#"<office:document-content xmlns:office="o" xmlns:text="t"><office:body><office:text>
+<text:h text:outline-level="1">Title</text:h>
+<text:p>One<text:s text:c="2"/><text:span>two</text:span></text:p>
+</office:text></office:body></office:document-content>"#;
+        let text = extract_paragraphs(xml, TextScope::Paragraphs).unwrap();
+        assert_eq!(text, "Title\nOne  two");
+    }
+
+    #[test]
+    fn test_parse_xlsx_rows() {
+        let shared = parse_shared_strings(
+            r#"<sst><si><t>Name</t></si><si><r><t>Al</t></r><r><t>ice</t></r><rPh><t>ア</t></rPh></si><si/></sst>"#,
+        )
+        .unwrap();
+        assert_eq!(shared, vec!["Name", "Alice", ""]);
+
+        let sheet = r#"<worksheet><sheetData>
+<row r="1"><c r="A1" t="s"><v>0</v></c><c r="B1" t="inlineStr"><is><t>Score</t></is></c></row>
+<row r="2"><c r="A2" t="s"><v>1</v></c><c r="B2"><f>SUM(1,2)</f><v>3</v></c><c r="C2" t="b"><v>1</v></c></row>
+<row r="3"><c r="A3" t="s"><v>2</v></c></row>
+</sheetData></worksheet>"#;
+        let rows = parse_sheet_rows(sheet, &shared).unwrap();
+        assert_eq!(rows, vec!["Name\tScore", "Alice\t3\tTRUE"]);
+    }
+
+    #[test]
+    fn test_collect_elements() {
+        let xml = r#"<workbook xmlns:r="rel"><sheets><sheet name="Data" sheetId="1" r:id="rId2"/><sheet name="NoId" sheetId="2"/></sheets></workbook>"#;
+        let sheets = collect_elements(xml, b"sheet", &["name", "id"]).unwrap();
+        assert_eq!(sheets, vec![vec!["Data".to_string(), "rId2".to_string()]]);
+    }
+}
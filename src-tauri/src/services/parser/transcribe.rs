@@ -0,0 +1,240 @@
+//! Audio transcription via a local whisper.cpp build
+//!
+//! Looks for the whisper.cpp CLI and a ggml model under `third_party_model/whisper/`
+//! (the CLI may also be on PATH). When either is missing, `transcribe_audio_file`
+//! returns `Ok(None)` and the AI pipeline transcribes through the processing model instead.
+
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use uuid::Uuid;
+
+use super::{third_party_model_dir, ProgressCallback};
+
+/// CLI names across whisper.cpp releases (`main` before 1.7)
+const WHISPER_BINARIES: &[&str] = &["whisper-cli", "whisper-cpp", "main"];
+/// Preferred models, best first
+const WHISPER_MODELS: &[&str] = &[
+    "ggml-large-v3-turbo.bin",
+    "ggml-medium.bin",
+    "ggml-small.bin",
+    "ggml-base.bin",
+    "ggml-tiny.bin",
+];
+/// Formats whisper.cpp decodes itself; anything else is converted with ffmpeg first
+const NATIVE_FORMATS: &[&str] = &["wav", "mp3", "flac", "ogg"];
+const PROGRESS_MARKER: &str = "progress =";
+
+/// Local whisper.cpp installation
+#[derive(Debug, Clone)]
+struct LocalWhisper {
+    binary: PathBuf,
+    model: PathBuf,
+}
+
+/// Find the whisper.cpp CLI and the best available model
+fn find_local_whisper() -> Option<LocalWhisper> {
+    let dir = third_party_model_dir().join("whisper");
+    let model = WHISPER_MODELS
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.is_file())?;
+    let binary = WHISPER_BINARIES.iter().find_map(|name| {
+        let bundled = dir.join(executable_name(name));
+        if bundled.is_file() {
+            Some(bundled)
+        } else if *name != "main" {
+            // `main` is too generic to trust from PATH
+            find_in_path(name)
+        } else {
+            None
+        }
+    })?;
+    Some(LocalWhisper { binary, model })
+}
+
+/// Path of the whisper.cpp CLI when local transcription is available
+pub fn local_whisper_binary() -> Option<PathBuf> {
+    find_local_whisper().map(|whisper| whisper.binary)
+}
+
+/// Transcribe an audio file with local whisper.cpp
+///
+/// Returns `Ok(None)` when local transcription is unavailable so the caller can fall back
+/// to the model provider. Progress is reported as `("transcribing", percent, None)`.
+pub fn transcribe_audio_file(
+    path: &str,
+    progress_callback: Option<&ProgressCallback>,
+) -> Result<Option<String>, String> {
+    let Some(whisper) = find_local_whisper() else {
+        tracing::debug!(path, "Local whisper unavailable, deferring transcription");
+        return Ok(None);
+    };
+
+    let work_dir = std::env::temp_dir().join(format!("neuralvault-whisper-{}", Uuid::new_v4()));
+    fs::create_dir_all(&work_dir).map_err(|e| format!("创建临时目录失败: {e}"))?;
+    let result = run_whisper(&whisper, Path::new(path), &work_dir, progress_callback);
+    let _ = fs::remove_dir_all(&work_dir);
+    result
+}
+
+fn run_whisper(
+    whisper: &LocalWhisper,
+    input: &Path,
+    work_dir: &Path,
+    progress_callback: Option<&ProgressCallback>,
+) -> Result<Option<String>, String> {
+    let report = |percentage: u8| {
+        if let Some(cb) = progress_callback {
+            cb("transcribing", Some(percentage), None);
+        }
+    };
+    report(0);
+
+    let extension = input
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_lowercase)
+        .unwrap_or_default();
+    let audio = if NATIVE_FORMATS.contains(&extension.as_str()) {
+        input.to_path_buf()
+    } else {
+        match convert_to_wav(input, work_dir)? {
+            Some(wav) => wav,
+            None => {
+                tracing::debug!(
+                    path = %input.display(),
+                    "ffmpeg unavailable, deferring transcription"
+                );
+                return Ok(None);
+            }
+        }
+    };
+
+    let output_base = work_dir.join("transcript");
+    let mut child = Command::new(&whisper.binary)
+        .arg("-m")
+        .arg(&whisper.model)
+        .arg("-f")
+        .arg(&audio)
+        .args(["-l", "auto", "-pp", "-otxt", "-of"])
+        .arg(&output_base)
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("启动 whisper 失败: {e}"))?;
+
+    // whisper.cpp prints "... progress = 35%" to stderr; keep the tail for error messages
+    let mut stderr_tail = Vec::new();
+    if let Some(stderr) = child.stderr.take() {
+        for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+            match parse_progress(&line) {
+                Some(percentage) => report(percentage.min(99)),
+                None => {
+                    if stderr_tail.len() == 20 {
+                        stderr_tail.remove(0);
+                    }
+                    stderr_tail.push(line);
+                }
+            }
+        }
+    }
+    let status = child.wait().map_err(|e| format!("whisper 运行失败: {e}"))?;
+    if !status.success() {
+        return Err(format!("whisper 转写失败: {}", stderr_tail.join("\n")));
+    }
+
+    let transcript = fs::read_to_string(output_base.with_extension("txt"))
+        .map_err(|e| format!("读取转写结果失败: {e}"))?;
+    let transcript = normalize_transcript(&transcript);
+    if transcript.is_empty() {
+        return Err("音频中未识别到语音".to_string());
+    }
+    report(100);
+    Ok(Some(transcript))
+}
+
+/// Convert to 16 kHz mono WAV; `Ok(None)` when ffmpeg is not installed
+fn convert_to_wav(input: &Path, work_dir: &Path) -> Result<Option<PathBuf>, String> {
+    let Some(ffmpeg) = find_in_path("ffmpeg") else {
+        return Ok(None);
+    };
+    let output = work_dir.join("input.wav");
+    let result = Command::new(ffmpeg)
+        .args(["-nostdin", "-loglevel", "error", "-y", "-i"])
+        .arg(input)
+        .args(["-ar", "16000", "-ac", "1", "-c:a", "pcm_s16le"])
+        .arg(&output)
+        .output()
+        .map_err(|e| format!("启动 ffmpeg 失败: {e}"))?;
+    if !result.status.success() {
+        return Err(format!(
+            "音频转换失败: {}",
+            String::from_utf8_lossy(&result.stderr).trim()
+        ));
+    }
+    Ok(Some(output))
+}
+
+fn parse_progress(line: &str) -> Option<u8> {
+    let (_, rest) = line.split_once(PROGRESS_MARKER)?;
+    rest.trim().trim_end_matches('%').trim().parse().ok()
+}
+
+/// Trim each segment line and drop blanks
+fn normalize_transcript(raw: &str) -> String {
+    raw.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn executable_name(name: &str) -> String {
+    if cfg!(windows) {
+        format!("{name}.exe")
+    } else {
+        name.to_string()
+    }
+}
+
+fn find_in_path(name: &str) -> Option<PathBuf> {
+    let file_name = executable_name(name);
+    let mut dirs: Vec<PathBuf> = std::env::var_os("PATH")
+        .map(|paths| std::env::split_paths(&paths).collect())
+        .unwrap_or_default();
+    // Apps launched from Finder don't inherit the shell PATH
+    if cfg!(target_os = "macos") {
+        dirs.extend(["/opt/homebrew/bin", "/usr/local/bin"].map(PathBuf::from));
+    }
+    dirs.into_iter()
+        .map(|dir| dir.join(&file_name))
+        .find(|path| path.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_whisper_progress_lines() {
+        assert_eq!(
+            parse_progress("whisper_print_progress_callback: progress =  35%"),
+            Some(35)
+        );
+        assert_eq!(
+            parse_progress("whisper_full_with_state: auto-detected language: en"),
+            None
+        );
+    }
+
+    #[test]
+    fn normalizes_transcript_lines() {
+        assert_eq!(
+            normalize_transcript(" Hello there.\n\n  Second line \n"),
+            "Hello there.\nSecond line"
+        );
+    }
+}
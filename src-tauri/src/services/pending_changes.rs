@@ -0,0 +1,277 @@
+//! 对话中模型提出的待确认修改
+//!
+//! 模型通过工具提出的重命名主题、移动资源、设置截止日期、新建任务只写入 `pending_changes`，
+//! 用户批准后才执行。提出时记下当前值供审核对比；批准时按当前数据重新校验。
+
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+use crate::db::{
+    apply_topic_operations, get_node_by_id, insert_node_revision_log, insert_pending_change,
+    list_source_nodes, move_resource_to_topic, set_pending_change_node, update_task_due_date,
+    DbPool, EdgeRelationType, NewNodeRevisionLog, NewPendingChange, NodeBuilder, NodeRecord,
+    NodeType, PendingChangeKind, PendingChangeRecord, TopicOperation,
+};
+use crate::error::{AppError, AppResult};
+use crate::i18n::MessageCode;
+use crate::utils::validate_title;
+
+/// 修订日志中标记由已批准的 AI 修改写入
+const PENDING_CHANGE_REASON: &str = "ai_pending_change";
+
+/// 模型提出的一项修改
+#[derive(Debug, Clone)]
+pub enum ProposedChange {
+    RenameTopic {
+        topic_id: i64,
+        title: String,
+    },
+    MoveResource {
+        resource_id: i64,
+        topic_id: i64,
+    },
+    /// due_date 为空表示清除截止日期
+    SetDueDate {
+        task_id: i64,
+        due_date: Option<String>,
+    },
+    CreateTask(ProposedTask),
+}
+
+/// 待新建的任务，以 JSON 存在 `new_value`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposedTask {
+    pub title: String,
+    pub due_date: Option<String>,
+    pub note: Option<String>,
+}
+
+impl ProposedTask {
+    fn normalize(self) -> AppResult<Self> {
+        Ok(Self {
+            title: validate_title(&self.title)?.to_string(),
+            due_date: normalize_due_date(self.due_date.as_deref())?,
+            note: self
+                .note
+                .map(|note| note.trim().to_string())
+                .filter(|note| !note.is_empty()),
+        })
+    }
+}
+
+/// 未删除且类型符合的节点
+async fn fetch_live_node(
+    db: &DbPool,
+    node_id: i64,
+    node_type: NodeType,
+    entity: &'static str,
+) -> AppResult<NodeRecord> {
+    match get_node_by_id(db, node_id).await {
+        Ok(node) if node.node_type == node_type && !node.is_deleted => Ok(node),
+        Ok(_) | Err(sqlx::Error::RowNotFound) => Err(AppError::NotFound {
+            entity,
+            id: node_id,
+        }),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// 修改针对的节点；只有未批准的 create_task 没有节点
+fn target_node_id(change: &PendingChangeRecord) -> AppResult<i64> {
+    change
+        .node_id
+        .ok_or(AppError::coded(MessageCode::InvalidNodeId))
+}
+
+fn invalid_payload(detail: impl ToString) -> AppError {
+    AppError::coded_with(MessageCode::InvalidPendingChangePayload, "detail", detail)
+}
+
+/// 截止日期与任务编辑器写入的格式一致：`YYYY-MM-DD 00:00:00`（批准时会再次规范化已存的值）
+fn normalize_due_date(raw: Option<&str>) -> AppResult<Option<String>> {
+    match raw.map(str::trim) {
+        Some(raw) if !raw.is_empty() => NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+            .or_else(|_| {
+                NaiveDateTime::parse_from_str(raw, "%Y-%m-%d %H:%M:%S").map(|time| time.date())
+            })
+            .map(|date| Some(date.format("%Y-%m-%d 00:00:00").to_string()))
+            .map_err(|_| AppError::coded_with(MessageCode::InvalidDate, "value", raw)),
+        _ => Ok(None),
+    }
+}
+
+/// 校验并记录一项待确认修改，返回 change_id
+pub async fn propose_change(
+    db: &DbPool,
+    change: ProposedChange,
+    reason: Option<&str>,
+    session_id: Option<i64>,
+) -> AppResult<i64> {
+    let (kind, node_id, old_value, new_value) = match change {
+        ProposedChange::RenameTopic { topic_id, title } => {
+            let topic = fetch_live_node(db, topic_id, NodeType::Topic, "topic").await?;
+            let title = validate_title(&title)?.to_string();
+            (
+                PendingChangeKind::RenameTopic,
+                Some(topic_id),
+                Some(topic.title),
+                Some(title),
+            )
+        }
+        ProposedChange::MoveResource {
+            resource_id,
+            topic_id,
+        } => {
+            fetch_live_node(db, resource_id, NodeType::Resource, "resource").await?;
+            fetch_live_node(db, topic_id, NodeType::Topic, "topic").await?;
+            let parents = list_source_nodes(db, resource_id, EdgeRelationType::Contains)
+                .await?
+                .into_iter()
+                .filter(|node| node.node_type == NodeType::Topic)
+                .map(|node| node.node_id.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            (
+                PendingChangeKind::MoveResource,
+                Some(resource_id),
+                Some(parents).filter(|value| !value.is_empty()),
+                Some(topic_id.to_string()),
+            )
+        }
+        ProposedChange::SetDueDate { task_id, due_date } => {
+            let task = fetch_live_node(db, task_id, NodeType::Task, "task").await?;
+            let due_date = normalize_due_date(due_date.as_deref())?;
+            (
+                PendingChangeKind::SetDueDate,
+                Some(task_id),
+                task.due_date,
+                due_date,
+            )
+        }
+        ProposedChange::CreateTask(task) => {
+            let task = task.normalize()?;
+            let new_value = serde_json::to_string(&task).map_err(invalid_payload)?;
+            (PendingChangeKind::CreateTask, None, None, Some(new_value))
+        }
+    };
+
+    let change_id = insert_pending_change(
+        db,
+        NewPendingChange {
+            kind,
+            node_id,
+            old_value: old_value.as_deref(),
+            new_value: new_value.as_deref(),
+            reason: reason.map(str::trim).filter(|value| !value.is_empty()),
+            session_id,
+        },
+    )
+    .await?;
+    tracing::debug!(change_id, ?kind, ?node_id, "Pending change proposed");
+    Ok(change_id)
+}
+
+/// 执行已批准的修改
+pub async fn apply_pending_change(db: &DbPool, change: &PendingChangeRecord) -> AppResult<()> {
+    let new_value = change.new_value.as_deref();
+    match change.kind {
+        PendingChangeKind::RenameTopic => {
+            let topic_id = target_node_id(change)?;
+            fetch_live_node(db, topic_id, NodeType::Topic, "topic").await?;
+            let title = validate_title(new_value.unwrap_or_default())?;
+            apply_topic_operations(
+                db,
+                &[TopicOperation::Rename {
+                    topic_id,
+                    new_title: title.to_string(),
+                }],
+            )
+            .await
+        }
+        PendingChangeKind::MoveResource => {
+            let resource_id = target_node_id(change)?;
+            fetch_live_node(db, resource_id, NodeType::Resource, "resource").await?;
+            let topic_id = new_value
+                .and_then(|value| value.parse::<i64>().ok())
+                .ok_or(AppError::coded(MessageCode::InvalidNodeId))?;
+            move_resource_to_topic(db, resource_id, topic_id).await
+        }
+        PendingChangeKind::SetDueDate => {
+            let task_id = target_node_id(change)?;
+            let task = fetch_live_node(db, task_id, NodeType::Task, "task").await?;
+            let due_date = normalize_due_date(new_value)?;
+            update_task_due_date(db, task_id, due_date.as_deref()).await?;
+            insert_node_revision_log(
+                db,
+                NewNodeRevisionLog {
+                    node_id: task_id,
+                    field_name: "due_date",
+                    old_value: task.due_date.as_deref(),
+                    new_value: due_date.as_deref(),
+                    reason: Some(PENDING_CHANGE_REASON),
+                    provider: None,
+                    model: None,
+                    confidence_score: None,
+                },
+            )
+            .await?;
+            Ok(())
+        }
+        PendingChangeKind::CreateTask => {
+            let task: ProposedTask =
+                serde_json::from_str(new_value.unwrap_or_default()).map_err(invalid_payload)?;
+            let task = task.normalize()?;
+            let builder = NodeBuilder::task()
+                .title(task.title)
+                .due_date(task.due_date)
+                .user_note(task.note);
+            // 新任务与它在修改记录中的关联一起提交
+            let mut tx = db.begin().await?;
+            let node_id = builder.insert_in(tx.as_mut()).await?;
+            set_pending_change_node(tx.as_mut(), change.change_id, node_id).await?;
+            tx.commit().await?;
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_due_date() {
+        assert_eq!(
+            normalize_due_date(Some(" 2025-03-07 ")).unwrap(),
+            Some("2025-03-07 00:00:00".to_string())
+        );
+        assert_eq!(
+            normalize_due_date(Some("2025-03-07 00:00:00")).unwrap(),
+            Some("2025-03-07 00:00:00".to_string())
+        );
+        assert_eq!(normalize_due_date(Some("")).unwrap(), None);
+        assert_eq!(normalize_due_date(None).unwrap(), None);
+        assert!(normalize_due_date(Some("next friday")).is_err());
+    }
+
+    #[test]
+    fn test_proposed_task_normalize() {
+        let task = ProposedTask {
+            title: "  Call Bob ".to_string(),
+            due_date: Some("2025-03-07".to_string()),
+            note: Some("  ".to_string()),
+        }
+        .normalize()
+        .unwrap();
+        assert_eq!(task.title, "Call Bob");
+        assert_eq!(task.due_date.as_deref(), Some("2025-03-07 00:00:00"));
+        assert_eq!(task.note, None);
+
+        let bad_date = ProposedTask {
+            title: "x".to_string(),
+            due_date: Some("tomorrow".to_string()),
+            note: None,
+        };
+        assert!(bad_date.normalize().is_err());
+    }
+}
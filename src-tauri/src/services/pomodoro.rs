@@ -0,0 +1,436 @@
+//! 番茄钟
+//!
+//! 与任务绑定的工作 / 休息循环。倒计时在 Rust 侧维护，每秒发送 `pomodoro-tick`，
+//! 阶段切换时发送 `pomodoro-phase`（托盘与 HUD 都监听这两个事件）；
+//! 每完成一个工作阶段自动写入 time_entries。
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::async_runtime::JoinHandle;
+use tauri::AppHandle;
+
+use crate::db::{
+    insert_time_entry, DbPool, NewNotification, NewTimeEntry, NotificationKind, TimeEntrySource,
+};
+use crate::error::{AppError, AppResult};
+use crate::i18n::MessageCode;
+use crate::services::{emit_event, push_notification, PomodoroPhasePayload};
+
+const TICK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 番茄钟配置（单位：分钟）
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PomodoroConfig {
+    pub work_minutes: u32,
+    pub short_break_minutes: u32,
+    pub long_break_minutes: u32,
+    /// 每完成几个番茄进入一次长休息
+    pub cycles_before_long_break: u32,
+}
+
+impl Default for PomodoroConfig {
+    fn default() -> Self {
+        Self {
+            work_minutes: 25,
+            short_break_minutes: 5,
+            long_break_minutes: 15,
+            cycles_before_long_break: 4,
+        }
+    }
+}
+
+impl PomodoroConfig {
+    pub fn validate(&self) -> AppResult<()> {
+        let in_range = |v: u32| (1..=180).contains(&v);
+        if !in_range(self.work_minutes)
+            || !in_range(self.short_break_minutes)
+            || !in_range(self.long_break_minutes)
+        {
+            return Err(AppError::coded(MessageCode::PomodoroDurationOutOfRange));
+        }
+        if self.cycles_before_long_break == 0 {
+            return Err(AppError::coded(MessageCode::PomodoroCyclesOutOfRange));
+        }
+        Ok(())
+    }
+
+    fn phase_seconds(&self, phase: PomodoroPhase) -> u64 {
+        let minutes = match phase {
+            PomodoroPhase::Work => self.work_minutes,
+            PomodoroPhase::ShortBreak => self.short_break_minutes,
+            PomodoroPhase::LongBreak => self.long_break_minutes,
+        };
+        u64::from(minutes) * 60
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PomodoroPhase {
+    Work,
+    ShortBreak,
+    LongBreak,
+}
+
+/// 当前番茄钟状态（事件 payload 与查询返回共用）
+#[derive(Debug, Clone, Serialize)]
+pub struct PomodoroState {
+    pub task_id: Option<i64>,
+    pub phase: PomodoroPhase,
+    pub remaining_seconds: u64,
+    pub completed_pomodoros: u32,
+    pub is_paused: bool,
+    pub config: PomodoroConfig,
+}
+
+/// 一个阶段结束（倒计时归零或被跳过）
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PhaseEnd {
+    phase: PomodoroPhase,
+    next_phase: PomodoroPhase,
+    started_at: DateTime<Utc>,
+    /// 实际计时秒数（不含暂停）
+    elapsed_seconds: u64,
+    /// 是否自然结束（跳过的阶段为 false）
+    finished: bool,
+}
+
+/// 纯状态机，不涉及 IO，便于测试
+#[derive(Debug)]
+struct PomodoroTimer {
+    config: PomodoroConfig,
+    phase: PomodoroPhase,
+    remaining: u64,
+    elapsed: u64,
+    completed: u32,
+    paused: bool,
+    phase_started_at: DateTime<Utc>,
+}
+
+impl PomodoroTimer {
+    fn new(config: PomodoroConfig, now: DateTime<Utc>) -> Self {
+        Self {
+            config,
+            phase: PomodoroPhase::Work,
+            remaining: config.phase_seconds(PomodoroPhase::Work),
+            elapsed: 0,
+            completed: 0,
+            paused: false,
+            phase_started_at: now,
+        }
+    }
+
+    fn tick(&mut self, seconds: u64, now: DateTime<Utc>) -> Option<PhaseEnd> {
+        if self.paused {
+            return None;
+        }
+        let step = seconds.min(self.remaining);
+        self.remaining -= step;
+        self.elapsed += step;
+        if self.remaining == 0 {
+            Some(self.advance(true, now))
+        } else {
+            None
+        }
+    }
+
+    fn skip(&mut self, now: DateTime<Utc>) -> PhaseEnd {
+        self.advance(false, now)
+    }
+
+    fn advance(&mut self, finished: bool, now: DateTime<Utc>) -> PhaseEnd {
+        let phase = self.phase;
+        if phase == PomodoroPhase::Work && finished {
+            self.completed += 1;
+        }
+
+        let next_phase = match phase {
+            PomodoroPhase::Work
+                if self.completed > 0
+                    && finished
+                    && self.completed % self.config.cycles_before_long_break == 0 =>
+            {
+                PomodoroPhase::LongBreak
+            }
+            PomodoroPhase::Work => PomodoroPhase::ShortBreak,
+            PomodoroPhase::ShortBreak | PomodoroPhase::LongBreak => PomodoroPhase::Work,
+        };
+
+        let end = PhaseEnd {
+            phase,
+            next_phase,
+            started_at: self.phase_started_at,
+            elapsed_seconds: self.elapsed,
+            finished,
+        };
+
+        self.phase = next_phase;
+        self.remaining = self.config.phase_seconds(next_phase);
+        self.elapsed = 0;
+        self.phase_started_at = now;
+        end
+    }
+
+    fn state(&self, task_id: Option<i64>) -> PomodoroState {
+        PomodoroState {
+            task_id,
+            phase: self.phase,
+            remaining_seconds: self.remaining,
+            completed_pomodoros: self.completed,
+            is_paused: self.paused,
+            config: self.config,
+        }
+    }
+}
+
+struct ActivePomodoro {
+    run_id: u64,
+    task_id: Option<i64>,
+    timer: PomodoroTimer,
+    ticker: Option<JoinHandle<()>>,
+}
+
+pub struct PomodoroService {
+    db: DbPool,
+    app: AppHandle,
+    active: Mutex<Option<ActivePomodoro>>,
+    next_run_id: AtomicU64,
+}
+
+impl PomodoroService {
+    pub fn new(db: DbPool, app: AppHandle) -> Self {
+        Self {
+            db,
+            app,
+            active: Mutex::new(None),
+            next_run_id: AtomicU64::new(1),
+        }
+    }
+
+    pub fn current(&self) -> Option<PomodoroState> {
+        let guard = self.active.lock().ok()?;
+        guard
+            .as_ref()
+            .map(|active| active.timer.state(active.task_id))
+    }
+
+    /// 开始番茄钟；已有进行中的番茄钟时会被替换
+    pub fn start(
+        self: &Arc<Self>,
+        task_id: Option<i64>,
+        config: PomodoroConfig,
+    ) -> AppResult<PomodoroState> {
+        config.validate()?;
+        let run_id = self.next_run_id.fetch_add(1, Ordering::Relaxed);
+        let timer = PomodoroTimer::new(config, Utc::now());
+        let state = timer.state(task_id);
+
+        {
+            let mut guard = self.active.lock().map_err(|e| e.to_string())?;
+            if let Some(previous) = guard.take() {
+                if let Some(ticker) = previous.ticker {
+                    ticker.abort();
+                }
+            }
+            *guard = Some(ActivePomodoro {
+                run_id,
+                task_id,
+                timer,
+                ticker: None,
+            });
+        }
+
+        let ticker = self.spawn_ticker(run_id);
+        if let Ok(mut guard) = self.active.lock() {
+            match guard.as_mut() {
+                Some(active) if active.run_id == run_id => active.ticker = Some(ticker),
+                _ => ticker.abort(),
+            }
+        }
+
+        tracing::info!(?task_id, ?config, "Pomodoro started");
+        Ok(state)
+    }
+
+    pub fn set_paused(&self, paused: bool) -> Result<PomodoroState, String> {
+        let mut guard = self.active.lock().map_err(|e| e.to_string())?;
+        let active = guard
+            .as_mut()
+            .ok_or_else(|| "没有进行中的番茄钟".to_string())?;
+        active.timer.paused = paused;
+        let state = active.timer.state(active.task_id);
+        drop(guard);
+
+        emit_event(&self.app, &state);
+        Ok(state)
+    }
+
+    /// 跳过当前阶段（跳过的工作阶段不计入时间记录）
+    pub async fn skip(&self) -> Result<PomodoroState, String> {
+        let (task_id, end, state) = {
+            let mut guard = self.active.lock().map_err(|e| e.to_string())?;
+            let active = guard
+                .as_mut()
+                .ok_or_else(|| "没有进行中的番茄钟".to_string())?;
+            let end = active.timer.skip(Utc::now());
+            (active.task_id, end, active.timer.state(active.task_id))
+        };
+        self.handle_phase_end(task_id, &end, state.completed_pomodoros)
+            .await;
+        emit_event(&self.app, &state);
+        Ok(state)
+    }
+
+    pub fn stop(&self) -> Result<Option<PomodoroState>, String> {
+        let mut guard = self.active.lock().map_err(|e| e.to_string())?;
+        let Some(active) = guard.take() else {
+            return Ok(None);
+        };
+        if let Some(ticker) = active.ticker {
+            ticker.abort();
+        }
+        tracing::info!(task_id = ?active.task_id, "Pomodoro stopped");
+        Ok(Some(active.timer.state(active.task_id)))
+    }
+
+    fn spawn_ticker(self: &Arc<Self>, run_id: u64) -> JoinHandle<()> {
+        let service = Arc::clone(self);
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(TICK_INTERVAL);
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                let (task_id, end, state) = {
+                    let Ok(mut guard) = service.active.lock() else {
+                        return;
+                    };
+                    let Some(active) = guard.as_mut().filter(|a| a.run_id == run_id) else {
+                        return;
+                    };
+                    if active.timer.paused {
+                        continue;
+                    }
+                    let end = active.timer.tick(TICK_INTERVAL.as_secs(), Utc::now());
+                    (active.task_id, end, active.timer.state(active.task_id))
+                };
+
+                if let Some(end) = end {
+                    service
+                        .handle_phase_end(task_id, &end, state.completed_pomodoros)
+                        .await;
+                }
+                emit_event(&service.app, &state);
+            }
+        })
+    }
+
+    async fn handle_phase_end(&self, task_id: Option<i64>, end: &PhaseEnd, completed: u32) {
+        if end.phase == PomodoroPhase::Work && end.finished {
+            let ended_at = Utc::now();
+            if let Err(err) = insert_time_entry(
+                &self.db,
+                NewTimeEntry {
+                    task_id,
+                    source: TimeEntrySource::Pomodoro,
+                    started_at: &end.started_at.to_rfc3339(),
+                    ended_at: &ended_at.to_rfc3339(),
+                    duration_seconds: end.elapsed_seconds as i64,
+                },
+            )
+            .await
+            {
+                tracing::error!(?task_id, error = %err, "Failed to log pomodoro time entry");
+            }
+        }
+
+        emit_event(
+            &self.app,
+            &PomodoroPhasePayload {
+                task_id,
+                finished_phase: end.phase,
+                next_phase: end.next_phase,
+                skipped: !end.finished,
+                completed_pomodoros: completed,
+            },
+        );
+
+        if end.finished {
+            let title = match end.next_phase {
+                PomodoroPhase::Work => "休息结束，开始下一个番茄",
+                PomodoroPhase::ShortBreak => "番茄完成，短休息一下",
+                PomodoroPhase::LongBreak => "番茄完成，进入长休息",
+            };
+            if let Err(err) = push_notification(
+                &self.db,
+                &self.app,
+                NewNotification {
+                    kind: NotificationKind::System,
+                    title,
+                    body: None,
+                    node_id: task_id,
+                    action: Some("open_pomodoro"),
+                },
+            )
+            .await
+            {
+                tracing::warn!(error = %err, "Failed to push pomodoro notification");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> PomodoroConfig {
+        PomodoroConfig {
+            work_minutes: 1,
+            short_break_minutes: 1,
+            long_break_minutes: 2,
+            cycles_before_long_break: 2,
+        }
+    }
+
+    #[test]
+    fn test_timer_cycles_into_long_break() {
+        let now = Utc::now();
+        let mut timer = PomodoroTimer::new(config(), now);
+
+        let end = timer.tick(60, now).expect("work finished");
+        assert_eq!(end.phase, PomodoroPhase::Work);
+        assert_eq!(end.next_phase, PomodoroPhase::ShortBreak);
+        assert_eq!(end.elapsed_seconds, 60);
+        assert!(end.finished);
+
+        assert_eq!(timer.tick(60, now).unwrap().next_phase, PomodoroPhase::Work);
+        assert_eq!(
+            timer.tick(60, now).unwrap().next_phase,
+            PomodoroPhase::LongBreak
+        );
+        assert_eq!(timer.completed, 2);
+        assert_eq!(timer.remaining, 120);
+    }
+
+    #[test]
+    fn test_timer_pause_and_skip() {
+        let now = Utc::now();
+        let mut timer = PomodoroTimer::new(config(), now);
+
+        timer.paused = true;
+        assert!(timer.tick(60, now).is_none());
+        assert_eq!(timer.remaining, 60);
+
+        timer.paused = false;
+        timer.tick(10, now);
+        let end = timer.skip(now);
+        assert!(!end.finished);
+        assert_eq!(end.elapsed_seconds, 10);
+        assert_eq!(timer.completed, 0);
+        assert_eq!(timer.phase, PomodoroPhase::ShortBreak);
+    }
+}
@@ -0,0 +1,427 @@
+//! 重复任务：类 RRULE 规则的解析与下一次日期计算
+//!
+//! 支持的字段：FREQ（DAILY / WEEKLY / MONTHLY / YEARLY）、INTERVAL、
+//! BYDAY（仅 WEEKLY，如 MO,WE）、BYMONTHDAY（仅 MONTHLY）、UNTIL（YYYY-MM-DD 或 YYYYMMDD）。
+//! 与日历导入（`ical.rs`）宽松的 RRULE 不同，规则由用户填写，不支持的字段直接报错。
+
+use chrono::{Datelike, Duration, Local, Months, NaiveDate, Weekday};
+
+use super::Frequency;
+use crate::db::{
+    get_node_by_id, list_open_recurring_tasks, list_tasks_by_date, mark_task_done,
+    materialize_next_task_occurrence, DbPool, NodeRecord,
+};
+
+const WEEKDAY_CODES: [(&str, Weekday); 7] = [
+    ("MO", Weekday::Mon),
+    ("TU", Weekday::Tue),
+    ("WE", Weekday::Wed),
+    ("TH", Weekday::Thu),
+    ("FR", Weekday::Fri),
+    ("SA", Weekday::Sat),
+    ("SU", Weekday::Sun),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrenceRule {
+    pub frequency: Frequency,
+    pub interval: u32,
+    /// WEEKLY：一周中的哪些天（按周一到周日排序）；为空时沿用起始日的星期
+    pub by_day: Vec<Weekday>,
+    /// MONTHLY：每月第几天，月份天数不足时取月末；为空时沿用起始日
+    pub by_month_day: Option<u32>,
+    pub until: Option<NaiveDate>,
+}
+
+impl RecurrenceRule {
+    /// 解析规则字符串（键名大小写不敏感，可带 `RRULE:` 前缀）
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let body = raw.trim();
+        let body = body
+            .strip_prefix("RRULE:")
+            .or_else(|| body.strip_prefix("rrule:"))
+            .unwrap_or(body);
+
+        let mut frequency = None;
+        let mut interval = 1;
+        let mut by_day = Vec::new();
+        let mut by_month_day = None;
+        let mut until = None;
+
+        for part in body
+            .split(';')
+            .map(str::trim)
+            .filter(|part| !part.is_empty())
+        {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("缺少 '=': {part}"))?;
+            let value = value.trim();
+            match key.trim().to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.to_ascii_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        _ => return Err(format!("不支持的 FREQ: {value}")),
+                    })
+                }
+                "INTERVAL" => {
+                    interval = value
+                        .parse::<u32>()
+                        .ok()
+                        .filter(|n| (1..=999).contains(n))
+                        .ok_or_else(|| format!("无效的 INTERVAL: {value}"))?;
+                }
+                "BYDAY" => {
+                    for code in value.split(',').map(str::trim) {
+                        let day = WEEKDAY_CODES
+                            .iter()
+                            .find(|(name, _)| name.eq_ignore_ascii_case(code))
+                            .map(|(_, day)| *day)
+                            .ok_or_else(|| format!("无效的 BYDAY: {code}"))?;
+                        if !by_day.contains(&day) {
+                            by_day.push(day);
+                        }
+                    }
+                }
+                "BYMONTHDAY" => {
+                    by_month_day = Some(
+                        value
+                            .parse::<u32>()
+                            .ok()
+                            .filter(|n| (1..=31).contains(n))
+                            .ok_or_else(|| format!("无效的 BYMONTHDAY: {value}"))?,
+                    );
+                }
+                "UNTIL" => {
+                    let date = value.get(..10).unwrap_or(value);
+                    until = Some(
+                        NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                            .or_else(|_| {
+                                NaiveDate::parse_from_str(value.get(..8).unwrap_or(value), "%Y%m%d")
+                            })
+                            .map_err(|_| format!("无效的 UNTIL: {value}"))?,
+                    );
+                }
+                other => return Err(format!("不支持的字段: {other}")),
+            }
+        }
+
+        let frequency = frequency.ok_or_else(|| "缺少 FREQ".to_string())?;
+        if !by_day.is_empty() && frequency != Frequency::Weekly {
+            return Err("BYDAY 仅用于 FREQ=WEEKLY".to_string());
+        }
+        if by_month_day.is_some() && frequency != Frequency::Monthly {
+            return Err("BYMONTHDAY 仅用于 FREQ=MONTHLY".to_string());
+        }
+        by_day.sort_by_key(|day: &Weekday| day.num_days_from_monday());
+
+        Ok(Self {
+            frequency,
+            interval,
+            by_day,
+            by_month_day,
+            until,
+        })
+    }
+
+    /// 以 anchor 为起点补全规则：MONTHLY 固定到起始日，避免遇到小月后逐月前移
+    pub fn anchored(mut self, anchor: NaiveDate) -> Self {
+        if self.frequency == Frequency::Monthly && self.by_month_day.is_none() {
+            self.by_month_day = Some(anchor.day());
+        }
+        self
+    }
+
+    /// 规范化的规则字符串（写入数据库）
+    pub fn to_rule_string(&self) -> String {
+        self.format(|until| until.format("%Y-%m-%d").to_string())
+    }
+
+    /// 导出到 .ics 的 RRULE 值：UNTIL 使用 RFC 5545 格式，带时间的任务取当天结束
+    pub fn to_ics_rrule(&self, all_day: bool) -> String {
+        self.format(|until| {
+            if all_day {
+                until.format("%Y%m%d").to_string()
+            } else {
+                until.format("%Y%m%dT235959").to_string()
+            }
+        })
+    }
+
+    fn format(&self, until: impl Fn(NaiveDate) -> String) -> String {
+        let frequency = match self.frequency {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+            Frequency::Yearly => "YEARLY",
+        };
+        let mut parts = vec![format!("FREQ={frequency}")];
+        if self.interval > 1 {
+            parts.push(format!("INTERVAL={}", self.interval));
+        }
+        if !self.by_day.is_empty() {
+            let days: Vec<&str> = self
+                .by_day
+                .iter()
+                .filter_map(|day| WEEKDAY_CODES.iter().find(|(_, d)| d == day))
+                .map(|(code, _)| *code)
+                .collect();
+            parts.push(format!("BYDAY={}", days.join(",")));
+        }
+        if let Some(day) = self.by_month_day {
+            parts.push(format!("BYMONTHDAY={day}"));
+        }
+        if let Some(date) = self.until {
+            parts.push(format!("UNTIL={}", until(date)));
+        }
+        parts.join(";")
+    }
+
+    /// anchor 之后（不含）晚于 after 的第一次日期；超过 UNTIL 时返回 None
+    pub fn next_after(&self, anchor: NaiveDate, after: NaiveDate) -> Option<NaiveDate> {
+        let after = after.max(anchor);
+        let interval = i64::from(self.interval);
+        let next = match self.frequency {
+            Frequency::Daily => {
+                let steps = (after - anchor).num_days() / interval + 1;
+                anchor + Duration::days(steps * interval)
+            }
+            Frequency::Weekly if self.by_day.is_empty() => {
+                let steps = (after - anchor).num_days() / (7 * interval) + 1;
+                anchor + Duration::weeks(steps * interval)
+            }
+            Frequency::Weekly => {
+                let week_start = |date: NaiveDate| {
+                    date - Duration::days(i64::from(date.weekday().num_days_from_monday()))
+                };
+                let first_week = week_start(anchor);
+                let weeks = (week_start(after) - first_week).num_weeks();
+                let mut block = weeks / interval * interval;
+                loop {
+                    let monday = first_week + Duration::weeks(block);
+                    let found = self
+                        .by_day
+                        .iter()
+                        .map(|day| monday + Duration::days(i64::from(day.num_days_from_monday())))
+                        .find(|date| *date > after);
+                    if let Some(date) = found {
+                        break date;
+                    }
+                    block += interval;
+                }
+            }
+            Frequency::Monthly | Frequency::Yearly => {
+                let step = if self.frequency == Frequency::Yearly {
+                    12 * interval
+                } else {
+                    interval
+                };
+                let months_between = (i64::from(after.year()) - i64::from(anchor.year())) * 12
+                    + i64::from(after.month())
+                    - i64::from(anchor.month());
+                let mut offset = months_between / step * step;
+                loop {
+                    let date = self.month_occurrence(anchor, offset)?;
+                    if date > after {
+                        break date;
+                    }
+                    offset += step;
+                }
+            }
+        };
+        match self.until {
+            Some(until) if next > until => None,
+            _ => Some(next),
+        }
+    }
+
+    /// 起始日之后的某天是否是一次重复
+    pub fn occurs_on(&self, anchor: NaiveDate, date: NaiveDate) -> bool {
+        date > anchor
+            && date
+                .pred_opt()
+                .and_then(|previous| self.next_after(anchor, previous))
+                == Some(date)
+    }
+
+    fn month_occurrence(&self, anchor: NaiveDate, offset: i64) -> Option<NaiveDate> {
+        let first = anchor.with_day(1)? + Months::new(u32::try_from(offset).ok()?);
+        let day = self.by_month_day.unwrap_or(anchor.day());
+        let days_in_month = (first + Months::new(1) - first).num_days() as u32;
+        first.with_day(day.min(days_in_month))
+    }
+}
+
+/// 把 due_date 拆成日期与其后的时间部分（如 `T09:00`），重复实例沿用同一时间
+fn split_due_date(due_date: &str) -> Option<(NaiveDate, &str)> {
+    let date = NaiveDate::parse_from_str(due_date.get(..10)?, "%Y-%m-%d").ok()?;
+    Some((date, &due_date[10..]))
+}
+
+/// 标记任务完成；重复任务同时生成下一次的任务，返回其 node_id
+///
+/// 逾期完成时下一次不早于今天，避免一次补出多个已过期的任务。
+pub async fn complete_task(db: &DbPool, node_id: i64) -> Result<Option<i64>, String> {
+    mark_task_done(db, node_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    materialize_next_occurrence(db, node_id).await
+}
+
+/// 已完成的重复任务生成下一次的任务；非重复任务返回 None
+pub async fn materialize_next_occurrence(db: &DbPool, node_id: i64) -> Result<Option<i64>, String> {
+    let node = get_node_by_id(db, node_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    let (Some(rule), Some(due_date)) = (node.recurrence_rule.as_deref(), node.due_date.as_deref())
+    else {
+        return Ok(None);
+    };
+    let Some((anchor, time)) = split_due_date(due_date) else {
+        return Ok(None);
+    };
+    let rule = RecurrenceRule::parse(rule)?;
+    let yesterday = Local::now().date_naive() - Duration::days(1);
+    let Some(next) = rule.next_after(anchor, yesterday) else {
+        return Ok(None);
+    };
+
+    let next_due = format!("{}{}", next.format("%Y-%m-%d"), time);
+    let next_id = materialize_next_task_occurrence(db, node_id, &next_due)
+        .await
+        .map_err(|e| e.to_string())?;
+    tracing::info!(node_id, next_id, next_due = %next_due, "Recurring task materialized");
+    Ok(Some(next_id))
+}
+
+/// 某天的任务，包括尚未生成的重复实例
+///
+/// 重复实例与未完成的原任务共享 node_id，due_date 为当天；完成原任务即推进到下一次。
+pub async fn list_tasks_with_occurrences(
+    db: &DbPool,
+    date: &str,
+) -> Result<Vec<NodeRecord>, String> {
+    let mut tasks = list_tasks_by_date(db, date)
+        .await
+        .map_err(|e| e.to_string())?;
+    let Some((day, _)) = split_due_date(date) else {
+        return Ok(tasks);
+    };
+
+    let recurring = list_open_recurring_tasks(db)
+        .await
+        .map_err(|e| e.to_string())?;
+    for mut task in recurring {
+        if tasks
+            .iter()
+            .any(|existing| existing.node_id == task.node_id)
+        {
+            continue;
+        }
+        let Some(rule) = task
+            .recurrence_rule
+            .as_deref()
+            .and_then(|rule| RecurrenceRule::parse(rule).ok())
+        else {
+            continue;
+        };
+        let Some((anchor, time)) = task.due_date.as_deref().and_then(split_due_date) else {
+            continue;
+        };
+        if rule.occurs_on(anchor, day) {
+            task.due_date = Some(format!("{}{}", day.format("%Y-%m-%d"), time));
+            tasks.push(task);
+        }
+    }
+    tasks.sort_by(|a, b| a.due_date.cmp(&b.due_date));
+    Ok(tasks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(raw: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(raw, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_normalize() {
+        let rule = RecurrenceRule::parse("rrule:freq=weekly;interval=2;byday=WE,MO;until=20251231")
+            .unwrap();
+        assert_eq!(
+            rule.to_rule_string(),
+            "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE;UNTIL=2025-12-31"
+        );
+        assert_eq!(
+            rule.to_ics_rrule(true),
+            "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE;UNTIL=20251231"
+        );
+        assert_eq!(
+            rule.to_ics_rrule(false),
+            "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE;UNTIL=20251231T235959"
+        );
+        assert!(RecurrenceRule::parse("INTERVAL=2").is_err());
+        assert!(RecurrenceRule::parse("FREQ=DAILY;BYDAY=MO").is_err());
+        assert!(RecurrenceRule::parse("FREQ=DAILY;COUNT=3").is_err());
+        assert_eq!(
+            RecurrenceRule::parse("FREQ=MONTHLY")
+                .unwrap()
+                .anchored(d("2025-01-31"))
+                .to_rule_string(),
+            "FREQ=MONTHLY;BYMONTHDAY=31"
+        );
+    }
+
+    #[test]
+    fn test_next_after_daily_and_weekly() {
+        let daily = RecurrenceRule::parse("FREQ=DAILY;INTERVAL=3").unwrap();
+        assert_eq!(
+            daily.next_after(d("2025-01-01"), d("2025-01-01")),
+            Some(d("2025-01-04"))
+        );
+        assert_eq!(
+            daily.next_after(d("2025-01-01"), d("2025-01-05")),
+            Some(d("2025-01-07"))
+        );
+
+        // 2025-01-01 是周三
+        let weekly = RecurrenceRule::parse("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,FR").unwrap();
+        assert_eq!(
+            weekly.next_after(d("2025-01-01"), d("2025-01-01")),
+            Some(d("2025-01-03"))
+        );
+        assert_eq!(
+            weekly.next_after(d("2025-01-01"), d("2025-01-03")),
+            Some(d("2025-01-13"))
+        );
+        assert!(weekly.occurs_on(d("2025-01-01"), d("2025-01-17")));
+        assert!(!weekly.occurs_on(d("2025-01-01"), d("2025-01-10")));
+    }
+
+    #[test]
+    fn test_next_after_monthly_clamps_to_month_end() {
+        let monthly = RecurrenceRule::parse("FREQ=MONTHLY")
+            .unwrap()
+            .anchored(d("2025-01-31"));
+        assert_eq!(
+            monthly.next_after(d("2025-01-31"), d("2025-01-31")),
+            Some(d("2025-02-28"))
+        );
+        // 从二月的实例继续，仍回到 31 号
+        assert_eq!(
+            monthly.next_after(d("2025-02-28"), d("2025-02-28")),
+            Some(d("2025-03-31"))
+        );
+
+        let yearly = RecurrenceRule::parse("FREQ=YEARLY;UNTIL=2026-12-31").unwrap();
+        assert_eq!(
+            yearly.next_after(d("2024-02-29"), d("2024-03-01")),
+            Some(d("2025-02-28"))
+        );
+        assert_eq!(yearly.next_after(d("2024-02-29"), d("2026-03-01")), None);
+    }
+}
@@ -0,0 +1,169 @@
+//! 资源内容解析与入队
+//!
+//! 捕获、批量捕获、压缩包展开与监听文件夹导入共用：解析文件内容写回节点，再加入 AI 队列。
+
+use tauri::AppHandle;
+
+use super::{
+    emit_event,
+    parser::{parse_resource_content, ProgressCallback},
+    JobPriority, ParseProgressPayload,
+};
+use crate::{
+    app_state::AppState,
+    db::{
+        update_node_content, update_resource_sync_status, ResourceEmbeddingStatus, ResourceSubtype,
+    },
+    AppResult,
+};
+
+fn emit_parse_progress(
+    app: Option<&AppHandle>,
+    node_id: Option<i64>,
+    status: &str,
+    percentage: Option<u8>,
+    error: Option<&str>,
+) {
+    let (app, node_id) = match (app, node_id) {
+        (Some(app), Some(node_id)) => (app, node_id),
+        _ => return,
+    };
+
+    emit_event(
+        app,
+        &ParseProgressPayload {
+            node_id,
+            status: status.to_string(),
+            percentage,
+            error: error.map(|message| message.to_string()),
+        },
+    );
+}
+
+/// 解析资源内容并加入 AI 队列，进度通过 parse-progress 事件通知
+///
+/// 解析或入队失败会记录到资源的 embedding 状态，并作为 `Ok(Some(error))` 返回
+#[allow(clippy::too_many_arguments)]
+pub async fn parse_and_enqueue(
+    app: &AppHandle,
+    state: &AppState,
+    node_id: i64,
+    subtype: ResourceSubtype,
+    content: Option<&str>,
+    resolved_path: Option<&str>,
+    file_hash: &str,
+    priority: JobPriority,
+) -> AppResult<Option<String>> {
+    let should_enqueue = match parse_content(
+        app,
+        state,
+        node_id,
+        subtype,
+        content,
+        resolved_path,
+        file_hash,
+    )
+    .await?
+    {
+        Ok(should_enqueue) => should_enqueue,
+        Err(err) => return Ok(Some(err)),
+    };
+
+    if should_enqueue {
+        if let Err(err) = state.ai_pipeline.enqueue_resource(node_id, priority).await {
+            update_resource_sync_status(
+                &state.db,
+                node_id,
+                ResourceEmbeddingStatus::Error,
+                None,
+                Some(&err),
+            )
+            .await?;
+            emit_parse_progress(Some(app), Some(node_id), "error", None, Some(&err));
+            return Ok(Some(err));
+        }
+    }
+
+    Ok(None)
+}
+
+/// 只解析资源内容，不加入 AI 队列（推迟处理的捕获模板）
+///
+/// 资源保持 pending，由“处理待定资源”或下次启动时的重新入队处理
+pub async fn parse_deferred(
+    app: &AppHandle,
+    state: &AppState,
+    node_id: i64,
+    subtype: ResourceSubtype,
+    content: Option<&str>,
+    resolved_path: Option<&str>,
+    file_hash: &str,
+) -> AppResult<Option<String>> {
+    let result = parse_content(
+        app,
+        state,
+        node_id,
+        subtype,
+        content,
+        resolved_path,
+        file_hash,
+    )
+    .await?;
+    Ok(result.err())
+}
+
+/// 解析内容并写回节点；返回是否需要 AI 处理，解析失败时返回错误信息
+async fn parse_content(
+    app: &AppHandle,
+    state: &AppState,
+    node_id: i64,
+    subtype: ResourceSubtype,
+    content: Option<&str>,
+    resolved_path: Option<&str>,
+    file_hash: &str,
+) -> AppResult<Result<bool, String>> {
+    emit_parse_progress(Some(app), Some(node_id), "parsing", Some(0), None);
+
+    // Create progress callback for parser
+    let app_clone = app.clone();
+    let progress_callback: ProgressCallback = Box::new(move |status, percentage, error| {
+        emit_parse_progress(Some(&app_clone), Some(node_id), status, percentage, error);
+    });
+
+    let file_content_result =
+        parse_resource_content(subtype, content, resolved_path, Some(&progress_callback));
+
+    let mut should_enqueue = false;
+    match file_content_result {
+        Ok(text) => {
+            if let Some(content) = text.as_deref() {
+                tracing::debug!(
+                    node_id,
+                    subtype = ?subtype,
+                    content = %content,
+                    "Parsed resource content"
+                );
+                update_node_content(&state.db, node_id, Some(content), Some(file_hash)).await?;
+                should_enqueue = !content.trim().is_empty();
+            } else if subtype == ResourceSubtype::Audio {
+                // 本地无法转写的音频交给 AI 管线转写
+                should_enqueue = true;
+            }
+            emit_parse_progress(Some(app), Some(node_id), "done", Some(100), None);
+        }
+        Err(err) => {
+            update_resource_sync_status(
+                &state.db,
+                node_id,
+                ResourceEmbeddingStatus::Error,
+                None,
+                Some(&err),
+            )
+            .await?;
+            emit_parse_progress(Some(app), Some(node_id), "error", None, Some(&err));
+            return Ok(Err(err));
+        }
+    }
+
+    Ok(Ok(should_enqueue))
+}
@@ -0,0 +1,218 @@
+//! 资源保留策略
+//!
+//! 按规则把创建时间较早的资源归档（不参与语义搜索与对话 RAG）或移入回收站，例如「新闻」主题下
+//! 超过 90 天的资源自动归档、30 天前的文本片段在没有关联时自动删除。启用的规则每天执行一次，
+//! 处理了资源时写入通知中心；预览只查询命中的资源，不做任何修改。
+
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::app_state::AppState;
+use crate::db::{
+    apply_retention_action, get_node_by_id, list_retention_candidates, list_retention_rules,
+    record_retention_run, DbPool, NewNotification, NodeType, NotificationKind, RetentionAction,
+    RetentionCandidateRow, RetentionRuleInput, RetentionRuleRecord,
+};
+use crate::i18n::MessageCode;
+use crate::services::push_notification;
+use crate::{AppError, AppResult};
+
+const SCHEDULER_INITIAL_DELAY: Duration = Duration::from_secs(30 * 60);
+const SCHEDULER_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+/// 预览时最多返回的资源数
+const PREVIEW_LIMIT: usize = 200;
+/// max_age_days 上限（约 100 年）
+const MAX_AGE_DAYS_LIMIT: i64 = 36_500;
+
+/// 规则预览（dry run）
+#[derive(Debug, Serialize)]
+pub struct RetentionPreview {
+    /// 命中的资源总数
+    pub total: usize,
+    /// 最早创建的前 [`PREVIEW_LIMIT`] 个
+    pub nodes: Vec<RetentionCandidateRow>,
+}
+
+/// 一条规则的执行结果
+#[derive(Debug, Clone, Serialize)]
+pub struct RetentionRunResult {
+    pub rule_id: i64,
+    pub name: String,
+    pub action: RetentionAction,
+    pub affected: usize,
+}
+
+fn rule_input(rule: &RetentionRuleRecord) -> RetentionRuleInput {
+    RetentionRuleInput {
+        name: rule.name.clone(),
+        topic_id: rule.topic_id,
+        resource_subtype: rule.resource_subtype,
+        max_age_days: rule.max_age_days,
+        action: rule.action,
+        skip_linked: rule.skip_linked,
+        is_enabled: rule.is_enabled,
+    }
+}
+
+/// 检查规则定义；名称去除首尾空白
+pub async fn validate_retention_rule(
+    db: &DbPool,
+    mut rule: RetentionRuleInput,
+) -> AppResult<RetentionRuleInput> {
+    let invalid =
+        |detail: &str| AppError::coded_with(MessageCode::InvalidRetentionRule, "detail", detail);
+    rule.name = rule.name.trim().to_string();
+    if rule.name.is_empty() {
+        return Err(invalid("名称不能为空"));
+    }
+    if !(1..=MAX_AGE_DAYS_LIMIT).contains(&rule.max_age_days) {
+        return Err(invalid("天数需在 1-36500 之间"));
+    }
+    if let Some(topic_id) = rule.topic_id {
+        let is_topic = get_node_by_id(db, topic_id)
+            .await
+            .is_ok_and(|node| node.node_type == NodeType::Topic && !node.is_deleted);
+        if !is_topic {
+            return Err(AppError::NotFound {
+                entity: "topic",
+                id: topic_id,
+            });
+        }
+    }
+    Ok(rule)
+}
+
+/// 规则当前会处理的资源，不做修改
+pub async fn preview_retention(
+    db: &DbPool,
+    rule: &RetentionRuleInput,
+) -> AppResult<RetentionPreview> {
+    let mut nodes = list_retention_candidates(db, rule).await?;
+    let total = nodes.len();
+    nodes.truncate(PREVIEW_LIMIT);
+    Ok(RetentionPreview { total, nodes })
+}
+
+/// 执行全部启用的规则；单条规则失败只记录日志
+pub async fn run_retention_rules(db: &DbPool) -> AppResult<Vec<RetentionRunResult>> {
+    let rules = list_retention_rules(db).await?;
+    let mut results = Vec::new();
+    for rule in rules.iter().filter(|rule| rule.is_enabled) {
+        match run_rule(db, rule).await {
+            Ok(affected) => results.push(RetentionRunResult {
+                rule_id: rule.rule_id,
+                name: rule.name.clone(),
+                action: rule.action,
+                affected,
+            }),
+            Err(err) => {
+                tracing::warn!(rule_id = rule.rule_id, error = %err, "Retention rule failed");
+            }
+        }
+    }
+    Ok(results)
+}
+
+async fn run_rule(db: &DbPool, rule: &RetentionRuleRecord) -> AppResult<usize> {
+    let node_ids: Vec<i64> = list_retention_candidates(db, &rule_input(rule))
+        .await?
+        .into_iter()
+        .map(|node| node.node_id)
+        .collect();
+    let affected = apply_retention_action(db, rule.action, &node_ids).await?;
+    record_retention_run(db, rule.rule_id, affected).await?;
+    if affected > 0 {
+        tracing::info!(
+            rule_id = rule.rule_id,
+            action = ?rule.action,
+            affected,
+            "Retention rule applied"
+        );
+    }
+    Ok(affected)
+}
+
+/// 通知正文，如「新闻：归档 12 个资源；剪贴板片段：移入回收站 3 个资源」；没有处理任何资源时为空
+fn summarize_results(results: &[RetentionRunResult]) -> Option<String> {
+    let parts: Vec<String> = results
+        .iter()
+        .filter(|result| result.affected > 0)
+        .map(|result| {
+            let action = match result.action {
+                RetentionAction::Archive => "归档",
+                RetentionAction::Delete => "移入回收站",
+            };
+            format!("{}：{action} {} 个资源", result.name, result.affected)
+        })
+        .collect();
+    (!parts.is_empty()).then(|| parts.join("；"))
+}
+
+async fn run_scheduled_retention(app: &AppHandle, state: &AppState) -> AppResult<()> {
+    let results = run_retention_rules(&state.db).await?;
+    let Some(body) = summarize_results(&results) else {
+        return Ok(());
+    };
+    push_notification(
+        &state.db,
+        app,
+        NewNotification {
+            kind: NotificationKind::System,
+            title: "保留策略已执行",
+            body: Some(&body),
+            node_id: None,
+            action: None,
+        },
+    )
+    .await?;
+    Ok(())
+}
+
+/// 每天执行一次启用的保留规则
+pub fn spawn_retention_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(SCHEDULER_INITIAL_DELAY).await;
+        loop {
+            if let Some(state) = app.try_state::<AppState>() {
+                if let Err(err) = run_scheduled_retention(&app, &state).await {
+                    tracing::warn!(error = %err, "Scheduled retention failed");
+                }
+            }
+            tokio::time::sleep(SCHEDULER_INTERVAL).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(name: &str, action: RetentionAction, affected: usize) -> RetentionRunResult {
+        RetentionRunResult {
+            rule_id: 1,
+            name: name.to_string(),
+            action,
+            affected,
+        }
+    }
+
+    #[test]
+    fn test_summarize_results_skips_idle_rules() {
+        assert_eq!(summarize_results(&[]), None);
+        assert_eq!(
+            summarize_results(&[result("新闻", RetentionAction::Archive, 0)]),
+            None
+        );
+        assert_eq!(
+            summarize_results(&[
+                result("新闻", RetentionAction::Archive, 12),
+                result("空规则", RetentionAction::Delete, 0),
+                result("剪贴板片段", RetentionAction::Delete, 3),
+            ])
+            .as_deref(),
+            Some("新闻：归档 12 个资源；剪贴板片段：移入回收站 3 个资源")
+        );
+    }
+}
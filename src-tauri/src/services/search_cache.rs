@@ -0,0 +1,112 @@
+//! 语义搜索结果缓存
+//!
+//! 保存最近几次混合搜索的结果（node_id + 分数），供 `quick_search` 在用户输入时
+//! 直接复用，不触发 embedding 模型。
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+/// 最多缓存的查询数
+const SEARCH_CACHE_CAPACITY: usize = 16;
+
+/// 单次查询的结果：(node_id, score)
+type CachedHits = Vec<(i64, f64)>;
+
+#[derive(Default)]
+pub struct SemanticSearchCache {
+    entries: Mutex<VecDeque<(String, CachedHits)>>,
+}
+
+impl SemanticSearchCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 记录一次搜索结果；同一查询只保留最新一次
+    pub fn store(&self, query: &str, hits: CachedHits) {
+        let query = normalize_query(query);
+        if query.is_empty() {
+            return;
+        }
+        let Ok(mut entries) = self.entries.lock() else {
+            return;
+        };
+        entries.retain(|(cached, _)| *cached != query);
+        entries.push_front((query, hits));
+        entries.truncate(SEARCH_CACHE_CAPACITY);
+    }
+
+    /// 清空缓存（换用新的嵌入模型后分数不再可比）
+    pub fn clear(&self) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.clear();
+        }
+    }
+
+    /// 取出以 prefix 开头的已缓存查询的结果，按最高分合并后降序返回
+    pub fn lookup(&self, prefix: &str) -> CachedHits {
+        let prefix = normalize_query(prefix);
+        if prefix.is_empty() {
+            return Vec::new();
+        }
+        let Ok(entries) = self.entries.lock() else {
+            return Vec::new();
+        };
+
+        let mut best: HashMap<i64, f64> = HashMap::new();
+        for (query, hits) in entries.iter() {
+            if !query.starts_with(&prefix) {
+                continue;
+            }
+            for &(node_id, score) in hits {
+                let entry = best.entry(node_id).or_insert(score);
+                if score > *entry {
+                    *entry = score;
+                }
+            }
+        }
+
+        let mut merged: CachedHits = best.into_iter().collect();
+        merged.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        merged
+    }
+}
+
+fn normalize_query(query: &str) -> String {
+    query
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup_matches_cached_queries_by_prefix() {
+        let cache = SemanticSearchCache::new();
+        cache.store("Rust async", vec![(1, 0.9), (2, 0.5)]);
+        cache.store("rust  macros", vec![(2, 0.8), (3, 0.4)]);
+        cache.store("python", vec![(4, 0.7)]);
+
+        assert_eq!(cache.lookup(" RUST"), vec![(1, 0.9), (2, 0.8), (3, 0.4)]);
+        assert_eq!(cache.lookup("rust m"), vec![(2, 0.8), (3, 0.4)]);
+        assert!(cache.lookup("go").is_empty());
+        assert!(cache.lookup("").is_empty());
+    }
+
+    #[test]
+    fn test_store_replaces_and_evicts() {
+        let cache = SemanticSearchCache::new();
+        cache.store("query", vec![(1, 0.1)]);
+        cache.store("Query", vec![(2, 0.2)]);
+        assert_eq!(cache.lookup("query"), vec![(2, 0.2)]);
+
+        for i in 0..SEARCH_CACHE_CAPACITY as i64 {
+            cache.store(&format!("other {i}"), vec![(i, 0.5)]);
+        }
+        assert!(cache.lookup("query").is_empty());
+    }
+}
@@ -0,0 +1,69 @@
+//! 搜索模型空闲预热
+//!
+//! 搜索用的 embedding 模型懒加载，空闲一段时间后会被卸载，导致首次搜索很慢。
+//! 后台任务在启动稳定后预热一次；模型被卸载后，等用户回到主窗口且处理队列
+//! 空闲时再预热。使用电池或在设置中关闭时跳过。
+
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager};
+
+use crate::app_state::AppState;
+use crate::utils::is_on_battery;
+
+/// 启动后等待多久再预热，避开启动时的初始化与重新入队
+const WARMUP_INITIAL_DELAY: Duration = Duration::from_secs(30);
+const WARMUP_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+pub fn spawn_search_warmup(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(WARMUP_INITIAL_DELAY).await;
+        let mut warmed_once = false;
+        loop {
+            if let Some(state) = app.try_state::<AppState>() {
+                // 启动后的首次预热不要求窗口在前台
+                let require_focus = warmed_once;
+                if should_warmup(&app, &state, require_focus).await {
+                    match warmup(&state).await {
+                        Ok(()) => {
+                            warmed_once = true;
+                            tracing::debug!("Search models warmed up");
+                        }
+                        Err(err) => tracing::warn!(error = %err, "Search warm-up failed"),
+                    }
+                }
+            }
+            tokio::time::sleep(WARMUP_CHECK_INTERVAL).await;
+        }
+    });
+}
+
+async fn should_warmup(app: &AppHandle, state: &AppState, require_focus: bool) -> bool {
+    let enabled = match state.ai_config.lock().await.load() {
+        Ok(config) => config.warmup_on_idle,
+        Err(_) => false,
+    };
+    if !enabled || !state.ai_pipeline.is_idle().await {
+        return false;
+    }
+    if require_focus {
+        let focused = app
+            .get_webview_window("main")
+            .and_then(|window| window.is_focused().ok())
+            .unwrap_or(false);
+        if !focused {
+            return false;
+        }
+    }
+    !tauri::async_runtime::spawn_blocking(is_on_battery)
+        .await
+        .unwrap_or(true)
+}
+
+async fn warmup(state: &AppState) -> Result<(), String> {
+    let ai = state.ai.wait_ready().await?;
+    if ai.embedding.is_search_warm().await {
+        return Ok(());
+    }
+    ai.embedding.warmup_search().await
+}
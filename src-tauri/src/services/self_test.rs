@@ -0,0 +1,210 @@
+//! 启动自检
+//!
+//! 检查打包后容易出问题的环节：数据库迁移、third_party_model 目录、OCR 模型、LanceDB 写入、
+//! 本地 whisper.cpp 程序。每一项独立执行，单项失败不影响其他项，结果供诊断页面展示。
+
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+
+use arrow_schema::{DataType, Field, Schema};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::app_state::AppState;
+use crate::db::list_pending_migrations;
+use crate::services::parser::{
+    build_ocr_engine, local_whisper_binary, missing_model_files, third_party_model_dir,
+    REQUIRED_MODEL_FILES,
+};
+use crate::AppResult;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SelfTestItem {
+    Migrations,
+    ModelDir,
+    OcrModel,
+    LancedbWritable,
+    Whisper,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SelfTestStatus {
+    Pass,
+    Fail,
+    /// 可选组件未安装
+    Skipped,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SelfTestCheck {
+    pub item: SelfTestItem,
+    pub status: SelfTestStatus,
+    /// 路径或错误信息
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SelfTestReport {
+    /// 没有失败项（跳过不算失败）
+    pub passed: bool,
+    pub checks: Vec<SelfTestCheck>,
+}
+
+impl SelfTestCheck {
+    fn from_result(item: SelfTestItem, result: Result<String, String>) -> Self {
+        let (status, detail) = match result {
+            Ok(detail) => (SelfTestStatus::Pass, detail),
+            Err(detail) => (SelfTestStatus::Fail, detail),
+        };
+        Self {
+            item,
+            status,
+            detail: Some(detail),
+        }
+    }
+}
+
+/// 依次执行所有检查
+pub async fn run_self_checks(state: &AppState) -> AppResult<SelfTestReport> {
+    let model_dir = third_party_model_dir();
+    let lancedb_path = state
+        .ai_config
+        .lock()
+        .await
+        .get_vector_config()?
+        .lancedb_path;
+
+    let checks = vec![
+        SelfTestCheck::from_result(SelfTestItem::Migrations, check_migrations(state).await),
+        SelfTestCheck::from_result(SelfTestItem::ModelDir, check_model_dir(&model_dir)),
+        SelfTestCheck::from_result(SelfTestItem::OcrModel, check_ocr_model().await),
+        SelfTestCheck::from_result(
+            SelfTestItem::LancedbWritable,
+            check_lancedb_writable(Path::new(&lancedb_path)).await,
+        ),
+        check_whisper().await,
+    ];
+    let passed = checks
+        .iter()
+        .all(|check| check.status != SelfTestStatus::Fail);
+    if !passed {
+        tracing::warn!(?checks, "Self test failed");
+    }
+    Ok(SelfTestReport { passed, checks })
+}
+
+async fn check_migrations(state: &AppState) -> Result<String, String> {
+    let pending = list_pending_migrations(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+    if pending.is_empty() {
+        return Ok("all migrations applied".to_string());
+    }
+    let versions = pending
+        .iter()
+        .map(i64::to_string)
+        .collect::<Vec<_>>()
+        .join(", ");
+    Err(format!("pending migrations: {versions}"))
+}
+
+fn check_model_dir(model_dir: &Path) -> Result<String, String> {
+    if !model_dir.is_dir() {
+        return Err(format!("missing directory: {}", model_dir.display()));
+    }
+    let missing = missing_model_files(model_dir);
+    if !missing.is_empty() {
+        return Err(format!(
+            "missing files in {}: {}",
+            model_dir.display(),
+            missing.join(", ")
+        ));
+    }
+    Ok(model_dir.display().to_string())
+}
+
+async fn check_ocr_model() -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(build_ocr_engine)
+        .await
+        .map_err(|e| e.to_string())??;
+    Ok("OCR engine loaded".to_string())
+}
+
+/// 在 LanceDB 目录下建一个临时库并写入一张空表，结束后删除
+async fn check_lancedb_writable(lancedb_path: &Path) -> Result<String, String> {
+    let probe_dir = lancedb_path.join(format!(".self-test-{}", Uuid::new_v4()));
+    let result = create_probe_table(&probe_dir).await;
+    if let Err(err) = std::fs::remove_dir_all(&probe_dir) {
+        if err.kind() != std::io::ErrorKind::NotFound {
+            tracing::warn!(path = %probe_dir.display(), error = %err, "Remove self test table failed");
+        }
+    }
+    result.map(|_| lancedb_path.display().to_string())
+}
+
+async fn create_probe_table(probe_dir: &Path) -> Result<(), String> {
+    let uri = probe_dir
+        .to_str()
+        .ok_or_else(|| format!("invalid path: {}", probe_dir.display()))?;
+    let db = lancedb::connect(uri)
+        .execute()
+        .await
+        .map_err(|e| e.to_string())?;
+    let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+    db.create_empty_table("probe", schema)
+        .execute()
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// 本地转写是可选的：未安装时跳过，安装了则确认程序能启动
+async fn check_whisper() -> SelfTestCheck {
+    let Some(binary) = local_whisper_binary() else {
+        return SelfTestCheck {
+            item: SelfTestItem::Whisper,
+            status: SelfTestStatus::Skipped,
+            detail: None,
+        };
+    };
+    let result = tauri::async_runtime::spawn_blocking(move || launch_binary(&binary))
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(|result| result);
+    SelfTestCheck::from_result(SelfTestItem::Whisper, result)
+}
+
+fn launch_binary(binary: &Path) -> Result<String, String> {
+    // 旧版本的 --help 以非零状态退出，能启动即视为可用
+    Command::new(binary)
+        .arg("--help")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map_err(|e| format!("{}: {e}", binary.display()))?;
+    Ok(binary.display().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_model_dir_reports_missing_files() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(check_model_dir(&dir.path().join("absent")).is_err());
+
+        std::fs::write(dir.path().join(REQUIRED_MODEL_FILES[0]), b"model").unwrap();
+        let err = check_model_dir(dir.path()).unwrap_err();
+        assert!(err.contains(REQUIRED_MODEL_FILES[1]) && err.contains(REQUIRED_MODEL_FILES[2]));
+
+        for name in &REQUIRED_MODEL_FILES[1..] {
+            std::fs::write(dir.path().join(name), b"model").unwrap();
+        }
+        assert!(check_model_dir(dir.path()).is_ok());
+    }
+}
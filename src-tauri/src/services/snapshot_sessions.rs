@@ -0,0 +1,190 @@
+//! 只读打开导出的 archive 备份
+//!
+//! 把 zip 中的数据库快照解压到临时目录，单独建一个只读连接池，用来查看旧备份的内容而不覆盖
+//! 当前数据库。会话只保存在内存中，检索只走 SQLite 全文索引，不加载向量库；资源文件留在 zip 中。
+//! 关闭会话时删除临时文件，应用启动时清理上次遗留的目录。
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Mutex;
+
+use chrono::Utc;
+use serde::Serialize;
+use zip::result::ZipError;
+use zip::ZipArchive;
+
+use crate::db::{get_snapshot_stats, open_snapshot_pool, DbPool};
+use crate::i18n::MessageCode;
+use crate::services::ARCHIVE_DB_ENTRY;
+use crate::{AppError, AppResult};
+
+/// 同时打开的备份数上限
+const MAX_SESSIONS: usize = 3;
+
+/// 已打开的备份
+#[derive(Debug, Clone, Serialize)]
+pub struct SnapshotInfo {
+    pub session_id: i64,
+    pub archive_path: String,
+    pub opened_at: String,
+    /// 未删除的节点数
+    pub node_count: i64,
+    /// 备份中最近一次修改节点的时间，近似于备份时间
+    pub last_updated_at: Option<String>,
+}
+
+struct SnapshotSession {
+    info: SnapshotInfo,
+    pool: DbPool,
+    dir: PathBuf,
+}
+
+/// 备份会话表，保存在 `AppState.snapshots`
+pub struct SnapshotSessions {
+    root: PathBuf,
+    next_id: AtomicI64,
+    sessions: Mutex<HashMap<i64, SnapshotSession>>,
+}
+
+fn invalid_archive(detail: impl ToString) -> AppError {
+    AppError::coded_with(MessageCode::InvalidSnapshotArchive, "detail", detail)
+}
+
+/// 从 archive 导出中解压数据库快照到 target
+fn extract_snapshot_db(archive_path: &Path, target: &Path) -> AppResult<()> {
+    let mut archive = ZipArchive::new(File::open(archive_path)?).map_err(invalid_archive)?;
+    let mut entry = match archive.by_name(ARCHIVE_DB_ENTRY) {
+        Ok(entry) => entry,
+        Err(ZipError::FileNotFound) => {
+            return Err(invalid_archive("不是完整备份（archive）格式的导出"))
+        }
+        Err(err) => return Err(invalid_archive(err)),
+    };
+    io::copy(&mut entry, &mut File::create(target)?)?;
+    Ok(())
+}
+
+impl SnapshotSessions {
+    /// root 为临时目录，每个会话使用其中的一个子目录
+    pub fn new(root: PathBuf) -> Self {
+        Self {
+            root,
+            next_id: AtomicI64::new(0),
+            sessions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<i64, SnapshotSession>> {
+        self.sessions.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 删除上次运行遗留的临时目录，启动时调用
+    pub fn clear_stale(&self) {
+        if let Err(err) = fs::remove_dir_all(&self.root) {
+            if err.kind() != io::ErrorKind::NotFound {
+                tracing::warn!(error = %err, "Failed to clear snapshot temp dir");
+            }
+        }
+    }
+
+    /// 解压并只读打开备份；旧版本的备份在临时副本上补齐迁移
+    pub async fn open(&self, archive_path: &Path) -> AppResult<SnapshotInfo> {
+        if !archive_path.is_file() {
+            return Err(invalid_archive(format!(
+                "{} 不是文件",
+                archive_path.display()
+            )));
+        }
+        if self.lock().len() >= MAX_SESSIONS {
+            return Err(invalid_archive(format!(
+                "最多同时打开 {MAX_SESSIONS} 个备份，请先关闭不再查看的备份"
+            )));
+        }
+
+        let session_id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let dir = self.root.join(format!("session-{session_id}"));
+        fs::create_dir_all(&dir)?;
+        let db_path = dir.join(ARCHIVE_DB_ENTRY);
+
+        let source = archive_path.to_path_buf();
+        let target = db_path.clone();
+        let opened = async {
+            tauri::async_runtime::spawn_blocking(move || extract_snapshot_db(&source, &target))
+                .await
+                .map_err(|e| AppError::from(e.to_string()))??;
+            let pool = open_snapshot_pool(&db_path).await?;
+            let (node_count, last_updated_at) = match get_snapshot_stats(&pool).await {
+                Ok(stats) => stats,
+                Err(err) => {
+                    pool.close().await;
+                    return Err(err.into());
+                }
+            };
+            Ok::<_, AppError>((pool, node_count, last_updated_at))
+        }
+        .await;
+        let (pool, node_count, last_updated_at) = match opened {
+            Ok(opened) => opened,
+            Err(err) => {
+                let _ = fs::remove_dir_all(&dir);
+                return Err(err);
+            }
+        };
+
+        let info = SnapshotInfo {
+            session_id,
+            archive_path: archive_path.display().to_string(),
+            opened_at: Utc::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            node_count,
+            last_updated_at,
+        };
+        self.lock().insert(
+            session_id,
+            SnapshotSession {
+                info: info.clone(),
+                pool,
+                dir,
+            },
+        );
+        tracing::info!(session_id, path = %info.archive_path, "Snapshot opened");
+        Ok(info)
+    }
+
+    /// 已打开的备份，先打开的在前
+    pub fn list(&self) -> Vec<SnapshotInfo> {
+        let mut infos: Vec<SnapshotInfo> = self
+            .lock()
+            .values()
+            .map(|session| session.info.clone())
+            .collect();
+        infos.sort_by_key(|info| info.session_id);
+        infos
+    }
+
+    /// 会话的只读连接池
+    pub fn pool(&self, session_id: i64) -> AppResult<DbPool> {
+        self.lock()
+            .get(&session_id)
+            .map(|session| session.pool.clone())
+            .ok_or(AppError::NotFound {
+                entity: "snapshot_session",
+                id: session_id,
+            })
+    }
+
+    /// 关闭会话并删除临时文件
+    pub async fn close(&self, session_id: i64) -> AppResult<()> {
+        let session = self.lock().remove(&session_id).ok_or(AppError::NotFound {
+            entity: "snapshot_session",
+            id: session_id,
+        })?;
+        session.pool.close().await;
+        if let Err(err) = fs::remove_dir_all(&session.dir) {
+            tracing::warn!(session_id, error = %err, "Failed to remove snapshot temp dir");
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,256 @@
+//! 捕获来源统计与按来源自动打标签
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::Serialize;
+
+use crate::db::{
+    attach_tag_to_node, get_or_create_tag, list_enabled_source_tag_rules, list_resource_sources,
+    DbPool, SourceMeta, SourceRuleField, SourceTagRuleRecord, TagSource,
+};
+
+/// 报表中每个维度最多返回的条目数
+const REPORT_TOP_N: usize = 50;
+
+/// 单个来源的统计
+#[derive(Debug, Serialize)]
+pub struct SourceCount {
+    pub name: String,
+    pub count: i64,
+    /// RFC3339
+    pub last_captured_at: Option<String>,
+}
+
+/// 捕获来源报表
+#[derive(Debug, Serialize)]
+pub struct CaptureSourcesReport {
+    pub total: i64,
+    pub domains: Vec<SourceCount>,
+    pub apps: Vec<SourceCount>,
+    pub window_titles: Vec<SourceCount>,
+}
+
+/// 从 URL 中提取域名（去掉 `www.` 前缀，统一小写）
+pub fn extract_domain(url: &str) -> Option<String> {
+    let parsed = reqwest::Url::parse(url.trim()).ok()?;
+    let host = parsed.host_str()?.to_lowercase();
+    let host = host.strip_prefix("www.").unwrap_or(&host);
+    if host.is_empty() {
+        None
+    } else {
+        Some(host.to_string())
+    }
+}
+
+/// 判断规则是否命中来源元数据
+pub fn rule_matches(field: SourceRuleField, pattern: &str, meta: &SourceMeta) -> bool {
+    let pattern = pattern.trim().to_lowercase();
+    if pattern.is_empty() {
+        return false;
+    }
+
+    match field {
+        SourceRuleField::Domain => meta
+            .url
+            .as_deref()
+            .and_then(extract_domain)
+            .map(|domain| domain == pattern || domain.ends_with(&format!(".{pattern}")))
+            .unwrap_or(false),
+        SourceRuleField::App => meta
+            .process_name
+            .as_deref()
+            .map(|name| name.trim().to_lowercase() == pattern)
+            .unwrap_or(false),
+        SourceRuleField::WindowTitle => meta
+            .window_title
+            .as_deref()
+            .map(|title| title.to_lowercase().contains(&pattern))
+            .unwrap_or(false),
+    }
+}
+
+/// 根据已启用的来源规则给新捕获的资源打标签，返回命中的标签名
+pub async fn apply_source_tag_rules(
+    db: &DbPool,
+    node_id: i64,
+    meta: &SourceMeta,
+) -> Result<Vec<String>, String> {
+    let rules = list_enabled_source_tag_rules(db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut applied: Vec<String> = Vec::new();
+    for SourceTagRuleRecord {
+        match_field,
+        pattern,
+        tag_name,
+        ..
+    } in rules
+    {
+        if !rule_matches(match_field, &pattern, meta) {
+            continue;
+        }
+        if applied
+            .iter()
+            .any(|name| name.eq_ignore_ascii_case(&tag_name))
+        {
+            continue;
+        }
+
+        let tag_id = get_or_create_tag(db, &tag_name)
+            .await
+            .map_err(|e| e.to_string())?;
+        attach_tag_to_node(db, node_id, tag_id, TagSource::Rule)
+            .await
+            .map_err(|e| e.to_string())?;
+        applied.push(tag_name);
+    }
+
+    if !applied.is_empty() {
+        tracing::debug!(node_id, tags = ?applied, "Applied source tag rules");
+    }
+
+    Ok(applied)
+}
+
+/// 聚合所有资源的来源：按域名、应用、窗口标题统计
+pub async fn build_capture_sources_report(db: &DbPool) -> Result<CaptureSourcesReport, String> {
+    let rows = list_resource_sources(db).await.map_err(|e| e.to_string())?;
+
+    let mut domains = SourceCounter::default();
+    let mut apps = SourceCounter::default();
+    let mut window_titles = SourceCounter::default();
+    let mut total = 0;
+
+    for row in rows {
+        let Some(meta) = row.source_meta.map(|m| m.0) else {
+            continue;
+        };
+        total += 1;
+        let captured_at = meta.captured_at.clone().or(row.created_at);
+
+        if let Some(domain) = meta.url.as_deref().and_then(extract_domain) {
+            domains.add(domain, captured_at.as_deref());
+        }
+        if let Some(app) = meta
+            .process_name
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+        {
+            apps.add(app.to_string(), captured_at.as_deref());
+        }
+        if let Some(title) = meta
+            .window_title
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+        {
+            window_titles.add(title.to_string(), captured_at.as_deref());
+        }
+    }
+
+    Ok(CaptureSourcesReport {
+        total,
+        domains: domains.into_sorted(),
+        apps: apps.into_sorted(),
+        window_titles: window_titles.into_sorted(),
+    })
+}
+
+#[derive(Default)]
+struct SourceCounter {
+    entries: HashMap<String, (i64, Option<DateTime<Utc>>)>,
+}
+
+impl SourceCounter {
+    fn add(&mut self, name: String, captured_at: Option<&str>) {
+        let entry = self.entries.entry(name).or_insert((0, None));
+        entry.0 += 1;
+        if let Some(at) = captured_at.and_then(parse_captured_at) {
+            if entry.1.map_or(true, |last| at > last) {
+                entry.1 = Some(at);
+            }
+        }
+    }
+
+    fn into_sorted(self) -> Vec<SourceCount> {
+        let mut items: Vec<SourceCount> = self
+            .entries
+            .into_iter()
+            .map(|(name, (count, last_captured_at))| SourceCount {
+                name,
+                count,
+                last_captured_at: last_captured_at.map(|at| at.to_rfc3339()),
+            })
+            .collect();
+        items.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+        items.truncate(REPORT_TOP_N);
+        items
+    }
+}
+
+/// captured_at 为 RFC3339（带时区），回退的 created_at 为 SQLite 的 UTC `YYYY-MM-DD HH:MM:SS`
+fn parse_captured_at(raw: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(raw)
+        .map(|at| at.with_timezone(&Utc))
+        .or_else(|_| NaiveDateTime::parse_from_str(raw, "%Y-%m-%d %H:%M:%S").map(|at| at.and_utc()))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meta(url: Option<&str>, app: Option<&str>, title: Option<&str>) -> SourceMeta {
+        SourceMeta {
+            url: url.map(String::from),
+            window_title: title.map(String::from),
+            process_name: app.map(String::from),
+            captured_at: None,
+            process_path: None,
+            sender: None,
+            sent_at: None,
+            auto_title: None,
+        }
+    }
+
+    #[test]
+    fn test_extract_domain() {
+        assert_eq!(
+            extract_domain("https://www.arxiv.org/abs/2401.00001"),
+            Some("arxiv.org".to_string())
+        );
+        assert_eq!(extract_domain("not a url"), None);
+    }
+
+    #[test]
+    fn test_rule_matches() {
+        let m = meta(
+            Some("https://export.arxiv.org/pdf/1"),
+            Some("Zotero"),
+            Some("Paper - Reader"),
+        );
+        assert!(rule_matches(SourceRuleField::Domain, "arxiv.org", &m));
+        assert!(!rule_matches(SourceRuleField::Domain, "xiv.org", &m));
+        assert!(rule_matches(SourceRuleField::App, "zotero", &m));
+        assert!(rule_matches(SourceRuleField::WindowTitle, "reader", &m));
+        assert!(!rule_matches(SourceRuleField::App, "", &m));
+    }
+
+    #[test]
+    fn test_source_counter_compares_parsed_times() {
+        let mut counter = SourceCounter::default();
+        // 按字符串比较时 "2024-03-01T09:00:00+08:00" 会被当作更晚
+        counter.add("a".to_string(), Some("2024-03-01T09:00:00+08:00"));
+        counter.add("a".to_string(), Some("2024-03-01 10:00:00"));
+        counter.add("a".to_string(), Some("garbage"));
+        let items = counter.into_sorted();
+        assert_eq!(items[0].count, 3);
+        assert_eq!(
+            items[0].last_captured_at.as_deref(),
+            Some("2024-03-01T10:00:00+00:00")
+        );
+    }
+}
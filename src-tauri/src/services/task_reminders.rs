@@ -0,0 +1,74 @@
+//! 任务提醒
+//!
+//! 后台每分钟扫描设置了提醒的待办任务，在截止时间前 `minutes_before` 分钟
+//! 写入通知中心并弹出系统通知；稍后提醒的任务在 `snoozed_until` 到达时再次提醒。
+
+use std::time::Duration;
+
+use chrono::{Local, NaiveDateTime};
+use tauri::{AppHandle, Manager};
+
+use super::push_system_notification;
+use crate::app_state::AppState;
+use crate::db::{
+    list_due_task_reminders, mark_task_reminder_sent, DbPool, NewNotification, NotificationKind,
+};
+
+const SCHEDULER_INITIAL_DELAY: Duration = Duration::from_secs(20);
+const SCHEDULER_INTERVAL: Duration = Duration::from_secs(60);
+/// 超过该时长仍未发送的提醒视为错过（如应用未运行），不再补发
+const REMINDER_GRACE_MINUTES: i64 = 60;
+/// 未指定时长时稍后提醒的分钟数
+pub const DEFAULT_SNOOZE_MINUTES: i64 = 10;
+
+fn format_local(dt: NaiveDateTime) -> String {
+    dt.format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+/// 从现在起 minutes 分钟后的本地时间
+pub fn snooze_until(minutes: i64) -> String {
+    format_local(Local::now().naive_local() + chrono::Duration::minutes(minutes))
+}
+
+async fn send_due_task_reminders(db: &DbPool, app: &AppHandle) -> Result<(), String> {
+    let now = Local::now().naive_local();
+    let since = now - chrono::Duration::minutes(REMINDER_GRACE_MINUTES);
+    let reminders = list_due_task_reminders(db, &format_local(since), &format_local(now))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    for reminder in reminders {
+        let body = format!("截止时间：{}", reminder.due_date);
+        push_system_notification(
+            db,
+            app,
+            NewNotification {
+                kind: NotificationKind::System,
+                title: &format!("任务提醒：{}", reminder.title),
+                body: Some(&body),
+                node_id: Some(reminder.node_id),
+                action: Some("open_task"),
+            },
+        )
+        .await?;
+        mark_task_reminder_sent(db, reminder.node_id, &reminder.due_date)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// 后台定时发送到期的任务提醒
+pub fn spawn_task_reminder_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(SCHEDULER_INITIAL_DELAY).await;
+        loop {
+            if let Some(state) = app.try_state::<AppState>() {
+                if let Err(err) = send_due_task_reminders(&state.db, &app).await {
+                    tracing::warn!(error = %err, "Send task reminders failed");
+                }
+            }
+            tokio::time::sleep(SCHEDULER_INTERVAL).await;
+        }
+    });
+}
@@ -0,0 +1,212 @@
+//! 任务计时器
+//!
+//! 与番茄钟不同，计时器没有固定时长：开始时写入一条 `timer` 来源、未结束的 time_entries，
+//! 停止时补上结束时间与时长。同一时间只有一个计时器，开始新任务时会先结束并记录上一个。
+//! 应用退出时停止计时；崩溃后重启时由 [`TaskTimerService::restore`] 按未结束的记录恢复。
+//! 状态变化时发送 `task-timer`（HUD 据 started_at 自行显示走时）。
+
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::db::{
+    close_time_entry, delete_open_time_entry, get_node_by_id, get_time_entry_by_id,
+    insert_open_time_entry, list_open_time_entries, DbPool, NodeType, TimeEntryRecord,
+    TimeEntrySource,
+};
+use crate::error::{AppError, AppResult};
+use crate::i18n::MessageCode;
+use crate::services::emit_event;
+
+/// 不足 1 秒的计时不写入记录
+const MIN_ENTRY_SECONDS: i64 = 1;
+
+/// 当前计时器状态（事件 payload 与查询返回共用）
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskTimerState {
+    pub task_id: i64,
+    pub title: String,
+    pub started_at: String,
+    pub elapsed_seconds: i64,
+    pub is_running: bool,
+}
+
+struct RunningTimer {
+    /// 计时器开始时写入的未结束记录
+    entry_id: i64,
+    task_id: i64,
+    title: String,
+    started_at: DateTime<Utc>,
+}
+
+impl RunningTimer {
+    fn state(&self, now: DateTime<Utc>, is_running: bool) -> TaskTimerState {
+        TaskTimerState {
+            task_id: self.task_id,
+            title: self.title.clone(),
+            started_at: self.started_at.to_rfc3339(),
+            elapsed_seconds: elapsed_seconds(self.started_at, now),
+            is_running,
+        }
+    }
+}
+
+fn elapsed_seconds(started_at: DateTime<Utc>, now: DateTime<Utc>) -> i64 {
+    (now - started_at).num_seconds().max(0)
+}
+
+pub struct TaskTimerService {
+    db: DbPool,
+    app: AppHandle,
+    running: Mutex<Option<RunningTimer>>,
+}
+
+impl TaskTimerService {
+    pub fn new(db: DbPool, app: AppHandle) -> Self {
+        Self {
+            db,
+            app,
+            running: Mutex::new(None),
+        }
+    }
+
+    pub fn current(&self) -> Option<TaskTimerState> {
+        let guard = self.running.lock().ok()?;
+        guard.as_ref().map(|timer| timer.state(Utc::now(), true))
+    }
+
+    /// 开始为任务计时；已有计时器时先停止并记录
+    pub async fn start(&self, task_id: i64) -> AppResult<TaskTimerState> {
+        let node = get_node_by_id(&self.db, task_id).await?;
+        if node.node_type != NodeType::Task || node.is_deleted {
+            return Err(AppError::coded(MessageCode::TimerTaskOnly));
+        }
+
+        self.stop().await?;
+
+        let started_at = Utc::now();
+        let entry_id = insert_open_time_entry(
+            &self.db,
+            task_id,
+            TimeEntrySource::Timer,
+            &started_at.to_rfc3339(),
+        )
+        .await?;
+        let timer = RunningTimer {
+            entry_id,
+            task_id,
+            title: node.title,
+            started_at,
+        };
+        let state = timer.state(timer.started_at, true);
+        {
+            let mut guard = self.running.lock().map_err(|e| e.to_string())?;
+            *guard = Some(timer);
+        }
+
+        emit_event(&self.app, &state);
+        tracing::info!(task_id, "Task timer started");
+        Ok(state)
+    }
+
+    /// 停止计时并补全时间记录；没有计时器或计时不足 1 秒（记录被删除）时返回 None
+    pub async fn stop(&self) -> AppResult<Option<TimeEntryRecord>> {
+        let timer = {
+            let mut guard = self.running.lock().map_err(|e| e.to_string())?;
+            guard.take()
+        };
+        let Some(timer) = timer else {
+            return Ok(None);
+        };
+
+        let ended_at = Utc::now();
+        let state = timer.state(ended_at, false);
+        emit_event(&self.app, &state);
+        tracing::info!(
+            task_id = timer.task_id,
+            seconds = state.elapsed_seconds,
+            "Task timer stopped"
+        );
+
+        if state.elapsed_seconds < MIN_ENTRY_SECONDS {
+            delete_open_time_entry(&self.db, timer.entry_id).await?;
+            return Ok(None);
+        }
+
+        close_time_entry(
+            &self.db,
+            timer.entry_id,
+            &ended_at.to_rfc3339(),
+            state.elapsed_seconds,
+        )
+        .await?;
+
+        let entry = get_time_entry_by_id(&self.db, timer.entry_id).await?;
+        Ok(Some(entry))
+    }
+
+    /// 启动时恢复异常退出前仍在运行的计时器
+    ///
+    /// 最近开始的未结束记录继续计时（任务已删除时按当前时间结束）；更早的未结束记录
+    /// 结束于下一条记录开始的时刻，因为开始新计时器时会先停止上一个。
+    pub async fn restore(&self) -> AppResult<Option<TaskTimerState>> {
+        let open = list_open_time_entries(&self.db).await?;
+        let now = Utc::now();
+        let mut next_started_at = now;
+        let mut resumed = None;
+
+        for (index, row) in open.into_iter().enumerate() {
+            let Some(started_at) = DateTime::parse_from_rfc3339(&row.started_at)
+                .ok()
+                .map(|time| time.with_timezone(&Utc))
+            else {
+                tracing::warn!(
+                    entry_id = row.entry_id,
+                    "Open time entry has invalid started_at"
+                );
+                continue;
+            };
+
+            let live_task = match row.task_id {
+                Some(task_id) if index == 0 => match get_node_by_id(&self.db, task_id).await {
+                    Ok(node) if node.node_type == NodeType::Task && !node.is_deleted => Some(node),
+                    Ok(_) | Err(sqlx::Error::RowNotFound) => None,
+                    Err(err) => return Err(err.into()),
+                },
+                _ => None,
+            };
+            if let Some(node) = live_task {
+                resumed = Some(RunningTimer {
+                    entry_id: row.entry_id,
+                    task_id: node.node_id,
+                    title: node.title,
+                    started_at,
+                });
+            } else {
+                let seconds = elapsed_seconds(started_at, next_started_at);
+                let result = if seconds < MIN_ENTRY_SECONDS {
+                    delete_open_time_entry(&self.db, row.entry_id).await
+                } else {
+                    let ended_at = next_started_at.to_rfc3339();
+                    close_time_entry(&self.db, row.entry_id, &ended_at, seconds).await
+                };
+                result?;
+            }
+            next_started_at = started_at;
+        }
+
+        let Some(timer) = resumed else {
+            return Ok(None);
+        };
+        let state = timer.state(now, true);
+        {
+            let mut guard = self.running.lock().map_err(|e| e.to_string())?;
+            *guard = Some(timer);
+        }
+        emit_event(&self.app, &state);
+        tracing::info!(task_id = state.task_id, "Task timer restored after restart");
+        Ok(Some(state))
+    }
+}
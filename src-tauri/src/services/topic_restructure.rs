@@ -0,0 +1,398 @@
+//! 主题层级整理
+//!
+//! 把整棵主题树交给 LLM，生成重命名 / 移动 / 合并建议。建议先经过校验
+//! （id 必须存在、不能成环、标题不能冲突），再附上可读的差异描述返回给前端；
+//! 用户勾选后由 `apply_topic_operations` 在单个事务中应用。
+
+use std::collections::{HashMap, HashSet};
+
+use serde::Serialize;
+
+use crate::app_state::AppState;
+use crate::db::{list_topic_links, list_topic_tree, TopicLinkRow, TopicOperation, TopicTreeRow};
+use crate::services::{get_processing_config, TopicOperationDraft};
+
+/// 主题树中摘要的最大展示字符数
+const TREE_SUMMARY_MAX_CHARS: usize = 60;
+
+/// 校验后的单条调整建议
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedTopicOperation {
+    #[serde(flatten)]
+    pub operation: TopicOperation,
+    pub reason: Option<String>,
+    /// 差异描述，例如「重命名「A」→「B」」
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TopicRestructurePlan {
+    pub operations: Vec<PlannedTopicOperation>,
+    /// 被校验丢弃的建议数
+    pub discarded: usize,
+}
+
+/// 分析主题树并生成调整建议（不修改数据）
+pub async fn plan_topic_restructure(state: &AppState) -> Result<TopicRestructurePlan, String> {
+    let topics = list_topic_tree(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+    if topics.len() < 2 {
+        return Ok(TopicRestructurePlan {
+            operations: Vec::new(),
+            discarded: 0,
+        });
+    }
+    let links = list_topic_links(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let ai = state.ai.wait_ready().await?;
+    let (provider, model, _, provider_config) = get_processing_config(&state.ai_config).await?;
+    let tree = render_topic_tree(&topics, &links);
+    let drafts = ai
+        .agent
+        .plan_topic_restructure(&provider, &model, &provider_config, &tree)
+        .await?;
+
+    let total = drafts.len();
+    let operations = validate_topic_operations(drafts, &topics, &links);
+    Ok(TopicRestructurePlan {
+        discarded: total - operations.len(),
+        operations,
+    })
+}
+
+/// 渲染缩进形式的主题树，供 prompt 使用
+fn render_topic_tree(topics: &[TopicTreeRow], links: &[TopicLinkRow]) -> String {
+    let known: HashSet<i64> = topics.iter().map(|topic| topic.node_id).collect();
+    let mut children: HashMap<i64, Vec<&TopicTreeRow>> = HashMap::new();
+    let mut has_parent = HashSet::new();
+    let by_id: HashMap<i64, &TopicTreeRow> =
+        topics.iter().map(|topic| (topic.node_id, topic)).collect();
+    for link in links {
+        if let (true, Some(child)) = (known.contains(&link.parent_id), by_id.get(&link.child_id)) {
+            children.entry(link.parent_id).or_default().push(child);
+            has_parent.insert(link.child_id);
+        }
+    }
+
+    let mut lines = Vec::new();
+    let mut visited = HashSet::new();
+    let roots = topics
+        .iter()
+        .filter(|topic| !has_parent.contains(&topic.node_id));
+    for root in roots {
+        render_subtree(root, 0, &children, &mut visited, &mut lines);
+    }
+    // 只存在于环中的主题没有根，单独列出
+    for topic in topics {
+        render_subtree(topic, 0, &children, &mut visited, &mut lines);
+    }
+    lines.join("\n")
+}
+
+fn render_subtree(
+    topic: &TopicTreeRow,
+    depth: usize,
+    children: &HashMap<i64, Vec<&TopicTreeRow>>,
+    visited: &mut HashSet<i64>,
+    lines: &mut Vec<String>,
+) {
+    if !visited.insert(topic.node_id) {
+        return;
+    }
+
+    let mut line = format!(
+        "{}- {} (id={}, {} 条)",
+        "  ".repeat(depth),
+        topic.title,
+        topic.node_id,
+        topic.item_count
+    );
+    if let Some(summary) = topic
+        .summary
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+    {
+        let summary: String = summary.chars().take(TREE_SUMMARY_MAX_CHARS).collect();
+        line.push_str(&format!("：{summary}"));
+    }
+    lines.push(line);
+
+    for child in children.get(&topic.node_id).into_iter().flatten() {
+        render_subtree(child, depth + 1, children, visited, lines);
+    }
+}
+
+/// 校验 LLM 建议并生成差异描述
+///
+/// 建议按顺序模拟应用，后面的建议基于前面的结果校验；丢弃：引用不存在或已被
+/// 合并掉的主题、会成环的移动 / 合并、与现有主题重名的重命名，以及无实际变化的操作。
+fn validate_topic_operations(
+    drafts: Vec<TopicOperationDraft>,
+    topics: &[TopicTreeRow],
+    links: &[TopicLinkRow],
+) -> Vec<PlannedTopicOperation> {
+    let mut titles: HashMap<i64, String> = topics
+        .iter()
+        .map(|topic| (topic.node_id, topic.title.clone()))
+        .collect();
+    let mut parents: HashMap<i64, Vec<i64>> = HashMap::new();
+    for link in links {
+        parents
+            .entry(link.child_id)
+            .or_default()
+            .push(link.parent_id);
+    }
+
+    let mut planned = Vec::new();
+    for draft in drafts {
+        let topic_id = draft.topic_id;
+        let Some(title) = titles.get(&topic_id).cloned() else {
+            continue;
+        };
+        let reason = draft
+            .reason
+            .as_deref()
+            .map(str::trim)
+            .filter(|reason| !reason.is_empty())
+            .map(str::to_string);
+
+        let (operation, description) = match draft.op.as_str() {
+            "rename" => {
+                let Some(new_title) = draft
+                    .new_title
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|new_title| !new_title.is_empty())
+                else {
+                    continue;
+                };
+                if titles.values().any(|existing| existing == new_title) {
+                    continue;
+                }
+                titles.insert(topic_id, new_title.to_string());
+                (
+                    TopicOperation::Rename {
+                        topic_id,
+                        new_title: new_title.to_string(),
+                    },
+                    format!("重命名「{title}」→「{new_title}」"),
+                )
+            }
+            "move" => {
+                let current = parents.get(&topic_id).cloned().unwrap_or_default();
+                let description = match draft.new_parent_id {
+                    Some(parent_id) => {
+                        let Some(parent_title) = titles.get(&parent_id) else {
+                            continue;
+                        };
+                        if current == [parent_id] || is_within(&parents, parent_id, topic_id) {
+                            continue;
+                        }
+                        format!("移动「{title}」到「{parent_title}」下")
+                    }
+                    None => {
+                        if current.is_empty() {
+                            continue;
+                        }
+                        format!("移动「{title}」到根层级")
+                    }
+                };
+                parents.insert(topic_id, draft.new_parent_id.into_iter().collect());
+                (
+                    TopicOperation::Move {
+                        topic_id,
+                        new_parent_id: draft.new_parent_id,
+                    },
+                    description,
+                )
+            }
+            "merge" => {
+                let Some(target_id) = draft.target_topic_id else {
+                    continue;
+                };
+                let Some(target_title) = titles.get(&target_id).cloned() else {
+                    continue;
+                };
+                if is_within(&parents, target_id, topic_id) {
+                    continue;
+                }
+                // 被合并主题的子节点改挂到目标主题下，随后移除被合并主题
+                titles.remove(&topic_id);
+                parents.remove(&topic_id);
+                for (child_id, child_parents) in parents.iter_mut() {
+                    if child_parents.contains(&topic_id) {
+                        child_parents.retain(|id| *id != topic_id);
+                        if *child_id != target_id && !child_parents.contains(&target_id) {
+                            child_parents.push(target_id);
+                        }
+                    }
+                }
+                (
+                    TopicOperation::Merge {
+                        topic_id,
+                        target_topic_id: target_id,
+                    },
+                    format!("合并「{title}」到「{target_title}」"),
+                )
+            }
+            _ => continue,
+        };
+
+        planned.push(PlannedTopicOperation {
+            operation,
+            reason,
+            description,
+        });
+    }
+    planned
+}
+
+/// node 是否等于 ancestor 或位于其子树中
+fn is_within(parents: &HashMap<i64, Vec<i64>>, node: i64, ancestor: i64) -> bool {
+    let mut stack = vec![node];
+    let mut seen = HashSet::new();
+    while let Some(current) = stack.pop() {
+        if current == ancestor {
+            return true;
+        }
+        if seen.insert(current) {
+            stack.extend(parents.get(&current).into_iter().flatten().copied());
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn topic(node_id: i64, title: &str, item_count: i64) -> TopicTreeRow {
+        TopicTreeRow {
+            node_id,
+            title: title.to_string(),
+            summary: None,
+            item_count,
+        }
+    }
+
+    fn link(parent_id: i64, child_id: i64) -> TopicLinkRow {
+        TopicLinkRow {
+            parent_id,
+            child_id,
+        }
+    }
+
+    fn draft(op: &str, topic_id: i64) -> TopicOperationDraft {
+        TopicOperationDraft {
+            op: op.to_string(),
+            topic_id,
+            new_title: None,
+            new_parent_id: None,
+            target_topic_id: None,
+            reason: Some("理由".to_string()),
+        }
+    }
+
+    fn sample() -> (Vec<TopicTreeRow>, Vec<TopicLinkRow>) {
+        let topics = vec![
+            topic(1, "编程", 0),
+            topic(2, "Rust", 5),
+            topic(3, "rust 笔记", 2),
+            topic(4, "异步", 3),
+        ];
+        let links = vec![link(1, 2), link(2, 4)];
+        (topics, links)
+    }
+
+    #[test]
+    fn test_render_topic_tree_indents_children() {
+        let (topics, links) = sample();
+        let tree = render_topic_tree(&topics, &links);
+        assert_eq!(
+            tree,
+            "- 编程 (id=1, 0 条)\n  - Rust (id=2, 5 条)\n    - 异步 (id=4, 3 条)\n- rust 笔记 (id=3, 2 条)"
+        );
+    }
+
+    #[test]
+    fn test_validate_keeps_valid_operations() {
+        let (topics, links) = sample();
+        let mut rename = draft("rename", 3);
+        rename.new_title = Some("Rust 笔记".to_string());
+        let mut merge = draft("merge", 3);
+        merge.target_topic_id = Some(2);
+        let mut move_root = draft("move", 4);
+        move_root.new_parent_id = None;
+
+        let planned = validate_topic_operations(vec![rename, merge, move_root], &topics, &links);
+        let descriptions: Vec<&str> = planned.iter().map(|p| p.description.as_str()).collect();
+        assert_eq!(
+            descriptions,
+            vec![
+                "重命名「rust 笔记」→「Rust 笔记」",
+                "合并「Rust 笔记」到「Rust」",
+                "移动「异步」到根层级",
+            ]
+        );
+        assert_eq!(planned[0].reason.as_deref(), Some("理由"));
+    }
+
+    #[test]
+    fn test_validate_drops_invalid_operations() {
+        let (topics, links) = sample();
+        let mut unknown = draft("rename", 99);
+        unknown.new_title = Some("x".to_string());
+        let mut duplicate_title = draft("rename", 3);
+        duplicate_title.new_title = Some("Rust".to_string());
+        let mut cycle = draft("move", 1);
+        cycle.new_parent_id = Some(4);
+        let mut noop = draft("move", 2);
+        noop.new_parent_id = Some(1);
+        let mut self_merge = draft("merge", 2);
+        self_merge.target_topic_id = Some(2);
+        let mut merge_into_child = draft("merge", 2);
+        merge_into_child.target_topic_id = Some(4);
+        let unknown_op = draft("delete", 3);
+
+        let planned = validate_topic_operations(
+            vec![
+                unknown,
+                duplicate_title,
+                cycle,
+                noop,
+                self_merge,
+                merge_into_child,
+                unknown_op,
+            ],
+            &topics,
+            &links,
+        );
+        assert!(planned.is_empty());
+    }
+
+    #[test]
+    fn test_validate_skips_operations_on_merged_topic() {
+        let (topics, links) = sample();
+        let mut merge = draft("merge", 3);
+        merge.target_topic_id = Some(2);
+        let mut rename = draft("rename", 3);
+        rename.new_title = Some("新名字".to_string());
+        // 合并后 4 仍在 2 之下，把 2 移到 4 下会成环
+        let mut cycle = draft("move", 2);
+        cycle.new_parent_id = Some(4);
+
+        let planned = validate_topic_operations(vec![merge, rename, cycle], &topics, &links);
+        assert_eq!(planned.len(), 1);
+        assert_eq!(
+            planned[0].operation,
+            TopicOperation::Merge {
+                topic_id: 3,
+                target_topic_id: 2
+            }
+        );
+    }
+}
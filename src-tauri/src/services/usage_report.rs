@@ -0,0 +1,194 @@
+//! LLM 用量记录与费用报告
+//!
+//! 对话与 AI 处理的每次调用按 provider / model / 用途写入 `usage_log`；
+//! 报告按天、模型、用途汇总，费用按配置的价格（其次内置价格表）估算，本地模型不计费。
+
+use serde::Serialize;
+
+use super::{model_pricing, ChatUsage, ModelPrice};
+use crate::db::{insert_usage_log, summarize_usage, DbPool, NewUsageLog, UsageOperation};
+
+/// 写入一次调用的用量；失败只记录日志，不影响调用本身
+pub async fn record_usage(
+    db: &DbPool,
+    provider: &str,
+    model: &str,
+    operation: UsageOperation,
+    usage: &ChatUsage,
+    session_id: Option<i64>,
+) {
+    let result = insert_usage_log(
+        db,
+        NewUsageLog {
+            provider,
+            model,
+            operation,
+            input_tokens: usage.input_tokens,
+            output_tokens: usage.output_tokens,
+            reasoning_tokens: usage.reasoning_tokens,
+            total_tokens: usage.total_tokens,
+            session_id,
+        },
+    )
+    .await;
+    if let Err(err) = result {
+        tracing::warn!(provider, model, ?operation, error = %err, "Failed to record LLM usage");
+    }
+}
+
+/// 报告中的一行：某天某模型某用途的用量
+#[derive(Debug, Serialize)]
+pub struct UsageReportRow {
+    pub day: String,
+    pub provider: String,
+    pub model: String,
+    pub operation: UsageOperation,
+    pub calls: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub reasoning_tokens: i64,
+    pub total_tokens: i64,
+    /// 价格未知时为 None
+    pub estimated_cost_usd: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UsageReport {
+    pub from: String,
+    pub to: String,
+    pub total_calls: i64,
+    pub total_tokens: i64,
+    /// 只包含价格已知的部分
+    pub total_cost_usd: f64,
+    /// 没有价格、未计入费用的模型
+    pub unpriced_models: Vec<String>,
+    pub rows: Vec<UsageReportRow>,
+}
+
+/// 查询模型价格（输入, 输出）：本地模型为 0，其次配置的价格，最后内置价格表
+pub fn resolve_model_price(
+    provider: &str,
+    model: &str,
+    prices: &[ModelPrice],
+) -> Option<(f64, f64)> {
+    if provider.eq_ignore_ascii_case("ollama") {
+        return Some((0.0, 0.0));
+    }
+    let normalized = model.trim().to_lowercase();
+    let normalized = normalized.strip_prefix("models/").unwrap_or(&normalized);
+    prices
+        .iter()
+        .filter(|price| normalized.starts_with(&price.model_prefix.trim().to_lowercase()))
+        .max_by_key(|price| price.model_prefix.trim().len())
+        .map(|price| (price.input_per_million, price.output_per_million))
+        .or_else(|| model_pricing(model))
+}
+
+/// 按百万 token 价格估算费用；思考 token 按输出价格计费
+pub fn estimate_usage_cost(
+    input_tokens: i64,
+    output_tokens: i64,
+    reasoning_tokens: i64,
+    (input_price, output_price): (f64, f64),
+) -> f64 {
+    (input_tokens as f64 * input_price + (output_tokens + reasoning_tokens) as f64 * output_price)
+        / 1_000_000.0
+}
+
+/// 汇总 [from, to]（本地日期，含两端）之间的用量
+pub async fn build_usage_report(
+    db: &DbPool,
+    prices: &[ModelPrice],
+    from: &str,
+    to: &str,
+) -> Result<UsageReport, sqlx::Error> {
+    let summary = summarize_usage(db, from, to).await?;
+
+    let mut report = UsageReport {
+        from: from.to_string(),
+        to: to.to_string(),
+        total_calls: 0,
+        total_tokens: 0,
+        total_cost_usd: 0.0,
+        unpriced_models: Vec::new(),
+        rows: Vec::with_capacity(summary.len()),
+    };
+    for row in summary {
+        let estimated_cost_usd =
+            resolve_model_price(&row.provider, &row.model, prices).map(|price| {
+                estimate_usage_cost(
+                    row.input_tokens,
+                    row.output_tokens,
+                    row.reasoning_tokens,
+                    price,
+                )
+            });
+        match estimated_cost_usd {
+            Some(cost) => report.total_cost_usd += cost,
+            None if !report.unpriced_models.contains(&row.model) => {
+                report.unpriced_models.push(row.model.clone())
+            }
+            None => {}
+        }
+        report.total_calls += row.calls;
+        report.total_tokens += row.total_tokens;
+        report.rows.push(UsageReportRow {
+            day: row.day,
+            provider: row.provider,
+            model: row.model,
+            operation: row.operation,
+            calls: row.calls,
+            input_tokens: row.input_tokens,
+            output_tokens: row.output_tokens,
+            reasoning_tokens: row.reasoning_tokens,
+            total_tokens: row.total_tokens,
+            estimated_cost_usd,
+        });
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn price(prefix: &str, input: f64, output: f64) -> ModelPrice {
+        ModelPrice {
+            model_prefix: prefix.to_string(),
+            input_per_million: input,
+            output_per_million: output,
+        }
+    }
+
+    #[test]
+    fn test_resolve_model_price() {
+        let prices = vec![
+            price("gemini-2.5", 1.0, 2.0),
+            price("gemini-2.5-pro", 3.0, 4.0),
+        ];
+        assert_eq!(
+            resolve_model_price("gemini", "gemini-2.5-pro-preview", &prices),
+            Some((3.0, 4.0))
+        );
+        assert_eq!(
+            resolve_model_price("gemini", "models/gemini-2.5-flash", &prices),
+            Some((1.0, 2.0))
+        );
+        assert_eq!(
+            resolve_model_price("gemini", "gemini-2.0-flash", &[]),
+            Some((0.10, 0.40))
+        );
+        assert_eq!(
+            resolve_model_price("ollama", "llama3", &[]),
+            Some((0.0, 0.0))
+        );
+        assert_eq!(resolve_model_price("gemini", "unknown-model", &[]), None);
+    }
+
+    #[test]
+    fn test_estimate_usage_cost() {
+        let cost = estimate_usage_cost(1_000_000, 500_000, 500_000, (0.3, 2.5));
+        assert!((cost - 2.8).abs() < 1e-9);
+        assert_eq!(estimate_usage_cost(0, 0, 0, (1.0, 1.0)), 0.0);
+    }
+}
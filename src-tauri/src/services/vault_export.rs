@@ -0,0 +1,733 @@
+//! 导出与定期自动导出
+//!
+//! 导出为 zip 文件，写入设置的文件夹：archive 为数据库快照加资源文件，可完整恢复；
+//! markdown 为每个节点一个 .md 文件加资源文件，front matter 带 id、标签与 Obsidian 风格的
+//! `[[链接]]`。增量导出只包含上次成功导出后有更新的节点及其文件（archive 的数据库快照始终完整）。
+//! 文件夹中只保留最近 keep 个导出文件。定期导出按设置的间隔检查，结果（成功或失败）写入通知中心。
+//! [`export_vault_folder`] 把整个库以同样的 Markdown 布局写成普通文件夹（另附 edges.jsonl），
+//! 便于迁移到 Obsidian 或做纯文件备份。
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use chrono::{Local, NaiveDateTime, Utc};
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::app_state::AppState;
+use crate::db::{
+    create_export_run, fail_interrupted_export_runs, finish_export_run, get_export_run,
+    get_latest_scheduled_export_run, get_latest_succeeded_export_run, list_all_edges,
+    list_all_node_tag_names, list_export_node_names, list_nodes_for_export, prune_export_runs,
+    snapshot_database, DbPool, EdgeRelationType, ExportFormat, ExportRunCounts, ExportRunRecord,
+    ExportTrigger, NewNotification, NodeRecord, NodeType, NotificationKind,
+};
+use crate::i18n::MessageCode;
+use crate::services::{build_edge_export, push_notification, ExportScheduleConfig, JobHandle};
+use crate::utils::{resolve_stored_path, ASSETS_PREFIX};
+use crate::{AppError, AppResult};
+
+const SCHEDULER_INITIAL_DELAY: Duration = Duration::from_secs(5 * 60);
+const SCHEDULER_INTERVAL: Duration = Duration::from_secs(30 * 60);
+/// archive 导出中数据库快照的文件名
+pub const ARCHIVE_DB_ENTRY: &str = "neuralvault.sqlite3";
+/// 导出文件名前缀，清理旧导出时只处理带此前缀的文件
+const EXPORT_FILE_PREFIX: &str = "neuralvault-export-";
+/// 文件夹导出的目录名前缀
+const VAULT_FOLDER_PREFIX: &str = "neuralvault-vault-";
+/// 保留的导出记录数
+const KEEP_RUNS: i64 = 50;
+/// Markdown 文件名中标题部分的最大字符数
+const MAX_FILE_TITLE_CHARS: usize = 60;
+
+/// 同一时间只允许一次导出
+static RUNNING: AtomicBool = AtomicBool::new(false);
+
+struct RunningGuard;
+
+impl RunningGuard {
+    fn acquire() -> Option<Self> {
+        RUNNING
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .ok()
+            .map(|_| Self)
+    }
+}
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        RUNNING.store(false, Ordering::Release);
+    }
+}
+
+/// zip 中的一项
+enum ExportEntry {
+    File { name: String, path: PathBuf },
+    Text { name: String, text: String },
+}
+
+/// 文件夹导出的结果
+#[derive(Debug, Serialize)]
+pub struct VaultFolderExport {
+    pub path: String,
+    pub node_count: usize,
+    /// 复制的资源文件数（缺失的文件跳过）
+    pub file_count: i64,
+    pub edge_count: usize,
+}
+
+/// front matter 中的标签与关联，链接为 `[[文件名]]`
+#[derive(Debug, Default)]
+struct MarkdownMeta {
+    tags: Vec<String>,
+    /// 包含该节点的主题 / 任务
+    parents: Vec<String>,
+    children: Vec<String>,
+    related: Vec<String>,
+    derived_from: Vec<String>,
+}
+
+fn invalid_export_folder(detail: impl ToString) -> AppError {
+    AppError::coded_with(MessageCode::InvalidExportFolder, "detail", detail)
+}
+
+/// 按设置导出一次，返回导出记录；失败也会写入导出记录
+///
+/// 作为后台任务运行时按写入的 zip 条目汇报进度，取消后删除未写完的文件
+pub async fn run_export(
+    app: &AppHandle,
+    state: &AppState,
+    config: &ExportScheduleConfig,
+    trigger: ExportTrigger,
+    job: Option<&JobHandle>,
+) -> AppResult<ExportRunRecord> {
+    let _guard =
+        RunningGuard::acquire().ok_or_else(|| AppError::coded(MessageCode::ExportRunning))?;
+    let folder = config
+        .folder
+        .as_deref()
+        .map(str::trim)
+        .filter(|folder| !folder.is_empty())
+        .map(PathBuf::from)
+        .ok_or_else(|| invalid_export_folder("未设置导出文件夹"))?;
+    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+
+    let since = if config.incremental {
+        get_latest_succeeded_export_run(&state.db, config.format)
+            .await?
+            .map(|run| run.started_at)
+    } else {
+        None
+    };
+    let run_id = create_export_run(&state.db, trigger, config.format, since.as_deref()).await?;
+    let mut counts = ExportRunCounts::default();
+    let result = write_export(
+        state,
+        &app_data_dir,
+        &folder,
+        config.format,
+        since.as_deref(),
+        &mut counts,
+        job,
+    )
+    .await;
+    let file_path = result
+        .as_ref()
+        .ok()
+        .map(|path| path.to_string_lossy().to_string());
+    let error = result.as_ref().err().map(|err| err.to_string());
+    finish_export_run(
+        &state.db,
+        run_id,
+        file_path.as_deref(),
+        counts,
+        error.as_deref(),
+    )
+    .await?;
+    result?;
+
+    if let Err(err) = prune_export_files(&folder, config.keep) {
+        tracing::warn!(folder = %folder.display(), error = %err, "Prune old exports failed");
+    }
+    if let Err(err) = prune_export_runs(&state.db, KEEP_RUNS).await {
+        tracing::warn!(error = %err, "Prune export runs failed");
+    }
+    tracing::info!(
+        run_id,
+        nodes = counts.node_count,
+        files = counts.file_count,
+        bytes = counts.size_bytes,
+        "Export finished"
+    );
+    get_export_run(&state.db, run_id)
+        .await?
+        .ok_or(AppError::NotFound {
+            entity: "export_run",
+            id: run_id,
+        })
+}
+
+async fn write_export(
+    state: &AppState,
+    app_data_dir: &Path,
+    folder: &Path,
+    format: ExportFormat,
+    since: Option<&str>,
+    counts: &mut ExportRunCounts,
+    job: Option<&JobHandle>,
+) -> AppResult<PathBuf> {
+    if !folder.is_dir() {
+        return Err(invalid_export_folder(format!(
+            "{} 不是文件夹",
+            folder.display()
+        )));
+    }
+    let nodes = list_nodes_for_export(&state.db, since).await?;
+    counts.node_count = nodes.len() as i64;
+
+    let file_name = export_file_name(
+        &Local::now().format("%Y%m%d-%H%M%S").to_string(),
+        format,
+        since.is_some(),
+    );
+    let path = folder.join(&file_name);
+
+    let mut entries = Vec::new();
+    let snapshot = match format {
+        ExportFormat::Archive => {
+            let snapshot = folder.join(format!(".{file_name}.sqlite3.tmp"));
+            if snapshot.exists() {
+                fs::remove_file(&snapshot)?;
+            }
+            snapshot_database(&state.db, &snapshot.to_string_lossy()).await?;
+            entries.push(ExportEntry::File {
+                name: ARCHIVE_DB_ENTRY.to_string(),
+                path: snapshot.clone(),
+            });
+            Some(snapshot)
+        }
+        ExportFormat::Markdown => None,
+    };
+    let mut meta = match format {
+        ExportFormat::Markdown => load_markdown_meta(&state.db).await?,
+        ExportFormat::Archive => HashMap::new(),
+    };
+    for node in &nodes {
+        if let Some(file_path) = node
+            .file_path
+            .as_deref()
+            .filter(|file_path| file_path.starts_with(ASSETS_PREFIX))
+        {
+            entries.push(ExportEntry::File {
+                name: file_path.to_string(),
+                path: resolve_stored_path(app_data_dir, file_path),
+            });
+        }
+        if format == ExportFormat::Markdown {
+            entries.push(ExportEntry::Text {
+                name: format!("notes/{}", markdown_file_name(node)),
+                text: render_node_markdown(node, &meta.remove(&node.node_id).unwrap_or_default()),
+            });
+        }
+    }
+
+    let target = path.clone();
+    let job = job.cloned();
+    let result =
+        tauri::async_runtime::spawn_blocking(move || write_zip(&target, entries, job.as_ref()))
+            .await
+            .map_err(|e| AppError::from(e.to_string()))
+            .and_then(|written| written);
+    if let Some(snapshot) = snapshot {
+        let _ = fs::remove_file(snapshot);
+    }
+    match result {
+        Ok(file_count) => {
+            counts.file_count = file_count;
+            counts.size_bytes = fs::metadata(&path)?.len() as i64;
+            Ok(path)
+        }
+        Err(err) => {
+            let _ = fs::remove_file(&path);
+            Err(err)
+        }
+    }
+}
+
+/// 把整个库导出为文件夹 `neuralvault-vault-<时间>`：notes/ 下每个节点一个 .md 文件，
+/// assets/ 下为资源文件，edges.jsonl 为全部关联（可用边导入恢复）
+///
+/// 与 zip 导出共用同一把锁；取消或失败时删除写了一半的文件夹
+pub async fn export_vault_folder(
+    app: &AppHandle,
+    state: &AppState,
+    folder: &Path,
+    job: Option<&JobHandle>,
+) -> AppResult<VaultFolderExport> {
+    let _guard =
+        RunningGuard::acquire().ok_or_else(|| AppError::coded(MessageCode::ExportRunning))?;
+    if !folder.is_dir() {
+        return Err(invalid_export_folder(format!(
+            "{} 不是文件夹",
+            folder.display()
+        )));
+    }
+    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let root = folder.join(format!(
+        "{VAULT_FOLDER_PREFIX}{}",
+        Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    if root.exists() {
+        return Err(invalid_export_folder(format!("{} 已存在", root.display())));
+    }
+
+    let nodes = list_nodes_for_export(&state.db, None).await?;
+    let mut meta = load_markdown_meta(&state.db).await?;
+    let (edges, edge_count) = build_edge_export(
+        &state.db,
+        &[
+            EdgeRelationType::Contains,
+            EdgeRelationType::RelatedTo,
+            EdgeRelationType::DerivedFrom,
+        ],
+        false,
+        &[],
+    )
+    .await?;
+
+    let mut entries = vec![ExportEntry::Text {
+        name: "edges.jsonl".to_string(),
+        text: edges,
+    }];
+    for node in &nodes {
+        if let Some(file_path) = node
+            .file_path
+            .as_deref()
+            .filter(|file_path| file_path.starts_with(ASSETS_PREFIX))
+        {
+            entries.push(ExportEntry::File {
+                name: file_path.to_string(),
+                path: resolve_stored_path(&app_data_dir, file_path),
+            });
+        }
+        entries.push(ExportEntry::Text {
+            name: format!("notes/{}", markdown_file_name(node)),
+            text: render_node_markdown(node, &meta.remove(&node.node_id).unwrap_or_default()),
+        });
+    }
+
+    let target = root.clone();
+    let job = job.cloned();
+    let result =
+        tauri::async_runtime::spawn_blocking(move || write_folder(&target, entries, job.as_ref()))
+            .await
+            .map_err(|e| AppError::from(e.to_string()))
+            .and_then(|written| written);
+    match result {
+        Ok(file_count) => {
+            tracing::info!(
+                path = %root.display(),
+                nodes = nodes.len(),
+                files = file_count,
+                edges = edge_count,
+                "Vault folder export finished"
+            );
+            Ok(VaultFolderExport {
+                path: root.to_string_lossy().to_string(),
+                node_count: nodes.len(),
+                file_count,
+                edge_count,
+            })
+        }
+        Err(err) => {
+            let _ = fs::remove_dir_all(&root);
+            Err(err)
+        }
+    }
+}
+
+/// 按节点收集标签与关联；链接目标限于会导出的节点（主题、资源、任务）
+async fn load_markdown_meta(db: &DbPool) -> AppResult<HashMap<i64, MarkdownMeta>> {
+    let links: HashMap<i64, String> = list_export_node_names(db)
+        .await?
+        .into_iter()
+        .map(|(node_id, uuid, title)| (node_id, markdown_link(&title, &uuid)))
+        .collect();
+    let mut meta: HashMap<i64, MarkdownMeta> = HashMap::new();
+    for (node_id, tag) in list_all_node_tag_names(db).await? {
+        meta.entry(node_id).or_default().tags.push(tag);
+    }
+    for edge in list_all_edges(db).await? {
+        let (Some(source), Some(target)) = (
+            links.get(&edge.source_node_id),
+            links.get(&edge.target_node_id),
+        ) else {
+            continue;
+        };
+        match edge.relation_type {
+            EdgeRelationType::Contains => {
+                let (source, target) = (source.clone(), target.clone());
+                meta.entry(edge.target_node_id)
+                    .or_default()
+                    .parents
+                    .push(source);
+                meta.entry(edge.source_node_id)
+                    .or_default()
+                    .children
+                    .push(target);
+            }
+            EdgeRelationType::RelatedTo => {
+                let (source, target) = (source.clone(), target.clone());
+                meta.entry(edge.source_node_id)
+                    .or_default()
+                    .related
+                    .push(target);
+                meta.entry(edge.target_node_id)
+                    .or_default()
+                    .related
+                    .push(source);
+            }
+            EdgeRelationType::DerivedFrom => {
+                let target = target.clone();
+                meta.entry(edge.source_node_id)
+                    .or_default()
+                    .derived_from
+                    .push(target);
+            }
+        }
+    }
+    Ok(meta)
+}
+
+/// 写入文件夹，返回复制的文件数（不含 Markdown 文本）；缺失的资源文件跳过
+fn write_folder(root: &Path, entries: Vec<ExportEntry>, job: Option<&JobHandle>) -> AppResult<i64> {
+    fs::create_dir_all(root)?;
+    let mut file_count = 0;
+    let total = entries.len();
+    for (index, entry) in entries.into_iter().enumerate() {
+        if let Some(job) = job {
+            job.check_cancelled()?;
+            job.progress(index, Some(total));
+        }
+        match entry {
+            ExportEntry::File { name, path } => {
+                if !path.is_file() {
+                    tracing::warn!(path = %path.display(), "Export skipped missing file");
+                    continue;
+                }
+                let target = root.join(name);
+                if let Some(parent) = target.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::copy(&path, &target)?;
+                file_count += 1;
+            }
+            ExportEntry::Text { name, text } => {
+                let target = root.join(name);
+                if let Some(parent) = target.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(target, text)?;
+            }
+        }
+    }
+    if let Some(job) = job {
+        job.progress(total, Some(total));
+    }
+    Ok(file_count)
+}
+
+/// 写入 zip，返回写入的文件数（不含 Markdown 文本）；缺失的资源文件跳过
+fn write_zip(path: &Path, entries: Vec<ExportEntry>, job: Option<&JobHandle>) -> AppResult<i64> {
+    let mut zip = ZipWriter::new(File::create(path)?);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut file_count = 0;
+    let total = entries.len();
+    for (index, entry) in entries.into_iter().enumerate() {
+        if let Some(job) = job {
+            job.check_cancelled()?;
+            job.progress(index, Some(total));
+        }
+        match entry {
+            ExportEntry::File { name, path } => {
+                let mut file = match File::open(&path) {
+                    Ok(file) => file,
+                    Err(err) => {
+                        tracing::warn!(path = %path.display(), error = %err, "Export skipped missing file");
+                        continue;
+                    }
+                };
+                zip.start_file(name, options).map_err(io::Error::from)?;
+                io::copy(&mut file, &mut zip)?;
+                file_count += 1;
+            }
+            ExportEntry::Text { name, text } => {
+                zip.start_file(name, options).map_err(io::Error::from)?;
+                zip.write_all(text.as_bytes())?;
+            }
+        }
+    }
+    zip.finish().map_err(io::Error::from)?;
+    if let Some(job) = job {
+        job.progress(total, Some(total));
+    }
+    Ok(file_count)
+}
+
+fn format_label(format: ExportFormat) -> &'static str {
+    match format {
+        ExportFormat::Archive => "archive",
+        ExportFormat::Markdown => "markdown",
+    }
+}
+
+fn export_file_name(timestamp: &str, format: ExportFormat, incremental: bool) -> String {
+    let scope = if incremental { "incremental" } else { "full" };
+    format!(
+        "{EXPORT_FILE_PREFIX}{timestamp}-{}-{scope}.zip",
+        format_label(format)
+    )
+}
+
+/// 按文件名（含时间戳）排序，保留最新的 keep 个，返回应删除的文件名
+fn select_stale_exports(mut names: Vec<String>, keep: u32) -> Vec<String> {
+    names.retain(|name| name.starts_with(EXPORT_FILE_PREFIX) && name.ends_with(".zip"));
+    names.sort_unstable_by(|a, b| b.cmp(a));
+    names.into_iter().skip(keep.max(1) as usize).collect()
+}
+
+fn prune_export_files(folder: &Path, keep: u32) -> io::Result<()> {
+    let names = fs::read_dir(folder)?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect();
+    for name in select_stale_exports(names, keep) {
+        fs::remove_file(folder.join(&name))?;
+        tracing::debug!(file = %name, "Old export removed");
+    }
+    Ok(())
+}
+
+/// 标题中的路径分隔符等非法字符替换为 `_`，并附加 uuid 前缀避免重名（不含扩展名）
+fn markdown_file_stem(title: &str, uuid: &str) -> String {
+    let title: String = title
+        .trim()
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .take(MAX_FILE_TITLE_CHARS)
+        .collect();
+    let title = title.trim_matches(|c: char| c == '.' || c.is_whitespace());
+    let title = if title.is_empty() { "untitled" } else { title };
+    let short_uuid: String = uuid.chars().take(8).collect();
+    format!("{title}-{short_uuid}")
+}
+
+fn markdown_file_name(node: &NodeRecord) -> String {
+    format!("{}.md", markdown_file_stem(&node.title, &node.uuid))
+}
+
+/// Obsidian 风格的内部链接
+fn markdown_link(title: &str, uuid: &str) -> String {
+    format!("[[{}]]", markdown_file_stem(title, uuid))
+}
+
+/// JSON 标量同时是合法的 YAML 标量，用于 front matter 的值
+fn yaml_value<T: Serialize + ?Sized>(value: &T) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+fn render_node_markdown(node: &NodeRecord, meta: &MarkdownMeta) -> String {
+    let mut lines = vec![
+        "---".to_string(),
+        format!("id: {}", node.node_id),
+        format!("uuid: {}", node.uuid),
+        format!("type: {}", yaml_value(&node.node_type)),
+        format!("title: {}", yaml_value(&node.title)),
+    ];
+    if node.node_type == NodeType::Task {
+        if let Some(status) = &node.task_status {
+            lines.push(format!("status: {}", yaml_value(status)));
+        }
+        if let Some(due_date) = &node.due_date {
+            lines.push(format!("due: {}", yaml_value(due_date)));
+        }
+    }
+    if let Some(created_at) = &node.created_at {
+        lines.push(format!("created: {}", yaml_value(created_at)));
+    }
+    if let Some(updated_at) = &node.updated_at {
+        lines.push(format!("updated: {}", yaml_value(updated_at)));
+    }
+    for (key, values) in [
+        ("tags", &meta.tags),
+        ("parents", &meta.parents),
+        ("children", &meta.children),
+        ("related", &meta.related),
+        ("derived_from", &meta.derived_from),
+    ] {
+        if !values.is_empty() {
+            lines.push(format!("{key}: {}", yaml_value(values)));
+        }
+    }
+    lines.push("---".to_string());
+    lines.push(String::new());
+    lines.push(format!("# {}", node.title.trim()));
+    lines.push(String::new());
+
+    if let Some(summary) = node.summary.as_deref().filter(|s| !s.trim().is_empty()) {
+        lines.push(format!("> {}", summary.trim()));
+        lines.push(String::new());
+    }
+    if let Some(file_path) = node
+        .file_path
+        .as_deref()
+        .filter(|file_path| file_path.starts_with(ASSETS_PREFIX))
+    {
+        lines.push(format!("[附件](../{file_path})"));
+        lines.push(String::new());
+    }
+    if let Some(content) = node
+        .file_content
+        .as_deref()
+        .filter(|s| !s.trim().is_empty())
+    {
+        lines.push(content.trim().to_string());
+        lines.push(String::new());
+    }
+    if let Some(note) = node.user_note.as_deref().filter(|s| !s.trim().is_empty()) {
+        lines.push("## 笔记".to_string());
+        lines.push(String::new());
+        lines.push(note.trim().to_string());
+    }
+    let mut text = lines.join("\n").trim_end().to_string();
+    text.push('\n');
+    text
+}
+
+/// 距上次定期导出是否已满设置的间隔；时间为 SQLite CURRENT_TIMESTAMP（UTC）
+fn is_export_due(last_started_at: Option<&str>, interval_hours: u32, now: NaiveDateTime) -> bool {
+    let Some(last) = last_started_at
+        .and_then(|value| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S").ok())
+    else {
+        return true;
+    };
+    now - last >= chrono::Duration::hours(i64::from(interval_hours.max(1)))
+}
+
+async fn run_scheduled_export(app: &AppHandle, state: &AppState) -> AppResult<()> {
+    let config = state.ai_config.lock().await.load()?.export_schedule;
+    if !config.enabled || config.folder.is_none() {
+        return Ok(());
+    }
+    let last = get_latest_scheduled_export_run(&state.db).await?;
+    let last_started = last.as_ref().map(|run| run.started_at.as_str());
+    if !is_export_due(last_started, config.interval_hours, Utc::now().naive_utc()) {
+        return Ok(());
+    }
+
+    let result = run_export(app, state, &config, ExportTrigger::Scheduled, None).await;
+    let (title, body) = match result {
+        Ok(run) => (
+            "自动导出完成",
+            format!(
+                "已导出 {} 个节点、{} 个文件到 {}",
+                run.node_count,
+                run.file_count,
+                run.file_path.unwrap_or_default()
+            ),
+        ),
+        // 手动导出正在进行，下次再检查
+        Err(AppError::Coded {
+            code: MessageCode::ExportRunning,
+            ..
+        }) => return Ok(()),
+        Err(err) => ("自动导出失败", err.to_string()),
+    };
+    push_notification(
+        &state.db,
+        app,
+        NewNotification {
+            kind: NotificationKind::Backup,
+            title,
+            body: Some(&body),
+            node_id: None,
+            action: Some("open_export_settings"),
+        },
+    )
+    .await?;
+    Ok(())
+}
+
+/// 定期检查是否需要自动导出；启动时把上次中断的导出标记为失败
+pub fn spawn_export_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        if let Some(state) = app.try_state::<AppState>() {
+            if let Err(err) = fail_interrupted_export_runs(&state.db).await {
+                tracing::warn!(error = %err, "Mark interrupted exports failed");
+            }
+        }
+        tokio::time::sleep(SCHEDULER_INITIAL_DELAY).await;
+        loop {
+            if let Some(state) = app.try_state::<AppState>() {
+                if let Err(err) = run_scheduled_export(&app, &state).await {
+                    tracing::warn!(error = %err, "Scheduled export failed");
+                }
+            }
+            tokio::time::sleep(SCHEDULER_INTERVAL).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn test_export_is_due_after_interval() {
+        let now = at("2025-01-15 12:00:00");
+        assert!(is_export_due(None, 24, now));
+        assert!(is_export_due(Some("not a date"), 24, now));
+        assert!(!is_export_due(Some("2025-01-15 01:00:00"), 24, now));
+        assert!(is_export_due(Some("2025-01-14 12:00:00"), 24, now));
+        assert!(is_export_due(Some("2025-01-15 11:00:00"), 0, now));
+    }
+
+    #[test]
+    fn test_markdown_file_stem_sanitizes_title() {
+        assert_eq!(
+            markdown_file_stem(" a/b: c? ", "0123456789abcdef"),
+            "a_b_ c_-01234567"
+        );
+        assert_eq!(markdown_file_stem("..", "0123456789"), "untitled-01234567");
+        assert_eq!(markdown_link("Rust", "abcdef0123"), "[[Rust-abcdef01]]");
+    }
+
+    #[test]
+    fn test_select_stale_exports_keeps_newest() {
+        let names = vec![
+            export_file_name("20250101-020000", ExportFormat::Archive, false),
+            export_file_name("20250103-020000", ExportFormat::Archive, true),
+            "notes.txt".to_string(),
+            export_file_name("20250102-020000", ExportFormat::Markdown, false),
+        ];
+        assert_eq!(
+            select_stale_exports(names.clone(), 2),
+            vec!["neuralvault-export-20250101-020000-archive-full.zip".to_string()]
+        );
+        assert_eq!(select_stale_exports(names, 0).len(), 2);
+    }
+}
@@ -0,0 +1,374 @@
+//! 统一搜索：合并 SQLite 全文检索与 LanceDB 混合检索
+//!
+//! 两路结果按 node_id 去重后用 RRF（Reciprocal Rank Fusion）合并排序，
+//! 再按节点类型、资源子类型、创建日期、所属主题、标签过滤，并去掉被排除检索的节点。
+//! AI 服务未就绪或向量检索失败时退化为纯关键词搜索。
+
+use std::collections::{HashMap, HashSet};
+
+use serde::Serialize;
+
+use super::AiServices;
+use crate::db::{
+    list_node_ids_with_all_tags, list_nodes_by_ids, list_search_excluded_node_ids,
+    list_target_nodes, parse_fulltext_terms, search_nodes_fulltext, DbPool, EdgeRelationType,
+    NodeRecord, NodeType, ResourceSubtype,
+};
+
+/// RRF 平滑常数
+const RRF_K: f64 = 60.0;
+/// 每路检索的候选数 = limit × 该倍数（过滤后仍能凑满结果）
+const CANDIDATE_FACTOR: i32 = 3;
+/// 片段最大字符数
+const SNIPPET_CHARS: usize = 160;
+
+/// 搜索过滤条件，日期为 `YYYY-MM-DD`（含首尾）
+#[derive(Debug, Default, Clone)]
+pub struct VaultSearchFilter {
+    pub node_type: Option<NodeType>,
+    pub resource_subtype: Option<ResourceSubtype>,
+    pub created_from: Option<String>,
+    pub created_to: Option<String>,
+    /// 仅搜索该主题直接包含的节点
+    pub topic_id: Option<i64>,
+    /// 仅搜索同时带有全部这些标签的节点
+    pub tag_ids: Vec<i64>,
+    /// 排除这些节点
+    pub exclude_node_ids: Vec<i64>,
+    /// 排除这些主题及其包含的节点
+    pub exclude_topic_ids: Vec<i64>,
+}
+
+/// 片段中的高亮区间，以 UTF-16 码元计（与 JS 字符串下标一致），左闭右开
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct HighlightRange {
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Snippet {
+    pub text: String,
+    pub highlights: Vec<HighlightRange>,
+}
+
+/// 单条命中
+#[derive(Debug)]
+pub struct VaultSearchHit {
+    pub node: NodeRecord,
+    pub score: f64,
+    pub snippet: Option<Snippet>,
+    pub keyword_match: bool,
+    pub semantic_match: bool,
+}
+
+#[derive(Default)]
+struct Candidate {
+    score: f64,
+    keyword_match: bool,
+    semantic_match: bool,
+    /// 向量检索命中的文本块
+    chunk_text: Option<String>,
+}
+
+pub async fn search_vault(
+    db: &DbPool,
+    ai: Option<&AiServices>,
+    query: &str,
+    filter: &VaultSearchFilter,
+    limit: usize,
+) -> Result<Vec<VaultSearchHit>, String> {
+    let terms = parse_fulltext_terms(query);
+    if terms.is_empty() {
+        return Ok(Vec::new());
+    }
+    let candidate_limit = (limit as i32).saturating_mul(CANDIDATE_FACTOR);
+
+    let mut scope: Option<HashSet<i64>> = match filter.topic_id {
+        Some(topic_id) => Some(
+            list_target_nodes(db, topic_id, EdgeRelationType::Contains)
+                .await
+                .map_err(|e| e.to_string())?
+                .into_iter()
+                .map(|node| node.node_id)
+                .collect(),
+        ),
+        None => None,
+    };
+    if !filter.tag_ids.is_empty() {
+        let tagged: HashSet<i64> = list_node_ids_with_all_tags(db, &filter.tag_ids)
+            .await
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .collect();
+        scope = Some(match scope {
+            Some(topic_nodes) => topic_nodes.intersection(&tagged).copied().collect(),
+            None => tagged,
+        });
+    }
+    let excluded: HashSet<i64> =
+        list_search_excluded_node_ids(db, &filter.exclude_node_ids, &filter.exclude_topic_ids)
+            .await
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .collect();
+    if let Some(scope) = scope.as_mut() {
+        scope.retain(|node_id| !excluded.contains(node_id));
+    }
+    if scope.as_ref().is_some_and(HashSet::is_empty) {
+        return Ok(Vec::new());
+    }
+
+    let mut candidates: HashMap<i64, Candidate> = HashMap::new();
+
+    let keyword_rows = search_nodes_fulltext(db, query, filter.node_type, candidate_limit)
+        .await
+        .map_err(|e| e.to_string())?;
+    for (rank, row) in keyword_rows.iter().enumerate() {
+        let candidate = candidates.entry(row.node_id).or_default();
+        candidate.score += rrf(rank);
+        candidate.keyword_match = true;
+    }
+
+    if let Some(ai) = ai {
+        let ids: Option<Vec<i64>> = scope.as_ref().map(|set| set.iter().copied().collect());
+        let excluded_ids: Vec<i64> = excluded.iter().copied().collect();
+        match ai
+            .search
+            .search_hybrid(
+                query,
+                "content",
+                ids.as_deref(),
+                &excluded_ids,
+                candidate_limit as u64,
+            )
+            .await
+        {
+            Ok(mut hits) => {
+                hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+                let mut seen = HashSet::new();
+                for hit in hits {
+                    if !seen.insert(hit.node_id) {
+                        continue;
+                    }
+                    let candidate = candidates.entry(hit.node_id).or_default();
+                    candidate.score += rrf(seen.len() - 1);
+                    candidate.semantic_match = true;
+                    candidate.chunk_text.get_or_insert(hit.chunk_text);
+                }
+            }
+            Err(err) => tracing::warn!(error = %err, "Vault search: vector search failed"),
+        }
+    }
+
+    let node_ids: Vec<i64> = candidates.keys().copied().collect();
+    let nodes = list_nodes_by_ids(db, &node_ids)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut hits: Vec<VaultSearchHit> = nodes
+        .into_iter()
+        .filter(|node| !excluded.contains(&node.node_id))
+        .filter(|node| matches_filter(node, filter, scope.as_ref()))
+        .filter_map(|node| {
+            let candidate = candidates.remove(&node.node_id)?;
+            let snippet = build_node_snippet(&node, candidate.chunk_text.as_deref(), &terms);
+            Some(VaultSearchHit {
+                node,
+                score: candidate.score,
+                snippet,
+                keyword_match: candidate.keyword_match,
+                semantic_match: candidate.semantic_match,
+            })
+        })
+        .collect();
+    hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+    hits.truncate(limit);
+    Ok(hits)
+}
+
+fn rrf(rank: usize) -> f64 {
+    1.0 / (RRF_K + rank as f64 + 1.0)
+}
+
+fn matches_filter(
+    node: &NodeRecord,
+    filter: &VaultSearchFilter,
+    scope: Option<&HashSet<i64>>,
+) -> bool {
+    if filter
+        .node_type
+        .is_some_and(|node_type| node.node_type != node_type)
+    {
+        return false;
+    }
+    if filter.resource_subtype.is_some() && node.resource_subtype != filter.resource_subtype {
+        return false;
+    }
+    if scope.is_some_and(|ids| !ids.contains(&node.node_id)) {
+        return false;
+    }
+    let created_date = node
+        .created_at
+        .as_deref()
+        .map(|created| &created[..created.len().min(10)]);
+    if let Some(from) = filter.created_from.as_deref() {
+        if created_date.is_none_or(|date| date < from) {
+            return false;
+        }
+    }
+    if let Some(to) = filter.created_to.as_deref() {
+        if created_date.is_none_or(|date| date > to) {
+            return false;
+        }
+    }
+    true
+}
+
+/// 优先用包含查询词的正文字段，其次用向量命中的文本块，最后用摘要
+fn build_node_snippet(
+    node: &NodeRecord,
+    chunk_text: Option<&str>,
+    terms: &[String],
+) -> Option<Snippet> {
+    let fields = [
+        node.file_content.as_deref(),
+        node.user_note.as_deref(),
+        node.summary.as_deref(),
+    ];
+    let matched = fields
+        .into_iter()
+        .flatten()
+        .find(|text| contains_any_term(text, terms));
+    let text = matched
+        .or(chunk_text)
+        .or(node.summary.as_deref())
+        .or(node.file_content.as_deref())?;
+    let snippet = build_snippet(text, terms, SNIPPET_CHARS);
+    (!snippet.text.is_empty()).then_some(snippet)
+}
+
+fn contains_any_term(text: &str, terms: &[String]) -> bool {
+    let lower = text.to_lowercase();
+    terms
+        .iter()
+        .any(|term| lower.contains(&term.to_lowercase()))
+}
+
+/// 截取第一个命中词附近的片段并计算所有命中词的高亮区间
+pub fn build_snippet(text: &str, terms: &[String], max_chars: usize) -> Snippet {
+    let normalized = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let chars: Vec<char> = normalized.chars().collect();
+    let lower: Vec<char> = chars.iter().map(|c| lower_char(*c)).collect();
+    let lower_terms: Vec<Vec<char>> = terms
+        .iter()
+        .map(|term| term.chars().map(lower_char).collect::<Vec<_>>())
+        .filter(|term| !term.is_empty())
+        .collect();
+
+    let first_match = lower_terms
+        .iter()
+        .filter_map(|term| find_chars(&lower, term, 0))
+        .min();
+    let start = first_match.map_or(0, |pos| {
+        pos.saturating_sub(max_chars / 4)
+            .min(chars.len().saturating_sub(max_chars))
+    });
+    let end = (start + max_chars).min(chars.len());
+
+    let prefix = if start > 0 { "…" } else { "" };
+    let suffix = if end < chars.len() { "…" } else { "" };
+    let body: String = chars[start..end].iter().collect();
+    let window = &lower[start..end];
+
+    // 命中区间（窗口内字符下标）
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for term in &lower_terms {
+        let mut from = 0;
+        while let Some(pos) = find_chars(window, term, from) {
+            ranges.push((pos, pos + term.len()));
+            from = pos + term.len();
+        }
+    }
+    ranges.sort_unstable();
+    let mut merged: Vec<(usize, usize)> = Vec::new();
+    for (s, e) in ranges {
+        match merged.last_mut() {
+            Some(last) if s <= last.1 => last.1 = last.1.max(e),
+            _ => merged.push((s, e)),
+        }
+    }
+
+    // 字符下标 -> UTF-16 下标
+    let prefix_units = prefix.encode_utf16().count();
+    let mut utf16_offsets = Vec::with_capacity(window.len() + 1);
+    let mut offset = prefix_units;
+    utf16_offsets.push(offset);
+    for c in &chars[start..end] {
+        offset += c.len_utf16();
+        utf16_offsets.push(offset);
+    }
+    let highlights = merged
+        .into_iter()
+        .map(|(s, e)| HighlightRange {
+            start: utf16_offsets[s],
+            end: utf16_offsets[e],
+        })
+        .collect();
+
+    Snippet {
+        text: format!("{prefix}{body}{suffix}"),
+        highlights,
+    }
+}
+
+/// 逐字符小写（只取映射的第一个字符，保持下标对齐）
+fn lower_char(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+fn find_chars(haystack: &[char], needle: &[char], from: usize) -> Option<usize> {
+    if needle.is_empty() || haystack.len() < needle.len() {
+        return None;
+    }
+    (from..=haystack.len() - needle.len()).find(|&i| haystack[i..i + needle.len()] == *needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terms(raw: &[&str]) -> Vec<String> {
+        raw.iter().map(|term| term.to_string()).collect()
+    }
+
+    #[test]
+    fn test_build_snippet_highlights() {
+        let snippet = build_snippet("Learn  Rust\nand rust again", &terms(&["rust"]), 100);
+        assert_eq!(snippet.text, "Learn Rust and rust again");
+        assert_eq!(
+            snippet.highlights,
+            vec![
+                HighlightRange { start: 6, end: 10 },
+                HighlightRange { start: 15, end: 19 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_snippet_window_and_utf16_offsets() {
+        let text = format!("{}😀向量检索{}", "a".repeat(50), "b".repeat(50));
+        let snippet = build_snippet(&text, &terms(&["向量"]), 20);
+        assert!(snippet.text.starts_with('…') && snippet.text.ends_with('…'));
+        let units: Vec<u16> = snippet.text.encode_utf16().collect();
+        let range = snippet.highlights[0];
+        assert_eq!(
+            String::from_utf16(&units[range.start..range.end]).unwrap(),
+            "向量"
+        );
+
+        let plain = build_snippet("no match here", &terms(&["zzz"]), 5);
+        assert_eq!(plain.text, "no ma…");
+        assert!(plain.highlights.is_empty());
+    }
+}
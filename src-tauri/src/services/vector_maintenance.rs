@@ -0,0 +1,132 @@
+//! 向量库维护
+//!
+//! 逐块写入与删除会让 Lance 表产生大量小 fragment 和历史版本。维护任务先删除 SQLite 中已不存在的
+//! 节点留下的向量，再合并 fragment、清理旧版本并把新增的行并入 ANN 索引。接通电源时每天自动执行一次。
+
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::app_state::AppState;
+use crate::db::list_all_node_ids;
+use crate::i18n::MessageCode;
+use crate::services::VectorOptimizeStats;
+use crate::utils::is_on_battery;
+use crate::{AppError, AppResult};
+
+const SCHEDULER_INITIAL_DELAY: Duration = Duration::from_secs(20 * 60);
+const SCHEDULER_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+/// 每次删除的节点数，避免过长的 IN 列表
+const ORPHAN_DELETE_BATCH: usize = 500;
+
+/// 同一时间只允许一次维护
+static RUNNING: AtomicBool = AtomicBool::new(false);
+
+struct RunningGuard;
+
+impl RunningGuard {
+    fn acquire() -> Option<Self> {
+        RUNNING
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .ok()
+            .map(|_| Self)
+    }
+}
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        RUNNING.store(false, Ordering::Release);
+    }
+}
+
+/// 维护结果
+#[derive(Debug, Serialize)]
+pub struct VectorMaintenanceReport {
+    /// 已不存在于 SQLite 的节点数
+    pub orphan_nodes: usize,
+    /// 删除的孤立向量行数
+    pub orphan_rows: usize,
+    #[serde(flatten)]
+    pub optimize: VectorOptimizeStats,
+}
+
+/// 删除孤立向量并整理向量表
+pub async fn run_vector_maintenance(state: &AppState) -> AppResult<VectorMaintenanceReport> {
+    let _guard = RunningGuard::acquire()
+        .ok_or_else(|| AppError::coded(MessageCode::VectorMaintenanceRunning))?;
+    let ai = state
+        .ai
+        .wait_ready()
+        .await
+        .map_err(|e| AppError::coded_with(MessageCode::AiNotReady, "detail", e))?;
+
+    // 先读向量表再读 SQLite：节点总是先写入 SQLite，期间新建的节点不会被误判为孤立
+    let vector_node_ids = ai
+        .embedding
+        .list_vector_node_ids()
+        .await
+        .map_err(AppError::AiService)?;
+    let existing: HashSet<i64> = list_all_node_ids(&state.db).await?.into_iter().collect();
+    let mut orphans = orphan_node_ids(&vector_node_ids, &existing);
+    orphans.sort_unstable();
+
+    let mut orphan_rows = 0;
+    for batch in orphans.chunks(ORPHAN_DELETE_BATCH) {
+        orphan_rows += ai
+            .embedding
+            .delete_nodes(batch)
+            .await
+            .map_err(AppError::AiService)?;
+    }
+    let optimize = ai.embedding.optimize().await.map_err(AppError::AiService)?;
+
+    tracing::info!(
+        orphan_nodes = orphans.len(),
+        orphan_rows,
+        fragments_removed = optimize.fragments_removed,
+        bytes_removed = optimize.bytes_removed,
+        "Vector maintenance finished"
+    );
+    Ok(VectorMaintenanceReport {
+        orphan_nodes: orphans.len(),
+        orphan_rows,
+        optimize,
+    })
+}
+
+fn orphan_node_ids(vector_node_ids: &HashSet<i64>, existing: &HashSet<i64>) -> Vec<i64> {
+    vector_node_ids.difference(existing).copied().collect()
+}
+
+/// 后台定期维护（使用电池时跳过）
+pub fn spawn_vector_maintenance_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(SCHEDULER_INITIAL_DELAY).await;
+        loop {
+            if !is_on_battery() {
+                if let Some(state) = app.try_state::<AppState>() {
+                    if let Err(err) = run_vector_maintenance(&state).await {
+                        tracing::debug!(error = %err, "Scheduled vector maintenance skipped");
+                    }
+                }
+            }
+            tokio::time::sleep(SCHEDULER_INTERVAL).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_orphan_node_ids() {
+        let vectors: HashSet<i64> = [1, 2, 5, 9].into_iter().collect();
+        let existing: HashSet<i64> = [1, 2, 3, 9].into_iter().collect();
+        assert_eq!(orphan_node_ids(&vectors, &existing), vec![5]);
+        assert!(orphan_node_ids(&HashSet::new(), &existing).is_empty());
+    }
+}
@@ -1,6 +1,7 @@
 use std::{
     fs,
     path::{Path, PathBuf},
+    sync::RwLock,
 };
 
 use tauri::{AppHandle, Manager};
@@ -14,11 +15,68 @@ pub fn parse_file_type(raw: Option<&str>) -> ResourceSubtype {
         Some(t) if t == "pdf" => ResourceSubtype::Pdf,
         Some(t) if t == "url" => ResourceSubtype::Url,
         Some(t) if t == "epub" => ResourceSubtype::Epub,
+        Some(t) if t == "office" => ResourceSubtype::Office,
+        Some(t) if t == "audio" => ResourceSubtype::Audio,
+        Some(t) if t == "notebook" => ResourceSubtype::Notebook,
+        Some(t) if t == "email" => ResourceSubtype::Email,
+        Some(t) if t == "archive" => ResourceSubtype::Archive,
         Some(t) if t == "other" => ResourceSubtype::Other,
         _ => ResourceSubtype::Text,
     }
 }
 
+/// 根据扩展名推断资源类型（未知扩展名归为 Other）
+pub fn parse_file_type_from_extension(ext: Option<&str>) -> ResourceSubtype {
+    match ext.map(|s| s.to_lowercase()).as_deref() {
+        Some("txt" | "md" | "markdown" | "csv" | "json" | "log") => ResourceSubtype::Text,
+        Some("png" | "jpg" | "jpeg" | "gif" | "webp" | "bmp") => ResourceSubtype::Image,
+        Some("pdf") => ResourceSubtype::Pdf,
+        Some("epub") => ResourceSubtype::Epub,
+        Some("docx" | "pptx" | "xlsx" | "odt" | "odp" | "ods") => ResourceSubtype::Office,
+        Some("mp3" | "wav" | "m4a" | "ogg" | "flac" | "aac" | "opus") => ResourceSubtype::Audio,
+        Some("ipynb") => ResourceSubtype::Notebook,
+        Some("eml" | "msg") => ResourceSubtype::Email,
+        Some("zip" | "tar" | "gz" | "tgz") => ResourceSubtype::Archive,
+        _ => ResourceSubtype::Other,
+    }
+}
+
+/// 附件扩展名：优先用文件名，其次按 MIME 推断
+///
+/// 扩展名会拼进落盘文件名，只接受不超过 8 位的 ASCII 字母数字，其余情况按 MIME 推断。
+pub fn attachment_extension(file_name: Option<&str>, mime: Option<&str>) -> Option<String> {
+    let from_name = file_name
+        .and_then(|name| name.rsplit_once('.'))
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+        .filter(|ext| {
+            !ext.is_empty() && ext.len() <= 8 && ext.bytes().all(|b| b.is_ascii_alphanumeric())
+        });
+    from_name.or_else(|| {
+        let ext = match mime?.to_lowercase().as_str() {
+            "image/png" => "png",
+            "image/jpeg" | "image/jpg" => "jpg",
+            "image/gif" => "gif",
+            "image/webp" => "webp",
+            "application/pdf" => "pdf",
+            "application/epub+zip" => "epub",
+            "application/vnd.openxmlformats-officedocument.wordprocessingml.document" => "docx",
+            "application/vnd.openxmlformats-officedocument.presentationml.presentation" => "pptx",
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet" => "xlsx",
+            "audio/mpeg" | "audio/mp3" => "mp3",
+            "audio/wav" | "audio/x-wav" => "wav",
+            "audio/mp4" | "audio/x-m4a" => "m4a",
+            "application/x-ipynb+json" => "ipynb",
+            "message/rfc822" => "eml",
+            "application/vnd.ms-outlook" => "msg",
+            "application/zip" | "application/x-zip-compressed" => "zip",
+            "application/gzip" | "application/x-gzip" => "gz",
+            "text/plain" => "txt",
+            _ => return None,
+        };
+        Some(ext.to_string())
+    })
+}
+
 pub fn get_extension(path: &str) -> Option<String> {
     Path::new(path)
         .extension()
@@ -26,29 +84,103 @@ pub fn get_extension(path: &str) -> Option<String> {
         .map(|s| s.to_lowercase())
 }
 
-pub fn get_assets_dir(app: &AppHandle) -> Result<PathBuf, String> {
-    let app_data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("获取应用数据目录失败: {}", e))?;
+/// 节点 file_path 中资源文件的相对路径前缀
+pub const ASSETS_PREFIX: &str = "assets/";
 
-    let assets_dir = app_data_dir.join("assets");
+/// 自定义资源文件目录；为空时使用应用数据目录下的 assets。启动时按配置设置，迁移后更新
+static CUSTOM_ASSETS_ROOT: RwLock<Option<PathBuf>> = RwLock::new(None);
 
-    fs::create_dir_all(&assets_dir).map_err(|e| format!("创建 assets 目录失败: {}", e))?;
+pub fn custom_assets_root() -> Option<PathBuf> {
+    CUSTOM_ASSETS_ROOT
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
 
-    Ok(assets_dir)
+pub fn set_custom_assets_root(root: Option<PathBuf>) {
+    *CUSTOM_ASSETS_ROOT
+        .write()
+        .unwrap_or_else(|e| e.into_inner()) = root;
 }
 
-pub fn resolve_file_path(app: &AppHandle, file_path: &str) -> Result<String, String> {
+/// 当前资源文件目录（不检查是否存在）
+pub fn assets_root(app_data_dir: &Path) -> PathBuf {
+    custom_assets_root().unwrap_or_else(|| app_data_dir.join("assets"))
+}
+
+/// 解析节点中保存的文件路径：`assets/` 开头的相对路径指向资源文件目录，
+/// 其余相对路径相对应用数据目录，绝对路径原样返回
+pub fn resolve_stored_path(app_data_dir: &Path, file_path: &str) -> PathBuf {
     let path = Path::new(file_path);
     if path.is_absolute() {
-        return Ok(path.to_string_lossy().to_string());
+        return path.to_path_buf();
+    }
+    match file_path.strip_prefix(ASSETS_PREFIX) {
+        Some(file_name) => assets_root(app_data_dir).join(file_name),
+        None => app_data_dir.join(path),
+    }
+}
+
+/// 确保资源文件目录可用：默认目录不存在时创建，自定义目录（如外接硬盘）缺失时返回错误
+pub fn ensure_assets_dir(app_data_dir: &Path) -> Result<PathBuf, String> {
+    if let Some(root) = custom_assets_root() {
+        if !root.is_dir() {
+            return Err(format!("资源文件目录不可用: {}", root.display()));
+        }
+        return Ok(root);
     }
 
+    let assets_dir = app_data_dir.join("assets");
+
+    fs::create_dir_all(&assets_dir).map_err(|e| format!("创建 assets 目录失败: {}", e))?;
+
+    Ok(assets_dir)
+}
+
+pub fn get_assets_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("获取应用数据目录失败: {}", e))?;
+
+    ensure_assets_dir(&app_data_dir)
+}
+
+pub fn resolve_file_path(app: &AppHandle, file_path: &str) -> Result<String, String> {
     let app_data_dir = app
         .path()
         .app_data_dir()
         .map_err(|e| format!("获取应用数据目录失败: {}", e))?;
 
-    Ok(app_data_dir.join(file_path).to_string_lossy().to_string())
+    Ok(resolve_stored_path(&app_data_dir, file_path)
+        .to_string_lossy()
+        .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attachment_extension() {
+        assert_eq!(
+            attachment_extension(Some("Report.PDF"), None).as_deref(),
+            Some("pdf")
+        );
+        assert_eq!(
+            attachment_extension(Some("photo"), Some("image/jpeg")).as_deref(),
+            Some("jpg")
+        );
+        // 含路径分隔符或非 ASCII 的扩展名回退到 MIME
+        assert_eq!(
+            attachment_extension(Some("x./../../evil"), Some("text/plain")).as_deref(),
+            Some("txt")
+        );
+        assert_eq!(
+            attachment_extension(Some("笔记.文本"), Some("application/pdf")).as_deref(),
+            Some("pdf")
+        );
+        assert_eq!(attachment_extension(Some("a.b\\c"), None), None);
+        assert_eq!(attachment_extension(Some("archive.toolongext"), None), None);
+    }
 }
@@ -1,3 +1,5 @@
+use std::{fs::File, io, path::Path};
+
 use sha2::{Digest, Sha256};
 
 pub fn compute_sha256(bytes: &[u8]) -> String {
@@ -5,3 +7,10 @@ pub fn compute_sha256(bytes: &[u8]) -> String {
     hasher.update(bytes);
     format!("{:x}", hasher.finalize())
 }
+
+/// 流式计算文件哈希，结果与 compute_sha256(文件内容) 一致
+pub fn compute_file_sha256(path: &Path) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut File::open(path)?, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
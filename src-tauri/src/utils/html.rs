@@ -0,0 +1,196 @@
+//! HTML 转纯文本
+//!
+//! 只做轻量处理：去掉标签、script/style 内容，块级标签换行，解码常见实体。
+//! 另提供从 HTML 中提取第一个表格的简单解析（用于粘贴表格）。
+
+/// 产生换行的块级标签
+const BLOCK_TAGS: &[&str] = &[
+    "p",
+    "div",
+    "br",
+    "li",
+    "ul",
+    "ol",
+    "tr",
+    "table",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "blockquote",
+    "pre",
+    "section",
+    "article",
+    "header",
+    "footer",
+    "en-note",
+];
+
+/// 将 HTML 转为纯文本，连续空白折叠、空行去除
+pub fn html_to_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len() / 2);
+    let mut rest = html;
+    let mut skip_until: Option<&str> = None;
+
+    while let Some(start) = rest.find('<') {
+        if skip_until.is_none() {
+            text.push_str(&decode_entities(&rest[..start]));
+        }
+        let Some(end) = rest[start..].find('>') else {
+            rest = &rest[start..];
+            break;
+        };
+        let tag = &rest[start + 1..start + end];
+        rest = &rest[start + end + 1..];
+
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        let closing = tag.starts_with('/');
+
+        if let Some(skipped) = skip_until {
+            if closing && name == skipped {
+                skip_until = None;
+            }
+            continue;
+        }
+        match name.as_str() {
+            "script" if !closing => skip_until = Some("script"),
+            "style" if !closing => skip_until = Some("style"),
+            _ if BLOCK_TAGS.contains(&name.as_str()) => text.push('\n'),
+            _ => text.push(' '),
+        }
+    }
+    if skip_until.is_none() {
+        text.push_str(&decode_entities(rest));
+    }
+
+    text.lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 提取 HTML 中第一个 `<table>` 的单元格文本（按行）
+///
+/// 不处理 rowspan / colspan；嵌套表格的单元格并入外层单元格文本。
+/// 没有表格或只有单列时返回 None。
+pub fn extract_html_table(html: &str) -> Option<Vec<Vec<String>>> {
+    let lower = html.to_ascii_lowercase();
+    let start = find_tag(&lower, "table", 0)?;
+    let end = lower[start..]
+        .find("</table")
+        .map_or(html.len(), |offset| start + offset);
+
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut pos = start;
+    while let Some(row_start) = find_tag(&lower[..end], "tr", pos) {
+        let row_end = find_tag(&lower[..end], "tr", row_start + 3).unwrap_or(end);
+        let mut cells = Vec::new();
+        let mut cell_pos = row_start;
+        while let Some(cell_start) = find_cell(&lower[..row_end], cell_pos) {
+            let content_start = lower[cell_start..row_end]
+                .find('>')
+                .map_or(row_end, |offset| cell_start + offset + 1);
+            let content_end = find_cell(&lower[..row_end], content_start).unwrap_or(row_end);
+            let text = html_to_text(&html[content_start..content_end]);
+            cells.push(text.lines().collect::<Vec<_>>().join(" "));
+            cell_pos = content_end;
+        }
+        if cells.iter().any(|cell| !cell.is_empty()) {
+            rows.push(cells);
+        }
+        pos = row_end;
+    }
+
+    let columns = rows.iter().map(Vec::len).max()?;
+    (columns >= 2).then_some(rows)
+}
+
+/// 从 from 开始查找开标签 `<name`（后接空白、`/` 或 `>`），lower 须已小写
+fn find_tag(lower: &str, name: &str, from: usize) -> Option<usize> {
+    let pattern = format!("<{name}");
+    let mut pos = from;
+    while let Some(offset) = lower.get(pos..)?.find(&pattern) {
+        let start = pos + offset;
+        let next = lower[start + pattern.len()..].chars().next();
+        if next.is_some_and(|c| c.is_whitespace() || c == '>' || c == '/') {
+            return Some(start);
+        }
+        pos = start + pattern.len();
+    }
+    None
+}
+
+fn find_cell(lower: &str, from: usize) -> Option<usize> {
+    match (find_tag(lower, "td", from), find_tag(lower, "th", from)) {
+        (Some(td), Some(th)) => Some(td.min(th)),
+        (td, th) => td.or(th),
+    }
+}
+
+fn decode_entities(raw: &str) -> String {
+    if !raw.contains('&') {
+        return raw.to_string();
+    }
+    raw.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_html_to_text() {
+        let html = "<html><head><style>p { color: red; }</style>\
+                    <script>alert('x')</script></head>\
+                    <body><h1>Title</h1><p>Hello&nbsp;<b>world</b> &amp; friends</p>\
+                    <ul><li>one</li><li>two</li></ul></body></html>";
+        assert_eq!(html_to_text(html), "Title\nHello world & friends\none\ntwo");
+    }
+
+    #[test]
+    fn test_html_to_text_plain_and_broken() {
+        assert_eq!(html_to_text("just text"), "just text");
+        assert_eq!(html_to_text("a < b"), "a < b");
+        assert_eq!(html_to_text("<div>x</div><br/>y"), "x\ny");
+    }
+
+    #[test]
+    fn test_extract_html_table() {
+        let html =
+            "<p>intro</p><TABLE class=\"t\"><thead><tr><th>Name</th><th>Qty</th></tr></thead>\
+                    <tbody><tr><td>Apple &amp; pear</td><td><b>3</b></td></tr>\
+                    <tr><td>Plum</td></tr></tbody></TABLE><table><tr><td>x</td></tr></table>";
+        assert_eq!(
+            extract_html_table(html),
+            Some(vec![
+                vec!["Name".to_string(), "Qty".to_string()],
+                vec!["Apple & pear".to_string(), "3".to_string()],
+                vec!["Plum".to_string()],
+            ])
+        );
+    }
+
+    #[test]
+    fn test_extract_html_table_rejects_non_tables() {
+        assert_eq!(extract_html_table("<p>no table</p>"), None);
+        assert_eq!(
+            extract_html_table("<table><tr><td>only</td></tr></table>"),
+            None
+        );
+        assert_eq!(extract_html_table("<tbody><thead>"), None);
+    }
+}
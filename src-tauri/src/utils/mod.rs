@@ -1,8 +1,18 @@
 mod file;
 mod hash;
+mod html;
+mod node_links;
+mod paste;
+mod power;
+mod text_stats;
 mod validation;
 pub mod crypto;
 
 pub use file::*;
 pub use hash::*;
+pub use html::*;
+pub use node_links::*;
+pub use paste::*;
+pub use power::*;
+pub use text_stats::*;
 pub use validation::*;
@@ -0,0 +1,76 @@
+//! 正文中对其他节点的引用
+//!
+//! 支持两种写法：直接粘贴节点的 uuid，或 `[[标题]]` / `[[标题|显示文字]]` 形式的链接（标题需完全一致）。
+
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+/// 正文中引用的 uuid（小写）与标题，均已去重并保持出现顺序
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct NodeLinkRefs {
+    pub uuids: Vec<String>,
+    pub titles: Vec<String>,
+}
+
+impl NodeLinkRefs {
+    pub fn is_empty(&self) -> bool {
+        self.uuids.is_empty() && self.titles.is_empty()
+    }
+}
+
+fn uuid_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"(?i)\b[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}\b")
+            .expect("valid uuid regex")
+    })
+}
+
+fn wiki_link_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"\[\[([^\[\]|\n]+)(?:\|[^\[\]\n]*)?\]\]").expect("valid wiki link regex")
+    })
+}
+
+pub fn extract_node_links(text: &str) -> NodeLinkRefs {
+    let mut refs = NodeLinkRefs::default();
+    for m in uuid_regex().find_iter(text) {
+        let uuid = m.as_str().to_lowercase();
+        if !refs.uuids.contains(&uuid) {
+            refs.uuids.push(uuid);
+        }
+    }
+    for caps in wiki_link_regex().captures_iter(text) {
+        let title = caps[1].trim();
+        if !title.is_empty() && !refs.titles.iter().any(|t| t == title) {
+            refs.titles.push(title.to_string());
+        }
+    }
+    refs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_node_links() {
+        let text = "见 [[Rust 异步]] 和 [[ 所有权 |这里]]，以及 \
+                    3F2504E0-4F89-11D3-9A0C-0305E82C3301。\n\
+                    再次引用 [[Rust 异步]] 与 3f2504e0-4f89-11d3-9a0c-0305e82c3301";
+        assert_eq!(
+            extract_node_links(text),
+            NodeLinkRefs {
+                uuids: vec!["3f2504e0-4f89-11d3-9a0c-0305e82c3301".to_string()],
+                titles: vec!["Rust 异步".to_string(), "所有权".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn test_extract_node_links_ignores_malformed() {
+        assert!(extract_node_links("[[]] [[ ]] [single] [[a\nb]] 3f2504e0-4f89").is_empty());
+    }
+}
@@ -0,0 +1,236 @@
+//! 粘贴内容识别：制表符分隔的表格、代码片段及其语言
+//!
+//! 都是启发式判断，宁可漏判也不把普通文字当成表格或代码。
+
+/// 判定为代码所需的"像代码"行占比
+const CODE_LINE_RATIO: f64 = 0.6;
+
+/// 各语言的特征片段，命中最多者胜出；并列时取靠前的语言
+const LANGUAGE_SIGNATURES: &[(&str, &[&str])] = &[
+    (
+        "rust",
+        &[
+            "fn ", "let mut ", "impl ", "pub fn", "::", "&str", "#[derive", "match ", "-> ",
+        ],
+    ),
+    (
+        "typescript",
+        &[
+            "interface ",
+            ": string",
+            ": number",
+            ": boolean",
+            "import type",
+            "export type",
+        ],
+    ),
+    (
+        "javascript",
+        &[
+            "const ",
+            "function ",
+            "=>",
+            "console.log",
+            "require(",
+            "export default",
+        ],
+    ),
+    (
+        "python",
+        &[
+            "def ", "elif ", "self.", "print(", "None", "import ", "lambda ",
+        ],
+    ),
+    ("go", &["func ", "package ", ":= ", "fmt.", "err != nil"]),
+    (
+        "java",
+        &[
+            "public class",
+            "public static void",
+            "System.out",
+            "private ",
+            "@Override",
+        ],
+    ),
+    (
+        "cpp",
+        &["#include", "std::", "printf(", "int main(", "nullptr"],
+    ),
+    (
+        "sql",
+        &[
+            "select ",
+            "insert into",
+            "create table",
+            " where ",
+            " from ",
+            "join ",
+        ],
+    ),
+    (
+        "bash",
+        &[
+            "#!/bin", "sudo ", "echo ", "export ", "| grep", "$(", "fi\n", "apt ",
+        ],
+    ),
+    ("html", &["<div", "</", "<!doctype", "<span", "<html"]),
+    ("css", &["px;", "color:", "margin:", "padding:", "display:"]),
+];
+
+/// SQL 语句开头（大小写不敏感）；SQL 没有分号结尾的行也应识别为代码
+const SQL_KEYWORDS: &[&str] = &[
+    "select ", "insert ", "update ", "delete ", "create ", "alter ", "drop ", "with ",
+];
+
+/// 识别从电子表格复制的制表符分隔文本
+///
+/// 至少两行，每行列数相同且不少于两列
+pub fn parse_tab_separated(text: &str) -> Option<Vec<Vec<String>>> {
+    let lines: Vec<&str> = text
+        .lines()
+        .map(|line| line.trim_end_matches('\r'))
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+    if lines.len() < 2 || lines.iter().all(|line| line.starts_with('\t')) {
+        return None;
+    }
+
+    let rows: Vec<Vec<String>> = lines
+        .iter()
+        .map(|line| {
+            line.split('\t')
+                .map(|cell| cell.trim().to_string())
+                .collect()
+        })
+        .collect();
+    let columns = rows[0].len();
+    (columns >= 2 && rows.iter().all(|row| row.len() == columns)).then_some(rows)
+}
+
+/// 文本是否像一段代码
+pub fn looks_like_code(text: &str) -> bool {
+    let trimmed = text.trim();
+    if is_json_document(trimmed) {
+        return true;
+    }
+    let lines: Vec<&str> = trimmed
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+    if lines.len() < 2 {
+        return false;
+    }
+    let lower = trimmed.to_lowercase();
+    if SQL_KEYWORDS
+        .iter()
+        .any(|keyword| lower.starts_with(keyword))
+        && lower.contains(';')
+    {
+        return true;
+    }
+
+    let code_lines = lines.iter().filter(|line| is_code_line(line)).count();
+    code_lines as f64 / lines.len() as f64 >= CODE_LINE_RATIO
+}
+
+/// 猜测代码语言，无法判断时返回 None
+pub fn guess_code_language(text: &str) -> Option<&'static str> {
+    let trimmed = text.trim();
+    if is_json_document(trimmed) {
+        return Some("json");
+    }
+    let lower = trimmed.to_lowercase();
+    LANGUAGE_SIGNATURES
+        .iter()
+        .map(|(language, signatures)| {
+            let hits = signatures
+                .iter()
+                .filter(|signature| lower.contains(&signature.to_lowercase()))
+                .count();
+            (*language, hits)
+        })
+        .fold(
+            None,
+            |best: Option<(&str, usize)>, (language, hits)| match best {
+                Some((_, best_hits)) if best_hits >= hits => best,
+                _ if hits > 0 => Some((language, hits)),
+                _ => best,
+            },
+        )
+        .map(|(language, _)| language)
+}
+
+fn is_json_document(text: &str) -> bool {
+    (text.starts_with('{') || text.starts_with('['))
+        && text.len() > 2
+        && serde_json::from_str::<serde_json::Value>(text).is_ok()
+}
+
+fn is_code_line(line: &str) -> bool {
+    let trimmed = line.trim();
+    let indented = line.starts_with("    ") || line.starts_with('\t');
+    let structural_end = trimmed.ends_with(['{', '}', ';', '(', ')', '[', ']']);
+    let block_start = trimmed.ends_with(':') && indented_block_keyword(trimmed);
+    let comment = trimmed.starts_with("//") || trimmed.starts_with("/*");
+    let operators = ["=>", "->", "::", "==", "!=", "&&", "||", "+=", ":="]
+        .iter()
+        .any(|op| trimmed.contains(op));
+    indented || structural_end || block_start || comment || operators
+}
+
+fn indented_block_keyword(line: &str) -> bool {
+    [
+        "def ", "class ", "if ", "elif ", "else", "for ", "while ", "try", "except", "with ",
+    ]
+    .iter()
+    .any(|keyword| line.starts_with(keyword))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tab_separated() {
+        let rows = parse_tab_separated("Name\tQty\r\nApple\t3\n\nPear\t\n").unwrap();
+        assert_eq!(
+            rows,
+            vec![vec!["Name", "Qty"], vec!["Apple", "3"], vec!["Pear", ""]]
+        );
+        assert_eq!(parse_tab_separated("a\tb"), None);
+        assert_eq!(parse_tab_separated("a\tb\nc\td\te"), None);
+        assert_eq!(parse_tab_separated("\tindented\n\tcode"), None);
+    }
+
+    #[test]
+    fn test_looks_like_code() {
+        assert!(looks_like_code("fn main() {\n    println!(\"hi\");\n}"));
+        assert!(looks_like_code("def f(x):\n    return x + 1"));
+        assert!(looks_like_code("{\"a\": [1, 2]}"));
+        assert!(looks_like_code(
+            "SELECT id\nFROM nodes\nWHERE is_deleted = 0;"
+        ));
+        assert!(!looks_like_code(
+            "Meeting notes.\nDiscuss the budget with the team."
+        ));
+        assert!(!looks_like_code("let me know"));
+    }
+
+    #[test]
+    fn test_guess_code_language() {
+        assert_eq!(
+            guess_code_language("fn main() {\n    let mut x = 1;\n}"),
+            Some("rust")
+        );
+        assert_eq!(
+            guess_code_language("def f(self):\n    print(self.x)"),
+            Some("python")
+        );
+        assert_eq!(
+            guess_code_language("interface A {\n  name: string;\n}"),
+            Some("typescript")
+        );
+        assert_eq!(guess_code_language("[1, 2, 3]"), Some("json"));
+        assert_eq!(guess_code_language("x\ny"), None);
+    }
+}
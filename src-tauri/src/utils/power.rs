@@ -0,0 +1,71 @@
+//! 电源状态检测
+//!
+//! 用于跳过后台的耗电任务（如模型预热）。无法判断时视为接通电源。
+
+/// 当前是否使用电池供电
+pub fn is_on_battery() -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        linux_on_battery()
+    }
+    #[cfg(target_os = "macos")]
+    {
+        std::process::Command::new("pmset")
+            .args(["-g", "batt"])
+            .output()
+            .map(|output| pmset_on_battery(&String::from_utf8_lossy(&output.stdout)))
+            .unwrap_or(false)
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    {
+        false
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn linux_on_battery() -> bool {
+    let Ok(entries) = std::fs::read_dir("/sys/class/power_supply") else {
+        return false;
+    };
+    entries.flatten().any(|entry| {
+        let read =
+            |name: &str| std::fs::read_to_string(entry.path().join(name)).unwrap_or_default();
+        supply_discharging(&read("type"), &read("status"))
+    })
+}
+
+/// sysfs 中的电池是否正在放电
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn supply_discharging(supply_type: &str, status: &str) -> bool {
+    supply_type.trim() == "Battery" && status.trim() == "Discharging"
+}
+
+/// 解析 `pmset -g batt` 输出，首行形如 `Now drawing from 'Battery Power'`
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn pmset_on_battery(output: &str) -> bool {
+    output
+        .lines()
+        .next()
+        .is_some_and(|line| line.contains("'Battery Power'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_supply_discharging() {
+        assert!(supply_discharging("Battery\n", "Discharging\n"));
+        assert!(!supply_discharging("Battery\n", "Charging\n"));
+        assert!(!supply_discharging("Mains\n", ""));
+    }
+
+    #[test]
+    fn test_pmset_on_battery() {
+        let battery = "Now drawing from 'Battery Power'\n -InternalBattery-0 (id=1234)\t85%; discharging; 5:12 remaining present: true";
+        let ac = "Now drawing from 'AC Power'\n -InternalBattery-0 (id=1234)\t100%; charged; 0:00 remaining present: true";
+        assert!(pmset_on_battery(battery));
+        assert!(!pmset_on_battery(ac));
+        assert!(!pmset_on_battery(""));
+    }
+}
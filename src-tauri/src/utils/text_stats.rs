@@ -0,0 +1,254 @@
+//! 正文统计：字数、token 数、语言与预计阅读时长
+//!
+//! 中日韩文字按字计数，其他文字按空白分隔的词计数；两者阅读速度不同，分别折算。
+//! token 数与语言都是启发式估算，不依赖模型的分词器。
+
+/// 英文等按词计数的阅读速度（词/分钟）
+const WORDS_PER_MINUTE: f64 = 230.0;
+/// 中日韩文字的阅读速度（字/分钟）
+const CJK_CHARS_PER_MINUTE: f64 = 400.0;
+/// 语言检测只看开头这么多字符
+const LANGUAGE_SAMPLE_CHARS: usize = 4000;
+/// 判定拉丁语系语言所需的最少常用词命中数
+const MIN_STOPWORD_HITS: usize = 3;
+
+/// 拉丁字母语言的常用词，命中最多者胜出
+const LANGUAGE_STOPWORDS: &[(&str, &[&str])] = &[
+    (
+        "en",
+        &[
+            "the", "and", "is", "are", "of", "to", "in", "that", "it", "with", "for", "this",
+        ],
+    ),
+    (
+        "de",
+        &[
+            "der", "die", "das", "und", "ist", "nicht", "ein", "eine", "mit", "ich", "zu", "auf",
+        ],
+    ),
+    (
+        "fr",
+        &[
+            "le", "la", "les", "et", "est", "des", "une", "un", "que", "pour", "dans", "pas",
+        ],
+    ),
+    (
+        "es",
+        &[
+            "el", "los", "las", "y", "es", "que", "una", "por", "para", "con", "del", "no",
+        ],
+    ),
+];
+
+/// 正文统计结果
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ContentStats {
+    pub word_count: i64,
+    pub char_count: i64,
+    pub token_count: i64,
+    /// ISO 639-1 语言代码；文字太少或无法判断时为 None
+    pub language: Option<&'static str>,
+    pub read_time_minutes: Option<i64>,
+}
+
+/// 字数统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WordCount {
+    /// 空白分隔的词（不含中日韩文字）
+    pub words: usize,
+    /// 中日韩文字
+    pub cjk_chars: usize,
+}
+
+impl WordCount {
+    pub fn total(&self) -> usize {
+        self.words + self.cjk_chars
+    }
+}
+
+pub fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{4E00}'..='\u{9FFF}'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{3040}'..='\u{30FF}'
+        | '\u{AC00}'..='\u{D7AF}'
+        | '\u{20000}'..='\u{2A6DF}')
+}
+
+pub fn count_words(text: &str) -> WordCount {
+    let mut count = WordCount::default();
+    let mut in_word = false;
+    for c in text.chars() {
+        if is_cjk(c) {
+            count.cjk_chars += 1;
+            in_word = false;
+        } else if c.is_alphanumeric() {
+            if !in_word {
+                count.words += 1;
+                in_word = true;
+            }
+        } else if c.is_whitespace() {
+            in_word = false;
+        }
+    }
+    count
+}
+
+/// 预计阅读分钟数（向上取整）；没有可读文字时返回 None
+pub fn estimate_read_minutes(text: &str) -> Option<i64> {
+    let count = count_words(text);
+    if count.total() == 0 {
+        return None;
+    }
+    let minutes =
+        count.words as f64 / WORDS_PER_MINUTE + count.cjk_chars as f64 / CJK_CHARS_PER_MINUTE;
+    Some((minutes.ceil() as i64).max(1))
+}
+
+/// 粗略估算 token 数：CJK 字符约 1 token/字，其余约 4 字符/token
+pub fn estimate_tokens(text: &str) -> i64 {
+    let mut cjk = 0i64;
+    let mut other = 0i64;
+    for ch in text.chars() {
+        if is_cjk(ch) {
+            cjk += 1;
+        } else {
+            other += 1;
+        }
+    }
+    cjk + (other + 3) / 4
+}
+
+/// 按文字系统判断中日韩俄，拉丁字母再按常用词区分
+pub fn detect_language(text: &str) -> Option<&'static str> {
+    let sample: String = text.chars().take(LANGUAGE_SAMPLE_CHARS).collect();
+    let (mut han, mut kana, mut hangul, mut cyrillic, mut latin) = (0, 0, 0, 0, 0);
+    for c in sample.chars() {
+        match c {
+            '\u{3040}'..='\u{30FF}' => kana += 1,
+            '\u{AC00}'..='\u{D7AF}' => hangul += 1,
+            '\u{0400}'..='\u{04FF}' => cyrillic += 1,
+            c if is_cjk(c) => han += 1,
+            c if c.is_ascii_alphabetic() || ('\u{00C0}'..='\u{024F}').contains(&c) => latin += 1,
+            _ => {}
+        }
+    }
+
+    // 拉丁字母约 5 个一词，与中日韩的字数比较
+    let cjk = han + kana + hangul;
+    if cjk > 0 && cjk * 5 >= latin {
+        return Some(if hangul * 2 > cjk {
+            "ko"
+        } else if kana * 10 >= cjk {
+            "ja"
+        } else {
+            "zh"
+        });
+    }
+    if cyrillic > latin {
+        return Some("ru");
+    }
+
+    let lower = sample.to_lowercase();
+    let words: Vec<&str> = lower
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|w| !w.is_empty())
+        .collect();
+    LANGUAGE_STOPWORDS
+        .iter()
+        .map(|(code, stopwords)| {
+            let hits = words.iter().filter(|w| stopwords.contains(w)).count();
+            (*code, hits)
+        })
+        .filter(|(_, hits)| *hits >= MIN_STOPWORD_HITS)
+        .fold(
+            None,
+            |best: Option<(&str, usize)>, (code, hits)| match best {
+                Some((_, best_hits)) if best_hits >= hits => best,
+                _ => Some((code, hits)),
+            },
+        )
+        .map(|(code, _)| code)
+}
+
+/// 解析或替换正文时计算，写入节点
+pub fn compute_content_stats(text: &str) -> ContentStats {
+    ContentStats {
+        word_count: count_words(text).total() as i64,
+        char_count: text.chars().count() as i64,
+        token_count: estimate_tokens(text),
+        language: detect_language(text),
+        read_time_minutes: estimate_read_minutes(text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_words() {
+        assert_eq!(
+            count_words("Hello, world! it's 2024"),
+            WordCount {
+                words: 4,
+                cjk_chars: 0
+            }
+        );
+        assert_eq!(
+            count_words("用 Rust 写一个笔记应用"),
+            WordCount {
+                words: 1,
+                cjk_chars: 8
+            }
+        );
+        assert_eq!(count_words("  \n\t-- ").total(), 0);
+    }
+
+    #[test]
+    fn test_estimate_read_minutes() {
+        assert_eq!(estimate_read_minutes(""), None);
+        assert_eq!(estimate_read_minutes("short note"), Some(1));
+        assert_eq!(estimate_read_minutes(&"word ".repeat(2300)), Some(10));
+        assert_eq!(estimate_read_minutes(&"字".repeat(4000)), Some(10));
+        assert_eq!(
+            estimate_read_minutes(&format!("{}{}", "word ".repeat(230), "字".repeat(400))),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn test_detect_language() {
+        assert_eq!(
+            detect_language("这是一段关于知识管理的中文笔记。"),
+            Some("zh")
+        );
+        assert_eq!(detect_language("これは日本語のメモです。"), Some("ja"));
+        assert_eq!(detect_language("이것은 한국어 메모입니다"), Some("ko"));
+        assert_eq!(detect_language("Это заметка на русском языке"), Some("ru"));
+        assert_eq!(
+            detect_language("This is a note about the design of the sync engine and its tests."),
+            Some("en")
+        );
+        assert_eq!(
+            detect_language("Das ist eine Notiz, und die Idee ist nicht neu."),
+            Some("de")
+        );
+        assert_eq!(
+            detect_language("C'est une note pour le projet et la liste des tâches."),
+            Some("fr")
+        );
+        assert_eq!(detect_language("Rust tokio serde"), None);
+        assert_eq!(detect_language(""), None);
+    }
+
+    #[test]
+    fn test_compute_content_stats() {
+        let stats = compute_content_stats("Hello world 你好");
+        assert_eq!(stats.word_count, 4);
+        assert_eq!(stats.char_count, 14);
+        assert_eq!(stats.read_time_minutes, Some(1));
+        assert_eq!(compute_content_stats("").read_time_minutes, None);
+    }
+}
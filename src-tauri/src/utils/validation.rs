@@ -2,8 +2,9 @@
 //!
 //! 提供集中的验证逻辑，避免在命令层重复验证代码
 
-use crate::db::{EdgeRelationType, ReviewStatus};
+use crate::db::{EdgeRelationType, ReviewStatus, SourceRuleField};
 use crate::error::{AppError, AppResult};
+use crate::i18n::MessageCode;
 
 /// 验证标题非空
 ///
@@ -11,7 +12,7 @@ use crate::error::{AppError, AppResult};
 pub fn validate_title(title: &str) -> AppResult<&str> {
     let trimmed = title.trim();
     if trimmed.is_empty() {
-        return Err(AppError::Validation("标题不能为空".to_string()));
+        return Err(AppError::coded(MessageCode::TitleEmpty));
     }
     Ok(trimmed)
 }
@@ -21,7 +22,7 @@ pub fn validate_title(title: &str) -> AppResult<&str> {
 pub fn validate_not_empty<'a>(value: &'a str, field_name: &str) -> AppResult<&'a str> {
     let trimmed = value.trim();
     if trimmed.is_empty() {
-        return Err(AppError::Validation(format!("{} 不能为空", field_name)));
+        return Err(AppError::coded_with(MessageCode::FieldEmpty, "field", field_name));
     }
     Ok(trimmed)
 }
@@ -32,7 +33,8 @@ pub fn parse_relation_type(raw: &str) -> AppResult<EdgeRelationType> {
     match raw {
         "contains" => Ok(EdgeRelationType::Contains),
         "related_to" => Ok(EdgeRelationType::RelatedTo),
-        _ => Err(AppError::Validation(format!("未知的关系类型: {}", raw))),
+        "derived_from" => Ok(EdgeRelationType::DerivedFrom),
+        _ => Err(AppError::coded_with(MessageCode::UnknownRelationType, "value", raw)),
     }
 }
 
@@ -42,7 +44,7 @@ pub fn parse_review_status(raw: &str) -> AppResult<ReviewStatus> {
         "unreviewed" => Ok(ReviewStatus::Unreviewed),
         "reviewed" | "approved" => Ok(ReviewStatus::Reviewed),
         "rejected" => Ok(ReviewStatus::Rejected),
-        _ => Err(AppError::Validation(format!("未知的审核状态: {}", raw))),
+        _ => Err(AppError::coded_with(MessageCode::UnknownReviewStatus, "value", raw)),
     }
 }
 
@@ -55,11 +57,21 @@ pub fn parse_review_status_or_default(raw: Option<&str>) -> ReviewStatus {
     }
 }
 
+/// 解析来源规则匹配字段
+pub fn parse_source_rule_field(raw: &str) -> AppResult<SourceRuleField> {
+    match raw {
+        "domain" => Ok(SourceRuleField::Domain),
+        "app" => Ok(SourceRuleField::App),
+        "window_title" => Ok(SourceRuleField::WindowTitle),
+        _ => Err(AppError::coded_with(MessageCode::UnknownSourceRuleField, "value", raw)),
+    }
+}
+
 /// 验证 node_id 有效
 #[allow(dead_code)]
 pub fn validate_node_id(node_id: i64) -> AppResult<i64> {
     if node_id <= 0 {
-        return Err(AppError::Validation("无效的节点 ID".into()));
+        return Err(AppError::coded(MessageCode::InvalidNodeId));
     }
     Ok(node_id)
 }
@@ -92,6 +104,10 @@ mod tests {
             parse_relation_type("related_to").unwrap(),
             EdgeRelationType::RelatedTo
         );
+        assert_eq!(
+            parse_relation_type("derived_from").unwrap(),
+            EdgeRelationType::DerivedFrom
+        );
         assert!(parse_relation_type("unknown").is_err());
     }
 
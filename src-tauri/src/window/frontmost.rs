@@ -0,0 +1,106 @@
+//! 前台窗口上下文采集
+//!
+//! HUD 弹出后自身会成为前台窗口，因此需要在显示 HUD 之前记录用户正在使用的应用，
+//! 供捕获资源时写入 source_meta。
+
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use active_win_pos_rs::get_active_window;
+
+/// 快照有效期：超过后视为过期，改为实时查询
+const SNAPSHOT_TTL: Duration = Duration::from_secs(120);
+
+/// 前台窗口信息
+#[derive(Debug, Clone)]
+pub struct FrontmostWindow {
+    pub app_name: Option<String>,
+    pub window_title: Option<String>,
+    pub process_path: Option<String>,
+}
+
+/// 缓存 HUD 弹出前的前台窗口
+#[derive(Default)]
+pub struct FrontmostWindowCache {
+    snapshot: Mutex<Option<(FrontmostWindow, Instant)>>,
+}
+
+impl FrontmostWindowCache {
+    /// 记录当前前台窗口（在 HUD 显示前调用）
+    pub fn remember(&self) {
+        let Some(window) = query_frontmost_window() else {
+            return;
+        };
+        if let Ok(mut guard) = self.snapshot.lock() {
+            *guard = Some((window, Instant::now()));
+        }
+    }
+
+    /// 取出最近一次快照（只消费一次，过期则丢弃）
+    pub fn take_recent(&self) -> Option<FrontmostWindow> {
+        let mut guard = self.snapshot.lock().ok()?;
+        match guard.take() {
+            Some((window, at)) if at.elapsed() <= SNAPSHOT_TTL => Some(window),
+            _ => None,
+        }
+    }
+}
+
+/// 查询当前前台窗口；若前台是 NeuralVault 自身则返回 None
+pub fn query_frontmost_window() -> Option<FrontmostWindow> {
+    let active = get_active_window().ok()?;
+    if active.process_id == u64::from(std::process::id()) {
+        return None;
+    }
+
+    let process_path = active.process_path.to_string_lossy().to_string();
+    let app_name = non_empty(active.app_name)
+        .or_else(|| app_name_from_path(&active.process_path))
+        .map(|name| normalize_app_name(&name));
+
+    Some(FrontmostWindow {
+        app_name,
+        window_title: non_empty(active.title),
+        process_path: non_empty(process_path),
+    })
+}
+
+fn non_empty(value: String) -> Option<String> {
+    if value.trim().is_empty() {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+/// macOS: 从 `/Applications/Zotero.app/Contents/MacOS/zotero` 中取出 `Zotero`
+#[cfg(target_os = "macos")]
+fn app_name_from_path(path: &Path) -> Option<String> {
+    path.ancestors()
+        .filter_map(|p| p.file_name()?.to_str())
+        .find_map(|name| name.strip_suffix(".app").map(|s| s.to_string()))
+        .or_else(|| path.file_stem()?.to_str().map(|s| s.to_string()))
+}
+
+/// Windows / Linux: 使用可执行文件名
+#[cfg(not(target_os = "macos"))]
+fn app_name_from_path(path: &Path) -> Option<String> {
+    path.file_stem()?.to_str().map(|s| s.to_string())
+}
+
+/// Windows 下 app_name 可能带有 `.exe` 后缀，统一去掉便于按应用过滤
+#[cfg(target_os = "windows")]
+fn normalize_app_name(name: &str) -> String {
+    let trimmed = name.trim();
+    if trimmed.to_lowercase().ends_with(".exe") {
+        trimmed[..trimmed.len() - 4].to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn normalize_app_name(name: &str) -> String {
+    name.trim().to_string()
+}
@@ -1,6 +1,36 @@
-use tauri::{App, Emitter, Listener, Manager};
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use tauri::{App, AppHandle, Listener, Manager};
 use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Modifiers, Shortcut, ShortcutState};
 
+use super::FrontmostWindowCache;
+use crate::app_state::AppState;
+use crate::services::{emit_event, CaptureProfile, HudFocusPayload};
+
+/// 已注册的捕获模板快捷键，重新注册前先全部注销
+#[derive(Default)]
+pub struct ProfileShortcuts(Mutex<Vec<Shortcut>>);
+
+/// 切换 HUD 的默认快捷键: Option + Space (macOS) / Alt + Space (Windows/Linux)
+pub fn default_hud_shortcut() -> Shortcut {
+    // Shortcut::new(修饰键, 主键)
+    // Modifiers::ALT 在 macOS 上对应 Option 键
+    Shortcut::new(Some(Modifiers::ALT), Code::Space)
+}
+
+/// 解析快捷键字符串，如 "CmdOrCtrl+Shift+W"
+pub fn parse_shortcut(value: &str) -> Option<Shortcut> {
+    Shortcut::from_str(value.trim()).ok()
+}
+
+/// 在 HUD 抢占焦点前记录前台窗口
+fn remember_frontmost(app: &tauri::AppHandle) {
+    if let Some(cache) = app.try_state::<FrontmostWindowCache>() {
+        cache.remember();
+    }
+}
+
 /// 切换 HUD 窗口的显示/隐藏状态
 #[tauri::command]
 pub async fn toggle_hud(app: tauri::AppHandle) -> Result<(), String> {
@@ -8,6 +38,7 @@ pub async fn toggle_hud(app: tauri::AppHandle) -> Result<(), String> {
         if hud_window.is_visible().unwrap_or(false) {
             hud_window.hide().map_err(|e| e.to_string())?;
         } else {
+            remember_frontmost(&app);
             hud_window.show().map_err(|e| e.to_string())?;
             hud_window.set_focus().map_err(|e| e.to_string())?;
         }
@@ -24,11 +55,86 @@ pub async fn hide_hud(app: tauri::AppHandle) -> Result<(), String> {
     Ok(())
 }
 
+/// 以捕获模板显示 HUD；HUD 已显示时只切换模板
+fn show_hud_with_profile(app: &AppHandle, profile_id: i64) {
+    if let Some(hud_window) = app.get_webview_window("hud") {
+        if !hud_window.is_visible().unwrap_or(false) {
+            remember_frontmost(app);
+            let _ = hud_window.show();
+        }
+        let _ = hud_window.set_focus();
+        emit_event(
+            &hud_window,
+            &HudFocusPayload {
+                profile_id: Some(profile_id),
+            },
+        );
+    }
+}
+
+/// 按捕获模板重新注册全局快捷键
+///
+/// 先注销上一次注册的模板快捷键；单个快捷键注册失败（如被其他程序占用）不影响其他模板，
+/// 失败的快捷键以逗号分隔返回
+pub fn register_profile_shortcuts(
+    app: &AppHandle,
+    profiles: &[CaptureProfile],
+) -> Result<(), String> {
+    let Some(registered) = app.try_state::<ProfileShortcuts>() else {
+        return Ok(());
+    };
+    let mut registered = registered
+        .0
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let global_shortcut = app.global_shortcut();
+    for shortcut in registered.drain(..) {
+        if let Err(err) = global_shortcut.unregister(shortcut) {
+            tracing::warn!(error = %err, "Unregister profile shortcut failed");
+        }
+    }
+
+    let mut failed = Vec::new();
+    for profile in profiles {
+        let Some(value) = profile.shortcut.as_deref() else {
+            continue;
+        };
+        let Some(shortcut) = parse_shortcut(value) else {
+            failed.push(value.to_string());
+            continue;
+        };
+        let profile_id = profile.profile_id;
+        let result = global_shortcut.on_shortcut(shortcut, move |app, _shortcut, event| {
+            if event.state == ShortcutState::Pressed {
+                show_hud_with_profile(app, profile_id);
+            }
+        });
+        match result {
+            Ok(()) => registered.push(shortcut),
+            Err(err) => {
+                tracing::warn!(
+                    profile_id,
+                    shortcut = value,
+                    error = %err,
+                    "Register profile shortcut failed"
+                );
+                failed.push(value.to_string());
+            }
+        }
+    }
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(failed.join(", "))
+    }
+}
+
 pub fn setup_hud(app: &App) -> Result<(), Box<dyn std::error::Error>> {
-    // 定义快捷键: Option + Space (macOS) / Alt + Space (Windows/Linux)
-    // Shortcut::new(修饰键, 主键)
-    // Modifiers::ALT 在 macOS 上对应 Option 键
-    let shortcut = Shortcut::new(Some(Modifiers::ALT), Code::Space);
+    app.manage(FrontmostWindowCache::default());
+    app.manage(ProfileShortcuts::default());
+
+    let shortcut = default_hud_shortcut();
 
     // ========== 全局快捷键注册 ==========
     // 注册 Option + Space 快捷键来切换 HUD 窗口
@@ -48,11 +154,12 @@ pub fn setup_hud(app: &App) -> Result<(), Box<dyn std::error::Error>> {
                         let _ = hud_window.hide();
                     } else {
                         // 窗口不可见则显示并聚焦
+                        remember_frontmost(&app_handle);
                         let _ = hud_window.show();
                         let _ = hud_window.set_focus();
                         // emit: 向前端发送事件，通知前端聚焦输入框
                         // 前端通过 listen("hud-focus", ...) 监听此事件
-                        let _ = hud_window.emit("hud-focus", ());
+                        emit_event(&hud_window, &HudFocusPayload { profile_id: None });
                     }
                 }
             }
@@ -69,5 +176,17 @@ pub fn setup_hud(app: &App) -> Result<(), Box<dyn std::error::Error>> {
         }
     });
 
+    // ========== 捕获模板快捷键 ==========
+    let state = app.state::<AppState>();
+    match tauri::async_runtime::block_on(async { state.ai_config.lock().await.load() }) {
+        Ok(config) => {
+            if let Err(failed) = register_profile_shortcuts(app.handle(), &config.capture_profiles)
+            {
+                tracing::warn!(shortcuts = %failed, "Capture profile shortcuts not registered");
+            }
+        }
+        Err(err) => tracing::warn!(error = %err, "Load capture profiles failed"),
+    }
+
     Ok(())
 }
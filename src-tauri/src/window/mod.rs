@@ -1,3 +1,5 @@
+mod frontmost;
 mod hud;
 
+pub use frontmost::*;
 pub use hud::*;
@@ -61,9 +61,12 @@ function App() {
           <DashboardPage
             tasks={dashboard.tasks}
             resources={dashboard.resources}
+            tagFilter={dashboard.tagFilter}
+            onTagFilterChange={dashboard.setTagFilter}
             loading={dashboard.loading}
             error={dashboard.error}
             onCapture={dashboard.handleCapture}
+            onCaptureFiles={dashboard.handleCaptureFiles}
             onRefresh={dashboard.reloadData}
             onSelectTask={nav.selectTask}
             onSelectResource={nav.selectResource}
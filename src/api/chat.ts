@@ -9,8 +9,11 @@ import type {
   CreateChatSessionResponse,
   ListChatSessionsRequest,
   ChatSession,
+  SessionRagSettings,
+  MessageSource,
   UpdateChatSessionRequest,
   DeleteChatSessionRequest,
+  ForkChatSessionRequest,
   CreateChatMessageRequest,
   CreateChatMessageResponse,
   ChatMessagePayload,
@@ -20,6 +23,10 @@ import type {
   RemoveMessageAttachmentRequest,
   SetSessionBindingsRequest,
   SetClassificationModeRequest,
+  SetSummaryLanguageRequest,
+  RetrievalConfig,
+  AnnIndexConfig,
+  ChatContextConfig,
 } from "../types";
 
 // ============================================
@@ -43,6 +50,21 @@ export const setProcessingProviderModel = (
 export const setClassificationMode = (request: SetClassificationModeRequest): Promise<void> =>
   apiCallVoid("set_classification_mode", { request });
 
+export const setSummaryLanguage = (request: SetSummaryLanguageRequest): Promise<void> =>
+  apiCallVoid("set_summary_language", { request });
+
+/** 返回限制范围后实际保存的参数 */
+export const setRetrievalConfig = (retrieval: RetrievalConfig): Promise<RetrievalConfig> =>
+  apiCall("set_retrieval_config", { retrieval });
+
+/** 返回限制范围后实际保存的参数，立即生效 */
+export const setAnnIndexConfig = (annIndex: AnnIndexConfig): Promise<AnnIndexConfig> =>
+  apiCall("set_ann_index_config", { annIndex });
+
+/** 返回限制范围后实际保存的参数 */
+export const setChatContextConfig = (chatContext: ChatContextConfig): Promise<ChatContextConfig> =>
+  apiCall("set_chat_context_config", { chatContext });
+
 // ============================================
 // Chat Streaming
 // ============================================
@@ -71,6 +93,12 @@ export const updateChatSession = (request: UpdateChatSessionRequest): Promise<vo
 export const deleteChatSession = (request: DeleteChatSessionRequest): Promise<void> =>
   apiCallVoid("delete_chat_session", { payload: request });
 
+/** 附件、引用来源、上下文绑定与 RAG 设置一并复制，原会话不变 */
+export const forkChatSession = (
+  request: ForkChatSessionRequest
+): Promise<CreateChatSessionResponse> =>
+  apiCall("fork_chat_session", { payload: request });
+
 // ============================================
 // Chat Message CRUD
 // ============================================
@@ -105,3 +133,14 @@ export const removeMessageAttachment = (request: RemoveMessageAttachmentRequest)
 
 export const setSessionBindings = (request: SetSessionBindingsRequest): Promise<void> =>
   apiCallVoid("set_session_bindings_command", { payload: request });
+
+/** 会话内各条回答引用的 RAG 来源 */
+export const listMessageSources = (sessionId: number): Promise<MessageSource[]> =>
+  apiCall("list_message_sources", { sessionId });
+
+/** 保存会话的 RAG 检索设置，全部为空时恢复全局配置 */
+export const setSessionRagSettings = (
+  sessionId: number,
+  settings: SessionRagSettings
+): Promise<SessionRagSettings> =>
+  apiCall("set_session_rag_settings", { sessionId, settings });
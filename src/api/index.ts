@@ -4,17 +4,61 @@
 export { apiCall, apiCallVoid, apiCallArray, ApiError, ApiValidationError } from "./client";
 
 // ============================================
-// System API (Dashboard, HUD, Clipboard, Assets)
+// System API (Dashboard, HUD, Clipboard, Capture Profiles, Assets)
 // ============================================
-export { fetchDashboardData, toggleHUD, hideHUD, readClipboard, getAssetsPath } from "./system";
+export {
+  fetchDashboardData,
+  toggleHUD,
+  hideHUD,
+  readClipboard,
+  previewClipboardOcr,
+  listCaptureProfiles,
+  saveCaptureProfile,
+  deleteCaptureProfile,
+  getAssetsPath,
+  getAssetsStorage,
+  relocateAssets,
+  getModelDirStatus,
+  setModelDir,
+  downloadModels,
+  verifyAssetIntegrity,
+  getLatestIntegrityReport,
+  runSelfTest,
+  listDataMigrations,
+  getJobStatus,
+  listJobs,
+  cancelJob,
+  exportVault,
+  importMarkdownFolder,
+  getMetrics,
+  getMetricsEndpointStatus,
+  setMetricsEndpoint,
+  listRetentionRules,
+  saveRetentionRule,
+  deleteRetentionRule,
+  previewRetentionRule,
+  runRetentionRulesNow,
+  setNodeArchived,
+  openSnapshot,
+  listSnapshots,
+  closeSnapshot,
+  searchSnapshot,
+  listSnapshotNodes,
+  getSnapshotNode,
+  listSnapshotTopicContents,
+  triageInbox,
+  applyInboxTriage,
+} from "./system";
 
 // ============================================
 // Node API (通用节点操作)
 // ============================================
 export {
   fetchPinnedNodes,
+  fetchNodeByUuid,
   fetchUnreviewedNodes,
   updateNodePinned,
+  updateNodeExcludeFromSearch,
   updateNodeReviewStatus,
   convertResourceToTopic,
   convertResourceToTask,
@@ -25,6 +69,7 @@ export {
   listTargetNodes,
   listSourceNodes,
   listAllEdges,
+  fetchGraph,
   listEdgesForTarget,
   confirmEdge,
 } from "./node";
@@ -34,6 +79,7 @@ export {
 // ============================================
 export {
   createTask,
+  createRecurringTask,
   softDeleteTask,
   hardDeleteTask,
   markTaskAsDone,
@@ -41,12 +87,19 @@ export {
   markTaskAsCancelled,
   updateTaskPriority,
   updateTaskDueDate,
+  updateTaskRecurrence,
   updateTaskTitle,
   updateTaskDescription,
   updateTaskSummary,
   fetchTasksByDate,
   fetchAllTasks,
   fetchActiveTasks,
+  createSubtask,
+  listSubtasks,
+  reparentTask,
+  getTaskReminder,
+  setTaskReminder,
+  snoozeTaskReminder,
 } from "./task";
 
 // ============================================
@@ -54,15 +107,29 @@ export {
 // ============================================
 export {
   quickCapture,
+  captureResourcesBatch,
+  previewArchive,
+  expandArchive,
   fetchAllResources,
   getResourceById,
   softDeleteResource,
   hardDeleteResource,
   updateResourceContent,
+  listResourceRevisions,
+  restoreResourceRevision,
+  getFileReferenceStatus,
+  listFileReferenceStatuses,
+  relinkReferencedFile,
   updateResourceTitle,
   updateResourceUserNote,
   fetchTaskResources,
   processPendingResources,
+  getEmbeddingStatusReport,
+  requeueFailedEmbeddings,
+  reclassifyResource,
+  listUnreviewedResources,
+  approveClassification,
+  rejectClassification,
 } from "./resource";
 
 // ============================================
@@ -88,12 +155,17 @@ export {
   removeApiKey,
   setProcessingProviderModel,
   setClassificationMode,
+  setSummaryLanguage,
+  setRetrievalConfig,
+  setChatContextConfig,
+  setAnnIndexConfig,
   sendChatMessage,
   createChatSession,
   getChatSession,
   listChatSessions,
   updateChatSession,
   deleteChatSession,
+  forkChatSession,
   createChatMessage,
   listChatMessages,
   updateChatMessage,
@@ -101,12 +173,39 @@ export {
   addMessageAttachments,
   removeMessageAttachment,
   setSessionBindings,
+  setSessionRagSettings,
+  listMessageSources,
 } from "./chat";
 
+// ============================================
+// Tag API
+// ============================================
+export {
+  addTag,
+  removeTag,
+  listNodeTags,
+  listTags,
+  listNodesByTag,
+  renameTag,
+  mergeTags,
+  listSuggestedTags,
+  acceptSuggestedTags,
+} from "./tags";
+
 // ============================================
 // Search API
 // ============================================
-export { searchSemantic, searchKeyword, warmupEmbedding } from "./search";
+export {
+  searchSemantic,
+  searchByImage,
+  searchKeyword,
+  warmupEmbedding,
+  getVectorStats,
+  rebuildVectorIndex,
+  optimizeVectorStore,
+  reindexEmbeddings,
+  listNodeChunks,
+} from "./search";
 
 // Re-export types for convenience
 export type { NodeRecord, SemanticSearchResult } from "../types";
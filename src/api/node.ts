@@ -3,11 +3,15 @@ import {
   nodeRecordSchema,
   edgeRecordSchema,
   edgeWithNodeSchema,
+  graphPageSchema,
   type EdgeWithNode,
+  type GraphPage,
+  type NodeType,
   type EdgeRecord,
   type NodeRecord,
   type ReviewStatus,
   type RelationType,
+  type GraphRelationType,
 } from "../types";
 import type { LinkNodesRequest, LinkNodesResponse, NodeListResponse } from "../types";
 
@@ -19,6 +23,10 @@ import type { LinkNodesRequest, LinkNodesResponse, NodeListResponse } from "../t
 export const fetchPinnedNodes = (): Promise<NodeRecord[]> =>
   apiCallArray("list_pinned_nodes", nodeRecordSchema);
 
+/** 按 uuid 获取节点（uuid 在导出 / 导入后不变，用于深链接） */
+export const fetchNodeByUuid = (uuid: string): Promise<NodeRecord> =>
+  apiCall("get_node_by_uuid_command", { uuid }, nodeRecordSchema);
+
 /** 获取所有待审核节点 */
 export const fetchUnreviewedNodes = (): Promise<NodeRecord[]> =>
   apiCallArray("list_unreviewed_nodes", nodeRecordSchema);
@@ -27,6 +35,14 @@ export const fetchUnreviewedNodes = (): Promise<NodeRecord[]> =>
 export const updateNodePinned = (nodeId: number, isPinned: boolean): Promise<void> =>
   apiCallVoid("update_node_pinned", { nodeId, isPinned });
 
+/** 排除在语义搜索与对话 RAG 之外；主题被排除时其包含的节点一并排除 */
+export const updateNodeExcludeFromSearch = (nodeId: number, excluded: boolean): Promise<void> =>
+  apiCallVoid("update_node_exclude_from_search", { nodeId, excluded });
+
+/** 主题是否接受 AI 自动归类；关闭后分类不会再把资源放入该主题 */
+export const setTopicAutoClassifyAllowed = (topicId: number, allowed: boolean): Promise<void> =>
+  apiCallVoid("set_topic_auto_classify_allowed", { topicId, allowed });
+
 /** 更新节点审核状态 */
 export const updateNodeReviewStatus = (nodeId: number, reviewStatus: ReviewStatus): Promise<void> =>
   apiCallVoid("update_node_review_status", { nodeId, reviewStatus });
@@ -126,3 +142,13 @@ export const confirmEdge = (
 /** 获取所有边（用于图谱） */
 export const listAllEdges = (): Promise<EdgeRecord[]> =>
   apiCallArray("list_all_edges_command", edgeRecordSchema);
+
+/** 图谱的一页；给出 root_node_id 时只取其 depth 步内的邻域 */
+export const fetchGraph = (request?: {
+  root_node_id?: number;
+  depth?: number;
+  node_types?: NodeType[];
+  relation_types?: GraphRelationType[];
+  limit?: number;
+  offset?: number;
+}): Promise<GraphPage> => apiCall("get_graph", { request }, graphPageSchema);
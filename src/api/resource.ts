@@ -1,6 +1,20 @@
 import { apiCall, apiCallVoid, apiCallArray } from "./client";
 import { nodeRecordSchema, type NodeRecord } from "../types";
-import type { CaptureRequest, CaptureResponse } from "../types";
+import type {
+  CaptureRequest,
+  CaptureResponse,
+  CaptureBatchRequest,
+  CaptureBatchResponse,
+  ArchivePreview,
+  ArchiveExpandSummary,
+  EmbeddingStatusReport,
+  RequeueEmbeddingsFilter,
+  ReclassifyOutcome,
+  UnreviewedClassification,
+  RejectClassificationResponse,
+  ContentRevisionRecord,
+  FileReferenceRecord,
+} from "../types";
 import { listTargetNodes } from "./node";
 
 // ============================================
@@ -10,6 +24,20 @@ import { listTargetNodes } from "./node";
 export const quickCapture = (request: CaptureRequest): Promise<CaptureResponse> =>
   apiCall("capture_resource", { payload: request });
 
+export const captureResourcesBatch = (
+  request: CaptureBatchRequest
+): Promise<CaptureBatchResponse> => apiCall("capture_resources_batch", { payload: request });
+
+// ============================================
+// 压缩包展开
+// ============================================
+
+export const previewArchive = (nodeId: number): Promise<ArchivePreview> =>
+  apiCall("preview_archive", { nodeId });
+
+export const expandArchive = (nodeId: number): Promise<ArchiveExpandSummary> =>
+  apiCall("expand_archive", { nodeId });
+
 // ============================================
 // Resource CRUD
 // ============================================
@@ -30,14 +58,56 @@ export const hardDeleteResource = (nodeId: number): Promise<void> =>
 // Resource 更新
 // ============================================
 
-export const updateResourceContent = (nodeId: number, content: string): Promise<void> =>
-  apiCallVoid("update_resource_content_command", { nodeId, content });
+/**
+ * 以下更新返回节点新的版本号；传入 expectedVersion 时，
+ * 节点在读取后被修改过（如 AI 处理写回）会以 node_edit_conflict 拒绝保存
+ */
+export const updateResourceContent = (
+  nodeId: number,
+  content: string,
+  expectedVersion?: number
+): Promise<number> =>
+  apiCall("update_resource_content_command", { nodeId, content, expectedVersion });
+
+export const updateResourceTitle = (
+  nodeId: number,
+  title: string,
+  expectedVersion?: number
+): Promise<number> =>
+  apiCall("update_resource_title_command", { nodeId, title, expectedVersion });
+
+export const updateResourceUserNote = (
+  nodeId: number,
+  userNote: string,
+  expectedVersion?: number
+): Promise<number> =>
+  apiCall("update_resource_user_note_command", { nodeId, userNote, expectedVersion });
+
+// ============================================
+// Resource 正文版本
+// ============================================
+
+export const listResourceRevisions = (nodeId: number): Promise<ContentRevisionRecord[]> =>
+  apiCall("list_resource_revisions", { nodeId });
+
+export const restoreResourceRevision = (revisionId: number): Promise<NodeRecord> =>
+  apiCall("restore_resource_revision", { revisionId }, nodeRecordSchema);
+
+// ============================================
+// 外部文件引用
+// ============================================
+
+/** 资源的引用状态；以复制方式捕获的资源返回 null */
+export const getFileReferenceStatus = (nodeId: number): Promise<FileReferenceRecord | null> =>
+  apiCall("get_file_reference_status", { nodeId });
 
-export const updateResourceTitle = (nodeId: number, title: string): Promise<void> =>
-  apiCallVoid("update_resource_title_command", { nodeId, title });
+export const listFileReferenceStatuses = (): Promise<FileReferenceRecord[]> =>
+  apiCall("list_file_reference_statuses");
 
-export const updateResourceUserNote = (nodeId: number, userNote: string): Promise<void> =>
-  apiCallVoid("update_resource_user_note_command", { nodeId, userNote });
+export const relinkReferencedFile = (
+  nodeId: number,
+  newPath: string
+): Promise<FileReferenceRecord> => apiCall("relink_referenced_file", { nodeId, newPath });
 
 // ============================================
 // Resource Processing
@@ -46,6 +116,35 @@ export const updateResourceUserNote = (nodeId: number, userNote: string): Promis
 export const processPendingResources = (): Promise<number> =>
   apiCall("process_pending_resources_command");
 
+export const getEmbeddingStatusReport = (): Promise<EmbeddingStatusReport> =>
+  apiCall("get_embedding_status_report");
+
+/** 重新入队向量化失败的资源，返回入队数量 */
+export const requeueFailedEmbeddings = (filter?: RequeueEmbeddingsFilter): Promise<number> =>
+  apiCall("requeue_failed_embeddings", { filter });
+
+/** 立即重新分类资源，hint 会作为用户提示写入分类提示词 */
+export const reclassifyResource = (nodeId: number, hint?: string): Promise<ReclassifyOutcome> =>
+  apiCall("reclassify_resource_command", { nodeId, hint });
+
+// ============================================
+// 分类审核队列
+// ============================================
+
+export const listUnreviewedResources = (): Promise<UnreviewedClassification[]> =>
+  apiCall("list_unreviewed_resources");
+
+/** 接受分类；topicId 为空时接受资源的全部自动主题关联 */
+export const approveClassification = (nodeId: number, topicId?: number): Promise<void> =>
+  apiCallVoid("approve_classification", { nodeId, topicId });
+
+/** 拒绝分类并移除自动主题关联；reclassify 为 true 时按 hint 重新分类 */
+export const rejectClassification = (
+  nodeId: number,
+  options: { topicId?: number; reclassify?: boolean; hint?: string } = {}
+): Promise<RejectClassificationResponse> =>
+  apiCall("reject_classification", { nodeId, ...options });
+
 // ============================================
 // Resource 关联查询
 // ============================================
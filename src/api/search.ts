@@ -1,21 +1,52 @@
 import { apiCall, apiCallVoid } from "./client";
-import type { NodeRecord, SemanticSearchResult } from "../types";
+import type {
+  EmbeddingModelSelection,
+  NodeRecord,
+  SemanticSearchResult,
+  StoredChunk,
+  VectorIndexStatus,
+  VectorMaintenanceReport,
+  VectorStats,
+} from "../types";
 
 // ============================================
 // Search API
 // ============================================
 
+/** excludeNodeIds / excludeTopicIds 只对本次查询生效，主题会连同其包含的节点一起排除 */
 export const searchSemantic = (
   query: string,
   scopeNodeIds?: number[],
   embeddingType?: "summary" | "content",
-  limit?: number
+  limit?: number,
+  excludeNodeIds?: number[],
+  excludeTopicIds?: number[]
 ): Promise<SemanticSearchResult[]> =>
   apiCall("search_semantic", {
     query,
     scopeNodeIds,
     embeddingType,
     limit,
+    excludeNodeIds,
+    excludeTopicIds,
+  });
+
+/** 以图搜图；给出 query 时与文字搜索结果按排名融合 */
+export const searchByImage = (
+  path: string,
+  limit?: number,
+  query?: string,
+  scopeNodeIds?: number[],
+  excludeNodeIds?: number[],
+  excludeTopicIds?: number[]
+): Promise<SemanticSearchResult[]> =>
+  apiCall("search_by_image", {
+    path,
+    limit,
+    query,
+    scopeNodeIds,
+    excludeNodeIds,
+    excludeTopicIds,
   });
 
 export const searchKeyword = (
@@ -31,3 +62,26 @@ export const searchKeyword = (
 
 export const warmupEmbedding = (): Promise<void> =>
   apiCallVoid("warmup_embedding");
+
+// ============================================
+// Vector Store
+// ============================================
+
+export const getVectorStats = (): Promise<VectorStats> => apiCall("get_vector_stats");
+
+/** 重建向量列的 ANN 索引，行数不足 256 的列跳过 */
+export const rebuildVectorIndex = (): Promise<VectorIndexStatus[]> =>
+  apiCall("rebuild_vector_index");
+
+/** 删除孤立向量并整理向量表 */
+export const optimizeVectorStore = (): Promise<VectorMaintenanceReport> =>
+  apiCall("optimize_vector_store");
+
+/** 换用新的嵌入模型重建全部向量，完成后切换到新表 */
+/** 返回任务 ID，完成后任务结果为 EmbeddingReindexReport */
+export const reindexEmbeddings = (models: EmbeddingModelSelection): Promise<number> =>
+  apiCall("reindex_embeddings", { models });
+
+/** 节点在向量库中实际存储的分块 */
+export const listNodeChunks = (nodeId: number): Promise<StoredChunk[]> =>
+  apiCall("list_node_chunks", { nodeId });
@@ -1,12 +1,41 @@
-import { apiCall, apiCallVoid } from "./client";
-import { dashboardSchema, type DashboardData, type ReadClipboardResponse } from "../types";
+import { apiCall, apiCallArray, apiCallVoid } from "./client";
+import {
+  dashboardSchema,
+  nodeRecordSchema,
+  type AssetsRelocateSummary,
+  type AssetsStorageStatus,
+  type CaptureProfile,
+  type ClipboardOcrPreview,
+  type DashboardData,
+  type DataMigrationRecord,
+  type JobSnapshot,
+  type InboxTriagePlan,
+  type IntegrityReport,
+  type MetricsEndpointStatus,
+  type MetricsSnapshot,
+  type ModelDirStatus,
+  type NodeRecord,
+  type NodeType,
+  type ReadClipboardResponse,
+  type RetentionPreview,
+  type RetentionRuleInput,
+  type RetentionRuleRecord,
+  type RetentionRunResult,
+  type SaveCaptureProfileRequest,
+  type SelfTestReport,
+  type SnapshotInfo,
+  type SnapshotSearchHit,
+  type TriageApplyReport,
+  type TriageDecision,
+} from "../types";
 
 // ============================================
 // Dashboard
 // ============================================
 
-export const fetchDashboardData = (): Promise<DashboardData> =>
-  apiCall("get_dashboard", undefined, dashboardSchema);
+/** 传入 tagIds 时只返回同时带有这些标签的任务和资源 */
+export const fetchDashboardData = (tagIds?: number[]): Promise<DashboardData> =>
+  apiCall("get_dashboard", tagIds?.length ? { tagIds } : undefined, dashboardSchema);
 
 // ============================================
 // HUD
@@ -25,9 +54,182 @@ export const hideHUD = (): Promise<void> =>
 export const readClipboard = (): Promise<ReadClipboardResponse> =>
   apiCall("read_clipboard");
 
+/** 剪贴板没有图片时返回 null */
+export const previewClipboardOcr = (): Promise<ClipboardOcrPreview | null> =>
+  apiCall("preview_clipboard_ocr");
+
+// ============================================
+// Capture Profiles
+// ============================================
+
+export const listCaptureProfiles = (): Promise<CaptureProfile[]> =>
+  apiCall("list_capture_profiles");
+
+export const saveCaptureProfile = (
+  payload: SaveCaptureProfileRequest
+): Promise<CaptureProfile> =>
+  apiCall("save_capture_profile", { payload });
+
+export const deleteCaptureProfile = (profileId: number): Promise<void> =>
+  apiCallVoid("delete_capture_profile", { profileId });
+
 // ============================================
 // File System
 // ============================================
 
 export const getAssetsPath = (): Promise<string> =>
   apiCall("get_assets_path");
+
+export const getAssetsStorage = (): Promise<AssetsStorageStatus> =>
+  apiCall("get_assets_storage");
+
+export const relocateAssets = (newPath: string): Promise<AssetsRelocateSummary> =>
+  apiCall("relocate_assets", { newPath });
+
+// ============================================
+// Model Directory
+// ============================================
+
+export const getModelDirStatus = (): Promise<ModelDirStatus> =>
+  apiCall("get_model_dir_status");
+
+/** path 为 null 时恢复自动查找 */
+export const setModelDir = (path: string | null): Promise<ModelDirStatus> =>
+  apiCall("set_model_dir", { path });
+
+/** 下载缺失的模型文件，进度见 model-download-progress 事件 */
+export const downloadModels = (): Promise<ModelDirStatus> => apiCall("download_models");
+
+// ============================================
+// File Integrity
+// ============================================
+
+export const verifyAssetIntegrity = (reparse: boolean): Promise<IntegrityReport> =>
+  apiCall("verify_asset_integrity", { reparse });
+
+export const getLatestIntegrityReport = (): Promise<IntegrityReport | null> =>
+  apiCall("get_latest_integrity_report");
+
+/** 自检打包相关的依赖（迁移、模型目录、OCR、LanceDB、本地 whisper），供诊断页面展示 */
+export const runSelfTest = (): Promise<SelfTestReport> => apiCall("run_self_test");
+
+// ============================================
+// Data Migrations
+// ============================================
+
+export const listDataMigrations = (): Promise<DataMigrationRecord[]> =>
+  apiCall("list_data_migrations_command");
+
+// ============================================
+// Background Jobs
+// ============================================
+
+export const getJobStatus = (jobId: number): Promise<JobSnapshot> =>
+  apiCall("get_job_status", { jobId });
+
+export const listJobs = (): Promise<JobSnapshot[]> => apiCall("list_jobs");
+
+/** 任务在下一个检查点停止，状态变为 cancelled */
+export const cancelJob = (jobId: number): Promise<JobSnapshot> =>
+  apiCall("cancel_job", { jobId });
+
+/** 整库导出为 Markdown 文件夹，返回任务 ID；结果（VaultFolderExport）见任务状态 */
+export const exportVault = (folder: string): Promise<number> =>
+  apiCall("export_vault", { folder });
+
+/** 导入 Markdown 文件夹（如 Obsidian 仓库），返回任务 ID；结果（MarkdownImportSummary）见任务状态 */
+export const importMarkdownFolder = (folder: string): Promise<number> =>
+  apiCall("import_markdown_folder", { folder });
+
+// ============================================
+// Metrics
+// ============================================
+
+/** 流水线、LLM 请求、检索耗时直方图与数据库错误等计数 */
+export const getMetrics = (): Promise<MetricsSnapshot> => apiCall("get_metrics");
+
+export const getMetricsEndpointStatus = (): Promise<MetricsEndpointStatus> =>
+  apiCall("get_metrics_endpoint_status");
+
+/** 开关本机 Prometheus 端点（127.0.0.1:<port>/metrics）；端口被占用时报错且不保存 */
+export const setMetricsEndpoint = (
+  enabled: boolean,
+  port?: number
+): Promise<MetricsEndpointStatus> =>
+  apiCall("set_metrics_endpoint", { enabled, port });
+
+// ============================================
+// Retention
+// ============================================
+
+export const listRetentionRules = (): Promise<RetentionRuleRecord[]> =>
+  apiCall("list_retention_rules");
+
+/** ruleId 为空时新建 */
+export const saveRetentionRule = (
+  rule: RetentionRuleInput,
+  ruleId?: number
+): Promise<RetentionRuleRecord> => apiCall("save_retention_rule", { ruleId, rule });
+
+export const deleteRetentionRule = (ruleId: number): Promise<void> =>
+  apiCallVoid("delete_retention_rule", { ruleId });
+
+/** 预览规则当前会处理的资源，不做修改 */
+export const previewRetentionRule = (rule: RetentionRuleInput): Promise<RetentionPreview> =>
+  apiCall("preview_retention_rule", { rule });
+
+export const runRetentionRulesNow = (): Promise<RetentionRunResult[]> =>
+  apiCall("run_retention_rules_now");
+
+export const setNodeArchived = (nodeId: number, archived: boolean): Promise<void> =>
+  apiCallVoid("set_node_archived", { nodeId, archived });
+
+// ============================================
+// Snapshots（只读查看备份）
+// ============================================
+
+/** 解压并只读打开 archive 导出，不影响当前数据库 */
+export const openSnapshot = (path: string): Promise<SnapshotInfo> =>
+  apiCall("open_snapshot", { path });
+
+export const listSnapshots = (): Promise<SnapshotInfo[]> => apiCall("list_snapshots");
+
+/** 关闭备份并删除临时文件 */
+export const closeSnapshot = (sessionId: number): Promise<void> =>
+  apiCallVoid("close_snapshot", { sessionId });
+
+/** 备份内只有全文检索，没有语义检索 */
+export const searchSnapshot = (
+  sessionId: number,
+  query: string,
+  nodeType?: NodeType,
+  limit?: number
+): Promise<SnapshotSearchHit[]> =>
+  apiCall("search_snapshot", { sessionId, query, nodeType, limit });
+
+export const listSnapshotNodes = (
+  sessionId: number,
+  nodeType: NodeType
+): Promise<NodeRecord[]> =>
+  apiCallArray("list_snapshot_nodes", nodeRecordSchema, { sessionId, nodeType });
+
+export const getSnapshotNode = (sessionId: number, nodeId: number): Promise<NodeRecord> =>
+  apiCall("get_snapshot_node", { sessionId, nodeId }, nodeRecordSchema);
+
+export const listSnapshotTopicContents = (
+  sessionId: number,
+  topicId: number
+): Promise<NodeRecord[]> =>
+  apiCallArray("list_snapshot_topic_contents", nodeRecordSchema, { sessionId, topicId });
+
+// ============================================
+// Inbox Triage
+// ============================================
+
+/** 让 LLM 为收件箱中的资源生成处理建议，不修改数据 */
+export const triageInbox = (limit?: number): Promise<InboxTriagePlan> =>
+  apiCall("triage_inbox", { limit });
+
+/** 逐条执行勾选的建议，单条失败不影响其余 */
+export const applyInboxTriage = (decisions: TriageDecision[]): Promise<TriageApplyReport> =>
+  apiCall("apply_inbox_triage_command", { decisions });
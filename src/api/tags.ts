@@ -0,0 +1,52 @@
+import { apiCall, apiCallVoid, apiCallArray } from "./client";
+import {
+  nodeRecordSchema,
+  type NodeRecord,
+  type TagRecord,
+  type TagSuggestionRecord,
+  type TagUsageRecord,
+} from "../types";
+
+// ============================================
+// 标签
+// ============================================
+
+/** 给节点打标签，同名标签（忽略大小写）复用 */
+export const addTag = (nodeId: number, name: string): Promise<TagRecord> =>
+  apiCall("add_tag", { nodeId, name });
+
+/** 移除节点上的标签 */
+export const removeTag = (nodeId: number, tagId: number): Promise<void> =>
+  apiCallVoid("remove_tag", { nodeId, tagId });
+
+/** 获取节点的标签 */
+export const listNodeTags = (nodeId: number): Promise<TagRecord[]> =>
+  apiCall("list_node_tags", { nodeId });
+
+/** 获取全部标签及使用次数 */
+export const listTags = (): Promise<TagUsageRecord[]> =>
+  apiCall("list_tags");
+
+/** 获取带有某个标签的节点 */
+export const listNodesByTag = (tagId: number): Promise<NodeRecord[]> =>
+  apiCallArray("list_nodes_by_tag", nodeRecordSchema, { tagId });
+
+/** 重命名标签；名称已被占用时报错，应改用合并 */
+export const renameTag = (tagId: number, name: string): Promise<TagRecord> =>
+  apiCall("rename_tag", { tagId, name });
+
+/** 把多个标签合并到目标标签 */
+export const mergeTags = (sourceTagIds: number[], targetTagId: number): Promise<TagRecord> =>
+  apiCall("merge_tags", { sourceTagIds, targetTagId });
+
+/** 获取 AI 建议、尚待确认的标签 */
+export const listSuggestedTags = (nodeId: number): Promise<TagSuggestionRecord[]> =>
+  apiCall("list_suggested_tags", { nodeId });
+
+/** 采纳 / 拒绝 AI 建议的标签，返回节点当前的标签 */
+export const acceptSuggestedTags = (
+  nodeId: number,
+  acceptedIds: number[],
+  rejectedIds: number[] = []
+): Promise<TagRecord[]> =>
+  apiCall("accept_suggested_tags", { nodeId, acceptedIds, rejectedIds });
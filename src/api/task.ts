@@ -1,6 +1,19 @@
 import { apiCall, apiCallVoid, apiCallArray } from "./client";
-import { nodeRecordSchema, type NodeRecord } from "../types";
-import type { CreateTaskRequest, CreateTaskResponse } from "../types";
+import {
+  nodeRecordSchema,
+  taskBoardColumnSchema,
+  taskListItemSchema,
+  type NodeRecord,
+  type TaskBoardColumn,
+  type TaskListItem,
+} from "../types";
+import type {
+  CreateRecurringTaskRequest,
+  CreateTaskRequest,
+  CreateTaskResponse,
+  TaskReminderRecord,
+  TaskStatus,
+} from "../types";
 
 // ============================================
 // Task CRUD 操作
@@ -9,6 +22,24 @@ import type { CreateTaskRequest, CreateTaskResponse } from "../types";
 export const createTask = (request: CreateTaskRequest): Promise<CreateTaskResponse> =>
   apiCall("create_task", { payload: request });
 
+/** 重复任务：完成后自动生成下一次 */
+export const createRecurringTask = (
+  request: CreateRecurringTaskRequest
+): Promise<CreateTaskResponse> => apiCall("create_recurring_task", { payload: request });
+
+export const createSubtask = (
+  parentTaskId: number,
+  request: CreateTaskRequest
+): Promise<CreateTaskResponse> =>
+  apiCall("create_subtask", { parentTaskId, payload: request });
+
+export const listSubtasks = (nodeId: number): Promise<NodeRecord[]> =>
+  apiCallArray("list_subtasks", nodeRecordSchema, { nodeId });
+
+/** parentTaskId 为 null 时移到顶层 */
+export const reparentTask = (nodeId: number, parentTaskId: number | null): Promise<void> =>
+  apiCallVoid("reparent_task", { nodeId, parentTaskId });
+
 export const softDeleteTask = (nodeId: number): Promise<void> =>
   apiCallVoid("soft_delete_task_command", { nodeId });
 
@@ -38,14 +69,34 @@ export const updateTaskPriority = (nodeId: number, priority: string): Promise<vo
 export const updateTaskDueDate = (nodeId: number, dueDate: string | null): Promise<void> =>
   apiCallVoid("update_task_due_date_command", { nodeId, dueDate });
 
-export const updateTaskTitle = (nodeId: number, title: string): Promise<void> =>
-  apiCallVoid("update_task_title_command", { nodeId, title });
-
-export const updateTaskDescription = (nodeId: number, description: string | null): Promise<void> =>
-  apiCallVoid("update_task_description_command", { nodeId, description });
-
-export const updateTaskSummary = (nodeId: number, summary: string | null): Promise<void> =>
-  apiCallVoid("update_task_summary_command", { nodeId, summary });
+/** 返回新的版本号；expectedVersion 不一致时拒绝保存 */
+export const updateTaskTitle = (
+  nodeId: number,
+  title: string,
+  expectedVersion?: number
+): Promise<number> =>
+  apiCall("update_task_title_command", { nodeId, title, expectedVersion });
+
+export const updateTaskDescription = (
+  nodeId: number,
+  description: string | null,
+  expectedVersion?: number
+): Promise<number> =>
+  apiCall("update_task_description_command", { nodeId, description, expectedVersion });
+
+/** recurrenceRule 为 null 时取消重复 */
+export const updateTaskRecurrence = (
+  nodeId: number,
+  recurrenceRule: string | null
+): Promise<NodeRecord> =>
+  apiCall("update_task_recurrence", { nodeId, recurrenceRule }, nodeRecordSchema);
+
+export const updateTaskSummary = (
+  nodeId: number,
+  summary: string | null,
+  expectedVersion?: number
+): Promise<number> =>
+  apiCall("update_task_summary_command", { nodeId, summary, expectedVersion });
 
 // ============================================
 // Task 查询
@@ -54,8 +105,52 @@ export const updateTaskSummary = (nodeId: number, summary: string | null): Promi
 export const fetchTasksByDate = (date: string): Promise<NodeRecord[]> =>
   apiCallArray("get_tasks_by_date", nodeRecordSchema, { date });
 
-export const fetchAllTasks = (): Promise<NodeRecord[]> =>
-  apiCallArray("get_all_tasks", nodeRecordSchema);
+export const fetchAllTasks = (): Promise<TaskListItem[]> =>
+  apiCallArray("get_all_tasks", taskListItemSchema);
 
 export const fetchActiveTasks = (): Promise<NodeRecord[]> =>
   apiCallArray("get_active_tasks", nodeRecordSchema);
+
+// ============================================
+// Task 看板
+// ============================================
+
+/** 按 todo、done、cancelled 分列，列内按拖拽顺序排列 */
+export const fetchTaskBoard = (): Promise<TaskBoardColumn[]> =>
+  apiCallArray("get_task_board", taskBoardColumnSchema);
+
+/** 把任务拖到 status 列的 position 处（从 0 开始），返回移动后的看板 */
+export const moveTaskOnBoard = (
+  nodeId: number,
+  status: TaskStatus,
+  position: number
+): Promise<TaskBoardColumn[]> =>
+  apiCallArray("move_task_on_board", taskBoardColumnSchema, { nodeId, status, position });
+
+// ============================================
+// Task 提醒
+// ============================================
+
+export const getTaskReminder = (nodeId: number): Promise<TaskReminderRecord | null> =>
+  apiCall("get_task_reminder_command", { nodeId });
+
+/** minutesBefore 为 null 时关闭提醒 */
+export const setTaskReminder = (
+  nodeId: number,
+  minutesBefore: number | null
+): Promise<TaskReminderRecord | null> =>
+  apiCall("set_task_reminder", { nodeId, minutesBefore });
+
+/** minutes 省略时使用默认稍后提醒时长 */
+export const snoozeTaskReminder = (
+  nodeId: number,
+  minutes?: number
+): Promise<TaskReminderRecord> => apiCall("snooze_task_reminder", { nodeId, minutes });
+
+// ============================================
+// Task 日历导出
+// ============================================
+
+/** 有截止日期的任务导出为 .ics 文件，返回导出的事件数 */
+export const exportTasksIcs = (path: string): Promise<number> =>
+  apiCall("export_tasks_ics", { path });
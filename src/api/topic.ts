@@ -27,11 +27,20 @@ export const hardDeleteTopic = (nodeId: number): Promise<void> =>
 // Topic 更新
 // ============================================
 
-export const updateTopicTitle = (nodeId: number, title: string): Promise<void> =>
-  apiCallVoid("update_topic_title_command", { topicId: nodeId, title });
-
-export const updateTopicSummary = (nodeId: number, summary: string | null): Promise<void> =>
-  apiCallVoid("update_topic_summary_command", { topicId: nodeId, summary });
+/** 返回新的版本号；expectedVersion 不一致时拒绝保存 */
+export const updateTopicTitle = (
+  nodeId: number,
+  title: string,
+  expectedVersion?: number
+): Promise<number> =>
+  apiCall("update_topic_title_command", { topicId: nodeId, title, expectedVersion });
+
+export const updateTopicSummary = (
+  nodeId: number,
+  summary: string | null,
+  expectedVersion?: number
+): Promise<number> =>
+  apiCall("update_topic_summary_command", { topicId: nodeId, summary, expectedVersion });
 
 export const updateTopicFavourite = (nodeId: number, isFavourite: boolean): Promise<void> =>
   apiCallVoid("update_topic_favourite_command", {
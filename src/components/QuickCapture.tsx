@@ -13,8 +13,9 @@ import { Textarea } from "@/components/ui/textarea";
 import { Card, CardContent } from "@/components/ui/card";
 import { Badge } from "@/components/ui/badge";
 import { Paperclip, ArrowUp, X, MessageSquare } from "lucide-react";
-import { readClipboard } from "@/api";
-import { ClipboardContent, InputMode } from "@/types";
+import { previewClipboardOcr, readClipboard } from "@/api";
+import { codeToMarkdown, mayNeedSmartPaste, tableToMarkdown } from "@/lib/clipboard";
+import { ClipboardContent, FileStorageMode, InputMode } from "@/types";
 import { useLanguage } from "@/contexts/LanguageContext";
 
 // variant: 组件变体，用于添加不同的 CSS 类
@@ -27,11 +28,25 @@ interface SelectedFile {
   path: string; // 文件绝对路径或相对路径（从剪贴板粘贴的图片）
   name: string; // 文件名（用于显示）
   isFromClipboard?: boolean; // 是否来自剪贴板（图片已保存到 assets）
+  ocrText?: string; // HUD 中预览并可修改的 OCR 文本，捕获时替代后台识别
+  ocrError?: string | null; // 预览识别失败的原因
 }
 
 interface QuickCaptureProps {
-  // content: 文本内容, filePath: 文件路径（二选一或都有）
-  onCapture: (content: string, filePath?: string) => Promise<void>;
+  // content: 文本内容, filePath: 文件路径（二选一或都有）, storage: 文件的捕获方式
+  // ocrText: 剪贴板图片经用户确认的 OCR 文本
+  onCapture: (
+    content: string,
+    filePath?: string,
+    storage?: FileStorageMode,
+    ocrText?: string
+  ) => Promise<void>;
+  // 多个文件时一次提交（content 作为每个文件的备注）；未提供则逐个调用 onCapture
+  onCaptureFiles?: (
+    content: string,
+    filePaths: string[],
+    storage?: FileStorageMode
+  ) => Promise<void>;
   loading?: boolean;
   variant?: QuickCaptureVariant;
   // 成功后的回调（HUD 用于隐藏窗口）
@@ -51,6 +66,7 @@ interface QuickCaptureProps {
 
 export function QuickCapture({
   onCapture,
+  onCaptureFiles,
   loading = false,
   variant = "card",
   onSuccess,
@@ -63,6 +79,8 @@ export function QuickCapture({
 }: QuickCaptureProps) {
   const [content, setContent] = useState("");
   const [selectedFiles, setSelectedFiles] = useState<SelectedFile[]>([]);
+  // 文件捕获方式：复制到资源库或原地引用（剪贴板图片已在 assets 中，不受影响）
+  const [storage, setStorage] = useState<FileStorageMode>("copy");
   const [isSubmitting, setIsSubmitting] = useState(false);
   const textareaRef = useRef<HTMLTextAreaElement>(null);
   const { t } = useLanguage();
@@ -153,15 +171,27 @@ export function QuickCapture({
       if (selectedFiles.length === 0) {
         // 只有文本，没有文件
         await onCapture(text);
+      } else if (
+        selectedFiles.length > 1 &&
+        onCaptureFiles &&
+        // 批量捕获不带 OCR 文本，有预览过的图片时逐个提交
+        !selectedFiles.some((file) => file.ocrText !== undefined)
+      ) {
+        await onCaptureFiles(
+          text,
+          selectedFiles.map((file) => file.path),
+          storage
+        );
       } else {
         // 有文件：每个文件都使用相同的文本
         for (const file of selectedFiles) {
-          await onCapture(text, file.path);
+          await onCapture(text, file.path, storage, file.ocrText);
         }
       }
 
       setContent("");
       setSelectedFiles([]);
+      setStorage("copy");
 
       // 重置 textarea 高度
       if (textareaRef.current) {
@@ -192,6 +222,7 @@ export function QuickCapture({
     // 检查是否有文件或图片（通过原生 clipboardData）
     const items = e.clipboardData?.items;
     let hasFileOrImage = false;
+    let hasImage = false;
 
     if (items) {
       for (let i = 0; i < items.length; i++) {
@@ -199,13 +230,41 @@ export function QuickCapture({
         // 检查是否是文件类型（图片也是文件）
         if (item.kind === "file") {
           hasFileOrImage = true;
+          hasImage = item.type.startsWith("image/");
           break;
         }
       }
     }
 
-    // 如果有文件或图片，使用 Rust 后端读取剪贴板
-    if (hasFileOrImage) {
+    // HUD 中粘贴图片：先预览 OCR 结果，用户可在捕获前修改
+    if (isHUD && hasImage) {
+      e.preventDefault();
+      try {
+        const preview = await previewClipboardOcr();
+        if (preview) {
+          setSelectedFiles((prev) => [
+            ...prev,
+            {
+              path: preview.file_path,
+              name: preview.file_name,
+              isFromClipboard: true,
+              ocrText: preview.text,
+              ocrError: preview.error,
+            },
+          ]);
+          return;
+        }
+        // 剪贴板里实际是文件（如复制文件附带的预览图），按普通粘贴处理
+        const response = await readClipboard();
+        handleClipboardContent(response.content);
+      } catch (err) {
+        console.error("读取剪贴板失败:", err);
+      }
+      return;
+    }
+
+    // 如果有文件或图片，或可能是表格 / 代码，使用 Rust 后端读取剪贴板
+    if (hasFileOrImage || mayNeedSmartPaste(e.clipboardData)) {
       e.preventDefault(); // 阻止默认粘贴行为
 
       try {
@@ -215,7 +274,7 @@ export function QuickCapture({
         console.error("读取剪贴板失败:", err);
       }
     }
-    // 如果是纯文本，让浏览器默认处理
+    // 单行纯文本，让浏览器默认处理
   };
 
   // 处理剪贴板内容
@@ -263,6 +322,18 @@ export function QuickCapture({
         appendContent(clipboardContent.data.content);
         break;
 
+      case "Table":
+        // 表格：转为 Markdown 表格保存
+        appendContent(tableToMarkdown(clipboardContent.data.rows));
+        break;
+
+      case "Code":
+        // 代码：包成带语言标记的代码块
+        appendContent(
+          codeToMarkdown(clipboardContent.data.content, clipboardContent.data.language)
+        );
+        break;
+
       case "Empty":
         // 剪贴板为空，不做处理
         break;
@@ -287,6 +358,26 @@ export function QuickCapture({
               "gif",
               "webp",
               "epub",
+              "docx",
+              "pptx",
+              "xlsx",
+              "odt",
+              "odp",
+              "ods",
+              "ipynb",
+              "eml",
+              "msg",
+              "zip",
+              "tar",
+              "gz",
+              "tgz",
+              "mp3",
+              "wav",
+              "m4a",
+              "ogg",
+              "flac",
+              "aac",
+              "opus",
             ],
           },
           { name: "文本文件", extensions: ["txt", "md"] },
@@ -294,7 +385,15 @@ export function QuickCapture({
             name: "图片",
             extensions: ["png", "jpg", "jpeg", "gif", "webp", "bmp", "svg"],
           },
-          { name: "文档", extensions: ["pdf", "epub"] },
+          {
+            name: "文档",
+            extensions: ["pdf", "epub", "docx", "pptx", "xlsx", "odt", "odp", "ods", "ipynb", "eml", "msg"],
+          },
+          {
+            name: "音频",
+            extensions: ["mp3", "wav", "m4a", "ogg", "flac", "aac", "opus"],
+          },
+          { name: "压缩包", extensions: ["zip", "tar", "gz", "tgz"] },
           { name: "所有文件", extensions: ["*"] },
         ],
       });
@@ -321,6 +420,13 @@ export function QuickCapture({
     setSelectedFiles((prev) => prev.filter((_, i) => i !== index));
   };
 
+  const handleOcrTextChange = (index: number, ocrText: string) => {
+    setSelectedFiles((prev) =>
+      prev.map((file, i) => (i === index ? { ...file, ocrText } : file))
+    );
+  };
+
+  const hasDiskFiles = selectedFiles.some((file) => !file.isFromClipboard);
   const isLoading = loading || isSubmitting;
   const canSubmit = (content.trim() || selectedFiles.length > 0) && !isLoading;
 
@@ -356,6 +462,53 @@ export function QuickCapture({
                   </button>
                 </div>
               ))}
+              {hasDiskFiles && (
+                <div
+                  className="flex items-center rounded-md border border-border text-xs overflow-hidden"
+                  title={t("dashboard", "storageReferenceDesc")}
+                >
+                  {(["copy", "reference"] as const).map((mode) => (
+                    <button
+                      key={mode}
+                      type="button"
+                      onClick={() => setStorage(mode)}
+                      className={cn(
+                        "px-2 py-1",
+                        storage === mode
+                          ? "bg-foreground text-background"
+                          : "text-muted-foreground hover:text-foreground"
+                      )}
+                    >
+                      {mode === "copy"
+                        ? t("dashboard", "storageCopy")
+                        : t("dashboard", "storageReference")}
+                    </button>
+                  ))}
+                </div>
+              )}
+              {selectedFiles.map(
+                (file, index) =>
+                  file.ocrText !== undefined && (
+                    <div key={`ocr-${file.path}-${index}`} className="w-full space-y-1">
+                      <div className="text-xs text-muted-foreground">
+                        {t("dashboard", "ocrPreview")} · {file.name}
+                      </div>
+                      <Textarea
+                        value={file.ocrText}
+                        onChange={(e) => handleOcrTextChange(index, e.target.value)}
+                        placeholder={
+                          file.ocrError
+                            ? t("dashboard", "ocrPreviewFailed")
+                            : t("dashboard", "ocrPreviewEmpty")
+                        }
+                        className="min-h-[60px] max-h-[120px] text-xs resize-none"
+                      />
+                      {file.ocrError && (
+                        <div className="text-xs text-destructive">{file.ocrError}</div>
+                      )}
+                    </div>
+                  )
+              )}
             </div>
           )}
 
@@ -0,0 +1,66 @@
+import { useEffect, useState } from "react";
+import { listTags } from "@/api";
+import type { TagUsageRecord } from "@/types";
+import { useLanguage } from "@/contexts/LanguageContext";
+import { cn } from "@/lib/utils";
+
+interface TagFilterProps {
+  selected: number[];
+  onChange: (tagIds: number[]) => void;
+}
+
+// 首页标签筛选：选中多个标签时取交集；没有标签时不显示
+export function TagFilter({ selected, onChange }: TagFilterProps) {
+  const { t } = useLanguage();
+  const [tags, setTags] = useState<TagUsageRecord[]>([]);
+
+  useEffect(() => {
+    let cancelled = false;
+    listTags()
+      .then((records) => {
+        if (!cancelled) setTags(records.filter((tag) => tag.node_count > 0));
+      })
+      .catch(console.error);
+    return () => {
+      cancelled = true;
+    };
+  }, []);
+
+  if (tags.length === 0) return null;
+
+  const toggle = (tagId: number) =>
+    onChange(
+      selected.includes(tagId) ? selected.filter((id) => id !== tagId) : [...selected, tagId]
+    );
+
+  return (
+    <div className="flex flex-wrap items-center gap-1.5">
+      <span className="text-xs text-muted-foreground/70">{t("dashboard", "tagFilter")}</span>
+      {tags.map((tag) => (
+        <button
+          key={tag.tag_id}
+          type="button"
+          onClick={() => toggle(tag.tag_id)}
+          className={cn(
+            "h-6 rounded-full border px-2.5 text-xs transition-colors",
+            selected.includes(tag.tag_id)
+              ? "border-foreground bg-foreground text-background"
+              : "border-border/60 text-muted-foreground hover:text-foreground"
+          )}
+        >
+          {tag.name}
+          <span className="ml-1 opacity-60">{tag.node_count}</span>
+        </button>
+      ))}
+      {selected.length > 0 && (
+        <button
+          type="button"
+          onClick={() => onChange([])}
+          className="h-6 px-1.5 text-xs text-muted-foreground hover:text-foreground"
+        >
+          {t("dashboard", "tagFilterClear")}
+        </button>
+      )}
+    </div>
+  );
+}
@@ -23,12 +23,18 @@ import {
   PopoverTrigger,
 } from "@/components/ui/popover";
 import { Calendar } from "@/components/ui/calendar";
-import { Calendar as CalendarIcon } from "lucide-react";
+import { Calendar as CalendarIcon, CheckCircle2, Circle, Plus } from "lucide-react";
 import { format } from "date-fns";
 import { cn } from "@/lib/utils";
 import { NodeRecord, TaskStatus, TaskPriority, priorityConfig } from "../types";
 import {
   createTask,
+  createRecurringTask,
+  createSubtask,
+  listSubtasks,
+  updateTaskRecurrence,
+  getTaskReminder,
+  setTaskReminder,
   updateTaskTitle,
   updateTaskDescription,
   updateTaskPriority,
@@ -51,8 +57,30 @@ interface FormData {
   status: TaskStatus;
   priority: TaskPriority;
   due_date: Date | undefined; // 改为 Date 对象
+  recurrence: string; // 重复规则，"none" 表示不重复
+  reminder: string; // 提前提醒的分钟数，"none" 表示不提醒
 }
 
+// 常用重复规则；其他规则（如指定星期几）原样显示
+const RECURRENCE_PRESETS: { value: string; label: string }[] = [
+  { value: "none", label: "不重复" },
+  { value: "FREQ=DAILY", label: "每天" },
+  { value: "FREQ=WEEKLY", label: "每周" },
+  { value: "FREQ=MONTHLY", label: "每月" },
+  { value: "FREQ=YEARLY", label: "每年" },
+];
+
+// 截止前提醒；全天任务从当天 00:00 起算
+const REMINDER_PRESETS: { value: string; label: string }[] = [
+  { value: "none", label: "不提醒" },
+  { value: "0", label: "准时" },
+  { value: "5", label: "提前 5 分钟" },
+  { value: "15", label: "提前 15 分钟" },
+  { value: "30", label: "提前 30 分钟" },
+  { value: "60", label: "提前 1 小时" },
+  { value: "1440", label: "提前 1 天" },
+];
+
 export function TaskEditCard({
   task,
   open,
@@ -70,6 +98,8 @@ export function TaskEditCard({
         status: task.task_status ?? "todo",
         priority: task.priority ?? "medium",
         due_date: task.due_date || undefined,
+        recurrence: task.recurrence_rule ?? "none",
+        reminder: "none",
       };
     }
     return {
@@ -78,21 +108,85 @@ export function TaskEditCard({
       status: "todo",
       priority: "medium",
       due_date: undefined,
+      recurrence: "none",
+      reminder: "none",
     };
   };
 
   const [formData, setFormData] = useState<FormData>(getInitialFormData());
   const [loading, setLoading] = useState(false);
   const [error, setError] = useState<string | null>(null);
+  const [subtasks, setSubtasks] = useState<NodeRecord[]>([]);
+  const [subtaskTitle, setSubtaskTitle] = useState("");
+  const [savedReminder, setSavedReminder] = useState("none");
+
+  const loadReminder = async (nodeId: number) => {
+    try {
+      const reminder = await getTaskReminder(nodeId);
+      const value = reminder ? String(reminder.minutes_before) : "none";
+      setSavedReminder(value);
+      setFormData((prev) => ({ ...prev, reminder: value }));
+    } catch (err) {
+      console.error("加载提醒失败:", err);
+    }
+  };
+
+  const reloadSubtasks = async (parentId: number) => {
+    try {
+      setSubtasks(await listSubtasks(parentId));
+    } catch (err) {
+      console.error("加载子任务失败:", err);
+    }
+  };
 
   // 当 task 或 open 变化时重置表单
   useEffect(() => {
     if (open) {
       setFormData(getInitialFormData());
       setError(null);
+      setSubtasks([]);
+      setSubtaskTitle("");
+      setSavedReminder("none");
+      if (task) {
+        reloadSubtasks(task.node_id);
+        loadReminder(task.node_id);
+      }
     }
   }, [open, task]);
 
+  // 子任务的增加与勾选立即生效，不等待保存
+  const handleAddSubtask = async () => {
+    if (!task || !subtaskTitle.trim()) return;
+    try {
+      await createSubtask(task.node_id, { title: subtaskTitle.trim() });
+      setSubtaskTitle("");
+      await reloadSubtasks(task.node_id);
+      onSuccess();
+    } catch (err) {
+      console.error("创建子任务失败:", err);
+      setError("创建子任务失败，请重试");
+    }
+  };
+
+  const handleToggleSubtask = async (subtask: NodeRecord) => {
+    if (!task) return;
+    try {
+      if (subtask.task_status === "done") {
+        await markTaskAsTodo(subtask.node_id);
+      } else {
+        await markTaskAsDone(subtask.node_id);
+      }
+      await reloadSubtasks(task.node_id);
+      onSuccess();
+    } catch (err) {
+      console.error("更新子任务失败:", err);
+      setError("更新子任务失败，请重试");
+    }
+  };
+
+  const activeSubtasks = subtasks.filter((subtask) => subtask.task_status !== "cancelled");
+  const doneSubtasks = activeSubtasks.filter((subtask) => subtask.task_status === "done");
+
   const handleSubmit = async (e: React.FormEvent) => {
     e.preventDefault();
     setError(null);
@@ -154,19 +248,49 @@ export function TaskEditCard({
 
         // 执行所有更新
         await Promise.all(updates);
+
+        // 重复规则依赖截止日期，放在日期更新之后
+        if (formData.recurrence !== (task.recurrence_rule ?? "none")) {
+          await updateTaskRecurrence(
+            task.node_id,
+            formData.recurrence === "none" ? null : formData.recurrence
+          );
+        }
+
+        // 提醒
+        if (formData.reminder !== savedReminder) {
+          await setTaskReminder(
+            task.node_id,
+            formData.reminder === "none" ? null : Number(formData.reminder)
+          );
+        }
       } else {
         // 创建模式
         const dueDateValue = formData.due_date
           ? `${format(formData.due_date, "yyyy-MM-dd")} 00:00:00`
           : undefined;
 
-        await createTask({
-          title: formData.title,
-          user_note: formData.description || undefined,
-          status: formData.status,
-          priority: formData.priority,
-          due_date: dueDateValue,
-        });
+        let created;
+        if (formData.recurrence !== "none") {
+          created = await createRecurringTask({
+            title: formData.title,
+            user_note: formData.description || undefined,
+            priority: formData.priority,
+            due_date: dueDateValue,
+            recurrence_rule: formData.recurrence,
+          });
+        } else {
+          created = await createTask({
+            title: formData.title,
+            user_note: formData.description || undefined,
+            status: formData.status,
+            priority: formData.priority,
+            due_date: dueDateValue,
+          });
+        }
+        if (formData.reminder !== "none") {
+          await setTaskReminder(created.node.node_id, Number(formData.reminder));
+        }
       }
 
       // 成功后回调
@@ -309,6 +433,119 @@ export function TaskEditCard({
             </Popover>
           </div>
 
+          {/* 重复 */}
+          <div className="space-y-2">
+            <Label htmlFor="recurrence">重复</Label>
+            <Select
+              value={formData.recurrence}
+              onValueChange={(value) =>
+                setFormData({ ...formData, recurrence: value })
+              }
+              disabled={loading}
+            >
+              <SelectTrigger id="recurrence">
+                <SelectValue />
+              </SelectTrigger>
+              <SelectContent>
+                {RECURRENCE_PRESETS.map((preset) => (
+                  <SelectItem key={preset.value} value={preset.value}>
+                    {preset.label}
+                  </SelectItem>
+                ))}
+                {!RECURRENCE_PRESETS.some((preset) => preset.value === formData.recurrence) && (
+                  <SelectItem value={formData.recurrence}>{formData.recurrence}</SelectItem>
+                )}
+              </SelectContent>
+            </Select>
+          </div>
+
+          {/* 提醒 */}
+          <div className="space-y-2">
+            <Label htmlFor="reminder">提醒</Label>
+            <Select
+              value={formData.reminder}
+              onValueChange={(value) =>
+                setFormData({ ...formData, reminder: value })
+              }
+              disabled={loading || !formData.due_date}
+            >
+              <SelectTrigger id="reminder">
+                <SelectValue />
+              </SelectTrigger>
+              <SelectContent>
+                {REMINDER_PRESETS.map((preset) => (
+                  <SelectItem key={preset.value} value={preset.value}>
+                    {preset.label}
+                  </SelectItem>
+                ))}
+                {!REMINDER_PRESETS.some((preset) => preset.value === formData.reminder) && (
+                  <SelectItem value={formData.reminder}>
+                    提前 {formData.reminder} 分钟
+                  </SelectItem>
+                )}
+              </SelectContent>
+            </Select>
+          </div>
+
+          {/* 子任务（仅编辑模式） */}
+          {isEditMode && (
+            <div className="space-y-2">
+              <Label>
+                子任务
+                {activeSubtasks.length > 0 && (
+                  <span className="ml-2 text-xs text-muted-foreground">
+                    {doneSubtasks.length}/{activeSubtasks.length}
+                  </span>
+                )}
+              </Label>
+              {subtasks.map((subtask) => (
+                <button
+                  key={subtask.node_id}
+                  type="button"
+                  className="flex w-full items-center gap-2 text-left text-sm"
+                  onClick={() => handleToggleSubtask(subtask)}
+                  disabled={loading || subtask.task_status === "cancelled"}
+                >
+                  {subtask.task_status === "done" ? (
+                    <CheckCircle2 className="h-4 w-4 text-green-500" />
+                  ) : (
+                    <Circle className="h-4 w-4 text-muted-foreground" />
+                  )}
+                  <span
+                    className={cn(
+                      subtask.task_status !== "todo" && "line-through text-muted-foreground"
+                    )}
+                  >
+                    {subtask.title}
+                  </span>
+                </button>
+              ))}
+              <div className="flex gap-2">
+                <Input
+                  value={subtaskTitle}
+                  onChange={(e) => setSubtaskTitle(e.target.value)}
+                  onKeyDown={(e) => {
+                    if (e.key === "Enter") {
+                      e.preventDefault();
+                      handleAddSubtask();
+                    }
+                  }}
+                  placeholder="添加子任务..."
+                  disabled={loading}
+                />
+                <Button
+                  type="button"
+                  variant="outline"
+                  size="icon"
+                  onClick={handleAddSubtask}
+                  disabled={loading || !subtaskTitle.trim()}
+                >
+                  <Plus className="h-4 w-4" />
+                </Button>
+              </div>
+            </div>
+          )}
+
           {/* 错误提示 */}
           {error && (
             <div className="text-sm text-destructive bg-destructive/10 p-3 rounded-md">
@@ -9,5 +9,6 @@ export { TiptapEditor } from "./TiptapEditor";
 export { TasksDialog } from "./TasksDialog";
 export { TemporaryChatPanel } from "./TemporaryChatPanel";
 export { GlobalSearchDialog } from "./GlobalSearchDialog";
+export { TagFilter } from "./TagFilter";
 // PDFViewer 不在这里导出，而是在 Workspace.tsx 中懒加载
 
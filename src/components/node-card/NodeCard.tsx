@@ -21,6 +21,7 @@ interface NodeCardProps {
   onApprove?: () => void;
   onReject?: () => void;
   onTogglePinned?: () => void;
+  onToggleExcludeFromSearch?: () => void;
   onDelete?: () => void;
   onConvert?: (targetType: "task" | "topic") => void;
   onLink?: () => void;
@@ -37,6 +38,7 @@ export function NodeCard({
   onApprove,
   onReject,
   onTogglePinned,
+  onToggleExcludeFromSearch,
   onDelete,
   onConvert,
   onLink,
@@ -129,6 +131,7 @@ export function NodeCard({
             onApprove={onApprove}
             onReject={onReject}
             onTogglePinned={onTogglePinned}
+            onToggleExcludeFromSearch={onToggleExcludeFromSearch}
             onDelete={onDelete}
             onConvert={onConvert}
             onLink={onLink}
@@ -24,6 +24,7 @@ interface NodeCardActionsProps {
   onApprove?: () => void;
   onReject?: () => void;
   onTogglePinned?: () => void;
+  onToggleExcludeFromSearch?: () => void;
   onDelete?: () => void;
   onConvert?: (targetType: "task" | "topic") => void;
   onLink?: () => void;
@@ -56,6 +57,7 @@ export function NodeCardActions({
   onApprove,
   onReject,
   onTogglePinned,
+  onToggleExcludeFromSearch,
   onDelete,
   onConvert,
   onLink,
@@ -79,7 +81,7 @@ export function NodeCardActions({
         </Button>
       )}
 
-      {convertOptions.length > 0 && (
+      {(convertOptions.length > 0 || onToggleExcludeFromSearch) && (
         <DropdownMenu>
           <DropdownMenuTrigger asChild>
             <Button
@@ -87,28 +89,46 @@ export function NodeCardActions({
               size="icon"
               className="h-7 w-7"
               onClick={(e) => e.stopPropagation()}
-              title="转换类型"
+              title="更多操作"
             >
               <MoreHorizontal className="h-3.5 w-3.5" />
             </Button>
           </DropdownMenuTrigger>
           <DropdownMenuContent align="end" className="w-40">
-            <DropdownMenuLabel className="text-xs font-normal text-muted-foreground">
-              节点转换
-            </DropdownMenuLabel>
-            <DropdownMenuSeparator />
-            {convertOptions.map((option) => (
-              <DropdownMenuItem
-                key={option.type}
-                className="cursor-pointer text-xs"
-                onClick={(e) => {
-                  e.stopPropagation();
-                  onConvert?.(option.type);
-                }}
-              >
-                {option.label}
-              </DropdownMenuItem>
-            ))}
+            {convertOptions.length > 0 && (
+              <>
+                <DropdownMenuLabel className="text-xs font-normal text-muted-foreground">
+                  节点转换
+                </DropdownMenuLabel>
+                <DropdownMenuSeparator />
+                {convertOptions.map((option) => (
+                  <DropdownMenuItem
+                    key={option.type}
+                    className="cursor-pointer text-xs"
+                    onClick={(e) => {
+                      e.stopPropagation();
+                      onConvert?.(option.type);
+                    }}
+                  >
+                    {option.label}
+                  </DropdownMenuItem>
+                ))}
+              </>
+            )}
+            {onToggleExcludeFromSearch && (
+              <>
+                {convertOptions.length > 0 && <DropdownMenuSeparator />}
+                <DropdownMenuItem
+                  className="cursor-pointer text-xs"
+                  onClick={(e) => {
+                    e.stopPropagation();
+                    onToggleExcludeFromSearch();
+                  }}
+                >
+                  {node.exclude_from_search ? "恢复参与检索" : "不参与检索"}
+                </DropdownMenuItem>
+              </>
+            )}
           </DropdownMenuContent>
         </DropdownMenu>
       )}
@@ -0,0 +1,155 @@
+import { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { expandArchive, previewArchive } from "@/api";
+import { Button } from "@/components/ui/button";
+import {
+  ArchiveExpandSummary,
+  ArchivePreview,
+  NodeRecord,
+  resourceSubtypeIcons,
+} from "@/types";
+import { useLanguage } from "@/contexts/LanguageContext";
+
+// 列表中最多显示的条目数
+const PREVIEW_LIMIT = 100;
+
+interface ArchiveExpandProgress {
+  node_id: number;
+  status: "extracting" | "importing" | "done" | "error";
+  processed: number;
+  total: number | null;
+}
+
+interface ArchivePanelProps {
+  resource: NodeRecord;
+}
+
+function formatSize(bytes: number): string {
+  if (bytes >= 1024 * 1024) return `${(bytes / 1024 / 1024).toFixed(1)} MB`;
+  if (bytes >= 1024) return `${(bytes / 1024).toFixed(1)} KB`;
+  return `${bytes} B`;
+}
+
+export function ArchivePanel({ resource }: ArchivePanelProps) {
+  const { t } = useLanguage();
+  const [preview, setPreview] = useState<ArchivePreview | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [expanding, setExpanding] = useState(false);
+  const [summary, setSummary] = useState<ArchiveExpandSummary | null>(null);
+  const [progress, setProgress] = useState<ArchiveExpandProgress | null>(null);
+
+  useEffect(() => {
+    setPreview(null);
+    setSummary(null);
+    setProgress(null);
+    setError(null);
+    previewArchive(resource.node_id)
+      .then(setPreview)
+      .catch((err) => setError(String(err)));
+  }, [resource.node_id]);
+
+  useEffect(() => {
+    let unlisten: (() => void) | null = null;
+    let cancelled = false;
+    listen<ArchiveExpandProgress>("archive-expand-progress", (event) => {
+      if (event.payload.node_id === resource.node_id) {
+        setProgress(event.payload);
+      }
+    }).then((fn) => {
+      if (cancelled) fn();
+      else unlisten = fn;
+    });
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, [resource.node_id]);
+
+  const handleExpand = async () => {
+    setExpanding(true);
+    setError(null);
+    try {
+      setSummary(await expandArchive(resource.node_id));
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setExpanding(false);
+    }
+  };
+
+  const skipped = summary
+    ? summary.skipped_unsupported + summary.skipped_too_large + summary.truncated
+    : 0;
+
+  return (
+    <div className="flex flex-col h-full p-6 gap-4 overflow-auto">
+      <div className="flex items-center gap-3">
+        <span className="text-3xl">{resourceSubtypeIcons.archive}</span>
+        <div className="flex-1 min-w-0">
+          <p className="text-lg font-medium truncate">{resource.title}</p>
+          {preview && (
+            <p className="text-sm text-muted-foreground">
+              {t("workspace", "archiveEntries")
+                .replace("{count}", String(preview.entries.length))
+                .replace("{supported}", String(preview.supported_count))}
+              {" · "}
+              {formatSize(preview.total_size)}
+            </p>
+          )}
+        </div>
+        <Button
+          onClick={handleExpand}
+          disabled={!preview || preview.supported_count === 0 || expanding}
+        >
+          {expanding ? t("workspace", "archiveExpanding") : t("workspace", "archiveExpand")}
+        </Button>
+      </div>
+
+      {error && <p className="text-sm text-destructive">{error}</p>}
+
+      {summary && (
+        <div className="text-sm text-muted-foreground space-y-1">
+          <p>
+            {t("workspace", "archiveExpanded")
+              .replace("{created}", String(summary.created_node_ids.length))
+              .replace("{linked}", String(summary.linked_existing))}
+          </p>
+          {skipped > 0 && (
+            <p>{t("workspace", "archiveSkipped").replace("{count}", String(skipped))}</p>
+          )}
+          {progress && progress.total !== null && progress.status !== "done" && (
+            <p>
+              {t("workspace", "archiveProgress")
+                .replace("{processed}", String(progress.processed))
+                .replace("{total}", String(progress.total))}
+            </p>
+          )}
+        </div>
+      )}
+
+      {preview && (
+        <div className="space-y-1">
+          {preview.entries.slice(0, PREVIEW_LIMIT).map((entry) => (
+            <div
+              key={entry.path}
+              className={
+                entry.subtype === "other"
+                  ? "flex items-center gap-2 text-xs text-muted-foreground/60"
+                  : "flex items-center gap-2 text-xs"
+              }
+            >
+              <span>{resourceSubtypeIcons[entry.subtype]}</span>
+              <span className="truncate flex-1">{entry.path}</span>
+              <span className="text-muted-foreground">{formatSize(entry.size)}</span>
+            </div>
+          ))}
+          {preview.entries.length > PREVIEW_LIMIT && (
+            <p className="text-xs text-muted-foreground">
+              … +{preview.entries.length - PREVIEW_LIMIT}
+            </p>
+          )}
+        </div>
+      )}
+    </div>
+  );
+}
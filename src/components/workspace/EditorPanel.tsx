@@ -8,6 +8,10 @@ import { TiptapEditor } from "@/components";
 import { TransformWrapper, TransformComponent } from "react-zoom-pan-pinch";
 import { convertFileSrc } from "@tauri-apps/api/core";
 import { useLanguage } from "@/contexts/LanguageContext";
+import { ArchivePanel } from "./ArchivePanel";
+import { RevisionHistory } from "./RevisionHistory";
+import { FileReferenceBanner } from "./FileReferenceBanner";
+import { NodeTags } from "./NodeTags";
 
 // 懒加载 PDF 组件
 const PDFViewer = lazy(() =>
@@ -34,8 +38,15 @@ interface EditorPanelProps {
   onViewModeChange: (mode: 'file' | 'text') => void;
   onEditingNameChange: (editing: boolean) => void;
   onDisplayNameChange: (name: string) => void;
+  onResourceUpdated?: (resource: NodeRecord) => void;
 }
 
+// 资源文件目录中的文件以 assets/ 相对路径保存；原地引用的文件保存绝对路径
+const isAssetsPath = (filePath: string) => filePath.startsWith("assets/");
+
+const resolveFilePath = (filePath: string, assetsPath: string) =>
+  isAssetsPath(filePath) ? `${assetsPath}/${filePath.slice("assets/".length)}` : filePath;
+
 export function EditorPanel({
   currentResource,
   isTopicMode = false,
@@ -54,6 +65,7 @@ export function EditorPanel({
   onViewModeChange,
   onEditingNameChange,
   onDisplayNameChange,
+  onResourceUpdated,
 }: EditorPanelProps) {
   const isTextResource = currentResource?.resource_subtype === "text";
   // 判断是否是文件类型资源（可以切换查看模式）
@@ -144,9 +156,7 @@ export function EditorPanel({
         );
       }
 
-      const fileName = pdfPath.replace("assets/", "");
-      const fullPath = `${assetsPath}/${fileName}`;
-      const pdfUrl = convertFileSrc(fullPath);
+      const pdfUrl = convertFileSrc(resolveFilePath(pdfPath, assetsPath));
 
       return (
         <Suspense
@@ -178,9 +188,7 @@ export function EditorPanel({
         );
       }
 
-      const fileName = imagePath.replace("assets/", "");
-      const fullPath = `${assetsPath}/${fileName}`;
-      const imageUrl = convertFileSrc(fullPath);
+      const imageUrl = convertFileSrc(resolveFilePath(imagePath, assetsPath));
 
       return (
         <div className="relative w-full h-full bg-black/5">
@@ -261,6 +269,10 @@ export function EditorPanel({
       );
     }
 
+    if (currentResource.resource_subtype === "archive") {
+      return <ArchivePanel resource={currentResource} />;
+    }
+
     if (currentResource.resource_subtype === "url") {
       return (
         <div className="flex flex-col items-center justify-center h-full text-muted-foreground">
@@ -358,19 +370,35 @@ export function EditorPanel({
         ) : (
           <span className="text-sm font-medium">{t("workspace", "workspaceArea")}</span>
         )}
-        {currentResource && (currentResource.resource_subtype === "text" || viewMode === 'text') && (
-          <Button
-            variant="ghost"
-            size="icon"
-            className="h-8 w-8 ml-auto"
-            disabled={(!isModified && editedDisplayName === (currentResource.title || "")) || isSaving}
-            onClick={onSave}
-            title={isSaving ? "保存中..." : "保存 (Ctrl+S)"}
-          >
-            {isSaving ? "⏳" : "💾"}
-          </Button>
-        )}
+        <div className="ml-auto flex items-center gap-1">
+          {currentResource && isTextResource && onResourceUpdated && (
+            <RevisionHistory
+              resource={currentResource}
+              disabled={isModified || isSaving}
+              onRestored={onResourceUpdated}
+            />
+          )}
+          {currentResource && (currentResource.resource_subtype === "text" || viewMode === 'text') && (
+            <Button
+              variant="ghost"
+              size="icon"
+              className="h-8 w-8"
+              disabled={(!isModified && editedDisplayName === (currentResource.title || "")) || isSaving}
+              onClick={onSave}
+              title={isSaving ? "保存中..." : "保存 (Ctrl+S)"}
+            >
+              {isSaving ? "⏳" : "💾"}
+            </Button>
+          )}
+        </div>
       </div>
+      {currentResource?.file_path && !isAssetsPath(currentResource.file_path) && (
+        <FileReferenceBanner
+          resource={currentResource}
+          onResourceUpdated={onResourceUpdated}
+        />
+      )}
+      {currentResource && <NodeTags nodeId={currentResource.node_id} />}
       {/* Editor Content */}
       <div className={cn(
         "flex-1 overflow-auto",
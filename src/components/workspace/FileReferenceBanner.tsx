@@ -0,0 +1,104 @@
+import { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { open } from "@tauri-apps/plugin-dialog";
+import { getFileReferenceStatus, getResourceById, relinkReferencedFile } from "@/api";
+import { Button } from "@/components/ui/button";
+import type { FileReferenceRecord, FileReferenceStatusPayload, NodeRecord } from "@/types";
+import { useLanguage } from "@/contexts/LanguageContext";
+
+interface FileReferenceBannerProps {
+  resource: NodeRecord;
+  // 引用文件被移动或重新指定后，资源的 file_path 随之变化
+  onResourceUpdated?: (resource: NodeRecord) => void;
+}
+
+// 原地引用的文件：显示路径；离线时提示并允许重新指定文件
+export function FileReferenceBanner({ resource, onResourceUpdated }: FileReferenceBannerProps) {
+  const { t } = useLanguage();
+  const nodeId = resource.node_id;
+  const [reference, setReference] = useState<FileReferenceRecord | null>(null);
+  const [relinking, setRelinking] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    let cancelled = false;
+    setReference(null);
+    setError(null);
+    getFileReferenceStatus(nodeId)
+      .then((record) => {
+        if (!cancelled) setReference(record);
+      })
+      .catch(console.error);
+    return () => {
+      cancelled = true;
+    };
+  }, [nodeId]);
+
+  useEffect(() => {
+    let unlisten: (() => void) | null = null;
+    let cancelled = false;
+    listen<FileReferenceStatusPayload>("file-reference-status", (event) => {
+      const payload = event.payload;
+      if (payload.node_id !== nodeId) return;
+      setReference((prev) =>
+        prev ? { ...prev, status: payload.status, file_path: payload.file_path } : prev
+      );
+      if (payload.file_path !== resource.file_path || payload.reparsed) {
+        getResourceById(nodeId)
+          .then((node) => onResourceUpdated?.(node))
+          .catch(console.error);
+      }
+    }).then((fn) => {
+      if (cancelled) fn();
+      else unlisten = fn;
+    });
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, [nodeId, resource.file_path, onResourceUpdated]);
+
+  const handleRelink = async () => {
+    const selected = await open({ multiple: false, defaultPath: reference?.file_path });
+    if (!selected || Array.isArray(selected)) return;
+
+    setRelinking(true);
+    setError(null);
+    try {
+      setReference(await relinkReferencedFile(nodeId, selected));
+      onResourceUpdated?.(await getResourceById(nodeId));
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setRelinking(false);
+    }
+  };
+
+  if (!reference) return null;
+
+  if (reference.status === "online") {
+    return (
+      <div
+        className="px-4 py-1 border-b text-xs text-muted-foreground truncate"
+        title={reference.file_path}
+      >
+        {t("workspace", "referenceOnline").replace("{path}", reference.file_path)}
+      </div>
+    );
+  }
+
+  return (
+    <div className="px-4 py-2 border-b bg-destructive/10 flex items-center gap-3">
+      <div className="min-w-0 flex-1 space-y-0.5">
+        <p className="text-sm font-medium text-destructive truncate" title={reference.file_path}>
+          {t("workspace", "referenceOffline").replace("{path}", reference.file_path)}
+        </p>
+        <p className="text-xs text-muted-foreground">{t("workspace", "referenceOfflineDesc")}</p>
+        {error && <p className="text-xs text-destructive">{error}</p>}
+      </div>
+      <Button variant="outline" size="sm" onClick={handleRelink} disabled={relinking}>
+        {t("workspace", "referenceRelink")}
+      </Button>
+    </div>
+  );
+}
@@ -0,0 +1,165 @@
+import { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { Check, X } from "lucide-react";
+import { acceptSuggestedTags, addTag, listNodeTags, listSuggestedTags, removeTag } from "@/api";
+import { Badge } from "@/components/ui/badge";
+import type { TagRecord, TagSuggestionRecord, TagSuggestionsPayload } from "@/types";
+import { useLanguage } from "@/contexts/LanguageContext";
+
+interface NodeTagsProps {
+  nodeId: number;
+}
+
+// 节点标签：显示已有标签，回车添加，点 × 移除；AI 建议的标签逐个采纳或忽略
+export function NodeTags({ nodeId }: NodeTagsProps) {
+  const { t } = useLanguage();
+  const [tags, setTags] = useState<TagRecord[]>([]);
+  const [suggestions, setSuggestions] = useState<TagSuggestionRecord[]>([]);
+  const [draft, setDraft] = useState("");
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    let cancelled = false;
+    setTags([]);
+    setSuggestions([]);
+    setError(null);
+    listNodeTags(nodeId)
+      .then((records) => {
+        if (!cancelled) setTags(records);
+      })
+      .catch(console.error);
+    listSuggestedTags(nodeId)
+      .then((records) => {
+        if (!cancelled) setSuggestions(records);
+      })
+      .catch(console.error);
+    return () => {
+      cancelled = true;
+    };
+  }, [nodeId]);
+
+  // 资源重新处理后 AI 会给出新一轮建议
+  useEffect(() => {
+    let unlisten: (() => void) | null = null;
+    let cancelled = false;
+    listen<TagSuggestionsPayload>("tag-suggestions", (event) => {
+      if (event.payload.node_id !== nodeId) return;
+      listSuggestedTags(nodeId)
+        .then((records) => {
+          if (!cancelled) setSuggestions(records);
+        })
+        .catch(console.error);
+    }).then((fn) => {
+      if (cancelled) fn();
+      else unlisten = fn;
+    });
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, [nodeId]);
+
+  const handleSuggestion = async (suggestionId: number, accept: boolean) => {
+    setError(null);
+    try {
+      const records = await acceptSuggestedTags(
+        nodeId,
+        accept ? [suggestionId] : [],
+        accept ? [] : [suggestionId]
+      );
+      setTags(records);
+      setSuggestions((prev) => prev.filter((item) => item.suggestion_id !== suggestionId));
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  const handleAdd = async () => {
+    const name = draft.trim();
+    if (!name) return;
+    setError(null);
+    try {
+      const tag = await addTag(nodeId, name);
+      setTags((prev) =>
+        prev.some((item) => item.tag_id === tag.tag_id)
+          ? prev
+          : [...prev, tag].sort((a, b) => a.name.localeCompare(b.name))
+      );
+      setDraft("");
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  const handleRemove = async (tagId: number) => {
+    setError(null);
+    try {
+      await removeTag(nodeId, tagId);
+      setTags((prev) => prev.filter((item) => item.tag_id !== tagId));
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  return (
+    <div className="px-4 py-1.5 border-b flex flex-wrap items-center gap-1.5">
+      <span className="text-xs text-muted-foreground">{t("workspace", "tags")}</span>
+      {tags.map((tag) => (
+        <Badge key={tag.tag_id} variant="secondary" className="gap-1 font-normal">
+          {tag.name}
+          <button
+            type="button"
+            className="opacity-60 hover:opacity-100"
+            title={t("workspace", "tagRemove")}
+            onClick={() => handleRemove(tag.tag_id)}
+          >
+            <X className="h-3 w-3" />
+          </button>
+        </Badge>
+      ))}
+      <input
+        className="min-w-[6rem] flex-1 bg-transparent text-xs outline-none placeholder:text-muted-foreground/60"
+        placeholder={t("workspace", "tagAddPlaceholder")}
+        value={draft}
+        onChange={(e) => setDraft(e.target.value)}
+        onKeyDown={(e) => {
+          if (e.key === "Enter" && !e.nativeEvent.isComposing) {
+            e.preventDefault();
+            handleAdd();
+          }
+        }}
+      />
+      {suggestions.length > 0 && (
+        <>
+          <span className="ml-1 text-xs text-muted-foreground/70">{t("workspace", "tagSuggested")}</span>
+          {suggestions.map((suggestion) => (
+            <Badge
+              key={suggestion.suggestion_id}
+              variant="outline"
+              className="gap-1 border-dashed font-normal text-muted-foreground"
+            >
+              {suggestion.name}
+              <button
+                type="button"
+                className="opacity-60 hover:opacity-100"
+                title={t("workspace", "tagSuggestionAccept")}
+                onClick={() => handleSuggestion(suggestion.suggestion_id, true)}
+              >
+                <Check className="h-3 w-3" />
+              </button>
+              <button
+                type="button"
+                className="opacity-60 hover:opacity-100"
+                title={t("workspace", "tagSuggestionReject")}
+                onClick={() => handleSuggestion(suggestion.suggestion_id, false)}
+              >
+                <X className="h-3 w-3" />
+              </button>
+            </Badge>
+          ))}
+        </>
+      )}
+      {error && <span className="w-full text-xs text-destructive">{error}</span>}
+    </div>
+  );
+}
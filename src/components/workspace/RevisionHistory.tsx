@@ -0,0 +1,95 @@
+import { useState } from "react";
+import { listResourceRevisions, restoreResourceRevision } from "@/api";
+import { Button } from "@/components/ui/button";
+import { Popover, PopoverContent, PopoverTrigger } from "@/components/ui/popover";
+import type { ContentRevisionRecord, NodeRecord } from "@/types";
+import { useLanguage } from "@/contexts/LanguageContext";
+
+interface RevisionHistoryProps {
+  resource: NodeRecord;
+  // 有未保存的修改时禁用，避免恢复覆盖编辑中的内容
+  disabled?: boolean;
+  onRestored: (resource: NodeRecord) => void;
+}
+
+export function RevisionHistory({ resource, disabled, onRestored }: RevisionHistoryProps) {
+  const { t } = useLanguage();
+  const [revisions, setRevisions] = useState<ContentRevisionRecord[] | null>(null);
+  const [restoringId, setRestoringId] = useState<number | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const handleOpenChange = (open: boolean) => {
+    if (!open) return;
+    setRevisions(null);
+    setError(null);
+    listResourceRevisions(resource.node_id)
+      .then(setRevisions)
+      .catch((err) => setError(String(err)));
+  };
+
+  const handleRestore = async (revisionId: number) => {
+    setRestoringId(revisionId);
+    setError(null);
+    try {
+      onRestored(await restoreResourceRevision(revisionId));
+      setRevisions(await listResourceRevisions(resource.node_id));
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setRestoringId(null);
+    }
+  };
+
+  return (
+    <Popover onOpenChange={handleOpenChange}>
+      <PopoverTrigger asChild>
+        <Button
+          variant="ghost"
+          size="icon"
+          className="h-8 w-8"
+          disabled={disabled}
+          title={t("workspace", "revisions")}
+        >
+          🕘
+        </Button>
+      </PopoverTrigger>
+      <PopoverContent className="w-80 p-2" align="end">
+        <p className="text-sm font-medium px-1 pb-2">{t("workspace", "revisions")}</p>
+        {error && <p className="text-xs text-destructive px-1">{error}</p>}
+        {revisions && revisions.length === 0 && (
+          <p className="text-xs text-muted-foreground px-1">{t("workspace", "revisionsEmpty")}</p>
+        )}
+        <div className="max-h-80 overflow-auto space-y-1">
+          {revisions?.map((revision) => (
+            <div key={revision.revision_id} className="rounded-md p-2 hover:bg-muted">
+              <div className="flex items-center gap-2 text-xs text-muted-foreground">
+                <span>{revision.created_at}</span>
+                <span>
+                  {revision.reason === "edit"
+                    ? t("workspace", "revisionReasonEdit")
+                    : t("workspace", "revisionReasonRestore")}
+                </span>
+                <span>
+                  {t("workspace", "revisionChars").replace(
+                    "{count}",
+                    String(revision.content_length),
+                  )}
+                </span>
+                <Button
+                  variant="outline"
+                  size="sm"
+                  className="h-6 px-2 text-xs ml-auto"
+                  disabled={restoringId !== null}
+                  onClick={() => handleRestore(revision.revision_id)}
+                >
+                  {t("workspace", "revisionRestore")}
+                </Button>
+              </div>
+              <p className="text-xs mt-1 line-clamp-3 whitespace-pre-wrap">{revision.preview}</p>
+            </div>
+          ))}
+        </div>
+      </PopoverContent>
+    </Popover>
+  );
+}
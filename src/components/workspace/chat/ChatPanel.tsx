@@ -27,10 +27,14 @@ import {
 } from "@/types";
 import { Send, Loader2, Settings } from "lucide-react";
 import { quickCapture, linkNodes } from "@/api";
-import { useLocalStorageString, useChatSessionManagement } from "@/hooks";
+import { useLocalStorage, useLocalStorageString, useChatSessionManagement } from "@/hooks";
 import { SessionItem } from "./SessionItem";
 import { MessageBubble } from "./MessageBubble";
 
+// 会话级检索分块数；"default" 沿用设置中的全局值
+const RAG_TOP_K_OPTIONS = [3, 5, 10, 20];
+const RAG_TOP_K_DEFAULT = "default";
+
 interface ChatPanelProps {
   width: number;
   tempWidth: number | null;
@@ -65,6 +69,10 @@ export function ChatPanel({
     "neuralvault_rag_scope",
     "local"
   );
+  const [ragTopKBySession, setRagTopKBySession] = useLocalStorage<Record<string, number>>(
+    "neuralvault_rag_top_k",
+    {}
+  );
   const [pinningIndex, setPinningIndex] = useState<number | null>(null);
 
   // Use the session management hook
@@ -82,6 +90,22 @@ export function ChatPanel({
     ragScope === "local"
       ? t("workspace", "ragScopeLocal")
       : t("workspace", "ragScopeGlobal");
+  const activeSessionId = sessionManager.activeSessionId;
+  const sessionTopK =
+    activeSessionId !== undefined ? ragTopKBySession[activeSessionId] : undefined;
+
+  const handleTopKChange = (value: string) => {
+    if (activeSessionId === undefined) return;
+    setRagTopKBySession((prev) => {
+      const next = { ...prev };
+      if (value === RAG_TOP_K_DEFAULT) {
+        delete next[activeSessionId];
+      } else {
+        next[activeSessionId] = Number(value);
+      }
+      return next;
+    });
+  };
 
   // Build available models list
   const availableModels: ModelOption[] = configuredProviders.flatMap(
@@ -127,6 +151,7 @@ export function ChatPanel({
         thinking_effort: thinkingEffort,
         context_resource_ids: contextResourceIds,
         rag_scope: ragScope,
+        retrieval: sessionTopK ? { top_k: sessionTopK } : undefined,
       });
       await sessionManager.loadSessions();
     } catch (e) {
@@ -347,6 +372,11 @@ export function ChatPanel({
                 }
                 isPinning={pinningIndex === idx}
                 onPin={() => void handlePinToContext(idx)}
+                onFork={
+                  msg.role === "assistant" && msg.messageId !== undefined && !isChatLoading
+                    ? () => void sessionManager.forkSession(msg.messageId!)
+                    : undefined
+                }
               />
             ))
           )}
@@ -443,6 +473,26 @@ export function ChatPanel({
               >
                 {ragScopeLabel}
               </Button>
+              <Select
+                value={sessionTopK ? String(sessionTopK) : RAG_TOP_K_DEFAULT}
+                onValueChange={handleTopKChange}
+                disabled={activeSessionId === undefined}
+              >
+                <SelectTrigger
+                  className="w-[80px] h-7 text-xs"
+                  title={t("workspace", "ragTopK")}
+                >
+                  <SelectValue />
+                </SelectTrigger>
+                <SelectContent>
+                  <SelectItem value={RAG_TOP_K_DEFAULT}>{t("workspace", "ragTopKDefault")}</SelectItem>
+                  {RAG_TOP_K_OPTIONS.map((count) => (
+                    <SelectItem key={count} value={String(count)}>
+                      Top {count}
+                    </SelectItem>
+                  ))}
+                </SelectContent>
+              </Select>
             </div>
             {/* Input field */}
             <div className="flex gap-2">
@@ -1,6 +1,6 @@
 import { Button } from "@/components/ui/button";
 import { cn } from "@/lib/utils";
-import { Pin, Loader2 } from "lucide-react";
+import { GitBranch, Pin, Loader2 } from "lucide-react";
 import { ThinkingBlock } from "../ThinkingBlock";
 import type { ChatMessage } from "@/types";
 import { useLanguage } from "@/contexts/LanguageContext";
@@ -12,6 +12,8 @@ interface MessageBubbleProps {
   showPinButton: boolean;
   isPinning: boolean;
   onPin: () => void;
+  /** 从这条消息处分叉出新会话；不传时不显示按钮 */
+  onFork?: () => void;
 }
 
 export function MessageBubble({
@@ -21,6 +23,7 @@ export function MessageBubble({
   showPinButton,
   isPinning,
   onPin,
+  onFork,
 }: MessageBubbleProps) {
   const { t } = useLanguage();
   const isUser = message.role === "user";
@@ -54,6 +57,16 @@ export function MessageBubble({
           />
         )}
         <div className="whitespace-pre-wrap">{message.content}</div>
+        {!isUser && message.sources && message.sources.length > 0 && (
+          <div className="mt-2 space-y-0.5 text-xs text-muted-foreground">
+            <div>{t("workspace", "chatSources")}</div>
+            {message.sources.map((source) => (
+              <div key={source.rank} className="truncate" title={source.snippet}>
+                [{source.rank}] {source.node_title}
+              </div>
+            ))}
+          </div>
+        )}
         {!isUser && message.usage && (
           <div className="mt-2 text-xs text-muted-foreground">
             {t("workspace", "tokenUsage")}{" "}
@@ -64,6 +77,21 @@ export function MessageBubble({
           </div>
         )}
       </div>
+      {/* Fork button */}
+      {onFork && (
+        <Button
+          variant="ghost"
+          size="icon"
+          className={cn(
+            "absolute -bottom-1 h-6 w-6 opacity-0 group-hover:opacity-100 transition-opacity",
+            showPinButton ? "right-7" : "right-0"
+          )}
+          onClick={onFork}
+          title={t("workspace", "forkChatSession")}
+        >
+          <GitBranch className="h-3.5 w-3.5" />
+        </Button>
+      )}
       {/* Pin button */}
       {showPinButton && (
         <Button
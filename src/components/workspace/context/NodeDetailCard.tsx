@@ -27,12 +27,10 @@ export function NodeDetailCard({ node, nodeType, onUpdate }: NodeDetailCardProps
   const titleField = useEditableField<string>({
     initialValue: node.title || "",
     onSave: async (value) => {
-      if (isTask) {
-        await updateTaskTitle(node.node_id, value);
-      } else {
-        await updateTopicTitle(node.node_id, value);
-      }
-      onUpdate?.({ ...node, title: value });
+      const version = isTask
+        ? await updateTaskTitle(node.node_id, value, node.version)
+        : await updateTopicTitle(node.node_id, value, node.version);
+      onUpdate?.({ ...node, title: value, version });
     },
   });
 
@@ -41,12 +39,10 @@ export function NodeDetailCard({ node, nodeType, onUpdate }: NodeDetailCardProps
     initialValue: node.summary || "",
     onSave: async (value) => {
       const summaryValue = value || null;
-      if (isTask) {
-        await updateTaskSummary(node.node_id, summaryValue);
-      } else {
-        await updateTopicSummary(node.node_id, summaryValue);
-      }
-      onUpdate?.({ ...node, summary: summaryValue });
+      const version = isTask
+        ? await updateTaskSummary(node.node_id, summaryValue, node.version)
+        : await updateTopicSummary(node.node_id, summaryValue, node.version);
+      onUpdate?.({ ...node, summary: summaryValue, version });
     },
   });
 
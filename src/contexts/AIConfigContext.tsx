@@ -9,13 +9,19 @@ import {
   removeApiKey,
   setProcessingProviderModel,
   setClassificationMode,
+  setSummaryLanguage,
+  setRetrievalConfig,
+  setChatContextConfig,
 } from "@/api";
 import {
   AI_PROVIDER_INFO,
   type AIProvider,
   type AIConfigStatus,
   type ClassificationMode,
+  type SummaryLanguageMode,
   type ModelOption,
+  type RetrievalConfig,
+  type ChatContextConfig,
 } from "@/types";
 
 // 当前启用的 providers（可扩展）
@@ -30,6 +36,10 @@ export interface AIConfigContextType {
   setSelectedModel: (model: ModelOption | null) => void;
   classificationMode: ClassificationMode | null;
   saveClassificationMode: (mode: ClassificationMode) => Promise<void>;
+  summaryLanguage: SummaryLanguageMode | null;
+  saveSummaryLanguage: (mode: SummaryLanguageMode) => Promise<void>;
+  saveRetrievalConfig: (retrieval: RetrievalConfig) => Promise<void>;
+  saveChatContextConfig: (chatContext: ChatContextConfig) => Promise<void>;
   saveKey: (provider: AIProvider, apiKey: string, baseUrl?: string) => Promise<void>;
   removeKey: (provider: AIProvider) => Promise<void>;
   saveProcessingProviderModel: (provider: AIProvider, model: string) => Promise<void>;
@@ -45,6 +55,7 @@ export function AIConfigProvider({ children }: { children: React.ReactNode }) {
   const [selectedModel, setSelectedModel] = useState<ModelOption | null>(null);
 
   const classificationMode = config?.classification_mode ?? null;
+  const summaryLanguage = config?.summary_language ?? null;
 
   const refreshConfig = useCallback(async () => {
     try {
@@ -130,6 +141,30 @@ export function AIConfigProvider({ children }: { children: React.ReactNode }) {
     [refreshConfig]
   );
 
+  const saveSummaryLanguage = useCallback(
+    async (mode: SummaryLanguageMode) => {
+      await setSummaryLanguage({ mode });
+      await refreshConfig();
+    },
+    [refreshConfig]
+  );
+
+  const saveRetrievalConfig = useCallback(
+    async (retrieval: RetrievalConfig) => {
+      await setRetrievalConfig(retrieval);
+      await refreshConfig();
+    },
+    [refreshConfig]
+  );
+
+  const saveChatContextConfig = useCallback(
+    async (chatContext: ChatContextConfig) => {
+      await setChatContextConfig(chatContext);
+      await refreshConfig();
+    },
+    [refreshConfig]
+  );
+
   return (
     <AIConfigContext.Provider
       value={{
@@ -141,6 +176,10 @@ export function AIConfigProvider({ children }: { children: React.ReactNode }) {
         setSelectedModel,
         classificationMode,
         saveClassificationMode,
+        summaryLanguage,
+        saveSummaryLanguage,
+        saveRetrievalConfig,
+        saveChatContextConfig,
         saveKey,
         removeKey,
         saveProcessingProviderModel,
@@ -3,14 +3,20 @@
  * 职责：消息状态、流式响应处理、发送消息
  */
 import React, { createContext, useContext, useState, useCallback, useRef } from "react";
-import { sendChatMessage as apiSendChatMessage, listChatMessages } from "@/api";
+import {
+  sendChatMessage as apiSendChatMessage,
+  listChatMessages,
+  listMessageSources,
+} from "@/api";
 import { listen } from "@tauri-apps/api/event";
 import type {
   ChatMessage,
   ChatUsage,
   ChatMessagePayload,
+  MessageSource,
   ThinkingEffort,
   RagScope,
+  RetrievalOverrides,
 } from "@/types";
 import { useChatSession } from "./ChatSessionContext";
 import { useAIConfig } from "./AIConfigContext";
@@ -24,6 +30,7 @@ interface SendMessageContext {
   thinking_effort?: ThinkingEffort;
   context_resource_ids?: number[];
   rag_scope?: RagScope;
+  retrieval?: RetrievalOverrides;
 }
 
 interface LoadContext {
@@ -47,12 +54,22 @@ export interface ChatMessageContextType {
 const ChatMessageContext = createContext<ChatMessageContextType | undefined>(undefined);
 
 // 将数据库消息转换为前端消息格式
-const toChatMessages = (turns: ChatMessagePayload[]): ChatMessage[] => {
+const toChatMessages = (
+  turns: ChatMessagePayload[],
+  sources: MessageSource[] = []
+): ChatMessage[] => {
+  const sourcesByMessage = new Map<number, MessageSource[]>();
+  for (const source of sources) {
+    const list = sourcesByMessage.get(source.message_id) ?? [];
+    list.push(source);
+    sourcesByMessage.set(source.message_id, list);
+  }
   const output: ChatMessage[] = [];
   for (const turn of turns) {
     const timestamp = turn.created_at ? new Date(turn.created_at) : new Date();
     if (turn.user_content) {
       output.push({
+        messageId: turn.message_id,
         role: "user",
         content: turn.user_content,
         timestamp,
@@ -61,11 +78,13 @@ const toChatMessages = (turns: ChatMessagePayload[]): ChatMessage[] => {
     }
     if (turn.assistant_content) {
       output.push({
+        messageId: turn.message_id,
         role: "assistant",
         content: turn.assistant_content,
         thinkingSummary: turn.thinking_summary ?? undefined,
         timestamp,
         usage: turn.usage,
+        sources: sourcesByMessage.get(turn.message_id),
       });
     }
   }
@@ -113,6 +132,17 @@ export function ChatMessageProvider({ children }: { children: React.ReactNode })
     });
   }, []);
 
+  const applySourcesToLastAssistant = useCallback((sources: MessageSource[]) => {
+    setMessages((prev) => {
+      const next = [...prev];
+      const lastIndex = next.length - 1;
+      if (lastIndex >= 0 && next[lastIndex].role === "assistant") {
+        next[lastIndex] = { ...next[lastIndex], sources };
+      }
+      return next;
+    });
+  }, []);
+
   const appendThinkingDeltaToLastAssistant = useCallback((delta: string) => {
     setMessages((prev) => {
       const next = [...prev];
@@ -137,6 +167,7 @@ export function ChatMessageProvider({ children }: { children: React.ReactNode })
         type: string;
         delta?: string;
         usage?: ChatUsage;
+        sources?: MessageSource[];
         message?: unknown;
       }>("chat-stream", (event) => {
         if (event.payload.session_id !== sessionId) return;
@@ -149,6 +180,10 @@ export function ChatMessageProvider({ children }: { children: React.ReactNode })
           appendThinkingDeltaToLastAssistant(event.payload.delta);
         }
 
+        if (event.payload.type === "sources" && event.payload.sources) {
+          applySourcesToLastAssistant(event.payload.sources);
+        }
+
         if (event.payload.type === "usage" && event.payload.usage) {
           applyUsageToLastAssistant(event.payload.usage);
           setIsChatLoading(false);
@@ -162,7 +197,12 @@ export function ChatMessageProvider({ children }: { children: React.ReactNode })
         }
       });
     },
-    [appendDeltaToLastAssistant, appendThinkingDeltaToLastAssistant, applyUsageToLastAssistant]
+    [
+      appendDeltaToLastAssistant,
+      appendThinkingDeltaToLastAssistant,
+      applyUsageToLastAssistant,
+      applySourcesToLastAssistant,
+    ]
   );
 
   // ============================================
@@ -197,10 +237,13 @@ export function ChatMessageProvider({ children }: { children: React.ReactNode })
           await syncSessionBindings(sessionId, options.context_resource_ids);
         }
 
-        const turns = await listChatMessages(sessionId);
+        const [turns, sources] = await Promise.all([
+          listChatMessages(sessionId),
+          listMessageSources(sessionId),
+        ]);
         if (loadToken !== loadTokenRef.current) return;
 
-        setMessages(toChatMessages(turns));
+        setMessages(toChatMessages(turns, sources));
       } catch (e) {
         if (loadToken === loadTokenRef.current) {
           setMessages([]);
@@ -282,6 +325,7 @@ export function ChatMessageProvider({ children }: { children: React.ReactNode })
           files: context.files,
           thinking_effort: context.thinking_effort,
           rag_scope: context.rag_scope,
+          retrieval: context.retrieval,
         });
       } catch (e) {
         setChatError(e instanceof Error ? e.message : "Chat failed");
@@ -6,7 +6,14 @@
 import { useCallback } from "react";
 import { useAIConfig } from "@/contexts/AIConfigContext";
 import { useChatMessage } from "@/contexts/ChatMessageContext";
-import type { ThinkingEffort, ModelOption, ChatMessage, AIProvider, RagScope } from "@/types";
+import type {
+  ThinkingEffort,
+  ModelOption,
+  ChatMessage,
+  AIProvider,
+  RagScope,
+  RetrievalOverrides,
+} from "@/types";
 
 interface ChatContext {
   session_id?: number;
@@ -17,6 +24,7 @@ interface ChatContext {
   thinking_effort?: ThinkingEffort;
   context_resource_ids?: number[];
   rag_scope?: RagScope;
+  retrieval?: RetrievalOverrides;
 }
 
 export interface UseChatReturn {
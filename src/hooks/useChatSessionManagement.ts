@@ -1,5 +1,5 @@
 import { useState, useCallback, useEffect, useMemo } from "react";
-import { listChatSessions, deleteChatSession } from "@/api";
+import { listChatSessions, deleteChatSession, forkChatSession } from "@/api";
 import { useChatSession, useChatMessage } from "@/contexts/AIContext";
 import type { ChatSession } from "@/types";
 
@@ -21,6 +21,7 @@ export interface ChatSessionActions {
   selectSession: (sessionId: number) => Promise<void>;
   createNewSession: () => void;
   deleteSession: (sessionId: number, skipConfirm?: boolean) => Promise<void>;
+  forkSession: (messageId: number) => Promise<void>;
 }
 
 export interface UseChatSessionManagementOptions {
@@ -144,6 +145,24 @@ export function useChatSessionManagement(
     ]
   );
 
+  // 复制当前会话到指定消息为止，并切换到新会话
+  const forkSession = useCallback(
+    async (messageId: number) => {
+      if (activeSessionId === undefined || isChatLoading) return;
+      try {
+        const { session_id } = await forkChatSession({
+          session_id: activeSessionId,
+          message_id: messageId,
+        });
+        await loadSessions();
+        await selectSession(session_id);
+      } catch (err) {
+        console.error("Failed to fork chat session:", err);
+      }
+    },
+    [activeSessionId, isChatLoading, loadSessions, selectSession]
+  );
+
   // Initialize and reload on context change
   useEffect(() => {
     if (!hasSessionContext) {
@@ -166,5 +185,6 @@ export function useChatSessionManagement(
     selectSession,
     createNewSession,
     deleteSession,
+    forkSession,
   };
 }
@@ -1,6 +1,7 @@
 import { useCallback } from "react";
 import { readClipboard } from "@/api";
 import type { ClipboardContent } from "@/types";
+import { codeToMarkdown, mayNeedSmartPaste, tableToMarkdown } from "@/lib/clipboard";
 
 interface SelectedFile {
   path: string;
@@ -45,8 +46,8 @@ export function useClipboard(): UseClipboardReturn {
         }
       }
 
-      // 如果有文件或图片，使用 Rust 后端读取
-      if (hasFileOrImage) {
+      // 如果有文件或图片，或可能是表格 / 代码，使用 Rust 后端读取
+      if (hasFileOrImage || mayNeedSmartPaste(e.clipboardData)) {
         e.preventDefault();
 
         try {
@@ -56,7 +57,7 @@ export function useClipboard(): UseClipboardReturn {
           console.error("读取剪贴板失败:", err);
         }
       }
-      // 纯文本让浏览器默认处理
+      // 单行纯文本让浏览器默认处理
     },
     []
   );
@@ -100,6 +101,14 @@ function processClipboardContent(
       callbacks.onText(content.data.content);
       break;
 
+    case "Table":
+      callbacks.onText(tableToMarkdown(content.data.rows));
+      break;
+
+    case "Code":
+      callbacks.onText(codeToMarkdown(content.data.content, content.data.language));
+      break;
+
     case "Empty":
       break;
   }
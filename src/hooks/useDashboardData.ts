@@ -1,30 +1,40 @@
 import { useState, useEffect, useCallback } from "react";
 import { z } from "zod";
-import { fetchDashboardData, fetchAllTasks, quickCapture, linkNodes } from "@/api";
+import {
+  fetchDashboardData,
+  fetchAllTasks,
+  quickCapture,
+  captureResourcesBatch,
+  linkNodes,
+} from "@/api";
 import { getFileTypeFromPath } from "@/lib/utils";
-import type { NodeRecord } from "@/types";
+import type { FileStorageMode, NodeRecord } from "@/types";
 
 interface UseDashboardDataReturn {
   tasks: NodeRecord[];
   allTasks: NodeRecord[];
   resources: NodeRecord[];
+  tagFilter: number[];
+  setTagFilter: (tagIds: number[]) => void;
   loading: boolean;
   error: string | null;
   setError: (error: string | null) => void;
   reloadData: (fallbackMessage?: string) => Promise<void>;
   handleCapture: (content: string, filePath?: string) => Promise<void>;
+  handleCaptureFiles: (content: string, filePaths: string[]) => Promise<void>;
   handleLinkResource: (resourceId: number, taskId: number) => Promise<void>;
 }
 
 /**
  * Dashboard 数据管理 hook
  *
- * 管理任务、资源数据的加载和刷新
+ * 管理任务、资源数据的加载和刷新；tagFilter 非空时只加载带有全部这些标签的任务和资源
  */
 export function useDashboardData(): UseDashboardDataReturn {
   const [tasks, setTasks] = useState<NodeRecord[]>([]);
   const [allTasks, setAllTasks] = useState<NodeRecord[]>([]);
   const [resources, setResources] = useState<NodeRecord[]>([]);
+  const [tagFilter, setTagFilter] = useState<number[]>([]);
   const [loading, setLoading] = useState(false);
   const [error, setError] = useState<string | null>(null);
 
@@ -32,7 +42,7 @@ export function useDashboardData(): UseDashboardDataReturn {
     setLoading(true);
     setError(null);
     try {
-      const data = await fetchDashboardData();
+      const data = await fetchDashboardData(tagFilter);
       setTasks(data.tasks);
       setResources(data.resources);
       const all = await fetchAllTasks();
@@ -47,10 +57,10 @@ export function useDashboardData(): UseDashboardDataReturn {
     } finally {
       setLoading(false);
     }
-  }, []);
+  }, [tagFilter]);
 
   const handleCapture = useCallback(
-    async (content: string, filePath?: string) => {
+    async (content: string, filePath?: string, storage?: FileStorageMode) => {
       setError(null);
       try {
         if (filePath) {
@@ -58,6 +68,7 @@ export function useDashboardData(): UseDashboardDataReturn {
             file_path: filePath,
             file_type: getFileTypeFromPath(filePath),
             content: content || undefined,
+            storage,
           });
         } else if (content) {
           await quickCapture({
@@ -76,6 +87,30 @@ export function useDashboardData(): UseDashboardDataReturn {
     [reloadData]
   );
 
+  const handleCaptureFiles = useCallback(
+    async (content: string, filePaths: string[], storage?: FileStorageMode) => {
+      setError(null);
+      try {
+        const { items } = await captureResourcesBatch({
+          file_paths: filePaths,
+          user_note: content || undefined,
+          storage,
+        });
+        const failed = items.filter((item) => item.error);
+        if (failed.length > 0) {
+          setError(`${failed.length} 个文件捕获失败`);
+        }
+        await reloadData();
+      } catch (err) {
+        console.error(err);
+        const message =
+          err instanceof Error && err.message ? err.message : "捕获失败";
+        setError(message);
+      }
+    },
+    [reloadData]
+  );
+
   const handleLinkResource = useCallback(
     async (resourceId: number, taskId: number) => {
       setError(null);
@@ -99,11 +134,14 @@ export function useDashboardData(): UseDashboardDataReturn {
     tasks,
     allTasks,
     resources,
+    tagFilter,
+    setTagFilter,
     loading,
     error,
     setError,
     reloadData,
     handleCapture,
+    handleCaptureFiles,
     handleLinkResource,
   };
 }
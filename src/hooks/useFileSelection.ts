@@ -18,11 +18,46 @@ interface UseFileSelectionReturn {
 const FILE_FILTERS = [
   {
     name: "支持的文件",
-    extensions: ["txt", "md", "pdf", "png", "jpg", "jpeg", "gif", "webp", "epub"],
+    extensions: [
+      "txt",
+      "md",
+      "pdf",
+      "png",
+      "jpg",
+      "jpeg",
+      "gif",
+      "webp",
+      "epub",
+      "docx",
+      "pptx",
+      "xlsx",
+      "odt",
+      "odp",
+      "ods",
+      "ipynb",
+      "eml",
+      "msg",
+      "zip",
+      "tar",
+      "gz",
+      "tgz",
+      "mp3",
+      "wav",
+      "m4a",
+      "ogg",
+      "flac",
+      "aac",
+      "opus",
+    ],
   },
   { name: "文本文件", extensions: ["txt", "md"] },
   { name: "图片", extensions: ["png", "jpg", "jpeg", "gif", "webp", "bmp", "svg"] },
-  { name: "文档", extensions: ["pdf", "epub"] },
+  {
+    name: "文档",
+    extensions: ["pdf", "epub", "docx", "pptx", "xlsx", "odt", "odp", "ods", "ipynb", "eml", "msg"],
+  },
+  { name: "音频", extensions: ["mp3", "wav", "m4a", "ogg", "flac", "aac", "opus"] },
+  { name: "压缩包", extensions: ["zip", "tar", "gz", "tgz"] },
   { name: "所有文件", extensions: ["*"] },
 ];
 
@@ -2,6 +2,7 @@ import { useCallback } from "react";
 import {
   updateNodeReviewStatus,
   updateNodePinned,
+  updateNodeExcludeFromSearch,
   convertResourceToTask,
   convertResourceToTopic,
   convertTaskToTopic,
@@ -22,6 +23,7 @@ export interface NodeOperationsActions {
   approveNode: (node: NodeRecord) => Promise<void>;
   rejectNode: (node: NodeRecord) => Promise<void>;
   togglePinned: (node: NodeRecord) => Promise<void>;
+  toggleExcludeFromSearch: (node: NodeRecord) => Promise<void>;
   deleteNode: (node: NodeRecord, skipConfirm?: boolean) => Promise<boolean>;
   convertNode: (node: NodeRecord, targetType: "task" | "topic") => Promise<NodeRecord | null>;
   confirmEdgeRelation: (edge: EdgeWithNode) => Promise<void>;
@@ -57,6 +59,14 @@ export function useNodeOperations(
     [onSuccess, onPinnedChange]
   );
 
+  const toggleExcludeFromSearch = useCallback(
+    async (node: NodeRecord) => {
+      await updateNodeExcludeFromSearch(node.node_id, !node.exclude_from_search);
+      onSuccess?.();
+    },
+    [onSuccess]
+  );
+
   const deleteNode = useCallback(
     async (node: NodeRecord, skipConfirm = false): Promise<boolean> => {
       if (!skipConfirm && !window.confirm("确定要删除该节点吗？")) {
@@ -115,6 +125,7 @@ export function useNodeOperations(
     approveNode,
     rejectNode,
     togglePinned,
+    toggleExcludeFromSearch,
     deleteNode,
     convertNode,
     confirmEdgeRelation,
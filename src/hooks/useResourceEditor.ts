@@ -119,19 +119,22 @@ export function useResourceEditor(
     setSaveSuccess(false);
 
     try {
+      // 每次保存都带上最新的版本号，期间被 AI 处理等改写过时后端会拒绝覆盖
+      const nodeId = currentResource.node_id;
+      let version = currentResource.version;
       if (hasContentChange) {
         if (isTextResource) {
-          await updateResourceContent(currentResource.node_id, content);
+          version = await updateResourceContent(nodeId, content, version);
         } else {
           // 保存到 user_note 字段
-          await updateResourceUserNote(currentResource.node_id, content);
+          version = await updateResourceUserNote(nodeId, content, version);
         }
       }
       if (hasNoteChange) {
-        await updateResourceUserNote(currentResource.node_id, userNote);
+        version = await updateResourceUserNote(nodeId, userNote, version);
       }
       if (hasNameChange) {
-        await updateResourceTitle(currentResource.node_id, editedDisplayName);
+        version = await updateResourceTitle(nodeId, editedDisplayName, version);
       }
 
       setIsContentModified(false);
@@ -147,18 +150,21 @@ export function useResourceEditor(
         updatedResource = {
           ...currentResource,
           ...(isTextResource ? { file_content: content } : { user_note: content }),
+          version,
         };
       }
       if (hasNoteChange) {
         updatedResource = {
           ...(updatedResource ?? currentResource),
           user_note: userNote,
+          version,
         };
       }
       if (hasNameChange) {
         updatedResource = {
           ...(updatedResource ?? currentResource),
           title: editedDisplayName,
+          version,
         };
       }
 
@@ -0,0 +1,27 @@
+// 粘贴的表格 / 代码转为 Markdown，作为文本资源保存时保留结构
+
+const escapeCell = (cell: string) => cell.replace(/\|/g, "\\|").replace(/\n/g, " ");
+
+/** 表格转 Markdown，首行作为表头，缺少的单元格补空 */
+export function tableToMarkdown(rows: string[][]): string {
+  if (rows.length === 0) return "";
+  const columns = Math.max(...rows.map((row) => row.length));
+  const line = (row: string[]) =>
+    `| ${Array.from({ length: columns }, (_, i) => escapeCell(row[i] ?? "")).join(" | ")} |`;
+  const divider = `| ${Array(columns).fill("---").join(" | ")} |`;
+  return [line(rows[0]), divider, ...rows.slice(1).map(line)].join("\n");
+}
+
+/** 代码转 Markdown 代码块；代码本身含 ``` 时加长围栏 */
+export function codeToMarkdown(content: string, language: string | null): string {
+  const longestFence = Math.max(2, ...(content.match(/`{3,}/g) ?? []).map((run) => run.length));
+  const fence = "`".repeat(longestFence + 1);
+  return `${fence}${language ?? ""}\n${content.replace(/\n+$/, "")}\n${fence}`;
+}
+
+/** 粘贴内容可能是表格或代码，需要交给后端识别 */
+export function mayNeedSmartPaste(data: DataTransfer | null): boolean {
+  if (!data) return false;
+  if (data.getData("text/html").toLowerCase().includes("<table")) return true;
+  return data.getData("text/plain").trim().includes("\n");
+}
@@ -17,6 +17,21 @@ export function getFileTypeFromPath(filePath: string): string {
   if (ext === "epub") {
     return "epub"
   }
+  if (["docx", "pptx", "xlsx", "odt", "odp", "ods"].includes(ext)) {
+    return "office"
+  }
+  if (["mp3", "wav", "m4a", "ogg", "flac", "aac", "opus"].includes(ext)) {
+    return "audio"
+  }
+  if (ext === "ipynb") {
+    return "notebook"
+  }
+  if (["eml", "msg"].includes(ext)) {
+    return "email"
+  }
+  if (["zip", "tar", "gz", "tgz"].includes(ext)) {
+    return "archive"
+  }
   if (["txt", "md", "json", "csv", "xml", "html", "css", "js", "ts"].includes(ext)) {
     return "text"
   }
@@ -42,6 +57,26 @@ export function getFileIcon(fileName: string): string {
     svg: "🖼️",
     pdf: "📕",
     epub: "📖",
+    docx: "📘",
+    odt: "📘",
+    pptx: "📙",
+    odp: "📙",
+    xlsx: "📗",
+    ods: "📗",
+    mp3: "🎧",
+    wav: "🎧",
+    m4a: "🎧",
+    ogg: "🎧",
+    flac: "🎧",
+    aac: "🎧",
+    opus: "🎧",
+    ipynb: "📓",
+    eml: "✉️",
+    msg: "✉️",
+    zip: "🗜️",
+    tar: "🗜️",
+    gz: "🗜️",
+    tgz: "🗜️",
     js: "📜",
     ts: "📜",
     html: "🌐",
@@ -3,13 +3,14 @@ import { Button } from "@/components/ui/button";
 
 import { Sparkles, CheckCircle2, Plus } from "lucide-react";
 
-import { NodeRecord, IngestProgress, InputMode } from "../types";
+import { NodeRecord, IngestProgress, InputMode, FileStorageMode } from "../types";
 import { TaskCard } from "../components/TaskCard";
 import { ResourceCard } from "../components/ResourceCard";
 import { QuickCapture } from "../components/QuickCapture";
 import { TaskEditCard } from "../components/TaskEditCard";
 import { TasksDialog } from "../components/TasksDialog";
 import { TemporaryChatPanel } from "../components/TemporaryChatPanel";
+import { TagFilter } from "../components/TagFilter";
 import { softDeleteTask, softDeleteResource, fetchAllTasks } from "../api";
 import { useLanguage } from "@/contexts/LanguageContext";
 import { isSameDay } from "date-fns";
@@ -18,9 +19,16 @@ import { sortTasksForDashboard } from "@/lib/taskSort";
 interface DashboardPageProps {
   tasks: NodeRecord[];
   resources: NodeRecord[];
+  tagFilter?: number[];
+  onTagFilterChange?: (tagIds: number[]) => void;
   loading: boolean;
   error: string | null;
-  onCapture: (content: string, filePath?: string) => Promise<void>;
+  onCapture: (content: string, filePath?: string, storage?: FileStorageMode) => Promise<void>;
+  onCaptureFiles?: (
+    content: string,
+    filePaths: string[],
+    storage?: FileStorageMode
+  ) => Promise<void>;
   onRefresh: () => Promise<void>;
   onSelectTask: (task: NodeRecord) => void;
   onSelectResource: (resource: NodeRecord) => void;
@@ -32,9 +40,12 @@ interface DashboardPageProps {
 export function DashboardPage({
   tasks,
   resources,
+  tagFilter = [],
+  onTagFilterChange,
   loading,
   error,
   onCapture,
+  onCaptureFiles,
   onRefresh,
   onSelectTask,
   onSelectResource,
@@ -101,11 +112,16 @@ export function DashboardPage({
         <div className="max-w-2xl">
           <QuickCapture
             onCapture={onCapture}
+            onCaptureFiles={onCaptureFiles}
             mode={captureMode}
             onModeChange={setCaptureMode}
             onChatSubmit={handleChatSubmit}
           />
         </div>
+
+        {onTagFilterChange && (
+          <TagFilter selected={tagFilter} onChange={onTagFilterChange} />
+        )}
       </section>
 
       {/* 2. Tasks Area */}
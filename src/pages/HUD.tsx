@@ -1,29 +1,71 @@
-import { useEffect, useCallback } from "react";
+import { useEffect, useCallback, useState } from "react";
 import { listen, emit } from "@tauri-apps/api/event";
-import { quickCapture } from "../api";
+import { quickCapture, captureResourcesBatch, listCaptureProfiles } from "../api";
 import { QuickCapture } from "../components";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
+import { useLanguage } from "@/contexts/LanguageContext";
 import { getFileTypeFromPath } from "../lib/utils";
+import type { CaptureProfile, FileStorageMode } from "../types";
+
+const NO_PROFILE = "none";
+
+interface HudFocusPayload {
+  // 通过捕获模板快捷键唤起时的模板 ID
+  profile_id: number | null;
+}
 
 export function HUDPage() {
+  const { t } = useLanguage();
+  const [profiles, setProfiles] = useState<CaptureProfile[]>([]);
+  const [profileId, setProfileId] = useState<number | undefined>(undefined);
+
   // 处理捕获
   const handleCapture = useCallback(
-    async (content: string, filePath?: string) => {
+    async (
+      content: string,
+      filePath?: string,
+      storage?: FileStorageMode,
+      ocrText?: string
+    ) => {
       if (filePath) {
         // 有文件：传递文件路径给 Rust
         await quickCapture({
           file_path: filePath,
           file_type: getFileTypeFromPath(filePath),
           content: content || undefined,
+          storage,
+          ocr_text: ocrText || undefined,
+          profile_id: profileId,
         });
       } else if (content) {
         // 纯文本
         await quickCapture({
           content,
           file_type: "text",
+          profile_id: profileId,
         });
       }
     },
-    []
+    [profileId]
+  );
+
+  // 多个文件：一次批量捕获
+  const handleCaptureFiles = useCallback(
+    async (content: string, filePaths: string[], storage?: FileStorageMode) => {
+      await captureResourcesBatch({
+        file_paths: filePaths,
+        user_note: content || undefined,
+        storage,
+        profile_id: profileId,
+      });
+    },
+    [profileId]
   );
 
   // 隐藏窗口
@@ -34,9 +76,18 @@ export function HUDPage() {
   // 监听 hud-focus 事件
   // 方向：后端 -> 前端
   // 场景：用户按下快捷键唤起窗口时，Rust 发送 hud-focus 信号
+  // 通过捕获模板快捷键唤起时带上模板 ID，默认快捷键则清除模板
   useEffect(() => {
-    const unlisten = listen("hud-focus", () => {
+    listCaptureProfiles()
+      .then(setProfiles)
+      .catch(() => setProfiles([]));
+
+    const unlisten = listen<HudFocusPayload>("hud-focus", (event) => {
       // QuickCapture 组件会自动聚焦（通过 autoFocus prop）
+      setProfileId(event.payload.profile_id ?? undefined);
+      listCaptureProfiles()
+        .then(setProfiles)
+        .catch(() => setProfiles([]));
     });
 
     return () => {
@@ -46,10 +97,31 @@ export function HUDPage() {
 
   return (
     <div className="h-screen w-screen flex items-center justify-center p-4 bg-transparent">
-      <div className="w-full max-w-lg">
+      <div className="w-full max-w-lg space-y-2">
+        {profiles.length > 0 && (
+          <Select
+            value={profileId !== undefined ? String(profileId) : NO_PROFILE}
+            onValueChange={(value) =>
+              setProfileId(value === NO_PROFILE ? undefined : Number(value))
+            }
+          >
+            <SelectTrigger className="h-8 w-48 bg-background">
+              <SelectValue />
+            </SelectTrigger>
+            <SelectContent>
+              <SelectItem value={NO_PROFILE}>{t("dashboard", "captureProfileNone")}</SelectItem>
+              {profiles.map((profile) => (
+                <SelectItem key={profile.profile_id} value={String(profile.profile_id)}>
+                  {profile.name}
+                </SelectItem>
+              ))}
+            </SelectContent>
+          </Select>
+        )}
         <QuickCapture
           variant="hud"
           onCapture={handleCapture}
+          onCaptureFiles={handleCaptureFiles}
           onSuccess={handleHide}
           onCancel={handleHide}
           autoFocus
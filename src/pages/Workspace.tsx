@@ -205,6 +205,7 @@ export function WorkspacePage({
             editing ? editor.startEditingName() : editor.cancelEditingName()
           }
           onDisplayNameChange={editor.setEditedDisplayName}
+          onResourceUpdated={context.updateResource}
         />
 
         {/* Right: Chat Panel */}
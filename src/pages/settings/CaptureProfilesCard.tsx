@@ -0,0 +1,232 @@
+import { useEffect, useState } from "react";
+import {
+  Card,
+  CardContent,
+  CardHeader,
+  CardTitle,
+} from "@/components/ui/card";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
+import { Badge } from "@/components/ui/badge";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Switch } from "@/components/ui/switch";
+import { Layers, Pencil, Trash2 } from "lucide-react";
+import { useLanguage } from "@/contexts/LanguageContext";
+import {
+  deleteCaptureProfile,
+  fetchAllTopics,
+  listCaptureProfiles,
+  saveCaptureProfile,
+} from "@/api";
+import type { CaptureProfile, NodeRecord } from "@/types";
+
+const NO_TOPIC = "none";
+
+interface ProfileForm {
+  profile_id?: number;
+  name: string;
+  shortcut: string;
+  topic_id: string;
+  tags: string;
+  defer_processing: boolean;
+}
+
+const EMPTY_FORM: ProfileForm = {
+  name: "",
+  shortcut: "",
+  topic_id: NO_TOPIC,
+  tags: "",
+  defer_processing: false,
+};
+
+export function CaptureProfilesCard() {
+  const { t } = useLanguage();
+  const [profiles, setProfiles] = useState<CaptureProfile[]>([]);
+  const [topics, setTopics] = useState<NodeRecord[]>([]);
+  const [form, setForm] = useState<ProfileForm>(EMPTY_FORM);
+  const [saving, setSaving] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    listCaptureProfiles()
+      .then(setProfiles)
+      .catch((err) => setError(String(err)));
+    fetchAllTopics()
+      .then(setTopics)
+      .catch((err) => setError(String(err)));
+  }, []);
+
+  const topicTitle = (topicId: number | null) =>
+    topics.find((topic) => topic.node_id === topicId)?.title;
+
+  const handleEdit = (profile: CaptureProfile) => {
+    setError(null);
+    setForm({
+      profile_id: profile.profile_id,
+      name: profile.name,
+      shortcut: profile.shortcut ?? "",
+      topic_id: profile.topic_id ? String(profile.topic_id) : NO_TOPIC,
+      tags: profile.tags.join(", "),
+      defer_processing: profile.defer_processing,
+    });
+  };
+
+  const handleSave = async () => {
+    setSaving(true);
+    setError(null);
+    try {
+      const saved = await saveCaptureProfile({
+        profile_id: form.profile_id,
+        name: form.name,
+        shortcut: form.shortcut.trim() || undefined,
+        topic_id: form.topic_id === NO_TOPIC ? undefined : Number(form.topic_id),
+        tags: form.tags.split(/[,，]/),
+        defer_processing: form.defer_processing,
+      });
+      setProfiles((prev) =>
+        prev.some((profile) => profile.profile_id === saved.profile_id)
+          ? prev.map((profile) => (profile.profile_id === saved.profile_id ? saved : profile))
+          : [...prev, saved]
+      );
+      setForm(EMPTY_FORM);
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const handleDelete = async (profileId: number) => {
+    setError(null);
+    try {
+      await deleteCaptureProfile(profileId);
+      setProfiles((prev) => prev.filter((profile) => profile.profile_id !== profileId));
+      if (form.profile_id === profileId) setForm(EMPTY_FORM);
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <Layers className="h-5 w-5" />
+          {t("settings", "captureProfiles")}
+        </CardTitle>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <p className="text-xs text-muted-foreground">{t("settings", "captureProfilesDesc")}</p>
+
+        {profiles.length === 0 ? (
+          <p className="text-sm text-muted-foreground">{t("settings", "captureProfilesEmpty")}</p>
+        ) : (
+          <div className="space-y-2">
+            {profiles.map((profile) => (
+              <div
+                key={profile.profile_id}
+                className="flex items-center justify-between gap-4"
+              >
+                <div className="space-y-0.5 min-w-0">
+                  <p className="text-sm font-medium truncate">{profile.name}</p>
+                  <p className="text-xs text-muted-foreground truncate">
+                    {[
+                      topicTitle(profile.topic_id),
+                      profile.tags.map((tag) => `#${tag}`).join(" "),
+                      profile.defer_processing ? t("settings", "captureProfileDefer") : null,
+                    ]
+                      .filter(Boolean)
+                      .join(" · ")}
+                  </p>
+                </div>
+                <div className="flex items-center gap-2 shrink-0">
+                  {profile.shortcut && (
+                    <Badge variant="secondary" className="font-mono">
+                      {profile.shortcut}
+                    </Badge>
+                  )}
+                  <Button variant="ghost" size="icon" onClick={() => handleEdit(profile)}>
+                    <Pencil className="h-4 w-4" />
+                  </Button>
+                  <Button
+                    variant="ghost"
+                    size="icon"
+                    onClick={() => handleDelete(profile.profile_id)}
+                  >
+                    <Trash2 className="h-4 w-4" />
+                  </Button>
+                </div>
+              </div>
+            ))}
+          </div>
+        )}
+
+        <div className="space-y-3 border-t pt-4">
+          <div className="grid grid-cols-2 gap-3">
+            <Input
+              value={form.name}
+              onChange={(e) => setForm({ ...form, name: e.target.value })}
+              placeholder={t("settings", "captureProfileName")}
+            />
+            <Input
+              value={form.shortcut}
+              onChange={(e) => setForm({ ...form, shortcut: e.target.value })}
+              placeholder={t("settings", "captureProfileShortcut")}
+              className="font-mono"
+            />
+            <Select
+              value={form.topic_id}
+              onValueChange={(value) => setForm({ ...form, topic_id: value })}
+            >
+              <SelectTrigger>
+                <SelectValue />
+              </SelectTrigger>
+              <SelectContent>
+                <SelectItem value={NO_TOPIC}>{t("settings", "captureProfileNoTopic")}</SelectItem>
+                {topics.map((topic) => (
+                  <SelectItem key={topic.node_id} value={String(topic.node_id)}>
+                    {topic.title}
+                  </SelectItem>
+                ))}
+              </SelectContent>
+            </Select>
+            <Input
+              value={form.tags}
+              onChange={(e) => setForm({ ...form, tags: e.target.value })}
+              placeholder={t("settings", "captureProfileTags")}
+            />
+          </div>
+          <div className="flex items-center justify-between">
+            <label className="flex items-center gap-2 text-sm">
+              <Switch
+                checked={form.defer_processing}
+                onCheckedChange={(checked) => setForm({ ...form, defer_processing: checked })}
+              />
+              {t("settings", "captureProfileDefer")}
+            </label>
+            <div className="flex gap-2">
+              {form.profile_id !== undefined && (
+                <Button variant="ghost" onClick={() => setForm(EMPTY_FORM)}>
+                  {t("settings", "captureProfileCancel")}
+                </Button>
+              )}
+              <Button onClick={handleSave} disabled={saving || !form.name.trim()}>
+                {form.profile_id !== undefined
+                  ? t("settings", "captureProfileSave")
+                  : t("settings", "captureProfileAdd")}
+              </Button>
+            </div>
+          </div>
+        </div>
+
+        {error && <p className="text-sm text-destructive">{error}</p>}
+      </CardContent>
+    </Card>
+  );
+}
@@ -14,10 +14,18 @@ import {
 import { Key } from "lucide-react";
 import { useLanguage } from "@/contexts/LanguageContext";
 import { useAIConfig } from "@/contexts/AIContext";
+import type { SummaryLanguageMode } from "@/types";
 
 export function ClassificationCard() {
   const { t } = useLanguage();
-  const { config, loading, classificationMode, saveClassificationMode } = useAIConfig();
+  const {
+    config,
+    loading,
+    classificationMode,
+    saveClassificationMode,
+    summaryLanguage,
+    saveSummaryLanguage,
+  } = useAIConfig();
 
   return (
     <Card>
@@ -51,6 +59,30 @@ export function ClassificationCard() {
             </SelectContent>
           </Select>
         </div>
+        <div className="flex items-center justify-between">
+          <div className="space-y-0.5">
+            <label className="text-sm font-medium">
+              {t("settings", "summaryLanguage")}
+            </label>
+            <p className="text-xs text-muted-foreground">
+              {t("settings", "summaryLanguageDesc")}
+            </p>
+          </div>
+          <Select
+            value={summaryLanguage ?? "configured"}
+            onValueChange={(val: SummaryLanguageMode) => saveSummaryLanguage(val)}
+            disabled={!config || loading}
+          >
+            <SelectTrigger className="w-[200px]">
+              <SelectValue />
+            </SelectTrigger>
+            <SelectContent>
+              <SelectItem value="match_source">{t("settings", "summaryLanguageMatchSource")}</SelectItem>
+              <SelectItem value="configured">{t("settings", "summaryLanguageConfigured")}</SelectItem>
+              <SelectItem value="bilingual">{t("settings", "summaryLanguageBilingual")}</SelectItem>
+            </SelectContent>
+          </Select>
+        </div>
       </CardContent>
     </Card>
   );
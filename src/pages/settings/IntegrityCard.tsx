@@ -0,0 +1,145 @@
+import { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import {
+  Card,
+  CardContent,
+  CardHeader,
+  CardTitle,
+} from "@/components/ui/card";
+import { Button } from "@/components/ui/button";
+import { ShieldCheck } from "lucide-react";
+import { useLanguage } from "@/contexts/LanguageContext";
+import { getLatestIntegrityReport, verifyAssetIntegrity } from "@/api";
+import type { IntegrityReport } from "@/types";
+
+// 列表中最多显示的问题数
+const ISSUE_LIMIT = 50;
+
+interface IntegrityCheckProgress {
+  check_id: number;
+  processed: number;
+  total: number;
+}
+
+export function IntegrityCard() {
+  const { t } = useLanguage();
+  const [report, setReport] = useState<IntegrityReport | null>(null);
+  const [running, setRunning] = useState(false);
+  const [progress, setProgress] = useState<IntegrityCheckProgress | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    getLatestIntegrityReport()
+      .then(setReport)
+      .catch((err) => setError(String(err)));
+  }, []);
+
+  useEffect(() => {
+    let unlisten: (() => void) | null = null;
+    let cancelled = false;
+    listen<IntegrityCheckProgress>("integrity-check-progress", (event) => {
+      setProgress(event.payload);
+    }).then((fn) => {
+      if (cancelled) fn();
+      else unlisten = fn;
+    });
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, []);
+
+  const handleVerify = async (reparse: boolean) => {
+    setRunning(true);
+    setProgress(null);
+    setError(null);
+    try {
+      setReport(await verifyAssetIntegrity(reparse));
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setRunning(false);
+    }
+  };
+
+  const check = report?.check;
+  const hasMismatch = report?.issues.some((issue) => issue.kind === "mismatch" && !issue.reparsed);
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <ShieldCheck className="h-5 w-5" />
+          {t("settings", "integrity")}
+        </CardTitle>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <div className="flex items-center justify-between gap-4">
+          <div className="space-y-0.5 min-w-0">
+            <p className="text-xs text-muted-foreground">{t("settings", "integrityDesc")}</p>
+            {check && (
+              <p className="text-sm">
+                {t("settings", "integrityLastCheck")
+                  .replace("{time}", check.finished_at ?? check.started_at)
+                  .replace("{checked}", String(check.checked_count))}
+                {" · "}
+                {check.missing_count + check.mismatched_count === 0
+                  ? t("settings", "integrityOk")
+                  : t("settings", "integrityIssues")
+                      .replace("{missing}", String(check.missing_count))
+                      .replace("{mismatched}", String(check.mismatched_count))}
+              </p>
+            )}
+          </div>
+          <div className="flex gap-2 shrink-0">
+            {hasMismatch && (
+              <Button variant="outline" onClick={() => handleVerify(true)} disabled={running}>
+                {t("settings", "integrityReparse")}
+              </Button>
+            )}
+            <Button variant="outline" onClick={() => handleVerify(false)} disabled={running}>
+              {running && progress
+                ? t("settings", "integrityVerifying")
+                    .replace("{processed}", String(progress.processed))
+                    .replace("{total}", String(progress.total))
+                : t("settings", "integrityVerify")}
+            </Button>
+          </div>
+        </div>
+
+        {(error || check?.error) && (
+          <p className="text-sm text-destructive">{error ?? check?.error}</p>
+        )}
+
+        {report && report.issues.length > 0 && (
+          <div className="space-y-1">
+            {report.issues.slice(0, ISSUE_LIMIT).map((issue) => (
+              <div key={issue.issue_id} className="flex items-center gap-2 text-xs">
+                <span
+                  className={
+                    issue.kind === "missing" ? "text-destructive" : "text-muted-foreground"
+                  }
+                >
+                  {issue.kind === "missing"
+                    ? t("settings", "integrityMissing")
+                    : t("settings", "integrityMismatch")}
+                </span>
+                <span className="truncate flex-1" title={issue.file_path}>
+                  {issue.title}
+                </span>
+                {issue.reparsed && (
+                  <span className="text-muted-foreground">{t("settings", "integrityReparsed")}</span>
+                )}
+              </div>
+            ))}
+            {report.issues.length > ISSUE_LIMIT && (
+              <p className="text-xs text-muted-foreground">
+                … +{report.issues.length - ISSUE_LIMIT}
+              </p>
+            )}
+          </div>
+        )}
+      </CardContent>
+    </Card>
+  );
+}
@@ -0,0 +1,208 @@
+import { useEffect, useState } from "react";
+import {
+  Card,
+  CardContent,
+  CardHeader,
+  CardTitle,
+} from "@/components/ui/card";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Switch } from "@/components/ui/switch";
+import { SlidersHorizontal } from "lucide-react";
+import { useLanguage } from "@/contexts/LanguageContext";
+import { useAIConfig } from "@/contexts/AIContext";
+import type {
+  ChatContextConfig,
+  FusionStrategy,
+  RetrievalConfig,
+  VectorDistance,
+} from "@/types";
+
+const DEFAULT_RETRIEVAL: RetrievalConfig = {
+  top_k: 5,
+  distance: "cosine",
+  vector_weight: 0.5,
+  fusion: "max",
+  rerank: false,
+};
+
+const DEFAULT_CHAT_CONTEXT: ChatContextConfig = {
+  max_input_tokens: 32000,
+  min_recent_messages: 4,
+};
+
+export function RetrievalCard() {
+  const { t } = useLanguage();
+  const { config, loading, saveRetrievalConfig, saveChatContextConfig } = useAIConfig();
+  const [form, setForm] = useState<RetrievalConfig>(DEFAULT_RETRIEVAL);
+  const [context, setContext] = useState<ChatContextConfig>(DEFAULT_CHAT_CONTEXT);
+  const [saving, setSaving] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    if (config?.retrieval) setForm(config.retrieval);
+  }, [config?.retrieval]);
+
+  useEffect(() => {
+    if (config?.chat_context) setContext(config.chat_context);
+  }, [config?.chat_context]);
+
+  const handleSave = async () => {
+    setSaving(true);
+    setError(null);
+    try {
+      await saveRetrievalConfig(form);
+      await saveChatContextConfig(context);
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <SlidersHorizontal className="h-5 w-5" />
+          {t("settings", "retrieval")}
+        </CardTitle>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <p className="text-xs text-muted-foreground">{t("settings", "retrievalDesc")}</p>
+
+        <div className="flex items-center justify-between">
+          <label className="text-sm font-medium">{t("settings", "retrievalTopK")}</label>
+          <Input
+            type="number"
+            min={1}
+            max={50}
+            value={form.top_k}
+            onChange={(e) => setForm({ ...form, top_k: Number(e.target.value) })}
+            className="w-[200px]"
+          />
+        </div>
+
+        <div className="flex items-center justify-between">
+          <label className="text-sm font-medium">{t("settings", "retrievalDistance")}</label>
+          <Select
+            value={form.distance}
+            onValueChange={(value: VectorDistance) => setForm({ ...form, distance: value })}
+          >
+            <SelectTrigger className="w-[200px]">
+              <SelectValue />
+            </SelectTrigger>
+            <SelectContent>
+              <SelectItem value="cosine">Cosine</SelectItem>
+              <SelectItem value="l2">L2</SelectItem>
+              <SelectItem value="dot">Dot</SelectItem>
+            </SelectContent>
+          </Select>
+        </div>
+
+        <div className="flex items-center justify-between">
+          <div className="space-y-0.5">
+            <label className="text-sm font-medium">
+              {t("settings", "retrievalVectorWeight")}
+            </label>
+            <p className="text-xs text-muted-foreground">
+              {t("settings", "retrievalVectorWeightDesc")}
+            </p>
+          </div>
+          <Input
+            type="number"
+            min={0}
+            max={1}
+            step={0.1}
+            value={form.vector_weight}
+            onChange={(e) => setForm({ ...form, vector_weight: Number(e.target.value) })}
+            className="w-[200px]"
+          />
+        </div>
+
+        <div className="flex items-center justify-between">
+          <div className="space-y-0.5">
+            <label className="text-sm font-medium">{t("settings", "retrievalFusion")}</label>
+            <p className="text-xs text-muted-foreground">
+              {t("settings", "retrievalFusionDesc")}
+            </p>
+          </div>
+          <Select
+            value={form.fusion}
+            onValueChange={(value: FusionStrategy) => setForm({ ...form, fusion: value })}
+          >
+            <SelectTrigger className="w-[200px]">
+              <SelectValue />
+            </SelectTrigger>
+            <SelectContent>
+              <SelectItem value="max">{t("settings", "retrievalFusionMax")}</SelectItem>
+              <SelectItem value="rrf">{t("settings", "retrievalFusionRrf")}</SelectItem>
+              <SelectItem value="normalized">
+                {t("settings", "retrievalFusionNormalized")}
+              </SelectItem>
+            </SelectContent>
+          </Select>
+        </div>
+
+        <div className="flex items-center justify-between">
+          <div className="space-y-0.5">
+            <label className="text-sm font-medium">{t("settings", "retrievalRerank")}</label>
+            <p className="text-xs text-muted-foreground">
+              {t("settings", "retrievalRerankDesc")}
+            </p>
+          </div>
+          <Switch
+            checked={form.rerank}
+            onCheckedChange={(checked) => setForm({ ...form, rerank: checked })}
+          />
+        </div>
+
+        <div className="flex items-center justify-between">
+          <div className="space-y-0.5">
+            <label className="text-sm font-medium">{t("settings", "chatMaxInputTokens")}</label>
+            <p className="text-xs text-muted-foreground">
+              {t("settings", "chatMaxInputTokensDesc")}
+            </p>
+          </div>
+          <Input
+            type="number"
+            min={2000}
+            step={1000}
+            value={context.max_input_tokens}
+            onChange={(e) => setContext({ ...context, max_input_tokens: Number(e.target.value) })}
+            className="w-[200px]"
+          />
+        </div>
+
+        <div className="flex items-center justify-between">
+          <label className="text-sm font-medium">{t("settings", "chatMinRecentMessages")}</label>
+          <Input
+            type="number"
+            min={1}
+            max={50}
+            value={context.min_recent_messages}
+            onChange={(e) =>
+              setContext({ ...context, min_recent_messages: Number(e.target.value) })
+            }
+            className="w-[200px]"
+          />
+        </div>
+
+        <div className="flex justify-end">
+          <Button onClick={handleSave} disabled={!config || loading || saving}>
+            {t("settings", "retrievalSave")}
+          </Button>
+        </div>
+
+        {error && <p className="text-sm text-destructive">{error}</p>}
+      </CardContent>
+    </Card>
+  );
+}
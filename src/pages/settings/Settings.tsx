@@ -5,7 +5,11 @@ import { APIConfigCard } from "./APIConfigCard";
 import { ProcessingConfigCard } from "./ProcessingConfigCard";
 import { LocalModelCard } from "./LocalModelCard";
 import { ClassificationCard } from "./ClassificationCard";
+import { RetrievalCard } from "./RetrievalCard";
 import { ShortcutsCard } from "./ShortcutsCard";
+import { CaptureProfilesCard } from "./CaptureProfilesCard";
+import { StorageCard } from "./StorageCard";
+import { IntegrityCard } from "./IntegrityCard";
 
 interface SettingsPageProps {
   theme: "light" | "dark" | "system";
@@ -31,7 +35,11 @@ export function SettingsPage({ theme, onThemeChange }: SettingsPageProps) {
         <ProcessingConfigCard />
         <LocalModelCard />
         <ClassificationCard />
+        <RetrievalCard />
+        <StorageCard />
+        <IntegrityCard />
         <ShortcutsCard />
+        <CaptureProfilesCard />
       </div>
     </div>
   );
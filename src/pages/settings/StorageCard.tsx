@@ -0,0 +1,129 @@
+import { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { open } from "@tauri-apps/plugin-dialog";
+import {
+  Card,
+  CardContent,
+  CardHeader,
+  CardTitle,
+} from "@/components/ui/card";
+import { Button } from "@/components/ui/button";
+import { HardDrive } from "lucide-react";
+import { useLanguage } from "@/contexts/LanguageContext";
+import { getAssetsStorage, relocateAssets } from "@/api";
+import type { AssetsRelocateSummary, AssetsStorageStatus } from "@/types";
+
+interface AssetsRelocateProgress {
+  status: "copying" | "removing" | "done";
+  processed: number;
+  total: number;
+}
+
+export function StorageCard() {
+  const { t } = useLanguage();
+  const [storage, setStorage] = useState<AssetsStorageStatus | null>(null);
+  const [relocating, setRelocating] = useState(false);
+  const [progress, setProgress] = useState<AssetsRelocateProgress | null>(null);
+  const [summary, setSummary] = useState<AssetsRelocateSummary | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    getAssetsStorage()
+      .then(setStorage)
+      .catch((err) => setError(String(err)));
+  }, []);
+
+  useEffect(() => {
+    let unlisten: (() => void) | null = null;
+    let cancelled = false;
+    listen<AssetsRelocateProgress>("assets-relocate-progress", (event) => {
+      setProgress(event.payload);
+    }).then((fn) => {
+      if (cancelled) fn();
+      else unlisten = fn;
+    });
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, []);
+
+  const handleRelocate = async () => {
+    const selected = await open({ directory: true, multiple: false });
+    if (!selected || Array.isArray(selected)) return;
+
+    setRelocating(true);
+    setProgress(null);
+    setSummary(null);
+    setError(null);
+    try {
+      const result = await relocateAssets(selected);
+      setSummary(result);
+      setStorage(result.status);
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setRelocating(false);
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <HardDrive className="h-5 w-5" />
+          {t("settings", "storage")}
+        </CardTitle>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <div className="flex items-center justify-between gap-4">
+          <div className="space-y-0.5 min-w-0">
+            <label className="text-sm font-medium">
+              {storage?.is_custom
+                ? t("settings", "storageCustom")
+                : t("settings", "storageDefault")}
+            </label>
+            <p className="text-xs text-muted-foreground truncate" title={storage?.path}>
+              {storage?.path}
+            </p>
+          </div>
+          <Button
+            variant="outline"
+            onClick={handleRelocate}
+            disabled={!storage || relocating}
+          >
+            {relocating && progress && progress.status === "copying"
+              ? t("settings", "storageRelocating")
+                  .replace("{processed}", String(progress.processed))
+                  .replace("{total}", String(progress.total))
+              : t("settings", "storageRelocate")}
+          </Button>
+        </div>
+
+        {storage && !storage.available && (
+          <p className="text-sm text-destructive">{t("settings", "storageUnavailable")}</p>
+        )}
+        {error && <p className="text-sm text-destructive">{error}</p>}
+        {summary && (
+          <div className="text-xs text-muted-foreground space-y-1">
+            <p>
+              {t("settings", "storageRelocated").replace(
+                "{count}",
+                String(summary.moved_files),
+              )}
+            </p>
+            {summary.left_behind > 0 && (
+              <p>
+                {t("settings", "storageLeftBehind").replace(
+                  "{count}",
+                  String(summary.left_behind),
+                )}
+              </p>
+            )}
+          </div>
+        )}
+        <p className="text-xs text-muted-foreground">{t("settings", "storageDesc")}</p>
+      </CardContent>
+    </Card>
+  );
+}
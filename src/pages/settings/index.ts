@@ -5,4 +5,8 @@ export { APIConfigCard } from "./APIConfigCard";
 export { ProcessingConfigCard } from "./ProcessingConfigCard";
 export { LocalModelCard } from "./LocalModelCard";
 export { ClassificationCard } from "./ClassificationCard";
+export { RetrievalCard } from "./RetrievalCard";
 export { ShortcutsCard } from "./ShortcutsCard";
+export { CaptureProfilesCard } from "./CaptureProfilesCard";
+export { StorageCard } from "./StorageCard";
+export { IntegrityCard } from "./IntegrityCard";
@@ -305,6 +305,7 @@ export function WarehousePage({
                       onApprove={() => nodeOps.approveNode(node)}
                       onReject={() => nodeOps.rejectNode(node)}
                       onTogglePinned={() => nodeOps.togglePinned(node)}
+                      onToggleExcludeFromSearch={() => nodeOps.toggleExcludeFromSearch(node)}
                       onDelete={() => nodeOps.deleteNode(node)}
                       onConvert={(targetType) => nodeOps.convertNode(node, targetType)}
                       onLink={() => void linkNodes.openDialog(node)}
@@ -75,6 +75,28 @@ export const translations = {
       classificationDesc: "控制低置信度结果的处理方式",
       classificationManual: "手动复核",
       classificationAggressive: "激进自动",
+      summaryLanguage: "摘要语言",
+      summaryLanguageDesc: "资源摘要使用的语言",
+      summaryLanguageMatchSource: "与原文一致",
+      summaryLanguageConfigured: "界面语言",
+      summaryLanguageBilingual: "原文 + 界面语言",
+      retrieval: "对话检索",
+      retrievalDesc: "每次对话从知识库检索的分块数与排序方式，综合性问题可适当调大数量",
+      retrievalTopK: "检索分块数",
+      retrievalDistance: "向量距离",
+      retrievalVectorWeight: "向量权重",
+      retrievalVectorWeightDesc: "0 只看关键词，1 只看语义，0.5 为等权",
+      retrievalFusion: "结果合并",
+      retrievalFusionDesc: "文本与图片结果的合并方式",
+      retrievalRerank: "重排",
+      retrievalRerankDesc: "用本地 cross-encoder 模型对前 50 个候选重新排序，更准确但更慢",
+      retrievalFusionMax: "原始分数",
+      retrievalFusionRrf: "排名融合（RRF）",
+      retrievalFusionNormalized: "归一化分数",
+      chatMaxInputTokens: "历史上限（token）",
+      chatMaxInputTokensDesc: "对话历史超过该长度时，较早的消息会被压缩为摘要",
+      chatMinRecentMessages: "原样保留的最近消息数",
+      retrievalSave: "保存",
       localModel: "本地模型",
       enableLocal: "启用本地模型",
       shortcuts: "键盘快捷键",
@@ -83,6 +105,37 @@ export const translations = {
       shortcutQuickCapture: "快速捕获",
       shortcutSearch: "全局搜索",
       shortcutSave: "保存（编辑器）",
+      captureProfiles: "捕获模板",
+      captureProfilesDesc: "每个模板可绑定独立的全局快捷键（如 Alt+Shift+W），捕获时自动归入主题、打标签，或推迟 AI 处理",
+      captureProfilesEmpty: "暂无捕获模板",
+      captureProfileName: "模板名称",
+      captureProfileShortcut: "快捷键（可选）",
+      captureProfileNoTopic: "不归入主题",
+      captureProfileTags: "标签，逗号分隔",
+      captureProfileDefer: "推迟 AI 处理",
+      captureProfileAdd: "添加模板",
+      captureProfileSave: "保存",
+      captureProfileCancel: "取消",
+      storage: "资源文件存储",
+      storageDesc: "捕获的文件保存在此目录，可迁移到外接硬盘等位置",
+      storageDefault: "默认位置",
+      storageCustom: "自定义位置",
+      storageUnavailable: "目录不可用，请连接所在硬盘或重新选择目录",
+      storageRelocate: "迁移到…",
+      storageRelocating: "迁移中 {processed}/{total}",
+      storageRelocated: "已迁移 {count} 个文件",
+      storageLeftBehind: "{count} 个旧文件未能删除",
+      integrity: "文件完整性",
+      integrityDesc: "重新计算资源文件的哈希，发现缺失或被修改的文件；每周在接通电源时自动校验一次",
+      integrityVerify: "校验文件",
+      integrityVerifying: "校验中 {processed}/{total}",
+      integrityReparse: "重新解析已变更的文件",
+      integrityLastCheck: "上次校验 {time}：{checked} 个文件",
+      integrityOk: "未发现问题",
+      integrityIssues: "{missing} 个缺失，{mismatched} 个已变更",
+      integrityMissing: "缺失",
+      integrityMismatch: "已变更",
+      integrityReparsed: "已重新解析",
       configured: "已配置",
       notConfigured: "未配置",
       configure: "配置",
@@ -93,12 +146,21 @@ export const translations = {
       greetingMorning: "早上好",
       greetingEvening: "晚上好",
       quickCapture: "快速记录想法...",
+      storageCopy: "复制到资源库",
+      storageReference: "原地引用",
+      storageReferenceDesc: "不复制文件，只记录路径（适合大文件）；文件被移走或硬盘未连接时暂时无法打开",
+      ocrPreview: "将提取的文字（可修改）",
+      ocrPreviewEmpty: "未识别到文字，可手动填写",
+      ocrPreviewFailed: "识别失败，捕获后会在后台重试",
+      captureProfileNone: "不使用模板",
       tasks: "待办事项",
       resources: "资源收件箱",
       createTask: "新建任务",
       noTasks: "暂无待办任务，开始创建吧！",
       completedToday: "今日已完成",
       completedTodayTasks: "今日已完成的任务",
+      tagFilter: "按标签筛选",
+      tagFilterClear: "清除",
     },
     workspace: {
       title: "工作台",
@@ -121,6 +183,8 @@ export const translations = {
       ragScope: "RAG 范围",
       ragScopeLocal: "当前节点",
       ragScopeGlobal: "全局",
+      ragTopK: "检索分块数",
+      ragTopKDefault: "默认",
       chatSessions: "会话历史",
       newChatSession: "新建对话",
       noChatSessions: "暂无历史会话",
@@ -143,6 +207,22 @@ export const translations = {
       resourceBreadcrumb: "资源",
       editText: "编辑标注",
       viewFile: "查看文件",
+      revisions: "历史版本",
+      revisionsEmpty: "暂无历史版本",
+      revisionRestore: "恢复",
+      revisionReasonEdit: "编辑前",
+      revisionReasonRestore: "恢复前",
+      revisionChars: "{count} 字",
+      referenceOnline: "原地引用: {path}",
+      referenceOffline: "引用的文件不可用: {path}",
+      referenceOfflineDesc: "文件可能已被移动、删除，或所在硬盘未连接。重新连接后会自动恢复，也可以重新指定文件。",
+      referenceRelink: "重新指定文件",
+      tags: "标签",
+      tagAddPlaceholder: "添加标签...",
+      tagRemove: "移除标签",
+      tagSuggested: "AI 建议",
+      tagSuggestionAccept: "采纳",
+      tagSuggestionReject: "忽略",
       attachedText: "备注",
       resourceNotes: "备注",
       resourceNotesPlaceholder: "添加备注...",
@@ -168,9 +248,17 @@ export const translations = {
       tokenUsageOutput: "输出",
       tokenUsageReasoning: "思考",
       tokenUsageTotal: "总计",
+      chatSources: "来源",
       pinToContext: "保存到上下文",
+      forkChatSession: "从这里分叉出新会话",
       containedNodes: "包含的节点",
       relatedNodes: "关联的节点",
+      archiveEntries: "共 {count} 个文件，{supported} 个可展开为资源",
+      archiveExpand: "展开为资源",
+      archiveExpanding: "展开中...",
+      archiveExpanded: "已创建 {created} 个资源，关联 {linked} 个已有资源",
+      archiveSkipped: "跳过 {count} 个条目（类型不支持或超出限制）",
+      archiveProgress: "解析中 {processed}/{total}",
     },
     common: {
       untitled: "无标题",
@@ -258,6 +346,28 @@ export const translations = {
       classificationDesc: "Controls how low-confidence results are handled",
       classificationManual: "Manual Review",
       classificationAggressive: "Aggressive Auto",
+      summaryLanguage: "Summary Language",
+      summaryLanguageDesc: "Language used for resource summaries",
+      summaryLanguageMatchSource: "Match Source",
+      summaryLanguageConfigured: "Interface Language",
+      summaryLanguageBilingual: "Source + Interface",
+      retrieval: "Chat Retrieval",
+      retrievalDesc: "How many chunks each chat retrieves from the vault and how they are ranked; raise the count for synthesis questions",
+      retrievalTopK: "Chunks per Query",
+      retrievalDistance: "Vector Distance",
+      retrievalVectorWeight: "Vector Weight",
+      retrievalVectorWeightDesc: "0 keyword only, 1 semantic only, 0.5 equal",
+      retrievalFusion: "Result Fusion",
+      retrievalFusionDesc: "How text and image results are merged",
+      retrievalRerank: "Rerank",
+      retrievalRerankDesc: "Reorder the top 50 candidates with a local cross-encoder model; more accurate but slower",
+      retrievalFusionMax: "Raw score",
+      retrievalFusionRrf: "Rank fusion (RRF)",
+      retrievalFusionNormalized: "Normalized score",
+      chatMaxInputTokens: "History Limit (tokens)",
+      chatMaxInputTokensDesc: "When the conversation grows past this, earlier messages are condensed into a summary",
+      chatMinRecentMessages: "Recent Messages Kept Verbatim",
+      retrievalSave: "Save",
       localModel: "Local Model (Ollama)",
       enableLocal: "Enable Local Model",
       shortcuts: "Keyboard Shortcuts",
@@ -266,6 +376,37 @@ export const translations = {
       shortcutQuickCapture: "Quick Capture",
       shortcutSearch: "Global Search",
       shortcutSave: "Save (Editor)",
+      captureProfiles: "Capture Profiles",
+      captureProfilesDesc: "Each profile can have its own global shortcut (e.g. Alt+Shift+W) and files captures into a topic, adds tags, or defers AI processing",
+      captureProfilesEmpty: "No capture profiles yet",
+      captureProfileName: "Profile name",
+      captureProfileShortcut: "Shortcut (optional)",
+      captureProfileNoTopic: "No topic",
+      captureProfileTags: "Tags, comma separated",
+      captureProfileDefer: "Defer AI processing",
+      captureProfileAdd: "Add profile",
+      captureProfileSave: "Save",
+      captureProfileCancel: "Cancel",
+      storage: "File Storage",
+      storageDesc: "Captured files are stored here; move them to an external drive if needed",
+      storageDefault: "Default location",
+      storageCustom: "Custom location",
+      storageUnavailable: "Directory unavailable. Connect its drive or choose another directory",
+      storageRelocate: "Move to…",
+      storageRelocating: "Moving {processed}/{total}",
+      storageRelocated: "Moved {count} files",
+      storageLeftBehind: "{count} old files could not be removed",
+      integrity: "File Integrity",
+      integrityDesc: "Re-hash stored files to find missing or modified ones; runs weekly when on AC power",
+      integrityVerify: "Verify Files",
+      integrityVerifying: "Verifying {processed}/{total}",
+      integrityReparse: "Re-parse changed files",
+      integrityLastCheck: "Last check {time}: {checked} files",
+      integrityOk: "No problems found",
+      integrityIssues: "{missing} missing, {mismatched} changed",
+      integrityMissing: "Missing",
+      integrityMismatch: "Changed",
+      integrityReparsed: "Re-parsed",
       configured: "Configured",
       notConfigured: "Not Configured",
       configure: "Configure",
@@ -276,12 +417,21 @@ export const translations = {
       greetingMorning: "Good Morning",
       greetingEvening: "Good Evening",
       quickCapture: "Quickly capture ideas...",
+      storageCopy: "Copy into library",
+      storageReference: "Reference in place",
+      storageReferenceDesc: "Index the file where it is without copying (good for large files); it can't be opened while moved or while its drive is disconnected",
+      ocrPreview: "Text to be extracted (editable)",
+      ocrPreviewEmpty: "No text recognized; you can type it in",
+      ocrPreviewFailed: "Recognition failed; it will be retried in the background after capture",
+      captureProfileNone: "No profile",
       tasks: "Tasks",
       resources: "Inbox Resources",
       createTask: "Create Task",
       noTasks: "No tasks yet. Create one!",
       completedToday: "Completed Today",
       completedTodayTasks: "Completed Today",
+      tagFilter: "Filter by tag",
+      tagFilterClear: "Clear",
     },
     workspace: {
       title: "Workspace",
@@ -304,6 +454,8 @@ export const translations = {
       ragScope: "RAG Scope",
       ragScopeLocal: "Current Node",
       ragScopeGlobal: "Global",
+      ragTopK: "Retrieved Chunks",
+      ragTopKDefault: "Default",
       chatSessions: "Sessions",
       newChatSession: "New Session",
       noChatSessions: "No sessions yet",
@@ -326,6 +478,22 @@ export const translations = {
       resourceBreadcrumb: "Resource",
       editText: "Edit Text",
       viewFile: "View File",
+      revisions: "Version History",
+      revisionsEmpty: "No previous versions",
+      revisionRestore: "Restore",
+      revisionReasonEdit: "Before edit",
+      revisionReasonRestore: "Before restore",
+      revisionChars: "{count} chars",
+      referenceOnline: "Referenced in place: {path}",
+      referenceOffline: "Referenced file unavailable: {path}",
+      referenceOfflineDesc: "The file may have been moved or deleted, or its drive is disconnected. It reconnects automatically, or you can locate the file again.",
+      referenceRelink: "Locate file",
+      tags: "Tags",
+      tagAddPlaceholder: "Add tag...",
+      tagRemove: "Remove tag",
+      tagSuggested: "Suggested",
+      tagSuggestionAccept: "Accept",
+      tagSuggestionReject: "Dismiss",
       attachedText: "Attached Text",
       resourceNotes: "Notes",
       resourceNotesPlaceholder: "Add notes...",
@@ -351,9 +519,17 @@ export const translations = {
       tokenUsageOutput: "Output",
       tokenUsageReasoning: "Reasoning",
       tokenUsageTotal: "Total",
+      chatSources: "Sources",
       pinToContext: "Save to Context",
+      forkChatSession: "Fork a new session from here",
       containedNodes: "Contained Nodes",
       relatedNodes: "Related Nodes",
+      archiveEntries: "{count} files, {supported} can be expanded into resources",
+      archiveExpand: "Expand into Resources",
+      archiveExpanding: "Expanding...",
+      archiveExpanded: "Created {created} resources, linked {linked} existing",
+      archiveSkipped: "Skipped {count} entries (unsupported type or over limits)",
+      archiveProgress: "Parsing {processed}/{total}",
     },
     common: {
       untitled: "Untitled",
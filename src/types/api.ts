@@ -1,4 +1,14 @@
-import type { TaskStatus, TaskPriority, RelationType, NodeRecord } from "./node";
+import type {
+  NodeType,
+  TaskStatus,
+  TaskPriority,
+  RelationType,
+  NodeRecord,
+  ResourceSubtype,
+  EmbeddingStatus,
+  ProcessingStage,
+  ReviewStatus,
+} from "./node";
 
 // ============================================
 // Task API Types
@@ -12,10 +22,27 @@ export interface CreateTaskRequest {
   due_date?: string;
 }
 
+/** recurrence_rule 为类 RRULE 规则，如 FREQ=WEEKLY;BYDAY=MO,WE */
+export interface CreateRecurringTaskRequest {
+  title: string;
+  user_note?: string;
+  priority?: TaskPriority;
+  due_date?: string;
+  recurrence_rule: string;
+}
+
 export interface CreateTaskResponse {
   node: NodeRecord;
 }
 
+export interface TaskReminderRecord {
+  node_id: number;
+  minutes_before: number;
+  snoozed_until: string | null;
+  reminded_due_date: string | null;
+  updated_at: string;
+}
+
 // ============================================
 // Capture API Types
 // ============================================
@@ -27,11 +54,19 @@ export interface CaptureSourceMeta {
   captured_at?: string;
 }
 
+// copy: 复制到资源文件目录（默认）；reference: 原地索引，只记录文件路径
+export type FileStorageMode = "copy" | "reference";
+
 export interface CaptureRequest {
   content?: string;
   file_path?: string;
   file_type?: string;
   source_meta?: CaptureSourceMeta;
+  storage?: FileStorageMode;
+  /** 图片捕获前用户确认 / 修改过的 OCR 文本，替代后台识别 */
+  ocr_text?: string;
+  /** 使用的捕获模板 */
+  profile_id?: number;
 }
 
 export interface CaptureResponse {
@@ -39,10 +74,530 @@ export interface CaptureResponse {
   node_uuid: string;
 }
 
+export interface CaptureBatchRequest {
+  file_paths: string[];
+  user_note?: string;
+  source_meta?: CaptureSourceMeta;
+  storage?: FileStorageMode;
+  profile_id?: number;
+}
+
+export interface CaptureBatchItem {
+  file_path: string;
+  node_id: number | null;
+  node_uuid: string | null;
+  error: string | null;
+}
+
+// 解析与入队在后台进行，进度见 capture-batch-progress 事件
+export interface CaptureBatchResponse {
+  batch_id: string;
+  items: CaptureBatchItem[];
+}
+
+// subtype 为 other 的条目展开时跳过
+export interface ArchiveEntry {
+  path: string;
+  size: number;
+  subtype: ResourceSubtype;
+}
+
+export interface ArchivePreview {
+  entries: ArchiveEntry[];
+  supported_count: number;
+  total_size: number;
+  max_entries: number;
+  max_entry_size: number;
+  max_total_size: number;
+}
+
+// 子资源的解析在后台进行，进度见 archive-expand-progress 事件
+export interface ArchiveExpandSummary {
+  created_node_ids: number[];
+  linked_existing: number;
+  skipped_unsupported: number;
+  skipped_too_large: number;
+  truncated: number;
+}
+
+// ============================================
+// Embedding Status API Types
+// ============================================
+
+export interface EmbeddingStateCount {
+  embedding_status: EmbeddingStatus;
+  processing_stage: ProcessingStage;
+  count: number;
+}
+
+export interface EmbeddingErrorCount {
+  error: string;
+  count: number;
+}
+
+export interface EmbeddingStatusReport {
+  total: number;
+  counts: EmbeddingStateCount[];
+  oldest_pending_at: string | null;
+  oldest_pending_age_secs: number | null;
+  top_errors: EmbeddingErrorCount[];
+  queue_idle: boolean;
+}
+
+export interface RequeueEmbeddingsFilter {
+  error_contains?: string;
+  failed_since?: string;
+  include_pending?: boolean;
+}
+
+/** 重新分类结果：topic_id 为空表示未归入任何主题（原有关联保持不变） */
+export interface ReclassifyOutcome {
+  topic_id: number | null;
+  review_status: ReviewStatus | null;
+  removed_topic_ids: number[];
+}
+
+/** 待审核分类：topic_* 为 AI 建议的主题（置信度最高的自动关联），可能为空 */
+export interface UnreviewedClassification {
+  node_id: number;
+  title: string;
+  summary: string | null;
+  resource_subtype: ResourceSubtype | null;
+  created_at: string | null;
+  topic_id: number | null;
+  topic_title: string | null;
+  confidence_score: number | null;
+}
+
+export interface RejectClassificationResponse {
+  removed_topic_ids: number[];
+  reclassified: ReclassifyOutcome | null;
+}
+
+// ============================================
+// Vector Store API Types
+// ============================================
+
+export interface VectorGroupCount {
+  embedding_type: string;
+  vector_kind: string;
+  embedding_model: string;
+  count: number;
+}
+
+export interface VectorStats {
+  table_name: string;
+  total_rows: number;
+  groups: VectorGroupCount[];
+  disk_bytes: number;
+}
+
+export interface VectorIndexStatus {
+  column: "text_vector" | "image_vector";
+  rows: number;
+  indexed: boolean;
+}
+
+/** 向量库维护结果 */
+export interface VectorMaintenanceReport {
+  orphan_nodes: number;
+  orphan_rows: number;
+  fragments_removed: number;
+  fragments_added: number;
+  old_versions_removed: number;
+  bytes_removed: number;
+}
+
+/** 重建向量使用的模型，向量维度需与模型输出一致 */
+export interface EmbeddingModelSelection {
+  dense_embedding_model: string;
+  dense_vector_size: number;
+  clip_text_embedding_model: string;
+  clip_text_vector_size: number;
+  image_embedding_model: string;
+  image_vector_size: number;
+}
+
+// reindex_embeddings 任务的结果，进度见 embedding-reindex-progress 事件
+export interface EmbeddingReindexReport {
+  table_name: string;
+  resources: number;
+  topics: number;
+  failed_node_ids: number[];
+}
+
+export interface StoredChunk {
+  vector_id: string;
+  embedding_type: string;
+  vector_kind: string;
+  embedding_model: string;
+  chunk_index: number;
+  chunk_text: string;
+  token_count: number | null;
+  embedding_hash: string;
+}
+
+// ============================================
+// Content Revision API Types
+// ============================================
+
+// 列表只含开头预览，恢复时由后端读取全文
+export interface ContentRevisionRecord {
+  revision_id: number;
+  node_id: number;
+  preview: string;
+  content_length: number;
+  content_hash: string | null;
+  reason: "edit" | "restore";
+  created_at: string;
+}
+
+// ============================================
+// File Reference API Types
+// ============================================
+
+export type FileReferenceStatus = "online" | "offline";
+
+export interface FileReferenceRecord {
+  node_id: number;
+  title: string;
+  file_path: string;
+  status: FileReferenceStatus;
+  file_size: number | null;
+  modified_at: number | null;
+  checked_at: string;
+  last_error: string | null;
+}
+
+// file-reference-status 事件
+export interface FileReferenceStatusPayload {
+  node_id: number;
+  file_path: string;
+  status: FileReferenceStatus;
+  reparsed: boolean;
+}
+
+// ============================================
+// Tag API Types
+// ============================================
+
+export interface TagRecord {
+  tag_id: number;
+  name: string;
+  created_at: string | null;
+}
+
+export interface TagUsageRecord extends TagRecord {
+  node_count: number;
+}
+
+export type TagSuggestionStatus = "pending" | "accepted" | "rejected";
+
+export interface TagSuggestionRecord {
+  suggestion_id: number;
+  node_id: number;
+  name: string;
+  status: TagSuggestionStatus;
+  created_at: string | null;
+}
+
+// tag-suggestions 事件
+export interface TagSuggestionsPayload {
+  node_id: number;
+  count: number;
+}
+
+// ============================================
+// Assets Storage API Types
+// ============================================
+
+export interface AssetsStorageStatus {
+  path: string;
+  is_custom: boolean;
+  // 自定义目录所在硬盘未连接时为 false
+  available: boolean;
+}
+
+// OCR 模型、pdfium、whisper.cpp 所在目录
+export interface ModelDirStatus {
+  path: string;
+  source: "custom" | "bundled" | "downloaded" | "development";
+  // 缺失的 OCR 模型文件，非空时可调用 downloadModels
+  missing: string[];
+}
+
+// 进度见 assets-relocate-progress 事件
+export interface AssetsRelocateSummary {
+  status: AssetsStorageStatus;
+  moved_files: number;
+  moved_bytes: number;
+  left_behind: number;
+}
+
+export interface IntegrityCheckRecord {
+  check_id: number;
+  trigger: "manual" | "scheduled";
+  started_at: string;
+  finished_at: string | null;
+  checked_count: number;
+  missing_count: number;
+  mismatched_count: number;
+  // 内容在应用内编辑过、无法与文件比较的资源
+  skipped_count: number;
+  reparsed_count: number;
+  error: string | null;
+}
+
+export interface IntegrityIssueRecord {
+  issue_id: number;
+  check_id: number;
+  node_id: number;
+  title: string;
+  kind: "missing" | "mismatch";
+  file_path: string;
+  expected_hash: string | null;
+  actual_hash: string | null;
+  reparsed: boolean;
+}
+
+// 进度见 integrity-check-progress 事件
+export interface IntegrityReport {
+  check: IntegrityCheckRecord;
+  issues: IntegrityIssueRecord[];
+}
+
+export interface SelfTestCheck {
+  item: "migrations" | "model_dir" | "ocr_model" | "lancedb_writable" | "whisper";
+  status: "pass" | "fail" | "skipped";
+  /** 路径或错误信息 */
+  detail: string | null;
+}
+
+// passed 为 false 表示至少一项失败（skipped 不算失败）
+export interface SelfTestReport {
+  passed: boolean;
+  checks: SelfTestCheck[];
+}
+
+// ============================================
+// Data Migration Types
+// ============================================
+
+// 后台数据迁移进度，实时进度见 data-migration-progress 事件
+export interface DataMigrationRecord {
+  name: string;
+  status: "pending" | "running" | "done" | "failed";
+  cursor: number;
+  processed: number;
+  total: number | null;
+  last_error: string | null;
+  started_at: string | null;
+  finished_at: string | null;
+  updated_at: string | null;
+}
+
+// ============================================
+// Background Job Types
+// ============================================
+
+export type JobKind =
+  | "export"
+  | "vault_export"
+  | "import_enex"
+  | "import_markdown"
+  | "embedding_reindex";
+
+export type JobStatus = "running" | "succeeded" | "failed" | "cancelled";
+
+// vault_export 任务的结果；path 为新建的导出文件夹
+export interface VaultFolderExport {
+  path: string;
+  node_count: number;
+  file_count: number;
+  edge_count: number;
+}
+
+// import_markdown 任务的结果；linked 为由 [[链接]] 建立的引用数（见 get_backlinks）
+export interface MarkdownImportSummary {
+  imported_notes: number;
+  skipped_duplicates: number;
+  failed_files: number;
+  linked: number;
+  unresolved_links: number;
+  node_ids: number[];
+}
+
+// 后台任务状态，实时进度见 job-progress 事件；result 为对应任务的返回值
+export interface JobSnapshot {
+  job_id: number;
+  kind: JobKind;
+  status: JobStatus;
+  processed: number;
+  total: number | null;
+  cancel_requested: boolean;
+  result: unknown;
+  error: string | null;
+  started_at: string;
+  finished_at: string | null;
+}
+
+// ============================================
+// Metrics Types
+// ============================================
+
+export interface CounterSnapshot {
+  name: string;
+  help: string;
+  value: number;
+}
+
+// count 为耗时不超过 le 秒的累计次数
+export interface HistogramBucket {
+  le: number;
+  count: number;
+}
+
+// 分位数按桶上限估算，没有数据或超出最后一个桶时为 null
+export interface HistogramSnapshot {
+  name: string;
+  help: string;
+  count: number;
+  sum_secs: number;
+  buckets: HistogramBucket[];
+  p50_secs: number | null;
+  p95_secs: number | null;
+}
+
+// 启动以来的运行指标，重启后清零
+export interface MetricsSnapshot {
+  counters: CounterSnapshot[];
+  histograms: HistogramSnapshot[];
+}
+
+export interface MetricsEndpointStatus {
+  enabled: boolean;
+  port: number;
+  running: boolean;
+  endpoint: string;
+}
+
+// ============================================
+// Retention Types
+// ============================================
+
+// archive: 不参与语义搜索与对话检索；delete: 移入回收站
+export type RetentionAction = "archive" | "delete";
+
+export interface RetentionRuleInput {
+  name: string;
+  // 只处理该主题（含子主题）下的资源，为空时不限主题
+  topic_id: number | null;
+  resource_subtype: ResourceSubtype | null;
+  max_age_days: number;
+  action: RetentionAction;
+  // 跳过有关联、被引用或挂在任务下的资源
+  skip_linked: boolean;
+  is_enabled?: boolean;
+}
+
+export interface RetentionRuleRecord {
+  rule_id: number;
+  name: string;
+  topic_id: number | null;
+  resource_subtype: ResourceSubtype | null;
+  max_age_days: number;
+  action: RetentionAction;
+  skip_linked: boolean;
+  is_enabled: boolean;
+  last_run_at: string | null;
+  last_affected: number;
+  created_at: string;
+}
+
+export interface RetentionCandidate {
+  node_id: number;
+  title: string;
+  resource_subtype: ResourceSubtype | null;
+  created_at: string | null;
+  archived_at: string | null;
+}
+
+// nodes 只包含最早创建的前 200 个
+export interface RetentionPreview {
+  total: number;
+  nodes: RetentionCandidate[];
+}
+
+export interface RetentionRunResult {
+  rule_id: number;
+  name: string;
+  action: RetentionAction;
+  affected: number;
+}
+
+// ============================================
+// Snapshot Types
+// ============================================
+
+// 只读打开的 archive 导出；last_updated_at 为备份中最近一次修改节点的时间
+export interface SnapshotInfo {
+  session_id: number;
+  archive_path: string;
+  opened_at: string;
+  node_count: number;
+  last_updated_at: string | null;
+}
+
+// 备份内全文检索命中；snippet 中的命中词以 [ ] 标记
+export interface SnapshotSearchHit {
+  node_id: number;
+  node_type: NodeType;
+  title: string;
+  summary: string | null;
+  snippet: string | null;
+  rank: number;
+}
+
+// ============================================
+// Inbox Triage Types
+// ============================================
+
+// 收件箱整理的处理方式；link_topic 会替换资源原有的主题归属
+export type TriageAction =
+  | { action: "link_topic"; topic_id: number }
+  | { action: "convert_to_task" }
+  | { action: "archive" }
+  | { action: "delete" };
+
+export type TriageSuggestion = TriageAction & {
+  node_id: number;
+  title: string;
+  reason: string | null;
+  description: string;
+};
+
+// discarded 为校验丢弃的建议数，failed_batches 为请求失败的批次数
+export interface InboxTriagePlan {
+  scanned: number;
+  suggestions: TriageSuggestion[];
+  discarded: number;
+  failed_batches: number;
+}
+
+export type TriageDecision = TriageAction & { node_id: number };
+
+export interface TriageApplyReport {
+  applied: number;
+  failed: { node_id: number; error: string }[];
+}
+
 // ============================================
 // Node Linking API Types
 // ============================================
 
+/** 节点引用：node_id 或节点 uuid；资源 / 任务 / 聊天命令的节点参数两者皆可 */
+export type NodeRef = number | string;
+
 export interface LinkNodesRequest {
   sourceNodeId: number;
   targetNodeId: number;
@@ -68,8 +623,38 @@ export type ClipboardContent =
   | { type: "Files"; data: { paths: string[] } }
   | { type: "Text"; data: { content: string } }
   | { type: "Html"; data: { content: string; plain_text: string | null } }
+  | { type: "Table"; data: { rows: string[][]; plain_text: string | null } }
+  | { type: "Code"; data: { content: string; language: string | null } }
   | { type: "Empty" };
 
 export interface ReadClipboardResponse {
   content: ClipboardContent;
 }
+
+/** 剪贴板图片的 OCR 预览；error 非空时 text 为空，仍可捕获图片 */
+export interface ClipboardOcrPreview {
+  file_path: string;
+  file_name: string;
+  text: string;
+  error: string | null;
+}
+
+/** 捕获模板：独立快捷键唤起 HUD，捕获时归入主题、打标签或推迟 AI 处理 */
+export interface CaptureProfile {
+  profile_id: number;
+  name: string;
+  shortcut: string | null;
+  topic_id: number | null;
+  tags: string[];
+  defer_processing: boolean;
+}
+
+/** profile_id 为空时新建 */
+export interface SaveCaptureProfileRequest {
+  profile_id?: number;
+  name: string;
+  shortcut?: string;
+  topic_id?: number;
+  tags: string[];
+  defer_processing: boolean;
+}
@@ -34,15 +34,61 @@ export interface AIProviderStatus {
   has_key: boolean;
   enabled: boolean;
   base_url: string | null;
+  /** 每分钟请求上限，null 为不限制 */
+  requests_per_minute: number | null;
 }
 
 export type ClassificationMode = "manual" | "aggressive";
 
+/** 摘要语言：与原文一致 / 界面语言 / 双语 */
+export type SummaryLanguageMode = "match_source" | "configured" | "bilingual";
+
+export type VectorDistance = "cosine" | "l2" | "dot";
+
+/** 文本与图片检索结果的合并方式 */
+export type FusionStrategy = "max" | "rrf" | "normalized";
+
+/** 对话 RAG 检索参数 */
+export interface RetrievalConfig {
+  top_k: number;
+  distance: VectorDistance;
+  /** 混合检索中向量结果的权重（0–1），其余给全文检索 */
+  vector_weight: number;
+  fusion: FusionStrategy;
+  /** 先取更多候选，用 cross-encoder 重排后再截取 top_k */
+  rerank: boolean;
+}
+
+/** 会话级覆盖，未给出的字段沿用全局配置 */
+export type RetrievalOverrides = Partial<RetrievalConfig>;
+
+/** 向量 ANN 索引（IVF_PQ）参数 */
+export interface AnnIndexConfig {
+  /** 向量列行数达到该值后自动建索引（至少 256） */
+  min_rows: number;
+  /** 查询时探查的分区数 */
+  nprobes: number;
+  /** 用原始向量重排的候选倍数，null 表示不重排 */
+  refine_factor: number | null;
+}
+
+/** 对话上下文窗口：历史超过上限时把较早的消息压缩为摘要 */
+export interface ChatContextConfig {
+  /** 发送给模型的历史 token 上限（估算值） */
+  max_input_tokens: number;
+  /** 压缩时至少原样保留的最近消息数 */
+  min_recent_messages: number;
+}
+
 export interface AIConfigStatus {
   providers: Record<string, AIProviderStatus>;
   processing_provider: string | null;
   processing_model: string | null;
   classification_mode: ClassificationMode;
+  summary_language: SummaryLanguageMode;
+  retrieval: RetrievalConfig;
+  ann_index: AnnIndexConfig;
+  chat_context: ChatContextConfig;
 }
 
 export interface SetApiKeyRequest {
@@ -60,6 +106,10 @@ export interface SetClassificationModeRequest {
   mode: ClassificationMode;
 }
 
+export interface SetSummaryLanguageRequest {
+  mode: SummaryLanguageMode;
+}
+
 // ============================================
 // Chat Message Types
 // ============================================
@@ -82,12 +132,27 @@ export interface ChatMessagePayload {
 }
 
 export interface ChatMessage {
+  /** 数据库中的消息 ID，正在发送的消息没有 */
+  messageId?: number;
   role: "user" | "assistant" | "system";
   content: string;
   thinkingSummary?: string;
   timestamp: Date;
   attachments?: { node_id: number }[];
   usage?: ChatUsage;
+  /** 回答引用的 RAG 来源 */
+  sources?: MessageSource[];
+}
+
+/** 注入 prompt 的 RAG 片段，rank 对应回答里的 [n] */
+export interface MessageSource {
+  message_id: number;
+  node_id: number;
+  node_title: string;
+  chunk_index: number;
+  rank: number;
+  score: number;
+  snippet: string;
 }
 
 export type RagScope = "local" | "global";
@@ -101,6 +166,11 @@ export interface SendChatRequest {
   files?: number[];
   thinking_effort?: ThinkingEffort;
   rag_scope?: RagScope;
+  retrieval?: RetrievalOverrides;
+  /** 本条消息的 RAG 排除这些节点 */
+  exclude_node_ids?: number[];
+  /** 本条消息的 RAG 排除这些主题及其包含的节点 */
+  exclude_topic_ids?: number[];
 }
 
 export interface ChatStreamAck {
@@ -123,6 +193,18 @@ export interface ChatSession {
   is_deleted: boolean;
   deleted_at?: string | null;
   user_id: number;
+  rag_settings?: SessionRagSettings | null;
+  /** summary 为历史摘要时，摘要覆盖到的最后一条消息 */
+  compacted_through_message_id?: number | null;
+}
+
+/** 会话级 RAG 检索设置，未设置的字段沿用全局检索配置 */
+export interface SessionRagSettings {
+  top_k?: number | null;
+  /** 低于该分数的片段不放入上下文 */
+  score_threshold?: number | null;
+  /** 检索摘要还是正文分块，默认正文 */
+  embedding_type?: "summary" | "content" | null;
 }
 
 export interface CreateChatSessionRequest {
@@ -154,6 +236,14 @@ export interface DeleteChatSessionRequest {
   session_id: number;
 }
 
+/** 复制到 message_id（含）为止成为新会话 */
+export interface ForkChatSessionRequest {
+  session_id: number;
+  message_id: number;
+  /** 为空时沿用原会话标题 */
+  title?: string;
+}
+
 // ============================================
 // Chat Message CRUD Types
 // ============================================
@@ -35,6 +35,11 @@ export const resourceSubtypeIcons: Record<ResourceSubtype, string> = {
   pdf: "📕",
   url: "🔗",
   epub: "📖",
+  office: "📊",
+  audio: "🎧",
+  notebook: "📓",
+  email: "✉️",
+  archive: "🗜️",
   other: "📎",
 };
 
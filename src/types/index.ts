@@ -11,11 +11,17 @@ export {
   embeddingStatusValues,
   processingStageValues,
   relationTypeValues,
+  graphRelationTypeValues,
   // Schemas
   sourceMetaSchema,
   nodeRecordSchema,
+  taskListItemSchema,
+  taskBoardColumnSchema,
   edgeRecordSchema,
   edgeWithNodeSchema,
+  graphNodeSchema,
+  graphEdgeSchema,
+  graphPageSchema,
   dashboardSchema,
 } from "./node";
 
@@ -28,10 +34,16 @@ export type {
   EmbeddingStatus,
   ProcessingStage,
   RelationType,
+  GraphRelationType,
   SourceMeta,
   NodeRecord,
+  TaskListItem,
+  TaskBoardColumn,
   EdgeRecord,
   EdgeWithNode,
+  GraphNode,
+  GraphEdge,
+  GraphPage,
   DashboardData,
   IngestProgress,
   NodeSearchSummary,
@@ -43,15 +55,83 @@ export type {
 // ============================================
 export type {
   CreateTaskRequest,
+  CreateRecurringTaskRequest,
   CreateTaskResponse,
+  TaskReminderRecord,
   CaptureSourceMeta,
+  FileStorageMode,
   CaptureRequest,
   CaptureResponse,
+  CaptureBatchRequest,
+  CaptureBatchItem,
+  CaptureBatchResponse,
+  ArchiveEntry,
+  ArchivePreview,
+  ArchiveExpandSummary,
+  EmbeddingStateCount,
+  EmbeddingErrorCount,
+  EmbeddingStatusReport,
+  RequeueEmbeddingsFilter,
+  ReclassifyOutcome,
+  UnreviewedClassification,
+  RejectClassificationResponse,
+  VectorGroupCount,
+  VectorStats,
+  VectorIndexStatus,
+  VectorMaintenanceReport,
+  EmbeddingModelSelection,
+  EmbeddingReindexReport,
+  StoredChunk,
+  ContentRevisionRecord,
+  FileReferenceStatus,
+  FileReferenceRecord,
+  FileReferenceStatusPayload,
+  TagRecord,
+  TagUsageRecord,
+  TagSuggestionStatus,
+  TagSuggestionRecord,
+  TagSuggestionsPayload,
+  AssetsStorageStatus,
+  AssetsRelocateSummary,
+  ModelDirStatus,
+  IntegrityCheckRecord,
+  IntegrityIssueRecord,
+  IntegrityReport,
+  SelfTestCheck,
+  SelfTestReport,
+  DataMigrationRecord,
+  JobKind,
+  JobStatus,
+  JobSnapshot,
+  VaultFolderExport,
+  MarkdownImportSummary,
+  CounterSnapshot,
+  HistogramBucket,
+  HistogramSnapshot,
+  MetricsSnapshot,
+  MetricsEndpointStatus,
+  RetentionAction,
+  RetentionRuleInput,
+  RetentionRuleRecord,
+  RetentionCandidate,
+  RetentionPreview,
+  RetentionRunResult,
+  SnapshotInfo,
+  SnapshotSearchHit,
+  TriageAction,
+  TriageSuggestion,
+  InboxTriagePlan,
+  TriageDecision,
+  TriageApplyReport,
+  NodeRef,
   LinkNodesRequest,
   LinkNodesResponse,
   NodeListResponse,
   ClipboardContent,
   ReadClipboardResponse,
+  ClipboardOcrPreview,
+  CaptureProfile,
+  SaveCaptureProfileRequest,
 } from "./api";
 
 // ============================================
@@ -66,6 +146,7 @@ export type {
   AIProvider,
   ThinkingEffort,
   ClassificationMode,
+  SummaryLanguageMode,
   ModelInfo,
   ProviderInfo,
   AIProviderStatus,
@@ -73,6 +154,13 @@ export type {
   SetApiKeyRequest,
   SetProcessingProviderModelRequest,
   SetClassificationModeRequest,
+  SetSummaryLanguageRequest,
+  VectorDistance,
+  FusionStrategy,
+  RetrievalConfig,
+  RetrievalOverrides,
+  AnnIndexConfig,
+  ChatContextConfig,
   ChatUsage,
   ChatMessagePayload,
   ChatMessage,
@@ -80,11 +168,14 @@ export type {
   SendChatRequest,
   ChatStreamAck,
   ChatSession,
+  SessionRagSettings,
+  MessageSource,
   CreateChatSessionRequest,
   CreateChatSessionResponse,
   ListChatSessionsRequest,
   UpdateChatSessionRequest,
   DeleteChatSessionRequest,
+  ForkChatSessionRequest,
   CreateChatMessageRequest,
   CreateChatMessageResponse,
   UpdateChatMessageRequest,
@@ -13,7 +13,7 @@ export type TaskStatus = (typeof taskStatusValues)[number];
 export const taskPriorityValues = ["high", "medium", "low"] as const;
 export type TaskPriority = (typeof taskPriorityValues)[number];
 
-export const resourceSubtypeValues = ["text", "image", "pdf", "url", "epub", "other"] as const;
+export const resourceSubtypeValues = ["text", "image", "pdf", "url", "epub", "office", "audio", "notebook", "email", "archive", "other"] as const;
 export type ResourceSubtype = (typeof resourceSubtypeValues)[number];
 
 export const reviewStatusValues = ["unreviewed", "reviewed", "rejected"] as const;
@@ -25,8 +25,11 @@ export type EmbeddingStatus = (typeof embeddingStatusValues)[number];
 export const processingStageValues = ["todo", "chunking", "embedding", "done"] as const;
 export type ProcessingStage = (typeof processingStageValues)[number];
 
-export const relationTypeValues = ["contains", "related_to"] as const;
+export const relationTypeValues = ["contains", "related_to", "derived_from"] as const;
 export type RelationType = (typeof relationTypeValues)[number];
+/** 图谱中的关系：边的类型加上正文引用 references */
+export const graphRelationTypeValues = [...relationTypeValues, "references"] as const;
+export type GraphRelationType = (typeof graphRelationTypeValues)[number];
 
 // ============================================
 // Zod Schemas
@@ -52,6 +55,9 @@ export const sourceMetaSchema = z.object({
   window_title: z.string().nullable().optional(),
   process_name: z.string().nullable().optional(),
   captured_at: z.string().nullable().optional(),
+  sender: z.string().nullable().optional(),
+  sent_at: z.string().nullable().optional(),
+  auto_title: z.string().nullable().optional(),
 });
 
 export type SourceMeta = z.infer<typeof sourceMetaSchema>;
@@ -86,10 +92,41 @@ export const nodeRecordSchema = z.object({
   updated_at: sqliteDateSchema.nullable(),
   is_deleted: z.boolean(),
   deleted_at: z.string().nullable(),
+  recurrence_rule: z.string().nullable().optional(),
+  exclude_from_search: z.boolean().optional(),
+  read_time_minutes: z.number().nullable().optional(),
+  reading_progress: z.number().nullable().optional(),
+  word_count: z.number().nullable().optional(),
+  char_count: z.number().nullable().optional(),
+  token_count: z.number().nullable().optional(),
+  content_language: z.string().nullable().optional(),
+  processing_attempts: z.number().optional(),
+  /** 标题、摘要、正文、备注每次修改加一，保存时回传以检测并发编辑 */
+  version: z.number().optional(),
+  /** 归档时间；归档的节点不参与语义搜索与对话检索 */
+  archived_at: z.string().nullable().optional(),
+  auto_classify_allowed: z.boolean().optional(),
 });
 
 export type NodeRecord = z.infer<typeof nodeRecordSchema>;
 
+/** get_all_tasks 的列表项：附带父任务与子任务完成情况（已取消的子任务不计入） */
+export const taskListItemSchema = nodeRecordSchema.extend({
+  parent_task_id: z.number().nullable(),
+  subtask_total: z.number(),
+  subtask_done: z.number(),
+});
+
+export type TaskListItem = z.infer<typeof taskListItemSchema>;
+
+/** 看板中的一列：同一状态的任务，按列内位置排序 */
+export const taskBoardColumnSchema = z.object({
+  status: z.enum(taskStatusValues),
+  tasks: z.array(nodeRecordSchema),
+});
+
+export type TaskBoardColumn = z.infer<typeof taskBoardColumnSchema>;
+
 export const edgeRecordSchema = z.object({
   edge_id: z.number(),
   source_node_id: z.number(),
@@ -109,6 +146,42 @@ export const edgeWithNodeSchema = z.object({
 
 export type EdgeWithNode = z.infer<typeof edgeWithNodeSchema>;
 
+/** 图谱节点概要：degree 为全部关系（含正文引用）的边数，depth 为距根节点的步数（全图为 null） */
+export const graphNodeSchema = z.object({
+  node_id: z.number(),
+  node_type: z.enum(nodeTypeValues),
+  title: z.string(),
+  resource_subtype: z.enum(resourceSubtypeValues).nullable(),
+  task_status: z.enum(taskStatusValues).nullable(),
+  degree: z.number(),
+  depth: z.number().nullable(),
+});
+
+export type GraphNode = z.infer<typeof graphNodeSchema>;
+
+/** 图谱中的一条关系：正文引用（references）没有 edge_id */
+export const graphEdgeSchema = z.object({
+  edge_id: z.number().nullable(),
+  source_node_id: z.number(),
+  target_node_id: z.number(),
+  relation_type: z.enum(graphRelationTypeValues),
+  confidence_score: z.number().nullable(),
+  is_manual: z.boolean(),
+  created_at: sqliteDateSchema.nullable(),
+});
+
+export type GraphEdge = z.infer<typeof graphEdgeSchema>;
+
+/** 图谱的一页：edges 连接本页与之前各页的节点，逐页累加即为完整子图 */
+export const graphPageSchema = z.object({
+  nodes: z.array(graphNodeSchema),
+  edges: z.array(graphEdgeSchema),
+  total: z.number(),
+  has_more: z.boolean(),
+});
+
+export type GraphPage = z.infer<typeof graphPageSchema>;
+
 export const dashboardSchema = z.object({
   tasks: z.array(nodeRecordSchema).default([]),
   resources: z.array(nodeRecordSchema).default([]),